@@ -0,0 +1,54 @@
+use anyhow::{Result, anyhow};
+use crate::cluster;
+use crate::slurm::discovery::merge_discovered;
+use crate::slurm::status::SlurmStatusSync;
+use crate::types::response_data::CapabilityDiscovery;
+use crate::{log_info, log_warn};
+use crate::automations::common;
+
+/// Query the cluster's partitions (sinfo) and QoS limits (sacctmgr) and merge them into a
+/// profile's capabilities (None = the active profile, or the shared defaults)
+///
+/// Nothing is saved unless `apply` - without it the merge is a preview the user can accept.
+/// A restricted sacctmgr is reported in `qos_error` rather than failing; partitions still merge.
+pub async fn discover_cluster_capabilities(apply: bool, profile_name: Option<&str>) -> Result<CapabilityDiscovery> {
+    let (_connection_manager, username) = common::require_connection_with_username("Cluster Discovery").await?;
+    let slurm = SlurmStatusSync::new(&username);
+
+    let partitions = slurm.query_partitions().await?;
+    if partitions.is_empty() {
+        return Err(anyhow!("sinfo listed no partitions"));
+    }
+    let (qos_options, qos_error) = match slurm.query_qos_limits().await {
+        Ok(qos_options) => (qos_options, None),
+        Err(e) => {
+            log_warn!(category: "Cluster Discovery", message: "Could not read QoS limits", details: "{}", e);
+            (Vec::new(), Some(e.to_string()))
+        }
+    };
+    log_info!(category: "Cluster Discovery", message: "Discovered cluster capabilities", details: "{} partitions, {} QoS", partitions.len(), qos_options.len());
+
+    let (_, current) = cluster::load_editable_capabilities(profile_name)?;
+    let missing_partitions = current.partitions.iter()
+        .filter(|configured| !partitions.iter().any(|discovered| discovered.name == configured.name))
+        .map(|configured| configured.name.clone())
+        .collect();
+
+    let merged = if apply {
+        cluster::edit_cluster_capabilities(profile_name, |capabilities| {
+            *capabilities = merge_discovered(capabilities, &partitions, &qos_options);
+            Ok(())
+        })?
+    } else {
+        merge_discovered(&current, &partitions, &qos_options)
+    };
+
+    Ok(CapabilityDiscovery {
+        partitions,
+        qos_options,
+        qos_error,
+        missing_partitions,
+        merged,
+        applied: apply,
+    })
+}
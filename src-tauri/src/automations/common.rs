@@ -2,8 +2,15 @@ use anyhow::{Result, anyhow};
 use chrono::Utc;
 use crate::types::{JobInfo, JobStatus};
 use crate::database::with_database;
-use crate::ssh::ConnectionManager;
-use crate::log_error;
+use crate::ssh::{ConnectionManager, RemoteExecutor};
+use crate::{log_debug, log_error, log_warn};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// Last timestamp handed out for an id, so ids created in the same microsecond still differ
+static LAST_ID_MICROS: AtomicI64 = AtomicI64::new(0);
+
+/// Attempts at finding an unused id before giving up
+const UNIQUE_ID_ATTEMPTS: usize = 5;
 
 /// Save job to database with error handling
 /// Clones job internally to satisfy database closure requirements
@@ -17,23 +24,58 @@ pub fn save_job_to_database(job: &JobInfo, context: &str) -> Result<()> {
         })
 }
 
+/// Microsecond timestamp for ids, strictly increasing across calls in this process
+/// Rapid successive calls (or a clock that steps backwards) get last + 1 instead of a repeat
+pub fn next_id_timestamp() -> i64 {
+    let now = Utc::now().timestamp_micros();
+    let previous = LAST_ID_MICROS
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| Some(now.max(last + 1)))
+        .unwrap_or_else(|last| last);
+    now.max(previous + 1)
+}
+
+/// Generate `{prefix}_{timestamp}` that `exists` reports as unused, retrying on collision
+/// The timestamp alone is unique within this process; the check covers records created
+/// elsewhere (another install, an imported job_info.json, a restored database)
+pub fn generate_unique_id(prefix: &str, exists: impl Fn(&str) -> Result<bool>) -> Result<String> {
+    for _ in 0..UNIQUE_ID_ATTEMPTS {
+        let id = format!("{}_{}", prefix, next_id_timestamp());
+        if !exists(&id)? {
+            return Ok(id);
+        }
+        log_warn!(category: "IDs", message: "Generated ID already in use, retrying", details: "{}", id);
+    }
+    Err(anyhow!("Could not generate a unique ID for '{}' after {} attempts", prefix, UNIQUE_ID_ATTEMPTS))
+}
+
+/// Generate a job ID that isn't already in the database
+pub fn generate_job_id(job_name: &str) -> Result<String> {
+    generate_unique_id(job_name, |id| {
+        let id = id.to_string();
+        Ok(with_database(move |db| db.load_job(&id))?.is_some())
+    })
+}
+
 /// Require connection and get username in one call
 /// Common pattern in automation workflows
 pub async fn require_connection_with_username(context: &str) -> Result<(&'static ConnectionManager, String)> {
     let connection_manager = crate::ssh::get_connection_manager();
+    let username = require_username(connection_manager, context).await?;
+    Ok((connection_manager, username))
+}
 
-    if !connection_manager.is_connected().await {
+/// Require an active connection on `remote` and get its username
+pub async fn require_username(remote: &dyn RemoteExecutor, context: &str) -> Result<String> {
+    if !remote.is_connected().await {
         log_error!(category: context, message: "SSH connection not active");
         return Err(anyhow!("Not connected to cluster"));
     }
 
-    let username = connection_manager.get_username().await
+    remote.get_username().await
         .map_err(|e| {
             log_error!(category: context, message: "Failed to get username", details: "{}", e);
             anyhow!("Failed to get cluster username: {}", e)
-        })?;
-
-    Ok((connection_manager, username))
+        })
 }
 
 /// Get required project directory from job
@@ -75,6 +117,24 @@ pub fn touch_job_timestamp(job: &mut JobInfo) {
     job.updated_at = Some(Utc::now().to_rfc3339());
 }
 
+/// Carry fields the user edits locally (notes, tags, pin) over from the stored record
+/// Long-running automations work on a copy loaded earlier; saving that copy as-is would
+/// silently undo an edit made in the meantime
+pub fn refresh_user_edits(job: &mut JobInfo) {
+    let job_id = job.job_id.clone();
+    match with_database(move |db| db.load_job(&job_id)) {
+        Ok(Some(stored)) => copy_user_edits(&stored, job),
+        Ok(None) => {}
+        Err(e) => log_debug!(category: "Jobs", message: "Could not reload job before saving", details: "{}: {}", job.job_id, e),
+    }
+}
+
+fn copy_user_edits(from: &JobInfo, to: &mut JobInfo) {
+    to.notes = from.notes.clone();
+    to.tags = from.tags.clone();
+    to.pinned = from.pinned;
+}
+
 /// Ensure path has trailing slash for rsync operations
 /// rsync requires trailing slash on source directory
 pub fn ensure_trailing_slash(path: &str) -> String {
@@ -124,9 +184,27 @@ mod tests {
                 walltime: "01:00:00".to_string(),
                 partition: "amilan".to_string(),
                 qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
             },
             input_files: vec![],
             output_files: vec![],
+            archived: false,
+            pinned: false,
+            tags: Vec::new(),
+            notes: None,
+            status_detail: None,
+            submitted_script: None,
+            template_snapshot: None,
+            rendered_config: None,
+            depends_on: None,
+            array: None,
+            notify_email: None,
+            notify_events: Vec::new(),
+            actual_cost: None,
+            namd_progress: None,
+            estimated_start: None,
+            queue_priority: None,
         };
 
         update_job_status(&mut job, JobStatus::Running);
@@ -163,9 +241,27 @@ mod tests {
                 walltime: "04:00:00".to_string(),
                 partition: "amilan".to_string(),
                 qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
             },
             input_files: vec![],
             output_files: vec![],
+            archived: false,
+            pinned: false,
+            tags: Vec::new(),
+            notes: None,
+            status_detail: None,
+            submitted_script: None,
+            template_snapshot: None,
+            rendered_config: None,
+            depends_on: None,
+            array: None,
+            notify_email: None,
+            notify_events: Vec::new(),
+            actual_cost: None,
+            namd_progress: None,
+            estimated_start: None,
+            queue_priority: None,
         };
 
         // Record original state
@@ -205,9 +301,27 @@ mod tests {
                 walltime: "04:00:00".to_string(),
                 partition: "amilan".to_string(),
                 qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
             },
             input_files: vec![],
             output_files: vec![],
+            archived: false,
+            pinned: false,
+            tags: Vec::new(),
+            notes: None,
+            status_detail: None,
+            submitted_script: None,
+            template_snapshot: None,
+            rendered_config: None,
+            depends_on: None,
+            array: None,
+            notify_email: None,
+            notify_events: Vec::new(),
+            actual_cost: None,
+            namd_progress: None,
+            estimated_start: None,
+            queue_priority: None,
         };
 
         // Record original state
@@ -220,4 +334,67 @@ mod tests {
         assert_eq!(job.completed_at, old_completed_at, "completed_at should not be overwritten");
         assert_eq!(job.status, JobStatus::Completed, "status should remain Completed");
     }
+
+    #[test]
+    fn test_copy_user_edits_keeps_sync_changes() {
+        let job = |status: JobStatus| {
+            let mut job = crate::automations::job_creation::create_job_info(
+                "job_notes".to_string(), "notes".to_string(), "template".to_string(),
+                std::collections::HashMap::new(), crate::types::SlurmConfig::default(), vec![],
+            );
+            job.status = status;
+            job
+        };
+        // Edited by the user while sync was working on its copy
+        let mut stored = job(JobStatus::Running);
+        stored.notes = Some("tighter restraints".to_string());
+        stored.tags = vec!["membrane".to_string()];
+        stored.pinned = true;
+
+        let mut synced = job(JobStatus::Completed);
+        synced.status_detail = Some("done".to_string());
+        copy_user_edits(&stored, &mut synced);
+
+        assert_eq!(synced.notes.as_deref(), Some("tighter restraints"));
+        assert_eq!(synced.tags, vec!["membrane"]);
+        assert!(synced.pinned);
+        assert_eq!(synced.status, JobStatus::Completed);
+        assert_eq!(synced.status_detail.as_deref(), Some("done"));
+    }
+
+    #[test]
+    fn test_rapid_ids_are_unique_and_increasing() {
+        let ids: Vec<i64> = (0..10_000).map(|_| next_id_timestamp()).collect();
+        assert!(ids.windows(2).all(|pair| pair[1] > pair[0]));
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| std::thread::spawn(|| (0..1000).map(|_| next_id_timestamp()).collect::<Vec<_>>()))
+            .collect();
+        let all: std::collections::HashSet<i64> = threads.into_iter().flat_map(|t| t.join().unwrap()).collect();
+        assert_eq!(all.len(), 8000);
+    }
+
+    #[test]
+    fn test_generate_unique_id_rapid_creations() {
+        let taken = std::cell::RefCell::new(std::collections::HashSet::new());
+        for _ in 0..1000 {
+            let id = generate_unique_id("equilibration", |id| Ok(taken.borrow().contains(id))).unwrap();
+            assert!(id.starts_with("equilibration_"));
+            assert!(taken.borrow_mut().insert(id));
+        }
+    }
+
+    #[test]
+    fn test_generate_unique_id_retries_on_collision() {
+        let checks = std::cell::Cell::new(0);
+        let id = generate_unique_id("job", |_| {
+            checks.set(checks.get() + 1);
+            Ok(checks.get() == 1)  // First candidate "already exists"
+        }).unwrap();
+        assert_eq!(checks.get(), 2);
+        assert!(id.starts_with("job_"));
+
+        assert!(generate_unique_id("job", |_| Ok(true)).is_err());
+        assert!(generate_unique_id("job", |_| Err(anyhow!("db locked"))).is_err());
+    }
 }
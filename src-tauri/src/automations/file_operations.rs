@@ -4,14 +4,59 @@
 
 use anyhow::{Result, anyhow};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use crate::types::FileUpload;
-use crate::types::response_data::DownloadInfo;
-use crate::ssh::ConnectionManager;
+use crate::types::{FileUpload, OutputDownloadProgress};
+use crate::types::response_data::{DiskSpaceCheck, DownloadInfo, FileUsage, FilteredDownload, InodeCheck, RemoteDirSize, RemoteText};
+use crate::ssh::{ArchiveEntry, ConnectionManager, SftpFileEntry, SSHError};
+use crate::ssh::cancellation;
 use crate::security::input;
-use crate::{log_info, log_debug};
+use crate::{log_info, log_debug, log_warn};
 use crate::automations::common;
 use crate::commands::helpers;
+use crate::database::with_database;
+use crate::ssh::zip_stream::{DEFAULT_COMPRESSION_LEVEL, MAX_COMPRESSION_LEVEL};
+
+/// Settings key for the deflate level of "download all" archives (0 = store only)
+/// Archives are built locally from SFTP reads, so this only changes the saved file's size;
+/// the files cross the link uncompressed at any level
+pub const ARCHIVE_COMPRESSION_SETTING: &str = "archive_compression_level";
+
+/// Load the archive compression level, falling back to zip's default
+pub fn load_archive_compression_level() -> u8 {
+    with_database(|db| db.load_setting::<u8>(ARCHIVE_COMPRESSION_SETTING))
+        .ok()
+        .flatten()
+        .unwrap_or(DEFAULT_COMPRESSION_LEVEL)
+}
+
+/// Persist the archive compression level (0-9)
+pub fn save_archive_compression_level(level: u8) -> Result<()> {
+    if level > MAX_COMPRESSION_LEVEL {
+        return Err(anyhow!("Compression level must be between 0 and {}", MAX_COMPRESSION_LEVEL));
+    }
+
+    with_database(|db| db.save_setting(ARCHIVE_COMPRESSION_SETTING, &level))
+}
+
+/// Settings key for the upload rate cap in bytes per second (absent = unlimited)
+pub const TRANSFER_RATE_LIMIT_SETTING: &str = "transfer_rate_limit";
+
+/// Load the saved upload rate cap, None when unlimited
+pub fn load_transfer_rate_limit() -> Option<u64> {
+    with_database(|db| db.load_setting::<u64>(TRANSFER_RATE_LIMIT_SETTING))
+        .ok()
+        .flatten()
+        .filter(|limit| *limit > 0)
+}
+
+/// Persist the upload rate cap and apply it to the connection manager (None or 0 = unlimited)
+pub fn save_transfer_rate_limit(max_bytes_per_sec: Option<u64>) -> Result<Option<u64>> {
+    let limit = max_bytes_per_sec.filter(|limit| *limit > 0);
+    with_database(|db| db.save_setting(TRANSFER_RATE_LIMIT_SETTING, &limit.unwrap_or(0)))?;
+    crate::ssh::get_connection_manager().set_upload_rate_limit(limit);
+    Ok(limit)
+}
 
 /// Validate a file for upload
 /// Checks: file exists, readable, size limits, safe filename
@@ -48,6 +93,12 @@ pub fn validate_upload_file(file: &FileUpload) -> Result<()> {
     Ok(())
 }
 
+/// Key a job download is cancellable under (`cancel_transfer`): "{job_id}:{file_path}" for a
+/// single file, "{job_id}:inputs" / "{job_id}:outputs" for a zip archive
+pub fn download_transfer_key(job_id: &str, name: &str) -> String {
+    format!("{}:{}", job_id, name)
+}
+
 /// Download a single file from a job
 /// Returns download info (path and size)
 pub async fn download_job_file(
@@ -74,9 +125,10 @@ pub async fn download_job_file(
         return Err(anyhow!("File '{}' not found", file_path));
     }
 
-    // Download file
+    // Download file - the destination was just chosen by the user, so any existing file there is replaced
     log_debug!(category: "File Download", message: "Downloading from remote", details: "{} -> {}", remote_path, local_destination);
-    let progress = connection_manager.download_file(&remote_path, local_destination).await
+    let registration = cancellation::register(&download_transfer_key(job_id, file_path), None);
+    let progress = connection_manager.download_file(&remote_path, local_destination, false, Some(registration.token()), false).await
         .map_err(|e| anyhow!("Download failed: {}", e))?;
 
     Ok(DownloadInfo {
@@ -86,51 +138,316 @@ pub async fn download_job_file(
 }
 
 /// Download all files of a specific type as a zip archive
-/// file_type: "inputs" or "outputs"
+/// file_type: "inputs" or "outputs". Each remote file is streamed straight into the archive
+/// at `local_destination` under its path in the job directory (e.g. "outputs/sim.dcd"), so
+/// neither the cluster nor local memory ever holds the whole archive
 pub async fn download_files_zip(
     job_id: &str,
     file_type: &str, // "inputs" or "outputs"
     local_destination: &str,
+    progress_callback: impl Fn(&OutputDownloadProgress) + Sync,
 ) -> Result<DownloadInfo> {
     log_info!(category: "File Operations", message: "Creating zip archive", details: "{}: {}", job_id, file_type);
 
+    let subdir = match file_type {
+        "inputs" => crate::ssh::JobDirectoryStructure::INPUT_FILES,
+        "outputs" => crate::ssh::JobDirectoryStructure::OUTPUTS,
+        _ => return Err(anyhow!("Invalid file type: {}", file_type)),
+    };
+
     // Load job and validate connection
     let job_info = helpers::load_job_or_fail(job_id, "File Download")?;
     let (connection_manager, _username) = common::require_connection_with_username("File Download").await?;
     let project_dir = common::require_project_dir(&job_info, "File Download")?;
 
-    // Generate zip command based on file type
-    let (zip_command, temp_zip_path) = match file_type {
-        "inputs" => crate::ssh::commands::zip_inputs_command(project_dir, job_id)?,
-        "outputs" => crate::ssh::commands::zip_outputs_command(project_dir, job_id)?,
-        _ => return Err(anyhow!("Invalid file type: {}", file_type)),
+    let remote_dir = format!("{}/{}", project_dir, subdir);
+    let entries: Vec<ArchiveEntry> = list_remote_entries_recursive(connection_manager, &remote_dir).await
+        .map_err(|e| anyhow!("Failed to list {}: {}", file_type, e))?
+        .into_iter()
+        .map(|entry| ArchiveEntry {
+            archive_name: format!("{}/{}", subdir, relative_to(&remote_dir, &entry.path)),
+            remote_path: entry.path,
+            size: entry.size,
+            modified_time: entry.modified_time,
+        })
+        .collect();
+    if entries.is_empty() {
+        return Err(anyhow!("Job has no {} files to download", file_type.trim_end_matches('s')));
+    }
+
+    // One event per percent of the total and per finished file
+    let bytes_total: u64 = entries.iter().map(|entry| entry.size).sum();
+    let last_reported = AtomicU64::new(u64::MAX);
+    let report = |completed: usize, bytes_done: u64| {
+        let percent = (bytes_done * 100).checked_div(bytes_total).unwrap_or(100);
+        let key = completed as u64 * 1000 + percent;
+        if last_reported.swap(key, Ordering::Relaxed) != key {
+            progress_callback(&OutputDownloadProgress {
+                job_id: job_id.to_string(),
+                file: entries[completed.min(entries.len() - 1)].archive_name.clone(),
+                completed,
+                total: entries.len(),
+                bytes_done,
+                bytes_total,
+            });
+        }
     };
 
-    // Execute zip command on server
-    log_debug!(category: "File Download", message: "Creating zip on server", details: "{}", zip_command);
-    let result = connection_manager.execute_command(&zip_command, None).await?;
-    if result.exit_code != 0 {
-        return Err(anyhow!("Failed to create zip file: {}", result.stderr));
+    log_debug!(category: "File Download", message: "Streaming files into zip", details: "{} files, {} bytes -> {}", entries.len(), bytes_total, local_destination);
+    let registration = cancellation::register(&download_transfer_key(job_id, file_type), None);
+    connection_manager.download_as_zip(&entries, local_destination, load_archive_compression_level(), Some(registration.token()), &report).await
+        .map_err(|e| anyhow!("Download failed: {}", e))?;
+
+    let file_size = std::fs::metadata(local_destination)?.len();
+    log_info!(category: "File Operations", message: "Zip download completed", details: "{} files, {} bytes", entries.len(), file_size);
+
+    Ok(DownloadInfo {
+        saved_to: local_destination.to_string(),
+        file_size,
+    })
+}
+
+/// Most patterns one filtered output download accepts
+const MAX_OUTPUT_PATTERNS: usize = 20;
+
+/// Check and normalize a filtered-download pattern
+/// Only `*` (any run of characters within a path component) and `?` (one character) are special;
+/// a bare extension such as ".dcd" is shorthand for "*.dcd"
+pub fn parse_output_pattern(pattern: &str) -> Result<String> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err(anyhow!("Pattern cannot be empty"));
+    }
+    if pattern.len() > 255 {
+        return Err(anyhow!("Pattern is too long: {}", pattern));
+    }
+    if pattern.starts_with('/') || pattern.contains("..") {
+        return Err(anyhow!("Pattern must be relative to the outputs directory: {}", pattern));
+    }
+    if let Some(c) = pattern.chars().find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '+' | '/' | '*' | '?'))) {
+        return Err(anyhow!("Pattern '{}' contains '{}' - only letters, digits, . _ - + / and the wildcards * ? are allowed", pattern, c));
     }
 
-    // Download the zip file
-    log_debug!(category: "File Download", message: "Downloading zip file", details: "{} -> {}", temp_zip_path, local_destination);
-    let download_result = connection_manager.download_file(&temp_zip_path, local_destination).await;
+    if pattern.starts_with('.') && !pattern.contains(['*', '?', '/']) {
+        return Ok(format!("*{}", pattern));
+    }
+    Ok(pattern.to_string())
+}
 
-    // Clean up temporary zip file (best effort, don't fail if cleanup fails)
-    cleanup_temp_file(connection_manager, &temp_zip_path).await;
+/// Whether an output file (path relative to outputs/) matches a parsed pattern
+/// Patterns without a '/' match the file name in any directory; patterns with one match the whole path
+pub fn output_pattern_matches(pattern: &str, path: &str) -> bool {
+    let target = if pattern.contains('/') {
+        path
+    } else {
+        path.rsplit('/').next().unwrap_or(path)
+    };
+    glob_matches(pattern.as_bytes(), target.as_bytes())
+}
 
-    // Return download result
-    let progress = download_result.map_err(|e| anyhow!("Download failed: {}", e))?;
+/// Wildcard match where `*` never crosses a '/'
+fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => {
+            // Try every split point up to the end of the current path component
+            let component_end = text.iter().position(|&c| c == b'/').unwrap_or(text.len());
+            (0..=component_end).any(|skip| glob_matches(rest, &text[skip..]))
+        }
+        Some((b'?', rest)) => matches!(text.split_first(), Some((&c, tail)) if c != b'/' && glob_matches(rest, tail)),
+        Some((&p, rest)) => matches!(text.split_first(), Some((&c, tail)) if c == p && glob_matches(rest, tail)),
+    }
+}
 
-    log_info!(category: "File Operations", message: "Zip download completed", details: "{} bytes", progress.total_bytes);
+/// List every file under a remote directory, descending into subdirectories
+/// Paths are relative to `dir`
+pub async fn list_remote_files_recursive(connection_manager: &ConnectionManager, dir: &str) -> Result<Vec<FileUsage>> {
+    Ok(list_remote_entries_recursive(connection_manager, dir).await?
+        .into_iter()
+        .map(|entry| FileUsage { path: relative_to(dir, &entry.path), size_bytes: entry.size })
+        .collect())
+}
 
-    Ok(DownloadInfo {
-        saved_to: local_destination.to_string(),
-        file_size: progress.total_bytes,
+/// Every file (not directory) under a remote directory with full paths, sorted by path
+async fn list_remote_entries_recursive(connection_manager: &ConnectionManager, dir: &str) -> Result<Vec<SftpFileEntry>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_string()];
+    while let Some(current) = pending.pop() {
+        for entry in connection_manager.list_files(&current, true).await? {
+            if entry.is_directory {
+                pending.push(entry.path);
+            } else {
+                files.push(entry);
+            }
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Path of a listed file relative to the directory it was listed under
+fn relative_to(dir: &str, path: &str) -> String {
+    path.strip_prefix(dir)
+        .map(|rest| rest.trim_start_matches('/'))
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Download the job outputs matching any of `patterns` into `local_dir`, keeping their
+/// layout under outputs/ (array task files land in task_N/). Each finished file is reported
+/// through `progress_callback`; a failed file stops the download and earlier files stay
+pub async fn download_outputs_matching(
+    job_id: &str,
+    patterns: &[String],
+    local_dir: &str,
+    progress_callback: impl Fn(&OutputDownloadProgress),
+) -> Result<FilteredDownload> {
+    if patterns.is_empty() {
+        return Err(anyhow!("At least one pattern is required"));
+    }
+    if patterns.len() > MAX_OUTPUT_PATTERNS {
+        return Err(anyhow!("At most {} patterns are allowed", MAX_OUTPUT_PATTERNS));
+    }
+    let patterns = patterns.iter().map(|pattern| parse_output_pattern(pattern)).collect::<Result<Vec<_>>>()?;
+    log_info!(category: "File Operations", message: "Downloading matching outputs", details: "{}: {}", job_id, patterns.join(" "));
+
+    let job_info = helpers::load_job_or_fail(job_id, "File Download")?;
+    let (connection_manager, _username) = common::require_connection_with_username("File Download").await?;
+    let project_dir = common::require_project_dir(&job_info, "File Download")?;
+    let outputs_dir = format!("{}/{}", project_dir, crate::ssh::JobDirectoryStructure::OUTPUTS);
+
+    let (matched, skipped): (Vec<FileUsage>, Vec<FileUsage>) = list_remote_files_recursive(connection_manager, &outputs_dir).await
+        .map_err(|e| anyhow!("Failed to list outputs: {}", e))?
+        .into_iter()
+        .partition(|file| patterns.iter().any(|pattern| output_pattern_matches(pattern, &file.path)));
+    log_debug!(category: "File Download", message: "Filtered outputs", details: "{} matched, {} skipped", matched.len(), skipped.len());
+
+    std::fs::create_dir_all(local_dir)
+        .map_err(|e| anyhow!("Could not create download directory '{}': {}", local_dir, e))?;
+
+    let bytes_total: u64 = matched.iter().map(|file| file.size_bytes).sum();
+    let mut bytes_done = 0;
+    for (index, file) in matched.iter().enumerate() {
+        input::validate_relative_file_path(&file.path)
+            .map_err(|e| anyhow!("Refusing to download '{}': {}", file.path, e))?;
+        let local_path = Path::new(local_dir).join(&file.path);
+        if let Some(parent) = local_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| anyhow!("Could not create '{}': {}", parent.display(), e))?;
+        }
+
+        let remote_path = format!("{}/{}", outputs_dir, file.path);
+        let transfer_key = download_transfer_key(job_id, &crate::ssh::JobDirectoryStructure::output_path(&file.path));
+        let registration = cancellation::register(&transfer_key, None);
+        let progress = connection_manager.download_file(&remote_path, &local_path.to_string_lossy(), false, Some(registration.token()), false).await
+            .map_err(|e| anyhow!("Download of '{}' failed: {}", file.path, e))?;
+
+        bytes_done += progress.total_bytes;
+        progress_callback(&OutputDownloadProgress {
+            job_id: job_id.to_string(),
+            file: file.path.clone(),
+            completed: index + 1,
+            total: matched.len(),
+            bytes_done,
+            bytes_total,
+        });
+    }
+
+    log_info!(category: "File Operations", message: "Matching outputs downloaded", details: "{} files, {} bytes to {}", matched.len(), bytes_done, local_dir);
+
+    Ok(FilteredDownload {
+        saved_to: local_dir.to_string(),
+        patterns,
+        matched,
+        skipped,
+        downloaded_bytes: bytes_done,
+    })
+}
+
+/// Bytes of a remote file `read_remote_text` returns when no limit is given
+pub const DEFAULT_TEXT_VIEW_BYTES: u64 = 1024 * 1024;
+
+/// Most bytes of a remote file `read_remote_text` returns
+pub const MAX_TEXT_VIEW_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Extensions of binary formats jobs commonly produce (trajectories, binary restarts, archives),
+/// refused before anything is read
+const BINARY_EXTENSIONS: &[&str] = &["dcd", "coor", "vel", "xtc", "trr", "nc", "h5", "gz", "bz2", "xz", "zip", "tar", "tgz"];
+
+/// Characters sniffed at the start of a file to tell text from binary
+const TEXT_SNIFF_CHARS: usize = 8192;
+
+/// Whether a file name has one of the known binary extensions (case-insensitive)
+pub fn has_binary_extension(path: &str) -> bool {
+    Path::new(path).extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| BINARY_EXTENSIONS.iter().any(|binary| binary.eq_ignore_ascii_case(ext)))
+}
+
+/// Content sniff: a NUL, or more than 10% control or undecodable characters, near the start
+pub fn looks_binary(content: &str) -> bool {
+    let mut examined = 0;
+    let mut suspicious = 0;
+    for c in content.chars().take(TEXT_SNIFF_CHARS) {
+        if c == '\0' {
+            return true;
+        }
+        if c == char::REPLACEMENT_CHARACTER || (c.is_control() && !matches!(c, '\n' | '\r' | '\t' | '\x0c')) {
+            suspicious += 1;
+        }
+        examined += 1;
+    }
+    suspicious * 10 > examined
+}
+
+/// Read the start of a text file in a job's project directory for display
+/// `max_bytes` defaults to 1 MB and is capped at 16 MB. Binary files are refused, by
+/// extension first and then by sniffing what was read, so trajectories never reach the UI
+pub async fn read_remote_text(job_id: &str, relative_path: &str, max_bytes: Option<u64>) -> Result<RemoteText> {
+    input::validate_relative_file_path(relative_path)
+        .map_err(|e| anyhow!("Invalid file path: {}", e))?;
+    if has_binary_extension(relative_path) {
+        return Err(anyhow!("'{}' is a binary file - download it instead", relative_path));
+    }
+
+    let job_info = helpers::load_job_or_fail(job_id, "File View")?;
+    let (connection_manager, _username) = common::require_connection_with_username("File View").await?;
+    let project_dir = common::require_project_dir(&job_info, "File View")?;
+    let remote_path = format!("{}/{}", project_dir, relative_path);
+
+    let max_bytes = max_bytes.unwrap_or(DEFAULT_TEXT_VIEW_BYTES).clamp(1, MAX_TEXT_VIEW_BYTES);
+    log_debug!(category: "File View", message: "Reading remote text", details: "{} (up to {} bytes)", remote_path, max_bytes);
+    let head = connection_manager.read_remote_head(&remote_path, max_bytes).await
+        .map_err(|e| anyhow!("Could not read '{}': {}", relative_path, e))?;
+
+    if looks_binary(&head.content) {
+        return Err(anyhow!("'{}' looks like a binary file - download it instead", relative_path));
+    }
+
+    Ok(RemoteText {
+        relative_path: relative_path.to_string(),
+        content: head.content,
+        shown_bytes: head.offset,
+        file_size: head.file_size,
+        truncated: head.has_more,
     })
 }
 
+/// Rename or move a file within the user's job directories (project or scratch)
+/// Both paths are absolute and must stay inside a job directory; an existing destination is never overwritten
+pub async fn rename_remote_file(old_path: &str, new_path: &str) -> Result<()> {
+    let (connection_manager, username) = common::require_connection_with_username("File Rename").await?;
+    crate::ssh::paths::confine_to_job_files(old_path, &username)
+        .map_err(|e| anyhow!("Invalid source path: {}", e))?;
+    crate::ssh::paths::confine_to_job_files(new_path, &username)
+        .map_err(|e| anyhow!("Invalid destination path: {}", e))?;
+
+    connection_manager.rename_remote_file(old_path, new_path).await?;
+    log_info!(category: "File Rename", message: "Renamed remote file", details: "{} -> {}", old_path, new_path);
+    Ok(())
+}
+
 /// Clean up a temporary file on the server
 /// Best-effort operation - logs errors but doesn't fail
 pub async fn cleanup_temp_file(connection_manager: &ConnectionManager, file_path: &str) {
@@ -150,10 +467,193 @@ pub async fn cleanup_temp_file(connection_manager: &ConnectionManager, file_path
     }
 }
 
+/// Decide whether `planned_files` new files fit in the free inodes of the target filesystem
+/// An unknown inode count never blocks an upload - the check is simply skipped
+pub fn evaluate_inode_capacity(remote_dir: &str, planned_files: u64, free_inodes: Option<u64>) -> InodeCheck {
+    let (sufficient, message) = match free_inodes {
+        None => (true, "Inode check skipped - filesystem does not report inode counts".to_string()),
+        Some(free) if planned_files > free => (false, format!(
+            "Uploading {} files would exceed the {} free inodes on the target filesystem - the upload would fail partway even with free space",
+            planned_files, free
+        )),
+        Some(free) => (true, format!("{} files fit in {} free inodes", planned_files, free)),
+    };
+
+    InodeCheck {
+        remote_dir: remote_dir.to_string(),
+        planned_files,
+        free_inodes,
+        sufficient,
+        message,
+    }
+}
+
+/// Check the free inodes under `remote_dir` before uploading many files
+/// Skips (reports sufficient) when `df -i` fails or the filesystem has no inode accounting
+pub async fn check_remote_inodes(remote_dir: &str, planned_files: u64) -> Result<InodeCheck> {
+    let (connection_manager, _username) = common::require_connection_with_username("Inode Check").await?;
+
+    let command = crate::ssh::commands::inode_usage_command(remote_dir);
+    let free_inodes = match connection_manager.execute_command(&command, Some(crate::cluster::timeouts::DEFAULT_COMMAND)).await {
+        Ok(result) if result.exit_code == 0 => crate::ssh::commands::parse_free_inodes(&result.stdout),
+        Ok(result) => {
+            log_debug!(category: "Inode Check", message: "df -i unavailable, skipping inode check", details: "{}", result.stderr.trim());
+            None
+        }
+        Err(e) => {
+            log_debug!(category: "Inode Check", message: "df -i failed, skipping inode check", details: "{}", e);
+            None
+        }
+    };
+
+    let check = evaluate_inode_capacity(remote_dir, planned_files, free_inodes);
+    if !check.sufficient {
+        log_warn!(category: "Inode Check", message: "Not enough free inodes for upload", details: "{}: {}", remote_dir, check.message);
+    }
+    Ok(check)
+}
+
+/// Byte count for messages, in the largest 1024-based unit below it
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = "bytes";
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    if unit == "bytes" { format!("{} bytes", bytes) } else { format!("{:.1} {}", value, unit) }
+}
+
+/// Decide whether `required_bytes` fit in the free space and quota of the target filesystem
+/// `space` is `parse_disk_space`'s (total, available); unknown values never block a write
+pub fn evaluate_disk_space(path: &str, required_bytes: u64, space: Option<(u64, u64)>, quota_remaining_bytes: Option<u64>) -> DiskSpaceCheck {
+    let available_bytes = space.map(|(_, available)| available);
+    let limit = match (available_bytes, quota_remaining_bytes) {
+        (Some(free), Some(quota)) if quota < free => Some((quota, "left under your quota")),
+        (Some(free), _) => Some((free, "free")),
+        (None, Some(quota)) => Some((quota, "left under your quota")),
+        (None, None) => None,
+    };
+
+    let shortfall_bytes = limit.map_or(0, |(room, _)| required_bytes.saturating_sub(room));
+    let message = match limit {
+        None => "Disk space check skipped - filesystem does not report free space".to_string(),
+        Some((room, kind)) if shortfall_bytes > 0 => format!(
+            "{} needs {} but only {} is {} (short by {})",
+            path, format_bytes(required_bytes), format_bytes(room), kind, format_bytes(shortfall_bytes)
+        ),
+        Some((room, kind)) => format!("{} fits in the {} {}", format_bytes(required_bytes), format_bytes(room), kind),
+    };
+
+    DiskSpaceCheck {
+        path: path.to_string(),
+        required_bytes,
+        total_bytes: space.map(|(total, _)| total),
+        available_bytes,
+        quota_remaining_bytes,
+        sufficient: shortfall_bytes == 0,
+        shortfall_bytes,
+        message,
+    }
+}
+
+/// Check free space and Lustre quota under `path` before writing `required_bytes` there
+/// `path` may not exist yet; its nearest existing parent is measured. A failing `df` or
+/// `lfs quota` (not installed, not Lustre) skips that half of the check
+pub async fn check_disk_space(path: &str, required_bytes: u64) -> Result<DiskSpaceCheck> {
+    let (connection_manager, username) = common::require_connection_with_username("Disk Space Check").await?;
+
+    let command = crate::ssh::commands::disk_space_command(path);
+    let space = match connection_manager.execute_command(&command, Some(crate::cluster::timeouts::DEFAULT_COMMAND)).await {
+        Ok(result) if result.exit_code == 0 => crate::ssh::commands::parse_disk_space(&result.stdout),
+        Ok(result) => {
+            log_debug!(category: "Disk Space Check", message: "df unavailable, skipping free space check", details: "{}", result.stderr.trim());
+            None
+        }
+        Err(e) => {
+            log_debug!(category: "Disk Space Check", message: "df failed, skipping free space check", details: "{}", e);
+            None
+        }
+    };
+
+    let command = crate::ssh::commands::lustre_quota_command(&username, path);
+    let quota_remaining = match connection_manager.execute_command(&command, Some(crate::cluster::timeouts::DEFAULT_COMMAND)).await {
+        Ok(result) if result.exit_code == 0 => crate::ssh::commands::parse_lustre_quota_remaining(&result.stdout),
+        Ok(_) => None,
+        Err(e) => {
+            log_debug!(category: "Disk Space Check", message: "lfs quota failed, skipping quota check", details: "{}", e);
+            None
+        }
+    };
+
+    let check = evaluate_disk_space(path, required_bytes, space, quota_remaining);
+    if !check.sufficient {
+        log_warn!(category: "Disk Space Check", message: "Not enough disk space for write", details: "{}", check.message);
+    }
+    Ok(check)
+}
+
+/// Fail with `SSHError::InsufficientSpace` unless `required_bytes` fit under `path`
+/// With `ignore_shortfall` the shortfall is only logged and the caller carries on
+pub async fn ensure_disk_space(path: &str, required_bytes: u64, ignore_shortfall: bool) -> Result<()> {
+    let check = check_disk_space(path, required_bytes).await?;
+    if check.sufficient {
+        return Ok(());
+    }
+    if ignore_shortfall {
+        log_warn!(category: "Disk Space Check", message: "Continuing despite low disk space", details: "{}", check.message);
+        return Ok(());
+    }
+    Err(SSHError::InsufficientSpace(check.message).into())
+}
+
+/// Check that a copy of the remote directory `source_dir` fits under `path`
+/// Best effort like the df and lfs halves of the check: if the source can't be measured,
+/// the check is skipped with a warning rather than failing the operation
+pub async fn ensure_space_for_directory(source_dir: &str, path: &str, ignore_shortfall: bool) -> Result<()> {
+    match remote_directory_size(source_dir).await {
+        Ok(required_bytes) => ensure_disk_space(path, required_bytes, ignore_shortfall).await,
+        Err(e) => {
+            log_warn!(category: "Disk Space Check", message: "Could not measure source directory, skipping disk space check", details: "{}: {}", source_dir, e);
+            Ok(())
+        }
+    }
+}
+
+/// Total size in bytes of a remote directory
+pub async fn remote_directory_size(dir: &str) -> Result<u64> {
+    let connection_manager = crate::ssh::get_connection_manager();
+    let result = connection_manager.execute_command(&crate::ssh::commands::directory_size_command(dir), Some(crate::cluster::timeouts::DEFAULT_COMMAND)).await?;
+    if result.exit_code != 0 {
+        return Err(anyhow!("Failed to measure {}: {}", dir, result.stderr.trim()));
+    }
+    Ok(crate::ssh::commands::parse_disk_usage(&result.stdout, dir)?.0)
+}
+
+/// Size and file count of a job's outputs/ directory in the project space
+pub async fn job_output_size(job_id: &str) -> Result<RemoteDirSize> {
+    let job_info = helpers::load_job_or_fail(job_id, "Output Size")?;
+    let (connection_manager, _username) = common::require_connection_with_username("Output Size").await?;
+    let project_dir = common::require_project_dir(&job_info, "Output Size")?;
+
+    let outputs_dir = format!("{}/{}", project_dir, crate::ssh::JobDirectoryStructure::OUTPUTS);
+    connection_manager.remote_dir_size(&outputs_dir).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_save_archive_compression_level_rejects_invalid_level() {
+        assert!(save_archive_compression_level(MAX_COMPRESSION_LEVEL + 1).is_err());
+        assert!(save_archive_compression_level(u8::MAX).is_err());
+    }
+
     #[test]
     fn test_validate_upload_file_path_traversal() {
         // Test path traversal in remote name (tested before filesystem checks)
@@ -213,4 +713,122 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_evaluate_inode_capacity() {
+        let fits = evaluate_inode_capacity("/projects/testuser", 5000, Some(5000));
+        assert!(fits.sufficient);
+        assert_eq!(fits.free_inodes, Some(5000));
+
+        let exceeds = evaluate_inode_capacity("/projects/testuser", 5001, Some(5000));
+        assert!(!exceeds.sufficient);
+        assert!(exceeds.message.contains("5001 files"));
+        assert!(exceeds.message.contains("5000 free inodes"));
+
+        // Unknown inode count must not block the upload
+        let skipped = evaluate_inode_capacity("/scratch/alpine/testuser", 1_000_000, None);
+        assert!(skipped.sufficient);
+        assert!(skipped.message.contains("skipped"));
+    }
+
+    #[test]
+    fn test_evaluate_disk_space() {
+        const GB: u64 = 1024 * 1024 * 1024;
+
+        let fits = evaluate_disk_space("/projects/testuser", GB, Some((100 * GB, 2 * GB)), None);
+        assert!(fits.sufficient);
+        assert_eq!(fits.shortfall_bytes, 0);
+
+        let short = evaluate_disk_space("/projects/testuser", 3 * GB, Some((100 * GB, 2 * GB)), None);
+        assert!(!short.sufficient);
+        assert_eq!(short.shortfall_bytes, GB);
+        assert_eq!(short.message, "/projects/testuser needs 3.0 GB but only 2.0 GB is free (short by 1.0 GB)");
+
+        // A quota tighter than the filesystem's free space is what counts
+        let quota = evaluate_disk_space("/scratch/alpine/testuser", 3 * GB, Some((1000 * GB, 500 * GB)), Some(GB));
+        assert_eq!(quota.shortfall_bytes, 2 * GB);
+        assert!(quota.message.contains("left under your quota"));
+
+        // Unknown free space must not block the write
+        let skipped = evaluate_disk_space("/scratch/alpine/testuser", 3 * GB, None, None);
+        assert!(skipped.sufficient);
+        assert!(skipped.message.contains("skipped"));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 bytes");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+
+    #[test]
+    fn test_parse_output_pattern() {
+        assert_eq!(parse_output_pattern("*.dcd").unwrap(), "*.dcd");
+        assert_eq!(parse_output_pattern(" final.* ").unwrap(), "final.*");
+        assert_eq!(parse_output_pattern(".log").unwrap(), "*.log");
+        assert_eq!(parse_output_pattern("task_0/*.log").unwrap(), "task_0/*.log");
+
+        assert!(parse_output_pattern("").is_err());
+        assert!(parse_output_pattern("/etc/*").is_err());
+        assert!(parse_output_pattern("../*.dcd").is_err());
+        assert!(parse_output_pattern("*.(dcd|log)").is_err());
+        assert!(parse_output_pattern("[a-z]*.log").is_err());
+        assert!(parse_output_pattern("*.dcd; rm -rf ~").is_err());
+    }
+
+    #[test]
+    fn test_output_pattern_matches() {
+        assert!(output_pattern_matches("*.dcd", "run.dcd"));
+        assert!(output_pattern_matches("*.dcd", "task_3/run.dcd"));
+        assert!(!output_pattern_matches("*.dcd", "run.dcd.bak"));
+        assert!(output_pattern_matches("final.*", "final.restart.coor"));
+        assert!(!output_pattern_matches("final.*", "prefinal.coor"));
+        assert!(output_pattern_matches("*.restart.coor", "final.restart.coor"));
+        assert!(output_pattern_matches("run?.log", "run1.log"));
+        assert!(!output_pattern_matches("run?.log", "run10.log"));
+        assert!(output_pattern_matches("namd_output.log", "namd_output.log"));
+
+        // Patterns with a directory match the whole path, and * stays within one component
+        assert!(output_pattern_matches("task_0/*.log", "task_0/namd.log"));
+        assert!(!output_pattern_matches("task_0/*.log", "task_1/namd.log"));
+        assert!(!output_pattern_matches("*/*.log", "namd.log"));
+        assert!(!output_pattern_matches("task_*", "task_0/namd.log"));
+    }
+
+
+    #[test]
+    fn test_relative_to() {
+        let dir = "/projects/user/namdrunner_jobs/test_job_1/outputs";
+        assert_eq!(relative_to(dir, "/projects/user/namdrunner_jobs/test_job_1/outputs/task_0/run.dcd"), "task_0/run.dcd");
+        assert_eq!(relative_to(dir, "/elsewhere/file"), "/elsewhere/file");
+    }
+
+
+    #[test]
+    fn test_has_binary_extension() {
+        assert!(has_binary_extension("outputs/sim.dcd"));
+        assert!(has_binary_extension("outputs/final.restart.coor"));
+        assert!(has_binary_extension("outputs/RUN.DCD"));
+        assert!(has_binary_extension("job_123.out.gz"));
+        assert!(!has_binary_extension("outputs/namd_output.log"));
+        assert!(!has_binary_extension("outputs/final.restart.xsc"));
+        assert!(!has_binary_extension("config.namd"));
+        assert!(!has_binary_extension("job.sbatch"));
+    }
+
+    #[test]
+    fn test_looks_binary() {
+        assert!(!looks_binary(""));
+        assert!(!looks_binary("ENERGY:       0      0.0000\r\n\tTIMING: 500\x0c\n"));
+        assert!(!looks_binary("Info: NAMD 3.0 for Linux-x86_64 – ±0.5 Å\n"));
+        assert!(looks_binary("CORD\0\0\0\x01"));
+        assert!(looks_binary(&String::from_utf8_lossy(&[0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0xfe, 0x03, 0x04])));
+
+        // A few stray bytes in an otherwise textual log stay text
+        let mut log = "ENERGY: 1 2 3 4 5 6 7 8 9\n".repeat(20);
+        log.push('\u{FFFD}');
+        assert!(!looks_binary(&log));
+    }
+
 }
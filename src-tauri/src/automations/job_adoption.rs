@@ -0,0 +1,312 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+
+use crate::types::{JobInfo, SlurmConfig};
+use crate::security::input;
+use crate::ssh::{paths, JobDirectoryStructure, RemoteExecutor};
+use crate::database::with_database;
+use crate::{log_info, log_debug, log_warn, log_error};
+use crate::automations::{common, job_creation};
+
+/// Metadata file written by job creation (and read back here when present)
+const JOB_METADATA_FILE: &str = "job_info.json";
+
+/// Read the SLURM settings from `#SBATCH` directives in a batch script
+///
+/// Only the directives NAMDRunner itself writes are recognized; anything missing
+/// keeps the SlurmConfig default. Both `--opt=value` and `--opt value` forms work.
+pub fn parse_sbatch_directives(script: &str) -> (Option<String>, SlurmConfig) {
+    let mut job_name = None;
+    let mut config = SlurmConfig::default();
+
+    for line in script.lines() {
+        let Some(directive) = line.trim().strip_prefix("#SBATCH") else {
+            continue;
+        };
+        let directive = directive.trim();
+        let (option, value) = match directive.split_once('=') {
+            Some((option, value)) => (option.trim(), value.trim()),
+            None => match directive.split_once(char::is_whitespace) {
+                Some((option, value)) => (option.trim(), value.trim()),
+                None => continue,
+            },
+        };
+        if value.is_empty() {
+            continue;
+        }
+
+        match option {
+            "--job-name" | "-J" => job_name = Some(value.to_string()),
+            "--ntasks" | "-n" => {
+                if let Ok(cores) = value.parse() {
+                    config.cores = cores;
+                }
+            }
+            "--mem" => config.memory = value.to_string(),
+            "--time" | "-t" => config.walltime = value.to_string(),
+            "--partition" | "-p" => config.partition = value.to_string(),
+            "--qos" | "-q" => config.qos = value.to_string(),
+            _ => {}
+        }
+    }
+
+    (job_name, config)
+}
+
+/// Build the local JobInfo for a directory staged outside the app
+///
+/// With existing metadata the job keeps its identity, template, and SLURM config,
+/// but run state is reset so it can be submitted fresh. Without metadata the job
+/// is named after the script's `--job-name` (or the directory) and its resources
+/// come from the `#SBATCH` directives. Either way the job starts in Created.
+pub fn build_adopted_job(
+    remote_dir: &str,
+    metadata: Option<JobInfo>,
+    sbatch_script: &str,
+    input_files: Vec<String>,
+) -> Result<JobInfo> {
+    let mut job = match metadata {
+        Some(existing) => {
+            let job_id = input::sanitize_job_id(&existing.job_id)
+                .map_err(|e| anyhow!("Invalid job ID in {}: {}", JOB_METADATA_FILE, e))?;
+            let files = if existing.input_files.is_empty() { input_files } else { existing.input_files };
+
+            job_creation::create_job_info(
+                job_id,
+                existing.job_name,
+                existing.template_id,
+                existing.template_values,
+                existing.slurm_config,
+                files,
+            )
+        }
+        None => {
+            let (script_name, slurm_config) = parse_sbatch_directives(sbatch_script);
+            let dir_name = remote_dir.rsplit('/').next().unwrap_or_default();
+            let job_name = input::sanitize_job_id(script_name.as_deref().unwrap_or(dir_name))
+                .or_else(|_| input::sanitize_job_id(dir_name))
+                .map_err(|e| anyhow!("Cannot derive a job name from '{}': {}", remote_dir, e))?;
+            // Collisions with tracked jobs are rejected by the duplicate check in execute_job_adoption
+            let job_id = format!("{}_{}", job_name, common::next_id_timestamp());
+
+            // No template: the directory already holds a rendered config and script
+            job_creation::create_job_info(job_id, job_name, String::new(), HashMap::new(), slurm_config, input_files)
+        }
+    };
+
+    job.project_dir = Some(remote_dir.to_string());
+    Ok(job)
+}
+
+/// Adopt a job directory that was staged on the cluster by hand
+///
+/// Validates the directory, reads job_info.json when present (otherwise infers
+/// the config from job.sbatch), uploads fresh metadata, then saves a Created job
+/// pointing at it. Nothing else is uploaded, so submission runs on the staged files.
+pub async fn execute_job_adoption(remote_dir: &str) -> Result<JobInfo> {
+    log_info!(category: "Job Adoption", message: "Adopting remote directory", details: "{}", remote_dir);
+
+    let (connection_manager, username) = common::require_connection_with_username("Job Adoption").await?;
+
+    let remote_dir = remote_dir.trim().trim_end_matches('/');
+    paths::confine_to_project(remote_dir, &username)?;
+
+    let job = read_staged_job(connection_manager, remote_dir).await?;
+
+    // Refuse to track the same job or directory twice
+    let existing = with_database(|db| db.load_all_jobs())?.jobs;
+    if let Some(tracked) = existing.iter().find(|j| j.job_id == job.job_id || j.project_dir.as_deref() == Some(remote_dir)) {
+        return Err(anyhow!("Directory '{}' is already tracked as job {}", remote_dir, tracked.job_id));
+    }
+
+    // Metadata first: a failed upload then leaves nothing tracked, and adopting again can retry
+    crate::ssh::metadata::upload_job_metadata(connection_manager, &job, remote_dir, "Job Adoption").await
+        .map_err(|e| anyhow!("Could not write job metadata to '{}': {}", remote_dir, e))?;
+
+    common::save_job_to_database(&job, "Job Adoption")?;
+
+    log_info!(category: "Job Adoption", message: "Job adopted", details: "{} ({})", job.job_id, remote_dir, show_toast: true);
+    Ok(job)
+}
+
+/// Files a staged directory must hold: submission runs the batch script, which runs the config
+const REQUIRED_STAGED_FILES: [(&str, &str); 2] = [
+    (JobDirectoryStructure::SLURM_SCRIPT, "batch script"),
+    (JobDirectoryStructure::NAMD_CONFIG, "NAMD config"),
+];
+
+/// Check a staged directory and build the job it describes, without saving anything
+/// A directory missing job.sbatch or config.namd is rejected here rather than at submission
+pub async fn read_staged_job(remote: &dyn RemoteExecutor, remote_dir: &str) -> Result<JobInfo> {
+    let entries = remote.list_files(remote_dir, false).await
+        .map_err(|e| {
+            log_error!(category: "Job Adoption", message: "Failed to list directory", details: "{}: {}", remote_dir, e);
+            anyhow!("Could not read directory '{}': {}", remote_dir, e)
+        })?;
+    let has_file = |name: &str| entries.iter().any(|entry| entry.name == name);
+
+    for (file, description) in REQUIRED_STAGED_FILES {
+        if !has_file(file) {
+            return Err(anyhow!("Directory '{}' has no {} - stage the {} before adopting", remote_dir, file, description));
+        }
+    }
+
+    let script_path = format!("{}/{}", remote_dir, JobDirectoryStructure::SLURM_SCRIPT);
+    let sbatch_script = remote.read_remote_file(&script_path).await
+        .map_err(|e| anyhow!("Could not read {}: {}", script_path, e))?;
+
+    let metadata = if has_file(JOB_METADATA_FILE) {
+        let metadata_path = format!("{}/{}", remote_dir, JOB_METADATA_FILE);
+        let content = remote.read_remote_file(&metadata_path).await
+            .map_err(|e| anyhow!("Could not read {}: {}", metadata_path, e))?;
+        match serde_json::from_str::<JobInfo>(&content) {
+            Ok(job) => Some(job),
+            Err(e) => {
+                log_warn!(category: "Job Adoption", message: "Ignoring unreadable job_info.json - inferring config from job.sbatch");
+                log_debug!(category: "Job Adoption", message: "job_info.json parse error", details: "{}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Input files are optional for hand-staged jobs
+    let input_dir = format!("{}/{}", remote_dir, JobDirectoryStructure::INPUT_FILES);
+    let input_files = match remote.list_files(&input_dir, false).await {
+        Ok(files) => files.into_iter().map(|f| f.name).collect(),
+        Err(e) => {
+            log_debug!(category: "Job Adoption", message: "No input_files directory", details: "{}: {}", input_dir, e);
+            Vec::new()
+        }
+    };
+
+    build_adopted_job(remote_dir, metadata, &sbatch_script, input_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::JobStatus;
+
+    const STAGED_SCRIPT: &str = "#!/bin/bash\n\
+        #SBATCH --job-name=manual_sim\n\
+        #SBATCH --partition=amilan\n\
+        #SBATCH --ntasks=32\n\
+        #SBATCH --time=12:00:00\n\
+        #SBATCH --mem 64GB\n\
+        #SBATCH --qos=long\n\
+        \n\
+        mpirun namd3 config.namd\n";
+
+    #[test]
+    fn test_parse_sbatch_directives() {
+        let (name, config) = parse_sbatch_directives(STAGED_SCRIPT);
+
+        assert_eq!(name.as_deref(), Some("manual_sim"));
+        assert_eq!(config.cores, 32);
+        assert_eq!(config.memory, "64GB");
+        assert_eq!(config.walltime, "12:00:00");
+        assert_eq!(config.partition, "amilan");
+        assert_eq!(config.qos, "long");
+    }
+
+    #[test]
+    fn test_adopt_without_metadata_infers_config() {
+        let dir = "/projects/testuser/manual_runs/sim_a";
+        let job = build_adopted_job(dir, None, STAGED_SCRIPT, vec!["structure.pdb".to_string()]).unwrap();
+
+        assert_eq!(job.status, JobStatus::Created);
+        assert_eq!(job.job_name, "manual_sim");
+        assert!(job.job_id.starts_with("manual_sim_"));
+        assert_eq!(job.project_dir.as_deref(), Some(dir));
+        assert!(job.scratch_dir.is_none());
+        assert!(job.template_id.is_empty());
+        assert_eq!(job.slurm_config.cores, 32);
+        assert_eq!(job.input_files, vec!["structure.pdb"]);
+
+        // Falls back to the directory name when the script doesn't name the job
+        let unnamed = build_adopted_job(dir, None, "#!/bin/bash\n#SBATCH --ntasks=4\n", vec![]).unwrap();
+        assert_eq!(unnamed.job_name, "sim_a");
+        assert_eq!(unnamed.slurm_config.cores, 4);
+        assert_eq!(unnamed.slurm_config.walltime, SlurmConfig::default().walltime);
+    }
+
+    #[test]
+    fn test_adopt_with_metadata_keeps_identity_and_resets_state() {
+        let dir = "/projects/testuser/namdrunner_jobs/old_job_1";
+        let mut previous = job_creation::create_job_info(
+            "old_job_1".to_string(),
+            "old_job".to_string(),
+            "vacuum_optimization_v1".to_string(),
+            HashMap::from([("temperature".to_string(), serde_json::json!(310))]),
+            SlurmConfig { cores: 8, ..SlurmConfig::default() },
+            vec!["old.pdb".to_string()],
+        );
+        previous.status = JobStatus::Failed;
+        previous.slurm_job_id = Some("12345".to_string());
+        previous.scratch_dir = Some("/scratch/alpine/testuser/namdrunner_jobs/old_job_1".to_string());
+        previous.error_info = Some("node failure".to_string());
+
+        let job = build_adopted_job(dir, Some(previous), STAGED_SCRIPT, vec!["listed.pdb".to_string()]).unwrap();
+
+        assert_eq!(job.job_id, "old_job_1");
+        assert_eq!(job.job_name, "old_job");
+        assert_eq!(job.template_id, "vacuum_optimization_v1");
+        assert_eq!(job.template_values["temperature"], serde_json::json!(310));
+        // Metadata wins over the script's directives
+        assert_eq!(job.slurm_config.cores, 8);
+        assert_eq!(job.input_files, vec!["old.pdb"]);
+
+        assert_eq!(job.status, JobStatus::Created);
+        assert!(job.slurm_job_id.is_none());
+        assert!(job.scratch_dir.is_none());
+        assert!(job.error_info.is_none());
+        assert_eq!(job.project_dir.as_deref(), Some(dir));
+    }
+
+    #[test]
+    fn test_adopt_rejects_invalid_metadata_job_id() {
+        let mut previous = job_creation::create_job_info(
+            "bad_job".to_string(), "bad".to_string(), String::new(), HashMap::new(), SlurmConfig::default(), vec![],
+        );
+        previous.job_id = "job; rm -rf ~".to_string();
+
+        assert!(build_adopted_job("/projects/testuser/bad", Some(previous), STAGED_SCRIPT, vec![]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_staged_job_requires_script_and_config() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let dir = "/projects/testuser/manual_run";
+        let remote = MockRemoteExecutor::new("testuser");
+        remote.filesystem.lock().unwrap()
+            .add_directory(dir, 0o755)
+            .add_text_file(&format!("{}/{}", dir, JobDirectoryStructure::SLURM_SCRIPT), STAGED_SCRIPT);
+
+        let error = read_staged_job(&remote, dir).await.unwrap_err().to_string();
+        assert!(error.contains(JobDirectoryStructure::NAMD_CONFIG), "{}", error);
+
+        remote.filesystem.lock().unwrap()
+            .add_text_file(&format!("{}/{}", dir, JobDirectoryStructure::NAMD_CONFIG), "run 1000\n");
+        let job = read_staged_job(&remote, dir).await.unwrap();
+        assert_eq!(job.job_name, "manual_sim");
+        assert_eq!(job.project_dir.as_deref(), Some(dir));
+        assert_eq!(job.status, JobStatus::Created);
+    }
+
+    #[tokio::test]
+    async fn test_read_staged_job_requires_script() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let dir = "/projects/testuser/manual_run";
+        let remote = MockRemoteExecutor::new("testuser");
+        remote.filesystem.lock().unwrap()
+            .add_directory(dir, 0o755)
+            .add_text_file(&format!("{}/{}", dir, JobDirectoryStructure::NAMD_CONFIG), "run 1000\n");
+
+        let error = read_staged_job(&remote, dir).await.unwrap_err().to_string();
+        assert!(error.contains(JobDirectoryStructure::SLURM_SCRIPT), "{}", error);
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::{Result, anyhow};
+use crate::types::{JobInfo, JobStatus};
+use crate::{log_info, log_debug, log_error};
+use crate::commands::helpers;
+use crate::automations::common;
+
+/// Cancel a queued or running job with scancel, then pull back whatever it produced
+/// Provides progress reporting through callbacks
+///
+/// The job may have finished between the UI showing it as active and the scancel, in which
+/// case it keeps the status SLURM reports instead of being marked Cancelled.
+pub async fn execute_job_cancellation(
+    job_id: String,
+    progress_callback: impl Fn(&str),
+) -> Result<JobInfo> {
+    progress_callback("Loading job information...");
+    let mut job = helpers::load_job_or_fail(&job_id, "Job Cancellation")?;
+
+    let slurm_job_id = job.slurm_job_id.clone()
+        .ok_or_else(|| anyhow!("Job {} has no SLURM job ID - it was never submitted", job.job_id))?;
+    if !matches!(job.status, JobStatus::Pending | JobStatus::Held | JobStatus::Running) {
+        return Err(anyhow!("Job {} is not queued or running (status {:?})", job.job_id, job.status));
+    }
+
+    let (connection_manager, username) = common::require_connection_with_username("Job Cancellation").await?;
+    let slurm_sync = crate::slurm::status::SlurmStatusSync::new(&username);
+
+    progress_callback("Cancelling SLURM job...");
+    log_debug!(category: "Job Cancellation", message: "Cancelling SLURM job", details: "{} (SLURM {})", job.job_id, slurm_job_id);
+    let scancel_result = slurm_sync.cancel_job(&slurm_job_id).await;
+
+    // scancel on a job that already left the queue can succeed or fail depending on the
+    // cluster, so ask SLURM what actually happened
+    progress_callback("Checking job state...");
+    let slurm_status = match slurm_sync.query_job_statuses(std::slice::from_ref(&slurm_job_id)).await {
+        Ok(results) => results.into_iter().find_map(|(_, status, _)| status.ok()),
+        Err(e) => {
+            log_debug!(category: "Job Cancellation", message: "Could not confirm job state after scancel", details: "{}: {}", slurm_job_id, e);
+            None
+        }
+    };
+
+    let final_status = cancellation_outcome(scancel_result, slurm_status)
+        .map_err(|e| anyhow!("Failed to cancel SLURM job {}: {}", slurm_job_id, e))?;
+    if final_status != JobStatus::Cancelled {
+        log_info!(category: "Job Cancellation", message: "Job finished before it could be cancelled", details: "{}: {:?}", job.job_id, final_status);
+    }
+
+    common::update_job_status(&mut job, final_status);
+    job.status_detail = None;
+    common::save_job_to_database(&job, "Job Cancellation")?;
+
+    // Same completion as a job finishing on its own: copy partial outputs and logs back
+    progress_callback("Retrieving partial outputs...");
+    if let Err(e) = crate::automations::execute_job_completion(connection_manager, &mut job).await {
+        log_error!(category: "Job Cancellation", message: "Completion after cancel failed", details: "{}: {}", job.job_id, e);
+        // The job is cancelled either way. Nothing retries this: sync skips finished jobs, so the
+        // partial outputs stay in scratch until the user downloads them or refetches the logs
+    }
+
+    log_info!(category: "Job Cancellation", message: "Job cancelled", details: "{}", job.job_id, show_toast: true);
+    Ok(job)
+}
+
+/// Status to record after a scancel attempt, given the state SLURM reports afterwards
+/// A job SLURM already lists as finished keeps that status whether or not scancel succeeded;
+/// otherwise a successful scancel means Cancelled and a failed one is an error
+fn cancellation_outcome(scancel_result: Result<()>, slurm_status: Option<JobStatus>) -> Result<JobStatus> {
+    match slurm_status {
+        Some(status @ (JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled)) => Ok(status),
+        _ => scancel_result.map(|()| JobStatus::Cancelled),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancelled_job() {
+        // squeue can still show the job (CG maps to Running) right after scancel
+        assert_eq!(cancellation_outcome(Ok(()), Some(JobStatus::Running)).unwrap(), JobStatus::Cancelled);
+        assert_eq!(cancellation_outcome(Ok(()), Some(JobStatus::Cancelled)).unwrap(), JobStatus::Cancelled);
+        assert_eq!(cancellation_outcome(Ok(()), None).unwrap(), JobStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_job_finished_before_scancel() {
+        // scancel reports success for a job that's gone, or fails with "already completed"
+        assert_eq!(cancellation_outcome(Ok(()), Some(JobStatus::Completed)).unwrap(), JobStatus::Completed);
+        assert_eq!(
+            cancellation_outcome(Err(anyhow!("Job/step already completing or completed")), Some(JobStatus::Failed)).unwrap(),
+            JobStatus::Failed
+        );
+    }
+
+    #[test]
+    fn test_scancel_failure_on_active_job() {
+        assert!(cancellation_outcome(Err(anyhow!("Access/permission denied")), Some(JobStatus::Running)).is_err());
+        assert!(cancellation_outcome(Err(anyhow!("Connection lost")), None).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::{Result, anyhow};
 use crate::types::{JobStatus, JobInfo};
-use crate::{log_info, log_error};
+use crate::types::response_data::JobAccounting;
+use crate::slurm::status::SlurmStatusSync;
+use crate::ssh::RemoteExecutor;
+use crate::{log_info, log_debug, log_error, log_warn};
 use crate::automations::common;
 
 /// Execute job completion automation (called automatically when job reaches terminal state)
@@ -8,10 +11,12 @@ use crate::automations::common;
 /// This function:
 /// 1. Rsyncs all files from scratch directory to project directory (DATA BOUNDARY CROSSED)
 /// 2. Fetches SLURM logs from project directory (after rsync)
-/// 3. Updates database with final state
+/// 3. Records the actual cost from sacct's elapsed walltime
+/// 4. Updates database with final state and rewrites the project's job_info.json
 ///
 /// Called automatically by job_sync when a job reaches terminal state (Completed, Failed, etc.)
-pub async fn execute_job_completion(job: &mut JobInfo) -> Result<()> {
+/// All remote work goes through `remote` (the global connection manager outside tests)
+pub async fn execute_job_completion(remote: &dyn RemoteExecutor, job: &mut JobInfo) -> Result<()> {
     let job_id = job.job_id.clone();
     log_info!(category: "Job Completion", message: "Starting automatic completion", details: "{}", job_id);
 
@@ -21,7 +26,7 @@ pub async fn execute_job_completion(job: &mut JobInfo) -> Result<()> {
     }
 
     // Verify SSH connection is active
-    let (connection_manager, _username) = common::require_connection_with_username("Job Completion").await?;
+    common::require_username(remote, "Job Completion").await?;
 
     // Ensure we have both project and scratch directories
     let project_dir = common::require_project_dir(job, "Job Completion")?.to_string();
@@ -32,7 +37,7 @@ pub async fn execute_job_completion(job: &mut JobInfo) -> Result<()> {
     let source_with_slash = common::ensure_trailing_slash(&scratch_dir);
 
     log_info!(category: "Job Completion", message: "Rsyncing scratch to project", details: "{} -> {}", scratch_dir, project_dir);
-    connection_manager.mirror_directory(&source_with_slash, &project_dir).await
+    remote.mirror_directory(&source_with_slash, &project_dir).await
         .map_err(|e| {
             log_error!(category: "Job Completion", message: "Rsync failed", details: "{}", e);
             anyhow!("Failed to rsync: {}", e)
@@ -40,8 +45,8 @@ pub async fn execute_job_completion(job: &mut JobInfo) -> Result<()> {
 
     log_info!(category: "Job Completion", message: "Rsync complete - all files now in project directory");
 
-    // Fetch logs from project directory (after rsync)
-    if let Err(e) = crate::automations::load_slurm_logs(job, false).await {
+    // Fetch logs from project directory (after rsync), unless the log fetch policy defers or disables it
+    if let Err(e) = crate::automations::job_sync::auto_fetch_slurm_logs(remote, job, crate::automations::job_sync::LogFetchTrigger::Completion).await {
         log_error!(category: "Job Completion", message: "Failed to fetch logs", details: "{}", e);
         // Don't fail completion if log fetch fails - logs are nice-to-have
     }
@@ -50,16 +55,9 @@ pub async fn execute_job_completion(job: &mut JobInfo) -> Result<()> {
     let output_dir = format!("{}/outputs", project_dir);
     log_info!(category: "Job Completion", message: "Fetching output file metadata", details: "{}", output_dir);
 
-    match connection_manager.list_files(&output_dir, false).await {
-        Ok(files) => {
-            // Convert SftpFileEntry to OutputFile
-            let output_files: Vec<crate::types::OutputFile> = files.into_iter()
-                .map(|entry| crate::types::OutputFile {
-                    name: entry.name,
-                    size: entry.size,
-                    modified_at: entry.modified_time.map(|t| t.to_string()).unwrap_or_else(|| "unknown".to_string()),
-                })
-                .collect();
+    let task_count = job.array.as_ref().map_or(0, |array| array.values.len());
+    match list_output_files(remote, &output_dir, task_count).await {
+        Ok(output_files) => {
             log_info!(category: "Job Completion", message: "Found output files", details: "{} files", output_files.len());
             job.output_files = output_files;
         }
@@ -70,16 +68,146 @@ pub async fn execute_job_completion(job: &mut JobInfo) -> Result<()> {
         }
     }
 
+    // sacct can lag the job's exit by a few seconds; job sync retries while actual_cost is None
+    record_actual_cost(remote, job).await;
+
     // Update database with timestamp
     common::touch_job_timestamp(job);
+    common::refresh_user_edits(job);
     common::save_job_to_database(job, "Job Completion")?;
 
+    // The rsync brought back scratch's job_info.json from before submission; replace it with
+    // the finished record (final status, outputs, notes)
+    if let Err(e) = crate::ssh::metadata::upload_job_metadata(remote, job, &project_dir, "Job Completion").await {
+        log_error!(category: "Job Completion", message: "Failed to update job metadata", details: "{}: {}", job_id, e);
+        // The local record is complete; the server copy catches up at the next boundary
+    }
+
     log_info!(category: "Job Completion", message: "Job completion successful", details: "{}", job_id);
     Ok(())
 }
 
+/// Output files of a finished job, read from its project outputs directory
+/// Array tasks (`task_count` of them) write into outputs/task_N; their files are listed as "task_N/<file>"
+pub async fn list_output_files(remote: &dyn RemoteExecutor, output_dir: &str, task_count: usize) -> Result<Vec<crate::types::OutputFile>> {
+    let output_file = |name: String, entry: &crate::ssh::SftpFileEntry| crate::types::OutputFile {
+        name,
+        size: entry.size,
+        modified_at: entry.modified_time.map(|t| t.to_string()).unwrap_or_else(|| "unknown".to_string()),
+    };
+
+    let mut output_files: Vec<crate::types::OutputFile> = remote.list_files(output_dir, false).await?
+        .iter()
+        .map(|entry| output_file(entry.name.clone(), entry))
+        .collect();
+
+    for task_id in 0..task_count {
+        let task_dir = crate::ssh::JobDirectoryStructure::array_task_dir(&task_id.to_string());
+        output_files.retain(|file| file.name != task_dir);
+        match remote.list_files(&format!("{}/{}", output_dir, task_dir), false).await {
+            Ok(task_files) => output_files.extend(task_files.iter()
+                .filter(|entry| !entry.is_directory)
+                .map(|entry| output_file(format!("{}/{}", task_dir, entry.name), entry))),
+            Err(e) => {
+                // A task that never started has no output directory
+                log_debug!(category: "Job Completion", message: "No outputs for array task", details: "{}: {}", task_dir, e);
+            }
+        }
+    }
+
+    Ok(output_files)
+}
+
+/// Set `actual_cost` from the job's sacct elapsed walltime at the cluster billing rates
+/// Leaves it None when sacct has no elapsed time yet (or can't be reached); returns whether it was set.
+/// Array jobs are skipped - each task is accounted separately.
+pub async fn record_actual_cost(remote: &dyn RemoteExecutor, job: &mut JobInfo) -> bool {
+    let Some(slurm_job_id) = job.slurm_job_id.clone() else { return false };
+    if job.array.is_some() {
+        return false;
+    }
+
+    match SlurmStatusSync::with_executor(remote).query_job_accounting(&slurm_job_id).await {
+        Ok(Some(JobAccounting { elapsed_seconds: Some(elapsed), .. })) => {
+            let config = &job.slurm_config;
+            let cost = crate::cluster::calculate_actual_cost(config.cores, elapsed, config.gpu_count());
+            log_info!(category: "Job Completion", message: "Recorded actual cost", details: "{}: {:.1} SU over {}s", job.job_id, cost, elapsed);
+            job.actual_cost = Some(cost);
+            true
+        }
+        Ok(_) => {
+            log_debug!(category: "Job Completion", message: "No elapsed time in sacct yet", details: "{} (SLURM {})", job.job_id, slurm_job_id);
+            false
+        }
+        Err(e) => {
+            log_warn!(category: "Job Completion", message: "Could not read accounting for cost", details: "{}: {}", job.job_id, e);
+            false
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    #[tokio::test]
+    async fn test_completion_log_fetch_follows_policy() {
+        use crate::types::JobStatus;
+        use crate::ssh::test_utils::MockRemoteExecutor;
+        use crate::ssh::directory_structure::JobDirectoryStructure;
+        use crate::automations::job_sync::{slurm_log_path, LogFetchPolicy, LogStream, LOG_FETCH_POLICY_SETTING};
+        use crate::database::with_database;
+
+        let _database = crate::database::fresh_global_database().await;
+        let project_dir = JobDirectoryStructure::project_dir("testuser", "completion_log_job");
+        let mut finished = crate::automations::job_creation::create_job_info(
+            "completion_log_job".to_string(),
+            "completion_log_job".to_string(),
+            "test".to_string(),
+            std::collections::HashMap::new(),
+            crate::types::SlurmConfig::default(),
+            vec![],
+        );
+        finished.status = JobStatus::Completed;
+        finished.slurm_job_id = Some("7001".to_string());
+        finished.project_dir = Some(project_dir.clone());
+        finished.scratch_dir = Some(JobDirectoryStructure::scratch_dir("testuser", "completion_log_job"));
+
+        for (policy, expected_reads) in [(LogFetchPolicy::Never, 0), (LogFetchPolicy::OnStatusView, 0), (LogFetchPolicy::OnCompletion, 2)] {
+            with_database(|db| db.save_setting(LOG_FETCH_POLICY_SETTING, &policy)).unwrap();
+            let remote = MockRemoteExecutor::new("testuser");
+            remote.filesystem.lock().unwrap()
+                .add_text_file(&slurm_log_path(&project_dir, "completion_log_job", "7001", None, LogStream::Stdout), "stdout")
+                .add_text_file(&slurm_log_path(&project_dir, "completion_log_job", "7001", None, LogStream::Stderr), "stderr");
+
+            let mut job = finished.clone();
+            super::execute_job_completion(&remote, &mut job).await.unwrap();
+
+            let log_reads = remote.executed().iter().filter(|cmd| cmd.contains("cat ")).count();
+            assert_eq!(log_reads, expected_reads, "{:?}", policy);
+            assert_eq!(job.slurm_stdout.is_some(), expected_reads > 0, "{:?}", policy);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_output_files_includes_array_task_outputs() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+        use crate::ssh::directory_structure::JobDirectoryStructure;
+
+        let output_dir = format!("{}/outputs", JobDirectoryStructure::project_dir("testuser", "job_001"));
+        let remote = MockRemoteExecutor::new("testuser");
+        remote.filesystem.lock().unwrap()
+            .add_directory(&output_dir, 0o755)
+            .add_file(&format!("{}/sim.dcd", output_dir), 2048, 0o644)
+            .add_directory(&format!("{}/task_0", output_dir), 0o755)
+            .add_file(&format!("{}/task_0/sim.dcd", output_dir), 1024, 0o644);
+
+        // task_1 never started, so it has no directory
+        let files = super::list_output_files(&remote, &output_dir, 2).await.unwrap();
+        let names: Vec<(&str, u64)> = files.iter().map(|file| (file.name.as_str(), file.size)).collect();
+        assert_eq!(names, vec![("sim.dcd", 2048), ("task_0/sim.dcd", 1024)]);
+
+        assert!(super::list_output_files(&remote, "/projects/testuser/missing", 0).await.is_err());
+    }
+
     #[test]
     fn test_rsync_source_trailing_slash() {
         use crate::ssh::directory_structure::JobDirectoryStructure;
@@ -3,12 +3,19 @@ use tauri::{AppHandle, Emitter};
 use std::collections::HashMap;
 use serde_json::Value;
 
-use crate::types::{CreateJobParams, JobInfo, JobStatus, SlurmConfig, FileUpload};
+use crate::types::{CreateJobParams, JobArray, JobInfo, JobStatus, SlurmConfig, FileUpload};
+use crate::templates::{Template, VariableType};
 use crate::security::input;
-use crate::ssh::paths;
+use crate::ssh::{paths, ConnectionManager, UploadTask};
 use crate::{log_info, log_debug, log_error};
 use crate::automations::{common, file_operations};
 
+/// Input files uploaded at once during job creation (one SFTP channel each)
+const MAX_PARALLEL_UPLOADS: usize = 4;
+
+/// Most tasks one sweep may have (SLURM's default MaxArraySize allows task IDs up to 1000)
+const MAX_ARRAY_TASKS: usize = 1000;
+
 /// Factory function to create a new JobInfo with business logic (status, timestamps)
 ///
 /// This is the correct way to create new jobs with proper initial state.
@@ -39,6 +46,22 @@ pub fn create_job_info(
         slurm_config,
         input_files,
         output_files: vec![],
+        archived: false,
+        pinned: false,
+        tags: Vec::new(),
+        notes: None,
+        status_detail: None,
+        submitted_script: None,
+        template_snapshot: None,
+        rendered_config: None,
+        depends_on: None,
+        array: None,
+        notify_email: None,
+        notify_events: Vec::new(),
+        actual_cost: None,
+        namd_progress: None,
+        estimated_start: None,
+        queue_priority: None,
     }
 }
 
@@ -60,6 +83,12 @@ pub async fn execute_job_creation_with_progress(
         .map_err(|e| anyhow!("Invalid job name: {}", e))?;
     log_debug!(category: "Job Creation", message: "Sanitized job name", details: "{}", clean_job_name);
 
+    // Dependencies must be tracked jobs; whether they were submitted is checked at submission
+    for dependency in params.depends_on.iter().flatten() {
+        crate::commands::helpers::load_job_or_fail(dependency, "Job Creation")
+            .map_err(|e| anyhow!("Invalid dependency {}: {}", dependency, e))?;
+    }
+
     progress_callback("Validating connection...");
 
     // Validate SSH connection and get username
@@ -68,43 +97,37 @@ pub async fn execute_job_creation_with_progress(
 
     progress_callback("Generating job paths...");
 
-    // Generate unique job ID using timestamp
-    let job_id = format!("{}_{}", clean_job_name, chrono::Utc::now().timestamp_micros());
+    // Generate unique job ID using timestamp (verified against the database)
+    let job_id = common::generate_job_id(&clean_job_name)?;
     let project_dir = paths::project_directory(&username, &job_id)?;
     log_info!(category: "Job Creation", message: "Generated job ID", details: "{} at path: {}", job_id, project_dir);
 
-    progress_callback("Creating project directories...");
+    progress_callback("Checking directory permissions...");
 
-    // Create project directory structure
-    log_info!(category: "Job Creation", message: "Creating project directory", details: "{}", project_dir);
-    connection_manager.create_directory(&project_dir).await
-        .map_err(|e| {
-            log_error!(category: "Job Creation", message: "Failed to create directory", details: "{}: {}", project_dir, e);
-            anyhow!("Could not create job directory on cluster: {}", e)
-        })?;
+    // Fail early with a clear message instead of an opaque mkdir/upload error
+    crate::ssh::permissions::ensure_project_writable(connection_manager, &username).await?;
 
-    // Create standard job subdirectories
-    for subdir in crate::ssh::JobDirectoryStructure::subdirectories() {
-        let subdir_path = format!("{}/{}", project_dir, subdir);
-        log_debug!(category: "Job Creation", message: "Creating subdirectory", details: "{}", subdir_path);
-        connection_manager.create_directory(&subdir_path).await
-            .map_err(|e| {
-                log_error!(category: "Job Creation", message: "Failed to create subdirectory", details: "{}: {}", subdir_path, e);
-                anyhow!("Failed to create subdirectory '{}': {}", subdir, e)
-            })?;
-    }
+    progress_callback("Creating project directories...");
+
+    create_project_directories(connection_manager, &project_dir).await?;
 
     progress_callback("Loading template...");
 
-    // Load template from database (before moving params)
+    // Load template from database (before moving params), with any parent templates merged in
     let template_id_for_db = params.template_id.clone();
     let template = crate::database::with_database(|db| {
         db.load_template(&template_id_for_db)
     })?
         .ok_or_else(|| anyhow!("Template not found: {}", params.template_id))?;
+    let template = crate::templates::resolve_template(&template, crate::commands::helpers::load_template_by_id)?;
 
     log_info!(category: "Job Creation", message: "Loaded template", details: "{}", template.name);
 
+    if let Some(array) = &params.array {
+        validate_job_array(array, &template)?;
+        log_info!(category: "Job Creation", message: "Creating job array", details: "{} tasks sweeping {}", array.values.len(), array.variable);
+    }
+
     progress_callback("Uploading input files...");
 
     // Upload files from template_values
@@ -136,12 +159,9 @@ pub async fn execute_job_creation_with_progress(
     let _ = app_handle.emit("file-upload-list", file_names.clone());
     log_info!(category: "Job Creation", message: "Emitted file upload list", details: "{} files", files_to_upload.len());
 
-    // Second pass: upload each file
-    for (var_key, local_file_path, filename) in files_to_upload {
-        progress_callback(&format!("Uploading file: {}", filename));
-        log_info!(category: "Job Creation", message: "Uploading file", details: "{}: {} -> {}", var_key, local_file_path, filename);
-
-        // Validate file using shared validation logic
+    // Second pass: validate every file before any upload starts
+    let mut upload_tasks = Vec::with_capacity(files_to_upload.len());
+    for (var_key, local_file_path, filename) in &files_to_upload {
         let file_upload = FileUpload {
             local_path: local_file_path.clone(),
             remote_name: filename.clone(),
@@ -152,25 +172,56 @@ pub async fn execute_job_creation_with_progress(
                 anyhow!("File validation failed for '{}': {}", filename, e)
             })?;
 
-        // Construct remote path
-        let remote_path = crate::ssh::JobDirectoryStructure::full_input_path(&project_dir, &filename);
+        // Progress events carry var_key for tracking
+        upload_tasks.push(UploadTask {
+            local_path: local_file_path.into(),
+            remote_path: crate::ssh::JobDirectoryStructure::full_input_path(&project_dir, filename),
+            progress_key: Some(var_key.clone()),
+        });
+    }
+
+    // Fail before a partial upload when the project filesystem can't hold the inputs
+    if !upload_tasks.is_empty() {
+        progress_callback("Checking disk space...");
+        let required_bytes = upload_tasks.iter()
+            .filter_map(|task| std::fs::metadata(&task.local_path).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        file_operations::ensure_disk_space(&project_dir, required_bytes, params.ignore_space_check).await?;
+    }
 
-        // Upload file (pass var_key for progress tracking)
-        connection_manager.upload_file(&local_file_path, &remote_path, Some(app_handle.clone()), Some(var_key.clone())).await
+    // Third pass: upload the files concurrently over one session
+    if !upload_tasks.is_empty() {
+        progress_callback(&format!("Uploading {} file(s)...", upload_tasks.len()));
+        log_info!(category: "Job Creation", message: "Uploading files", details: "{} files to {}", upload_tasks.len(), project_dir);
+
+        // Verified: a silently corrupted input otherwise surfaces as a cryptic NAMD failure
+        connection_manager.upload_files_parallel(upload_tasks, MAX_PARALLEL_UPLOADS, Some(app_handle.clone()), true).await
             .map_err(|e| {
-                log_error!(category: "Job Creation", message: "Failed to upload file", details: "{}: {}", filename, e);
-                anyhow!("Could not upload file '{}': {}", filename, e)
+                log_error!(category: "Job Creation", message: "Failed to upload files", details: "{}", e);
+                anyhow!("Could not upload input files: {}", e)
             })?;
 
-        log_info!(category: "Job Creation", message: "Successfully uploaded", details: "{} -> {}", local_file_path, remote_path);
+        log_info!(category: "Job Creation", message: "Successfully uploaded input files", details: "{} files", files_to_upload.len());
+    }
 
-        // Update template_values with just the filename (not full path)
-        // The renderer will prepend "input_files/" when rendering the template
+    // Update template_values with just the filename (not full path)
+    // The renderer will prepend "input_files/" when rendering the template
+    for (var_key, _, filename) in files_to_upload {
         template_values_for_rendering.insert(var_key, Value::String(filename));
     }
 
     progress_callback("Rendering template...");
 
+    // Array jobs render one config per task; config.namd is task 0's, so the job validates like any other
+    let task_configs = match &params.array {
+        Some(array) => {
+            template_values_for_rendering.insert(array.variable.clone(), array.values[0].clone());
+            render_array_task_configs(&template, &template_values_for_rendering, array)?
+        }
+        None => Vec::new(),
+    };
+
     // Render NAMD config from template with uploaded filenames
     let namd_config_content = crate::templates::render_template(&template, &template_values_for_rendering)?;
     log_info!(category: "Job Creation", message: "Rendered NAMD config", details: "{} bytes", namd_config_content.len());
@@ -190,19 +241,68 @@ pub async fn execute_job_creation_with_progress(
 
     // Set only project directory (this fixes the workflow separation issue)
     job_info.project_dir = Some(project_dir.clone());
-    // job_info.scratch_dir remains None - set during submission only
+    job_info.depends_on = params.depends_on;
+    job_info.array = params.array;
+    job_info.notify_email = params.notify_email;
+    job_info.notify_events = params.notify_events;
+    job_info.template_snapshot = Some(template);
+    job_info.rendered_config = Some(namd_config_content.clone());
     log_debug!(category: "Job Creation", message: "Set project directory", details: "{}", project_dir);
 
+    // job_info.scratch_dir remains None - set during submission only
+    upload_job_artifacts(connection_manager, &job_info, &username, &namd_config_content, &task_configs, &progress_callback).await?;
+
+    progress_callback("Job creation completed successfully");
+    log_info!(category: "Job Creation", message: "Job created successfully", details: "{}", job_id, show_toast: true);
+
+    Ok((job_id, job_info))
+}
+
+/// Create a job's project directory and its standard subdirectories
+pub(crate) async fn create_project_directories(connection_manager: &ConnectionManager, project_dir: &str) -> Result<()> {
+    log_info!(category: "Job Creation", message: "Creating project directory", details: "{}", project_dir);
+    connection_manager.create_directory(project_dir).await
+        .map_err(|e| {
+            log_error!(category: "Job Creation", message: "Failed to create directory", details: "{}: {}", project_dir, e);
+            anyhow!("Could not create job directory on cluster: {}", e)
+        })?;
+
+    // Create standard job subdirectories
+    for subdir in crate::ssh::JobDirectoryStructure::subdirectories() {
+        let subdir_path = format!("{}/{}", project_dir, subdir);
+        log_debug!(category: "Job Creation", message: "Creating subdirectory", details: "{}", subdir_path);
+        connection_manager.create_directory(&subdir_path).await
+            .map_err(|e| {
+                log_error!(category: "Job Creation", message: "Failed to create subdirectory", details: "{}: {}", subdir_path, e);
+                anyhow!("Failed to create subdirectory '{}': {}", subdir, e)
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Write the batch script and rendered configs into the job's project directory,
+/// then save the job to the database and upload its metadata
+pub(crate) async fn upload_job_artifacts(
+    connection_manager: &ConnectionManager,
+    job_info: &JobInfo,
+    username: &str,
+    namd_config_content: &str,
+    task_configs: &[String],
+    progress_callback: &impl Fn(&str),
+) -> Result<()> {
+    let project_dir = common::require_project_dir(job_info, "Job Creation")?;
+
     progress_callback("Generating SLURM batch script...");
 
     // Generate SLURM script using script generator
     // Pass scratch directory directly (job_info.scratch_dir remains None until submission)
-    let scratch_dir = paths::scratch_directory(&username, &job_id)?;
-    let slurm_script = crate::slurm::script_generator::SlurmScriptGenerator::generate_namd_script(&job_info, &scratch_dir)?;
+    let scratch_dir = paths::scratch_directory(username, &job_info.job_id)?;
+    let slurm_script = crate::slurm::script_generator::SlurmScriptGenerator::generate_namd_script(job_info, &scratch_dir)?;
     log_info!(category: "Job Creation", message: "Generated SLURM script", details: "{} bytes", slurm_script.len());
 
     // Upload script to job root directory
-    let script_path = format!("{}/job.sbatch", project_dir);
+    let script_path = format!("{}/{}", project_dir, crate::ssh::JobDirectoryStructure::SLURM_SCRIPT);
     crate::ssh::metadata::upload_content(connection_manager, &slurm_script, &script_path).await
         .map_err(|e| {
             log_error!(category: "Job Creation", message: "Failed to upload SLURM script", details: "{}", e);
@@ -213,39 +313,96 @@ pub async fn execute_job_creation_with_progress(
     progress_callback("Uploading NAMD configuration...");
 
     // Upload rendered config to job root directory
-    let config_path = format!("{}/config.namd", project_dir);
-    crate::ssh::metadata::upload_content(connection_manager, &namd_config_content, &config_path).await
+    let config_path = format!("{}/{}", project_dir, crate::ssh::JobDirectoryStructure::NAMD_CONFIG);
+    crate::ssh::metadata::upload_content(connection_manager, namd_config_content, &config_path).await
         .map_err(|e| {
             log_error!(category: "Job Creation", message: "Failed to upload NAMD config", details: "{}", e);
             anyhow!("Failed to upload NAMD config: {}", e)
         })?;
     log_debug!(category: "Job Creation", message: "NAMD config uploaded", details: "{}", config_path);
 
+    for (task_id, task_config) in task_configs.iter().enumerate() {
+        progress_callback(&format!("Uploading array task config {} of {}...", task_id + 1, task_configs.len()));
+        let task_config_path = format!("{}/{}", project_dir, crate::ssh::JobDirectoryStructure::array_task_config(&task_id.to_string()));
+        crate::ssh::metadata::upload_content(connection_manager, task_config, &task_config_path).await
+            .map_err(|e| {
+                log_error!(category: "Job Creation", message: "Failed to upload array task config", details: "{}: {}", task_config_path, e);
+                anyhow!("Failed to upload NAMD config for array task {}: {}", task_id, e)
+            })?;
+    }
+
     progress_callback("Saving job to database...");
-    log_debug!(category: "Job Creation", message: "Saving job to database", details: "{}", job_id);
+    log_debug!(category: "Job Creation", message: "Saving job to database", details: "{}", job_info.job_id);
 
     // Save to database using common helper
-    common::save_job_to_database(&job_info, "Job Creation")?;
+    common::save_job_to_database(job_info, "Job Creation")?;
 
     progress_callback("Creating job metadata...");
 
     log_info!(category: "Job Creation", message: "Creating job metadata", details: "{}/job_info.json", project_dir);
-    crate::ssh::metadata::upload_job_metadata(connection_manager, &job_info, &project_dir, "Job Creation").await
+    crate::ssh::metadata::upload_job_metadata(connection_manager, job_info, project_dir, "Job Creation").await
         .map_err(|e| {
             log_error!(category: "Job Creation", message: "Failed to upload job metadata", details: "{}", e);
             anyhow!("Failed to create job metadata: {}", e)
         })?;
     log_debug!(category: "Job Creation", message: "Job metadata created", details: "{}/job_info.json", project_dir);
 
-    progress_callback("Job creation completed successfully");
-    log_info!(category: "Job Creation", message: "Job created successfully", details: "{}", job_id, show_toast: true);
+    Ok(())
+}
 
-    Ok((job_id, job_info))
+/// Check a sweep before anything is created on the cluster
+fn validate_job_array(array: &JobArray, template: &Template) -> Result<()> {
+    if array.values.is_empty() {
+        return Err(anyhow!("Job array needs at least one value for '{}'", array.variable));
+    }
+    if array.values.len() > MAX_ARRAY_TASKS {
+        return Err(anyhow!("Job array has {} tasks; at most {} are allowed", array.values.len(), MAX_ARRAY_TASKS));
+    }
+
+    let var_def = template.variables.get(&array.variable)
+        .ok_or_else(|| anyhow!("Template has no variable '{}' to sweep", array.variable))?;
+    match &var_def.var_type {
+        VariableType::FileUpload { .. } => {
+            Err(anyhow!("File variable '{}' cannot be swept - input files are shared by all tasks", array.variable))
+        }
+        VariableType::Number { min, max, .. } => {
+            for value in &array.values {
+                let number = value.as_f64()
+                    .ok_or_else(|| anyhow!("Sweep value {} for '{}' is not a number", value, array.variable))?;
+                if number < *min || number > *max {
+                    return Err(anyhow!("Sweep value {} for '{}' is outside {} to {}", number, array.variable, min, max));
+                }
+            }
+            Ok(())
+        }
+        VariableType::Select { options, .. } => {
+            for value in &array.values {
+                if !value.as_str().is_some_and(|choice| options.iter().any(|option| option == choice)) {
+                    return Err(anyhow!("Sweep value {} for '{}' is not one of {:?}", value, array.variable, options));
+                }
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Render the NAMD config of every array task, in task ID order
+pub(crate) fn render_array_task_configs(template: &Template, values: &HashMap<String, Value>, array: &JobArray) -> Result<Vec<String>> {
+    let mut task_values = values.clone();
+    array.values.iter().enumerate()
+        .map(|(task_id, value)| {
+            task_values.insert(array.variable.clone(), value.clone());
+            crate::templates::render_template(template, &task_values)
+                .map_err(|e| anyhow!("Failed to render config for array task {}: {}", task_id, e))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_create_job_info_initial_state() {
@@ -260,6 +417,8 @@ mod tests {
                 walltime: "02:00:00".to_string(),
                 partition: "amilan".to_string(),
                 qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
             },
             vec!["structure.pdb".to_string(), "topology.psf".to_string()],
         );
@@ -315,6 +474,8 @@ mod tests {
                 walltime: "01:00:00".to_string(),
                 partition: "amilan".to_string(),
                 qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
             },
             vec![],
         );
@@ -343,6 +504,8 @@ mod tests {
                 walltime: "04:00:00".to_string(),
                 partition: "amilan".to_string(),
                 qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
             },
             vec!["structure.pdb".to_string()],
         );
@@ -350,4 +513,69 @@ mod tests {
         assert_eq!(job_info.template_values, template_values);
         assert_eq!(job_info.template_values.len(), 2);
     }
+
+    fn sweep_template() -> Template {
+        let mut variables = HashMap::new();
+        variables.insert("temperature".to_string(), crate::templates::VariableDefinition {
+            key: "temperature".to_string(),
+            label: "Temperature (K)".to_string(),
+            var_type: VariableType::Number { min: 200.0, max: 400.0, default: 300.0 },
+            help_text: None,
+            visible_when: None,
+            unit: None,
+            render_unit: None,
+            precision: None,
+        });
+        variables.insert("structure_file".to_string(), crate::templates::VariableDefinition {
+            key: "structure_file".to_string(),
+            label: "Structure".to_string(),
+            var_type: VariableType::FileUpload { extensions: vec![".pdb".to_string()] },
+            help_text: None,
+            visible_when: None,
+            unit: None,
+            render_unit: None,
+            precision: None,
+        });
+        Template {
+            id: "sweep".to_string(),
+            name: "Sweep".to_string(),
+            description: String::new(),
+            namd_config_template: "coordinates {{structure_file}}\ntemperature {{temperature}}\n".to_string(),
+            variables,
+            extends: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn sweep(variable: &str, values: Vec<Value>) -> JobArray {
+        JobArray { variable: variable.to_string(), values, task_statuses: vec![] }
+    }
+
+    #[test]
+    fn test_validate_job_array() {
+        let template = sweep_template();
+
+        assert!(validate_job_array(&sweep("temperature", vec![json!(300), json!(310.5)]), &template).is_ok());
+
+        assert!(validate_job_array(&sweep("temperature", vec![]), &template).is_err());
+        assert!(validate_job_array(&sweep("temperature", vec![json!(300); MAX_ARRAY_TASKS + 1]), &template).is_err());
+        assert!(validate_job_array(&sweep("pressure", vec![json!(1)]), &template).is_err());
+        assert!(validate_job_array(&sweep("structure_file", vec![json!("a.pdb")]), &template).is_err());
+        assert!(validate_job_array(&sweep("temperature", vec![json!(500)]), &template).is_err());
+        assert!(validate_job_array(&sweep("temperature", vec![json!("hot")]), &template).is_err());
+    }
+
+    #[test]
+    fn test_render_array_task_configs() {
+        let mut values = HashMap::new();
+        values.insert("structure_file".to_string(), json!("protein.pdb"));
+
+        let configs = render_array_task_configs(&sweep_template(), &values, &sweep("temperature", vec![json!(300), json!(310)])).unwrap();
+
+        assert_eq!(configs, vec![
+            "coordinates input_files/protein.pdb\ntemperature 300\n".to_string(),
+            "coordinates input_files/protein.pdb\ntemperature 310\n".to_string(),
+        ]);
+    }
 }
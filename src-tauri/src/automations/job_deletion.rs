@@ -1,5 +1,7 @@
 use anyhow::{Result, anyhow};
-use crate::{log_info, log_debug};
+use crate::types::JobInfo;
+use crate::types::response_data::{DeletionPreview, DirectoryDeletionPreview};
+use crate::{log_info, log_debug, log_warn};
 use crate::commands::helpers;
 use crate::automations::common;
 use crate::database::with_database;
@@ -20,19 +22,17 @@ pub async fn execute_job_deletion(
     log_info!(category: "Job Deletion", message: "Loaded job", details: "{} ({})", job_info.job_id, job_info.job_name);
 
     // Cancel SLURM job if still active
-    if matches!(job_info.status, crate::types::JobStatus::Pending | crate::types::JobStatus::Running) {
-        if let Some(slurm_job_id) = &job_info.slurm_job_id {
-            progress_callback("Cancelling SLURM job...");
+    if let Some(slurm_job_id) = active_slurm_job(&job_info) {
+        progress_callback("Cancelling SLURM job...");
 
-            let (_connection_manager, username) = common::require_connection_with_username("Job Deletion").await?;
+        let (_connection_manager, username) = common::require_connection_with_username("Job Deletion").await?;
 
-            log_debug!(category: "Job Deletion", message: "Cancelling SLURM job", details: "{}", slurm_job_id);
-            let slurm_sync = crate::slurm::status::SlurmStatusSync::new(&username);
-            slurm_sync.cancel_job(slurm_job_id).await
-                .map_err(|e| anyhow!("Failed to cancel SLURM job {}: {}", slurm_job_id, e))?;
+        log_debug!(category: "Job Deletion", message: "Cancelling SLURM job", details: "{}", slurm_job_id);
+        let slurm_sync = crate::slurm::status::SlurmStatusSync::new(&username);
+        slurm_sync.cancel_job(slurm_job_id).await
+            .map_err(|e| anyhow!("Failed to cancel SLURM job {}: {}", slurm_job_id, e))?;
 
-            log_info!(category: "Job Deletion", message: "Successfully cancelled SLURM job", details: "{}", slurm_job_id);
-        }
+        log_info!(category: "Job Deletion", message: "Successfully cancelled SLURM job", details: "{}", slurm_job_id);
     }
 
     // Delete remote directories if requested
@@ -44,31 +44,9 @@ pub async fn execute_job_deletion(
             return Err(anyhow!("Cannot delete remote files: Not connected to cluster"));
         }
 
-        // Collect directories to delete
-        let mut directories_to_delete = Vec::new();
-
-        if let Some(project_dir) = &job_info.project_dir {
-            directories_to_delete.push(("project", project_dir.clone()));
-        }
-
-        if let Some(scratch_dir) = &job_info.scratch_dir {
-            directories_to_delete.push(("scratch", scratch_dir.clone()));
-        }
-
         // Validate and delete each directory
-        for (dir_type, dir_path) in directories_to_delete {
-            // Safety validation: ensure path is a NAMDRunner directory
-            if !dir_path.contains(crate::ssh::directory_structure::JOB_BASE_DIRECTORY) {
-                return Err(anyhow!(
-                    "Refusing to delete '{}' - not a NAMDRunner job directory",
-                    dir_path
-                ));
-            }
-
-            // Safety validation: no dangerous path patterns
-            if dir_path.contains("..") || dir_path == "/" || dir_path.starts_with("/etc") || dir_path.starts_with("/usr") {
-                return Err(anyhow!("Refusing to delete dangerous directory: {}", dir_path));
-            }
+        for (dir_type, dir_path) in job_directories(&job_info) {
+            validate_deletable_directory(&dir_path)?;
 
             log_debug!(category: "Job Deletion", message: "Deleting directory", details: "{}: {}", dir_type, dir_path);
             connection_manager.delete_directory(&dir_path).await
@@ -93,3 +71,180 @@ pub async fn execute_job_deletion(
     log_info!(category: "Job Deletion", message: "Job deleted successfully", details: "{}", job_id, show_toast: true);
     Ok(())
 }
+
+/// Files listed per directory in a deletion preview; totals still count every file
+const MAX_PREVIEW_FILES: usize = 100;
+
+/// List what deleting a job would remove, without changing anything
+/// Remote listing failures are reported in the preview rather than failing it,
+/// since a database-only deletion doesn't need the cluster
+pub async fn preview_job_deletion(job_id: &str) -> Result<DeletionPreview> {
+    let job_info = helpers::load_job_or_fail(job_id, "Job Deletion")?;
+
+    let mut preview = DeletionPreview {
+        job_id: job_info.job_id.clone(),
+        job_name: job_info.job_name.clone(),
+        cancels_slurm_job: active_slurm_job(&job_info).cloned(),
+        remote_directories: Vec::new(),
+        remote_file_count: 0,
+        remote_bytes: 0,
+        remote_error: None,
+    };
+
+    let directories = job_directories(&job_info);
+    if directories.is_empty() {
+        return Ok(preview);
+    }
+
+    let connection_manager = get_connection_manager();
+    if !connection_manager.is_connected().await {
+        preview.remote_error = Some("Not connected to cluster".to_string());
+        return Ok(preview);
+    }
+
+    for (dir_type, dir_path) in directories {
+        match preview_directory(dir_type, &dir_path).await {
+            Ok(directory) => preview.remote_directories.push(directory),
+            Err(e) => {
+                log_warn!(category: "Job Deletion", message: "Failed to list directory for deletion preview", details: "{}: {}", dir_path, e);
+                preview.remote_error = Some(format!("Failed to list {} directory '{}': {}", dir_type, dir_path, e));
+            }
+        }
+    }
+
+    preview.remote_file_count = preview.remote_directories.iter().map(|dir| dir.file_count).sum();
+    preview.remote_bytes = preview.remote_directories.iter().map(|dir| dir.total_bytes).sum();
+    Ok(preview)
+}
+
+/// Walk one job directory with SFTP listings and total up its files
+async fn preview_directory(dir_type: &str, dir_path: &str) -> Result<DirectoryDeletionPreview> {
+    let mut directory = DirectoryDeletionPreview {
+        kind: dir_type.to_string(),
+        path: dir_path.to_string(),
+        exists: false,
+        file_count: 0,
+        total_bytes: 0,
+        files: Vec::new(),
+        refused: None,
+    };
+
+    if let Err(e) = validate_deletable_directory(dir_path) {
+        directory.refused = Some(e.to_string());
+        return Ok(directory);
+    }
+
+    let connection_manager = get_connection_manager();
+    if !connection_manager.file_exists(dir_path).await? {
+        return Ok(directory);
+    }
+    directory.exists = true;
+
+    let mut files = crate::automations::file_operations::list_remote_files_recursive(connection_manager, dir_path).await?;
+
+    directory.file_count = files.len() as u64;
+    directory.total_bytes = files.iter().map(|file| file.size_bytes).sum();
+    files.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.path.cmp(&b.path)));
+    files.truncate(MAX_PREVIEW_FILES);
+    directory.files = files;
+    Ok(directory)
+}
+
+/// SLURM job that must be cancelled before deletion, if the job is still queued or running
+fn active_slurm_job(job_info: &JobInfo) -> Option<&String> {
+    if matches!(job_info.status, crate::types::JobStatus::Pending | crate::types::JobStatus::Held | crate::types::JobStatus::Running) {
+        job_info.slurm_job_id.as_ref()
+    } else {
+        None
+    }
+}
+
+/// The job's remote directories, labelled for messages
+fn job_directories(job_info: &JobInfo) -> Vec<(&'static str, String)> {
+    let mut directories = Vec::new();
+
+    if let Some(project_dir) = &job_info.project_dir {
+        directories.push(("project", project_dir.clone()));
+    }
+
+    if let Some(scratch_dir) = &job_info.scratch_dir {
+        directories.push(("scratch", scratch_dir.clone()));
+    }
+
+    directories
+}
+
+/// Refuse to `rm -rf` anything that isn't plainly a NAMDRunner job directory
+fn validate_deletable_directory(dir_path: &str) -> Result<()> {
+    // Safety validation: ensure path is a NAMDRunner directory
+    if !dir_path.contains(crate::ssh::directory_structure::JOB_BASE_DIRECTORY) {
+        return Err(anyhow!(
+            "Refusing to delete '{}' - not a NAMDRunner job directory",
+            dir_path
+        ));
+    }
+
+    // Safety validation: no dangerous path patterns
+    if dir_path.contains("..") || dir_path == "/" || dir_path.starts_with("/etc") || dir_path.starts_with("/usr") {
+        return Err(anyhow!("Refusing to delete dangerous directory: {}", dir_path));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::JobStatus;
+
+    fn job(status: JobStatus) -> JobInfo {
+        let mut job = crate::automations::job_creation::create_job_info(
+            "test_job_1".to_string(),
+            "test_job".to_string(),
+            String::new(),
+            std::collections::HashMap::new(),
+            crate::types::SlurmConfig {
+                cores: 4,
+                memory: "4GB".to_string(),
+                walltime: "01:00:00".to_string(),
+                partition: "amilan".to_string(),
+                qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
+            },
+            Vec::new(),
+        );
+        job.status = status;
+        job.slurm_job_id = Some("12345".to_string());
+        job
+    }
+
+    #[test]
+    fn test_active_slurm_job() {
+        assert_eq!(active_slurm_job(&job(JobStatus::Running)).map(String::as_str), Some("12345"));
+        assert_eq!(active_slurm_job(&job(JobStatus::Held)).map(String::as_str), Some("12345"));
+        assert!(active_slurm_job(&job(JobStatus::Completed)).is_none());
+        assert!(active_slurm_job(&job(JobStatus::Created)).is_none());
+    }
+
+    #[test]
+    fn test_job_directories() {
+        let mut job = job(JobStatus::Completed);
+        assert!(job_directories(&job).is_empty());
+
+        job.project_dir = Some("/projects/user/namdrunner_jobs/test_job_1".to_string());
+        job.scratch_dir = Some("/scratch/alpine/user/namdrunner_jobs/test_job_1".to_string());
+        let directories = job_directories(&job);
+        assert_eq!(directories.len(), 2);
+        assert_eq!(directories[0].0, "project");
+        assert_eq!(directories[1].0, "scratch");
+    }
+
+    #[test]
+    fn test_validate_deletable_directory() {
+        assert!(validate_deletable_directory("/projects/user/namdrunner_jobs/test_job_1").is_ok());
+        assert!(validate_deletable_directory("/projects/user/other").is_err());
+        assert!(validate_deletable_directory("/projects/user/namdrunner_jobs/../..").is_err());
+        assert!(validate_deletable_directory("/etc/namdrunner_jobs").is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use serde::Serialize;
+use crate::types::{JobInfo, ExportFormat};
+
+/// One exported job. Field order is the CSV column order - append new columns at the end
+/// so spreadsheets built on earlier exports keep lining up
+#[derive(Debug, Serialize)]
+pub struct JobExportRow {
+    pub job_id: String,
+    pub job_name: String,
+    pub status: String,
+    pub template_id: String,
+    pub slurm_job_id: Option<String>,
+    pub partition: String,
+    pub qos: String,
+    pub cores: u32,
+    pub memory: String,
+    pub walltime: String,
+    pub gpus: u32,
+    /// Estimated SUs from the cluster billing rates; None unless costs were requested
+    pub estimated_cost: Option<u32>,
+    pub created_at: String,
+    pub submitted_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub archived: bool,
+    /// Joined with ';' in CSV
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+    /// SUs used, recorded from sacct when the job finished; separate from the estimate
+    pub actual_cost: Option<f64>,
+}
+
+pub const EXPORT_COLUMNS: &[&str] = &[
+    "job_id", "job_name", "status", "template_id", "slurm_job_id", "partition", "qos",
+    "cores", "memory", "walltime", "gpus", "estimated_cost", "created_at", "submitted_at",
+    "completed_at", "archived", "tags", "notes", "actual_cost",
+];
+
+impl JobExportRow {
+    pub fn new(job: &JobInfo, estimated_cost: Option<u32>) -> Self {
+        let config = &job.slurm_config;
+        Self {
+            job_id: job.job_id.clone(),
+            job_name: job.job_name.clone(),
+            // Same spelling as the stored record ("COMPLETED"), not the Debug name
+            status: serde_json::to_value(&job.status).ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            template_id: job.template_id.clone(),
+            slurm_job_id: job.slurm_job_id.clone(),
+            partition: config.partition.clone(),
+            qos: config.qos.clone(),
+            cores: config.cores,
+            memory: config.memory.clone(),
+            walltime: config.walltime.clone(),
+            gpus: config.gpu_count(),
+            estimated_cost,
+            created_at: job.created_at.clone(),
+            submitted_at: job.submitted_at.clone(),
+            completed_at: job.completed_at.clone(),
+            archived: job.archived,
+            tags: job.tags.clone(),
+            notes: job.notes.clone(),
+            actual_cost: job.actual_cost,
+        }
+    }
+
+    fn csv_cells(&self) -> Vec<String> {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        vec![
+            self.job_id.clone(),
+            self.job_name.clone(),
+            self.status.clone(),
+            self.template_id.clone(),
+            optional(&self.slurm_job_id),
+            self.partition.clone(),
+            self.qos.clone(),
+            self.cores.to_string(),
+            self.memory.clone(),
+            self.walltime.clone(),
+            self.gpus.to_string(),
+            self.estimated_cost.map(|cost| cost.to_string()).unwrap_or_default(),
+            self.created_at.clone(),
+            optional(&self.submitted_at),
+            optional(&self.completed_at),
+            self.archived.to_string(),
+            self.tags.join(";"),
+            optional(&self.notes),
+            self.actual_cost.map(|cost| format!("{:.2}", cost)).unwrap_or_default(),
+        ]
+    }
+}
+
+/// Render jobs as CSV (header row first) or a pretty-printed JSON array
+pub fn render_job_export(rows: &[JobExportRow], format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(rows)?),
+        ExportFormat::Csv => {
+            let mut csv = csv_line(EXPORT_COLUMNS.iter().map(|column| column.to_string()));
+            for row in rows {
+                csv.push_str(&csv_line(row.csv_cells()));
+            }
+            Ok(csv)
+        }
+    }
+}
+
+fn csv_line(cells: impl IntoIterator<Item = String>) -> String {
+    let cells: Vec<String> = cells.into_iter().map(|cell| csv_escape(&cell)).collect();
+    format!("{}\r\n", cells.join(","))
+}
+
+/// Quote a cell when it holds a comma, quote or line break, doubling any quotes (RFC 4180)
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{JobStatus, SlurmConfig};
+    use std::collections::HashMap;
+
+    fn job() -> JobInfo {
+        let config = SlurmConfig {
+            cores: 48,
+            memory: "64GB".to_string(),
+            walltime: "24:00:00".to_string(),
+            partition: "amilan".to_string(),
+            qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
+        };
+        let mut job = crate::automations::job_creation::create_job_info(
+            "job_001".to_string(), "equilibration".to_string(), "vacuum_optimization_v1".to_string(),
+            HashMap::new(), config, vec![],
+        );
+        job.status = JobStatus::Completed;
+        job.created_at = "2025-01-15T10:30:00Z".to_string();
+        job
+    }
+
+    #[test]
+    fn test_csv_columns_and_empty_optional_fields() {
+        let rows = [JobExportRow::new(&job(), None)];
+        let csv = render_job_export(&rows, ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(lines[0], EXPORT_COLUMNS.join(","));
+        assert_eq!(
+            lines[1],
+            "job_001,equilibration,COMPLETED,vacuum_optimization_v1,,amilan,normal,48,64GB,24:00:00,0,,2025-01-15T10:30:00Z,,,false,,,"
+        );
+        assert_eq!(lines[1].split(',').count(), EXPORT_COLUMNS.len());
+        assert_eq!(lines[2], "");
+    }
+
+    #[test]
+    fn test_csv_escaping() {
+        let mut job = job();
+        job.tags = vec!["membrane".to_string(), "prod".to_string()];
+        job.notes = Some("Restarted, see \"run 2\"\nthen finished".to_string());
+        job.actual_cost = Some(640.0 / 3.0);
+        let rows = [JobExportRow::new(&job, Some(1152))];
+        let csv = render_job_export(&rows, ExportFormat::Csv).unwrap();
+
+        assert!(csv.ends_with(",1152,2025-01-15T10:30:00Z,,,false,membrane;prod,\"Restarted, see \"\"run 2\"\"\nthen finished\",213.33\r\n"), "{}", csv);
+        assert_eq!(csv_escape("plain"), "plain");
+    }
+
+    #[test]
+    fn test_json_export() {
+        let rows = [JobExportRow::new(&job(), Some(1152))];
+        let json = render_job_export(&rows, ExportFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value[0]["job_id"], "job_001");
+        assert_eq!(value[0]["status"], "COMPLETED");
+        assert_eq!(value[0]["estimated_cost"], 1152);
+        assert!(value[0]["submitted_at"].is_null());
+        let keys: Vec<&String> = value[0].as_object().unwrap().keys().collect();
+        assert_eq!(keys.len(), EXPORT_COLUMNS.len());
+    }
+}
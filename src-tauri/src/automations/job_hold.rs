@@ -0,0 +1,74 @@
+use anyhow::{Result, anyhow};
+use crate::types::{JobInfo, JobStatus};
+use crate::{log_info, log_debug};
+use crate::commands::helpers;
+use crate::automations::common;
+
+/// Hold a pending job with scontrol hold so it stays queued without starting
+pub async fn execute_job_hold(job_id: String) -> Result<JobInfo> {
+    change_hold(job_id, true).await
+}
+
+/// Release a held job with scontrol release so the scheduler can start it again
+pub async fn execute_job_release(job_id: String) -> Result<JobInfo> {
+    change_hold(job_id, false).await
+}
+
+async fn change_hold(job_id: String, hold: bool) -> Result<JobInfo> {
+    let category = if hold { "Job Hold" } else { "Job Release" };
+    let mut job = helpers::load_job_or_fail(&job_id, category)?;
+
+    let slurm_job_id = job.slurm_job_id.clone()
+        .ok_or_else(|| anyhow!("Job {} has no SLURM job ID - it was never submitted", job.job_id))?;
+    let new_status = hold_transition(&job.status, hold)
+        .map_err(|e| anyhow!("Job {}: {}", job.job_id, e))?;
+
+    let (_connection_manager, username) = common::require_connection_with_username(category).await?;
+    let slurm_sync = crate::slurm::status::SlurmStatusSync::new(&username);
+
+    log_debug!(category: category, message: "Updating SLURM hold", details: "{} (SLURM {})", job.job_id, slurm_job_id);
+    if hold {
+        slurm_sync.hold_job(&slurm_job_id).await?;
+    } else {
+        slurm_sync.release_job(&slurm_job_id).await?;
+    }
+
+    common::update_job_status(&mut job, new_status);
+    // Any queue detail from before no longer applies; the next sync fills in a fresh one
+    job.status_detail = None;
+    common::save_job_to_database(&job, category)?;
+
+    let message = if hold { "Job held" } else { "Job released" };
+    log_info!(category: category, message: message, details: "{}", job.job_id, show_toast: true);
+    Ok(job)
+}
+
+/// Status after a hold (Pending -> Held) or release (Held -> Pending)
+/// Running jobs can't be held - SLURM only holds jobs that haven't started
+fn hold_transition(status: &JobStatus, hold: bool) -> Result<JobStatus> {
+    match (status, hold) {
+        (JobStatus::Pending, true) => Ok(JobStatus::Held),
+        (JobStatus::Held, false) => Ok(JobStatus::Pending),
+        (_, true) => Err(anyhow!("only pending jobs can be held (status {:?})", status)),
+        (_, false) => Err(anyhow!("job is not held (status {:?})", status)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hold_and_release_transitions() {
+        assert_eq!(hold_transition(&JobStatus::Pending, true).unwrap(), JobStatus::Held);
+        assert_eq!(hold_transition(&JobStatus::Held, false).unwrap(), JobStatus::Pending);
+    }
+
+    #[test]
+    fn test_invalid_transitions() {
+        assert!(hold_transition(&JobStatus::Running, true).is_err());
+        assert!(hold_transition(&JobStatus::Held, true).is_err());
+        assert!(hold_transition(&JobStatus::Pending, false).is_err());
+        assert!(hold_transition(&JobStatus::Completed, false).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use anyhow::{Result, anyhow};
+
+use crate::types::{JobArray, JobInfo};
+use crate::ssh::paths;
+use crate::{log_info, log_debug, log_error};
+use crate::automations::{common, file_operations, job_creation};
+
+/// Clone a job into a new Created job: same template, values, SLURM config and inputs.
+/// Inputs are copied cluster-side from the source project, so nothing is re-uploaded.
+/// The source job is only read.
+pub async fn execute_job_rerun(
+    source_job_id: &str,
+    progress_callback: impl Fn(&str),
+) -> Result<JobInfo> {
+    progress_callback("Loading source job...");
+    log_info!(category: "Job Rerun", message: "Starting job rerun", details: "Source: {}", source_job_id);
+
+    let source = crate::commands::helpers::load_job_or_fail(source_job_id, "Job Rerun")?;
+    let source_project_dir = common::require_project_dir(&source, "Job Rerun")?;
+
+    progress_callback("Validating connection...");
+    let (connection_manager, username) = common::require_connection_with_username("Job Rerun").await?;
+
+    progress_callback("Generating job paths...");
+    let job_id = common::generate_job_id(&source.job_name)?;
+    let project_dir = paths::project_directory(&username, &job_id)?;
+    log_info!(category: "Job Rerun", message: "Generated job ID", details: "{} at path: {}", job_id, project_dir);
+
+    progress_callback("Checking directory permissions...");
+    crate::ssh::permissions::ensure_project_writable(connection_manager, &username).await?;
+
+    // Render before touching the cluster so a deleted template without a snapshot fails cleanly
+    progress_callback("Rendering template...");
+    let (namd_config_content, task_configs, template_snapshot) = if source.template_id.is_empty() {
+        // Adopted jobs have no template; their staged config is the only source
+        let source_config = format!("{}/{}", source_project_dir, crate::ssh::JobDirectoryStructure::NAMD_CONFIG);
+        let content = connection_manager.read_remote_file(&source_config).await
+            .map_err(|e| anyhow!("Could not read source job config {}: {}", source_config, e))?;
+        (content, Vec::new(), None)
+    } else {
+        let template = crate::commands::helpers::load_job_template_or_fail(&source, "Job Rerun")?;
+
+        // Stored values already hold input filenames and, for sweeps, task 0's value
+        let task_configs = match &source.array {
+            Some(array) => job_creation::render_array_task_configs(&template, &source.template_values, array)?,
+            None => Vec::new(),
+        };
+        (crate::templates::render_template(&template, &source.template_values)?, task_configs, Some(template))
+    };
+
+    let source_inputs = format!("{}/{}", source_project_dir, crate::ssh::JobDirectoryStructure::INPUT_FILES);
+    progress_callback("Checking disk space...");
+    file_operations::ensure_space_for_directory(&source_inputs, &project_dir, false).await?;
+
+    progress_callback("Creating project directories...");
+    job_creation::create_project_directories(connection_manager, &project_dir).await?;
+
+    progress_callback("Copying input files...");
+    let destination_inputs = format!("{}/{}", project_dir, crate::ssh::JobDirectoryStructure::INPUT_FILES);
+    let result = connection_manager.mirror_directory(&common::ensure_trailing_slash(&source_inputs), &destination_inputs).await?;
+    if result.exit_code != 0 {
+        log_error!(category: "Job Rerun", message: "Failed to copy input files", details: "{}: {}", source_inputs, result.stderr);
+        return Err(anyhow!("Could not copy input files from {}: {}", source.job_id, result.stderr.trim()));
+    }
+    log_debug!(category: "Job Rerun", message: "Copied input files", details: "{} -> {}", source_inputs, destination_inputs);
+
+    let mut job_info = rerun_job_info(&source, job_id);
+    job_info.project_dir = Some(project_dir);
+    job_info.template_snapshot = template_snapshot;
+    job_info.rendered_config = Some(namd_config_content.clone());
+
+    job_creation::upload_job_artifacts(connection_manager, &job_info, &username, &namd_config_content, &task_configs, &progress_callback).await?;
+
+    progress_callback("Job rerun created successfully");
+    log_info!(category: "Job Rerun", message: "Created rerun job", details: "{} from {}", job_info.job_id, source.job_id, show_toast: true);
+
+    Ok(job_info)
+}
+
+/// Build the new job from the source's configuration, with fresh state.
+/// Run state (SLURM ID, logs, outputs, cost) and dependencies on earlier jobs are not carried over.
+fn rerun_job_info(source: &JobInfo, job_id: String) -> JobInfo {
+    let mut job_info = job_creation::create_job_info(
+        job_id,
+        source.job_name.clone(),
+        source.template_id.clone(),
+        source.template_values.clone(),
+        source.slurm_config.clone(),
+        source.input_files.clone(),
+    );
+
+    job_info.array = source.array.as_ref().map(|array| JobArray { task_statuses: Vec::new(), ..array.clone() });
+    job_info.notify_email = source.notify_email.clone();
+    job_info.notify_events = source.notify_events.clone();
+    job_info.tags = source.tags.clone();
+    job_info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ArrayTaskStatus, JobStatus, OutputFile, SlurmConfig};
+    use std::collections::HashMap;
+
+    fn finished_job() -> JobInfo {
+        let mut values = HashMap::new();
+        values.insert("structure_file".to_string(), serde_json::json!("hextube.psf"));
+        let mut job = job_creation::create_job_info(
+            "run_1700000000000".to_string(),
+            "run".to_string(),
+            "vacuum_optimization_v1".to_string(),
+            values,
+            SlurmConfig {
+                cores: 24,
+                memory: "16GB".to_string(),
+                walltime: "04:00:00".to_string(),
+                partition: "amilan".to_string(),
+                qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
+            },
+            vec!["hextube.psf".to_string()],
+        );
+        job.status = JobStatus::Completed;
+        job.created_at = "2024-01-01T00:00:00Z".to_string();
+        job.submitted_at = Some("2024-01-01T00:01:00Z".to_string());
+        job.completed_at = Some("2024-01-01T04:00:00Z".to_string());
+        job.slurm_job_id = Some("12345".to_string());
+        job.project_dir = Some("/projects/user/namdrunner_jobs/run_1700000000000".to_string());
+        job.scratch_dir = Some("/scratch/user/namdrunner_jobs/run_1700000000000".to_string());
+        job.slurm_stdout = Some("NAMD output".to_string());
+        job.slurm_stderr = Some("warning".to_string());
+        job.output_files = vec![OutputFile { name: "output.dcd".to_string(), size: 1024, modified_at: "2024-01-01T04:00:00Z".to_string() }];
+        job.actual_cost = Some(96.0);
+        job.depends_on = Some(vec!["equil_1690000000000".to_string()]);
+        job.pinned = true;
+        job.notes = Some("first attempt".to_string());
+        job.tags = vec!["hextube".to_string()];
+        job.notify_email = Some("user@example.edu".to_string());
+        job
+    }
+
+    #[test]
+    fn test_rerun_job_info_copies_configuration() {
+        let source = finished_job();
+        let job = rerun_job_info(&source, "run_1800000000000".to_string());
+
+        assert_eq!(job.job_id, "run_1800000000000");
+        assert_eq!(job.job_name, source.job_name);
+        assert_eq!(job.template_id, source.template_id);
+        assert_eq!(job.template_values, source.template_values);
+        assert_eq!(job.slurm_config.cores, 24);
+        assert_eq!(job.slurm_config.walltime, "04:00:00");
+        assert_eq!(job.input_files, source.input_files);
+        assert_eq!(job.tags, source.tags);
+        assert_eq!(job.notify_email, source.notify_email);
+    }
+
+    #[test]
+    fn test_rerun_job_info_starts_fresh() {
+        let source = finished_job();
+        let job = rerun_job_info(&source, "run_1800000000000".to_string());
+
+        assert_eq!(job.status, JobStatus::Created);
+        assert_ne!(job.created_at, source.created_at);
+        assert!(job.submitted_at.is_none());
+        assert!(job.completed_at.is_none());
+        assert!(job.slurm_job_id.is_none());
+        assert!(job.project_dir.is_none());
+        assert!(job.scratch_dir.is_none());
+        assert!(job.slurm_stdout.is_none());
+        assert!(job.slurm_stderr.is_none());
+        assert!(job.output_files.is_empty());
+        assert!(job.actual_cost.is_none());
+        assert!(job.depends_on.is_none());
+        assert!(!job.pinned);
+        assert!(job.notes.is_none());
+    }
+
+    #[test]
+    fn test_rerun_job_info_resets_array_task_statuses() {
+        let mut source = finished_job();
+        source.array = Some(JobArray {
+            variable: "temperature".to_string(),
+            values: vec![serde_json::json!(300), serde_json::json!(310)],
+            task_statuses: vec![ArrayTaskStatus { task_id: 0, status: JobStatus::Completed }],
+        });
+
+        let job = rerun_job_info(&source, "run_1800000000000".to_string());
+        let array = job.array.expect("array copied");
+        assert_eq!(array.variable, "temperature");
+        assert_eq!(array.values.len(), 2);
+        assert!(array.task_statuses.is_empty());
+    }
+}
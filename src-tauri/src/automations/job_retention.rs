@@ -0,0 +1,259 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::types::{JobInfo, JobStatus};
+use crate::database::with_database;
+use crate::{log_info, log_debug, log_error};
+
+/// Settings key for the retention policy (number of days, absent = disabled)
+pub const RETENTION_DAYS_SETTING: &str = "job_retention_days";
+
+/// Upper bound accepted for the retention setting (10 years)
+pub const MAX_RETENTION_DAYS: u32 = 3650;
+
+/// How often the retention pass re-runs while the app stays open
+const RETENTION_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
+/// Load the configured retention age in days (None = auto-archive disabled)
+pub fn load_retention_days() -> Result<Option<u32>> {
+    with_database(|db| db.load_setting::<u32>(RETENTION_DAYS_SETTING))
+}
+
+/// Persist the retention age in days (None disables auto-archive)
+pub fn save_retention_days(days: Option<u32>) -> Result<()> {
+    if let Some(days) = days {
+        if days == 0 || days > MAX_RETENTION_DAYS {
+            return Err(anyhow!("Retention must be between 1 and {} days", MAX_RETENTION_DAYS));
+        }
+    }
+
+    with_database(|db| db.save_setting(RETENTION_DAYS_SETTING, &days))
+}
+
+/// Select terminal jobs that finished more than `max_age` ago
+///
+/// Pinned and already-archived jobs are never selected. Age is measured from
+/// completed_at, falling back to updated_at for jobs imported without one.
+/// Jobs whose timestamps can't be parsed are left alone rather than guessed at.
+pub fn select_jobs_to_archive(jobs: &[JobInfo], now: DateTime<Utc>, max_age: Duration) -> Vec<String> {
+    jobs.iter()
+        .filter(|job| !job.archived && !job.pinned)
+        .filter(|job| matches!(job.status, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled))
+        .filter(|job| {
+            job.completed_at.as_deref()
+                .or(job.updated_at.as_deref())
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                .is_some_and(|finished| now.signed_duration_since(finished) >= max_age)
+        })
+        .map(|job| job.job_id.clone())
+        .collect()
+}
+
+/// Archive a job the user chose to hide
+/// Only finished jobs: a queued or running job hidden from the list would keep using allocation unseen
+pub fn archive_finished_job(job: &mut JobInfo) -> Result<()> {
+    if !matches!(job.status, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled) {
+        return Err(anyhow!("Only finished jobs can be archived - job {} is {:?}", job.job_id, job.status));
+    }
+    job.archived = true;
+    Ok(())
+}
+
+/// Un-archive a job the user restored; returns true if it had to be pinned
+///
+/// A restored job old enough for the retention policy would be archived again on the
+/// next pass, so it is pinned to keep it where the user put it.
+pub fn restore_archived_job(job: &mut JobInfo, retention_days: Option<u32>, now: DateTime<Utc>) -> bool {
+    job.archived = false;
+
+    let would_rearchive = retention_days.is_some_and(|days| {
+        !select_jobs_to_archive(std::slice::from_ref(job), now, Duration::days(days as i64)).is_empty()
+    });
+    if would_rearchive {
+        job.pinned = true;
+    }
+    would_rearchive
+}
+
+/// Archive terminal jobs older than the configured retention age
+///
+/// Returns the IDs of the jobs archived. Does nothing when no retention is configured.
+pub fn run_retention_pass() -> Result<Vec<String>> {
+    let Some(days) = load_retention_days()? else {
+        log_debug!(category: "Job Retention", message: "No retention policy configured - skipping");
+        return Ok(Vec::new());
+    };
+
+    let jobs = with_database(|db| db.load_all_jobs())?.jobs;
+    let to_archive = select_jobs_to_archive(&jobs, Utc::now(), Duration::days(days as i64));
+
+    for mut job in jobs.into_iter().filter(|job| to_archive.contains(&job.job_id)) {
+        job.archived = true;
+        with_database(|db| db.save_job(&job))?;
+        log_info!(category: "Job Retention", message: "Archived job", details: "{} ({}) - finished over {} days ago", job.job_id, job.job_name, days);
+    }
+
+    if !to_archive.is_empty() {
+        log_info!(
+            category: "Job Retention",
+            message: "Auto-archived old jobs",
+            details: "{} jobs older than {} days", to_archive.len(), days,
+            show_toast: true
+        );
+    }
+
+    Ok(to_archive)
+}
+
+/// Start the background retention task
+/// Runs once at startup and then every RETENTION_CHECK_INTERVAL
+pub fn start_retention_task() {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(RETENTION_CHECK_INTERVAL);
+
+        loop {
+            // First tick completes immediately, giving the startup pass
+            interval.tick().await;
+
+            if let Err(e) = run_retention_pass() {
+                log_error!(category: "Job Retention", message: "Retention pass failed", details: "{}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SlurmConfig;
+    use std::collections::HashMap;
+
+    fn job(id: &str, status: JobStatus, completed_days_ago: Option<i64>, now: DateTime<Utc>) -> JobInfo {
+        JobInfo {
+            job_id: id.to_string(),
+            job_name: id.to_string(),
+            status,
+            slurm_job_id: None,
+            created_at: (now - Duration::days(365)).to_rfc3339(),
+            updated_at: None,
+            submitted_at: None,
+            completed_at: completed_days_ago.map(|d| (now - Duration::days(d)).to_rfc3339()),
+            project_dir: None,
+            scratch_dir: None,
+            error_info: None,
+            slurm_stdout: None,
+            slurm_stderr: None,
+            template_id: "test".to_string(),
+            template_values: HashMap::new(),
+            slurm_config: SlurmConfig::default(),
+            input_files: vec![],
+            output_files: vec![],
+            archived: false,
+            pinned: false,
+            tags: Vec::new(),
+            notes: None,
+            status_detail: None,
+            submitted_script: None,
+            template_snapshot: None,
+            rendered_config: None,
+            depends_on: None,
+            array: None,
+            notify_email: None,
+            notify_events: Vec::new(),
+            actual_cost: None,
+            namd_progress: None,
+            estimated_start: None,
+            queue_priority: None,
+        }
+    }
+
+    #[test]
+    fn test_selects_terminal_jobs_past_threshold() {
+        let now = Utc::now();
+        let jobs = vec![
+            job("old_completed", JobStatus::Completed, Some(45), now),
+            job("old_failed", JobStatus::Failed, Some(31), now),
+            job("old_cancelled", JobStatus::Cancelled, Some(30), now),
+            job("recent_completed", JobStatus::Completed, Some(5), now),
+        ];
+
+        let selected = select_jobs_to_archive(&jobs, now, Duration::days(30));
+
+        assert_eq!(selected, vec!["old_completed", "old_failed", "old_cancelled"]);
+    }
+
+    #[test]
+    fn test_never_selects_active_jobs() {
+        let now = Utc::now();
+        let mut running = job("running", JobStatus::Running, None, now);
+        running.updated_at = Some((now - Duration::days(90)).to_rfc3339());
+        let jobs = vec![
+            running,
+            job("pending", JobStatus::Pending, None, now),
+            job("created", JobStatus::Created, None, now),
+        ];
+
+        assert!(select_jobs_to_archive(&jobs, now, Duration::days(30)).is_empty());
+    }
+
+    #[test]
+    fn test_pinned_jobs_are_exempt() {
+        let now = Utc::now();
+        let mut pinned = job("pinned", JobStatus::Completed, Some(400), now);
+        pinned.pinned = true;
+        let jobs = vec![pinned, job("unpinned", JobStatus::Completed, Some(400), now)];
+
+        assert_eq!(select_jobs_to_archive(&jobs, now, Duration::days(30)), vec!["unpinned"]);
+    }
+
+    #[test]
+    fn test_skips_archived_and_undated_jobs() {
+        let now = Utc::now();
+        let mut archived = job("archived", JobStatus::Completed, Some(100), now);
+        archived.archived = true;
+        let mut updated_only = job("updated_only", JobStatus::Failed, None, now);
+        updated_only.updated_at = Some((now - Duration::days(60)).to_rfc3339());
+        let mut bad_timestamp = job("bad_timestamp", JobStatus::Completed, None, now);
+        bad_timestamp.completed_at = Some("not-a-date".to_string());
+        let jobs = vec![archived, updated_only, bad_timestamp, job("no_dates", JobStatus::Completed, None, now)];
+
+        // Falls back to updated_at; missing or unparseable dates are left alone
+        assert_eq!(select_jobs_to_archive(&jobs, now, Duration::days(30)), vec!["updated_only"]);
+    }
+
+    #[test]
+    fn test_archive_only_finished_jobs() {
+        let now = Utc::now();
+        for status in [JobStatus::Completed, JobStatus::Failed, JobStatus::Cancelled] {
+            let mut finished = job("finished", status, Some(1), now);
+            assert!(archive_finished_job(&mut finished).is_ok());
+            assert!(finished.archived);
+        }
+        for status in [JobStatus::Created, JobStatus::Pending, JobStatus::Held, JobStatus::Running] {
+            let mut active = job("active", status, None, now);
+            assert!(archive_finished_job(&mut active).is_err());
+            assert!(!active.archived);
+        }
+    }
+
+    #[test]
+    fn test_restore_pins_only_when_retention_would_rearchive() {
+        let now = Utc::now();
+        let mut old = job("old", JobStatus::Completed, Some(100), now);
+        old.archived = true;
+        assert!(restore_archived_job(&mut old, Some(30), now));
+        assert!(!old.archived);
+        assert!(old.pinned);
+
+        let mut recent = job("recent", JobStatus::Completed, Some(5), now);
+        recent.archived = true;
+        assert!(!restore_archived_job(&mut recent, Some(30), now));
+        assert!(!recent.archived && !recent.pinned);
+
+        // No policy, nothing to protect against
+        let mut no_policy = job("no_policy", JobStatus::Completed, Some(100), now);
+        no_policy.archived = true;
+        assert!(!restore_archived_job(&mut no_policy, None, now));
+        assert!(!no_policy.archived && !no_policy.pinned);
+    }
+}
@@ -1,11 +1,14 @@
 use anyhow::{Result, anyhow};
 use chrono::Utc;
 
-use crate::types::{JobInfo, JobStatus};
-use crate::ssh::paths;
+use std::collections::HashMap;
+
+use crate::types::{BatchSubmitFailure, BatchSubmitProgress, BatchSubmitResult, JobInfo, JobStatus};
+use crate::types::response_data::DryRunResult;
+use crate::ssh::{paths, RemoteExecutor};
 use crate::database::with_database;
-use crate::{log_info, log_debug, log_error};
-use crate::automations::common;
+use crate::{log_info, log_debug, log_error, log_warn};
+use crate::automations::{common, file_operations};
 
 /// Validate that a job is in a valid state for submission
 /// Returns Ok(()) if valid, Err with descriptive message if invalid
@@ -19,6 +22,51 @@ pub fn validate_job_submission_state(job: &JobInfo) -> Result<()> {
     Ok(())
 }
 
+/// SLURM job IDs to pass to `--dependency=afterok` for a job's dependencies
+/// Dependencies that already completed are satisfied and left out; ones that were never
+/// submitted, or that failed or were cancelled (afterok could never be met), are errors
+pub fn dependency_slurm_ids(job_id: &str, dependencies: &[JobInfo]) -> Result<Vec<String>> {
+    let mut slurm_ids = Vec::new();
+    for dependency in dependencies {
+        match (&dependency.status, &dependency.slurm_job_id) {
+            (JobStatus::Completed, _) => {}
+            (JobStatus::Failed | JobStatus::Cancelled, _) => {
+                return Err(anyhow!(
+                    "Job {} depends on {}, which is {:?} - it would never start",
+                    job_id, dependency.job_id, dependency.status
+                ));
+            }
+            (_, Some(slurm_job_id)) => slurm_ids.push(slurm_job_id.clone()),
+            (_, None) => {
+                return Err(anyhow!(
+                    "Job {} depends on {}, which has not been submitted yet. Submit {} first",
+                    job_id, dependency.job_id, dependency.job_id
+                ));
+            }
+        }
+    }
+    Ok(slurm_ids)
+}
+
+/// Record a successful submission on the job, including the exact script sbatch ran
+pub fn record_submission(job: &mut JobInfo, scratch_dir: &str, slurm_job_id: &str, submitted_at: &str, submitted_script: String) {
+    job.scratch_dir = Some(scratch_dir.to_string());
+    job.slurm_job_id = Some(slurm_job_id.to_string());
+    job.submitted_at = Some(submitted_at.to_string());
+    job.submitted_script = Some(submitted_script);
+    common::update_job_status(job, JobStatus::Pending);
+}
+
+/// The SLURM script as mirrored to scratch - what sbatch will actually run, user edits included
+pub async fn read_submitted_script(remote: &dyn RemoteExecutor, scratch_dir: &str) -> Result<String> {
+    let script_path = format!("{}/{}", scratch_dir, crate::ssh::JobDirectoryStructure::SLURM_SCRIPT);
+    remote.read_remote_file(&script_path).await
+        .map_err(|e| {
+            log_error!(category: "Job Submission", message: "Failed to read SLURM script", details: "{}: {}", script_path, e);
+            anyhow!("Failed to read SLURM script before submission: {}", e)
+        })
+}
+
 /// Simplified job submission automation that follows NAMDRunner's direct function patterns.
 /// Provides progress reporting through callbacks.
 ///
@@ -26,6 +74,7 @@ pub fn validate_job_submission_state(job: &JobInfo) -> Result<()> {
 /// submits to SLURM, and updates job status. This maintains proper workflow separation.
 pub async fn execute_job_submission_with_progress(
     job_id: String,
+    ignore_space_check: bool,
     progress_callback: impl Fn(&str),
 ) -> Result<JobInfo> {
     progress_callback("Loading job information...");
@@ -49,18 +98,46 @@ pub async fn execute_job_submission_with_progress(
         log_error!(category: "Job Submission", message: "Job cannot be submitted", details: "{} - status: {:?}", job_id, job_info.status);
     })?;
 
+    // Resolve dependencies to SLURM IDs before anything is copied to scratch
+    let dependencies = job_info.depends_on.iter().flatten()
+        .map(|dependency| crate::commands::helpers::load_job_or_fail(dependency, "Job Submission"))
+        .collect::<Result<Vec<_>>>()?;
+    let dependency_ids = dependency_slurm_ids(&job_info.job_id, &dependencies)?;
+    if !dependency_ids.is_empty() {
+        log_info!(category: "Job Submission", message: "Job has dependencies", details: "{} after SLURM jobs {}", job_id, dependency_ids.join(", "));
+    }
+
     progress_callback("Validating connection...");
 
     // Verify SSH connection and get username
     let (connection_manager, username) = common::require_connection_with_username("Job Submission").await?;
     log_info!(category: "Job Submission", message: "Submitting job for user", details: "{}", username);
 
-    progress_callback("Mirroring job directory to scratch...");
-
     let project_dir = common::require_project_dir(&job_info, "Job Submission")?;
 
     // Generate scratch directory path using existing validation functions
     let scratch_dir = paths::scratch_directory(&username, &job_info.job_id)?;
+
+    progress_callback("Validating NAMD configuration...");
+
+    // Physics errors (e.g. a 4 fs timestep without rigidBonds) block submission; warnings are only logged
+    let config_path = format!("{}/{}", project_dir, crate::ssh::JobDirectoryStructure::NAMD_CONFIG);
+    let namd_config = connection_manager.read_remote_file(&config_path).await
+        .map_err(|e| anyhow!("Failed to read NAMD config before submission: {}", e))?;
+    let namd_validation = crate::validation::namd::validate_namd_config(&namd_config);
+    for warning in &namd_validation.warnings {
+        log_warn!(category: "Job Submission", message: "NAMD config warning", details: "{}: {}", job_id, warning);
+    }
+    if !namd_validation.is_valid {
+        log_error!(category: "Job Submission", message: "NAMD config failed validation", details: "{}: {}", job_id, namd_validation.issues.join("; "));
+        return Err(anyhow!("NAMD config failed validation:\n{}", namd_validation.issues.join("\n")));
+    }
+
+    // The mirror copies the whole project directory; a full scratch would stop it halfway
+    progress_callback("Checking scratch disk space...");
+    file_operations::ensure_space_for_directory(project_dir, &scratch_dir, ignore_space_check).await?;
+
+    progress_callback("Mirroring job directory to scratch...");
     log_info!(category: "Job Submission", message: "Mirroring project to scratch", details: "{} -> {}", project_dir, scratch_dir);
 
     // Use rsync to mirror entire job directory from project to scratch
@@ -78,10 +155,14 @@ pub async fn execute_job_submission_with_progress(
     progress_callback("Submitting job to SLURM...");
 
     // Submit job using SLURM commands module (using mirrored script in scratch)
-    let script_relative = "job.sbatch";
+    let script_relative = crate::ssh::JobDirectoryStructure::SLURM_SCRIPT;
     let script_path = format!("{}/{}", scratch_dir, script_relative);
+
+    // Keep the exact script being submitted so it stays recoverable locally
+    let submitted_script = read_submitted_script(connection_manager, &scratch_dir).await?;
+
     log_info!(category: "Job Submission", message: "Executing sbatch with script", details: "{}", script_path);
-    let submit_cmd = crate::slurm::commands::submit_job_command(&scratch_dir, script_relative)?;
+    let submit_cmd = crate::slurm::commands::submit_job_command(&scratch_dir, script_relative, &dependency_ids)?;
     let output = connection_manager.execute_command(&submit_cmd, Some(crate::cluster::timeouts::JOB_SUBMIT)).await
         .map_err(|e| {
             log_error!(category: "Job Submission", message: "Failed to submit job to SLURM", details: "{}", e);
@@ -101,13 +182,11 @@ pub async fn execute_job_submission_with_progress(
     progress_callback("Updating job status...");
 
     // Update job info with submission details
-    job_info.scratch_dir = Some(scratch_dir.clone());
-    job_info.slurm_job_id = Some(slurm_job_id.clone());
-    job_info.submitted_at = Some(submitted_at.clone());
-    common::update_job_status(&mut job_info, JobStatus::Pending);
+    record_submission(&mut job_info, &scratch_dir, &slurm_job_id, &submitted_at, submitted_script);
     log_debug!(category: "Job Submission", message: "Updated job status to Pending");
 
-    // Save updated job info to database
+    // Save updated job info to database, keeping notes/tags/pin edited while sbatch ran
+    common::refresh_user_edits(&mut job_info);
     common::save_job_to_database(&job_info, "Job Submission")?;
     log_debug!(category: "Job Submission", message: "Saved job to database");
 
@@ -127,6 +206,130 @@ pub async fn execute_job_submission_with_progress(
     Ok(job_info)
 }
 
+/// Check a job's SLURM script with `sbatch --test-only` without submitting it
+///
+/// The script is generated as it would be for submission, uploaded to a temp file and run
+/// from the existing project directory, so no directories are created. The job itself is
+/// not modified and the temp file is removed whatever sbatch reports.
+pub async fn execute_dry_run_submission(job_id: String) -> Result<DryRunResult> {
+    log_info!(category: "Dry Run", message: "Starting dry run submission", details: "{}", job_id);
+
+    let job_info = crate::commands::helpers::load_job_or_fail(&job_id, "Dry Run")?;
+    validate_job_submission_state(&job_info)?;
+
+    let dependencies = job_info.depends_on.iter().flatten()
+        .map(|dependency| crate::commands::helpers::load_job_or_fail(dependency, "Dry Run"))
+        .collect::<Result<Vec<_>>>()?;
+    let dependency_ids = dependency_slurm_ids(&job_info.job_id, &dependencies)?;
+
+    let (connection_manager, username) = common::require_connection_with_username("Dry Run").await?;
+    let project_dir = common::require_project_dir(&job_info, "Dry Run")?;
+    let scratch_dir = match &job_info.scratch_dir {
+        Some(dir) => dir.clone(),
+        None => paths::scratch_directory(&username, &job_info.job_id)?,
+    };
+
+    let script = crate::slurm::script_generator::SlurmScriptGenerator::generate_namd_script(&job_info, &scratch_dir)
+        .map_err(|e| anyhow!("Script generation error: {}", e))?;
+    let script_path = crate::slurm::commands::dry_run_script_path(&job_info.job_id)?;
+    let test_cmd = crate::slurm::commands::test_only_submit_command(project_dir, &script_path, &dependency_ids)?;
+
+    connection_manager.upload_bytes(&script_path, script.as_bytes(), false).await
+        .map_err(|e| anyhow!("Failed to upload script for dry run: {}", e))?;
+    let output = connection_manager.execute_command(&test_cmd, Some(crate::cluster::timeouts::JOB_SUBMIT)).await;
+
+    let cleanup_cmd = crate::ssh::commands::remove_temp_file_command(&script_path)?;
+    if let Err(e) = connection_manager.execute_command(&cleanup_cmd, None).await {
+        log_error!(category: "Dry Run", message: "Failed to remove dry run script", details: "{}: {}", script_path, e);
+    }
+
+    let output = output.map_err(|e| anyhow!("Could not run sbatch --test-only: {}", e))?;
+    // --test-only reports on stderr, whether it accepts the job or not
+    let combined = format!("{}{}", output.stdout, output.stderr);
+    let result = crate::slurm::commands::parse_sbatch_test_only(&combined, output.exit_code, script);
+
+    if result.accepted {
+        log_info!(category: "Dry Run", message: "Dry run accepted", details: "{}: estimated start {}", job_id, result.estimated_start.as_deref().unwrap_or("unknown"));
+    } else {
+        log_error!(category: "Dry Run", message: "Dry run rejected", details: "{}: {}", job_id, result.errors.join("; "));
+    }
+    Ok(result)
+}
+
+/// Submit several jobs one after another, continuing past failures
+///
+/// Jobs go through the same path as a single submission, sequentially so the cluster's
+/// submission rate limits are respected. A failed job is recorded and the batch moves on;
+/// jobs that depend on others in the batch are submitted after them so afterok can resolve.
+pub async fn execute_batch_submission(
+    job_ids: Vec<String>,
+    progress_callback: impl Fn(&BatchSubmitProgress),
+) -> BatchSubmitResult {
+    let dependencies: HashMap<String, Vec<String>> = job_ids.iter()
+        .filter_map(|job_id| {
+            let job = crate::commands::helpers::load_job_or_fail(job_id, "Batch Submission").ok()?;
+            Some((job.job_id, job.depends_on.unwrap_or_default()))
+        })
+        .collect();
+    let order = submission_order(&job_ids, &dependencies);
+    log_info!(category: "Batch Submission", message: "Submitting jobs", details: "{}", order.join(", "));
+
+    let mut result = BatchSubmitResult::default();
+    for (index, job_id) in order.iter().enumerate() {
+        let error = match execute_job_submission_with_progress(job_id.clone(), false, |_| {}).await {
+            Ok(job) => {
+                result.submitted.push(job);
+                None
+            }
+            Err(e) => {
+                log_error!(category: "Batch Submission", message: "Job submission failed", details: "{}: {}", job_id, e);
+                result.failed.push(BatchSubmitFailure { job_id: job_id.clone(), error: e.to_string() });
+                Some(e.to_string())
+            }
+        };
+        progress_callback(&BatchSubmitProgress {
+            job_id: job_id.clone(),
+            completed: index + 1,
+            total: order.len(),
+            submitted: result.submitted.len(),
+            failed: result.failed.len(),
+            error,
+        });
+    }
+
+    log_info!(
+        category: "Batch Submission",
+        message: "Batch submission finished",
+        details: "{} of {} submitted, {} failed", result.submitted.len(), order.len(), result.failed.len(),
+        show_toast: true
+    );
+    result
+}
+
+/// Order a batch so each job comes after any batch member it depends on, otherwise keeping
+/// the requested order. Duplicates are dropped; jobs in a dependency cycle keep their place
+/// and fail at submission with the usual unsubmitted-dependency error.
+fn submission_order(job_ids: &[String], dependencies: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut remaining: Vec<&String> = Vec::new();
+    for job_id in job_ids {
+        if !remaining.contains(&job_id) {
+            remaining.push(job_id);
+        }
+    }
+
+    let mut order: Vec<String> = Vec::new();
+    while !remaining.is_empty() {
+        let ready = remaining.iter().position(|job_id| {
+            dependencies.get(*job_id).into_iter().flatten()
+                .all(|dependency| !remaining.contains(&dependency))
+        });
+        // Nothing ready means a cycle: take the next job as requested
+        let next = remaining.remove(ready.unwrap_or(0));
+        order.push(next.clone());
+    }
+    order
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,9 +358,27 @@ mod tests {
                 walltime: "02:00:00".to_string(),
                 partition: "amilan".to_string(),
                 qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
             },
             input_files: vec![],
             output_files: vec![],
+            archived: false,
+            pinned: false,
+            tags: Vec::new(),
+            notes: None,
+            status_detail: None,
+            submitted_script: None,
+            template_snapshot: None,
+            rendered_config: None,
+            depends_on: None,
+            array: None,
+            notify_email: None,
+            notify_events: Vec::new(),
+            actual_cost: None,
+            namd_progress: None,
+            estimated_start: None,
+            queue_priority: None,
         }
     }
 
@@ -217,5 +438,123 @@ mod tests {
         assert!(error_msg.contains("cannot be submitted"));
         assert!(error_msg.contains("Created or Failed"));
     }
-}
 
+    fn sbatch_directives(script: &str) -> Vec<&str> {
+        script.lines().filter(|line| line.starts_with("#SBATCH")).collect()
+    }
+
+    fn dependency(job_id: &str, status: JobStatus, slurm_job_id: Option<&str>) -> JobInfo {
+        let mut job = create_test_job(status);
+        job.job_id = job_id.to_string();
+        job.slurm_job_id = slurm_job_id.map(String::from);
+        job
+    }
+
+    #[test]
+    fn test_dependency_slurm_ids() {
+        let dependencies = vec![
+            dependency("equil_1", JobStatus::Running, Some("1001")),
+            dependency("min_1", JobStatus::Pending, Some("1002")),
+            // Already done - afterok is met, and SLURM may have forgotten the job
+            dependency("setup_1", JobStatus::Completed, Some("999")),
+        ];
+
+        assert_eq!(dependency_slurm_ids("prod_1", &dependencies).unwrap(), vec!["1001", "1002"]);
+        assert!(dependency_slurm_ids("prod_1", &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unsubmitted_dependency_is_error() {
+        let err = dependency_slurm_ids("prod_1", &[dependency("equil_1", JobStatus::Created, None)]).unwrap_err();
+        assert!(err.to_string().contains("equil_1"));
+        assert!(err.to_string().contains("not been submitted"));
+    }
+
+    #[test]
+    fn test_failed_dependency_is_error() {
+        let err = dependency_slurm_ids("prod_1", &[dependency("equil_1", JobStatus::Failed, Some("1001"))]).unwrap_err();
+        assert!(err.to_string().contains("never start"));
+    }
+
+    #[tokio::test]
+    async fn test_record_submission_stores_script() {
+        use crate::slurm::script_generator::SlurmScriptGenerator;
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let mut job = create_test_job(JobStatus::Created);
+        let scratch_dir = "/scratch/alpine/testuser/namdrunner_jobs/test_job_123";
+        let generated = SlurmScriptGenerator::generate_namd_script(&job, scratch_dir).unwrap();
+
+        // The copy on scratch is what gets stored - here with a user edit the generator wouldn't produce
+        let remote_script = format!("{}\n# edited on the cluster\n", generated);
+        let remote = MockRemoteExecutor::new("testuser");
+        remote.filesystem.lock().unwrap()
+            .add_text_file(&format!("{}/{}", scratch_dir, crate::ssh::JobDirectoryStructure::SLURM_SCRIPT), &remote_script);
+
+        let script = read_submitted_script(&remote, scratch_dir).await.unwrap();
+        record_submission(&mut job, scratch_dir, "12345678", "2025-01-15T10:30:00Z", script);
+
+        assert_eq!(job.status, JobStatus::Pending);
+        assert_eq!(job.slurm_job_id.as_deref(), Some("12345678"));
+        assert_eq!(job.scratch_dir.as_deref(), Some(scratch_dir));
+        assert_eq!(job.submitted_script.as_deref(), Some(remote_script.as_str()));
+
+        // Same directives preview_slurm_script shows for this job's name and resources
+        // (only the preview banner and example working directory differ)
+        let preview = SlurmScriptGenerator::preview_script(job.job_name.clone(), job.slurm_config.clone()).unwrap();
+        let stored = job.submitted_script.as_deref().unwrap();
+        assert_eq!(sbatch_directives(stored), sbatch_directives(&preview));
+        assert!(stored.contains(&format!("cd {}", scratch_dir)));
+        assert!(stored.contains(&format!("# Job ID: {}", job.job_id)));
+
+        // No script on scratch fails the submission rather than storing nothing
+        let err = read_submitted_script(&remote, "/scratch/alpine/testuser/namdrunner_jobs/missing").await.unwrap_err();
+        assert!(err.to_string().contains("Failed to read SLURM script"));
+    }
+
+    #[test]
+    fn test_submitted_script_survives_serialization() {
+        let mut job = create_test_job(JobStatus::Created);
+        record_submission(&mut job, "/scratch/test", "1", "2025-01-15T10:30:00Z", "#!/bin/bash\n#SBATCH --ntasks=4".to_string());
+
+        let json = serde_json::to_string(&job).unwrap();
+        let restored: JobInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.submitted_script, job.submitted_script);
+
+        // Records saved before the field existed still load
+        let mut value = serde_json::to_value(&job).unwrap();
+        value.as_object_mut().unwrap().remove("submitted_script");
+        let legacy: JobInfo = serde_json::from_value(value).unwrap();
+        assert!(legacy.submitted_script.is_none());
+    }
+
+    #[test]
+    fn test_submission_order_puts_dependencies_first() {
+        let ids = |list: &[&str]| list.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let dependencies = HashMap::from([
+            ("prod".to_string(), ids(&["equil"])),
+            ("equil".to_string(), ids(&["min"])),
+            ("other".to_string(), ids(&["submitted_earlier"])),
+        ]);
+
+        assert_eq!(
+            submission_order(&ids(&["prod", "other", "equil", "min"]), &dependencies),
+            ids(&["other", "min", "equil", "prod"])
+        );
+        // Already in order, with a duplicate
+        assert_eq!(
+            submission_order(&ids(&["min", "equil", "min", "prod"]), &dependencies),
+            ids(&["min", "equil", "prod"])
+        );
+    }
+
+    #[test]
+    fn test_submission_order_with_cycle() {
+        let dependencies = HashMap::from([
+            ("a".to_string(), vec!["b".to_string()]),
+            ("b".to_string(), vec!["a".to_string()]),
+        ]);
+        let order = submission_order(&["a".to_string(), "b".to_string(), "c".to_string()], &dependencies);
+        assert_eq!(order, vec!["c", "a", "b"]);
+    }
+}
@@ -1,11 +1,14 @@
 use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
 
-use crate::types::{JobInfo, JobStatus};
-use crate::ssh::get_connection_manager;
-use crate::database::with_database;
-use crate::slurm::status::SlurmStatusSync;
-use crate::{log_info, log_debug, log_error};
+use crate::types::{JobInfo, JobStatus, NamdProgress};
+use crate::ssh::RemoteExecutor;
+use crate::database::{with_database, JobDatabase};
+use crate::slurm::namd_progress::NamdProgressParser;
+use crate::slurm::status::{SlurmStatusSync, MaintenanceWindow, QueuePosition, ReservationHold, classify_pending_reason, held_status_detail, is_dependency_never_satisfied, is_held_reason, is_next_to_start, pending_status_detail};
+use crate::{log_info, log_debug, log_error, log_warn};
 use crate::automations::common;
+use std::sync::atomic::{AtomicU32, Ordering};
 
 /// Job sync result for a single job
 #[derive(Debug, Clone)]
@@ -23,19 +26,108 @@ pub struct JobSyncResult {
 /// - Local database
 /// - job_info.json on server
 /// - Triggers job_completion automation when jobs finish
-pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
+///
+/// All remote work goes through `remote` (the global connection manager outside tests)
+/// Only one sync runs at a time: a call arriving mid-sync waits for it and returns that sync's outcome, error included.
+/// `scheduled` marks the frontend's timed polls; only those lengthen the suggested gap when nothing changed
+pub async fn sync_all_jobs(remote: &dyn RemoteExecutor, scheduled: bool) -> Result<crate::types::SyncJobsResult> {
+    let sync = async { sync_jobs_exclusive(remote, scheduled).await.map_err(|e| e.to_string()) };
+    let (outcome, joined) = run_or_join(&SYNC_LOCK, sync).await;
+    if joined {
+        log_info!(category: "Job Sync", message: "Sync already in progress - returning its results");
+    }
+
+    let mut result = outcome.map_err(|e| anyhow!(e))?;
+    result.already_in_progress = joined;
+    Ok(result)
+}
+
+/// Fastest and slowest suggested gap between syncs
+pub const MIN_POLL_SECS: u64 = 30;
+pub const MAX_POLL_SECS: u64 = 600;
+
+/// Jobs submitted this recently are watched closely: they often start, or fail, within minutes
+const RECENT_SUBMISSION_SECS: i64 = 300;
+
+/// Scheduled syncs in a row that changed no job; each one doubles the suggested gap
+static QUIET_SYNCS: AtomicU32 = AtomicU32::new(0);
+
+/// Seconds to wait before the next sync
+/// Starts at MIN_POLL_SECS and doubles for each quiet sync in a row, up to MAX_POLL_SECS;
+/// `urgent` (a job is likely to change state soon) keeps it at the minimum
+pub fn suggest_poll_interval(quiet_syncs: u32, urgent: bool) -> u64 {
+    if urgent {
+        return MIN_POLL_SECS;
+    }
+    MIN_POLL_SECS.saturating_mul(1 << quiet_syncs.min(16)).min(MAX_POLL_SECS)
+}
+
+/// Quiet-sync count after a sync: a change resets it, a quiet scheduled sync adds one,
+/// and a quiet manual refresh leaves it alone so clicking refresh doesn't slow the polling down
+fn next_quiet_syncs(quiet_syncs: u32, changed: bool, scheduled: bool) -> u32 {
+    match (changed, scheduled) {
+        (true, _) => 0,
+        (false, true) => quiet_syncs.saturating_add(1),
+        (false, false) => quiet_syncs,
+    }
+}
+
+/// Record whether a sync changed any job and return the suggested wait before the next one
+fn next_poll_suggestion(changed: bool, urgent: bool, scheduled: bool) -> u64 {
+    let quiet_syncs = QUIET_SYNCS.load(Ordering::Relaxed);
+    QUIET_SYNCS.store(next_quiet_syncs(quiet_syncs, changed, scheduled), Ordering::Relaxed);
+    suggest_poll_interval(if changed { 0 } else { quiet_syncs }, urgent)
+}
+
+/// Whether any job was submitted within RECENT_SUBMISSION_SECS of `now`
+fn has_recent_submission(jobs: &[JobInfo], now: chrono::DateTime<chrono::Utc>) -> bool {
+    jobs.iter()
+        .filter_map(|job| job.submitted_at.as_deref())
+        .filter_map(|submitted_at| chrono::DateTime::parse_from_rfc3339(submitted_at).ok())
+        .any(|submitted_at| (now - submitted_at.with_timezone(&chrono::Utc)).num_seconds() < RECENT_SUBMISSION_SECS)
+}
+
+/// Full sync outcome shared with calls that joined it
+/// (errors as strings, since anyhow::Error can't be cloned)
+type SharedSyncOutcome = std::result::Result<crate::types::SyncJobsResult, String>;
+
+/// Held for the duration of a sync so a timer poll and a manual refresh don't both query SLURM.
+/// The holder leaves its outcome here for the calls that waited on it.
+static SYNC_LOCK: tokio::sync::Mutex<Option<SharedSyncOutcome>> = tokio::sync::Mutex::const_new(None);
+
+/// Run `operation` if `slot` is free and leave its output there; otherwise wait for the holder
+/// and return a copy of the output it left. The bool is true when the output was joined.
+/// `operation` only runs in the second case if the holder left nothing (e.g. a single-job sync).
+async fn run_or_join<T: Clone, F: std::future::Future<Output = T>>(slot: &tokio::sync::Mutex<Option<T>>, operation: F) -> (T, bool) {
+    let mut guard = match slot.try_lock() {
+        Ok(guard) => guard,
+        Err(_) => {
+            let guard = slot.lock().await;
+            if let Some(output) = guard.as_ref() {
+                return (output.clone(), true);
+            }
+            guard
+        }
+    };
+    let output = operation.await;
+    *guard = Some(output.clone());
+    (output, false)
+}
+
+async fn sync_jobs_exclusive(remote: &dyn RemoteExecutor, scheduled: bool) -> Result<crate::types::SyncJobsResult> {
     log_info!(category: "Job Sync", message: "Starting job status sync");
 
     // Verify SSH connection and get username
-    let (_connection_manager, username) = common::require_connection_with_username("Job Sync").await?;
+    let username = common::require_username(remote, "Job Sync").await?;
     log_debug!(category: "Job Sync", message: "Syncing jobs for user", details: "{}", username);
 
     // Load all jobs from database
-    let all_jobs = with_database(move |db| db.load_all_jobs())
+    let mut all_jobs = with_database(move |db| db.load_all_jobs())
         .map_err(|e| {
             log_error!(category: "Job Sync", message: "Failed to load jobs from database", details: "{}", e);
             anyhow!("Failed to load jobs: {}", e)
-        })?;
+        })?
+        .jobs;
 
     // Check if database is empty (first connection after DB reset)
     // If empty, automatically discover jobs from cluster
@@ -43,7 +135,7 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
         log_info!(category: "Job Sync", message: "Database empty - triggering automatic job discovery");
 
         // Attempt discovery (don't fail sync if discovery fails)
-        match discover_jobs(&username).await {
+        match discover_jobs(remote, &username).await {
             Ok(report) => {
                 if !report.imported_jobs.is_empty() {
                     log_info!(
@@ -63,9 +155,14 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
                 for failure in &report.failed_imports {
                     log_error!(category: "Job Sync", message: "Failed to import", details: "{}: {}", failure.directory, failure.reason);
                 }
+                if !report.missing_templates.is_empty() {
+                    let job_ids: Vec<&str> = report.missing_templates.iter().map(|missing| missing.job_id.as_str()).collect();
+                    let warning = format!("Templates missing for imported jobs (rerun and preview unavailable): {}", job_ids.join(", "));
+                    log_warn!(category: "Job Discovery", message: &warning, show_toast: true);
+                }
 
                 // Reload jobs after discovery
-                let all_jobs_after_discovery = with_database(|db| db.load_all_jobs())
+                let all_jobs_after_discovery = with_database(|db| db.load_unarchived_jobs())
                     .map_err(|e| anyhow!("Failed to reload jobs after discovery: {}", e))?;
 
                 // Return the discovered jobs
@@ -74,6 +171,10 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
                     jobs: all_jobs_after_discovery,
                     jobs_updated: 0,
                     errors: vec![],
+                    warnings: vec![],
+                    already_in_progress: false,
+                    // Imported jobs haven't been checked against SLURM yet
+                    suggested_next_poll_secs: next_poll_suggestion(true, true, scheduled),
                 });
             }
             Err(e) => {
@@ -83,9 +184,21 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
         }
     }
 
+    // Two local jobs claiming the same SLURM job means one of them is wrong - surface it,
+    // toasting only when the duplicates change so a timed poll doesn't repeat it every time
+    let warnings = duplicate_slurm_id_warnings(&all_jobs);
+    if replace_if_changed(&REPORTED_DUPLICATES, &warnings) {
+        for warning in &warnings {
+            log_warn!(category: "Job Sync", message: warning, show_toast: true);
+        }
+    }
+
+    // Finished jobs whose cost sacct couldn't supply at completion get another try
+    backfill_actual_costs(remote, &mut all_jobs).await;
+
     // Filter to only jobs that need syncing (Pending or Running)
     let active_jobs: Vec<JobInfo> = all_jobs.iter()
-        .filter(|job| matches!(job.status, JobStatus::Pending | JobStatus::Running))
+        .filter(|job| matches!(job.status, JobStatus::Pending | JobStatus::Held | JobStatus::Running))
         .cloned()
         .collect();
 
@@ -94,16 +207,19 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
         // Still return complete job list (even if no active jobs)
         return Ok(crate::types::SyncJobsResult {
             success: true,
-            jobs: all_jobs,
+            jobs: all_jobs.into_iter().filter(|job| !job.archived).collect(),
             jobs_updated: 0,
             errors: vec![],
+            warnings,
+            already_in_progress: false,
+            suggested_next_poll_secs: next_poll_suggestion(false, false, scheduled),
         });
     }
 
     log_info!(category: "Job Sync", message: "Found active jobs to sync", details: "{} jobs", active_jobs.len());
 
     // Create SLURM status sync helper
-    let slurm_sync = SlurmStatusSync::new(&username);
+    let slurm_sync = SlurmStatusSync::with_executor(remote);
 
     // Extract SLURM job IDs for batch query
     let job_ids: Vec<String> = active_jobs.iter()
@@ -114,13 +230,16 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
     if job_ids.is_empty() {
         log_info!(category: "Job Sync", message: "No jobs have SLURM job IDs, skipping batch query");
         // Load complete job list to return
-        let final_jobs = with_database(|db| db.load_all_jobs())
+        let final_jobs = with_database(|db| db.load_unarchived_jobs())
             .map_err(|e| anyhow!("Failed to load jobs: {}", e))?;
         return Ok(crate::types::SyncJobsResult {
             success: true,
             jobs: final_jobs,
             jobs_updated: 0,
             errors: vec![],
+            warnings,
+            already_in_progress: false,
+            suggested_next_poll_secs: next_poll_suggestion(false, false, scheduled),
         });
     }
 
@@ -133,35 +252,34 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
             anyhow!("Failed to query SLURM job status: {}", e)
         })?;
 
-    // Create lookup map of SLURM job ID -> JobInfo
-    let job_map: std::collections::HashMap<String, JobInfo> = active_jobs.into_iter()
-        .filter_map(|job| {
-            if let Some(slurm_id) = &job.slurm_job_id {
-                Some((slurm_id.clone(), job))
-            } else {
-                None
-            }
-        })
-        .collect();
+    let maintenance_windows = maintenance_windows_for(&slurm_sync, &batch_results).await;
+
+    // Poll closely while a job is at the front of the queue or was just submitted
+    let transition_likely = has_recent_submission(&active_jobs, chrono::Utc::now())
+        || batch_results.iter().any(|(_, status, reason)| {
+            matches!(status, Ok(JobStatus::Pending)) && reason.as_deref().is_some_and(is_next_to_start)
+        });
+
+    // Create lookup map of SLURM job ID -> JobInfo(s)
+    // Multiple jobs per ID are kept (and warned about above) rather than silently dropped
+    let mut job_map: std::collections::HashMap<String, Vec<JobInfo>> = std::collections::HashMap::new();
+    for job in active_jobs {
+        if let Some(slurm_id) = job.slurm_job_id.clone() {
+            job_map.entry(slurm_id).or_default().push(job);
+        }
+    }
 
     let mut results = Vec::new();
 
     // Process batch results
-    for (slurm_job_id, status_result) in batch_results {
-        if let Some(job) = job_map.get(&slurm_job_id) {
-            match status_result {
+    for (slurm_job_id, status_result, pending_reason) in batch_results {
+        for job in job_map.get(&slurm_job_id).into_iter().flatten() {
+            match &status_result {
                 Ok(new_status) => {
-                    match update_job_with_status(job.clone(), new_status).await {
+                    let status = apply_slurm_status(remote, &slurm_sync, job, &slurm_job_id, new_status, pending_reason.as_deref(), &maintenance_windows).await;
+                    match status {
                         Ok(result) => {
-                            if result.updated {
-                                log_info!(
-                                    category: "Job Sync",
-                                    message: "Job status changed",
-                                    details: "{}: {:?} -> {:?}", result.job_id, result.old_status, result.new_status
-                                );
-                            } else {
-                                log_debug!(category: "Job Sync", message: "Job status unchanged", details: "{}: {:?}", result.job_id, result.old_status);
-                            }
+                            log_sync_result(&result);
                             results.push(result);
                         }
                         Err(e) => {
@@ -177,6 +295,7 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
     }
 
     let jobs_updated = results.iter().filter(|r| r.updated).count() as u32;
+    let suggested_next_poll_secs = next_poll_suggestion(jobs_updated > 0, transition_likely, scheduled);
 
     log_info!(category: "Job Sync", message: "Sync completed", details: "{} jobs checked, {} updated",
         results.len(),
@@ -184,7 +303,7 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
     );
 
     // Load complete job list to return (backend owns complete state)
-    let all_jobs = with_database(|db| db.load_all_jobs())
+    let all_jobs = with_database(|db| db.load_unarchived_jobs())
         .map_err(|e| {
             log_error!(category: "Job Sync", message: "Failed to load complete job list", details: "{}", e);
             anyhow!("Failed to load jobs: {}", e)
@@ -195,18 +314,143 @@ pub async fn sync_all_jobs() -> Result<crate::types::SyncJobsResult> {
         jobs: all_jobs,
         jobs_updated,
         errors: vec![],
+        warnings,
+        already_in_progress: false,
+        suggested_next_poll_secs,
+    })
+}
+
+/// Refresh one job from SLURM without querying the rest of the active jobs
+/// Goes through the same status handling as sync_all_jobs, so completion still runs if the job just finished.
+/// Waits for a running full sync first so the two never write the same job at once.
+pub async fn sync_single_job(remote: &dyn RemoteExecutor, job_id: &str) -> Result<JobInfo> {
+    let mut sync = SYNC_LOCK.lock().await;
+    // Calls queued behind this one didn't wait on a full sync, so they mustn't reuse the last one's outcome
+    *sync = None;
+
+    let job = crate::commands::helpers::load_job_or_fail(job_id, "Job Sync")?;
+    if !matches!(job.status, JobStatus::Pending | JobStatus::Held | JobStatus::Running) {
+        log_debug!(category: "Job Sync", message: "Job not active, nothing to refresh", details: "{}: {:?}", job.job_id, job.status);
+        return Ok(job);
+    }
+    let Some(slurm_job_id) = job.slurm_job_id.clone() else {
+        return Ok(job);
+    };
+
+    common::require_username(remote, "Job Sync").await?;
+    let slurm_sync = SlurmStatusSync::with_executor(remote);
+    let statuses = slurm_sync.query_job_statuses(std::slice::from_ref(&slurm_job_id)).await
+        .map_err(|e| anyhow!("Failed to query SLURM job status: {}", e))?;
+    let maintenance_windows = maintenance_windows_for(&slurm_sync, &statuses).await;
+
+    let (_, status_result, pending_reason) = statuses.into_iter()
+        .find(|(id, _, _)| *id == slurm_job_id)
+        .ok_or_else(|| anyhow!("SLURM returned no status for job {}", slurm_job_id))?;
+    let new_status = status_result?;
+
+    let result = apply_slurm_status(remote, &slurm_sync, &job, &slurm_job_id, &new_status, pending_reason.as_deref(), &maintenance_windows).await?;
+    log_sync_result(&result);
+
+    crate::commands::helpers::load_job_or_fail(job_id, "Job Sync")
+}
+
+fn log_sync_result(result: &JobSyncResult) {
+    if result.updated && result.old_status == result.new_status {
+        log_info!(category: "Job Sync", message: "Job status detail changed", details: "{}: {:?}", result.job_id, result.new_status);
+    } else if result.updated {
+        log_info!(
+            category: "Job Sync",
+            message: "Job status changed",
+            details: "{}: {:?} -> {:?}", result.job_id, result.old_status, result.new_status
+        );
+    } else {
+        log_debug!(category: "Job Sync", message: "Job status unchanged", details: "{}: {:?}", result.job_id, result.old_status);
+    }
+}
+
+/// Explanation for a job failed because a job it depends on did not succeed
+const DEPENDENCY_FAILED_DETAIL: &str = "A job this one depends on did not complete successfully, so it could never start";
+
+/// Maintenance reservations, read only when some job is held for one (best effort)
+async fn maintenance_windows_for(slurm_sync: &SlurmStatusSync<'_>, statuses: &[(String, Result<JobStatus>, Option<String>)]) -> Vec<MaintenanceWindow> {
+    let held_for_maintenance = statuses.iter()
+        .any(|(_, _, reason)| reason.as_deref().and_then(classify_pending_reason) == Some(ReservationHold::Maintenance));
+    if !held_for_maintenance {
+        return Vec::new();
+    }
+    slurm_sync.query_maintenance_windows().await.unwrap_or_else(|e| {
+        log_warn!(category: "Job Sync", message: "Could not read maintenance reservations", details: "{}", e);
+        Vec::new()
     })
 }
 
-/// Update a single job with fetched SLURM status
-async fn update_job_with_status(mut job: JobInfo, new_status: JobStatus) -> Result<JobSyncResult> {
+/// Apply the status SLURM reported for one job, then save it through update_job_with_status
+/// Never-startable jobs are cancelled and failed, held jobs told apart from pending ones,
+/// pending jobs given an explanation, failed jobs their reason, and array jobs their per-task statuses
+async fn apply_slurm_status(
+    remote: &dyn RemoteExecutor,
+    slurm_sync: &SlurmStatusSync<'_>,
+    job: &JobInfo,
+    slurm_job_id: &str,
+    new_status: &JobStatus,
+    reason: Option<&str>,
+    maintenance_windows: &[MaintenanceWindow],
+) -> Result<JobSyncResult> {
+    let never_starts = *new_status == JobStatus::Pending
+        && reason.is_some_and(is_dependency_never_satisfied);
+    let (new_status, status_detail) = if never_starts {
+        // Don't leave a job that can never run sitting in the user's queue
+        if let Err(e) = slurm_sync.cancel_job(slurm_job_id).await {
+            log_warn!(category: "Job Sync", message: "Could not cancel job with unmet dependency", details: "{}: {}", slurm_job_id, e);
+        }
+        (JobStatus::Failed, Some(DEPENDENCY_FAILED_DETAIL.to_string()))
+    } else if *new_status == JobStatus::Pending && reason.is_some_and(is_held_reason) {
+        // squeue lists held jobs as PENDING; the reason tells them apart
+        (JobStatus::Held, reason.and_then(held_status_detail))
+    } else {
+        let status_detail = match new_status {
+            JobStatus::Pending => reason
+                .and_then(|reason| pending_status_detail(reason, maintenance_windows)),
+            _ => None,
+        };
+        (new_status.clone(), status_detail)
+    };
+
+    // Array jobs show per-task progress, naming the tasks that failed
+    let mut job = job.clone();
+    // For finished jobs the reason says why SLURM ended them (out of memory, walltime, node failure...)
+    if matches!(new_status, JobStatus::Failed | JobStatus::Cancelled) && !never_starts {
+        if let Some(reason) = reason {
+            job.error_info = Some(reason.to_string());
+        }
+    }
+    let status_detail = match job.array.as_mut() {
+        Some(array) => {
+            match slurm_sync.query_array_tasks(slurm_job_id).await {
+                Ok(tasks) if !tasks.is_empty() => array.task_statuses = tasks,
+                Ok(_) => {}
+                Err(e) => {
+                    log_warn!(category: "Job Sync", message: "Could not read array task statuses", details: "{}: {}", slurm_job_id, e);
+                }
+            }
+            array.status_summary().or(status_detail)
+        }
+        None => status_detail,
+    };
+
+    update_job_with_status(remote, job, new_status, status_detail).await
+}
+
+/// Update a single job with fetched SLURM status and its explanation (if any)
+async fn update_job_with_status(remote: &dyn RemoteExecutor, mut job: JobInfo, new_status: JobStatus, status_detail: Option<String>) -> Result<JobSyncResult> {
     let job_id = job.job_id.clone();
     let old_status = job.status.clone();
 
-    // Check if status changed
+    // Check if status (or the explanation shown with it) changed
     let status_changed = new_status != old_status;
+    let detail_changed = status_detail != job.status_detail;
 
-    if !status_changed {
+    if !status_changed && !detail_changed {
         return Ok(JobSyncResult {
             job_id,
             old_status,
@@ -215,6 +459,20 @@ async fn update_job_with_status(mut job: JobInfo, new_status: JobStatus) -> Resu
         });
     }
 
+    job.status_detail = status_detail;
+
+    if !status_changed {
+        common::touch_job_timestamp(&mut job);
+        common::refresh_user_edits(&mut job);
+        common::save_job_to_database(&job, "Job Sync")?;
+        return Ok(JobSyncResult {
+            job_id,
+            old_status,
+            new_status,
+            updated: true,
+        });
+    }
+
     // Status changed - update job
     log_debug!(category: "Job Sync", message: "Status changed for job", details: "{}: {:?} -> {:?}", job_id, old_status, new_status);
 
@@ -225,7 +483,7 @@ async fn update_job_with_status(mut job: JobInfo, new_status: JobStatus) -> Resu
         log_info!(category: "Job Sync", message: "Job reached terminal state", details: "{}: {:?}", job_id, new_status);
 
         // Trigger automatic job completion (rsync scratch→project, fetch logs, update metadata)
-        if let Err(e) = crate::automations::execute_job_completion(&mut job).await {
+        if let Err(e) = crate::automations::execute_job_completion(remote, &mut job).await {
             log_error!(category: "Job Sync", message: "Automatic completion failed", details: "{}: {}", job_id, e);
             // Don't fail sync - completion will retry on next sync
         } else {
@@ -235,6 +493,7 @@ async fn update_job_with_status(mut job: JobInfo, new_status: JobStatus) -> Resu
 
     // Update database (Metadata-at-Boundaries: only update DB during execution, not server metadata)
     // Server metadata is updated at lifecycle boundaries (creation, submission, completion)
+    common::refresh_user_edits(&mut job);
     common::save_job_to_database(&job, "Job Sync")?;
     log_debug!(category: "Job Sync", message: "Database updated for job", details: "{}", job_id);
 
@@ -255,13 +514,82 @@ async fn update_job_with_status(mut job: JobInfo, new_status: JobStatus) -> Resu
     })
 }
 
+/// Settings key for the automatic log fetch policy
+pub const LOG_FETCH_POLICY_SETTING: &str = "log_fetch_policy";
+
+/// When SLURM logs are fetched automatically (manual refetch always works)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFetchPolicy {
+    /// Fetch as part of job completion (default)
+    #[default]
+    OnCompletion,
+    /// Defer until the job's status is viewed - jobs never opened cost no bandwidth
+    OnStatusView,
+    /// Never fetch automatically
+    Never,
+}
+
+/// Code paths that may trigger an automatic log fetch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFetchTrigger {
+    Completion,
+    StatusView,
+}
+
+impl LogFetchPolicy {
+    /// Whether this policy permits an automatic fetch from the given trigger
+    pub fn allows(self, trigger: LogFetchTrigger) -> bool {
+        matches!(
+            (self, trigger),
+            (LogFetchPolicy::OnCompletion, LogFetchTrigger::Completion)
+                | (LogFetchPolicy::OnStatusView, LogFetchTrigger::StatusView)
+        )
+    }
+}
+
+/// Load the configured log fetch policy, falling back to the default
+pub fn load_log_fetch_policy() -> LogFetchPolicy {
+    with_database(|db| db.load_setting::<LogFetchPolicy>(LOG_FETCH_POLICY_SETTING))
+        .unwrap_or_else(|e| {
+            log_debug!(category: "Log Fetch", message: "Could not load log fetch policy, using default", details: "{}", e);
+            None
+        })
+        .unwrap_or_default()
+}
+
+/// Fetch SLURM logs (cached-only, non-forced) if the log fetch policy allows it for this trigger
+/// Returns whether a fetch was attempted
+pub async fn auto_fetch_slurm_logs(remote: &dyn RemoteExecutor, job: &mut JobInfo, trigger: LogFetchTrigger) -> Result<bool> {
+    let policy = load_log_fetch_policy();
+    if !policy.allows(trigger) {
+        log_debug!(category: "Log Fetch", message: "Skipping automatic log fetch", details: "{}: policy {:?}, trigger {:?}", job.job_id, policy, trigger);
+        return Ok(false);
+    }
+
+    load_slurm_logs(remote, job, false).await?;
+    Ok(true)
+}
+
+/// Deferred log fetch for a job whose status is being viewed (LogFetchPolicy::OnStatusView)
+/// Only finished jobs still missing a log are fetched; returns whether logs were read
+pub async fn fetch_logs_on_status_view(remote: &dyn RemoteExecutor, job: &mut JobInfo) -> Result<bool> {
+    let logs_missing = job.slurm_stdout.is_none() || job.slurm_stderr.is_none();
+    let finished = matches!(job.status, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled);
+    if !logs_missing || !finished {
+        return Ok(false);
+    }
+
+    auto_fetch_slurm_logs(remote, job, LogFetchTrigger::StatusView).await
+}
+
 /// Fetch SLURM logs from server
 ///
 /// - force=false: Only fetch if not already cached, silently skip if missing dirs
 /// - force=true: Always fetch, error if missing dirs, set to empty on read failure
 ///
 /// NOTE: Logs are fetched from project_dir (after rsync in job completion)
-pub async fn load_slurm_logs(job: &mut JobInfo, force: bool) -> Result<()> {
+pub async fn load_slurm_logs(remote: &dyn RemoteExecutor, job: &mut JobInfo, force: bool) -> Result<()> {
     let category = if force { "Log Refetch" } else { "Log Fetch" };
     log_debug!(category: category, message: "ENTRY", details: "job_id={}, status={:?}, force={}", job.job_id, job.status, force);
 
@@ -289,15 +617,12 @@ pub async fn load_slurm_logs(job: &mut JobInfo, force: bool) -> Result<()> {
         }
     };
 
-    let connection_manager = get_connection_manager();
-
     // Fetch stdout
     let should_fetch_stdout = force || job.slurm_stdout.is_none();
     if should_fetch_stdout {
-        let stdout_path = format!("{}/{}_{}.out", project_dir, job.job_name, slurm_job_id);
-        log_debug!(category: category, message: "Fetching stdout", details: "{}", stdout_path);
+        log_debug!(category: category, message: "Fetching stdout", details: "{}", job.job_id);
 
-        match connection_manager.read_remote_file(&stdout_path).await {
+        match read_job_slurm_log(remote, &project_dir, job, &slurm_job_id, LogStream::Stdout).await {
             Ok(content) => {
                 let content_len = content.len();
                 job.slurm_stdout = Some(content);
@@ -317,10 +642,9 @@ pub async fn load_slurm_logs(job: &mut JobInfo, force: bool) -> Result<()> {
     // Fetch stderr
     let should_fetch_stderr = force || job.slurm_stderr.is_none();
     if should_fetch_stderr {
-        let stderr_path = format!("{}/{}_{}.err", project_dir, job.job_name, slurm_job_id);
-        log_debug!(category: category, message: "Fetching stderr", details: "{}", stderr_path);
+        log_debug!(category: category, message: "Fetching stderr", details: "{}", job.job_id);
 
-        match connection_manager.read_remote_file(&stderr_path).await {
+        match read_job_slurm_log(remote, &project_dir, job, &slurm_job_id, LogStream::Stderr).await {
             Ok(content) => {
                 let content_len = content.len();
                 job.slurm_stderr = Some(content);
@@ -341,14 +665,191 @@ pub async fn load_slurm_logs(job: &mut JobInfo, force: bool) -> Result<()> {
     Ok(())
 }
 
-/// Internal helper to discover jobs from server
-/// Returns detailed report of imported jobs and failures
-async fn discover_jobs(username: &str) -> Result<crate::types::response_data::DiscoveryReport> {
-    use crate::types::response_data::{DiscoveryReport, FailedImport};
+/// Which SLURM log of a job to read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
 
-    log_info!(category: "Job Discovery", message: "Starting automatic discovery", details: "user: {}", username);
+/// Path of a SLURM log as named by the script's --output/--error directives
+/// Array tasks each write their own log (`{name}_%A_%a`), so `array_task` picks the task
+pub fn slurm_log_path(dir: &str, job_name: &str, slurm_job_id: &str, array_task: Option<u32>, stream: LogStream) -> String {
+    let extension = match stream {
+        LogStream::Stdout => "out",
+        LogStream::Stderr => "err",
+    };
+    match array_task {
+        Some(task) => format!("{}/{}_{}_{}.{}", dir, job_name, slurm_job_id, task, extension),
+        None => format!("{}/{}_{}.{}", dir, job_name, slurm_job_id, extension),
+    }
+}
+
+/// Read a job's SLURM log from `dir`; for array jobs, every task's log in task order,
+/// each under a `==> task N <==` header. Tasks whose log is missing are skipped.
+async fn read_job_slurm_log(remote: &dyn RemoteExecutor, dir: &str, job: &JobInfo, slurm_job_id: &str, stream: LogStream) -> Result<String> {
+    let Some(array) = &job.array else {
+        return read_slurm_log(remote, &slurm_log_path(dir, &job.job_name, slurm_job_id, None, stream)).await;
+    };
+
+    let mut sections = Vec::new();
+    let mut last_error = None;
+    for task in 0..array.values.len() as u32 {
+        let log_path = slurm_log_path(dir, &job.job_name, slurm_job_id, Some(task), stream);
+        match read_slurm_log(remote, &log_path).await {
+            Ok(content) => sections.push(format!("==> task {} <==\n{}", task, content)),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    match (sections.is_empty(), last_error) {
+        (true, Some(e)) => Err(e),
+        _ => Ok(sections.join("\n")),
+    }
+}
+
+/// Check that `array_task` fits the job: required for array jobs (and within the array), refused otherwise
+fn validate_array_task(job: &JobInfo, array_task: Option<u32>) -> Result<()> {
+    match (&job.array, array_task) {
+        (Some(array), Some(task)) if (task as usize) < array.values.len() => Ok(()),
+        (Some(array), Some(task)) => Err(anyhow!("Job {} has no array task {} (tasks 0-{})", job.job_id, task, array.values.len().saturating_sub(1))),
+        (Some(_), None) => Err(anyhow!("Job {} is an array job - choose which task's log to read", job.job_id)),
+        (None, Some(_)) => Err(anyhow!("Job {} is not an array job", job.job_id)),
+        (None, None) => Ok(()),
+    }
+}
+
+/// Read the part of a job's SLURM log written since `from_offset`, for live log viewing
+/// While the job is queued or running the log is still being written in scratch; afterwards
+/// it is read from the project directory it was copied back to. Array jobs name the task to follow.
+pub async fn stream_job_log(job_id: &str, stream: LogStream, array_task: Option<u32>, from_offset: u64) -> Result<crate::ssh::RemoteFileTail> {
+    let job = crate::commands::helpers::load_job_or_fail(job_id, "Log Stream")?;
+    let slurm_job_id = job.slurm_job_id.as_deref()
+        .ok_or_else(|| anyhow!("Job {} has not been submitted", job.job_id))?;
+    validate_array_task(&job, array_task)?;
+    let dir = match job.status {
+        JobStatus::Pending | JobStatus::Held | JobStatus::Running => common::require_scratch_dir(&job, "Log Stream")?,
+        _ => common::require_project_dir(&job, "Log Stream")?,
+    };
+    let (connection_manager, _username) = common::require_connection_with_username("Log Stream").await?;
+
+    let log_path = slurm_log_path(dir, &job.job_name, slurm_job_id, array_task, stream);
+    let tail = connection_manager.tail_remote_file(&log_path, from_offset).await?;
+    if tail.reset {
+        log_debug!(category: "Log Stream", message: "Log shrank below offset, restarting from the beginning", details: "{} (offset {}, size {})", log_path, from_offset, tail.file_size);
+    }
+    Ok(tail)
+}
+
+/// Scheduler's start estimate and priority for a pending job
+/// None for jobs in any other state, so the scheduler is only asked about jobs still queued
+pub async fn read_queue_position(job: &JobInfo) -> Result<Option<QueuePosition>> {
+    if job.status != JobStatus::Pending {
+        return Ok(None);
+    }
+    let Some(slurm_job_id) = job.slurm_job_id.as_deref() else {
+        return Ok(None);
+    };
+    let (_connection_manager, username) = common::require_connection_with_username("Queue Position").await?;
 
-    let connection_manager = get_connection_manager();
+    SlurmStatusSync::new(&username).query_queue_position(slurm_job_id).await
+}
+
+/// SLURM's accounting record (elapsed, peak memory, CPU efficiency, exit code) for a submitted job
+/// Array jobs are refused: their tasks are accounted separately and have no single record
+pub async fn read_job_accounting(job_id: &str) -> Result<crate::types::response_data::JobAccounting> {
+    let job = crate::commands::helpers::load_job_or_fail(job_id, "Job Accounting")?;
+    let slurm_job_id = job.slurm_job_id.as_deref()
+        .ok_or_else(|| anyhow!("Job {} has not been submitted to SLURM", job_id))?;
+    if job.array.is_some() {
+        return Err(anyhow!("Accounting is not available for array jobs - each task is accounted separately"));
+    }
+    let (_connection_manager, username) = common::require_connection_with_username("Job Accounting").await?;
+
+    SlurmStatusSync::new(&username).query_job_accounting(slurm_job_id).await?
+        .ok_or_else(|| anyhow!("SLURM has no accounting record for job {} (SLURM ID {})", job_id, slurm_job_id))
+}
+
+/// Bytes read from the end of a long NAMD log for the latest step and timing
+const NAMD_PROGRESS_TAIL_BYTES: u64 = 64 * 1024;
+
+/// Progress of a running job's NAMD run, parsed from namd_output.log in scratch
+/// The start of the log carries the phase and step count, the end the latest step and timing,
+/// so a long log is read in those two pieces. None for jobs that aren't running and for
+/// array jobs, whose tasks each log in their own directory.
+pub async fn read_namd_progress(job: &JobInfo) -> Result<Option<NamdProgress>> {
+    if job.status != JobStatus::Running || job.array.is_some() {
+        return Ok(None);
+    }
+    let scratch_dir = common::require_scratch_dir(job, "NAMD Progress")?;
+    let (connection_manager, _username) = common::require_connection_with_username("NAMD Progress").await?;
+
+    let log_path = format!("{}/{}", scratch_dir, crate::ssh::JobDirectoryStructure::NAMD_OUTPUT_LOG);
+    let head = connection_manager.tail_remote_file(&log_path, 0).await?;
+    let mut parser = NamdProgressParser::default();
+    parser.feed(&head.content);
+
+    if head.has_more {
+        let tail_start = head.file_size.saturating_sub(NAMD_PROGRESS_TAIL_BYTES).max(head.offset);
+        let tail = connection_manager.tail_remote_file(&log_path, tail_start).await?;
+        parser.feed_from_middle(&tail.content);
+    }
+
+    Ok(parser.progress())
+}
+
+/// Read a SLURM log file, falling back to a gzipped copy (`.out.gz`) when the plain
+/// file has been compressed away on the cluster. Decompression happens server-side.
+async fn read_slurm_log(remote: &dyn RemoteExecutor, log_path: &str) -> Result<String> {
+    let compressed_path = compressed_log_path(log_path);
+
+    let plain_exists = remote.file_exists(log_path).await.unwrap_or(true);
+    if !plain_exists && remote.file_exists(&compressed_path).await.unwrap_or(false) {
+        log_debug!(category: "Log Fetch", message: "Reading compressed log", details: "{}", compressed_path);
+        return remote.read_remote_file(&compressed_path).await;
+    }
+
+    remote.read_remote_file(log_path).await
+}
+
+/// Path of the gzipped variant of a log file
+fn compressed_log_path(log_path: &str) -> String {
+    if crate::ssh::commands::is_gzip_path(log_path) {
+        log_path.to_string()
+    } else {
+        format!("{}.gz", log_path)
+    }
+}
+
+/// Check that a discovered job_info.json belongs to the directory it was found in
+///
+/// The directory name must match the stored job_id, unless the stored project_dir
+/// points at this exact directory (adopted jobs keep their original folder name).
+/// Returns the failure reason for a mismatch.
+pub fn discovered_job_mismatch(dir_name: &str, dir_path: &str, job: &JobInfo) -> Option<String> {
+    if job.job_id == dir_name || job.project_dir.as_deref() == Some(dir_path) {
+        return None;
+    }
+
+    Some(format!(
+        "job_info.json belongs to job '{}' but was found in directory '{}' (copied or renamed?)",
+        job.job_id, dir_name
+    ))
+}
+
+/// Job records found on the server, not yet checked against the database
+/// `candidates` passed every check; `failed_imports` names each directory that didn't and why
+pub struct ScannedJobs {
+    pub candidates: Vec<JobInfo>,
+    pub failed_imports: Vec<crate::types::response_data::FailedImport>,
+}
+
+/// Read and check job_info.json in every directory of the user's remote jobs folder
+/// `batch_size` files are read per remote command (clamped to 1..=MAX_DISCOVERY_BATCH_SIZE).
+/// Failures stay per-directory; only an unreadable jobs folder fails the scan
+pub async fn scan_job_directories(remote: &dyn RemoteExecutor, username: &str, batch_size: usize) -> Result<ScannedJobs> {
+    use crate::types::response_data::FailedImport;
 
     // Construct remote jobs directory path
     use crate::ssh::directory_structure::JobDirectoryStructure;
@@ -356,7 +857,7 @@ async fn discover_jobs(username: &str) -> Result<crate::types::response_data::Di
     log_debug!(category: "Job Discovery", message: "Scanning directory", details: "{}", remote_jobs_dir);
 
     // List directories in the jobs folder
-    let job_dirs = connection_manager.list_files(&remote_jobs_dir, true).await
+    let job_dirs = remote.list_files(&remote_jobs_dir, true).await
         .map_err(|e| {
             log_error!(category: "Job Discovery", message: "Failed to list directories", details: "{}", e);
             anyhow!("Failed to list job directories: {}", e)
@@ -368,15 +869,24 @@ async fn discover_jobs(username: &str) -> Result<crate::types::response_data::Di
 
     log_info!(category: "Job Discovery", message: "Found directories", details: "{} directories", job_dirs.len());
 
-    let mut imported_jobs = Vec::new();
+    let mut candidates = Vec::new();
     let mut failed_imports = Vec::new();
 
-    // Read job_info.json from each directory
-    for job_dir in job_dirs {
-        let job_info_path = format!("{}/{}/job_info.json", remote_jobs_dir, job_dir);
+    // Read job_info.json in batches, one remote command per batch (failures stay per-directory)
+    let mut reads = Vec::with_capacity(job_dirs.len());
+    for batch in job_dirs.chunks(clamp_discovery_batch_size(batch_size)) {
+        let paths: Vec<String> = batch.iter()
+            .map(|job_dir| format!("{}/{}/job_info.json", remote_jobs_dir, job_dir))
+            .collect();
+        match remote.read_remote_files(&paths).await {
+            Ok(results) => reads.extend(batch.iter().cloned().zip(results)),
+            // The batch command itself failed; each of its directories reports why
+            Err(e) => reads.extend(batch.iter().map(|job_dir| (job_dir.clone(), Err(anyhow!("{}", e))))),
+        }
+    }
 
-        // Try to read the job info file
-        let job_json = match connection_manager.read_remote_file(&job_info_path).await {
+    for (job_dir, read_result) in reads {
+        let job_json = match read_result {
             Ok(content) => content,
             Err(e) => {
                 let error_msg = format!("Could not read job_info.json: {}", e);
@@ -403,6 +913,39 @@ async fn discover_jobs(username: &str) -> Result<crate::types::response_data::Di
             }
         };
 
+        // A copied or renamed directory carries another job's identity - don't import it
+        let job_dir_path = format!("{}/{}", remote_jobs_dir, job_dir);
+        if let Some(reason) = discovered_job_mismatch(&job_dir, &job_dir_path, &job_info) {
+            log_warn!(category: "Job Discovery", message: "Skipping misfiled job", details: "{}: {}", job_dir, reason);
+            failed_imports.push(FailedImport {
+                directory: job_dir,
+                reason,
+            });
+            continue;
+        }
+
+        candidates.push(job_info);
+    }
+
+    // Import in creation order so the report doesn't depend on directory listing order
+    sort_by_created_at(&mut candidates);
+
+    Ok(ScannedJobs { candidates, failed_imports })
+}
+
+/// Internal helper to discover jobs from server
+/// Returns detailed report of imported jobs and failures
+async fn discover_jobs(remote: &dyn RemoteExecutor, username: &str) -> Result<crate::types::response_data::DiscoveryReport> {
+    use crate::types::response_data::{DiscoveryReport, MissingTemplate};
+
+    log_info!(category: "Job Discovery", message: "Starting automatic discovery", details: "user: {}", username);
+
+    let ScannedJobs { candidates, failed_imports } = scan_job_directories(remote, username, load_discovery_batch_size()).await?;
+
+    let mut imported_jobs = Vec::new();
+    let mut restored_templates = Vec::new();
+    let mut missing_templates = Vec::new();
+    for job_info in candidates {
         // Check if job already exists in database
         let job_id = job_info.job_id.clone();
         let job_name = job_info.job_name.clone();
@@ -416,20 +959,33 @@ async fn discover_jobs(username: &str) -> Result<crate::types::response_data::Di
             match db.load_job(&job_id_for_log) {
                 Ok(Some(_)) => {
                     log_debug!(category: "Job Discovery", message: "Job already exists, skipping", details: "{}", job_id_for_log);
-                    Ok(false)
+                    Ok(None)
                 }
                 Ok(None) => {
                     db.save_job(&job_info_clone)?;
                     log_info!(category: "Job Discovery", message: "Imported", details: "{} ({})", job_id_for_log, job_name_for_log);
-                    Ok(true)
+                    link_job_template(db, &job_info_clone).map(Some)
                 }
                 Err(e) => Err(e),
             }
         })?;
 
-        if imported {
-            imported_jobs.push(job_info);
+        let Some(linkage) = imported else { continue };
+        match linkage {
+            TemplateLinkage::Present => {}
+            TemplateLinkage::Restored => {
+                log_info!(category: "Job Discovery", message: "Restored template from job snapshot", details: "{} (from {})", job_info.template_id, job_info.job_id);
+                restored_templates.push(job_info.template_id.clone());
+            }
+            TemplateLinkage::Missing => {
+                log_warn!(category: "Job Discovery", message: "Imported job's template is missing", details: "{} uses {}", job_info.job_id, job_info.template_id);
+                missing_templates.push(MissingTemplate {
+                    job_id: job_info.job_id.clone(),
+                    template_id: job_info.template_id.clone(),
+                });
+            }
         }
+        imported_jobs.push(job_info);
     }
 
     log_info!(
@@ -451,6 +1007,676 @@ async fn discover_jobs(username: &str) -> Result<crate::types::response_data::Di
     Ok(DiscoveryReport {
         imported_jobs,
         failed_imports,
+        restored_templates,
+        missing_templates,
     })
 }
 
+/// How a discovered job's template_id lines up with the local templates
+#[derive(Debug, PartialEq)]
+pub enum TemplateLinkage {
+    /// The template exists locally, or the job (adopted) has none
+    Present,
+    /// The template was missing and has been re-created from the job's snapshot
+    Restored,
+    /// The template is missing and the job carries no snapshot of it
+    Missing,
+}
+
+/// Make sure a discovered job's template exists locally, restoring it from the job's snapshot if needed
+pub fn link_job_template(db: &JobDatabase, job: &JobInfo) -> Result<TemplateLinkage> {
+    if job.template_id.is_empty() || db.load_template(&job.template_id)?.is_some() {
+        return Ok(TemplateLinkage::Present);
+    }
+
+    match &job.template_snapshot {
+        Some(snapshot) if snapshot.id == job.template_id => {
+            // Snapshots are resolved, so the restored template stands alone without its parent
+            db.save_template(&crate::templates::Template { extends: None, ..snapshot.clone() })?;
+            Ok(TemplateLinkage::Restored)
+        }
+        _ => Ok(TemplateLinkage::Missing),
+    }
+}
+
+/// Settings key for how many job_info.json files discovery reads per remote command
+pub const DISCOVERY_BATCH_SIZE_SETTING: &str = "discovery_batch_size";
+
+/// job_info.json files discovery reads per remote command by default
+/// Commands on the one SSH session run one after another, so batching is what saves round trips
+pub const DEFAULT_DISCOVERY_BATCH_SIZE: usize = 16;
+
+/// Largest allowed batch; each batch's files come back in one command output
+pub const MAX_DISCOVERY_BATCH_SIZE: usize = 100;
+
+/// Keep a requested batch size within 1..=MAX_DISCOVERY_BATCH_SIZE
+pub fn clamp_discovery_batch_size(batch_size: usize) -> usize {
+    batch_size.clamp(1, MAX_DISCOVERY_BATCH_SIZE)
+}
+
+/// Load the discovery batch size, falling back to the default
+pub fn load_discovery_batch_size() -> usize {
+    with_database(|db| db.load_setting::<usize>(DISCOVERY_BATCH_SIZE_SETTING))
+        .ok()
+        .flatten()
+        .map(clamp_discovery_batch_size)
+        .unwrap_or(DEFAULT_DISCOVERY_BATCH_SIZE)
+}
+
+/// Persist the discovery batch size, clamped to the allowed range; returns the value saved
+pub fn save_discovery_batch_size(batch_size: usize) -> Result<usize> {
+    let batch_size = clamp_discovery_batch_size(batch_size);
+    with_database(|db| db.save_setting(DISCOVERY_BATCH_SIZE_SETTING, &batch_size))?;
+    Ok(batch_size)
+}
+
+/// Order jobs oldest first by created_at (RFC 3339 strings sort chronologically)
+fn sort_by_created_at(jobs: &mut [JobInfo]) {
+    jobs.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+}
+
+/// How long after completion a job without an actual cost keeps being retried
+/// sacct normally catches up within seconds; past this the record is assumed missing
+const ACTUAL_COST_RETRY_HOURS: i64 = 24;
+
+/// Whether a finished job still needs its actual cost read from sacct
+fn needs_actual_cost(job: &JobInfo, now: chrono::DateTime<chrono::Utc>) -> bool {
+    let finished_recently = job.completed_at.as_deref()
+        .and_then(|completed_at| chrono::DateTime::parse_from_rfc3339(completed_at).ok())
+        .is_some_and(|completed_at| now.signed_duration_since(completed_at) < chrono::Duration::hours(ACTUAL_COST_RETRY_HOURS));
+
+    matches!(job.status, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled)
+        && job.actual_cost.is_none()
+        && job.slurm_job_id.is_some()
+        && job.array.is_none()
+        && finished_recently
+}
+
+/// Record actual costs for finished jobs that completed before sacct had their elapsed time
+async fn backfill_actual_costs(remote: &dyn RemoteExecutor, jobs: &mut [JobInfo]) {
+    let now = chrono::Utc::now();
+    for job in jobs.iter_mut().filter(|job| needs_actual_cost(job, now)) {
+        if !crate::automations::job_completion::record_actual_cost(remote, job).await {
+            continue;
+        }
+        common::refresh_user_edits(job);
+        if let Err(e) = common::save_job_to_database(job, "Job Sync") {
+            log_error!(category: "Job Sync", message: "Failed to save actual cost", details: "{}: {}", job.job_id, e);
+        }
+    }
+}
+
+/// Build a warning for every SLURM job ID referenced by more than one local job
+///
+/// Duplicates can appear when discovery imports a job that a local record already
+/// tracks under a different job_id (e.g. after a resubmit bug).
+fn duplicate_slurm_id_warnings(jobs: &[JobInfo]) -> Vec<String> {
+    let mut jobs_by_slurm_id: std::collections::BTreeMap<&str, Vec<&str>> = std::collections::BTreeMap::new();
+    for job in jobs {
+        if let Some(slurm_id) = job.slurm_job_id.as_deref() {
+            jobs_by_slurm_id.entry(slurm_id).or_default().push(&job.job_id);
+        }
+    }
+
+    jobs_by_slurm_id.into_iter()
+        .filter(|(_, job_ids)| job_ids.len() > 1)
+        .map(|(slurm_id, job_ids)| format!(
+            "SLURM job {} is referenced by {} jobs: {}",
+            slurm_id, job_ids.len(), job_ids.join(", ")
+        ))
+        .collect()
+}
+
+/// Duplicate-ID warnings last shown as toasts
+static REPORTED_DUPLICATES: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+
+/// Store `warnings` as the last reported set; true when they differ from it
+fn replace_if_changed(reported: &std::sync::Mutex<Vec<String>>, warnings: &[String]) -> bool {
+    let mut reported = reported.lock().unwrap();
+    if reported.as_slice() == warnings {
+        return false;
+    }
+    *reported = warnings.to_vec();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job_with_slurm_id(job_id: &str, slurm_job_id: Option<&str>) -> JobInfo {
+        JobInfo {
+            job_id: job_id.to_string(),
+            job_name: job_id.to_string(),
+            status: JobStatus::Running,
+            slurm_job_id: slurm_job_id.map(str::to_string),
+            created_at: "2025-01-15T10:00:00Z".to_string(),
+            updated_at: None,
+            submitted_at: None,
+            completed_at: None,
+            project_dir: None,
+            scratch_dir: None,
+            error_info: None,
+            slurm_stdout: None,
+            slurm_stderr: None,
+            template_id: "test".to_string(),
+            template_values: std::collections::HashMap::new(),
+            slurm_config: crate::types::SlurmConfig::default(),
+            input_files: vec![],
+            output_files: vec![],
+            archived: false,
+            pinned: false,
+            tags: Vec::new(),
+            notes: None,
+            status_detail: None,
+            submitted_script: None,
+            template_snapshot: None,
+            rendered_config: None,
+            depends_on: None,
+            array: None,
+            notify_email: None,
+            notify_events: Vec::new(),
+            actual_cost: None,
+            namd_progress: None,
+            estimated_start: None,
+            queue_priority: None,
+        }
+    }
+
+    #[test]
+    fn test_no_warnings_for_unique_slurm_ids() {
+        let jobs = vec![
+            job_with_slurm_id("job_a", Some("1001")),
+            job_with_slurm_id("job_b", Some("1002")),
+            job_with_slurm_id("job_c", None),
+            job_with_slurm_id("job_d", None),
+        ];
+
+        assert!(duplicate_slurm_id_warnings(&jobs).is_empty());
+    }
+
+    #[test]
+    fn test_warns_on_duplicate_slurm_ids() {
+        let jobs = vec![
+            job_with_slurm_id("job_local", Some("1001")),
+            job_with_slurm_id("job_unique", Some("1002")),
+            job_with_slurm_id("job_imported", Some("1001")),
+            job_with_slurm_id("job_other_a", Some("2000")),
+            job_with_slurm_id("job_other_b", Some("2000")),
+            job_with_slurm_id("job_other_c", Some("2000")),
+        ];
+
+        let warnings = duplicate_slurm_id_warnings(&jobs);
+
+        assert_eq!(warnings, vec![
+            "SLURM job 1001 is referenced by 2 jobs: job_local, job_imported".to_string(),
+            "SLURM job 2000 is referenced by 3 jobs: job_other_a, job_other_b, job_other_c".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_needs_actual_cost() {
+        let now = chrono::Utc::now();
+        let finished = |hours_ago: i64| {
+            let mut job = job_with_slurm_id("job_done", Some("1001"));
+            job.status = JobStatus::Completed;
+            job.completed_at = Some((now - chrono::Duration::hours(hours_ago)).to_rfc3339());
+            job
+        };
+
+        assert!(needs_actual_cost(&finished(1), now));
+        // Past the retry window the sacct record is assumed missing
+        assert!(!needs_actual_cost(&finished(ACTUAL_COST_RETRY_HOURS + 1), now));
+
+        let mut recorded = finished(1);
+        recorded.actual_cost = Some(12.5);
+        assert!(!needs_actual_cost(&recorded, now));
+
+        let mut running = finished(1);
+        running.status = JobStatus::Running;
+        assert!(!needs_actual_cost(&running, now));
+
+        let mut never_submitted = finished(1);
+        never_submitted.slurm_job_id = None;
+        assert!(!needs_actual_cost(&never_submitted, now));
+    }
+
+    #[test]
+    fn test_slurm_log_path_matches_script_directives() {
+        assert_eq!(slurm_log_path("/scratch/alpine/u/job_1", "run", "123", None, LogStream::Stdout), "/scratch/alpine/u/job_1/run_123.out");
+        assert_eq!(slurm_log_path("/projects/u/job_1", "run", "123", None, LogStream::Stderr), "/projects/u/job_1/run_123.err");
+        // Array tasks: --output={name}_%A_%a.out
+        assert_eq!(slurm_log_path("/projects/u/job_1", "sweep", "123", Some(2), LogStream::Stdout), "/projects/u/job_1/sweep_123_2.out");
+    }
+
+    #[test]
+    fn test_compressed_log_path() {
+        assert_eq!(compressed_log_path("/projects/u/job/run_123.out"), "/projects/u/job/run_123.out.gz");
+        assert_eq!(compressed_log_path("/projects/u/job/run_123.err.gz"), "/projects/u/job/run_123.err.gz");
+    }
+
+    #[test]
+    fn test_duplicate_warnings_are_reported_once_per_change() {
+        let reported = std::sync::Mutex::new(Vec::new());
+        let duplicate = vec!["SLURM job 1001 is referenced by 2 jobs: job_a, job_b".to_string()];
+
+        assert!(!replace_if_changed(&reported, &[]));
+        assert!(replace_if_changed(&reported, &duplicate));
+        assert!(!replace_if_changed(&reported, &duplicate));
+        // Resolved, then back again
+        assert!(replace_if_changed(&reported, &[]));
+        assert!(replace_if_changed(&reported, &duplicate));
+    }
+
+    #[tokio::test]
+    async fn test_sync_reports_jobs_sharing_a_slurm_id() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let _database = crate::database::fresh_global_database().await;
+        for (job_id, created_at) in [("job_local", "2025-01-15T10:00:00Z"), ("job_imported", "2025-01-16T10:00:00Z")] {
+            let mut job = job_with_slurm_id(job_id, Some("5001"));
+            job.created_at = created_at.to_string();
+            with_database(|db| db.save_job(&job)).unwrap();
+        }
+        let mut remote = MockRemoteExecutor::new("testuser");
+        remote.respond_to("squeue", "5001|RUNNING|None\n");
+
+        let result = sync_all_jobs(&remote, false).await.unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.warnings, vec!["SLURM job 5001 is referenced by 2 jobs: job_imported, job_local".to_string()]);
+    }
+
+    #[test]
+    fn test_log_fetch_policy_on_completion() {
+        assert!(LogFetchPolicy::OnCompletion.allows(LogFetchTrigger::Completion));
+        assert!(!LogFetchPolicy::OnCompletion.allows(LogFetchTrigger::StatusView));
+    }
+
+    #[test]
+    fn test_log_fetch_policy_on_status_view() {
+        assert!(!LogFetchPolicy::OnStatusView.allows(LogFetchTrigger::Completion));
+        assert!(LogFetchPolicy::OnStatusView.allows(LogFetchTrigger::StatusView));
+    }
+
+    #[test]
+    fn test_log_fetch_policy_never() {
+        assert!(!LogFetchPolicy::Never.allows(LogFetchTrigger::Completion));
+        assert!(!LogFetchPolicy::Never.allows(LogFetchTrigger::StatusView));
+    }
+
+    #[test]
+    fn test_log_fetch_policy_default_and_serialization() {
+        // Default preserves the original fetch-on-completion behavior
+        assert_eq!(LogFetchPolicy::default(), LogFetchPolicy::OnCompletion);
+        assert_eq!(serde_json::to_string(&LogFetchPolicy::OnStatusView).unwrap(), "\"on_status_view\"");
+        assert_eq!(serde_json::from_str::<LogFetchPolicy>("\"never\"").unwrap(), LogFetchPolicy::Never);
+    }
+
+    #[tokio::test]
+    async fn test_status_view_log_fetch_follows_policy() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+        use crate::ssh::directory_structure::JobDirectoryStructure;
+
+        let _database = crate::database::fresh_global_database().await;
+        let project_dir = JobDirectoryStructure::project_dir("testuser", "log_policy_job");
+        let mut finished = job_with_slurm_id("log_policy_job", Some("6001"));
+        finished.status = JobStatus::Completed;
+        finished.project_dir = Some(project_dir.clone());
+
+        for (policy, expected_reads) in [(LogFetchPolicy::Never, 0), (LogFetchPolicy::OnCompletion, 0), (LogFetchPolicy::OnStatusView, 2)] {
+            with_database(|db| db.save_setting(LOG_FETCH_POLICY_SETTING, &policy)).unwrap();
+            let remote = MockRemoteExecutor::new("testuser");
+            remote.filesystem.lock().unwrap()
+                .add_text_file(&slurm_log_path(&project_dir, "log_policy_job", "6001", None, LogStream::Stdout), "stdout")
+                .add_text_file(&slurm_log_path(&project_dir, "log_policy_job", "6001", None, LogStream::Stderr), "stderr");
+
+            let mut job = finished.clone();
+            let fetched = fetch_logs_on_status_view(&remote, &mut job).await.unwrap();
+
+            let log_reads = remote.executed().iter().filter(|cmd| cmd.contains("cat ")).count();
+            assert_eq!(log_reads, expected_reads, "{:?}", policy);
+            assert_eq!(fetched, expected_reads > 0, "{:?}", policy);
+        }
+
+        // Under OnStatusView, jobs still running or with cached logs are left alone
+        let remote = MockRemoteExecutor::new("testuser");
+        let mut running = finished.clone();
+        running.status = JobStatus::Running;
+        let mut cached = finished.clone();
+        cached.slurm_stdout = Some("stdout".to_string());
+        cached.slurm_stderr = Some("stderr".to_string());
+        assert!(!fetch_logs_on_status_view(&remote, &mut running).await.unwrap());
+        assert!(!fetch_logs_on_status_view(&remote, &mut cached).await.unwrap());
+        assert!(remote.executed().is_empty());
+    }
+
+    #[test]
+    fn test_discovered_job_matching_directory() {
+        let base = "/projects/testuser/namdrunner_jobs";
+        let job = job_with_slurm_id("job_123", None);
+
+        assert!(discovered_job_mismatch("job_123", &format!("{}/job_123", base), &job).is_none());
+
+        // Adopted jobs record their real directory even when it's named differently
+        let mut adopted = job_with_slurm_id("sim_a_1700000000", None);
+        adopted.project_dir = Some(format!("{}/sim_a", base));
+        assert!(discovered_job_mismatch("sim_a", &format!("{}/sim_a", base), &adopted).is_none());
+    }
+
+    #[test]
+    fn test_discovered_job_mismatched_directory() {
+        let base = "/projects/testuser/namdrunner_jobs";
+
+        // Directory copied to job_123_copy still carries job_123's metadata
+        let mut copied = job_with_slurm_id("job_123", None);
+        copied.project_dir = Some(format!("{}/job_123", base));
+        let reason = discovered_job_mismatch("job_123_copy", &format!("{}/job_123_copy", base), &copied)
+            .expect("copied directory should be flagged");
+        assert!(reason.contains("'job_123'"));
+        assert!(reason.contains("'job_123_copy'"));
+
+        // No project_dir to vouch for it either
+        let renamed = job_with_slurm_id("old_name", None);
+        assert!(discovered_job_mismatch("new_name", &format!("{}/new_name", base), &renamed).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_apply_slurm_status_leaves_unchanged_job_alone() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let remote = MockRemoteExecutor::new("testuser");
+        let slurm_sync = SlurmStatusSync::with_executor(&remote);
+        let job = job_with_slurm_id("job_001", Some("1001"));
+
+        let result = apply_slurm_status(&remote, &slurm_sync, &job, "1001", &JobStatus::Running, None, &[]).await.unwrap();
+
+        assert!(!result.updated);
+        assert_eq!(result.new_status, JobStatus::Running);
+        assert!(remote.executed().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sync_single_job_records_a_finished_job() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let _database = crate::database::fresh_global_database().await;
+        let job = job_with_slurm_id("single_sync_job", Some("4001"));
+        with_database(|db| db.save_job(&job)).unwrap();
+
+        // Gone from the queue, so the state comes from sacct
+        let mut remote = MockRemoteExecutor::new("testuser");
+        remote.respond_to("squeue", "").respond_to("sacct", "4001|COMPLETED\n");
+
+        let synced = sync_single_job(&remote, "single_sync_job").await.unwrap();
+
+        assert_eq!(synced.status, JobStatus::Completed);
+        assert!(synced.completed_at.is_some());
+        let stored = with_database(|db| db.load_job("single_sync_job")).unwrap().unwrap();
+        assert_eq!(stored.status, JobStatus::Completed);
+        assert!(remote.executed().iter().any(|cmd| cmd.starts_with("sacct")));
+    }
+
+    #[test]
+    fn test_suggest_poll_interval_backs_off_while_quiet() {
+        let intervals: Vec<u64> = (0..7).map(|quiet| suggest_poll_interval(quiet, false)).collect();
+        assert_eq!(intervals, vec![30, 60, 120, 240, 480, 600, 600]);
+        assert_eq!(suggest_poll_interval(u32::MAX, false), MAX_POLL_SECS);
+
+        // A likely transition overrides the back-off
+        assert_eq!(suggest_poll_interval(5, true), MIN_POLL_SECS);
+    }
+
+    #[test]
+    fn test_only_scheduled_quiet_syncs_extend_the_back_off() {
+        assert_eq!(next_quiet_syncs(3, false, true), 4);
+        // A manual refresh that found nothing new keeps the current gap
+        assert_eq!(next_quiet_syncs(3, false, false), 3);
+        assert_eq!(next_quiet_syncs(3, true, false), 0);
+        assert_eq!(next_quiet_syncs(3, true, true), 0);
+        assert_eq!(next_quiet_syncs(u32::MAX, false, true), u32::MAX);
+    }
+
+    #[test]
+    fn test_has_recent_submission() {
+        let now = chrono::DateTime::parse_from_rfc3339("2025-01-15T12:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        let mut job = job_with_slurm_id("job_001", Some("1001"));
+        assert!(!has_recent_submission(std::slice::from_ref(&job), now));
+
+        job.submitted_at = Some("2025-01-15T11:58:00Z".to_string());
+        assert!(has_recent_submission(std::slice::from_ref(&job), now));
+
+        job.submitted_at = Some("2025-01-15T11:00:00Z".to_string());
+        assert!(!has_recent_submission(std::slice::from_ref(&job), now));
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_syncs_run_one_query_round() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let mut remote = MockRemoteExecutor::new("testuser");
+        remote.respond_to("squeue", "1001|RUNNING|None\n");
+        let lock = tokio::sync::Mutex::new(None);
+        let job_ids = vec!["1001".to_string()];
+        let query = || async {
+            // Stay mid-sync long enough for the second call to arrive
+            tokio::task::yield_now().await;
+            SlurmStatusSync::with_executor(&remote).query_job_statuses(&job_ids).await.unwrap().len()
+        };
+
+        let (first, second) = tokio::join!(run_or_join(&lock, query()), run_or_join(&lock, query()));
+
+        assert_eq!(first, (1, false));
+        assert_eq!(second, (1, true));
+        assert_eq!(remote.executed().len(), 1);
+
+        // Once the first sync is done the next one runs normally
+        assert_eq!(run_or_join(&lock, query()).await, (1, false));
+        assert_eq!(remote.executed().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_joined_sync_shares_the_running_sync_failure() {
+        let lock = tokio::sync::Mutex::new(None);
+        let failing = async {
+            tokio::task::yield_now().await;
+            Err::<u32, String>("Failed to query SLURM job status".to_string())
+        };
+        let (first, second) = tokio::join!(run_or_join(&lock, failing), run_or_join(&lock, async { Ok(1) }));
+
+        assert_eq!(first, (Err("Failed to query SLURM job status".to_string()), false));
+        assert_eq!(second, (Err("Failed to query SLURM job status".to_string()), true));
+    }
+
+    #[tokio::test]
+    async fn test_sync_queued_behind_a_single_job_sync_runs_itself() {
+        let lock = tokio::sync::Mutex::new(Some(7));
+        let mut single = lock.lock().await;
+        *single = None;
+
+        let waiting = run_or_join(&lock, async { 1 });
+        let release = async {
+            tokio::task::yield_now().await;
+            drop(single);
+        };
+        let (output, ()) = tokio::join!(waiting, release);
+
+        assert_eq!(output, (1, false));
+    }
+
+    #[test]
+    fn test_link_job_template_restores_from_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = JobDatabase::new(&dir.path().join("test.db").to_string_lossy()).unwrap();
+        let snapshot = crate::templates::Template {
+            id: "equilibration".to_string(),
+            name: "Equilibration".to_string(),
+            description: String::new(),
+            namd_config_template: "run {{steps}}".to_string(),
+            variables: std::collections::HashMap::new(),
+            extends: Some("base_md".to_string()),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+        };
+
+        let mut job = job_with_slurm_id("job_001", Some("1001"));
+        job.template_id = "equilibration".to_string();
+        assert_eq!(link_job_template(&db, &job).unwrap(), TemplateLinkage::Missing);
+
+        job.template_snapshot = Some(snapshot);
+        assert_eq!(link_job_template(&db, &job).unwrap(), TemplateLinkage::Restored);
+        let restored = db.load_template("equilibration").unwrap().unwrap();
+        assert_eq!(restored.namd_config_template, "run {{steps}}");
+        assert_eq!(restored.extends, None);
+
+        // Once restored (or for adopted jobs without a template) there is nothing to do
+        assert_eq!(link_job_template(&db, &job).unwrap(), TemplateLinkage::Present);
+        job.template_id = String::new();
+        assert_eq!(link_job_template(&db, &job).unwrap(), TemplateLinkage::Present);
+    }
+
+    #[tokio::test]
+    async fn test_scan_job_directories_checks_each_directory() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+        use crate::ssh::directory_structure::JobDirectoryStructure;
+
+        let base = JobDirectoryStructure::project_base("testuser");
+        let older = job_with_slurm_id("job_old", Some("1001"));
+        let mut newer = job_with_slurm_id("job_new", Some("1002"));
+        newer.created_at = "2025-03-01T00:00:00Z".to_string();
+
+        let remote = MockRemoteExecutor::new("testuser");
+        remote.filesystem.lock().unwrap()
+            .add_directory(&base, 0o755)
+            .add_directory(&format!("{}/job_new", base), 0o755)
+            .add_text_file(&format!("{}/job_new/job_info.json", base), &serde_json::to_string(&newer).unwrap())
+            .add_directory(&format!("{}/job_old", base), 0o755)
+            .add_text_file(&format!("{}/job_old/job_info.json", base), &serde_json::to_string(&older).unwrap())
+            .add_directory(&format!("{}/job_empty", base), 0o755)
+            .add_directory(&format!("{}/job_broken", base), 0o755)
+            .add_text_file(&format!("{}/job_broken/job_info.json", base), "{not json")
+            .add_directory(&format!("{}/job_old_copy", base), 0o755)
+            .add_text_file(&format!("{}/job_old_copy/job_info.json", base), &serde_json::to_string(&older).unwrap())
+            .add_text_file(&format!("{}/notes.txt", base), "not a job directory");
+
+        let scanned = scan_job_directories(&remote, "testuser", DEFAULT_DISCOVERY_BATCH_SIZE).await.unwrap();
+
+        let ids: Vec<&str> = scanned.candidates.iter().map(|job| job.job_id.as_str()).collect();
+        assert_eq!(ids, vec!["job_old", "job_new"]);
+
+        let failures: Vec<(&str, &str)> = scanned.failed_imports.iter()
+            .map(|failure| (failure.directory.as_str(), failure.reason.as_str()))
+            .collect();
+        assert_eq!(failures.len(), 3);
+        assert!(failures[0].0 == "job_empty" && failures[0].1.starts_with("Could not read job_info.json"));
+        assert!(failures[1].0 == "job_broken" && failures[1].1.starts_with("Invalid JSON"));
+        assert!(failures[2].0 == "job_old_copy" && failures[2].1.contains("copied or renamed"));
+    }
+
+    #[tokio::test]
+    async fn test_scan_job_directories_reads_in_batched_round_trips() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+        use crate::ssh::directory_structure::JobDirectoryStructure;
+
+        let base = JobDirectoryStructure::project_base("testuser");
+        let remote = MockRemoteExecutor::new("testuser");
+        {
+            let mut filesystem = remote.filesystem.lock().unwrap();
+            filesystem.add_directory(&base, 0o755);
+            for n in 0..5 {
+                let mut job = job_with_slurm_id(&format!("job_{:02}", n), None);
+                job.created_at = format!("2025-01-{:02}T00:00:00Z", n + 1);
+                filesystem
+                    .add_directory(&format!("{}/{}", base, job.job_id), 0o755)
+                    .add_text_file(&format!("{}/{}/job_info.json", base, job.job_id), &serde_json::to_string(&job).unwrap());
+            }
+        }
+
+        let scanned = scan_job_directories(&remote, "testuser", 2).await.unwrap();
+
+        assert_eq!(scanned.candidates.len(), 5);
+        assert!(scanned.failed_imports.is_empty());
+        // Two full batches and the one left over, each a single command
+        let batches: Vec<usize> = remote.executed().iter().map(|command| command.matches("job_info.json").count()).collect();
+        assert_eq!(batches, vec![2, 2, 1]);
+
+        // Out-of-range sizes are clamped, never zero
+        let scanned = scan_job_directories(&remote, "testuser", 0).await.unwrap();
+        assert_eq!(scanned.candidates.len(), 5);
+        assert_eq!(remote.executed().len(), 3 + 5);
+    }
+
+    #[test]
+    fn test_clamp_discovery_batch_size() {
+        assert_eq!(clamp_discovery_batch_size(0), 1);
+        assert_eq!(clamp_discovery_batch_size(DEFAULT_DISCOVERY_BATCH_SIZE), DEFAULT_DISCOVERY_BATCH_SIZE);
+        assert_eq!(clamp_discovery_batch_size(usize::MAX), MAX_DISCOVERY_BATCH_SIZE);
+    }
+
+    #[tokio::test]
+    async fn test_load_slurm_logs_reads_each_array_task() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let mut job = job_with_slurm_id("job_sweep", Some("555"));
+        job.status = JobStatus::Completed;
+        job.project_dir = Some("/projects/testuser/namdrunner_jobs/job_sweep".to_string());
+        job.array = Some(crate::types::JobArray {
+            variable: "temperature".to_string(),
+            values: vec![serde_json::json!(300), serde_json::json!(310), serde_json::json!(320)],
+            task_statuses: Vec::new(),
+        });
+
+        let dir = "/projects/testuser/namdrunner_jobs/job_sweep";
+        let remote = MockRemoteExecutor::new("testuser");
+        remote.filesystem.lock().unwrap()
+            .add_text_file(&format!("{}/job_sweep_555_0.out", dir), "task zero")
+            .add_text_file(&format!("{}/job_sweep_555_2.out", dir), "task two")
+            .add_text_file(&format!("{}/job_sweep_555_1.err", dir), "oom");
+
+        load_slurm_logs(&remote, &mut job, false).await.unwrap();
+
+        assert_eq!(job.slurm_stdout.as_deref(), Some("==> task 0 <==\ntask zero\n==> task 2 <==\ntask two"));
+        assert_eq!(job.slurm_stderr.as_deref(), Some("==> task 1 <==\noom"));
+    }
+
+    #[test]
+    fn test_validate_array_task() {
+        let plain = job_with_slurm_id("job_plain", Some("1"));
+        assert!(validate_array_task(&plain, None).is_ok());
+        assert!(validate_array_task(&plain, Some(0)).is_err());
+
+        let mut sweep = job_with_slurm_id("job_sweep", Some("2"));
+        sweep.array = Some(crate::types::JobArray {
+            variable: "temperature".to_string(),
+            values: vec![serde_json::json!(300), serde_json::json!(310)],
+            task_statuses: Vec::new(),
+        });
+        assert!(validate_array_task(&sweep, Some(1)).is_ok());
+        assert!(validate_array_task(&sweep, Some(2)).is_err());
+        assert!(validate_array_task(&sweep, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scan_job_directories_requires_the_jobs_folder() {
+        let remote = crate::ssh::test_utils::MockRemoteExecutor::new("testuser");
+        let error = scan_job_directories(&remote, "testuser", DEFAULT_DISCOVERY_BATCH_SIZE).await.err().unwrap();
+        assert!(error.to_string().contains("Failed to list job directories"));
+    }
+
+    #[test]
+    fn test_sort_by_created_at() {
+        let mut jobs = vec![
+            job_with_slurm_id("job_mid", None),
+            job_with_slurm_id("job_new", None),
+            job_with_slurm_id("job_old", None),
+        ];
+        jobs[0].created_at = "2025-02-01T00:00:00Z".to_string();
+        jobs[1].created_at = "2025-03-01T00:00:00Z".to_string();
+        jobs[2].created_at = "2025-01-01T00:00:00Z".to_string();
+
+        sort_by_created_at(&mut jobs);
+
+        let ids: Vec<&str> = jobs.iter().map(|j| j.job_id.as_str()).collect();
+        assert_eq!(ids, vec!["job_old", "job_mid", "job_new"]);
+    }
+}
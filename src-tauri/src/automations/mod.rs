@@ -2,19 +2,31 @@
 // Provides progress tracking through callbacks while maintaining direct function patterns
 
 pub mod job_creation;
+pub mod job_rerun;
 pub mod job_submission;
 pub mod job_completion;
 pub mod job_deletion;
+pub mod job_cancellation;
+pub mod job_hold;
 pub mod job_sync;
+pub mod job_retention;
+pub mod job_export;
+pub mod job_adoption;
+pub mod temp_sweep;
+pub mod cluster_discovery;
 pub mod file_operations;
 pub mod common;
 
 // Re-export simplified automation functions with progress reporting
 pub use job_creation::execute_job_creation_with_progress;
-pub use job_submission::execute_job_submission_with_progress;
+pub use job_rerun::execute_job_rerun;
+pub use job_submission::{execute_job_submission_with_progress, execute_batch_submission, execute_dry_run_submission};
 pub use job_completion::execute_job_completion;  // Internal automatic completion
 pub use job_deletion::execute_job_deletion;
+pub use job_cancellation::execute_job_cancellation;
+pub use job_hold::{execute_job_hold, execute_job_release};
+pub use job_adoption::execute_job_adoption;
 pub use job_sync::{sync_all_jobs, load_slurm_logs, JobSyncResult};
 pub use file_operations::{
-    download_job_file, download_files_zip, validate_upload_file
+    download_job_file, download_files_zip, validate_upload_file, check_remote_inodes, check_disk_space
 };
\ No newline at end of file
@@ -0,0 +1,127 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::ssh::SftpFileEntry;
+use crate::ssh::commands::{temp_file_job_id, REMOTE_TEMP_DIR};
+use crate::database::with_database;
+use crate::automations::{common, file_operations};
+use crate::{log_info, log_debug, log_error};
+
+/// Temp files older than this are assumed orphaned (no dry run takes this long)
+pub const STALE_TEMP_AGE: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// Pick orphaned temp files from a listing of the remote temp directory
+///
+/// A file is only selected when its name follows the dry-run script pattern, the
+/// embedded job ID belongs to one of our own jobs, and its mtime is older than
+/// `max_age`. The temp directory is shared with other users and tools, so
+/// anything that doesn't match all three is left alone. Returns full paths.
+pub fn select_stale_temp_files(
+    entries: &[SftpFileEntry],
+    known_job_ids: &HashSet<String>,
+    now_secs: u64,
+    max_age: Duration,
+) -> Vec<String> {
+    entries.iter()
+        .filter(|entry| !entry.is_directory)
+        .filter(|entry| temp_file_job_id(&entry.name).is_some_and(|job_id| known_job_ids.contains(job_id)))
+        .filter(|entry| entry.modified_time.is_some_and(|mtime| now_secs.saturating_sub(mtime) >= max_age.as_secs()))
+        .map(|entry| format!("{}/{}", REMOTE_TEMP_DIR, entry.name))
+        .collect()
+}
+
+/// Remove dry-run scripts left behind by dry runs the connection dropped out of
+/// Returns the paths removed (best effort - individual failures are logged)
+pub async fn sweep_orphaned_temp_files() -> Result<Vec<String>> {
+    let (connection_manager, _username) = common::require_connection_with_username("Temp Sweep").await?;
+
+    let entries = connection_manager.list_files(REMOTE_TEMP_DIR, false).await?;
+    let known_job_ids: HashSet<String> = with_database(|db| db.load_all_jobs())?
+        .jobs
+        .into_iter()
+        .map(|job| job.job_id)
+        .collect();
+
+    let now_secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+    let stale = select_stale_temp_files(&entries, &known_job_ids, now_secs, STALE_TEMP_AGE);
+
+    if stale.is_empty() {
+        log_debug!(category: "Temp Sweep", message: "No orphaned temp files found");
+        return Ok(stale);
+    }
+
+    for path in &stale {
+        file_operations::cleanup_temp_file(connection_manager, path).await;
+    }
+    log_info!(category: "Temp Sweep", message: "Removed orphaned temp files", details: "{}", stale.join(", "));
+
+    Ok(stale)
+}
+
+/// Run the sweep in the background after a successful connect
+pub fn start_temp_sweep() {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = sweep_orphaned_temp_files().await {
+            log_error!(category: "Temp Sweep", message: "Temp file sweep failed", details: "{}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_800_000_000;
+    const HOUR: u64 = 60 * 60;
+
+    fn entry(name: &str, age_secs: Option<u64>) -> SftpFileEntry {
+        SftpFileEntry {
+            name: name.to_string(),
+            path: format!("{}/{}", REMOTE_TEMP_DIR, name),
+            size: 1024,
+            is_directory: false,
+            permissions: 0o644,
+            modified_time: age_secs.map(|age| NOW - age),
+        }
+    }
+
+    fn known(ids: &[&str]) -> HashSet<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_selects_stale_scripts_for_known_jobs() {
+        let entries = vec![
+            entry("namdrunner_dryrun_job_1.sbatch", Some(13 * HOUR)),
+            entry("namdrunner_dryrun_job_2.sbatch", Some(48 * HOUR)),
+            entry("namdrunner_dryrun_job_3.sbatch", Some(HOUR)),
+        ];
+
+        let stale = select_stale_temp_files(&entries, &known(&["job_1", "job_2", "job_3"]), NOW, STALE_TEMP_AGE);
+
+        assert_eq!(stale, vec!["/tmp/namdrunner_dryrun_job_1.sbatch", "/tmp/namdrunner_dryrun_job_2.sbatch"]);
+    }
+
+    #[test]
+    fn test_never_touches_non_matching_files() {
+        let mut directory = entry("namdrunner_dryrun_job_1.sbatch", Some(100 * HOUR));
+        directory.is_directory = true;
+        let entries = vec![
+            // Not our naming pattern
+            entry("job_1.sbatch", Some(100 * HOUR)),
+            entry("namdrunner_dryrun_job_1.sh", Some(100 * HOUR)),
+            entry("ssh-agent.sock", Some(100 * HOUR)),
+            entry("namdrunner_dryrun_job;rm.sbatch", Some(100 * HOUR)),
+            // Download archives from earlier versions are no longer ours to clear
+            entry("namdrunner_outputs_job_1.zip", Some(100 * HOUR)),
+            // Our pattern, but another user's (or unknown) job
+            entry("namdrunner_dryrun_someone_else.sbatch", Some(100 * HOUR)),
+            // Our pattern and job, but unknown age or a directory
+            entry("namdrunner_dryrun_job_1.sbatch", None),
+            directory,
+        ];
+
+        assert!(select_stale_temp_files(&entries, &known(&["job_1"]), NOW, STALE_TEMP_AGE).is_empty());
+    }
+}
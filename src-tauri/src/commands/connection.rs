@@ -1,17 +1,109 @@
 use crate::types::*;
-use crate::types::response_data::ConnectionStatus;
-use crate::ssh::get_connection_manager;
+use crate::types::response_data::{ConnectionPing, ConnectionStatus};
+use crate::ssh::{get_connection_manager, ConnectionConfig, Credentials, NetworkPreset, TimeoutOverrides};
+use crate::ssh::diagnostics::{self, ConnectionDiagnosis};
+use crate::ssh::interactive::{self, InteractiveAuth};
+use crate::ssh::host_keys::HostKeyStore;
+use crate::security::{keychain, SecurePassword};
+use tauri::Emitter;
+use crate::database::with_database;
+use crate::cluster;
 use crate::{log_info, log_debug, log_error};
 
+/// Settings key for the last selected network preset
+pub const NETWORK_PRESET_SETTING: &str = "network_preset";
+
+/// Settings key for a keepalive interval chosen with set_connection_keepalive
+pub const KEEPALIVE_INTERVAL_SETTING: &str = "keepalive_interval";
+
+/// Settings key for connect/file-transfer timeout overrides
+pub const TIMEOUT_OVERRIDES_SETTING: &str = "connection_timeouts";
+
+/// How many recent connection targets are kept
+pub const MAX_RECENT_CONNECTIONS: usize = 10;
+
 #[tauri::command(rename_all = "snake_case")]
-pub async fn connect_to_cluster(params: ConnectParams) -> ApiResult<SessionInfo> {
-    log_info!(category: "Connection", message: "Starting connection", details: "Host: {}, User: {}", params.host, params.username);
-    let port = 22;
+pub async fn connect_to_cluster(app_handle: tauri::AppHandle, mut params: ConnectParams) -> ApiResult<SessionInfo> {
+    let (host, port) = match cluster::resolve_login_target(&params.host, params.port, cluster::active_cluster_profile().as_ref()) {
+        Ok(target) => target,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+    log_info!(category: "Connection", message: "Starting connection", details: "Host: {}:{}, User: {}", host, port, params.username);
 
-    match get_connection_manager().connect(params.host.clone(), port, params.username.clone(), &params.password).await {
+    let timeout_overrides = match resolve_timeout_overrides(params.timeout_overrides()) {
+        Ok(overrides) => overrides,
+        Err(e) => return ApiResult::error(e),
+    };
+
+    let config = match resolve_network_preset(params.network_preset) {
+        Some(preset) => {
+            log_debug!(category: "Connection", message: "Using network preset", details: "{:?}", preset);
+            ConnectionConfig::for_preset(preset)
+        }
+        None => ConnectionConfig::default(),
+    };
+    let config = ConnectionConfig {
+        jump_host: params.jump_host.clone(),
+        auto_reconnect: params.auto_reconnect,
+        // An explicitly chosen keepalive wins over the preset's
+        keepalive_interval: saved_keepalive_interval().unwrap_or(config.keepalive_interval),
+        ..config
+    };
+    // Explicit timeouts likewise win over the preset's
+    let config = config.with_timeout_overrides(timeout_overrides);
+    if !timeout_overrides.is_empty() {
+        log_debug!(category: "Connection", message: "Using timeout overrides", details: "connect {}s, file transfer {}s", config.timeout, config.file_transfer_timeout);
+    }
+    if let Some(jump) = &config.jump_host {
+        log_debug!(category: "Connection", message: "Using jump host", details: "{}:{}", jump.host, jump.port);
+    }
+
+    // A password login with no password typed uses the one saved in the OS keychain, if any
+    let password_login = matches!(params.auth_method, AuthMethod::Password);
+    let mut used_saved_password = false;
+    if password_login && params.password.is_empty() {
+        if let Some(saved) = load_saved_password(&host, &params.username).await {
+            log_debug!(category: "Connection", message: "Using password saved in the OS keychain");
+            params.password = saved;
+            used_saved_password = true;
+        }
+    }
+
+    let manager = get_connection_manager();
+    let username = params.username.clone();
+    let result = match params.credentials() {
+        Some(Credentials::Password(password)) => manager.connect(host, port, username, password, config).await,
+        Some(Credentials::KeyFile { private_key, passphrase }) => {
+            log_debug!(category: "Connection", message: "Using key authentication", details: "{}", private_key.display());
+            manager.connect_with_key(host, port, username, Some(private_key), passphrase, config).await
+        }
+        Some(Credentials::Agent) => {
+            log_debug!(category: "Connection", message: "Using SSH agent authentication");
+            manager.connect_with_key(host, port, username, None, None, config).await
+        }
+        // Prompts go to the frontend as events; answers come back through respond_to_auth_challenge
+        Some(Credentials::KeyboardInteractive(_)) | None => {
+            log_debug!(category: "Connection", message: "Using keyboard-interactive authentication");
+            let auth = std::sync::Arc::new(InteractiveAuth::new(move |challenge| {
+                let _ = app_handle.emit(interactive::AUTH_CHALLENGE_EVENT, challenge);
+            }, interactive::CHALLENGE_RESPONSE_TIMEOUT));
+            manager.connect_interactive(host, port, username, &auth, config).await
+        }
+    };
+
+    match result {
         Ok(connection_info) => {
             log_info!(category: "Connection", message: "Successfully connected to cluster", show_toast: true);
 
+            // Clean up dry-run scripts orphaned by a previous crash or dropped connection
+            crate::automations::temp_sweep::start_temp_sweep();
+
+            remember_recent_connection(&connection_info.host, port, &connection_info.username, &connection_info.connected_at);
+
+            if password_login && params.remember_password && !used_saved_password {
+                save_password(&connection_info.host, &connection_info.username, &params.password).await;
+            }
+
             let session_info = SessionInfo {
                 host: connection_info.host,
                 username: connection_info.username,
@@ -23,7 +115,7 @@ pub async fn connect_to_cluster(params: ConnectParams) -> ApiResult<SessionInfo>
         Err(e) => {
             log_error!(category: "Connection", message: "Connection failed", details: "Error: {}", e);
 
-            let error_message = if let Some(ssh_err) = e.downcast_ref::<crate::ssh::errors::SSHError>() {
+            let error_message = if let Some(ssh_err) = crate::ssh::errors::find_ssh_error(&e) {
                 let conn_error = crate::ssh::errors::map_ssh_error(ssh_err);
                 log_debug!(category: "Connection", message: "Error details", details: "Category: {} (Code: {})", conn_error.category, conn_error.code);
 
@@ -39,11 +131,279 @@ pub async fn connect_to_cluster(params: ConnectParams) -> ApiResult<SessionInfo>
                 generic_msg
             };
 
+            if used_saved_password {
+                return ApiResult::error(format!("{} (used the password saved in the OS keychain - enter it again if it has changed)", error_message));
+            }
             ApiResult::error(error_message)
         }
     }
 }
 
+/// Password saved in the OS keychain for this host+username; keychain errors are logged and treated as none
+/// Keychain calls can block on an unlock prompt, so they run off the async runtime
+async fn load_saved_password(host: &str, username: &str) -> Option<SecurePassword> {
+    let (host, username) = (host.to_string(), username.to_string());
+    match tokio::task::spawn_blocking(move || keychain::load_password(&host, &username)).await {
+        Ok(Ok(password)) => password,
+        Ok(Err(e)) => {
+            log_debug!(category: "Connection", message: "No saved password available", details: "{}", e);
+            None
+        }
+        Err(e) => {
+            log_error!(category: "Connection", message: "Keychain lookup failed to run", details: "{}", e);
+            None
+        }
+    }
+}
+
+/// Save the password after a "remember me" login; failure doesn't affect the connection
+async fn save_password(host: &str, username: &str, password: &SecurePassword) {
+    let (host, username, password) = (host.to_string(), username.to_string(), password.clone());
+    match tokio::task::spawn_blocking(move || keychain::store_password(&host, &username, &password)).await {
+        Ok(Ok(())) => log_info!(category: "Connection", message: "Password saved to the OS keychain"),
+        Ok(Err(e)) => log_error!(category: "Connection", message: "Failed to save password", details: "{}", e, show_toast: true),
+        Err(e) => log_error!(category: "Connection", message: "Keychain save failed to run", details: "{}", e),
+    }
+}
+
+/// Save a password in the OS keychain for a login target, for password logins that leave it blank
+/// An empty host means the active cluster profile's login server
+#[tauri::command(rename_all = "snake_case")]
+pub async fn store_credential(host: String, username: String, password: SecurePassword) -> ApiResult<()> {
+    let host = match cluster::resolve_login_target(&host, None, cluster::active_cluster_profile().as_ref()) {
+        Ok((host, _port)) => host,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    let target = format!("{}@{}", username, host);
+    match tokio::task::spawn_blocking(move || keychain::store_password(&host, &username, &password)).await {
+        Ok(Ok(())) => {
+            log_info!(category: "Connection", message: "Password saved to the OS keychain", details: "{}", target);
+            ApiResult::success(())
+        }
+        Ok(Err(e)) => ApiResult::error(e.to_string()),
+        Err(e) => ApiResult::error(format!("Keychain save failed to run: {}", e)),
+    }
+}
+
+/// Remove a saved password from the OS keychain; returns false when none was saved
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_credential(host: String, username: String) -> ApiResult<bool> {
+    let host = match cluster::resolve_login_target(&host, None, cluster::active_cluster_profile().as_ref()) {
+        Ok((host, _port)) => host,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    let target = format!("{}@{}", username, host);
+    match tokio::task::spawn_blocking(move || keychain::delete_password(&host, &username)).await {
+        Ok(Ok(removed)) => {
+            log_info!(category: "Connection", message: "Saved password removed", details: "{} (found: {})", target, removed);
+            ApiResult::success(removed)
+        }
+        Ok(Err(e)) => ApiResult::error(e.to_string()),
+        Err(e) => ApiResult::error(format!("Keychain delete failed to run: {}", e)),
+    }
+}
+
+/// Answer the keyboard-interactive challenge a login is waiting on (one answer per prompt)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn respond_to_auth_challenge(responses: Vec<String>) -> ApiResult<()> {
+    match interactive::submit_challenge_responses(responses) {
+        Ok(()) => ApiResult::success(()),
+        Err(e) => {
+            log_debug!(category: "Connection", message: "Challenge response not delivered", details: "{}", e);
+            ApiResult::error(e.to_string())
+        }
+    }
+}
+
+/// Accept the changed host key a connect was just rejected for, then reconnect to use it
+/// Returns the fingerprint that is now trusted
+#[tauri::command(rename_all = "snake_case")]
+pub async fn trust_host_key(host: String, port: Option<u16>) -> ApiResult<String> {
+    let port = port.unwrap_or(22);
+    let result = HostKeyStore::default_location().and_then(|store| store.trust_pending(&host, port));
+
+    match result {
+        Ok(fingerprint) => {
+            log_info!(category: "Connection", message: "Trusted new host key", details: "{}:{} {}", host, port, fingerprint, show_toast: true);
+            ApiResult::success(fingerprint)
+        }
+        Err(e) => {
+            log_error!(category: "Connection", message: "Failed to trust host key", details: "{}", e);
+            ApiResult::error(e.to_string())
+        }
+    }
+}
+
+/// Test each hop of the connection path (resolve, connect, authenticate) without
+/// touching the active session, reporting which hop and stage failed
+#[tauri::command(rename_all = "snake_case")]
+pub async fn diagnose_connection(params: ConnectParams) -> ApiResult<ConnectionDiagnosis> {
+    let (host, port) = match cluster::resolve_login_target(&params.host, params.port, cluster::active_cluster_profile().as_ref()) {
+        Ok(target) => target,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+    log_info!(category: "Connection", message: "Running connection diagnostic", details: "Host: {}:{}, User: {}", host, port, params.username);
+
+    let config = ConnectionConfig {
+        jump_host: params.jump_host.clone(),
+        ..params.network_preset.map(ConnectionConfig::for_preset).unwrap_or_default()
+    };
+    let hops = diagnostics::diagnostic_hops(&host, port, config.jump_host.as_ref());
+    let mut probe = diagnostics::NetworkProbe::new(params, &config);
+
+    match tokio::task::spawn_blocking(move || diagnostics::run_diagnostic_sequence(&hops, |hop, stage| probe.probe(hop, stage))).await {
+        Ok(diagnosis) => {
+            log_info!(category: "Connection", message: "Connection diagnostic finished", details: "{}", diagnosis.summary);
+            ApiResult::success(diagnosis)
+        }
+        Err(e) => {
+            log_error!(category: "Connection", message: "Connection diagnostic failed to run", details: "{}", e);
+            ApiResult::error(format!("Diagnostic failed to run: {}", e))
+        }
+    }
+}
+
+/// Add a successful login target to the recent connections list; failures are only logged
+fn remember_recent_connection(host: &str, port: u16, username: &str, connected_at: &str) {
+    let recent = RecentConnection {
+        host: host.to_string(),
+        port,
+        username: username.to_string(),
+        last_connected_at: connected_at.to_string(),
+    };
+    if let Err(e) = with_database(|db| db.record_recent_connection(&recent, MAX_RECENT_CONNECTIONS)) {
+        log_error!(category: "Connection", message: "Failed to save recent connection", details: "{}", e);
+    }
+}
+
+/// Recently used login targets (host, port, username), most recent first; no credentials are stored
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_recent_connections() -> ApiResult<Vec<RecentConnection>> {
+    match with_database(|db| db.list_recent_connections()) {
+        Ok(connections) => ApiResult::success(connections),
+        Err(e) => ApiResult::error(format!("Failed to load recent connections: {}", e)),
+    }
+}
+
+/// Forget all recent connections; returns how many were removed
+#[tauri::command(rename_all = "snake_case")]
+pub async fn clear_recent_connections() -> ApiResult<usize> {
+    match with_database(|db| db.clear_recent_connections()) {
+        Ok(removed) => {
+            log_info!(category: "Connection", message: "Cleared recent connections", details: "{} removed", removed);
+            ApiResult::success(removed)
+        }
+        Err(e) => {
+            log_error!(category: "Connection", message: "Failed to clear recent connections", details: "{}", e);
+            ApiResult::error(format!("Failed to clear recent connections: {}", e))
+        }
+    }
+}
+
+/// Pick the preset for this connection and remember an explicit choice for next time
+fn resolve_network_preset(requested: Option<NetworkPreset>) -> Option<NetworkPreset> {
+    match requested {
+        Some(preset) => {
+            if let Err(e) = with_database(|db| db.save_setting(NETWORK_PRESET_SETTING, &preset)) {
+                log_error!(category: "Connection", message: "Failed to save network preset", details: "{}", e);
+            }
+            Some(preset)
+        }
+        None => with_database(|db| db.load_setting(NETWORK_PRESET_SETTING)).unwrap_or_else(|e| {
+            log_debug!(category: "Connection", message: "No saved network preset", details: "{}", e);
+            None
+        }),
+    }
+}
+
+/// Get the saved network preset (None = defaults, never selected)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_network_preset() -> ApiResult<Option<NetworkPreset>> {
+    match with_database(|db| db.load_setting(NETWORK_PRESET_SETTING)) {
+        Ok(preset) => ApiResult::success(preset),
+        Err(e) => ApiResult::error(format!("Failed to load network preset: {}", e)),
+    }
+}
+
+/// Set the keepalive interval in seconds (0 disables), applying it to the current
+/// connection immediately and saving it for later connects
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_connection_keepalive(seconds: u32) -> ApiResult<()> {
+    if let Err(e) = ConnectionConfig::validate_keepalive_interval(seconds) {
+        return ApiResult::error(e.to_string());
+    }
+
+    if let Err(e) = with_database(|db| db.save_setting(KEEPALIVE_INTERVAL_SETTING, &seconds)) {
+        log_error!(category: "Connection", message: "Failed to save keepalive interval", details: "{}", e);
+        return ApiResult::error(format!("Failed to save keepalive interval: {}", e));
+    }
+
+    let applied = get_connection_manager().set_keepalive_interval(seconds).await;
+    log_info!(category: "Connection", message: "Keepalive interval updated", details: "{} seconds (live connection updated: {})", seconds, applied);
+    ApiResult::success(())
+}
+
+/// Keepalive interval saved by set_connection_keepalive, if any
+fn saved_keepalive_interval() -> Option<u32> {
+    with_database(|db| db.load_setting(KEEPALIVE_INTERVAL_SETTING)).unwrap_or_else(|e| {
+        log_debug!(category: "Connection", message: "No saved keepalive interval", details: "{}", e);
+        None
+    })
+}
+
+/// Validate timeouts requested for a connect and save them, field by field, over the saved ones
+/// With none requested, the saved overrides apply
+fn resolve_timeout_overrides(requested: TimeoutOverrides) -> Result<TimeoutOverrides, String> {
+    requested.validate().map_err(|e| e.to_string())?;
+
+    let saved = saved_timeout_overrides();
+    if requested.is_empty() {
+        return Ok(saved);
+    }
+
+    let overrides = requested.or(saved);
+    if let Err(e) = with_database(|db| db.save_setting(TIMEOUT_OVERRIDES_SETTING, &overrides)) {
+        log_error!(category: "Connection", message: "Failed to save timeout overrides", details: "{}", e);
+    }
+    Ok(overrides)
+}
+
+/// Timeout overrides saved by a connect or set_connection_timeouts (empty if never set)
+fn saved_timeout_overrides() -> TimeoutOverrides {
+    with_database(|db| db.load_setting(TIMEOUT_OVERRIDES_SETTING))
+        .unwrap_or_else(|e| {
+            log_debug!(category: "Connection", message: "No saved timeout overrides", details: "{}", e);
+            None
+        })
+        .unwrap_or_default()
+}
+
+/// Replace the saved connect and file-transfer timeouts; None returns that timeout to the preset's value
+/// Takes effect on the next connect
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_connection_timeouts(connect_timeout: Option<u64>, file_transfer_timeout: Option<u64>) -> ApiResult<TimeoutOverrides> {
+    let overrides = TimeoutOverrides { connect_timeout, file_transfer_timeout };
+    if let Err(e) = overrides.validate() {
+        return ApiResult::error(e.to_string());
+    }
+
+    if let Err(e) = with_database(|db| db.save_setting(TIMEOUT_OVERRIDES_SETTING, &overrides)) {
+        log_error!(category: "Connection", message: "Failed to save timeout overrides", details: "{}", e);
+        return ApiResult::error(format!("Failed to save timeouts: {}", e));
+    }
+
+    log_info!(category: "Connection", message: "Connection timeouts updated", details: "{:?}", overrides);
+    ApiResult::success(overrides)
+}
+
+/// Saved timeout overrides; fields left unset follow the network preset
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_connection_timeouts() -> ApiResult<TimeoutOverrides> {
+    ApiResult::success(saved_timeout_overrides())
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn disconnect() -> ApiResult<()> {
     match get_connection_manager().disconnect().await {
@@ -52,11 +412,36 @@ pub async fn disconnect() -> ApiResult<()> {
     }
 }
 
+/// Measure latency to the cluster with one `echo` round-trip (no retries)
+/// A failed probe is still a successful result, carrying the error and authentication state
+#[tauri::command(rename_all = "snake_case")]
+pub async fn ping_connection() -> ApiResult<ConnectionPing> {
+    let connection_manager = get_connection_manager();
+    let probe = connection_manager.ping(Some(crate::cluster::timeouts::STATUS_CHECK)).await;
+    let authenticated = connection_manager.is_connected().await;
+
+    let ping = match probe {
+        Ok(latency) => ConnectionPing {
+            authenticated,
+            latency_ms: Some(latency.as_millis() as u64),
+            error: None,
+        },
+        Err(e) => {
+            log_debug!(category: "Connection", message: "Ping failed", details: "{}", e);
+            ConnectionPing { authenticated, latency_ms: None, error: Some(e.to_string()) }
+        }
+    };
+    ApiResult::success(ping)
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_connection_status() -> ApiResult<ConnectionStatus> {
-    let connection_info = get_connection_manager().get_connection_info().await;
+    let connection_manager = get_connection_manager();
+    let connection_info = connection_manager.get_connection_info().await;
 
-    let (state, session_info) = if let Some(info) = connection_info {
+    let (state, session_info) = if connection_manager.is_expired() {
+        (ConnectionState::Expired, None)
+    } else if let Some(info) = connection_info {
         if info.connected {
             let session_info = Some(SessionInfo {
                 host: info.host,
@@ -29,8 +29,8 @@ pub async fn get_database_info() -> ApiResult<DatabaseInfo> {
     };
 
     // Get job count
-    let job_count = match crate::database::with_database(|db| db.load_all_jobs()) {
-        Ok(jobs) => jobs.len(),
+    let loaded = match crate::database::with_database(|db| db.load_all_jobs()) {
+        Ok(loaded) => loaded,
         Err(e) => {
             log_error!(category: "Database", message: "Failed to get job count", details: "Error: {}", e);
             return ApiResult::error(format!("Failed to get job count: {}", e));
@@ -40,7 +40,8 @@ pub async fn get_database_info() -> ApiResult<DatabaseInfo> {
     ApiResult::success(DatabaseInfo {
         path: db_path.to_string_lossy().to_string(),
         size_bytes,
-        job_count,
+        job_count: loaded.jobs.len(),
+        skipped_job_records: loaded.skipped_rows,
     })
 }
 
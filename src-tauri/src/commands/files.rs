@@ -2,10 +2,10 @@
 // UI concerns (file dialogs) handled here, business logic in automations/file_operations
 
 use crate::types::*;
-use crate::types::response_data::DownloadInfo;
+use crate::types::response_data::{DiskSpaceCheck, DownloadInfo, FilteredDownload, InodeCheck, RemoteDirSize, RemoteText};
 use crate::automations;
 use crate::commands::helpers;
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter};
 
 /// Open a file dialog to select a single NAMD input file
 /// Returns selected file path with metadata, or None if cancelled
@@ -82,7 +82,8 @@ pub async fn download_file(job_id: String, file_type: String, file_path: String)
     }
 }
 
-/// Download all files as a zip archive (inputs or outputs)
+/// Download all files as a zip archive (inputs or outputs), built locally as files stream in
+/// Emits archive-download-progress
 #[tauri::command(rename_all = "snake_case")]
 pub async fn download_all_files(job_id: String, file_type: String) -> ApiResult<DownloadInfo> {
     use rfd::FileDialog;
@@ -119,12 +120,158 @@ pub async fn download_all_files(job_id: String, file_type: String) -> ApiResult<
     };
 
     // Delegate to automation layer (business logic)
-    match automations::download_files_zip(&clean_job_id, folder, &save_path.to_string_lossy()).await {
+    match automations::download_files_zip(&clean_job_id, folder, &save_path.to_string_lossy(), |progress| {
+        if let Some(app_handle) = crate::logging::get_app_handle() {
+            let _ = app_handle.emit("archive-download-progress", progress);
+        }
+    }).await {
         Ok(info) => ApiResult::success(info),
         Err(e) => ApiResult::error(e.to_string()),
     }
 }
 
+/// Download only the outputs matching simple globs ("*.log", "final.*", ".dcd" for "*.dcd")
+/// into a folder, keeping their layout under outputs/. Emits output-download-progress per file
+#[tauri::command(rename_all = "snake_case")]
+pub async fn download_outputs_matching(app_handle: AppHandle, job_id: String, patterns: Vec<String>, destination: Option<String>) -> ApiResult<FilteredDownload> {
+    use rfd::FileDialog;
+
+    // Validate and sanitize job ID
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    // Reject bad patterns before asking for a folder
+    if let Err(e) = patterns.iter().try_for_each(|pattern| automations::file_operations::parse_output_pattern(pattern).map(|_| ())) {
+        return ApiResult::error(format!("Invalid pattern: {}", e));
+    }
+
+    let destination = match destination {
+        Some(dir) => dir,
+        None => match FileDialog::new().set_title("Save Matching Output Files").pick_folder() {
+            Some(dir) => dir.to_string_lossy().to_string(),
+            None => return ApiResult::error("Download cancelled".to_string()),
+        },
+    };
+
+    // Delegate to automation layer (business logic)
+    match automations::file_operations::download_outputs_matching(&clean_job_id, &patterns, &destination, |progress| {
+        let _ = app_handle.emit("output-download-progress", progress);
+    }).await {
+        Ok(result) => ApiResult::success(result),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Read the start of a job's text file (path relative to the project directory) for in-app viewing
+/// Returns at most max_bytes (default 1 MB, up to 16 MB) plus the file's total size; binary files are refused
+#[tauri::command(rename_all = "snake_case")]
+pub async fn read_remote_text(job_id: String, relative_path: String, max_bytes: Option<u64>) -> ApiResult<RemoteText> {
+    // Validate and sanitize job ID
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::file_operations::read_remote_text(&clean_job_id, &relative_path, max_bytes).await {
+        Ok(text) => ApiResult::success(text),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Rename or move a remote file or directory within the user's job directories
+/// Moves between project and scratch fall back to copy and delete; an existing destination is an error
+#[tauri::command(rename_all = "snake_case")]
+pub async fn rename_remote_file(old_path: String, new_path: String) -> ApiResult<()> {
+    match automations::file_operations::rename_remote_file(&old_path, &new_path).await {
+        Ok(()) => ApiResult::success(()),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Total size in bytes and recursive file count of one of the user's job directories, or a directory in one
+/// Unreadable subdirectories give a partial result with a warning instead of an error
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remote_dir_size(path: String) -> ApiResult<RemoteDirSize> {
+    match crate::ssh::get_connection_manager().remote_dir_size(&path).await {
+        Ok(size) => ApiResult::success(size),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Size of a job's outputs directory, for the size badge on job listings
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_job_output_size(job_id: String) -> ApiResult<RemoteDirSize> {
+    // Validate and sanitize job ID
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::file_operations::job_output_size(&clean_job_id).await {
+        Ok(size) => ApiResult::success(size),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Cancel an in-flight transfer by its progress key
+/// Uploads use the key from their progress events; job downloads use "{job_id}:{file_path}",
+/// or "{job_id}:inputs" / "{job_id}:outputs" for zip archives. Returns false if nothing is running under the key
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_transfer(progress_key: String) -> ApiResult<bool> {
+    ApiResult::success(crate::ssh::cancellation::cancel(&progress_key))
+}
+
+/// Check the target filesystem has enough free inodes before a many-file upload
+/// Thousands of small files can exhaust inodes while plenty of bytes remain free
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_remote_inodes(remote_dir: String, file_count: u64) -> ApiResult<InodeCheck> {
+    match automations::check_remote_inodes(&remote_dir, file_count).await {
+        Ok(check) => ApiResult::success(check),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Check free space and quota under a remote path, e.g. to show the user how much room is left
+/// `required_bytes` (default 0) is compared against the tighter of free space and quota
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_disk_space(path: String, required_bytes: Option<u64>) -> ApiResult<DiskSpaceCheck> {
+    match automations::check_disk_space(&path, required_bytes.unwrap_or(0)).await {
+        Ok(check) => ApiResult::success(check),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Get the zip level of locally built download archives (0 = store only, 9 = smallest)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_archive_compression_level() -> ApiResult<u8> {
+    ApiResult::success(automations::file_operations::load_archive_compression_level())
+}
+
+/// Set the zip level of locally built download archives
+/// Only the saved archive's size changes - transfer size doesn't; 0 skips compression for already-compressed data
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_archive_compression_level(level: u8) -> ApiResult<u8> {
+    match automations::file_operations::save_archive_compression_level(level) {
+        Ok(()) => ApiResult::success(level),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Cap the upload rate in bytes per second; None or 0 removes the cap
+/// Returns the limit now in effect (None = unlimited)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_transfer_rate_limit(max_bytes_per_sec: Option<u64>) -> ApiResult<Option<u64>> {
+    match automations::file_operations::save_transfer_rate_limit(max_bytes_per_sec) {
+        Ok(limit) => ApiResult::success(limit),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,4 +323,26 @@ mod tests {
         assert!(!result.success);
         assert!(result.error.as_ref().unwrap().contains("Invalid file type"));
     }
+
+    #[tokio::test]
+    async fn test_read_remote_text_rejects_traversal_and_binaries() {
+        let result = read_remote_text("test_job_123".to_string(), "../../../etc/passwd".to_string(), None).await;
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("Invalid file path"));
+
+        let result = read_remote_text("test_job_123".to_string(), "outputs/sim.dcd".to_string(), None).await;
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("binary"));
+    }
+
+    #[tokio::test]
+    async fn test_remote_dir_size_rejects_paths_outside_job_storage() {
+        let result = remote_dir_size("/etc".to_string()).await;
+        assert!(!result.success);
+
+        let result = remote_dir_size("/projects/testuser/../../etc".to_string()).await;
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("parent directory"));
+    }
+
 }
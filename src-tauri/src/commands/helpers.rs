@@ -38,6 +38,41 @@ pub fn load_template_or_fail(template_id: &str, context: &str) -> Result<Templat
         })
 }
 
+/// Load a template with inheritance resolved - what rendering and validation should use
+/// Editing and export want the stored template instead (load_template_or_fail)
+pub fn load_resolved_template_or_fail(template_id: &str, context: &str) -> Result<Template> {
+    let template = load_template_or_fail(template_id, context)?;
+    crate::templates::resolve_template(&template, load_template_by_id).map_err(|e| {
+        log_error!(category: context, message: "Failed to resolve template inheritance", details: "{}: {}", template_id, e);
+        e
+    })
+}
+
+/// Load the resolved template an existing job renders with
+/// The job's template_snapshot wins, so later template edits or deletion don't change what the job used;
+/// jobs created before snapshots existed fall back to the template in the database
+pub fn load_job_template_or_fail(job: &JobInfo, context: &str) -> Result<Template> {
+    if let Some(snapshot) = &job.template_snapshot {
+        return Ok(snapshot.clone());
+    }
+
+    let stored = load_template_by_id(&job.template_id).map_err(|e| {
+        log_error!(category: context, message: "Database error", details: "{}", e);
+        anyhow!("Database error: {}", e)
+    })?;
+    if stored.is_none() {
+        log_error!(category: context, message: "Template not found", details: "Template ID: {}, Job: {}", job.template_id, job.job_id);
+        return Err(anyhow!("Template '{}' not found and job '{}' has no template snapshot", job.template_id, job.job_id));
+    }
+    load_resolved_template_or_fail(&job.template_id, context)
+}
+
+/// Template loader for resolve_template backed by the database
+pub fn load_template_by_id(template_id: &str) -> Result<Option<Template>> {
+    let template_id = template_id.to_string();
+    with_database(move |db| db.load_template(&template_id))
+}
+
 /// Sanitize and validate job_id from command input
 /// Returns ApiResult with sanitized job_id on success, error on validation failure
 pub fn sanitize_command_job_id(job_id: &str) -> ApiResult<String> {
@@ -1,11 +1,12 @@
 use crate::types::*;
 use crate::types::commands::ValidateJobConfigParams;
+use crate::types::response_data::{JobPage, JobSummary, SubmissionArtifacts, ScratchUsage, SubmissionReadiness};
 use crate::security::input;
 use crate::validation::job::ValidationResult;
 use crate::database::with_database;
 use crate::commands::helpers;
 use crate::automations;
-use crate::{log_info, log_error};
+use crate::{log_info, log_debug, log_error};
 use tauri::Emitter;
 
 #[tauri::command(rename_all = "snake_case")]
@@ -18,12 +19,39 @@ pub async fn create_job(app_handle: tauri::AppHandle, params: CreateJobParams) -
         }
     };
 
+    // Dependencies are job IDs too - validate them at the boundary as well
+    let depends_on = match params.depends_on.map(|ids| ids.iter().map(|id| input::sanitize_job_id(id)).collect::<Result<Vec<_>, _>>()).transpose() {
+        Ok(ids) => ids.filter(|ids| !ids.is_empty()),
+        Err(e) => {
+            return ApiResult::error(format!("Invalid dependency: {}", e));
+        }
+    };
+
+    // The address is written into the batch script - validate it at the boundary too
+    let notify_email = match params.notify_email.as_deref().map(str::trim).filter(|email| !email.is_empty()).map(input::sanitize_email).transpose() {
+        Ok(email) => email,
+        Err(e) => {
+            return ApiResult::error(format!("Invalid notification email: {}", e));
+        }
+    };
+    let notify_events = match (&notify_email, params.notify_events.is_empty()) {
+        (None, false) => return ApiResult::error("Notification events need an email address".to_string()),
+        (Some(_), true) => MailEvent::DEFAULT_EVENTS.to_vec(),
+        _ => params.notify_events,
+    };
+
     // Create validated params
     let validated_params = CreateJobParams {
         job_name: clean_job_name,
         template_id: params.template_id,
         template_values: params.template_values,
         slurm_config: params.slurm_config,
+        depends_on,
+        // Task statuses come from SLURM, never from the caller
+        array: params.array.map(|array| JobArray { task_statuses: Vec::new(), ..array }),
+        notify_email,
+        notify_events,
+        ignore_space_check: params.ignore_space_check,
     };
 
     // Call automation with progress tracking
@@ -42,8 +70,43 @@ pub async fn create_job(app_handle: tauri::AppHandle, params: CreateJobParams) -
 }
 
 
+/// Clone a job's template values, SLURM config and input files into a new Created job
+/// The source job is left untouched; the copy is ready to submit
 #[tauri::command(rename_all = "snake_case")]
-pub async fn submit_job(job_id: String, app_handle: tauri::AppHandle) -> ApiResult<JobInfo> {
+pub async fn rerun_job(app_handle: tauri::AppHandle, job_id: String) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::execute_job_rerun(&clean_job_id, move |msg| {
+        let _ = app_handle.emit("job-creation-progress", msg);
+    }).await {
+        Ok(job_info) => ApiResult::success(job_info),
+        Err(e) => {
+            log_error!(category: "Job Rerun", message: "Failed to rerun job", details: "{}: {}", clean_job_id, e);
+            ApiResult::error(e.to_string())
+        }
+    }
+}
+
+
+/// Track a job directory that was staged on the cluster outside the app
+/// The job is saved in Created state and submits from the staged files
+#[tauri::command(rename_all = "snake_case")]
+pub async fn adopt_remote_job(remote_dir: String) -> ApiResult<JobInfo> {
+    match automations::execute_job_adoption(&remote_dir).await {
+        Ok(job) => ApiResult::success(job),
+        Err(e) => {
+            log_error!(category: "Job Adoption", message: "Failed to adopt directory", details: "{}: {}", remote_dir, e);
+            ApiResult::error(e.to_string())
+        }
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn submit_job(job_id: String, ignore_space_check: Option<bool>, app_handle: tauri::AppHandle) -> ApiResult<JobInfo> {
     let sanitize_result = helpers::sanitize_command_job_id(&job_id);
     if !sanitize_result.success {
         return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
@@ -55,6 +118,7 @@ pub async fn submit_job(job_id: String, app_handle: tauri::AppHandle) -> ApiResu
 
     match automations::execute_job_submission_with_progress(
         clean_job_id,
+        ignore_space_check.unwrap_or(false),
         move |msg| {
             let _ = handle_clone.emit("job-submission-progress", msg);
         }
@@ -64,23 +128,212 @@ pub async fn submit_job(job_id: String, app_handle: tauri::AppHandle) -> ApiResu
     }
 }
 
+/// Check a job's SLURM script with `sbatch --test-only` without queueing it
+/// A rejected script is a successful result with `accepted: false` and the scheduler's errors
+#[tauri::command(rename_all = "snake_case")]
+pub async fn dry_run_submit(job_id: String) -> ApiResult<crate::types::response_data::DryRunResult> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::execute_dry_run_submission(clean_job_id).await {
+        Ok(result) => ApiResult::success(result),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Submit several jobs in one go; failures are reported per job instead of stopping the batch
+#[tauri::command(rename_all = "snake_case")]
+pub async fn submit_jobs(job_ids: Vec<String>, app_handle: tauri::AppHandle) -> ApiResult<BatchSubmitResult> {
+    if job_ids.is_empty() {
+        return ApiResult::error("No jobs to submit".to_string());
+    }
+
+    let mut clean_job_ids = Vec::with_capacity(job_ids.len());
+    for job_id in &job_ids {
+        let sanitize_result = helpers::sanitize_command_job_id(job_id);
+        if !sanitize_result.success {
+            return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+        }
+        clean_job_ids.push(sanitize_result.data.unwrap());
+    }
+
+    let result = automations::execute_batch_submission(clean_job_ids, move |progress| {
+        let _ = app_handle.emit("batch-submission-progress", progress);
+    }).await;
+    ApiResult::success(result)
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_job_status(job_id: String) -> ApiResult<JobInfo> {
     // Retrieve job from database
     let job_id_for_db = job_id.clone();
-    match with_database(move |db| db.load_job(&job_id_for_db)) {
-        Ok(Some(job)) => ApiResult::success(job),
-        Ok(None) => ApiResult::error(format!("Job {} not found", job_id)),
-        Err(e) => ApiResult::error(format!("Failed to load job {}: {}", job_id, e)),
+    let mut job = match with_database(move |db| db.load_job(&job_id_for_db)) {
+        Ok(Some(job)) => job,
+        Ok(None) => return ApiResult::error(format!("Job {} not found", job_id)),
+        Err(e) => return ApiResult::error(format!("Failed to load job {}: {}", job_id, e)),
+    };
+
+    // Deferred log fetch (LogFetchPolicy::OnStatusView) for finished jobs without cached logs
+    if crate::ssh::get_connection_manager().is_connected().await {
+        match automations::job_sync::fetch_logs_on_status_view(crate::ssh::get_connection_manager(), &mut job).await {
+            Ok(true) => {
+                let job_clone = job.clone();
+                if let Err(e) = with_database(move |db| db.save_job(&job_clone)) {
+                    log_error!(category: "Jobs", message: "Failed to save fetched logs", details: "{}", e);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => {
+                log_error!(category: "Jobs", message: "Failed to fetch logs on status view", details: "{}: {}", job.job_id, e);
+            }
+        }
+    }
+
+    // Live queue position for pending jobs; the job status is still useful without it
+    if job.status == JobStatus::Pending && crate::ssh::get_connection_manager().is_connected().await {
+        match automations::job_sync::read_queue_position(&job).await {
+            Ok(Some(position)) => {
+                job.estimated_start = position.estimated_start;
+                job.queue_priority = position.priority;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                log_debug!(category: "Jobs", message: "Could not read queue position", details: "{}: {}", job.job_id, e);
+            }
+        }
+    }
+
+    // Live progress for running jobs
+    if job.status == JobStatus::Running && crate::ssh::get_connection_manager().is_connected().await {
+        match automations::job_sync::read_namd_progress(&job).await {
+            Ok(progress) => job.namd_progress = progress,
+            Err(e) => {
+                log_debug!(category: "Jobs", message: "Could not read NAMD progress", details: "{}: {}", job.job_id, e);
+            }
+        }
+    }
+
+    ApiResult::success(job)
+}
+
+/// Read a job's SLURM log from `from_offset` onward, for polling a live log view
+/// Pass the returned offset back on the next call; `reset` means the log was truncated and reading restarted.
+/// Array jobs must pass `array_task`, since each task writes its own log
+#[tauri::command(rename_all = "snake_case")]
+pub async fn stream_job_log(job_id: String, stream: automations::job_sync::LogStream, array_task: Option<u32>, from_offset: u64) -> ApiResult<crate::ssh::RemoteFileTail> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::job_sync::stream_job_log(&clean_job_id, stream, array_task, from_offset).await {
+        Ok(tail) => ApiResult::success(tail),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// SLURM accounting for a submitted job: elapsed time, peak memory, CPU efficiency, exit code
+/// For right-sizing the resources of later runs
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_job_accounting(job_id: String) -> ApiResult<crate::types::response_data::JobAccounting> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
     }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::job_sync::read_job_accounting(&clean_job_id).await {
+        Ok(accounting) => ApiResult::success(accounting),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Get how many job_info.json files job discovery reads per remote command
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_discovery_batch_size() -> ApiResult<usize> {
+    ApiResult::success(automations::job_sync::load_discovery_batch_size())
 }
 
+/// Set how many job_info.json files job discovery reads per remote command
+/// Clamped to 1..=100; returns the value saved
 #[tauri::command(rename_all = "snake_case")]
-pub async fn get_all_jobs() -> ApiResult<Vec<JobInfo>> {
+pub async fn set_discovery_batch_size(batch_size: usize) -> ApiResult<usize> {
+    match automations::job_sync::save_discovery_batch_size(batch_size) {
+        Ok(saved) => {
+            log_info!(category: "Job Discovery", message: "Discovery batch size updated", details: "{}", saved);
+            ApiResult::success(saved)
+        }
+        Err(e) => ApiResult::error(format!("Failed to save discovery batch size: {}", e)),
+    }
+}
+
+/// Get the automatic log fetch policy
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_log_fetch_policy() -> ApiResult<automations::job_sync::LogFetchPolicy> {
+    ApiResult::success(automations::job_sync::load_log_fetch_policy())
+}
+
+/// Set the automatic log fetch policy (on_completion, on_status_view, never)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_log_fetch_policy(policy: automations::job_sync::LogFetchPolicy) -> ApiResult<automations::job_sync::LogFetchPolicy> {
+    match with_database(|db| db.save_setting(automations::job_sync::LOG_FETCH_POLICY_SETTING, &policy)) {
+        Ok(()) => {
+            log_info!(category: "Log Fetch", message: "Log fetch policy updated", details: "{:?}", policy);
+            ApiResult::success(policy)
+        }
+        Err(e) => ApiResult::error(format!("Failed to save log fetch policy: {}", e)),
+    }
+}
+
+/// Unarchived jobs (archived too with `include_archived`), newest first unless `sort_by`/`order`
+/// say otherwise. Without `limit` every job from `offset` on is returned; `total` counts all matching jobs
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_all_jobs(
+    tag: Option<String>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    sort_by: Option<JobSortField>,
+    order: Option<SortOrder>,
+    include_archived: Option<bool>,
+) -> ApiResult<JobPage> {
     log_info!(category: "Jobs", message: "Loading jobs from database");
 
-    match with_database(|db| db.load_all_jobs()) {
-        Ok(jobs) => ApiResult::success(jobs),
+    let tag = match tag.as_deref().map(input::sanitize_tag).transpose() {
+        Ok(tag) => tag,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+    let offset = offset.unwrap_or(0);
+    let sort_by = sort_by.unwrap_or_default();
+    let order = order.unwrap_or_default();
+    let include_archived = include_archived.unwrap_or(false);
+
+    let load_result = match tag {
+        // Tagged sets are small; page them here with the same ordering the SQL path uses
+        Some(tag) => with_database(move |db| {
+            let mut jobs: Vec<JobInfo> = db.load_jobs_by_tag(&tag)?.jobs.into_iter()
+                .filter(|job| include_archived || !job.archived)
+                .collect();
+            let total = jobs.len() as u32;
+            jobs.sort_by(|a, b| {
+                let by_key = sort_by.key(a).cmp(&sort_by.key(b));
+                let by_key = if order == SortOrder::Asc { by_key } else { by_key.reverse() };
+                by_key.then_with(|| b.created_at.cmp(&a.created_at)).then_with(|| a.job_id.cmp(&b.job_id))
+            });
+            let jobs = jobs.into_iter().skip(offset as usize).take(limit.map_or(usize::MAX, |limit| limit as usize)).collect();
+            Ok(JobPage { jobs, total })
+        }),
+        None => with_database(move |db| Ok(JobPage {
+            jobs: db.load_jobs_page(offset, limit, sort_by, order, include_archived)?.jobs,
+            total: db.count_jobs(include_archived)?,
+        })),
+    };
+
+    match load_result {
+        Ok(page) => ApiResult::success(page),
         Err(e) => {
             log_error!(category: "Jobs", message: "Failed to load jobs", details: "Database error: {}", e);
             ApiResult::error(format!("Failed to load jobs: {}", e))
@@ -88,11 +341,46 @@ pub async fn get_all_jobs() -> ApiResult<Vec<JobInfo>> {
     }
 }
 
+/// Lightweight job list for the list view - no cached logs or template values
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_all_jobs_summary() -> ApiResult<Vec<JobSummary>> {
+    log_info!(category: "Jobs", message: "Loading job summaries from database");
+
+    match with_database(|db| db.load_job_summaries()) {
+        Ok(summaries) => ApiResult::success(summaries),
+        Err(e) => {
+            log_error!(category: "Jobs", message: "Failed to load job summaries", details: "Database error: {}", e);
+            ApiResult::error(format!("Failed to load jobs: {}", e))
+        }
+    }
+}
+
+/// Search unarchived jobs by name, template and status, newest first
+/// Cached SLURM logs are searched too when `include_logs` is set (slower on large logs)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn search_jobs(query: String, include_logs: Option<bool>) -> ApiResult<Vec<JobSummary>> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return ApiResult::error("Search query cannot be empty".to_string());
+    }
+    let include_logs = include_logs.unwrap_or(false);
+    log_debug!(category: "Jobs", message: "Searching jobs", details: "'{}' (logs: {})", query, include_logs);
+
+    match with_database(move |db| db.search_job_summaries(&query, include_logs)) {
+        Ok(summaries) => ApiResult::success(summaries),
+        Err(e) => {
+            log_error!(category: "Jobs", message: "Job search failed", details: "Database error: {}", e);
+            ApiResult::error(format!("Failed to search jobs: {}", e))
+        }
+    }
+}
+
+/// `scheduled` is set by the frontend's poll timer; a manual refresh leaves it unset
 #[tauri::command(rename_all = "snake_case")]
-pub async fn sync_jobs() -> SyncJobsResult {
+pub async fn sync_jobs(scheduled: Option<bool>) -> SyncJobsResult {
     log_info!(category: "Jobs", message: "Starting job sync");
 
-    match automations::sync_all_jobs().await {
+    match automations::sync_all_jobs(crate::ssh::get_connection_manager(), scheduled.unwrap_or(false)).await {
         Ok(result) => {
             log_info!(category: "Jobs", message: "Job sync completed", details: "Synced {} jobs", result.jobs_updated);
             result
@@ -104,11 +392,46 @@ pub async fn sync_jobs() -> SyncJobsResult {
                 jobs: vec![],
                 jobs_updated: 0,
                 errors: vec![e.to_string()],
+                warnings: vec![],
+                already_in_progress: false,
+                suggested_next_poll_secs: automations::job_sync::MIN_POLL_SECS,
             }
         }
     }
 }
 
+/// Refresh one job's status from SLURM (e.g. on its detail page) without a full sync
+#[tauri::command(rename_all = "snake_case")]
+pub async fn sync_single_job(job_id: String) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::job_sync::sync_single_job(crate::ssh::get_connection_manager(), &clean_job_id).await {
+        Ok(job) => ApiResult::success(job),
+        Err(e) => {
+            log_error!(category: "Jobs", message: "Job refresh failed", details: "{}: {}", clean_job_id, e);
+            ApiResult::error(format!("Failed to refresh job: {}", e))
+        }
+    }
+}
+
+/// List what deleting a job would remove, split by the `delete_remote` flag, without deleting anything
+#[tauri::command(rename_all = "snake_case")]
+pub async fn preview_job_deletion(job_id: String) -> ApiResult<crate::types::response_data::DeletionPreview> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::job_deletion::preview_job_deletion(&clean_job_id).await {
+        Ok(preview) => ApiResult::success(preview),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
 
 #[tauri::command(rename_all = "snake_case")]
 pub async fn delete_job(job_id: String, delete_remote: bool, app_handle: tauri::AppHandle) -> ApiResult<()> {
@@ -132,6 +455,58 @@ pub async fn delete_job(job_id: String, delete_remote: bool, app_handle: tauri::
     }
 }
 
+/// Cancel a queued or running job with scancel and retrieve its partial outputs
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_job(job_id: String, app_handle: tauri::AppHandle) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    let handle_clone = app_handle.clone();
+
+    match automations::execute_job_cancellation(
+        clean_job_id,
+        move |msg| {
+            let _ = handle_clone.emit("job-cancellation-progress", msg);
+        }
+    ).await {
+        Ok(job) => ApiResult::success(job),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Hold a pending job with scontrol hold; it stays queued but won't start until released
+#[tauri::command(rename_all = "snake_case")]
+pub async fn hold_job(job_id: String) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::execute_job_hold(clean_job_id).await {
+        Ok(job) => ApiResult::success(job),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
+/// Release a held job with scontrol release so it can be scheduled again
+#[tauri::command(rename_all = "snake_case")]
+pub async fn release_job(job_id: String) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    match automations::execute_job_release(clean_job_id).await {
+        Ok(job) => ApiResult::success(job),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
 /// Refetch SLURM logs from server, overwriting cached logs
 /// Used when user explicitly clicks "Refetch Logs" button
 #[tauri::command(rename_all = "snake_case")]
@@ -149,7 +524,7 @@ pub async fn refetch_slurm_logs(job_id: String) -> ApiResult<JobInfo> {
     };
 
     // Refetch logs from server
-    if let Err(e) = automations::load_slurm_logs(&mut job_info, true).await {
+    if let Err(e) = automations::load_slurm_logs(crate::ssh::get_connection_manager(), &mut job_info, true).await {
         return ApiResult::error(format!("Failed to refetch logs: {}", e));
     }
 
@@ -162,29 +537,263 @@ pub async fn refetch_slurm_logs(job_id: String) -> ApiResult<JobInfo> {
     ApiResult::success(job_info)
 }
 
+/// Pin or unpin a job - pinned jobs are exempt from retention auto-archive
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_job_pinned(job_id: String, pinned: bool) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    let mut job_info = match helpers::load_job_or_fail(&clean_job_id, "Jobs") {
+        Ok(job) => job,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    job_info.pinned = pinned;
+
+    let job_clone = job_info.clone();
+    if let Err(e) = with_database(move |db| db.save_job(&job_clone)) {
+        return ApiResult::error(format!("Failed to save job: {}", e));
+    }
+
+    ApiResult::success(job_info)
+}
+
+/// Set or clear a job's notes (blank clears them)
+/// Saved locally now; the server's job_info.json picks them up at the next lifecycle boundary
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_job_notes(job_id: String, notes: Option<String>) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    let notes = match crate::validation::job::validate_job_notes(notes) {
+        Ok(notes) => notes,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    let mut job_info = match helpers::load_job_or_fail(&clean_job_id, "Jobs") {
+        Ok(job) => job,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    job_info.notes = notes;
+
+    let job_clone = job_info.clone();
+    if let Err(e) = with_database(move |db| db.save_job(&job_clone)) {
+        return ApiResult::error(format!("Failed to save job: {}", e));
+    }
+
+    ApiResult::success(job_info)
+}
+
+/// Tag a job for grouping and filtering; adding a tag it already has is a no-op
+#[tauri::command(rename_all = "snake_case")]
+pub async fn add_job_tag(job_id: String, tag: String) -> ApiResult<JobInfo> {
+    update_job_tags(&job_id, &tag, |tags, tag| {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    })
+}
+
+/// Remove a tag from a job; removing a tag it doesn't have is a no-op
+#[tauri::command(rename_all = "snake_case")]
+pub async fn remove_job_tag(job_id: String, tag: String) -> ApiResult<JobInfo> {
+    update_job_tags(&job_id, &tag, |tags, tag| tags.retain(|existing| *existing != tag))
+}
+
+fn update_job_tags(job_id: &str, tag: &str, update: impl FnOnce(&mut Vec<String>, String)) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    let clean_tag = match input::sanitize_tag(tag) {
+        Ok(tag) => tag,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    let mut job_info = match helpers::load_job_or_fail(&clean_job_id, "Jobs") {
+        Ok(job) => job,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    update(&mut job_info.tags, clean_tag);
+
+    let job_clone = job_info.clone();
+    if let Err(e) = with_database(move |db| db.save_job(&job_clone)) {
+        return ApiResult::error(format!("Failed to save job: {}", e));
+    }
+
+    ApiResult::success(job_info)
+}
+
+/// Hide a finished job from the job list without deleting anything; restore_job brings it back
+/// The database record and the job's files on the cluster are kept
+#[tauri::command(rename_all = "snake_case")]
+pub async fn archive_job(job_id: String) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    let mut job_info = match helpers::load_job_or_fail(&clean_job_id, "Jobs") {
+        Ok(job) => job,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    if let Err(e) = automations::job_retention::archive_finished_job(&mut job_info) {
+        return ApiResult::error(e.to_string());
+    }
+
+    let job_clone = job_info.clone();
+    if let Err(e) = with_database(move |db| db.save_job(&job_clone)) {
+        return ApiResult::error(format!("Failed to save job: {}", e));
+    }
+
+    log_info!(category: "Jobs", message: "Job archived", details: "{}", job_info.job_id);
+    ApiResult::success(job_info)
+}
+
+/// Bring an archived job back into the job list
+/// Pins it when the retention policy would otherwise archive it again on the next pass
+#[tauri::command(rename_all = "snake_case")]
+pub async fn restore_job(job_id: String) -> ApiResult<JobInfo> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    let mut job_info = match helpers::load_job_or_fail(&clean_job_id, "Jobs") {
+        Ok(job) => job,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    let retention_days = match automations::job_retention::load_retention_days() {
+        Ok(days) => days,
+        Err(e) => return ApiResult::error(format!("Failed to load retention policy: {}", e)),
+    };
+    if automations::job_retention::restore_archived_job(&mut job_info, retention_days, chrono::Utc::now()) {
+        log_info!(category: "Jobs", message: "Pinned restored job so retention keeps it visible", details: "{}", job_info.job_id);
+    }
+
+    let job_clone = job_info.clone();
+    if let Err(e) = with_database(move |db| db.save_job(&job_clone)) {
+        return ApiResult::error(format!("Failed to save job: {}", e));
+    }
+
+    log_info!(category: "Jobs", message: "Job restored", details: "{}", job_info.job_id);
+    ApiResult::success(job_info)
+}
+
+/// Export every job (archived included) to a CSV or JSON file for reporting
+/// Shows a save dialog unless `path` is given; `include_cost` adds estimated SUs from the billing rates
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_jobs(format: ExportFormat, path: Option<String>, include_cost: Option<bool>) -> ApiResult<String> {
+    use automations::job_export::{JobExportRow, render_job_export};
+
+    let jobs = match with_database(|db| db.load_all_jobs()) {
+        Ok(loaded) => loaded.jobs,
+        Err(e) => {
+            log_error!(category: "Jobs", message: "Failed to load jobs for export", details: "Error: {}", e);
+            return ApiResult::error(format!("Failed to load jobs: {}", e));
+        }
+    };
+
+    let include_cost = include_cost.unwrap_or(false);
+    let rows: Vec<JobExportRow> = jobs.iter()
+        .map(|job| {
+            let cost = include_cost.then(|| crate::cluster::calculate_job_cost(
+                job.slurm_config.cores,
+                job.slurm_config.walltime.clone(),
+                job.slurm_config.gpu_count(),
+            ));
+            JobExportRow::new(job, cost)
+        })
+        .collect();
+
+    let content = match render_job_export(&rows, format) {
+        Ok(content) => content,
+        Err(e) => {
+            log_error!(category: "Jobs", message: "Failed to serialize job export", details: "Error: {}", e);
+            return ApiResult::error(format!("Serialization error: {}", e));
+        }
+    };
+
+    let path = match path {
+        Some(p) => std::path::PathBuf::from(p),
+        None => {
+            use rfd::FileDialog;
+            let save_path = FileDialog::new()
+                .set_file_name(format!("namdrunner_jobs.{}", format.extension()))
+                .set_title("Export Jobs")
+                .add_filter(format.extension().to_uppercase(), &[format.extension()])
+                .save_file();
+            match save_path {
+                Some(p) => p,
+                None => {
+                    log_info!(category: "Jobs", message: "Export cancelled by user");
+                    return ApiResult::error("Export cancelled".to_string());
+                }
+            }
+        }
+    };
+
+    match std::fs::write(&path, content) {
+        Ok(_) => {
+            let path_str = path.to_string_lossy().to_string();
+            log_info!(category: "Jobs", message: "Jobs exported", details: "{} jobs to {}", rows.len(), path_str, show_toast: true);
+            ApiResult::success(path_str)
+        }
+        Err(e) => {
+            log_error!(category: "Jobs", message: "Failed to write job export", details: "Error: {}", e);
+            ApiResult::error(format!("Failed to write file: {}", e))
+        }
+    }
+}
+
+/// Get the retention policy in days (None = auto-archive disabled)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_job_retention_days() -> ApiResult<Option<u32>> {
+    match automations::job_retention::load_retention_days() {
+        Ok(days) => ApiResult::success(days),
+        Err(e) => ApiResult::error(format!("Failed to load retention policy: {}", e)),
+    }
+}
+
+/// Set the retention policy and apply it immediately
+/// Terminal jobs finished more than `days` ago are archived (not deleted); None disables
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_job_retention_days(days: Option<u32>) -> ApiResult<Option<u32>> {
+    if let Err(e) = automations::job_retention::save_retention_days(days) {
+        return ApiResult::error(e.to_string());
+    }
+
+    log_info!(category: "Job Retention", message: "Retention policy updated", details: "{:?} days", days);
+
+    if let Err(e) = automations::job_retention::run_retention_pass() {
+        log_error!(category: "Job Retention", message: "Retention pass failed", details: "{}", e);
+    }
+
+    ApiResult::success(days)
+}
+
 // Job completion automation commands
 
 /// Preview SLURM script with given resource configuration
 /// Returns what the job.sbatch file will look like
 #[tauri::command(rename_all = "snake_case")]
-pub async fn preview_slurm_script(
-    job_name: String,
-    cores: u32,
-    memory: String,
-    walltime: String,
-    partition: String,
-    qos: String
-) -> ApiResult<String> {
+pub async fn preview_slurm_script(job_name: String, slurm_config: SlurmConfig) -> ApiResult<String> {
     log_info!(category: "Jobs", message: "Generating SLURM script preview");
 
-    let slurm_config = crate::types::SlurmConfig {
-        cores,
-        memory,
-        walltime,
-        partition,
-        qos,
-    };
-
     match crate::slurm::script_generator::SlurmScriptGenerator::preview_script(job_name, slurm_config) {
         Ok(script) => {
             log_info!(category: "Jobs", message: "SLURM script preview generated");
@@ -197,9 +806,181 @@ pub async fn preview_slurm_script(
     }
 }
 
+/// Preview the NAMD config and SLURM script a job will submit, side by side
+/// Renders both from the stored job and cross-checks them for mismatches
+#[tauri::command(rename_all = "snake_case")]
+pub async fn preview_submission_artifacts(job_id: String) -> ApiResult<SubmissionArtifacts> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    log_info!(category: "Jobs", message: "Generating submission preview", details: "{}", clean_job_id);
+
+    let job_info = match helpers::load_job_or_fail(&clean_job_id, "Submission Preview") {
+        Ok(job) => job,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    // Scratch directory is only recorded at submission - derive it for jobs not yet submitted
+    let scratch_dir = match &job_info.scratch_dir {
+        Some(dir) => dir.clone(),
+        None => {
+            let scratch_result = match automations::common::require_connection_with_username("Submission Preview").await {
+                Ok((_, username)) => crate::ssh::paths::scratch_directory(&username, &job_info.job_id),
+                Err(e) => Err(e),
+            };
+            match scratch_result {
+                Ok(dir) => dir,
+                Err(e) => return ApiResult::error(format!("Cannot determine scratch directory: {}", e)),
+            }
+        }
+    };
+
+    // Jobs keep the config they were created with; older records are re-rendered from their template
+    let namd_config = match job_info.rendered_config.clone() {
+        Some(config) => config,
+        None => {
+            let template = match helpers::load_job_template_or_fail(&job_info, "Submission Preview") {
+                Ok(t) => t,
+                Err(e) => return ApiResult::error(e.to_string()),
+            };
+            match crate::templates::render_template(&template, &job_info.template_values) {
+                Ok(rendered) => rendered,
+                Err(e) => {
+                    log_error!(category: "Jobs", message: "Submission preview render failed", details: "Error: {}", e);
+                    return ApiResult::error(format!("Rendering error: {}", e));
+                }
+            }
+        }
+    };
+
+    let slurm_script = match crate::slurm::script_generator::SlurmScriptGenerator::generate_namd_script(&job_info, &scratch_dir) {
+        Ok(script) => script,
+        Err(e) => {
+            log_error!(category: "Jobs", message: "Submission preview script failed", details: "Error: {}", e);
+            return ApiResult::error(format!("Script generation error: {}", e));
+        }
+    };
+
+    let validation = crate::validation::job::validate_submission_artifacts(&namd_config, &slurm_script, &job_info.input_files);
+    if !validation.is_valid {
+        log_error!(category: "Jobs", message: "Submission artifacts are inconsistent", details: "{}", validation.issues.join("; "));
+    }
+
+    ApiResult::success(SubmissionArtifacts {
+        namd_config,
+        slurm_script,
+        validation,
+    })
+}
+
+/// Checklist of everything a job needs before it can be submitted
+/// Remote items are read from the server; they report "not checked" while disconnected
+#[tauri::command(rename_all = "snake_case")]
+pub async fn submission_readiness(job_id: String) -> ApiResult<SubmissionReadiness> {
+    use crate::ssh::JobDirectoryStructure;
+    use crate::validation::readiness::{build_submission_readiness, RemoteJobState};
+
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    let job_info = match helpers::load_job_or_fail(&clean_job_id, "Submission Readiness") {
+        Ok(job) => job,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    let connection_manager = crate::ssh::get_connection_manager();
+    let mut remote = RemoteJobState {
+        connected: connection_manager.is_connected().await,
+        ..RemoteJobState::default()
+    };
+
+    if let (true, Some(project_dir)) = (remote.connected, &job_info.project_dir) {
+        remote.root_entries = connection_manager.list_files(project_dir, true).await.ok();
+        let has_file = |name: &str| remote.root_entries.iter().flatten().any(|e| !e.is_directory && e.name == name);
+        let (has_config, has_script) = (has_file(JobDirectoryStructure::NAMD_CONFIG), has_file(JobDirectoryStructure::SLURM_SCRIPT));
+
+        let input_dir = format!("{}/{}", project_dir, JobDirectoryStructure::INPUT_FILES);
+        remote.input_entries = connection_manager.list_files(&input_dir, false).await.ok();
+        if has_config {
+            let path = format!("{}/{}", project_dir, JobDirectoryStructure::NAMD_CONFIG);
+            remote.namd_config = connection_manager.read_remote_file(&path).await.ok();
+        }
+        if has_script {
+            let path = format!("{}/{}", project_dir, JobDirectoryStructure::SLURM_SCRIPT);
+            remote.slurm_script = connection_manager.read_remote_file(&path).await.ok();
+        }
+    }
+
+    let readiness = build_submission_readiness(&job_info, &remote);
+    log_info!(category: "Jobs", message: "Submission readiness checked", details: "{}: {}", clean_job_id, if readiness.ready { "ready" } else { "not ready" });
+
+    ApiResult::success(readiness)
+}
+
 /// Validate complete job configuration
 /// Checks job name, template selection, template values, and resource configuration
 #[tauri::command(rename_all = "snake_case")]
 pub async fn validate_job_config(params: ValidateJobConfigParams) -> ValidationResult {
     crate::validation::job::validate_job_config(params).await
 }
+
+/// Number of largest files reported by scratch_usage
+const SCRATCH_USAGE_MAX_FILES: usize = 10;
+
+/// Report how much scratch space a job is using, with its largest files
+#[tauri::command(rename_all = "snake_case")]
+pub async fn scratch_usage(job_id: String) -> ApiResult<ScratchUsage> {
+    let sanitize_result = helpers::sanitize_command_job_id(&job_id);
+    if !sanitize_result.success {
+        return ApiResult::error(sanitize_result.error.unwrap_or_else(|| "Invalid job ID".to_string()));
+    }
+    let clean_job_id = sanitize_result.data.unwrap();
+
+    let job_info = match helpers::load_job_or_fail(&clean_job_id, "Scratch Usage") {
+        Ok(job) => job,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    let scratch_dir = match job_info.scratch_dir {
+        Some(dir) => dir,
+        None => return ApiResult::error("Job has no scratch directory (not yet submitted)".to_string()),
+    };
+
+    let (connection_manager, username) = match automations::common::require_connection_with_username("Scratch Usage").await {
+        Ok(result) => result,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    if let Err(e) = crate::ssh::paths::confine_to_scratch(&scratch_dir, &username) {
+        log_error!(category: "Scratch Usage", message: "Refusing to measure path outside scratch", details: "{}", e);
+        return ApiResult::error(format!("Invalid scratch directory: {}", e));
+    }
+
+    log_info!(category: "Scratch Usage", message: "Measuring scratch usage", details: "{}", scratch_dir);
+
+    let command = crate::ssh::commands::disk_usage_command(&scratch_dir, SCRATCH_USAGE_MAX_FILES);
+    let result = match connection_manager.execute_command(&command, Some(crate::cluster::timeouts::DEFAULT_COMMAND)).await {
+        Ok(result) => result,
+        Err(e) => return ApiResult::error(format!("Failed to measure scratch usage: {}", e)),
+    };
+
+    if result.exit_code != 0 {
+        log_error!(category: "Scratch Usage", message: "du failed", details: "{}", result.stderr);
+        return ApiResult::error(format!("Failed to measure scratch usage: {}", result.stderr.trim()));
+    }
+
+    match crate::ssh::commands::parse_disk_usage(&result.stdout, &scratch_dir) {
+        Ok((total_bytes, largest_files)) => ApiResult::success(ScratchUsage {
+            scratch_dir,
+            total_bytes,
+            largest_files,
+        }),
+        Err(e) => ApiResult::error(format!("Failed to parse scratch usage: {}", e)),
+    }
+}
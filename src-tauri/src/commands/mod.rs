@@ -6,4 +6,5 @@ pub mod templates;
 pub mod database;
 pub mod cluster;
 pub mod validation;
+pub mod logging;
 pub mod helpers;
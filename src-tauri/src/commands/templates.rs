@@ -3,7 +3,7 @@ use crate::database::with_database;
 use crate::templates::Template;
 use crate::commands::helpers;
 use crate::validation::job::ValidationResult;
-use crate::{log_info, log_error};
+use crate::{log_info, log_error, log_warn};
 use std::collections::HashMap;
 use serde_json::Value;
 use anyhow::{Result, anyhow};
@@ -45,6 +45,15 @@ pub async fn get_template(template_id: String) -> ApiResult<Template> {
     ApiResult::success(template)
 }
 
+/// Get a template with its parent templates merged in - the form a job is created from
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_resolved_template(template_id: String) -> ApiResult<Template> {
+    match helpers::load_resolved_template_or_fail(&template_id, "Templates") {
+        Ok(template) => ApiResult::success(template),
+        Err(e) => ApiResult::error(e.to_string()),
+    }
+}
+
 /// Create a new template
 #[tauri::command(rename_all = "snake_case")]
 pub async fn create_template(template: Template) -> ApiResult<String> {
@@ -65,6 +74,10 @@ pub async fn create_template(template: Template) -> ApiResult<String> {
         }
     }
 
+    if let Err(e) = check_template(&template) {
+        return ApiResult::error(e.to_string());
+    }
+
     // Save the new template
     let template_id = template.id.clone();
     match with_database(|db| db.save_template(&template)) {
@@ -89,6 +102,10 @@ pub async fn update_template(template_id: String, template: Template) -> ApiResu
         return ApiResult::error(e.to_string());
     }
 
+    if let Err(e) = check_template(&template) {
+        return ApiResult::error(e.to_string());
+    }
+
     // Save updated template
     match with_database(|db| db.save_template(&template)) {
         Ok(_) => {
@@ -102,7 +119,43 @@ pub async fn update_template(template_id: String, template: Template) -> ApiResu
     }
 }
 
-/// Delete a template (blocked if jobs are using it)
+/// Save a copy of a template under a new name and a freshly generated ID
+/// The copy keeps the source's parent, if any; default templates are recognised by ID, so the
+/// copy is an ordinary user template
+#[tauri::command(rename_all = "snake_case")]
+pub async fn duplicate_template(source_id: String, new_name: String) -> ApiResult<Template> {
+    log_info!(category: "Templates", message: "Duplicating template", details: "Source: {}, Name: '{}'", source_id, new_name);
+
+    let source = match helpers::load_template_or_fail(&source_id, "Templates") {
+        Ok(t) => t,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    let new_id = crate::automations::common::generate_unique_id(&format!("{}_copy", source.id), |id| {
+        let id = id.to_string();
+        Ok(with_database(move |db| db.load_template(&id))?.is_some())
+    });
+    let template = match new_id.and_then(|id| copy_template(&source, id, &new_name, chrono::Utc::now().to_rfc3339())) {
+        Ok(t) => t,
+        Err(e) => {
+            log_error!(category: "Templates", message: "Failed to duplicate template", details: "Error: {}", e);
+            return ApiResult::error(e.to_string());
+        }
+    };
+
+    match with_database(|db| db.save_template(&template)) {
+        Ok(_) => {
+            log_info!(category: "Templates", message: "Template duplicated successfully", details: "Name: '{}', ID: '{}'", template.name, template.id, show_toast: true);
+            ApiResult::success(template)
+        }
+        Err(e) => {
+            log_error!(category: "Templates", message: "Failed to save duplicated template", details: "Error: {}", e);
+            ApiResult::error(format!("Failed to save template: {}", e))
+        }
+    }
+}
+
+/// Delete a template (blocked if jobs are using it or other templates extend it)
 #[tauri::command(rename_all = "snake_case")]
 pub async fn delete_template(template_id: String) -> ApiResult<()> {
     log_info!(category: "Templates", message: "Deleting template", details: "ID: {}", template_id);
@@ -121,6 +174,19 @@ pub async fn delete_template(template_id: String) -> ApiResult<()> {
         return ApiResult::error(format!("Cannot delete template: {} job(s) are using it", job_count));
     }
 
+    // Templates that extend this one would lose their config
+    let children = match with_database(|db| db.list_child_templates(&template_id)) {
+        Ok(children) => children,
+        Err(e) => {
+            log_error!(category: "Templates", message: "Failed to check child templates", details: "Error: {}", e);
+            return ApiResult::error(format!("Database error: {}", e));
+        }
+    };
+    if !children.is_empty() {
+        log_error!(category: "Templates", message: "Cannot delete template - other templates extend it", details: "Template: {}, Children: {}", template_id, children.join(", "));
+        return ApiResult::error(format!("Cannot delete template: extended by {}", children.join(", ")));
+    }
+
     match with_database(|db| db.delete_template(&template_id)) {
         Ok(true) => {
             log_info!(category: "Templates", message: "Template deleted successfully", show_toast: true);
@@ -137,6 +203,48 @@ pub async fn delete_template(template_id: String) -> ApiResult<()> {
     }
 }
 
+/// Per-template job counts (total and by status) and last-used date
+#[tauri::command(rename_all = "snake_case")]
+pub async fn template_usage_stats() -> ApiResult<Vec<crate::types::response_data::TemplateUsage>> {
+    match with_database(|db| db.template_usage_stats()) {
+        Ok(stats) => ApiResult::success(stats),
+        Err(e) => {
+            log_error!(category: "Templates", message: "Failed to compute template usage", details: "Error: {}", e);
+            ApiResult::error(format!("Database error: {}", e))
+        }
+    }
+}
+
+/// Check which jobs using a template would still validate against its current definition
+/// Reports missing, removed, and retyped variables per job before bulk operations
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_jobs_compatible(template_id: String) -> ApiResult<Vec<crate::types::response_data::JobCompatibility>> {
+    log_info!(category: "Templates", message: "Checking job compatibility", details: "Template: {}", template_id);
+
+    let template = match helpers::load_resolved_template_or_fail(&template_id, "Templates") {
+        Ok(t) => t,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    let jobs = match with_database(|db| db.load_all_jobs()) {
+        Ok(loaded) => loaded.jobs,
+        Err(e) => {
+            log_error!(category: "Templates", message: "Failed to load jobs", details: "Error: {}", e);
+            return ApiResult::error(format!("Database error: {}", e));
+        }
+    };
+
+    let reports: Vec<_> = jobs.iter()
+        .filter(|job| job.template_id == template_id)
+        .map(|job| crate::validation::template::check_job_compatibility(&template, job))
+        .collect();
+
+    let incompatible = reports.iter().filter(|r| !r.compatible).count();
+    log_info!(category: "Templates", message: "Compatibility check complete", details: "{} of {} jobs incompatible", incompatible, reports.len());
+
+    ApiResult::success(reports)
+}
+
 /// Validate template values against template definition
 #[tauri::command(rename_all = "snake_case")]
 pub async fn validate_template_values(
@@ -145,7 +253,7 @@ pub async fn validate_template_values(
 ) -> ValidationResult {
 
     // Load template
-    let template = match helpers::load_template_or_fail(&template_id, "Templates") {
+    let template = match helpers::load_resolved_template_or_fail(&template_id, "Templates") {
         Ok(t) => t,
         Err(e) => {
             return ValidationResult {
@@ -177,7 +285,7 @@ pub async fn preview_namd_config(
     log_info!(category: "Templates", message: "Previewing NAMD config", details: "Template: {}", template_id);
 
     // Load template
-    let template = match helpers::load_template_or_fail(&template_id, "Templates") {
+    let template = match helpers::load_resolved_template_or_fail(&template_id, "Templates") {
         Ok(t) => t,
         Err(e) => return ApiResult::error(e.to_string()),
     };
@@ -195,13 +303,62 @@ pub async fn preview_namd_config(
     }
 }
 
+/// Render a template with the real filenames about to be uploaded
+/// Shows exactly what will be sent to the cluster, unlike the sample-name previews
+#[tauri::command(rename_all = "snake_case")]
+pub async fn render_with_files(
+    template_id: String,
+    values: HashMap<String, Value>,
+    file_names: HashMap<String, String>,
+) -> ApiResult<String> {
+    log_info!(category: "Templates", message: "Rendering template with upload files", details: "Template: {}, {} files", template_id, file_names.len());
+
+    let template = match helpers::load_resolved_template_or_fail(&template_id, "Templates") {
+        Ok(t) => t,
+        Err(e) => return ApiResult::error(e.to_string()),
+    };
+
+    match render_with_file_names(&template, &values, &file_names) {
+        Ok(rendered) => ApiResult::success(rendered),
+        Err(e) => {
+            log_error!(category: "Templates", message: "Render with files failed", details: "Error: {}", e);
+            ApiResult::error(e.to_string())
+        }
+    }
+}
+
+/// Validate real filenames, substitute them for the FileUpload values, and render
+fn render_with_file_names(
+    template: &Template,
+    values: &HashMap<String, Value>,
+    file_names: &HashMap<String, String>,
+) -> Result<String> {
+    let file_check = crate::validation::template::validate_file_names(template, values, file_names);
+    if !file_check.is_valid {
+        return Err(anyhow!("Invalid files: {}", file_check.issues.join("; ")));
+    }
+
+    let mut merged = values.clone();
+    for (key, name) in file_names {
+        merged.insert(key.clone(), Value::from(name.clone()));
+    }
+
+    let value_check = crate::validation::template::validate_values(template, &merged);
+    if !value_check.is_valid {
+        return Err(anyhow!("Invalid values: {}", value_check.issues.join("; ")));
+    }
+
+    crate::templates::render_template(template, &merged)
+        .map_err(|e| anyhow!("Rendering error: {}", e))
+}
+
 /// Preview template with default/sample values (for template editor testing)
 #[tauri::command(rename_all = "snake_case")]
 pub async fn preview_template_with_defaults(template_id: String) -> ApiResult<String> {
     log_info!(category: "Templates", message: "Previewing template with defaults", details: "Template: {}", template_id);
 
     // Load template
-    let template = match helpers::load_template_or_fail(&template_id, "Templates") {
+    let template = match helpers::load_resolved_template_or_fail(&template_id, "Templates") {
         Ok(t) => t,
         Err(e) => return ApiResult::error(e.to_string()),
     };
@@ -213,6 +370,8 @@ pub async fn preview_template_with_defaults(template_id: String) -> ApiResult<St
             crate::templates::VariableType::Number { default, .. } => Value::from(*default),
             crate::templates::VariableType::Text { default } => Value::from(default.clone()),
             crate::templates::VariableType::Boolean { default } => Value::from(*default),
+            crate::templates::VariableType::Select { default, .. } => Value::from(default.clone()),
+            crate::templates::VariableType::List { default } => Value::from(default.clone()),
             crate::templates::VariableType::FileUpload { extensions } => {
                 // Generate sample filename (renderer will prepend input_files/)
                 let default_ext = ".dat".to_string();
@@ -328,19 +487,33 @@ pub async fn import_template() -> ApiResult<Template> {
         }
     };
 
-    // Validate template structure
-    if let Err(e) = validate_template_structure(&template) {
-        log_error!(category: "Templates", message: "Template validation failed", details: "Error: {}", e);
-        return ApiResult::error(e.to_string());
+    // Validate template structure with any parent merged in (a parent has to be imported first)
+    let validated = crate::templates::resolve_template(&template, helpers::load_template_by_id)
+        .and_then(|resolved| validate_template_structure(&resolved));
+    match validated {
+        Ok(warnings) => log_template_warnings(&warnings),
+        Err(e) => {
+            log_error!(category: "Templates", message: "Template validation failed", details: "Error: {}", e);
+            return ApiResult::error(e.to_string());
+        }
     }
 
     // Handle ID conflicts - generate new ID if needed
     match with_database(|db| db.load_template(&template.id)) {
         Ok(Some(_existing)) => {
             // Template with this ID exists - generate new ID
-            let timestamp = chrono::Utc::now().timestamp();
             let old_id = template.id.clone();
-            template.id = format!("{}_imported_{}", old_id, timestamp);
+            let new_id = crate::automations::common::generate_unique_id(&format!("{}_imported", old_id), |id| {
+                let id = id.to_string();
+                Ok(with_database(move |db| db.load_template(&id))?.is_some())
+            });
+            template.id = match new_id {
+                Ok(id) => id,
+                Err(e) => {
+                    log_error!(category: "Templates", message: "Could not generate template ID", details: "Error: {}", e);
+                    return ApiResult::error(format!("Database error: {}", e));
+                }
+            };
             log_info!(category: "Templates", message: "Template ID conflict resolved", details: "Original: '{}', New: '{}'", old_id, template.id);
         }
         Ok(None) => {
@@ -381,8 +554,72 @@ fn sanitize_filename(name: &str) -> String {
         .collect()
 }
 
-/// Validate template structure for import
-fn validate_template_structure(template: &Template) -> Result<()> {
+/// A copy of `source` with a new ID and name, created and updated at `now`
+fn copy_template(source: &Template, id: String, name: &str, now: String) -> Result<Template> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("Template name is required"));
+    }
+    Ok(Template {
+        id,
+        name: name.to_string(),
+        created_at: now.clone(),
+        updated_at: now,
+        ..source.clone()
+    })
+}
+
+/// Validate a template about to be created or updated, logging any warnings
+fn check_template(template: &Template) -> Result<()> {
+    let warnings = check_inheritance(template)
+        .and_then(|resolved| validate_template_structure(&resolved))
+        .inspect_err(|e| {
+            log_error!(category: "Templates", message: "Template validation failed", details: "Error: {}", e);
+        })?;
+    log_template_warnings(&warnings);
+    Ok(())
+}
+
+fn log_template_warnings(warnings: &[String]) {
+    for warning in warnings {
+        log_warn!(category: "Templates", message: warning, show_toast: true);
+    }
+}
+
+/// Check that a template about to be saved resolves, along with every template that extends it,
+/// and return it resolved. Catches a missing parent, cycles, and edits to a parent that would
+/// break a child's block overrides or drop a variable the child's blocks use
+fn check_inheritance(template: &Template) -> Result<Template> {
+    // The candidate stands in for the stored version of itself
+    let load = |id: &str| -> Result<Option<Template>> {
+        if id == template.id {
+            Ok(Some(template.clone()))
+        } else {
+            helpers::load_template_by_id(id)
+        }
+    };
+    let resolved = crate::templates::resolve_template(template, load)?;
+
+    let mut pending = vec![template.id.clone()];
+    let mut checked = std::collections::HashSet::new();
+    while let Some(id) = pending.pop() {
+        if !checked.insert(id.clone()) {
+            continue;
+        }
+        for child_id in with_database(|db| db.list_child_templates(&id))? {
+            if let Some(child) = load(&child_id)? {
+                crate::templates::resolve_template(&child, load)
+                    .and_then(|resolved_child| crate::validation::template::validate_template_completeness(&resolved_child))
+                    .map_err(|e| anyhow!("Template '{}' extends this one and would break: {}", child_id, e))?;
+            }
+            pending.push(child_id);
+        }
+    }
+    Ok(resolved)
+}
+
+/// Validate template structure (inheritance already resolved) before saving; returns warnings
+fn validate_template_structure(template: &Template) -> Result<Vec<String>> {
     // Validate required fields
     if template.id.trim().is_empty() {
         return Err(anyhow!("Template ID is required"));
@@ -396,6 +633,10 @@ fn validate_template_structure(template: &Template) -> Result<()> {
         return Err(anyhow!("NAMD config template is required"));
     }
 
+    crate::templates::inheritance::validate_blocks(&template.namd_config_template)
+        .and_then(|_| crate::templates::renderer::validate_syntax(&template.namd_config_template))
+        .map_err(|e| anyhow!("NAMD config template: {}", e))?;
+
     // Validate ID contains only safe characters
     if !template.id.chars().all(|c| c.is_alphanumeric() || c == '_') {
         return Err(anyhow!("Template ID must contain only alphanumeric characters and underscores"));
@@ -441,11 +682,55 @@ fn validate_template_structure(template: &Template) -> Result<()> {
                     }
                 }
             }
+            crate::templates::VariableType::Select { options, default } => {
+                if options.is_empty() {
+                    return Err(anyhow!("Variable '{}': Select must specify at least one option", key));
+                }
+                if !options.contains(default) {
+                    return Err(anyhow!("Variable '{}': default '{}' must be one of the options {:?}", key, default, options));
+                }
+            }
             _ => {} // Text and Boolean have no constraints to validate
         }
+
+        // Validate units: only Numbers convert, and only within one dimension
+        let is_number = matches!(var_def.var_type, crate::templates::VariableType::Number { .. });
+        if !is_number && (var_def.unit.is_some() || var_def.render_unit.is_some() || var_def.precision.is_some()) {
+            return Err(anyhow!("Variable '{}': unit, render_unit and precision only apply to Number variables", key));
+        }
+        match (&var_def.unit, &var_def.render_unit) {
+            (Some(unit), Some(render_unit)) => crate::templates::units::check_conversion(unit, render_unit)
+                .map_err(|e| anyhow!("Variable '{}': {}", key, e))?,
+            (None, Some(_)) => return Err(anyhow!("Variable '{}': render_unit needs a unit to convert from", key)),
+            _ => {}
+        }
+        if var_def.precision.is_some_and(|p| p > crate::templates::units::MAX_PRECISION) {
+            return Err(anyhow!("Variable '{}': precision can be at most {} decimal places", key, crate::templates::units::MAX_PRECISION));
+        }
+
+        // Validate visibility condition
+        if let Some(condition) = &var_def.visible_when {
+            if condition.variable == *key {
+                return Err(anyhow!("Variable '{}': visible_when cannot refer to itself", key));
+            }
+            if !template.variables.contains_key(&condition.variable) {
+                return Err(anyhow!("Variable '{}': visible_when refers to unknown variable '{}'", key, condition.variable));
+            }
+            // Follow the chain of conditions; coming back to this variable means a cycle
+            let mut current = &condition.variable;
+            for _ in 0..template.variables.len() {
+                match template.variables.get(current).and_then(|v| v.visible_when.as_ref()) {
+                    Some(next) if next.variable == *key => {
+                        return Err(anyhow!("Variable '{}': visible_when conditions form a cycle", key));
+                    }
+                    Some(next) => current = &next.variable,
+                    None => break,
+                }
+            }
+        }
     }
 
-    Ok(())
+    crate::validation::template::validate_template_completeness(template)
 }
 
 #[cfg(test)]
@@ -466,6 +751,10 @@ mod tests {
                     default: 300.0,
                 },
                 help_text: Some("Simulation temperature".to_string()),
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
         variables.insert(
@@ -477,6 +766,10 @@ mod tests {
                     extensions: vec![".psf".to_string()],
                 },
                 help_text: Some("PSF structure file".to_string()),
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
 
@@ -486,6 +779,7 @@ mod tests {
             description: "Test template".to_string(),
             namd_config_template: "temperature {{temperature}}\nstructure {{structure_file}}".to_string(),
             variables,
+            extends: None,
             created_at: "2025-01-01T00:00:00Z".to_string(),
             updated_at: "2025-01-01T00:00:00Z".to_string(),
         }
@@ -560,6 +854,10 @@ mod tests {
                     default: "test".to_string(),
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
         let result = validate_template_structure(&template);
@@ -579,6 +877,10 @@ mod tests {
                     default: "test".to_string(),
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
         let result = validate_template_structure(&template);
@@ -600,6 +902,10 @@ mod tests {
                     default: 75.0,
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
         let result = validate_template_structure(&template);
@@ -623,6 +929,10 @@ mod tests {
                     default: 200.0, // out of range
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
         let result = validate_template_structure(&template);
@@ -644,6 +954,10 @@ mod tests {
                     extensions: vec![],
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
         let result = validate_template_structure(&template);
@@ -663,6 +977,10 @@ mod tests {
                     extensions: vec!["psf".to_string()], // missing dot
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
         let result = validate_template_structure(&template);
@@ -670,6 +988,115 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("must start with a dot"));
     }
 
+    #[test]
+    fn test_validate_template_structure_select() {
+        let select = |options: &[&str], default: &str| VariableDefinition {
+            key: "integrator".to_string(),
+            label: "Integrator".to_string(),
+            var_type: VariableType::Select {
+                options: options.iter().map(|o| o.to_string()).collect(),
+                default: default.to_string(),
+            },
+            help_text: None,
+            visible_when: None,
+            unit: None,
+            render_unit: None,
+            precision: None,
+        };
+        let mut template = create_test_template("test_id", "Test");
+
+        template.variables.insert("integrator".to_string(), select(&["velocity-verlet", "langevin"], "langevin"));
+        assert!(validate_template_structure(&template).is_ok());
+
+        template.variables.insert("integrator".to_string(), select(&[], ""));
+        let err = validate_template_structure(&template).unwrap_err().to_string();
+        assert!(err.contains("at least one option"));
+
+        template.variables.insert("integrator".to_string(), select(&["velocity-verlet", "langevin"], "brownian"));
+        let err = validate_template_structure(&template).unwrap_err().to_string();
+        assert!(err.contains("must be one of the options"));
+    }
+
+    #[test]
+    fn test_validate_template_structure_visible_when() {
+        use crate::templates::Condition;
+        let condition = |variable: &str| Some(Condition { variable: variable.to_string(), equals: Value::from(true) });
+
+        let mut template = create_test_template("test_id", "Test");
+        template.variables.get_mut("temperature").unwrap().visible_when = condition("structure_file");
+        assert!(validate_template_structure(&template).is_ok());
+
+        template.variables.get_mut("temperature").unwrap().visible_when = condition("npt_enabled");
+        let err = validate_template_structure(&template).unwrap_err().to_string();
+        assert!(err.contains("unknown variable 'npt_enabled'"));
+
+        template.variables.get_mut("temperature").unwrap().visible_when = condition("structure_file");
+        template.variables.get_mut("structure_file").unwrap().visible_when = condition("temperature");
+        let err = validate_template_structure(&template).unwrap_err().to_string();
+        assert!(err.contains("form a cycle"));
+
+        // Templates exported before visibility conditions existed still import
+        let json = serde_json::to_string(&create_test_template("test_id", "Test")).unwrap();
+        assert!(!json.contains("visible_when"));
+        let imported: Template = serde_json::from_str(&json).unwrap();
+        assert!(imported.variables.values().all(|v| v.visible_when.is_none()));
+    }
+
+    #[test]
+    fn test_validate_template_structure_undefined_variable() {
+        let mut template = create_test_template("test_id", "Test");
+        template.namd_config_template = "temperature {{temperatur}}\nstructure {{structure_file}}".to_string();
+        let err = validate_template_structure(&template).unwrap_err().to_string();
+        assert!(err.contains("undefined variables: {{temperatur}} on line 1"), "{}", err);
+
+        template.namd_config_template = "structure {{structure_file}}".to_string();
+        let warnings = validate_template_structure(&template).unwrap();
+        assert_eq!(warnings, vec!["Variable 'temperature' is defined but never used in the NAMD config template"]);
+    }
+
+    #[test]
+    fn test_validate_template_structure_units() {
+        let mut template = create_test_template("test_id", "Test");
+        let temperature = template.variables.get_mut("temperature").unwrap();
+        temperature.unit = Some("C".to_string());
+        temperature.render_unit = Some("K".to_string());
+        temperature.precision = Some(2);
+        assert!(validate_template_structure(&template).is_ok());
+
+        template.variables.get_mut("temperature").unwrap().render_unit = Some("nm".to_string());
+        let err = validate_template_structure(&template).unwrap_err().to_string();
+        assert!(err.contains("cannot convert 'C' (temperature) to 'nm' (length)"), "{}", err);
+
+        let temperature = template.variables.get_mut("temperature").unwrap();
+        temperature.unit = None;
+        let err = validate_template_structure(&template).unwrap_err().to_string();
+        assert!(err.contains("render_unit needs a unit"), "{}", err);
+
+        let mut template = create_test_template("test_id", "Test");
+        template.variables.get_mut("structure_file").unwrap().unit = Some("A".to_string());
+        let err = validate_template_structure(&template).unwrap_err().to_string();
+        assert!(err.contains("only apply to Number variables"), "{}", err);
+    }
+
+    #[test]
+    fn test_copy_template() {
+        let mut source = create_test_template("npt_base", "NPT Base");
+        source.extends = Some("equilibration".to_string());
+
+        let copy = copy_template(&source, "npt_base_copy_1".to_string(), "  NPT 310K ", "2025-06-01T00:00:00+00:00".to_string()).unwrap();
+        assert_eq!(copy.id, "npt_base_copy_1");
+        assert_eq!(copy.name, "NPT 310K");
+        assert_eq!(copy.created_at, "2025-06-01T00:00:00+00:00");
+        assert_eq!(copy.updated_at, copy.created_at);
+        assert_eq!(copy.namd_config_template, source.namd_config_template);
+        assert_eq!(copy.variables.len(), source.variables.len());
+        assert_eq!(copy.extends.as_deref(), Some("equilibration"));
+        assert!(validate_template_structure(&copy).is_ok());
+
+        let err = copy_template(&source, "x".to_string(), "   ", String::new()).unwrap_err().to_string();
+        assert_eq!(err, "Template name is required");
+    }
+
     #[test]
     fn test_template_json_round_trip() {
         let template = create_test_template("test_template", "Test Template");
@@ -703,4 +1130,35 @@ mod tests {
         assert!(json.contains("\"created_at\""));
         assert!(json.contains("\"updated_at\""));
     }
+
+    #[test]
+    fn test_render_with_real_file_names() {
+        let template = create_test_template("test_template", "Test Template");
+        let mut values = HashMap::new();
+        values.insert("temperature".to_string(), Value::from(310.0));
+        // Sample value from the form is replaced by the real upload
+        values.insert("structure_file".to_string(), Value::from("structure_file.psf"));
+        let files: HashMap<String, String> = [("structure_file".to_string(), "/home/user/sims/hiv_protease.psf".to_string())].into();
+
+        let rendered = render_with_file_names(&template, &values, &files).unwrap();
+
+        assert_eq!(rendered, "temperature 310\nstructure input_files/hiv_protease.psf");
+    }
+
+    #[test]
+    fn test_render_with_files_rejects_bad_extension_and_values() {
+        let template = create_test_template("test_template", "Test Template");
+        let mut values = HashMap::new();
+        values.insert("temperature".to_string(), Value::from(310.0));
+
+        let wrong_ext: HashMap<String, String> = [("structure_file".to_string(), "hiv_protease.pdb".to_string())].into();
+        let err = render_with_file_names(&template, &values, &wrong_ext).unwrap_err().to_string();
+        assert!(err.starts_with("Invalid files:"));
+        assert!(err.contains("hiv_protease.pdb"));
+
+        values.insert("temperature".to_string(), Value::from(900.0));
+        let good: HashMap<String, String> = [("structure_file".to_string(), "hiv_protease.psf".to_string())].into();
+        let err = render_with_file_names(&template, &values, &good).unwrap_err().to_string();
+        assert!(err.starts_with("Invalid values:"));
+    }
 }
@@ -11,11 +11,13 @@ pub fn validate_resource_allocation_command(
     walltime: String,
     partition_id: String,
     qos_id: String,
+    gpus: Option<u32>,
+    gpu_type: Option<String>,
 ) -> ValidationResult {
     crate::log_info!(
         category: "Validation",
         message: "Validating resource allocation",
-        details: "cores: {}, memory: {}, partition: {}, qos: {}", cores, memory, partition_id, qos_id
+        details: "cores: {}, gpus: {}, memory: {}, partition: {}, qos: {}", cores, gpus.unwrap_or(0), memory, partition_id, qos_id
     );
 
     let config = crate::types::SlurmConfig {
@@ -24,6 +26,8 @@ pub fn validate_resource_allocation_command(
         walltime,
         partition: partition_id.clone(),
         qos: qos_id.clone(),
+        gpus,
+        gpu_type,
     };
 
     let result = validate_resource_allocation(&config, &partition_id, &qos_id);
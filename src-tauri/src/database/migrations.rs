@@ -0,0 +1,191 @@
+use rusqlite::{Connection, Transaction};
+use anyhow::{Result, anyhow};
+use crate::{log_info, log_warn};
+
+/// One ordered change to stored data, applied once and recorded in `schema_version`
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    pub apply: fn(&Transaction) -> Result<()>,
+}
+
+/// Every migration in order; append new ones with the next version number
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Backfill empty tags on job records",
+        apply: backfill_job_tags,
+    },
+    Migration {
+        version: 2,
+        description: "Add extends column to templates",
+        apply: add_template_extends,
+    },
+];
+
+/// Version recorded in the database; 0 for databases created before versioning
+pub fn current_version(conn: &Connection) -> Result<u32> {
+    conn.execute_batch("CREATE TABLE IF NOT EXISTS schema_version (version INTEGER NOT NULL);")?;
+    let version: Option<u32> = conn.query_row("SELECT MAX(version) FROM schema_version", [], |row| row.get(0))?;
+    Ok(version.unwrap_or(0))
+}
+
+/// Apply every migration newer than the database's version, each in its own transaction
+/// A failing migration rolls back and stops the run, leaving the database at the last good version
+pub fn run_migrations(conn: &Connection, migrations: &[Migration]) -> Result<u32> {
+    let mut version = current_version(conn)?;
+
+    if let Some(latest) = migrations.last().map(|migration| migration.version) {
+        if version > latest {
+            // Written by a newer NAMDRunner; records still load because new fields are defaulted
+            log_warn!(category: "Database", message: "Database schema is newer than this version of NAMDRunner", details: "schema {} > {}", version, latest);
+        }
+    }
+
+    for migration in migrations {
+        if migration.version <= version {
+            continue;
+        }
+        let tx = conn.unchecked_transaction()?;
+        (migration.apply)(&tx)
+            .map_err(|e| anyhow!("Migration {} ({}) failed: {}", migration.version, migration.description, e))?;
+        tx.execute("DELETE FROM schema_version", [])?;
+        tx.execute("INSERT INTO schema_version (version) VALUES (?1)", [migration.version])?;
+        tx.commit()?;
+
+        log_info!(category: "Database", message: "Applied database migration", details: "{}: {}", migration.version, migration.description);
+        version = migration.version;
+    }
+
+    Ok(version)
+}
+
+/// Job records saved before tags existed get an explicit empty list
+fn backfill_job_tags(tx: &Transaction) -> Result<()> {
+    tx.execute(
+        "UPDATE jobs SET data = json_set(data, '$.tags', json('[]'))
+         WHERE json_valid(data) AND json_type(data) = 'object' AND json_type(data, '$.tags') IS NULL",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Parent template ID for template inheritance; NULL for standalone templates
+fn add_template_extends(tx: &Transaction) -> Result<()> {
+    tx.execute("ALTER TABLE templates ADD COLUMN extends TEXT", [])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("
+            CREATE TABLE jobs (job_id TEXT PRIMARY KEY, data TEXT NOT NULL);
+            CREATE TABLE templates (
+                id TEXT PRIMARY KEY, name TEXT NOT NULL, description TEXT, namd_config_template TEXT NOT NULL,
+                variables TEXT NOT NULL, created_at TEXT NOT NULL, updated_at TEXT NOT NULL
+            );
+        ").unwrap();
+        conn.execute(
+            "INSERT INTO jobs (job_id, data) VALUES ('job_old', ?1)",
+            [r#"{"job_id": "job_old", "job_name": "equilibration", "status": "COMPLETED"}"#],
+        ).unwrap();
+        conn.execute("INSERT INTO jobs (job_id, data) VALUES ('job_tagged', ?1)", [r#"{"job_id": "job_tagged", "tags": ["keep"]}"#]).unwrap();
+        conn.execute("INSERT INTO jobs (job_id, data) VALUES ('job_array', '[1, 2, 3]')", []).unwrap();
+        conn
+    }
+
+    fn job_data(conn: &Connection, job_id: &str) -> serde_json::Value {
+        let data: String = conn.query_row("SELECT data FROM jobs WHERE job_id = ?1", [job_id], |row| row.get(0)).unwrap();
+        serde_json::from_str(&data).unwrap()
+    }
+
+    const LATEST: u32 = MIGRATIONS.len() as u32;
+
+    #[test]
+    fn test_backfill_tags_from_version_zero() {
+        let conn = legacy_db();
+        assert_eq!(current_version(&conn).unwrap(), 0);
+
+        assert_eq!(run_migrations(&conn, MIGRATIONS).unwrap(), LATEST);
+        assert_eq!(current_version(&conn).unwrap(), LATEST);
+
+        let old = job_data(&conn, "job_old");
+        assert_eq!(old["tags"], serde_json::json!([]));
+        assert_eq!(old["job_name"], "equilibration");
+        assert_eq!(old["status"], "COMPLETED");
+        assert_eq!(job_data(&conn, "job_tagged")["tags"], serde_json::json!(["keep"]));
+        assert_eq!(job_data(&conn, "job_array"), serde_json::json!([1, 2, 3]));
+
+        // Already current: nothing to do
+        assert_eq!(run_migrations(&conn, MIGRATIONS).unwrap(), LATEST);
+    }
+
+    #[test]
+    fn test_template_extends_column() {
+        let conn = legacy_db();
+        conn.execute(
+            "INSERT INTO templates VALUES ('vacuum', 'Vacuum', '', 'run 1000', '{}', '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+        run_migrations(&conn, MIGRATIONS).unwrap();
+
+        let extends: Option<String> = conn.query_row("SELECT extends FROM templates WHERE id = 'vacuum'", [], |row| row.get(0)).unwrap();
+        assert_eq!(extends, None);
+    }
+
+    fn rename_job_name(tx: &Transaction) -> Result<()> {
+        tx.execute(
+            "UPDATE jobs SET data = json_remove(json_set(data, '$.name', json_extract(data, '$.job_name')), '$.job_name')
+             WHERE json_type(data) = 'object' AND json_type(data, '$.job_name') IS NOT NULL",
+            [],
+        )?;
+        Ok(())
+    }
+
+    fn fail_after_writing(tx: &Transaction) -> Result<()> {
+        tx.execute("DELETE FROM jobs", [])?;
+        Err(anyhow!("disk full"))
+    }
+
+    #[test]
+    fn test_migrations_apply_in_order_from_version_n() {
+        let conn = legacy_db();
+        run_migrations(&conn, MIGRATIONS).unwrap();
+
+        let migrations = [
+            Migration { version: LATEST, description: "already applied", apply: fail_after_writing },
+            Migration { version: LATEST + 1, description: "rename job_name", apply: rename_job_name },
+        ];
+        assert_eq!(run_migrations(&conn, &migrations).unwrap(), LATEST + 1);
+        assert_eq!(current_version(&conn).unwrap(), LATEST + 1);
+
+        let old = job_data(&conn, "job_old");
+        assert_eq!(old["name"], "equilibration");
+        assert!(old.get("job_name").is_none());
+        assert_eq!(old["tags"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() {
+        let conn = legacy_db();
+        run_migrations(&conn, MIGRATIONS).unwrap();
+
+        let migrations = [
+            Migration { version: LATEST, description: "already applied", apply: backfill_job_tags },
+            Migration { version: LATEST + 1, description: "broken", apply: fail_after_writing },
+            Migration { version: LATEST + 2, description: "rename job_name", apply: rename_job_name },
+        ];
+        let error = run_migrations(&conn, &migrations).unwrap_err();
+        assert!(error.to_string().contains(&format!("Migration {} (broken) failed", LATEST + 1)));
+
+        // Still at the last good version, with every row intact and later migrations not applied
+        assert_eq!(current_version(&conn).unwrap(), LATEST);
+        assert_eq!(job_data(&conn, "job_old")["job_name"], "equilibration");
+        let count: u32 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 3);
+    }
+}
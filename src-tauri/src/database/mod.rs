@@ -1,14 +1,25 @@
+pub mod migrations;
+
 use rusqlite::Connection;
-use crate::types::JobInfo;
+use crate::types::{JobInfo, JobSortField, JobStatus, SortOrder};
+use crate::types::response_data::JobSummary;
 use crate::templates::{Template, TemplateSummary};
 use anyhow::{Result, anyhow};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
-use crate::{log_info, log_debug};
+use crate::{log_info, log_debug, log_warn};
 use tauri::Manager;
 
+/// Jobs loaded from the database, plus how many rows couldn't be parsed
+#[derive(Debug, Default)]
+pub struct JobLoadResult {
+    pub jobs: Vec<JobInfo>,
+    pub skipped_rows: usize,
+}
+
 /// Simple document-store database for jobs and templates
-/// Stores JobInfo and Template as JSON - no complex schema, no migrations needed
+/// Stores JobInfo and Template as JSON - no complex schema; changes to stored records go
+/// through the ordered migrations in `migrations.rs`
 #[derive(Clone)]
 pub struct JobDatabase {
     conn: Arc<Mutex<Connection>>,
@@ -47,6 +58,7 @@ impl JobDatabase {
                 variables TEXT NOT NULL,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
+                -- extends (parent template ID) is added by migration 2
             );
 
             -- Cluster config table - stores ClusterCapabilities as JSON
@@ -54,7 +66,30 @@ impl JobDatabase {
                 id TEXT PRIMARY KEY,
                 data TEXT NOT NULL
             );
+
+            -- Cluster profiles table - stores named ClusterProfile login targets as JSON
+            CREATE TABLE IF NOT EXISTS cluster_profiles (
+                name TEXT PRIMARY KEY,
+                data TEXT NOT NULL
+            );
+
+            -- Recent connections - successful login targets (never credentials), newest first
+            CREATE TABLE IF NOT EXISTS recent_connections (
+                host TEXT NOT NULL,
+                username TEXT NOT NULL,
+                port INTEGER NOT NULL,
+                last_connected_at TEXT NOT NULL,
+                PRIMARY KEY (host, username)
+            );
+
+            -- Settings table - user preferences stored as JSON values by key
+            CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
         "#)?;
+
+        migrations::run_migrations(conn, migrations::MIGRATIONS)?;
         Ok(())
     }
 
@@ -88,26 +123,170 @@ impl JobDatabase {
         }
     }
 
-    pub fn load_all_jobs(&self) -> Result<Vec<JobInfo>> {
+    /// Load every job, skipping (and logging) rows whose JSON doesn't parse
+    /// One corrupt or outdated record shouldn't hide all the others
+    pub fn load_all_jobs(&self) -> Result<JobLoadResult> {
         let conn = self.conn.lock().unwrap();
 
         let mut stmt = conn.prepare(
-            "SELECT data FROM jobs ORDER BY json_extract(data, '$.created_at') DESC"
+            // json_valid guard: json_extract errors on malformed JSON, which would fail the whole query
+            "SELECT job_id, data FROM jobs
+             ORDER BY CASE WHEN json_valid(data) THEN json_extract(data, '$.created_at') END DESC"
         )?;
 
         let rows = stmt.query_map([], |row| {
-            let json_data: String = row.get(0)?;
-            Ok(json_data)
+            let job_id: String = row.get(0)?;
+            let json_data: String = row.get(1)?;
+            Ok((job_id, json_data))
         })?;
 
-        let mut jobs = Vec::new();
+        parse_job_rows(rows)
+    }
+
+    /// Load every job carrying `tag`, newest first, skipping rows whose JSON doesn't parse
+    /// Matched in SQL against the stored tags array; records without tags never match
+    pub fn load_jobs_by_tag(&self, tag: &str) -> Result<JobLoadResult> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT job_id, data FROM jobs
+             WHERE json_valid(data)
+               AND EXISTS (SELECT 1 FROM json_each(data, '$.tags') WHERE json_each.value = ?1)
+             ORDER BY json_extract(data, '$.created_at') DESC"
+        )?;
+
+        let rows = stmt.query_map([tag], |row| {
+            let job_id: String = row.get(0)?;
+            let json_data: String = row.get(1)?;
+            Ok((job_id, json_data))
+        })?;
+
+        parse_job_rows(rows)
+    }
+
+    /// Load one page of jobs (archived ones only with `include_archived`), sorted and sliced in
+    /// SQL so only that page is deserialized
+    /// Ties fall back to newest first, then job_id, so pages stay stable; `limit` None loads the rest
+    pub fn load_jobs_page(&self, offset: u32, limit: Option<u32>, sort_by: JobSortField, order: SortOrder, include_archived: bool) -> Result<JobLoadResult> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT job_id, data FROM jobs
+             WHERE json_valid(data) AND (?3 OR NOT COALESCE(json_extract(data, '$.archived'), 0))
+             ORDER BY json_extract(data, '{}') {}, json_extract(data, '$.created_at') DESC, job_id
+             LIMIT ?1 OFFSET ?2",
+            sort_by.json_path(),
+            order.sql()
+        ))?;
+
+        // SQLite treats a negative LIMIT as no limit
+        let limit = limit.map_or(-1, i64::from);
+        let rows = stmt.query_map(rusqlite::params![limit, offset, include_archived], |row| {
+            let job_id: String = row.get(0)?;
+            let json_data: String = row.get(1)?;
+            Ok((job_id, json_data))
+        })?;
+
+        parse_job_rows(rows)
+    }
+
+    /// Number of jobs `load_jobs_page` pages through with the same `include_archived`
+    /// Counted in SQL, so a record too outdated to deserialize is counted but never loaded
+    pub fn count_jobs(&self, include_archived: bool) -> Result<u32> {
+        let conn = self.conn.lock().unwrap();
+
+        let count: u32 = conn.query_row(
+            "SELECT COUNT(*) FROM jobs
+             WHERE json_valid(data) AND (?1 OR NOT COALESCE(json_extract(data, '$.archived'), 0))",
+            [include_archived],
+            |row| row.get(0),
+        )?;
+
+        Ok(count)
+    }
+
+    /// Load list rows for unarchived jobs, newest first
+    /// Extracts only the summary fields in SQL so cached logs are never deserialized
+    pub fn load_job_summaries(&self) -> Result<Vec<JobSummary>> {
+        self.query_job_summaries("", &[])
+    }
+
+    /// List rows for unarchived jobs matching `query`, newest first
+    /// Case-insensitive substring match on job name, template and status; the cached SLURM
+    /// logs are only scanned when `include_logs` is set since they can run to megabytes
+    pub fn search_job_summaries(&self, query: &str, include_logs: bool) -> Result<Vec<JobSummary>> {
+        let pattern = format!("%{}%", escape_like(query));
+        let mut fields = vec!["job_name", "template_id", "status"];
+        if include_logs {
+            fields.extend(["slurm_stdout", "slurm_stderr"]);
+        }
+        let filter = format!("AND ({})", fields.iter()
+            .map(|field| format!("json_extract(data, '$.{}') LIKE ?1 ESCAPE '\\'", field))
+            .collect::<Vec<_>>()
+            .join(" OR "));
+        self.query_job_summaries(&filter, &[&pattern])
+    }
+
+    /// Summary rows for unarchived jobs, narrowed by an extra `AND ...` clause (fixed SQL only;
+    /// user input goes through `params`)
+    fn query_job_summaries(&self, filter: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<JobSummary>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT job_id,
+                    json_extract(data, '$.job_name'),
+                    json_extract(data, '$.status'),
+                    json_extract(data, '$.slurm_job_id'),
+                    json_extract(data, '$.created_at'),
+                    json_extract(data, '$.updated_at'),
+                    json_extract(data, '$.submitted_at'),
+                    json_extract(data, '$.completed_at'),
+                    json_extract(data, '$.tags')
+             FROM jobs
+             WHERE json_valid(data) AND NOT COALESCE(json_extract(data, '$.archived'), 0) {}
+             ORDER BY json_extract(data, '$.created_at') DESC",
+            filter
+        ))?;
+
+        let rows = stmt.query_map(params, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+                row.get::<_, Option<String>>(7)?,
+                row.get::<_, Option<String>>(8)?,
+            ))
+        })?;
+
+        let mut summaries = Vec::new();
         for row_result in rows {
-            let json_data = row_result?;
-            let job_info: JobInfo = serde_json::from_str(&json_data)?;
-            jobs.push(job_info);
+            let (job_id, job_name, status, slurm_job_id, created_at, updated_at, submitted_at, completed_at, tags) = row_result?;
+            let status = status.and_then(|s| serde_json::from_value::<JobStatus>(serde_json::Value::String(s)).ok());
+            // Records saved before tags existed have none
+            let tags = tags.and_then(|tags| serde_json::from_str::<Vec<String>>(&tags).ok()).unwrap_or_default();
+
+            match (job_name, status, created_at) {
+                (Some(job_name), Some(status), Some(created_at)) => summaries.push(JobSummary {
+                    job_id, job_name, status, slurm_job_id, created_at, updated_at, submitted_at, completed_at, tags,
+                }),
+                _ => log_warn!(category: "Database", message: "Skipping unreadable job record", details: "{}: missing name, status or created_at", job_id),
+            }
         }
 
-        Ok(jobs)
+        Ok(summaries)
+    }
+
+    /// Load jobs for display - archived jobs stay in the database but are hidden
+    pub fn load_unarchived_jobs(&self) -> Result<Vec<JobInfo>> {
+        Ok(self.load_all_jobs()?
+            .jobs
+            .into_iter()
+            .filter(|job| !job.archived)
+            .collect())
     }
 
     pub fn delete_job(&self, job_id: &str) -> Result<bool> {
@@ -130,7 +309,7 @@ impl JobDatabase {
         let variables_json = serde_json::to_string(&template.variables)?;
 
         conn.execute(
-            "INSERT OR REPLACE INTO templates (id, name, description, namd_config_template, variables, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT OR REPLACE INTO templates (id, name, description, namd_config_template, variables, created_at, updated_at, extends) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             rusqlite::params![
                 &template.id,
                 &template.name,
@@ -139,6 +318,7 @@ impl JobDatabase {
                 &variables_json,
                 &template.created_at,
                 &template.updated_at,
+                &template.extends,
             ],
         )?;
 
@@ -149,7 +329,7 @@ impl JobDatabase {
         let conn = self.conn.lock().unwrap();
 
         let mut stmt = conn.prepare(
-            "SELECT id, name, description, namd_config_template, variables, created_at, updated_at FROM templates WHERE id = ?1"
+            "SELECT id, name, description, namd_config_template, variables, created_at, updated_at, extends FROM templates WHERE id = ?1"
         )?;
 
         let mut rows = stmt.query([id])?;
@@ -162,6 +342,7 @@ impl JobDatabase {
             let variables_json: String = row.get(4)?;
             let created_at: String = row.get(5)?;
             let updated_at: String = row.get(6)?;
+            let extends: Option<String> = row.get(7)?;
 
             let variables = serde_json::from_str(&variables_json)?;
 
@@ -171,6 +352,7 @@ impl JobDatabase {
                 description,
                 namd_config_template,
                 variables,
+                extends,
                 created_at,
                 updated_at,
             }))
@@ -202,6 +384,21 @@ impl JobDatabase {
         Ok(templates)
     }
 
+    /// IDs of templates that extend `id` directly, sorted
+    pub fn list_child_templates(&self, id: &str) -> Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare("SELECT id FROM templates WHERE extends = ?1 ORDER BY id")?;
+        let rows = stmt.query_map([id], |row| row.get(0))?;
+
+        let mut children = Vec::new();
+        for row_result in rows {
+            children.push(row_result?);
+        }
+
+        Ok(children)
+    }
+
     pub fn delete_template(&self, id: &str) -> Result<bool> {
         let conn = self.conn.lock().unwrap();
 
@@ -227,9 +424,82 @@ impl JobDatabase {
         Ok(count)
     }
 
+    /// Usage statistics for every template, most used first
+    /// Aggregated in SQL with json_extract; rows that aren't valid JSON are ignored
+    pub fn template_usage_stats(&self) -> Result<Vec<crate::types::response_data::TemplateUsage>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.name, COUNT(j.job_id), MAX(json_extract(j.data, '$.created_at'))
+             FROM templates t
+             LEFT JOIN jobs j ON json_valid(j.data) AND json_extract(j.data, '$.template_id') = t.id
+             GROUP BY t.id
+             ORDER BY COUNT(j.job_id) DESC, t.name"
+        )?;
+        let mut usage = stmt.query_map([], |row| {
+            Ok(crate::types::response_data::TemplateUsage {
+                template_id: row.get(0)?,
+                template_name: row.get(1)?,
+                total_jobs: row.get(2)?,
+                jobs_by_status: std::collections::BTreeMap::new(),
+                last_used: row.get(3)?,
+            })
+        })?.collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT json_extract(data, '$.template_id'), json_extract(data, '$.status'), COUNT(*)
+             FROM jobs
+             WHERE json_valid(data)
+             GROUP BY 1, 2"
+        )?;
+        let status_counts = stmt.query_map([], |row| {
+            Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, u32>(2)?))
+        })?;
+
+        for row in status_counts {
+            let (Some(template_id), Some(status), count) = row? else {
+                continue;
+            };
+            if let Some(entry) = usage.iter_mut().find(|u| u.template_id == template_id) {
+                entry.jobs_by_status.insert(status, count);
+            }
+        }
+
+        Ok(usage)
+    }
+
     // Cluster Config CRUD operations
 
     pub fn save_cluster_config(&self, config: &crate::cluster::ClusterCapabilities) -> Result<()> {
+        self.save_cluster_config_row("default", config)
+    }
+
+    pub fn load_cluster_config(&self) -> Result<Option<crate::cluster::ClusterCapabilities>> {
+        self.load_cluster_config_row("default")
+    }
+
+    /// A profile's edited capabilities live in cluster_config beside the shared defaults
+    pub fn save_profile_capabilities(&self, profile_name: &str, config: &crate::cluster::ClusterCapabilities) -> Result<()> {
+        self.save_cluster_config_row(&profile_config_id(profile_name), config)
+    }
+
+    /// None until the profile's capabilities are first edited
+    pub fn load_profile_capabilities(&self, profile_name: &str) -> Result<Option<crate::cluster::ClusterCapabilities>> {
+        self.load_cluster_config_row(&profile_config_id(profile_name))
+    }
+
+    pub fn delete_profile_capabilities(&self, profile_name: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows_affected = conn.execute(
+            "DELETE FROM cluster_config WHERE id = ?1",
+            [profile_config_id(profile_name)],
+        )?;
+
+        Ok(rows_affected > 0)
+    }
+
+    fn save_cluster_config_row(&self, id: &str, config: &crate::cluster::ClusterCapabilities) -> Result<()> {
         let conn = self.conn.lock().unwrap();
 
         // Serialize entire ClusterCapabilities to JSON
@@ -237,18 +507,18 @@ impl JobDatabase {
 
         conn.execute(
             "INSERT OR REPLACE INTO cluster_config (id, data) VALUES (?1, ?2)",
-            rusqlite::params!["default", &json_data],
+            rusqlite::params![id, &json_data],
         )?;
 
         Ok(())
     }
 
-    pub fn load_cluster_config(&self) -> Result<Option<crate::cluster::ClusterCapabilities>> {
+    fn load_cluster_config_row(&self, id: &str) -> Result<Option<crate::cluster::ClusterCapabilities>> {
         let conn = self.conn.lock().unwrap();
 
         let mut stmt = conn.prepare("SELECT data FROM cluster_config WHERE id = ?1")?;
 
-        let mut rows = stmt.query(["default"])?;
+        let mut rows = stmt.query([id])?;
 
         if let Some(row) = rows.next()? {
             let json_data: String = row.get(0)?;
@@ -269,6 +539,167 @@ impl JobDatabase {
 
         Ok(rows_affected > 0)
     }
+
+    // Cluster profile CRUD operations
+
+    pub fn save_cluster_profile(&self, profile: &crate::cluster::ClusterProfile) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let json_data = serde_json::to_string(profile)?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO cluster_profiles (name, data) VALUES (?1, ?2)",
+            rusqlite::params![&profile.name, &json_data],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn load_cluster_profile(&self, name: &str) -> Result<Option<crate::cluster::ClusterProfile>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare("SELECT data FROM cluster_profiles WHERE name = ?1")?;
+
+        let mut rows = stmt.query([name])?;
+
+        if let Some(row) = rows.next()? {
+            let json_data: String = row.get(0)?;
+            Ok(Some(serde_json::from_str(&json_data)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn list_cluster_profiles(&self) -> Result<Vec<crate::cluster::ClusterProfile>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare("SELECT data FROM cluster_profiles ORDER BY name")?;
+
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut profiles = Vec::new();
+        for row in rows {
+            profiles.push(serde_json::from_str(&row?)?);
+        }
+        Ok(profiles)
+    }
+
+    pub fn delete_cluster_profile(&self, name: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows_affected = conn.execute(
+            "DELETE FROM cluster_profiles WHERE name = ?1",
+            [name],
+        )?;
+
+        Ok(rows_affected > 0)
+    }
+
+    // Recent connection operations
+
+    /// Record a successful connection, keeping one row per host+username and at most `max_entries` rows
+    /// Hosts are stored lowercased so differently cased entries collapse into one
+    pub fn record_recent_connection(&self, connection: &crate::types::RecentConnection, max_entries: usize) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO recent_connections (host, username, port, last_connected_at) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![connection.host.to_lowercase(), &connection.username, connection.port, &connection.last_connected_at],
+        )?;
+
+        conn.execute(
+            "DELETE FROM recent_connections WHERE rowid NOT IN (
+                SELECT rowid FROM recent_connections ORDER BY last_connected_at DESC LIMIT ?1
+            )",
+            [max_entries as i64],
+        )?;
+
+        Ok(())
+    }
+
+    /// Recent connections, most recently used first
+    pub fn list_recent_connections(&self) -> Result<Vec<crate::types::RecentConnection>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT host, port, username, last_connected_at FROM recent_connections ORDER BY last_connected_at DESC"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(crate::types::RecentConnection {
+                host: row.get(0)?,
+                port: row.get(1)?,
+                username: row.get(2)?,
+                last_connected_at: row.get(3)?,
+            })
+        })?;
+
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Forget all recent connections; returns how many were removed
+    pub fn clear_recent_connections(&self) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute("DELETE FROM recent_connections", [])?)
+    }
+
+    // Settings operations
+
+    pub fn save_setting<T: serde::Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let json_value = serde_json::to_string(value)?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            rusqlite::params![key, &json_value],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn load_setting<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare("SELECT value FROM settings WHERE key = ?1")?;
+
+        let mut rows = stmt.query([key])?;
+
+        if let Some(row) = rows.next()? {
+            let json_value: String = row.get(0)?;
+            Ok(Some(serde_json::from_str(&json_value)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// cluster_config row id for a profile's capabilities; prefixed so no profile name can
+/// collide with the shared "default" row
+fn profile_config_id(profile_name: &str) -> String {
+    format!("profile:{}", profile_name)
+}
+
+/// Escape LIKE wildcards so the query matches literally (paired with ESCAPE '\')
+fn escape_like(query: &str) -> String {
+    query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Deserialize (job_id, data) rows, skipping (and logging) ones whose JSON doesn't parse
+fn parse_job_rows(rows: impl Iterator<Item = rusqlite::Result<(String, String)>>) -> Result<JobLoadResult> {
+    let mut result = JobLoadResult::default();
+    for row_result in rows {
+        let (job_id, json_data) = row_result?;
+        match serde_json::from_str::<JobInfo>(&json_data) {
+            Ok(job_info) => result.jobs.push(job_info),
+            Err(e) => {
+                log_warn!(category: "Database", message: "Skipping unreadable job record", details: "{}: {}", job_id, e);
+                result.skipped_rows += 1;
+            }
+        }
+    }
+
+    Ok(result)
 }
 
 // Thread-safe global database instance
@@ -506,3 +937,387 @@ where
         .ok_or_else(|| anyhow!("Database not initialized"))?;
     f(db)
 }
+
+/// Tests that go through the global database take turns, each on a fresh one
+#[cfg(test)]
+static GLOBAL_DATABASE_TEST: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Initialize the global database in a new temp directory for the calling test
+/// Hold both returned values for the whole test: the guard keeps other such tests out
+#[cfg(test)]
+pub async fn fresh_global_database() -> (tokio::sync::MutexGuard<'static, ()>, tempfile::TempDir) {
+    let guard = GLOBAL_DATABASE_TEST.lock().await;
+    let dir = tempfile::tempdir().unwrap();
+    initialize_database(&dir.path().join("test.db").to_string_lossy()).unwrap();
+    (guard, dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn test_db() -> (tempfile::TempDir, JobDatabase) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = JobDatabase::new(&dir.path().join("test.db").to_string_lossy()).unwrap();
+        (dir, db)
+    }
+
+    fn job(job_id: &str, created_at: &str) -> JobInfo {
+        let mut job = crate::automations::job_creation::create_job_info(
+            job_id.to_string(),
+            job_id.to_string(),
+            "template".to_string(),
+            HashMap::new(),
+            crate::types::SlurmConfig::default(),
+            vec![],
+        );
+        job.created_at = created_at.to_string();
+        job
+    }
+
+    fn insert_raw(db: &JobDatabase, job_id: &str, data: &str) {
+        db.conn.lock().unwrap()
+            .execute("INSERT INTO jobs (job_id, data) VALUES (?1, ?2)", rusqlite::params![job_id, data])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_load_all_jobs_skips_unparseable_rows() {
+        let (_dir, db) = test_db();
+        db.save_job(&job("job_old", "2025-01-01T00:00:00Z")).unwrap();
+        db.save_job(&job("job_new", "2025-02-01T00:00:00Z")).unwrap();
+
+        // Valid JSON from an incompatible schema, and a record of the wrong type entirely
+        insert_raw(&db, "job_schema", r#"{"job_id": "job_schema", "status": "RUNNING", "created_at": "2025-03-01T00:00:00Z"}"#);
+        insert_raw(&db, "job_array", "[1, 2, 3]");
+
+        let result = db.load_all_jobs().unwrap();
+
+        let ids: Vec<&str> = result.jobs.iter().map(|j| j.job_id.as_str()).collect();
+        assert_eq!(ids, vec!["job_new", "job_old"]);
+        assert_eq!(result.skipped_rows, 2);
+        assert_eq!(db.load_unarchived_jobs().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_load_jobs_by_tag() {
+        let (_dir, db) = test_db();
+        let mut equil = job("job_equil", "2025-01-01T00:00:00Z");
+        equil.tags = vec!["membrane".to_string(), "equilibration".to_string()];
+        db.save_job(&equil).unwrap();
+        let mut prod = job("job_prod", "2025-02-01T00:00:00Z");
+        prod.tags = vec!["membrane".to_string()];
+        db.save_job(&prod).unwrap();
+        db.save_job(&job("job_untagged", "2025-03-01T00:00:00Z")).unwrap();
+        // Saved before tags existed
+        insert_raw(&db, "job_legacy", r#"{"job_id": "job_legacy", "created_at": "2025-04-01T00:00:00Z"}"#);
+
+        let ids = |tag: &str| -> Vec<String> {
+            db.load_jobs_by_tag(tag).unwrap().jobs.into_iter().map(|j| j.job_id).collect()
+        };
+        assert_eq!(ids("membrane"), vec!["job_prod", "job_equil"]);
+        assert_eq!(ids("equilibration"), vec!["job_equil"]);
+        assert!(ids("membran").is_empty());
+    }
+
+    #[test]
+    fn test_load_jobs_page() {
+        let (_dir, db) = test_db();
+        for (id, name, created_at) in [
+            ("job_a", "equilibration", "2025-01-01T00:00:00Z"),
+            ("job_b", "production", "2025-02-01T00:00:00Z"),
+            ("job_c", "minimization", "2025-03-01T00:00:00Z"),
+        ] {
+            let mut job = job(id, created_at);
+            job.job_name = name.to_string();
+            db.save_job(&job).unwrap();
+        }
+        let mut archived = job("job_archived", "2025-04-01T00:00:00Z");
+        archived.archived = true;
+        db.save_job(&archived).unwrap();
+
+        let ids = |offset: u32, limit: Option<u32>, sort_by: JobSortField, order: SortOrder| -> Vec<String> {
+            db.load_jobs_page(offset, limit, sort_by, order, false).unwrap().jobs.into_iter().map(|j| j.job_id).collect()
+        };
+        assert_eq!(db.count_jobs(false).unwrap(), 3);
+        assert_eq!(ids(0, Some(2), JobSortField::CreatedAt, SortOrder::Desc), vec!["job_c", "job_b"]);
+        assert_eq!(ids(2, Some(2), JobSortField::CreatedAt, SortOrder::Desc), vec!["job_a"]);
+        assert_eq!(ids(0, None, JobSortField::JobName, SortOrder::Asc), vec!["job_a", "job_c", "job_b"]);
+        assert_eq!(ids(1, None, JobSortField::JobName, SortOrder::Desc), vec!["job_c", "job_a"]);
+        assert!(ids(5, Some(2), JobSortField::CreatedAt, SortOrder::Desc).is_empty());
+
+        // Archived jobs only when asked for
+        assert_eq!(db.count_jobs(true).unwrap(), 4);
+        let with_archived = db.load_jobs_page(0, Some(1), JobSortField::CreatedAt, SortOrder::Desc, true).unwrap();
+        assert_eq!(with_archived.jobs[0].job_id, "job_archived");
+    }
+
+    #[test]
+    fn test_search_job_summaries() {
+        let (_dir, db) = test_db();
+        let mut failed = job("job_membrane_prod", "2025-01-01T00:00:00Z");
+        failed.status = JobStatus::Failed;
+        failed.slurm_stderr = Some("FATAL ERROR: Atoms moving too fast".to_string());
+        db.save_job(&failed).unwrap();
+        let mut running = job("job_membrane_equil", "2025-02-01T00:00:00Z");
+        running.status = JobStatus::Running;
+        running.template_id = "vacuum_optimization".to_string();
+        db.save_job(&running).unwrap();
+        db.save_job(&job("job_100_percent", "2025-03-01T00:00:00Z")).unwrap();
+
+        let ids = |query: &str, include_logs: bool| -> Vec<String> {
+            db.search_job_summaries(query, include_logs).unwrap().into_iter().map(|s| s.job_id).collect()
+        };
+        assert_eq!(ids("MEMBRANE", false), vec!["job_membrane_equil", "job_membrane_prod"]);
+        assert_eq!(ids("vacuum", false), vec!["job_membrane_equil"]);
+        assert_eq!(ids("failed", false), vec!["job_membrane_prod"]);
+
+        // Log content only when asked for
+        assert!(ids("moving too fast", false).is_empty());
+        assert_eq!(ids("moving too fast", true), vec!["job_membrane_prod"]);
+
+        // Wildcards match literally
+        assert_eq!(ids("100_p", false), vec!["job_100_percent"]);
+        assert!(ids("%", false).is_empty());
+    }
+
+    #[test]
+    fn test_job_summaries_exclude_logs() {
+        let (_dir, db) = test_db();
+        let mut finished = job("job_done", "2025-01-01T00:00:00Z");
+        finished.status = JobStatus::Completed;
+        finished.slurm_job_id = Some("12345".to_string());
+        finished.slurm_stdout = Some("x".repeat(100_000));
+        finished.slurm_stderr = Some("warning: something".to_string());
+        finished.tags = vec!["membrane".to_string()];
+        db.save_job(&finished).unwrap();
+
+        let summaries = db.load_job_summaries().unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].status, JobStatus::Completed);
+        assert_eq!(summaries[0].slurm_job_id.as_deref(), Some("12345"));
+        assert_eq!(summaries[0].tags, vec!["membrane".to_string()]);
+
+        let json = serde_json::to_value(&summaries[0]).unwrap();
+        let object = json.as_object().unwrap();
+        assert!(!object.contains_key("slurm_stdout"));
+        assert!(!object.contains_key("slurm_stderr"));
+        assert!(!object.contains_key("template_values"));
+    }
+
+    #[test]
+    fn test_job_summaries_order_and_filtering() {
+        let (_dir, db) = test_db();
+        db.save_job(&job("job_old", "2025-01-01T00:00:00Z")).unwrap();
+        db.save_job(&job("job_new", "2025-03-01T00:00:00Z")).unwrap();
+        db.save_job(&job("job_mid", "2025-02-01T00:00:00Z")).unwrap();
+        let mut archived = job("job_archived", "2025-04-01T00:00:00Z");
+        archived.archived = true;
+        db.save_job(&archived).unwrap();
+        insert_raw(&db, "job_bad", "[1, 2, 3]");
+        insert_raw(&db, "job_partial", r#"{"job_id": "job_partial", "created_at": "2025-05-01T00:00:00Z"}"#);
+
+        let ids: Vec<String> = db.load_job_summaries().unwrap().into_iter().map(|s| s.job_id).collect();
+        assert_eq!(ids, vec!["job_new", "job_mid", "job_old"]);
+
+        // Same order as the full load
+        let full: Vec<String> = db.load_unarchived_jobs().unwrap().into_iter().map(|j| j.job_id).collect();
+        assert_eq!(ids, full);
+    }
+
+    fn template(id: &str, name: &str) -> Template {
+        Template {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            namd_config_template: String::new(),
+            variables: HashMap::new(),
+            extends: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_template_extends_round_trip_and_children() {
+        let (_dir, db) = test_db();
+        let mut solvent = template("explicit_solvent", "Explicit Solvent");
+        solvent.extends = Some("base".to_string());
+        let mut vacuum = template("vacuum", "Vacuum");
+        vacuum.extends = Some("base".to_string());
+        db.save_template(&template("base", "Base")).unwrap();
+        db.save_template(&vacuum).unwrap();
+        db.save_template(&solvent).unwrap();
+
+        assert_eq!(db.load_template("vacuum").unwrap().unwrap().extends.as_deref(), Some("base"));
+        assert_eq!(db.load_template("base").unwrap().unwrap().extends, None);
+        assert_eq!(db.list_child_templates("base").unwrap(), vec!["explicit_solvent", "vacuum"]);
+        assert!(db.list_child_templates("vacuum").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_job_template_snapshot_round_trip() {
+        let (_dir, db) = test_db();
+        let mut snapshot = template("template", "Production");
+        snapshot.namd_config_template = "run {{steps}}".to_string();
+
+        let mut snapshotted = job("job_new", "2025-02-01T00:00:00Z");
+        snapshotted.template_snapshot = Some(snapshot);
+        snapshotted.rendered_config = Some("run 5000".to_string());
+        db.save_job(&snapshotted).unwrap();
+        db.save_job(&job("job_old", "2025-01-01T00:00:00Z")).unwrap();
+
+        let loaded = db.load_job("job_new").unwrap().unwrap();
+        let loaded_snapshot = loaded.template_snapshot.unwrap();
+        assert_eq!(loaded_snapshot.id, "template");
+        assert_eq!(loaded_snapshot.name, "Production");
+        assert_eq!(loaded_snapshot.namd_config_template, "run {{steps}}");
+        assert_eq!(loaded.rendered_config.as_deref(), Some("run 5000"));
+
+        // Jobs saved without a snapshot still load
+        let older = db.load_job("job_old").unwrap().unwrap();
+        assert!(older.template_snapshot.is_none() && older.rendered_config.is_none());
+    }
+
+    #[test]
+    fn test_template_usage_stats() {
+        use crate::types::JobStatus;
+
+        let (_dir, db) = test_db();
+        db.save_template(&template("equil", "Equilibration")).unwrap();
+        db.save_template(&template("prod", "Production")).unwrap();
+        db.save_template(&template("unused", "Unused")).unwrap();
+
+        let seeded = [
+            ("job_1", "equil", JobStatus::Completed, "2025-01-10T00:00:00Z"),
+            ("job_2", "equil", JobStatus::Completed, "2025-03-05T00:00:00Z"),
+            ("job_3", "equil", JobStatus::Failed, "2025-02-01T00:00:00Z"),
+            ("job_4", "prod", JobStatus::Running, "2025-02-20T00:00:00Z"),
+            ("job_5", "deleted_template", JobStatus::Created, "2025-04-01T00:00:00Z"),
+        ];
+        for (job_id, template_id, status, created_at) in seeded {
+            let mut job = job(job_id, created_at);
+            job.template_id = template_id.to_string();
+            job.status = status;
+            db.save_job(&job).unwrap();
+        }
+
+        let stats = db.template_usage_stats().unwrap();
+
+        let ids: Vec<&str> = stats.iter().map(|s| s.template_id.as_str()).collect();
+        assert_eq!(ids, vec!["equil", "prod", "unused"]);
+
+        assert_eq!(stats[0].template_name, "Equilibration");
+        assert_eq!(stats[0].total_jobs, 3);
+        assert_eq!(stats[0].jobs_by_status.get("COMPLETED"), Some(&2));
+        assert_eq!(stats[0].jobs_by_status.get("FAILED"), Some(&1));
+        assert_eq!(stats[0].jobs_by_status.len(), 2);
+        assert_eq!(stats[0].last_used.as_deref(), Some("2025-03-05T00:00:00Z"));
+
+        assert_eq!(stats[1].total_jobs, 1);
+        assert_eq!(stats[1].jobs_by_status.get("RUNNING"), Some(&1));
+
+        assert_eq!(stats[2].total_jobs, 0);
+        assert!(stats[2].jobs_by_status.is_empty());
+        assert_eq!(stats[2].last_used, None);
+        assert_eq!(db.count_jobs_using_template("equil").unwrap(), stats[0].total_jobs);
+    }
+
+    #[test]
+    fn test_cluster_profile_round_trip() {
+        let (_dir, db) = test_db();
+        let profile = |name: &str, login_server: &str| crate::cluster::ClusterProfile {
+            name: name.to_string(),
+            login_server: login_server.to_string(),
+            port: 22,
+            module_setup: None,
+        };
+
+        db.save_cluster_profile(&profile("summit", "login.summit.example.edu")).unwrap();
+        db.save_cluster_profile(&profile("alpine", "login.rc.colorado.edu")).unwrap();
+        db.save_cluster_profile(&profile("summit", "login2.summit.example.edu")).unwrap();
+
+        let names: Vec<String> = db.list_cluster_profiles().unwrap().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["alpine", "summit"]);
+        assert_eq!(db.load_cluster_profile("summit").unwrap().unwrap().login_server, "login2.summit.example.edu");
+
+        assert!(db.delete_cluster_profile("summit").unwrap());
+        assert!(!db.delete_cluster_profile("summit").unwrap());
+        assert!(db.load_cluster_profile("summit").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_recent_connections_dedupe_and_order() {
+        let (_dir, db) = test_db();
+        let recent = |host: &str, username: &str, at: &str| crate::types::RecentConnection {
+            host: host.to_string(),
+            port: 22,
+            username: username.to_string(),
+            last_connected_at: at.to_string(),
+        };
+
+        db.record_recent_connection(&recent("login.rc.colorado.edu", "alice", "2026-01-01T00:00:00Z"), 10).unwrap();
+        db.record_recent_connection(&recent("login.summit.example.edu", "alice", "2026-01-02T00:00:00Z"), 10).unwrap();
+        // Same host+username again (host case differs) replaces the first entry and moves it to the top
+        db.record_recent_connection(&recent("LOGIN.rc.colorado.edu", "alice", "2026-01-03T00:00:00Z"), 10).unwrap();
+
+        let list = db.list_recent_connections().unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0], recent("login.rc.colorado.edu", "alice", "2026-01-03T00:00:00Z"));
+        assert_eq!(list[1].host, "login.summit.example.edu");
+
+        assert_eq!(db.clear_recent_connections().unwrap(), 2);
+        assert!(db.list_recent_connections().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_recent_connections_capped() {
+        let (_dir, db) = test_db();
+        for i in 0..5 {
+            db.record_recent_connection(&crate::types::RecentConnection {
+                host: format!("login{}.example.edu", i),
+                port: 22,
+                username: "alice".to_string(),
+                last_connected_at: format!("2026-01-0{}T00:00:00Z", i + 1),
+            }, 3).unwrap();
+        }
+
+        let hosts: Vec<String> = db.list_recent_connections().unwrap().into_iter().map(|c| c.host).collect();
+        assert_eq!(hosts, vec!["login4.example.edu", "login3.example.edu", "login2.example.edu"]);
+    }
+
+    #[test]
+    fn test_profile_capabilities_kept_apart_from_defaults() {
+        let (_dir, db) = test_db();
+        let defaults = crate::cluster::load_default_config_for_tests();
+        let mut edited = defaults.clone();
+        edited.billing_rates.cpu_cost_per_core_hour = 2.5;
+
+        db.save_cluster_config(&defaults).unwrap();
+        db.save_profile_capabilities("default", &edited).unwrap();
+
+        assert_eq!(db.load_cluster_config().unwrap().unwrap().billing_rates.cpu_cost_per_core_hour, defaults.billing_rates.cpu_cost_per_core_hour);
+        assert_eq!(db.load_profile_capabilities("default").unwrap().unwrap().billing_rates.cpu_cost_per_core_hour, 2.5);
+        assert!(db.load_profile_capabilities("summit").unwrap().is_none());
+
+        assert!(db.delete_profile_capabilities("default").unwrap());
+        assert!(db.load_cluster_config().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_load_all_jobs_survives_truncated_json() {
+        let (_dir, db) = test_db();
+        db.save_job(&job("job_ok", "2025-01-01T00:00:00Z")).unwrap();
+
+        // The status index can't evaluate malformed JSON, so drop it to simulate a partially-written row
+        db.conn.lock().unwrap().execute_batch("DROP INDEX idx_jobs_status").unwrap();
+        insert_raw(&db, "job_partial", r#"{"job_id": "job_partial", "job_name": "#);
+
+        let result = db.load_all_jobs().unwrap();
+
+        assert_eq!(result.jobs.len(), 1);
+        assert_eq!(result.jobs[0].job_id, "job_ok");
+        assert_eq!(result.skipped_rows, 1);
+    }
+}
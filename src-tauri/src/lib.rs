@@ -42,6 +42,18 @@ pub fn run() {
                 message: "Database initialized"
             );
 
+            // NAMDRUNNER_DEMO=1 runs file operations against an in-memory filesystem
+            ssh::demo::enable_from_env();
+
+            // Restore the saved upload rate cap
+            ssh::get_connection_manager().set_upload_rate_limit(automations::file_operations::load_transfer_rate_limit());
+
+            // Watch for connections that silently died while the app sat idle
+            ssh::monitor::start_connection_monitor(app.handle().clone());
+
+            // Archive old finished jobs according to the user's retention policy
+            automations::job_retention::start_retention_task();
+
             // Default templates are loaded on-demand when list_templates is first called
             // This ensures logs appear in frontend (setup hook runs before frontend connects)
 
@@ -51,10 +63,28 @@ pub fn run() {
             // App initialization
             commands::app::initialize_app,
             commands::app::get_recent_logs,
+            commands::app::get_demo_mode,
+            commands::app::set_demo_mode,
+            commands::logging::export_support_bundle,
+            commands::logging::export_logs,
+            commands::logging::set_log_level,
+            commands::logging::set_log_filter,
             // Connection lifecycle
             commands::connection::connect_to_cluster,
             commands::connection::disconnect,
             commands::connection::get_connection_status,
+            commands::connection::ping_connection,
+            commands::connection::get_network_preset,
+            commands::connection::set_connection_keepalive,
+            commands::connection::set_connection_timeouts,
+            commands::connection::get_connection_timeouts,
+            commands::connection::list_recent_connections,
+            commands::connection::clear_recent_connections,
+            commands::connection::store_credential,
+            commands::connection::delete_credential,
+            commands::connection::diagnose_connection,
+            commands::connection::respond_to_auth_challenge,
+            commands::connection::trust_host_key,
             // Cluster configuration
             commands::cluster::get_cluster_capabilities,
             commands::cluster::save_cluster_config,
@@ -62,32 +92,87 @@ pub fn run() {
             commands::cluster::calculate_job_cost,
             commands::cluster::estimate_queue_time,
             commands::cluster::suggest_qos,
+            commands::cluster::list_cluster_profiles,
+            commands::cluster::save_cluster_profile,
+            commands::cluster::delete_cluster_profile,
+            commands::cluster::activate_cluster_profile,
+            commands::cluster::save_partition,
+            commands::cluster::remove_partition,
+            commands::cluster::save_qos,
+            commands::cluster::remove_qos,
+            commands::cluster::set_billing_rates,
+            commands::cluster::discover_cluster_capabilities,
             // Validation
             commands::validation::validate_resource_allocation_command,
             // Job management
             commands::jobs::create_job,
+            commands::jobs::rerun_job,
             commands::jobs::submit_job,
+            commands::jobs::submit_jobs,
+            commands::jobs::dry_run_submit,
+            commands::jobs::adopt_remote_job,
             commands::jobs::get_job_status,
             commands::jobs::get_all_jobs,
+            commands::jobs::get_all_jobs_summary,
+            commands::jobs::search_jobs,
             commands::jobs::sync_jobs,
+            commands::jobs::sync_single_job,
+            commands::jobs::preview_job_deletion,
             commands::jobs::delete_job,
+            commands::jobs::cancel_job,
+            commands::jobs::hold_job,
+            commands::jobs::release_job,
             commands::jobs::refetch_slurm_logs,
+            commands::jobs::stream_job_log,
+            commands::jobs::get_job_accounting,
+            commands::jobs::get_discovery_batch_size,
+            commands::jobs::set_discovery_batch_size,
+            commands::jobs::get_log_fetch_policy,
+            commands::jobs::set_log_fetch_policy,
+            commands::jobs::set_job_pinned,
+            commands::jobs::archive_job,
+            commands::jobs::restore_job,
+            commands::jobs::export_jobs,
+            commands::jobs::update_job_notes,
+            commands::jobs::add_job_tag,
+            commands::jobs::remove_job_tag,
+            commands::jobs::get_job_retention_days,
+            commands::jobs::set_job_retention_days,
             // File management
             commands::files::select_input_file,
             commands::files::download_file,
             commands::files::download_all_files,
+            commands::files::download_outputs_matching,
+            commands::files::read_remote_text,
+            commands::files::rename_remote_file,
+            commands::files::remote_dir_size,
+            commands::files::get_job_output_size,
+            commands::files::get_archive_compression_level,
+            commands::files::set_archive_compression_level,
+            commands::files::set_transfer_rate_limit,
+            commands::files::check_remote_inodes,
+            commands::files::check_disk_space,
+            commands::files::cancel_transfer,
             // Template management
             commands::templates::list_templates,
             commands::templates::get_template,
+            commands::templates::get_resolved_template,
             commands::templates::create_template,
             commands::templates::update_template,
             commands::templates::delete_template,
             commands::templates::export_template,
             commands::templates::import_template,
+            commands::templates::duplicate_template,
             commands::templates::validate_template_values,
+            commands::templates::check_jobs_compatible,
             commands::templates::preview_namd_config,
             commands::templates::preview_template_with_defaults,
+            commands::templates::render_with_files,
+            commands::templates::template_usage_stats,
             commands::jobs::preview_slurm_script,
+            commands::jobs::preview_submission_artifacts,
+            commands::jobs::scratch_usage,
+            commands::jobs::submission_readiness,
             commands::jobs::validate_job_config,
             // Database management
             commands::database::get_database_info,
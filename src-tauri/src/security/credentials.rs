@@ -2,6 +2,12 @@ use secstr::SecStr;
 use serde::{Deserialize, Deserializer};
 
 /// Secure password handling with automatic memory clearing
+///
+/// The bytes live in a SecStr buffer, which is mlocked and zeroed when dropped.
+/// Lifetime: a password is deserialized from the connect request (or loaded from
+/// the OS keychain, see `security::keychain`), borrowed by `ConnectionManager::connect`
+/// down to `userauth_password`, and dropped when the command returns. Only opt-in
+/// auto-reconnect keeps an in-memory copy while connected; nothing is written to disk.
 #[derive(Clone)]
 pub struct SecurePassword(SecStr);
 
@@ -13,11 +19,12 @@ impl SecurePassword {
 
     /// Create from a string reference
     pub fn from_str(password: &str) -> Self {
-        Self(SecStr::from(password.to_string()))
+        Self(SecStr::from(password.as_bytes()))
     }
 
     /// Access the password temporarily for authentication
-    /// The closure ensures the password is only accessible for a short time
+    /// The closure ensures the password is only accessible for a short time.
+    /// The `&str` borrows the secure buffer directly - don't copy it out.
     pub fn with_password<F, R>(&self, f: F) -> R
     where
         F: FnOnce(&str) -> R,
@@ -28,15 +35,6 @@ impl SecurePassword {
         f(password_str)
     }
 
-    /// Convert to a standard string (use sparingly and clear immediately after use)
-    /// This is only for compatibility with libraries that require &str
-    pub fn expose(&self) -> String {
-        let bytes = self.0.unsecure();
-        std::str::from_utf8(bytes)
-            .expect("Password must be valid UTF-8")
-            .to_string()
-    }
-
     /// Check if the password is empty
     pub fn is_empty(&self) -> bool {
         self.0.unsecure().is_empty()
@@ -48,15 +46,16 @@ impl SecurePassword {
     }
 }
 
-impl std::fmt::Debug for SecurePassword {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("SecurePassword([REDACTED])")
+/// Empty - for requests that don't carry a password (e.g. key authentication)
+impl Default for SecurePassword {
+    fn default() -> Self {
+        Self::from_str("")
     }
 }
 
-impl Drop for SecurePassword {
-    fn drop(&mut self) {
-        // SecStr handles secure memory clearing automatically
+impl std::fmt::Debug for SecurePassword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecurePassword([REDACTED])")
     }
 }
 
@@ -66,6 +65,7 @@ impl<'de> Deserialize<'de> for SecurePassword {
     where
         D: Deserializer<'de>,
     {
+        // The String's buffer is moved into SecStr, not copied
         let password = String::deserialize(deserializer)?;
         Ok(SecurePassword::new(password))
     }
@@ -126,8 +126,16 @@ mod tests {
 
         let params = ConnectParams {
             host: "test.example.com".to_string(),
+            port: None,
             username: "testuser".to_string(),
             password: SecurePassword::new("secret123".to_string()),
+            auth_method: Default::default(),
+            network_preset: None,
+            jump_host: None,
+            auto_reconnect: false,
+            connect_timeout: None,
+            file_transfer_timeout: None,
+            remember_password: false,
         };
 
         // Test that Debug output of connection params doesn't expose password
@@ -139,6 +147,41 @@ mod tests {
         assert!(!debug_output.contains("secret"));
     }
 
+    #[test]
+    fn test_key_auth_params_keep_passphrase_secure() {
+        use crate::types::commands::{AuthMethod, ConnectParams};
+        use crate::ssh::Credentials;
+
+        let params: ConnectParams = serde_json::from_str(r#"{
+            "host": "login.example.edu",
+            "username": "testuser",
+            "auth_method": {"type": "key", "key_path": "/home/testuser/.ssh/id_ed25519", "passphrase": "key-secret"}
+        }"#).unwrap();
+
+        assert!(params.password.is_empty());
+        assert!(!format!("{:?}", params).contains("key-secret"));
+        match params.credentials().unwrap() {
+            Credentials::KeyFile { private_key, passphrase } => {
+                assert_eq!(private_key, std::path::Path::new("/home/testuser/.ssh/id_ed25519"));
+                passphrase.unwrap().with_password(|p| assert_eq!(p, "key-secret"));
+            }
+            other => panic!("expected key file credentials, got {:?}", other),
+        }
+
+        // No key path falls back to the SSH agent; no auth_method keeps password auth
+        let agent: ConnectParams = serde_json::from_str(r#"{"host": "h", "username": "u", "auth_method": {"type": "key"}}"#).unwrap();
+        assert!(matches!(agent.auth_method, AuthMethod::Key { key_path: None, passphrase: None }));
+        assert!(matches!(agent.credentials(), Some(Credentials::Agent)));
+
+        let password: ConnectParams = serde_json::from_str(r#"{"host": "h", "username": "u", "password": "pw"}"#).unwrap();
+        assert!(matches!(password.credentials(), Some(Credentials::Password(_))));
+
+        // Keyboard-interactive answers come from the user at login time, not the request
+        let mfa: ConnectParams = serde_json::from_str(r#"{"host": "h", "username": "u", "auth_method": {"type": "keyboard_interactive"}}"#).unwrap();
+        assert!(matches!(mfa.auth_method, AuthMethod::KeyboardInteractive));
+        assert!(mfa.credentials().is_none());
+    }
+
     #[test]
     fn test_secure_password_clone_safety() {
         let password1 = SecurePassword::new("original_password".to_string());
@@ -153,6 +196,17 @@ mod tests {
         assert!(format!("{:?}", password2).contains("REDACTED"));
     }
 
+    #[test]
+    fn test_with_password_borrows_secure_buffer() {
+        let password = SecurePassword::from_str("borrowed");
+
+        // Each access hands out the same buffer rather than a fresh copy
+        let first = password.with_password(|p| p.as_ptr());
+        let second = password.with_password(|p| p.as_ptr());
+        assert_eq!(first, second);
+        assert_eq!(first, password.0.unsecure().as_ptr());
+    }
+
     #[test]
     fn test_secure_password_length_safe() {
         let password = SecurePassword::new("test123".to_string());
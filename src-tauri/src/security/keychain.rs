@@ -0,0 +1,71 @@
+use anyhow::{anyhow, Result};
+use super::SecurePassword;
+
+/// Service name every NAMDRunner keychain entry is filed under
+const KEYCHAIN_SERVICE: &str = "namdrunner";
+
+/// Account name for a login target: one entry per host+username
+/// Hosts are lowercased so differently cased spellings share an entry
+pub fn credential_account(host: &str, username: &str) -> Result<String> {
+    let host = host.trim().to_lowercase();
+    let username = username.trim();
+    if host.is_empty() || username.is_empty() {
+        return Err(anyhow!("Host and username are required for a saved credential"));
+    }
+    Ok(format!("{}@{}", username, host))
+}
+
+fn entry(host: &str, username: &str) -> Result<keyring::Entry> {
+    let account = credential_account(host, username)?;
+    keyring::Entry::new(KEYCHAIN_SERVICE, &account)
+        .map_err(|e| anyhow!("Could not open the OS keychain: {}", e))
+}
+
+/// Save a password in the OS keychain (Keychain, Credential Manager or Secret Service)
+/// Replaces any password already saved for the same host+username
+pub fn store_password(host: &str, username: &str, password: &SecurePassword) -> Result<()> {
+    if password.is_empty() {
+        return Err(anyhow!("Cannot save an empty password"));
+    }
+    let entry = entry(host, username)?;
+    password.with_password(|secret| entry.set_password(secret))
+        .map_err(|e| anyhow!("Could not save the password to the OS keychain: {}", e))
+}
+
+/// Load a saved password; None when nothing is saved for this host+username
+/// The keychain's String is moved straight into a SecurePassword, which zeroes it on drop
+pub fn load_password(host: &str, username: &str) -> Result<Option<SecurePassword>> {
+    match entry(host, username)?.get_password() {
+        Ok(secret) => Ok(Some(SecurePassword::new(secret))),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(anyhow!("Could not read the OS keychain: {}", e)),
+    }
+}
+
+/// Remove a saved password; returns false when there was none
+pub fn delete_password(host: &str, username: &str) -> Result<bool> {
+    match entry(host, username)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(anyhow!("Could not remove the password from the OS keychain: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credential_account_keys_by_host_and_username() {
+        assert_eq!(credential_account("Login.RC.Colorado.edu", "alice").unwrap(), "alice@login.rc.colorado.edu");
+        assert_eq!(credential_account(" login.rc.colorado.edu ", " alice ").unwrap(), "alice@login.rc.colorado.edu");
+        assert!(credential_account("", "alice").is_err());
+        assert!(credential_account("login.rc.colorado.edu", "  ").is_err());
+    }
+
+    #[test]
+    fn test_store_password_rejects_empty() {
+        let error = store_password("login.rc.colorado.edu", "alice", &SecurePassword::default()).unwrap_err();
+        assert!(error.to_string().contains("empty"));
+    }
+}
@@ -1,5 +1,6 @@
 pub mod credentials;
 pub mod input;
+pub mod keychain;
 pub mod shell;
 
 pub use credentials::SecurePassword;
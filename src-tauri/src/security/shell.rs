@@ -17,6 +17,43 @@ pub fn safe_cd_and_run(directory: &str, command: &str) -> String {
     format!("cd {} && {}", escape_parameter(directory), command)
 }
 
+/// Longest command line we send in one exec
+/// Well under Linux's ARG_MAX (2 MiB) and the smaller limits of some sshd/shell setups,
+/// leaving room for the environment
+pub const MAX_COMMAND_LENGTH: usize = 100_000;
+
+/// Whether a generated command line is too long to send as one exec
+pub fn exceeds_command_limit(command: &str, max_len: usize) -> bool {
+    command.len() > max_len
+}
+
+/// Split arguments into consecutive batches whose joined length fits the budget
+///
+/// `fixed_len` is the length of the command with no arguments and `separator_len` the
+/// length of whatever joins two arguments. An argument too long to share a batch still
+/// gets one of its own - callers should check the built command with `exceeds_command_limit`.
+pub fn batch_arguments(args: &[String], fixed_len: usize, separator_len: usize, max_len: usize) -> Vec<&[String]> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut len = fixed_len;
+
+    for (i, arg) in args.iter().enumerate() {
+        let added = if i == start { arg.len() } else { separator_len + arg.len() };
+        if i > start && len + added > max_len {
+            batches.push(&args[start..i]);
+            start = i;
+            len = fixed_len + arg.len();
+        } else {
+            len += added;
+        }
+    }
+
+    if start < args.len() {
+        batches.push(&args[start..]);
+    }
+    batches
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,4 +129,44 @@ mod tests {
                     "Injection attempt should be wrapped: {}", attempt);
         }
     }
+
+    fn args(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_exceeds_command_limit() {
+        assert!(!exceeds_command_limit("squeue -j 1", 11));
+        assert!(exceeds_command_limit("squeue -j 12", 11));
+        assert!(!exceeds_command_limit(&"x".repeat(MAX_COMMAND_LENGTH), MAX_COMMAND_LENGTH));
+        assert!(exceeds_command_limit(&"x".repeat(MAX_COMMAND_LENGTH + 1), MAX_COMMAND_LENGTH));
+    }
+
+    #[test]
+    fn test_batch_arguments_fits_budget() {
+        let ids = args(&["1001", "1002", "1003", "1004", "1005"]);
+
+        // Everything fits: one batch
+        assert_eq!(batch_arguments(&ids, 10, 1, 1000), vec![&ids[..]]);
+
+        // fixed 10 + "1001,1002" (9) = 19 fits in 20, a third id would not
+        let batches = batch_arguments(&ids, 10, 1, 20);
+        assert_eq!(batches, vec![&ids[0..2], &ids[2..4], &ids[4..5]]);
+        for batch in &batches {
+            assert!(10 + batch.iter().map(String::len).sum::<usize>() + batch.len() - 1 <= 20);
+        }
+
+        // Order and coverage preserved
+        let rejoined: Vec<&String> = batches.iter().flat_map(|b| b.iter()).collect();
+        assert_eq!(rejoined, ids.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_batch_arguments_edge_cases() {
+        assert!(batch_arguments(&[], 10, 1, 20).is_empty());
+
+        // An oversized argument gets its own batch rather than being dropped
+        let items = args(&["a", "this-argument-is-way-too-long", "b"]);
+        assert_eq!(batch_arguments(&items, 5, 1, 10), vec![&items[0..1], &items[1..2], &items[2..3]]);
+    }
 }
@@ -0,0 +1,208 @@
+use crate::types::response_data::JobAccounting;
+
+/// Seconds in a SLURM duration: `[D-]HH:MM:SS`, `MM:SS` or `MM:SS.mmm` (TotalCPU)
+pub fn parse_slurm_duration(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<f64>().ok()?, clock),
+        None => (0.0, value),
+    };
+
+    let mut seconds = 0.0;
+    for (part, scale) in clock.rsplit(':').zip([1.0, 60.0, 3600.0]) {
+        seconds += part.parse::<f64>().ok()? * scale;
+    }
+    if clock.split(':').count() > 3 {
+        return None;
+    }
+    Some(days * 86400.0 + seconds)
+}
+
+/// Bytes in a SLURM memory size such as "123456K" or "2.50G"
+/// A bare number is taken in `default_unit` bytes (1 for MaxRSS, 1 MiB for ReqMem)
+pub fn parse_slurm_memory(value: &str, default_unit: u64) -> Option<u64> {
+    let value = value.trim();
+    let (number, unit) = match value.char_indices().find(|(_, c)| c.is_ascii_alphabetic()) {
+        Some((index, unit)) => (&value[..index], Some(unit.to_ascii_uppercase())),
+        None => (value, None),
+    };
+    let scale = match unit {
+        None => default_unit,
+        Some('K') => 1 << 10,
+        Some('M') => 1 << 20,
+        Some('G') => 1 << 30,
+        Some('T') => 1 << 40,
+        Some(_) => return None,
+    };
+    Some((number.parse::<f64>().ok()? * scale as f64) as u64)
+}
+
+/// One sacct line: JobID|Elapsed|MaxRSS|MaxVMSize|CPUTime|TotalCPU|State|ExitCode|ReqMem|AllocCPUS
+struct AccountingLine<'a> {
+    job_id: &'a str,
+    elapsed: &'a str,
+    max_rss: &'a str,
+    max_vmsize: &'a str,
+    cpu_time: &'a str,
+    total_cpu: &'a str,
+    state: &'a str,
+    exit_code: &'a str,
+    req_mem: &'a str,
+    alloc_cpus: &'a str,
+}
+
+fn parse_line(line: &str) -> Option<AccountingLine<'_>> {
+    let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+    let [job_id, elapsed, max_rss, max_vmsize, cpu_time, total_cpu, state, exit_code, req_mem, alloc_cpus, ..] = fields[..] else {
+        return None;
+    };
+    Some(AccountingLine { job_id, elapsed, max_rss, max_vmsize, cpu_time, total_cpu, state, exit_code, req_mem, alloc_cpus })
+}
+
+/// Requested memory for the whole job; ReqMem ends in "c" for per-CPU and "n" for per-node requests
+fn requested_memory(req_mem: &str, alloc_cpus: &str) -> Option<u64> {
+    let (size, per_cpu) = match req_mem.strip_suffix('c') {
+        Some(size) => (size, true),
+        None => (req_mem.strip_suffix('n').unwrap_or(req_mem), false),
+    };
+    let bytes = parse_slurm_memory(size, 1 << 20)?;
+    if per_cpu {
+        return Some(bytes * alloc_cpus.parse::<u64>().ok()?);
+    }
+    Some(bytes)
+}
+
+/// Largest of several memory sizes, ignoring blanks and zeros
+fn peak_memory<'a>(values: impl Iterator<Item = &'a str>) -> Option<u64> {
+    values
+        .filter_map(|value| parse_slurm_memory(value, 1))
+        .filter(|bytes| *bytes > 0)
+        .max()
+}
+
+/// Fold `sacct_accounting_command` output into one record for `slurm_job_id`
+///
+/// State, exit code, elapsed and CPU time come from the job allocation's own line. Memory
+/// peaks are only recorded on steps (".batch", ".extern", ".0") on most clusters, so the
+/// largest step value is used. TotalCPU falls back to the sum of the steps when the
+/// allocation line doesn't carry it. None when sacct has no line for the job.
+pub fn parse_job_accounting(output: &str, slurm_job_id: &str) -> Option<JobAccounting> {
+    let lines: Vec<AccountingLine> = output.lines().filter_map(parse_line).collect();
+    let main = lines.iter().find(|line| line.job_id == slurm_job_id)?;
+    let steps: Vec<&AccountingLine> = lines.iter()
+        .filter(|line| line.job_id.strip_prefix(slurm_job_id).is_some_and(|rest| rest.starts_with('.')))
+        .collect();
+
+    let all_lines = || std::iter::once(main).chain(steps.iter().copied());
+    let max_rss_bytes = peak_memory(all_lines().map(|line| line.max_rss));
+    let max_vmsize_bytes = peak_memory(all_lines().map(|line| line.max_vmsize));
+
+    let cpu_time_seconds = parse_slurm_duration(main.cpu_time).map(|seconds| seconds as u64);
+    let total_cpu_seconds = parse_slurm_duration(main.total_cpu)
+        .filter(|seconds| *seconds > 0.0)
+        .or_else(|| {
+            let step_total: f64 = steps.iter().filter_map(|line| parse_slurm_duration(line.total_cpu)).sum();
+            (step_total > 0.0).then_some(step_total)
+        });
+    let cpu_efficiency = match (total_cpu_seconds, cpu_time_seconds) {
+        (Some(used), Some(allocated)) if allocated > 0 => Some((used / allocated as f64).min(1.0)),
+        _ => None,
+    };
+
+    let requested_memory_bytes = requested_memory(main.req_mem, main.alloc_cpus);
+    let memory_efficiency = match (max_rss_bytes, requested_memory_bytes) {
+        (Some(used), Some(requested)) if requested > 0 => Some(used as f64 / requested as f64),
+        _ => None,
+    };
+
+    Some(JobAccounting {
+        slurm_job_id: slurm_job_id.to_string(),
+        // "CANCELLED by 12345" keeps only the state
+        state: main.state.split_whitespace().next().unwrap_or_default().to_string(),
+        exit_code: main.exit_code.to_string(),
+        elapsed_seconds: parse_slurm_duration(main.elapsed).map(|seconds| seconds as u64),
+        cpu_time_seconds,
+        total_cpu_seconds,
+        cpu_efficiency,
+        max_rss_bytes,
+        max_vmsize_bytes,
+        requested_memory_bytes,
+        memory_efficiency,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_slurm_duration() {
+        assert_eq!(parse_slurm_duration("00:05:12"), Some(312.0));
+        assert_eq!(parse_slurm_duration("1-02:03:04"), Some(93784.0));
+        assert_eq!(parse_slurm_duration("05:12.500"), Some(312.5));
+        assert_eq!(parse_slurm_duration(""), None);
+        assert_eq!(parse_slurm_duration("INVALID"), None);
+        assert_eq!(parse_slurm_duration("1:2:3:4"), None);
+    }
+
+    #[test]
+    fn test_parse_slurm_memory() {
+        assert_eq!(parse_slurm_memory("2048K", 1), Some(2 * 1024 * 1024));
+        assert_eq!(parse_slurm_memory("1.5G", 1), Some(3 * (1 << 29)));
+        assert_eq!(parse_slurm_memory("0", 1), Some(0));
+        assert_eq!(parse_slurm_memory("4000", 1 << 20), Some(4000 << 20));
+        assert_eq!(parse_slurm_memory("", 1), None);
+    }
+
+    #[test]
+    fn test_memory_only_on_batch_step() {
+        let output = "\
+12345|02:00:00|||4-00:00:00|06:00:00|COMPLETED|0:0|64G|48
+12345.batch|02:00:00|20971520K|31457280K|4-00:00:00|05:59:00|COMPLETED|0:0||48
+12345.extern|02:00:00|1024K|4096K|4-00:00:00|00:00:01|COMPLETED|0:0||48
+";
+        let accounting = parse_job_accounting(output, "12345").unwrap();
+        assert_eq!(accounting.state, "COMPLETED");
+        assert_eq!(accounting.exit_code, "0:0");
+        assert_eq!(accounting.elapsed_seconds, Some(7200));
+        assert_eq!(accounting.cpu_time_seconds, Some(345600));
+        assert_eq!(accounting.total_cpu_seconds, Some(21600.0));
+        assert_eq!(accounting.cpu_efficiency, Some(21600.0 / 345600.0));
+        assert_eq!(accounting.max_rss_bytes, Some(20 * (1 << 30)));
+        assert_eq!(accounting.max_vmsize_bytes, Some(30 * (1 << 30)));
+        assert_eq!(accounting.requested_memory_bytes, Some(64 * (1 << 30)));
+        assert_eq!(accounting.memory_efficiency, Some(20.0 / 64.0));
+    }
+
+    #[test]
+    fn test_per_cpu_memory_and_step_cpu_totals() {
+        // Older SLURM: per-CPU ReqMem and no TotalCPU on the allocation line
+        let output = "\
+777|00:10:00|||02:40:00|00:00:00|OUT_OF_MEMORY|0:125|3800Mc|16
+777.batch|00:10:00|60000M|62000M|02:40:00|01:20:00|OUT_OF_MEMORY|0:125|3800Mc|16
+777.0|00:09:00|59000M|61000M|02:24:00|00:40:00|OUT_OF_MEMORY|0:125|3800Mc|16
+";
+        let accounting = parse_job_accounting(output, "777").unwrap();
+        assert_eq!(accounting.state, "OUT_OF_MEMORY");
+        assert_eq!(accounting.requested_memory_bytes, Some(3800 * 16 * (1 << 20)));
+        assert_eq!(accounting.max_rss_bytes, Some(60000 * (1 << 20)));
+        assert_eq!(accounting.total_cpu_seconds, Some(7200.0));
+        assert_eq!(accounting.cpu_efficiency, Some(0.75));
+    }
+
+    #[test]
+    fn test_unknown_job_and_cancelled_state() {
+        assert_eq!(parse_job_accounting("", "12345"), None);
+        assert_eq!(parse_job_accounting("99999|00:00:01|||00:00:01|00:00:00|COMPLETED|0:0|1G|1\n", "12345"), None);
+
+        let output = "12345|00:00:30|||00:00:30|00:00:00|CANCELLED by 5001|0:15|1G|1\n";
+        let accounting = parse_job_accounting(output, "12345").unwrap();
+        assert_eq!(accounting.state, "CANCELLED");
+        assert_eq!(accounting.total_cpu_seconds, None);
+        assert_eq!(accounting.cpu_efficiency, None);
+        assert_eq!(accounting.max_rss_bytes, None);
+    }
+}
@@ -14,9 +14,65 @@ use anyhow::Result;
 ///
 /// This uses safe shell escaping for the directory path and submits
 /// the specified script in that directory.
-pub fn submit_job_command(scratch_dir: &str, script_name: &str) -> Result<String> {
+/// `dependencies` are SLURM job IDs that must finish successfully first (afterok)
+pub fn submit_job_command(scratch_dir: &str, script_name: &str, dependencies: &[String]) -> Result<String> {
+    sbatch_command(scratch_dir, script_name, dependencies, "")
+}
+
+/// Build `sbatch --test-only`: the scheduler validates the script and estimates a start time
+/// without queueing anything. Same arguments as submit_job_command
+pub fn test_only_submit_command(work_dir: &str, script_path: &str, dependencies: &[String]) -> Result<String> {
+    sbatch_command(work_dir, &shell::escape_parameter(script_path), dependencies, "--test-only ")
+}
+
+fn sbatch_command(dir: &str, script: &str, dependencies: &[String], options: &str) -> Result<String> {
+    let dependency_option = if dependencies.is_empty() {
+        String::new()
+    } else {
+        let clean_ids = dependencies.iter().map(|id| input::sanitize_job_id(id)).collect::<Result<Vec<_>>>()?;
+        format!("--dependency=afterok:{} ", clean_ids.join(":"))
+    };
+
     // Use safe shell escaping for directory
-    Ok(shell::safe_cd_and_run(scratch_dir, &format!("sbatch {}", script_name)))
+    Ok(shell::safe_cd_and_run(dir, &format!("sbatch {}{}{}", options, dependency_option, script)))
+}
+
+/// Temp path a dry run uploads its script to (removed once sbatch has checked it)
+pub fn dry_run_script_path(job_id: &str) -> Result<String> {
+    let clean_id = input::sanitize_job_id(job_id)?;
+    use crate::ssh::commands::{DRY_RUN_SCRIPT_PREFIX, DRY_RUN_SCRIPT_SUFFIX, REMOTE_TEMP_DIR};
+    Ok(format!("{}/{}{}{}", REMOTE_TEMP_DIR, DRY_RUN_SCRIPT_PREFIX, clean_id, DRY_RUN_SCRIPT_SUFFIX))
+}
+
+/// Parse `sbatch --test-only` output (sbatch writes it to stderr) into a dry run of `script`
+///
+/// Accepted:  "sbatch: Job 3429485 to start at 2025-01-15T10:30:00 using 48 processors on nodes c3cpu-a2-u1-1 in partition amilan"
+/// Rejected:  "sbatch: error: Batch job submission failed: Requested node configuration is not available"
+pub fn parse_sbatch_test_only(output: &str, exit_code: i32, script: String) -> crate::types::response_data::DryRunResult {
+    let estimated_start = output.lines()
+        .find_map(|line| line.split_once(" to start at "))
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .map(String::from);
+
+    let mut errors: Vec<String> = output.lines()
+        .filter_map(|line| line.split_once("error:"))
+        .map(|(_, message)| message.trim().to_string())
+        .filter(|message| !message.is_empty())
+        .collect();
+    if exit_code != 0 && errors.is_empty() {
+        errors.push(match output.trim() {
+            "" => format!("sbatch exited with code {}", exit_code),
+            text => text.to_string(),
+        });
+    }
+
+    crate::types::response_data::DryRunResult {
+        accepted: exit_code == 0 && errors.is_empty(),
+        estimated_start,
+        errors,
+        output: output.trim().to_string(),
+        script,
+    }
 }
 
 /// Parse sbatch output to extract SLURM job ID
@@ -34,13 +90,14 @@ pub fn parse_sbatch_output(output: &str) -> Option<String> {
 // SLURM job status query commands
 
 /// Get active job status using squeue
-/// Always uses consistent job_id|status format for reliable parsing
+/// Always uses consistent job_id|status|reason format for reliable parsing
+/// (reason is the pending reason, "None" for running jobs)
 pub fn squeue_command(job_ids: &[String]) -> Result<String> {
     let sanitized_ids: Result<Vec<_>> = job_ids.iter()
         .map(|id| input::sanitize_job_id(id))
         .collect();
     let job_list = sanitized_ids?.join(",");
-    Ok(format!("squeue -j {} --format='%i|%T' --noheader", job_list))
+    Ok(format!("squeue -j {} --format='%i|%T|%r' --noheader", job_list))
 }
 
 /// Get completed job status using sacct
@@ -50,7 +107,53 @@ pub fn sacct_command(job_ids: &[String]) -> Result<String> {
         .map(|id| input::sanitize_job_id(id))
         .collect();
     let job_list = sanitized_ids?.join(",");
-    Ok(format!("sacct -j {} --format=JobID,State --parsable2 --noheader", job_list))
+    Ok(format!("sacct -j {} --format=JobID,State,Start --parsable2 --noheader", job_list))
+}
+
+/// Accounting record of one job and its steps (batch, extern, srun steps), one `|`-separated line each
+pub fn sacct_accounting_command(job_id: &str) -> Result<String> {
+    let clean_id = input::sanitize_job_id(job_id)?;
+    Ok(format!(
+        "sacct -j {} -o JobID,Elapsed,MaxRSS,MaxVMSize,CPUTime,TotalCPU,State,ExitCode,ReqMem,AllocCPUS -P --noheader",
+        clean_id
+    ))
+}
+
+/// squeue commands for any number of jobs, split so no command line exceeds the shell limit
+pub fn squeue_commands(job_ids: &[String]) -> Result<Vec<String>> {
+    batched_job_commands(job_ids, squeue_command, shell::MAX_COMMAND_LENGTH)
+}
+
+/// sacct commands for any number of jobs, split so no command line exceeds the shell limit
+pub fn sacct_commands(job_ids: &[String]) -> Result<Vec<String>> {
+    batched_job_commands(job_ids, sacct_command, shell::MAX_COMMAND_LENGTH)
+}
+
+/// Build one command per batch of comma-separated job IDs that fits within `max_len`
+fn batched_job_commands(
+    job_ids: &[String],
+    build: fn(&[String]) -> Result<String>,
+    max_len: usize,
+) -> Result<Vec<String>> {
+    let fixed_len = build(&[])?.len();
+
+    shell::batch_arguments(job_ids, fixed_len, 1, max_len)
+        .into_iter()
+        .map(|batch| {
+            let command = build(batch)?;
+            if shell::exceeds_command_limit(&command, max_len) {
+                return Err(anyhow::anyhow!("Command too long even for a single job ID ({} bytes)", command.len()));
+            }
+            Ok(command)
+        })
+        .collect()
+}
+
+/// Scheduler's start estimate for a pending job: job_id|expected start|priority|reason
+/// `--start` makes squeue report the backfill scheduler's start time instead of N/A where it can
+pub fn queue_start_command(job_id: &str) -> Result<String> {
+    let clean_id = input::sanitize_job_id(job_id)?;
+    Ok(format!("squeue --start -j {} --format='%i|%S|%Q|%r' --noheader", clean_id))
 }
 
 /// Cancel job command - single job
@@ -59,6 +162,39 @@ pub fn cancel_job_command(job_id: &str) -> Result<String> {
     Ok(format!("scancel {}", clean_id))
 }
 
+/// Hold a pending job so it stays queued but won't start
+pub fn hold_job_command(job_id: &str) -> Result<String> {
+    let clean_id = input::sanitize_job_id(job_id)?;
+    Ok(format!("scontrol hold {}", clean_id))
+}
+
+/// Release a held job back to the scheduler
+pub fn release_job_command(job_id: &str) -> Result<String> {
+    let clean_id = input::sanitize_job_id(job_id)?;
+    Ok(format!("scontrol release {}", clean_id))
+}
+
+/// List reservations one per line (maintenance windows carry Flags=MAINT)
+pub fn reservations_command() -> &'static str {
+    "scontrol show reservation --oneliner"
+}
+
+/// Report the cluster's SLURM version (e.g. "slurm 23.02.7")
+pub fn slurm_version_command() -> &'static str {
+    "sinfo --version"
+}
+
+/// One line per partition and node type: name (default marked '*'), time limit, CPUs per node,
+/// memory per node in MB and GRES
+pub fn partitions_command() -> &'static str {
+    "sinfo -h -o \"%P %l %c %m %G\""
+}
+
+/// QoS names and wall time limits, '|' separated without a header
+pub fn qos_limits_command() -> &'static str {
+    "sacctmgr -n -P show qos format=Name,MaxWall"
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,11 +202,57 @@ mod tests {
 
     #[test]
     fn test_submit_job_command() {
-        let cmd = submit_job_command("/scratch/test", "job.sbatch").unwrap();
+        let cmd = submit_job_command("/scratch/test", "job.sbatch", &[]).unwrap();
         assert!(cmd.contains("cd '/scratch/test'"));
         assert!(cmd.contains("sbatch job.sbatch"));
     }
 
+    #[test]
+    fn test_submit_job_command_with_dependencies() {
+        let cmd = submit_job_command("/scratch/test", "job.sbatch", &["1001".to_string(), "1002".to_string()]).unwrap();
+        assert!(cmd.contains("sbatch --dependency=afterok:1001:1002 job.sbatch"));
+
+        assert!(submit_job_command("/scratch/test", "job.sbatch", &["1001; rm -rf ~".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_test_only_submit_command() {
+        let cmd = test_only_submit_command("/projects/user/namdrunner_jobs/job_001", "/tmp/namdrunner_dryrun_job_001.sbatch", &["1001".to_string()]).unwrap();
+        assert!(cmd.contains("cd '/projects/user/namdrunner_jobs/job_001'"));
+        assert!(cmd.contains("sbatch --test-only --dependency=afterok:1001 '/tmp/namdrunner_dryrun_job_001.sbatch'"));
+
+        assert_eq!(dry_run_script_path("job_001").unwrap(), "/tmp/namdrunner_dryrun_job_001.sbatch");
+        assert!(dry_run_script_path("../job").is_err());
+    }
+
+    #[test]
+    fn test_parse_sbatch_test_only() {
+        let accepted = parse_sbatch_test_only(
+            "sbatch: Job 3429485 to start at 2025-01-15T10:30:00 using 48 processors on nodes c3cpu-a2-u1-1 in partition amilan\n",
+            0,
+            "#!/bin/bash".to_string(),
+        );
+        assert!(accepted.accepted);
+        assert_eq!(accepted.estimated_start.as_deref(), Some("2025-01-15T10:30:00"));
+        assert!(accepted.errors.is_empty());
+        assert_eq!(accepted.script, "#!/bin/bash");
+
+        let rejected = parse_sbatch_test_only(
+            "sbatch: error: invalid partition specified: amilan-gpu\nsbatch: error: Batch job submission failed: Invalid partition name specified\n",
+            1,
+            String::new(),
+        );
+        assert!(!rejected.accepted);
+        assert_eq!(rejected.estimated_start, None);
+        assert_eq!(rejected.errors, vec![
+            "invalid partition specified: amilan-gpu".to_string(),
+            "Batch job submission failed: Invalid partition name specified".to_string(),
+        ]);
+
+        let unexplained = parse_sbatch_test_only("", 127, String::new());
+        assert_eq!(unexplained.errors, vec!["sbatch exited with code 127".to_string()]);
+    }
+
     #[test]
     fn test_parse_sbatch() {
         assert_eq!(
@@ -93,10 +275,14 @@ mod tests {
         // Malicious inputs should be rejected
         assert!(squeue_command(&["12345; rm -rf /".to_string()]).is_err());
         assert!(cancel_job_command("../../etc/passwd").is_err());
+        assert!(hold_job_command("12345 && whoami").is_err());
+        assert!(release_job_command("$(id)").is_err());
 
         // Valid inputs should work
         assert!(squeue_command(&["12345".to_string()]).is_ok());
         assert!(cancel_job_command("12345").is_ok());
+        assert_eq!(hold_job_command("12345").unwrap(), "scontrol hold 12345");
+        assert_eq!(release_job_command("12345").unwrap(), "scontrol release 12345");
     }
 
     #[test]
@@ -105,13 +291,42 @@ mod tests {
         let single = squeue_command(&["12345".to_string()]).unwrap();
         let batch = squeue_command(&["12345".to_string(), "67890".to_string()]).unwrap();
 
-        // Both use job_id|status format
-        assert!(single.contains("--format='%i|%T'"));
-        assert!(batch.contains("--format='%i|%T'"));
+        // Both use job_id|status|reason format
+        assert!(single.contains("--format='%i|%T|%r'"));
+        assert!(batch.contains("--format='%i|%T|%r'"));
         assert!(single.contains("squeue -j 12345"));
         assert!(batch.contains("squeue -j 12345,67890"));
     }
 
+    #[test]
+    fn test_batched_job_commands_split_long_lists() {
+        let job_ids: Vec<String> = (10_000_000..10_000_050).map(|id| id.to_string()).collect();
+
+        // Small budget forces several batches
+        let commands = batched_job_commands(&job_ids, squeue_command, 200).unwrap();
+        assert!(commands.len() > 1);
+        assert!(commands.iter().all(|cmd| cmd.len() <= 200));
+        assert!(commands.iter().all(|cmd| cmd.contains("--format='%i|%T|%r'")));
+
+        // Every job queried exactly once, in order
+        let queried: Vec<String> = commands.iter()
+            .flat_map(|cmd| cmd.split_whitespace().nth(2).unwrap().split(',').map(String::from).collect::<Vec<_>>())
+            .collect();
+        assert_eq!(queried, job_ids);
+
+        // Normal-sized lists stay a single command identical to the unbatched builder
+        assert_eq!(squeue_commands(&job_ids).unwrap(), vec![squeue_command(&job_ids).unwrap()]);
+        assert_eq!(sacct_commands(&job_ids[..2]).unwrap(), vec![sacct_command(&job_ids[..2]).unwrap()]);
+        assert!(squeue_commands(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_batched_job_commands_rejects_unfittable_and_invalid() {
+        let job_ids = vec!["12345".to_string()];
+        assert!(batched_job_commands(&job_ids, squeue_command, 20).is_err());
+        assert!(squeue_commands(&["1; rm -rf /".to_string()]).is_err());
+    }
+
     #[test]
     fn test_sacct_consistent_format() {
         // Single and batch queries use identical format for reliable parsing
@@ -125,6 +340,20 @@ mod tests {
         assert!(batch.contains("--parsable2"));
     }
 
+    #[test]
+    fn test_sacct_accounting_command() {
+        let cmd = sacct_accounting_command("12345").unwrap();
+        assert_eq!(cmd, "sacct -j 12345 -o JobID,Elapsed,MaxRSS,MaxVMSize,CPUTime,TotalCPU,State,ExitCode,ReqMem,AllocCPUS -P --noheader");
+        assert!(sacct_accounting_command("12345; rm -rf ~").is_err());
+    }
+
+    #[test]
+    fn test_queue_start_command() {
+        let cmd = queue_start_command("12345").unwrap();
+        assert_eq!(cmd, "squeue --start -j 12345 --format='%i|%S|%Q|%r' --noheader");
+        assert!(queue_start_command("12345; scancel -u alice").is_err());
+    }
+
     #[test]
     fn test_cancel_job_command() {
         assert!(cancel_job_command("12345").is_ok());
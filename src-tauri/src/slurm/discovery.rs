@@ -0,0 +1,297 @@
+use crate::cluster::{ClusterCapabilities, PartitionSpec, QosSpec};
+use crate::types::response_data::{DiscoveredPartition, DiscoveredQos};
+use super::accounting::parse_slurm_duration;
+
+/// Names that can go straight into capabilities (and from there into sbatch directives)
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 64 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Whole hours in a SLURM time limit, rounded up; None for "infinite", "UNLIMITED" or blank
+fn parse_time_limit_hours(value: &str) -> Option<u32> {
+    let seconds = parse_slurm_duration(value)?;
+    Some((seconds / 3600.0).ceil() as u32)
+}
+
+/// Upper end of a sinfo number that may be a range ("32-64") or marked as varying ("64+")
+fn parse_upper<T: std::str::FromStr>(value: &str) -> Option<T> {
+    value.trim_end_matches('+').rsplit('-').next()?.parse().ok()
+}
+
+/// GPU type and count from a GRES list such as "gpu:a100:3(S:0-1),tmpfs:100G" or "gpu:3"
+fn parse_gpu_gres(gres: &str) -> Option<(Option<String>, u32)> {
+    // Socket bindings in parentheses can hold commas of their own
+    let mut plain = String::new();
+    let mut depth = 0;
+    for c in gres.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ if depth == 0 => plain.push(c),
+            _ => {}
+        }
+    }
+
+    plain.split(',').find_map(|item| {
+        let parts: Vec<&str> = item.trim().split(':').collect();
+        match parts.as_slice() {
+            ["gpu", count] => Some((None, count.parse().ok()?)),
+            ["gpu", gpu_type, count] => Some((Some(gpu_type.to_string()), count.parse().ok()?)),
+            _ => None,
+        }
+    })
+}
+
+/// Parse `partitions_command` output into one entry per partition
+///
+/// Partitions with several node types get one line each; those are merged by taking the
+/// largest node. Lines that don't parse, and names unsafe for sbatch, are skipped.
+pub fn parse_sinfo_partitions(output: &str) -> Vec<DiscoveredPartition> {
+    let mut partitions: Vec<DiscoveredPartition> = Vec::new();
+
+    for line in output.lines() {
+        let [name, time_limit, cpus, memory, gres] = line.split_whitespace().collect::<Vec<_>>()[..] else { continue };
+        let (name, is_default) = match name.strip_suffix('*') {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        let (Some(cores), Some(memory)) = (parse_upper::<u32>(cpus), parse_upper::<u64>(memory)) else { continue };
+        if !is_safe_name(name) {
+            continue;
+        }
+        let gpu = parse_gpu_gres(gres);
+
+        match partitions.iter_mut().find(|partition| partition.name == name) {
+            Some(partition) => {
+                partition.is_default |= is_default;
+                partition.cores_per_node = partition.cores_per_node.max(cores);
+                partition.memory_per_node_mb = partition.memory_per_node_mb.max(memory);
+                if let Some((gpu_type, count)) = gpu {
+                    if partition.gpu_count.is_none_or(|existing| count > existing) {
+                        partition.gpu_type = gpu_type;
+                        partition.gpu_count = Some(count);
+                    }
+                }
+            }
+            None => partitions.push(DiscoveredPartition {
+                name: name.to_string(),
+                is_default,
+                max_walltime_hours: parse_time_limit_hours(time_limit),
+                cores_per_node: cores,
+                memory_per_node_mb: memory,
+                gpu_type: gpu.as_ref().and_then(|(gpu_type, _)| gpu_type.clone()),
+                gpu_count: gpu.map(|(_, count)| count),
+            }),
+        }
+    }
+
+    partitions
+}
+
+/// Parse `qos_limits_command` output ("Name|MaxWall" per line)
+pub fn parse_sacctmgr_qos(output: &str) -> Vec<DiscoveredQos> {
+    output.lines()
+        .filter_map(|line| {
+            let (name, max_wall) = line.split_once('|')?;
+            let name = name.trim();
+            is_safe_name(name).then(|| DiscoveredQos {
+                name: name.to_string(),
+                max_walltime_hours: parse_time_limit_hours(max_wall.trim_end_matches('|')),
+            })
+        })
+        .collect()
+}
+
+/// Memory per core in GB (two decimals) for a node's memory and cores
+fn memory_per_core_gb(partition: &DiscoveredPartition) -> f64 {
+    let per_core = partition.memory_per_node_mb as f64 / 1024.0 / partition.cores_per_node.max(1) as f64;
+    (per_core * 100.0).floor() / 100.0
+}
+
+/// Merge discovered partitions and QoS into existing capabilities
+///
+/// Known partitions keep their titles and descriptions but take the discovered node size and
+/// GPUs; new ones are added. Known QoS take the discovered MaxWall; new QoS are allowed on every
+/// discovered partition, capped at the longest partition time limit when they set no MaxWall.
+/// A new partition no QoS covers joins the default QoS, so the result passes validation.
+/// Configured partitions sinfo didn't list are left alone.
+pub fn merge_discovered(capabilities: &ClusterCapabilities, partitions: &[DiscoveredPartition], qos_options: &[DiscoveredQos]) -> ClusterCapabilities {
+    let mut merged = capabilities.clone();
+    let default_partition = partitions.iter().find(|partition| partition.is_default).map(|partition| partition.name.as_str());
+
+    for discovered in partitions {
+        let spec = PartitionSpec {
+            name: discovered.name.clone(),
+            title: discovered.name.clone(),
+            description: "Discovered with sinfo".to_string(),
+            max_cores: discovered.cores_per_node,
+            max_memory_per_core_gb: memory_per_core_gb(discovered),
+            gpu_type: discovered.gpu_type.clone(),
+            gpu_count: discovered.gpu_count,
+            is_default: discovered.is_default,
+        };
+        match merged.partitions.iter_mut().find(|partition| partition.name == discovered.name) {
+            Some(existing) => {
+                existing.max_cores = spec.max_cores;
+                existing.max_memory_per_core_gb = spec.max_memory_per_core_gb;
+                existing.gpu_type = spec.gpu_type;
+                existing.gpu_count = spec.gpu_count;
+            }
+            None => merged.partitions.push(spec),
+        }
+    }
+    if let Some(default_partition) = default_partition {
+        for partition in &mut merged.partitions {
+            partition.is_default = partition.name == default_partition;
+        }
+    }
+
+    let longest_partition_hours = partitions.iter().filter_map(|partition| partition.max_walltime_hours).max();
+    let discovered_names: Vec<String> = partitions.iter().map(|partition| partition.name.clone()).collect();
+    for discovered in qos_options {
+        match merged.qos_options.iter_mut().find(|qos| qos.name == discovered.name) {
+            Some(existing) => {
+                if let Some(hours) = discovered.max_walltime_hours {
+                    existing.max_walltime_hours = hours;
+                }
+            }
+            None if !discovered_names.is_empty() => merged.qos_options.push(QosSpec {
+                name: discovered.name.clone(),
+                title: discovered.name.clone(),
+                description: "Discovered with sacctmgr".to_string(),
+                max_walltime_hours: discovered.max_walltime_hours.or(longest_partition_hours).unwrap_or(24),
+                valid_partitions: discovered_names.clone(),
+                min_memory_gb: None,
+                is_default: false,
+            }),
+            None => {}
+        }
+    }
+
+    // Every partition needs a QoS; uncovered ones join the default QoS (or a new "normal")
+    let uncovered: Vec<String> = merged.partitions.iter()
+        .filter(|partition| !merged.qos_options.iter().any(|qos| qos.valid_partitions.contains(&partition.name)))
+        .map(|partition| partition.name.clone())
+        .collect();
+    if !uncovered.is_empty() {
+        let fallback = merged.qos_options.iter().position(|qos| qos.is_default).or((!merged.qos_options.is_empty()).then_some(0));
+        match fallback {
+            Some(index) => merged.qos_options[index].valid_partitions.extend(uncovered),
+            None => merged.qos_options.push(QosSpec {
+                name: "normal".to_string(),
+                title: "normal".to_string(),
+                description: "Added for discovered partitions".to_string(),
+                max_walltime_hours: longest_partition_hours.unwrap_or(24),
+                valid_partitions: uncovered,
+                min_memory_gb: None,
+                is_default: true,
+            }),
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SINFO: &str = "\
+amilan* 1-00:00:00 64 239000 (null)
+amilan* 1-00:00:00 32 117000+ (null)
+amem 7-00:00:00 48 1000000 (null)
+aa100 1-00:00:00 64 243000 gpu:a100:3(S:0-1)
+atesting 1:00:00 8-16 64000 (null)
+acompile 12:00:00 4 16000 gpu:2,tmpfs:100G
+debug infinite 2 8000 (null)
+broken line
+";
+
+    #[test]
+    fn test_parse_sinfo_partitions() {
+        let partitions = parse_sinfo_partitions(SINFO);
+        assert_eq!(partitions.len(), 6);
+
+        let amilan = &partitions[0];
+        assert!(amilan.is_default);
+        assert_eq!(amilan.max_walltime_hours, Some(24));
+        assert_eq!((amilan.cores_per_node, amilan.memory_per_node_mb), (64, 239000));
+
+        let aa100 = partitions.iter().find(|p| p.name == "aa100").unwrap();
+        assert_eq!((aa100.gpu_type.as_deref(), aa100.gpu_count), (Some("a100"), Some(3)));
+
+        let atesting = partitions.iter().find(|p| p.name == "atesting").unwrap();
+        assert_eq!(atesting.cores_per_node, 16);
+        assert_eq!(atesting.max_walltime_hours, Some(1));
+
+        let acompile = partitions.iter().find(|p| p.name == "acompile").unwrap();
+        assert_eq!((acompile.gpu_type.as_deref(), acompile.gpu_count), (None, Some(2)));
+
+        assert_eq!(partitions.iter().find(|p| p.name == "debug").unwrap().max_walltime_hours, None);
+    }
+
+    #[test]
+    fn test_parse_gpu_gres() {
+        assert_eq!(parse_gpu_gres("(null)"), None);
+        assert_eq!(parse_gpu_gres("gpu:mi100:3(S:0,1),tmpfs:1"), Some((Some("mi100".to_string()), 3)));
+        assert_eq!(parse_gpu_gres("tmpfs:100G,gpu:4"), Some((None, 4)));
+    }
+
+    #[test]
+    fn test_parse_sacctmgr_qos() {
+        let qos = parse_sacctmgr_qos("normal|1-00:00:00\nlong|7-00:00:00\nunlimited|\nbad;name|1:00:00\n");
+        assert_eq!(qos, vec![
+            DiscoveredQos { name: "normal".to_string(), max_walltime_hours: Some(24) },
+            DiscoveredQos { name: "long".to_string(), max_walltime_hours: Some(168) },
+            DiscoveredQos { name: "unlimited".to_string(), max_walltime_hours: None },
+        ]);
+        assert!(parse_sacctmgr_qos("").is_empty());
+    }
+
+    #[test]
+    fn test_merge_into_alpine_defaults() {
+        let defaults = crate::cluster::load_default_config_for_tests();
+        let mut partitions = parse_sinfo_partitions(SINFO);
+        partitions[0].is_default = false;
+        let amem = partitions.iter_mut().find(|p| p.name == "amem").unwrap();
+        amem.is_default = true;
+        let qos = parse_sacctmgr_qos("normal|12:00:00\ngpu-long|3-00:00:00\n");
+
+        let merged = merge_discovered(&defaults, &partitions, &qos);
+        crate::cluster::validate_capabilities(&merged).unwrap();
+
+        // Known partition keeps its title, takes the discovered node size and default marker
+        let amem = merged.partitions.iter().find(|p| p.name == "amem").unwrap();
+        assert_eq!(amem.title, defaults.partitions.iter().find(|p| p.name == "amem").unwrap().title);
+        assert_eq!(amem.max_cores, 48);
+        assert!(amem.is_default);
+        assert!(!merged.partitions.iter().find(|p| p.name == "amilan").unwrap().is_default);
+
+        // Known QoS takes the discovered MaxWall; a new QoS covers every discovered partition
+        assert_eq!(merged.qos_options.iter().find(|q| q.name == "normal").unwrap().max_walltime_hours, 12);
+        let gpu_long = merged.qos_options.iter().find(|q| q.name == "gpu-long").unwrap();
+        assert_eq!(gpu_long.max_walltime_hours, 72);
+        assert_eq!(gpu_long.valid_partitions.len(), partitions.len());
+
+        // Without new QoS, a new partition joins the default QoS
+        let merged = merge_discovered(&defaults, &partitions, &[]);
+        crate::cluster::validate_capabilities(&merged).unwrap();
+        assert!(merged.qos_options.iter().find(|q| q.name == "normal").unwrap().valid_partitions.contains(&"debug".to_string()));
+    }
+
+    #[test]
+    fn test_merge_without_qos_access() {
+        // sacctmgr restricted and nothing configured yet: partitions still get a QoS
+        let mut empty = crate::cluster::load_default_config_for_tests();
+        empty.partitions.clear();
+        empty.qos_options.clear();
+        empty.job_presets.clear();
+
+        let merged = merge_discovered(&empty, &parse_sinfo_partitions(SINFO), &[]);
+        crate::cluster::validate_capabilities(&merged).unwrap();
+        assert_eq!(merged.qos_options.len(), 1);
+        assert_eq!(merged.qos_options[0].name, "normal");
+        assert_eq!(merged.qos_options[0].max_walltime_hours, 168);
+        assert_eq!(merged.partitions.iter().find(|p| p.name == "amilan").unwrap().max_memory_per_core_gb, 3.64);
+    }
+}
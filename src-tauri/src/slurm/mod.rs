@@ -1,3 +1,6 @@
 pub mod status;
 pub mod commands;
 pub mod script_generator;
+pub mod namd_progress;
+pub mod accounting;
+pub mod discovery;
@@ -0,0 +1,257 @@
+use crate::types::{NamdPhase, NamdProgress};
+
+/// Incremental parser for NAMD stdout: feed it log text in order, then ask for progress
+///
+/// NAMD announces each phase ("TCL: Minimizing for 1000 steps", "TCL: Running for 50000 steps")
+/// and reports the step it reached on ENERGY:, TIMING: and PERFORMANCE: lines. Steps count
+/// up across phases and start at firsttimestep, so progress is measured from where the
+/// current phase started rather than from zero.
+#[derive(Debug, Default)]
+pub struct NamdProgressParser {
+    phase: Option<NamdPhase>,
+    phase_start_step: u64,
+    phase_steps: Option<u64>,
+    current_step: Option<u64>,
+    timestep_fs: Option<f64>,
+    seconds_per_step: Option<f64>,
+    reported_ns_per_day: Option<f64>,
+}
+
+impl NamdProgressParser {
+    /// Parse a chunk made of whole lines
+    pub fn feed(&mut self, chunk: &str) {
+        for line in chunk.lines() {
+            self.parse_line(line.trim());
+        }
+    }
+
+    /// Parse a chunk read from the middle of the log, whose first line is probably cut off
+    pub fn feed_from_middle(&mut self, chunk: &str) {
+        if let Some((_, rest)) = chunk.split_once('\n') {
+            self.feed(rest);
+        }
+    }
+
+    /// Progress through the current phase, once NAMD has reported a step
+    pub fn progress(&self) -> Option<NamdProgress> {
+        let current_step = self.current_step?.saturating_sub(self.phase_start_step);
+        let phase = self.phase.clone().unwrap_or(NamdPhase::Dynamics);
+        let total_steps = self.phase_steps;
+
+        let percent_complete = total_steps
+            .filter(|total| *total > 0)
+            .map(|total| (current_step as f64 / total as f64 * 100.0).min(100.0));
+        let eta_seconds = match (total_steps, self.seconds_per_step) {
+            (Some(total), Some(seconds)) => Some((total.saturating_sub(current_step) as f64 * seconds).round() as u64),
+            _ => None,
+        };
+
+        // ns/day means nothing while minimizing - there is no simulated time
+        let ns_per_day = match phase {
+            NamdPhase::Minimization => None,
+            NamdPhase::Dynamics => self.reported_ns_per_day.or_else(|| {
+                let seconds = self.seconds_per_step.filter(|seconds| *seconds > 0.0)?;
+                Some(86_400.0 / seconds * self.timestep_fs? * 1e-6)
+            }),
+        };
+
+        Some(NamdProgress { phase, current_step, total_steps, percent_complete, ns_per_day, eta_seconds })
+    }
+
+    fn parse_line(&mut self, line: &str) {
+        if let Some(rest) = line.strip_prefix("TCL: Minimizing for") {
+            self.start_phase(NamdPhase::Minimization, leading_number(rest));
+        } else if let Some(rest) = line.strip_prefix("TCL: Running for") {
+            self.start_phase(NamdPhase::Dynamics, leading_number(rest));
+        } else if let Some(rest) = line.strip_prefix("Info: FIRST TIMESTEP") {
+            // A new NAMD run (possibly a restart from a later step) begins here
+            if let Some(first_step) = leading_number(rest) {
+                *self = Self { timestep_fs: self.timestep_fs, phase_steps: self.phase_steps, ..Self::default() };
+                self.phase_start_step = first_step;
+                self.current_step = Some(first_step);
+            }
+        } else if let Some(rest) = line.strip_prefix("Info: NUMBER OF STEPS") {
+            // numsteps without an explicit run/minimize command
+            if self.phase_steps.is_none() {
+                self.phase_steps = leading_number(rest).filter(|steps| *steps > 0);
+            }
+        } else if let Some(rest) = line.strip_prefix("Info: TIMESTEP") {
+            self.timestep_fs = rest.trim().parse().ok();
+        } else if let Some(rest) = line.strip_prefix("ENERGY:") {
+            self.record_step(leading_number(rest));
+        } else if let Some(rest) = line.strip_prefix("TIMING:") {
+            // TIMING: 500  CPU: 21.4, 0.0428/step  Wall: 21.5, 0.0430/step, 5.96 hours remaining, ...
+            self.record_step(leading_number(rest));
+            if let Some(wall) = rest.split("Wall:").nth(1) {
+                self.seconds_per_step = per_step_value(wall).or(self.seconds_per_step);
+            }
+        } else if let Some(rest) = line.strip_prefix("PERFORMANCE:") {
+            // PERFORMANCE: 500  averaging 4.01 ns/day, 0.0431 sec/step with current rate 4.02 ns/day, ...
+            self.record_step(leading_number(rest));
+            let fields: Vec<&str> = rest.split_whitespace().collect();
+            // The run average comes first; "with current rate" values follow
+            let value_before = |unit: &str| fields.windows(2)
+                .find(|pair| pair[1].starts_with(unit))
+                .and_then(|pair| pair[0].parse().ok());
+            if let Some(ns_per_day) = value_before("ns/day") {
+                self.reported_ns_per_day = Some(ns_per_day);
+            }
+            if let Some(seconds) = value_before("sec/step") {
+                self.seconds_per_step = Some(seconds);
+            }
+        }
+    }
+
+    fn start_phase(&mut self, phase: NamdPhase, steps: Option<u64>) {
+        self.phase = Some(phase);
+        self.phase_start_step = self.current_step.unwrap_or(self.phase_start_step);
+        self.phase_steps = steps;
+        // Timing from the previous phase doesn't predict this one
+        self.seconds_per_step = None;
+        self.reported_ns_per_day = None;
+    }
+
+    fn record_step(&mut self, step: Option<u64>) {
+        let Some(step) = step else {
+            return;
+        };
+        // The counter went backwards without a FIRST TIMESTEP line we saw: a new run
+        // started from step 0 in a part of the log we didn't read
+        if step < self.phase_start_step {
+            self.phase_start_step = 0;
+            self.seconds_per_step = None;
+            self.reported_ns_per_day = None;
+        }
+        self.current_step = Some(step);
+    }
+}
+
+/// First whitespace-separated field as an integer step count
+fn leading_number(text: &str) -> Option<u64> {
+    text.split_whitespace().next()?.parse().ok()
+}
+
+/// "21.5, 0.0430/step, ..." -> 0.0430
+fn per_step_value(text: &str) -> Option<f64> {
+    text.split([',', ' '])
+        .find_map(|field| field.strip_suffix("/step"))
+        .and_then(|value| value.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DYNAMICS_LOG: &str = "\
+Info: TIMESTEP 2
+Info: NUMBER OF STEPS 0
+Info: FIRST TIMESTEP 0
+TCL: Running for 50000 steps
+ENERGY:       0      1234.5     2345.6
+TIMING: 500  CPU: 21.4, 0.0428/step  Wall: 21.5, 0.0430/step, 5.96 hours remaining, 1163.0 MB of memory in use.
+ENERGY:     500      1234.5     2345.6
+ENERGY:   10000      1234.5     2345.6
+";
+
+    #[test]
+    fn test_dynamics_progress_and_eta() {
+        let mut parser = NamdProgressParser::default();
+        parser.feed(DYNAMICS_LOG);
+
+        let progress = parser.progress().unwrap();
+        assert_eq!(progress.phase, NamdPhase::Dynamics);
+        assert_eq!(progress.current_step, 10_000);
+        assert_eq!(progress.total_steps, Some(50_000));
+        assert_eq!(progress.percent_complete, Some(20.0));
+        assert_eq!(progress.eta_seconds, Some(1720)); // 40000 steps * 0.043 s
+        // 2 fs every 0.043 s
+        let ns_per_day = progress.ns_per_day.unwrap();
+        assert!((ns_per_day - 4.0186).abs() < 0.001, "{}", ns_per_day);
+    }
+
+    #[test]
+    fn test_minimization_then_dynamics() {
+        let mut parser = NamdProgressParser::default();
+        parser.feed("\
+Info: TIMESTEP 2
+Info: FIRST TIMESTEP 0
+TCL: Minimizing for 1000 steps
+ENERGY:     400      -5000.1
+TIMING: 400  CPU: 8.0, 0.02/step  Wall: 8.1, 0.02/step, 0.1 hours remaining, 800 MB of memory in use.
+");
+        let progress = parser.progress().unwrap();
+        assert_eq!(progress.phase, NamdPhase::Minimization);
+        assert_eq!(progress.current_step, 400);
+        assert_eq!(progress.total_steps, Some(1000));
+        assert_eq!(progress.eta_seconds, Some(12));
+        assert_eq!(progress.ns_per_day, None);
+
+        // Steps keep counting from where minimization stopped
+        parser.feed("\
+ENERGY:    1000      -5100.2
+TCL: Running for 20000 steps
+ENERGY:    6000      1234.5
+");
+        let progress = parser.progress().unwrap();
+        assert_eq!(progress.phase, NamdPhase::Dynamics);
+        assert_eq!(progress.current_step, 5000);
+        assert_eq!(progress.total_steps, Some(20_000));
+        assert_eq!(progress.percent_complete, Some(25.0));
+        // No timing reported yet for the new phase
+        assert_eq!(progress.eta_seconds, None);
+    }
+
+    #[test]
+    fn test_restart_from_later_step() {
+        let mut parser = NamdProgressParser::default();
+        parser.feed("\
+Info: TIMESTEP 2
+Info: FIRST TIMESTEP 100000
+TCL: Running for 50000 steps
+ENERGY:  125000      1234.5
+");
+        let progress = parser.progress().unwrap();
+        assert_eq!(progress.current_step, 25_000);
+        assert_eq!(progress.percent_complete, Some(50.0));
+    }
+
+    #[test]
+    fn test_step_counter_reset() {
+        let mut parser = NamdProgressParser::default();
+        parser.feed("\
+Info: FIRST TIMESTEP 100000
+TCL: Running for 50000 steps
+ENERGY:  120000      1234.5
+");
+        // Only the tail of the second run's log was read
+        parser.feed_from_middle("\
+234.5     2345.6
+ENERGY:     0      1234.5
+ENERGY:   5000      1234.5
+");
+        let progress = parser.progress().unwrap();
+        assert_eq!(progress.current_step, 5000);
+        assert_eq!(progress.percent_complete, Some(10.0));
+    }
+
+    #[test]
+    fn test_performance_line() {
+        let mut parser = NamdProgressParser::default();
+        parser.feed("\
+Info: TIMESTEP 2
+TCL: Running for 10000 steps
+PERFORMANCE: 5000  averaging 4.01154 ns/day, 0.0430771 sec/step with current rate 4.2 ns/day, 0.041 sec/step
+");
+        let progress = parser.progress().unwrap();
+        assert_eq!(progress.current_step, 5000);
+        assert_eq!(progress.ns_per_day, Some(4.01154));
+        assert_eq!(progress.eta_seconds, Some(215));
+    }
+
+    #[test]
+    fn test_no_steps_yet() {
+        let mut parser = NamdProgressParser::default();
+        parser.feed("Info: NAMD 3.0.1 for Linux-x86_64-MPI\nInfo: TIMESTEP 2\n");
+        assert!(parser.progress().is_none());
+    }
+}
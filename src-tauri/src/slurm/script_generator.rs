@@ -22,15 +22,23 @@ impl SlurmScriptGenerator {
         // Use provided scratch directory as working directory
         let working_dir = scratch_dir;
 
+        // Array jobs run one NAMD task per sweep value
+        let array_size = job_info.array.as_ref().map(|array| array.values.len());
+        let execution = match array_size {
+            Some(_) => Self::build_array_task_execution(),
+            None => Self::build_namd_execution(slurm_config.cores),
+        };
+
         // Build script from modular sections
         let sections = [
             Self::build_shebang(),
-            Self::build_slurm_directives(&job_name, slurm_config, &memory_with_unit)?,
+            Self::build_slurm_directives(&job_name, slurm_config, &memory_with_unit, array_size)?
+                + &Self::build_mail_directives(job_info)?,
             Self::build_job_metadata(job_info),
             Self::build_environment_setup(),
-            Self::build_module_loads(),
+            Self::build_module_loads(crate::cluster::active_cluster_profile().and_then(|profile| profile.module_setup).as_deref()),
             Self::build_working_directory(working_dir),
-            Self::build_namd_execution(slurm_config.cores),
+            execution,
         ];
 
         Ok(sections.join("\n"))
@@ -53,10 +61,10 @@ impl SlurmScriptGenerator {
         // Build script sections (without job metadata since this is preview)
         let sections = [
             Self::build_shebang(),
-            Self::build_slurm_directives(&safe_job_name, &slurm_config, &memory_with_unit)?,
+            Self::build_slurm_directives(&safe_job_name, &slurm_config, &memory_with_unit, None)?,
             Self::build_preview_comment(),
             Self::build_environment_setup(),
-            Self::build_module_loads(),
+            Self::build_module_loads(crate::cluster::active_cluster_profile().and_then(|profile| profile.module_setup).as_deref()),
             Self::build_working_directory(preview_scratch_dir),
             Self::build_namd_execution(slurm_config.cores),
         ];
@@ -72,23 +80,53 @@ impl SlurmScriptGenerator {
         "#!/bin/bash".to_string()
     }
 
-    fn build_slurm_directives(job_name: &str, config: &SlurmConfig, memory: &str) -> Result<String> {
+    fn build_slurm_directives(job_name: &str, config: &SlurmConfig, memory: &str, array_size: Option<usize>) -> Result<String> {
+        // Array tasks log separately as {name}_{array job id}_{task id}
+        let (log_suffix, array_directive) = match array_size {
+            Some(0) => return Err(anyhow!("Job array must have at least one task")),
+            Some(size) => ("%A_%a", format!("#SBATCH --array=0-{}\n", size - 1)),
+            None => ("%j", String::new()),
+        };
+        let gres_directive = match config.gpu_gres()? {
+            Some(gres) => format!("#SBATCH --gres={}\n", gres),
+            None => String::new(),
+        };
+
         Ok(format!(
             "#SBATCH --job-name={}\n\
-             #SBATCH --output={}_%j.out\n\
-             #SBATCH --error={}_%j.err\n\
+             {}#SBATCH --output={}_{}.out\n\
+             #SBATCH --error={}_{}.err\n\
              #SBATCH --partition={}\n\
              #SBATCH --nodes=1\n\
              #SBATCH --ntasks={}\n\
-             #SBATCH --time={}\n\
+             {}#SBATCH --time={}\n\
              #SBATCH --mem={}\n\
              #SBATCH --qos={}\n\
              #SBATCH --constraint=ib",
-            job_name, job_name, job_name, config.partition,
-            config.cores, config.walltime, memory, config.qos
+            job_name, array_directive, job_name, log_suffix, job_name, log_suffix, config.partition,
+            config.cores, gres_directive, config.walltime, memory, config.qos
         ))
     }
 
+    /// Email notification directives, empty when the job has no address
+    /// The address is re-validated here since it lands verbatim in the script
+    fn build_mail_directives(job_info: &JobInfo) -> Result<String> {
+        let Some(email) = job_info.notify_email.as_deref() else {
+            return Ok(String::new());
+        };
+        let email = crate::security::input::sanitize_email(email)
+            .map_err(|e| anyhow!("Invalid notification email: {}", e))?;
+
+        let events = if job_info.notify_events.is_empty() {
+            MailEvent::DEFAULT_EVENTS.as_slice()
+        } else {
+            job_info.notify_events.as_slice()
+        };
+        let mail_types: Vec<&str> = events.iter().map(MailEvent::slurm_name).collect();
+
+        Ok(format!("\n#SBATCH --mail-user={}\n#SBATCH --mail-type={}", email, mail_types.join(",")))
+    }
+
     fn build_job_metadata(job_info: &JobInfo) -> String {
         format!(
             "\n# Generated by NAMDRunner on {}\n\
@@ -114,12 +152,16 @@ impl SlurmScriptGenerator {
          export SLURM_EXPORT_ENV=ALL  # Required for OpenMPI".to_string()
     }
 
-    fn build_module_loads() -> String {
-        "\n# Load required modules for NAMD execution\n\
-         module purge\n\
-         module load gcc/14.2.0\n\
-         module load openmpi/5.0.6\n\
-         module load namd/3.0.1_cpu".to_string()
+    /// `module_setup` comes from the active cluster profile; None uses the Alpine modules
+    fn build_module_loads(module_setup: Option<&str>) -> String {
+        match module_setup {
+            Some(setup) => format!("\n# Load required modules for NAMD execution\n{}", setup),
+            None => "\n# Load required modules for NAMD execution\n\
+                     module purge\n\
+                     module load gcc/14.2.0\n\
+                     module load openmpi/5.0.6\n\
+                     module load namd/3.0.1_cpu".to_string(),
+        }
     }
 
     fn build_working_directory(dir: &str) -> String {
@@ -127,8 +169,36 @@ impl SlurmScriptGenerator {
     }
 
     fn build_namd_execution(_cores: u32) -> String {
-        "\n# Execute NAMD with MPI (OpenMPI handles CPU affinity automatically)\n\
-             mpirun -np $SLURM_NTASKS namd3 config.namd > namd_output.log".to_string()
+        format!(
+            "\n# Execute NAMD with MPI (OpenMPI handles CPU affinity automatically)\n\
+             mpirun -np $SLURM_NTASKS namd3 {} > {}",
+            crate::ssh::JobDirectoryStructure::NAMD_CONFIG,
+            crate::ssh::JobDirectoryStructure::NAMD_OUTPUT_LOG
+        )
+    }
+
+    /// Each array task gets its own directory so NAMD outputs and logs don't collide;
+    /// it sees the shared inputs and writes into outputs/task_N through symlinks
+    fn build_array_task_execution() -> String {
+        use crate::ssh::JobDirectoryStructure;
+        format!(
+            "\n# Run this array task's config in its own directory\n\
+             TASK_DIR={task_dir}\n\
+             mkdir -p \"$TASK_DIR\" \"{outputs}/$TASK_DIR\"\n\
+             ln -sfn ../{inputs} \"$TASK_DIR/{inputs}\"\n\
+             ln -sfn \"../{outputs}/$TASK_DIR\" \"$TASK_DIR/{outputs}\"\n\
+             cp \"{task_config}\" \"$TASK_DIR/{config}\"\n\
+             cd \"$TASK_DIR\"\n\
+             \n\
+             # Execute NAMD with MPI (OpenMPI handles CPU affinity automatically)\n\
+             mpirun -np $SLURM_NTASKS namd3 {config} > {log}",
+            task_dir = JobDirectoryStructure::array_task_dir("${SLURM_ARRAY_TASK_ID}"),
+            task_config = JobDirectoryStructure::array_task_config("${SLURM_ARRAY_TASK_ID}"),
+            inputs = JobDirectoryStructure::INPUT_FILES,
+            outputs = JobDirectoryStructure::OUTPUTS,
+            config = JobDirectoryStructure::NAMD_CONFIG,
+            log = JobDirectoryStructure::NAMD_OUTPUT_LOG,
+        )
     }
 
     // ===== Helper Functions =====
@@ -178,9 +248,27 @@ mod tests {
                 walltime: "24:00:00".to_string(),
                 partition: "amilan".to_string(),
                 qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
             },
             input_files: vec![],
             output_files: vec![],
+            archived: false,
+            pinned: false,
+            tags: Vec::new(),
+            notes: None,
+            status_detail: None,
+            submitted_script: None,
+            template_snapshot: None,
+            rendered_config: None,
+            depends_on: None,
+            array: None,
+            notify_email: None,
+            notify_events: Vec::new(),
+            actual_cost: None,
+            namd_progress: None,
+            estimated_start: None,
+            queue_priority: None,
         }
     }
 
@@ -233,9 +321,11 @@ mod tests {
             walltime: "24:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
 
-        let directives = SlurmScriptGenerator::build_slurm_directives("test_job", &config, "32GB").unwrap();
+        let directives = SlurmScriptGenerator::build_slurm_directives("test_job", &config, "32GB", None).unwrap();
 
         // Verify required SLURM directives are present
         assert!(directives.contains("#SBATCH --job-name=test_job"));
@@ -248,6 +338,23 @@ mod tests {
         assert!(directives.contains("#SBATCH --mem=32GB"));
         assert!(directives.contains("#SBATCH --qos=normal"));
         assert!(directives.contains("#SBATCH --constraint=ib"));
+        assert!(!directives.contains("--gres"));
+    }
+
+    #[test]
+    fn test_build_slurm_directives_with_gpus() {
+        let config = SlurmConfig {
+            partition: "aa100".to_string(),
+            gpus: Some(2),
+            gpu_type: Some("a100".to_string()),
+            ..SlurmConfig::default()
+        };
+
+        let directives = SlurmScriptGenerator::build_slurm_directives("gpu_job", &config, "32GB", None).unwrap();
+        assert!(directives.contains("#SBATCH --ntasks=4\n#SBATCH --gres=gpu:a100:2\n#SBATCH --time="));
+
+        let config = SlurmConfig { gpu_type: Some("a100 --exclusive".to_string()), ..config };
+        assert!(SlurmScriptGenerator::build_slurm_directives("gpu_job", &config, "32GB", None).is_err());
     }
 
     #[test]
@@ -270,13 +377,20 @@ mod tests {
 
     #[test]
     fn test_build_module_loads() {
-        let modules = SlurmScriptGenerator::build_module_loads();
+        let modules = SlurmScriptGenerator::build_module_loads(None);
         assert!(modules.contains("module purge"));
         assert!(modules.contains("module load gcc/14.2.0"));
         assert!(modules.contains("module load openmpi/5.0.6"));
         assert!(modules.contains("module load namd/3.0.1_cpu"));
     }
 
+    #[test]
+    fn test_build_module_loads_from_profile() {
+        let modules = SlurmScriptGenerator::build_module_loads(Some("module purge\nmodule load namd/2.14"));
+        assert!(modules.ends_with("\nmodule purge\nmodule load namd/2.14"));
+        assert!(!modules.contains("gcc/14.2.0"));
+    }
+
     #[test]
     fn test_build_working_directory() {
         let dir = SlurmScriptGenerator::build_working_directory("/scratch/alpine/user/job_123");
@@ -298,6 +412,8 @@ mod tests {
             walltime: "12:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
 
         let script = SlurmScriptGenerator::preview_script("preview_test".to_string(), config).unwrap();
@@ -391,4 +507,52 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invalid memory specification"));
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_generate_array_script() {
+        let mut job = create_test_job_info("temperature_sweep");
+        job.array = Some(JobArray {
+            variable: "temperature".to_string(),
+            values: vec![serde_json::json!(300), serde_json::json!(310), serde_json::json!(320)],
+            task_statuses: vec![],
+        });
+
+        let script = SlurmScriptGenerator::generate_namd_script(&job, "/scratch/test").unwrap();
+
+        assert!(script.contains("#SBATCH --array=0-2"));
+        assert!(script.contains("#SBATCH --output=temperature_sweep_%A_%a.out"));
+        assert!(script.contains("#SBATCH --error=temperature_sweep_%A_%a.err"));
+        assert!(script.contains("TASK_DIR=task_${SLURM_ARRAY_TASK_ID}"));
+        assert!(script.contains("cp \"config_${SLURM_ARRAY_TASK_ID}.namd\" \"$TASK_DIR/config.namd\""));
+        assert!(script.contains("ln -sfn \"../outputs/$TASK_DIR\" \"$TASK_DIR/outputs\""));
+
+        // Still runs config.namd, so submission validation applies unchanged
+        let validation = crate::validation::job::validate_submission_artifacts("outputName outputs/sweep\n", &script, &[]);
+        assert!(validation.is_valid, "{:?}", validation.issues);
+    }
+
+    #[test]
+    fn test_generate_script_with_email_notifications() {
+        let mut job = create_test_job_info("notify_job");
+        let script = SlurmScriptGenerator::generate_namd_script(&job, "/scratch/test").unwrap();
+        assert!(!script.contains("--mail"));
+
+        job.notify_email = Some("alice@colorado.edu".to_string());
+        let script = SlurmScriptGenerator::generate_namd_script(&job, "/scratch/test").unwrap();
+        assert!(script.contains("#SBATCH --constraint=ib\n#SBATCH --mail-user=alice@colorado.edu\n#SBATCH --mail-type=END,FAIL\n"));
+
+        job.notify_events = vec![MailEvent::Begin, MailEvent::TimeLimit80];
+        let script = SlurmScriptGenerator::generate_namd_script(&job, "/scratch/test").unwrap();
+        assert!(script.contains("#SBATCH --mail-type=BEGIN,TIME_LIMIT_80"));
+
+        job.notify_email = Some("alice@colorado.edu\n#SBATCH --partition=amem".to_string());
+        assert!(SlurmScriptGenerator::generate_namd_script(&job, "/scratch/test").is_err());
+    }
+
+    #[test]
+    fn test_empty_array_fails() {
+        let mut job = create_test_job_info("empty_sweep");
+        job.array = Some(JobArray { variable: "temperature".to_string(), values: vec![], task_statuses: vec![] });
+
+        assert!(SlurmScriptGenerator::generate_namd_script(&job, "/scratch/test").is_err());
+    }
+}
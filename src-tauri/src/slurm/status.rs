@@ -1,47 +1,297 @@
-use crate::types::JobStatus;
-use crate::ssh::{get_connection_manager, retry_quick};
+use crate::types::{ArrayTaskStatus, JobStatus};
+use crate::ssh::{get_connection_manager, retry_quick, CommandResult, RemoteExecutor};
 use super::commands::*;
 use crate::log_warn;
 use anyhow::{Result, anyhow};
 
-pub struct SlurmStatusSync {}
+/// SLURM queries and job control, run through a RemoteExecutor
+pub struct SlurmStatusSync<'a> {
+    remote: &'a dyn RemoteExecutor,
+}
+
+/// Pending reason that means the job is waiting on a reservation rather than resources
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReservationHold {
+    /// Held back by a maintenance reservation (its walltime would overlap the window)
+    Maintenance,
+    /// Waiting for the job's own advanced reservation to start
+    Reservation,
+}
+
+/// Maintenance reservation window reported by scontrol
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaintenanceWindow {
+    pub name: String,
+    pub start: String,
+    pub end: String,
+}
+
+/// Classify a squeue pending reason, e.g. "ReqNodeNotAvail, Reserved for maintenance"
+pub fn classify_pending_reason(reason: &str) -> Option<ReservationHold> {
+    let reason = reason.trim().to_lowercase();
+
+    if reason.contains("maintenance") {
+        Some(ReservationHold::Maintenance)
+    } else if reason.starts_with("reservation") || reason.starts_with("resv") {
+        Some(ReservationHold::Reservation)
+    } else {
+        None
+    }
+}
+
+/// Whether a pending job can never start because a dependency failed (afterok not met)
+/// SLURM leaves such jobs queued forever unless the cluster sets kill_invalid_depend
+pub fn is_dependency_never_satisfied(reason: &str) -> bool {
+    reason.trim().trim_start_matches('(').trim_end_matches(')').eq_ignore_ascii_case("DependencyNeverSatisfied")
+}
+
+/// Whether a pending job is held (scontrol hold) rather than waiting to be scheduled
+/// Matches both user holds (JobHeldUser) and admin holds (JobHeldAdmin)
+pub fn is_held_reason(reason: &str) -> bool {
+    let reason = reason.trim().trim_start_matches('(').trim_end_matches(')');
+    reason.eq_ignore_ascii_case("JobHeldUser") || reason.eq_ignore_ascii_case("JobHeldAdmin")
+}
+
+/// Whether a pending job is at the front of the queue, waiting only for nodes to free up
+/// SLURM reports Resources (rather than Priority) once nothing ahead of the job blocks it
+pub fn is_next_to_start(reason: &str) -> bool {
+    reason.trim().trim_start_matches('(').trim_end_matches(')').eq_ignore_ascii_case("Resources")
+}
+
+/// Detail for a held job; only admin holds need explaining since the user can't release them
+pub fn held_status_detail(reason: &str) -> Option<String> {
+    reason.trim().trim_start_matches('(').trim_end_matches(')').eq_ignore_ascii_case("JobHeldAdmin")
+        .then(|| "Held by a cluster administrator - only an administrator can release it".to_string())
+}
+
+/// Why a finished job failed or was cancelled early, from its sacct state
+/// `started` is false when sacct has no start time (the job never left the queue)
+/// None for ordinary outcomes: completed, a plain non-zero exit, or a cancel while running
+pub fn terminal_state_detail(state: &str, started: bool) -> Option<String> {
+    let state = state.split_whitespace().next().unwrap_or_default().to_uppercase();
+    let detail = match state.as_str() {
+        "TO" | "TIMEOUT" => "Killed: reached its walltime limit - increase --time or restart from the last checkpoint",
+        "OOM" | "OUT_OF_MEMORY" | "OUT_OF_ME+" => "Killed: out of memory - increase --mem",
+        "NF" | "NODE_FAIL" => "A compute node failed while the job was running - resubmit it",
+        "PR" | "PREEMPTED" => "Preempted by a higher-priority job - resubmit it, or use a QoS that can't be preempted",
+        "BF" | "BOOT_FAIL" => "Its nodes failed to boot - resubmit it",
+        "DL" | "DEADLINE" => "Could not finish before its --deadline",
+        "CA" | "CANCELLED" if !started => "Cancelled before it started",
+        _ => return None,
+    };
+    Some(detail.to_string())
+}
+
+/// Parse `scontrol show reservation --oneliner` output into maintenance windows, earliest first
+/// Reservations without the MAINT flag are ignored
+pub fn parse_maintenance_windows(output: &str) -> Vec<MaintenanceWindow> {
+    let mut windows: Vec<MaintenanceWindow> = output.lines()
+        .filter_map(|line| {
+            let fields: std::collections::HashMap<&str, &str> = line.split_whitespace()
+                .filter_map(|field| field.split_once('='))
+                .collect();
+
+            let is_maintenance = fields.get("Flags")
+                .is_some_and(|flags| flags.split(',').any(|flag| flag == "MAINT"));
+            if !is_maintenance {
+                return None;
+            }
+
+            Some(MaintenanceWindow {
+                name: fields.get("ReservationName")?.to_string(),
+                start: fields.get("StartTime")?.to_string(),
+                end: fields.get("EndTime")?.to_string(),
+            })
+        })
+        .collect();
+
+    // SLURM timestamps are ISO 8601, so string order is time order
+    windows.sort_by(|a, b| a.start.cmp(&b.start));
+    windows
+}
+
+/// "2026-10-20T06:00:00" -> "2026-10-20 06:00"
+fn format_reservation_time(timestamp: &str) -> String {
+    let readable = timestamp.replacen('T', " ", 1);
+    match readable.len() {
+        19 => readable[..16].to_string(),
+        _ => readable,
+    }
+}
+
+/// Explain why a pending job hasn't started, when the reason is a reservation
+/// Returns None for ordinary reasons (Priority, Resources, ...) which need no explanation
+pub fn pending_status_detail(reason: &str, windows: &[MaintenanceWindow]) -> Option<String> {
+    match classify_pending_reason(reason)? {
+        ReservationHold::Maintenance => Some(match windows.first() {
+            Some(window) => format!(
+                "Waiting for cluster maintenance ({} to {}) - the job will start after the window ends",
+                format_reservation_time(&window.start),
+                format_reservation_time(&window.end)
+            ),
+            None => "Waiting for scheduled cluster maintenance - the job will start after the window ends".to_string(),
+        }),
+        ReservationHold::Reservation => Some(
+            "Waiting for its reservation to become available - the job will start when the reservation begins".to_string()
+        ),
+    }
+}
+
+/// Where a pending job stands in the queue, from `squeue --start`
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuePosition {
+    /// Scheduler's expected start (ISO 8601, cluster local time); None while it has no estimate
+    pub estimated_start: Option<String>,
+    pub priority: Option<u64>,
+    pub reason: Option<String>,
+}
+
+/// Parse `queue_start_command` output; for array jobs the earliest task estimate wins
+/// "N/A", "Unknown" and empty fields mean the scheduler has no value yet
+pub fn parse_queue_position(output: &str) -> Option<QueuePosition> {
+    let known = |field: &str| {
+        let field = field.trim().trim_start_matches('(').trim_end_matches(')');
+        match field {
+            "" | "N/A" | "Unknown" | "None" => None,
+            _ => Some(field.to_string()),
+        }
+    };
+
+    output.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('|').collect();
+            if fields.len() < 4 {
+                return None;
+            }
+            Some(QueuePosition {
+                estimated_start: known(fields[1]),
+                priority: fields[2].trim().parse().ok(),
+                reason: known(fields[3]),
+            })
+        })
+        // SLURM timestamps are ISO 8601, so string order is time order; None sorts last
+        .min_by(|a, b| match (&a.estimated_start, &b.estimated_start) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        })
+}
+
+/// Split an array task's job ID ("1234_7", "1234_[2-49]") into the array job ID and its task spec
+/// None for plain job IDs and for job steps ("1234_7.batch")
+pub fn split_array_job_id(job_id: &str) -> Option<(&str, &str)> {
+    if job_id.contains('.') {
+        return None;
+    }
+    job_id.split_once('_')
+}
+
+/// Task IDs named by an array task spec: "7", "[2-49]", "[2-5,7%4]" (%N is the concurrency limit)
+pub fn parse_array_task_spec(spec: &str) -> Vec<u32> {
+    let spec = spec.split('%').next().unwrap_or("").trim_start_matches('[').trim_end_matches(']');
+
+    spec.split(',')
+        .flat_map(|part| match part.split_once('-') {
+            Some((start, end)) => match (start.parse::<u32>(), end.parse::<u32>()) {
+                (Ok(start), Ok(end)) if start <= end => (start..=end).collect(),
+                _ => Vec::new(),
+            },
+            None => part.parse().ok().into_iter().collect(),
+        })
+        .collect()
+}
 
-impl SlurmStatusSync {
+/// Overall status of an array job from its tasks' statuses
+/// Active while any task is queued or running; once all have ended it's Completed only if
+/// every task completed, Failed if any failed, otherwise Cancelled
+pub fn aggregate_array_status(statuses: &[JobStatus]) -> Option<JobStatus> {
+    let any = |wanted: JobStatus| statuses.contains(&wanted);
+
+    if statuses.is_empty() {
+        None
+    } else if any(JobStatus::Running) {
+        Some(JobStatus::Running)
+    } else if any(JobStatus::Pending) {
+        Some(JobStatus::Pending)
+    } else if statuses.iter().all(|status| *status == JobStatus::Completed) {
+        Some(JobStatus::Completed)
+    } else if any(JobStatus::Failed) {
+        Some(JobStatus::Failed)
+    } else {
+        Some(JobStatus::Cancelled)
+    }
+}
+
+/// Merge array task lines into one line per array job, so callers see the ID they submitted
+/// Other lines (plain jobs, job steps) pass through unchanged
+fn fold_array_tasks(lines: Vec<(String, JobStatus, Option<String>)>) -> Vec<(String, JobStatus, Option<String>)> {
+    let mut folded: Vec<(String, Vec<JobStatus>, Option<String>)> = Vec::new();
+
+    for (job_id, status, reason) in lines {
+        let Some((array_id, _)) = split_array_job_id(&job_id) else {
+            folded.push((job_id, vec![status], reason));
+            continue;
+        };
+
+        let array_id = array_id.to_string();
+        match folded.iter_mut().find(|(id, _, _)| *id == array_id) {
+            Some((_, statuses, existing_reason)) => {
+                statuses.push(status);
+                if existing_reason.is_none() {
+                    *existing_reason = reason;
+                }
+            }
+            None => folded.push((array_id, vec![status], reason)),
+        }
+    }
+
+    folded.into_iter()
+        .filter_map(|(job_id, statuses, reason)| Some((job_id, aggregate_array_status(&statuses)?, reason)))
+        .collect()
+}
+
+impl SlurmStatusSync<'static> {
+    /// Query through the global connection
     pub fn new(_username: &str) -> Self {
-        Self {}
+        Self::with_executor(get_connection_manager())
+    }
+}
+
+impl<'a> SlurmStatusSync<'a> {
+    pub fn with_executor(remote: &'a dyn RemoteExecutor) -> Self {
+        Self { remote }
     }
 
     /// Query SLURM for job statuses
-    /// Returns Vec of (job_id, Result<JobStatus>) for each queried job
+    /// Returns Vec of (job_id, Result<JobStatus>, reason) for each queried job: the reason is squeue's
+    /// pending reason for queued jobs, and for finished jobs why they failed (see terminal_state_detail)
     ///
-    /// Uses consistent job_id|status format from both squeue (active) and sacct (completed)
-    pub async fn query_job_statuses(&self, job_ids: &[String]) -> Result<Vec<(String, Result<JobStatus>)>> {
+    /// Uses consistent job_id|status format from both squeue (active) and sacct (completed);
+    /// squeue adds the pending reason as a third field
+    pub async fn query_job_statuses(&self, job_ids: &[String]) -> Result<Vec<(String, Result<JobStatus>, Option<String>)>> {
         if job_ids.is_empty() {
             return Ok(Vec::new());
         }
 
         let mut results = Vec::new();
 
-        // Query active jobs with squeue
-        let squeue_cmd = squeue_command(job_ids)?;
-        let squeue_result = retry_quick(|| {
-            let cmd = squeue_cmd.clone();
-            async move {
-                let connection_manager = get_connection_manager();
-                connection_manager.execute_command(&cmd, Some(crate::cluster::timeouts::SLURM_OPERATION)).await
-                    .map_err(|e| anyhow!("SLURM squeue failed: {}", e))
-            }
-        }).await?;
+        // Query active jobs with squeue (split into batches for very long job lists)
+        let mut squeue_output = String::new();
+        for squeue_cmd in squeue_commands(job_ids)? {
+            let squeue_result = self.run_slurm_command(&squeue_cmd, "squeue").await?;
+            squeue_output.push_str(&squeue_result.stdout);
+            squeue_output.push('\n');
+        }
 
-        // Parse squeue output (format: job_id|status per line)
-        for line in squeue_result.stdout.lines() {
-            if let Some((job_id, status)) = Self::parse_status_line(line) {
-                results.push((job_id, Ok(status)));
-            }
+        // Parse squeue output (format: job_id|status|reason per line), one result per array job
+        let squeue_lines = squeue_output.lines().filter_map(Self::parse_status_line).collect();
+        for (job_id, status, reason) in fold_array_tasks(squeue_lines) {
+            results.push((job_id, Ok(status), reason));
         }
 
         // Find jobs not in active queue (need sacct for completed jobs)
-        let found_job_ids: std::collections::HashSet<_> = results.iter().map(|(id, _)| id.as_str()).collect();
+        let found_job_ids: std::collections::HashSet<_> = results.iter().map(|(id, _, _)| id.as_str()).collect();
         let missing_jobs: Vec<String> = job_ids.iter()
             .filter(|id| !found_job_ids.contains(id.as_str()))
             .cloned()
@@ -49,45 +299,168 @@ impl SlurmStatusSync {
 
         if !missing_jobs.is_empty() {
             // Query completed jobs with sacct
-            let sacct_cmd = sacct_command(&missing_jobs)?;
-            let sacct_result = retry_quick(|| {
-                let cmd = sacct_cmd.clone();
-                async move {
-                    let connection_manager = get_connection_manager();
-                    connection_manager.execute_command(&cmd, Some(crate::cluster::timeouts::SLURM_OPERATION)).await
-                        .map_err(|e| anyhow!("SLURM sacct failed: {}", e))
-                }
-            }).await?;
+            let mut sacct_output = String::new();
+            for sacct_cmd in sacct_commands(&missing_jobs)? {
+                let sacct_result = self.run_slurm_command(&sacct_cmd, "sacct").await?;
+                sacct_output.push_str(&sacct_result.stdout);
+                sacct_output.push('\n');
+            }
 
-            // Parse sacct output (format: job_id|status per line with --parsable2)
-            for line in sacct_result.stdout.lines() {
-                if let Some((job_id, status)) = Self::parse_status_line(line) {
-                    results.push((job_id, Ok(status)));
-                }
+            // Parse sacct output (format: job_id|state|start per line with --parsable2)
+            let sacct_lines = sacct_output.lines().filter_map(Self::parse_sacct_line).collect();
+            for (job_id, status, detail) in fold_array_tasks(sacct_lines) {
+                results.push((job_id, Ok(status), detail));
             }
         }
 
         // For any jobs still not found, add error results
-        let final_found: std::collections::HashSet<String> = results.iter().map(|(id, _)| id.clone()).collect();
+        let final_found: std::collections::HashSet<String> = results.iter().map(|(id, _, _)| id.clone()).collect();
         for job_id in job_ids {
             if !final_found.contains(job_id) {
-                results.push((job_id.clone(), Err(anyhow!("Job {} not found in SLURM queue or history", job_id))));
+                results.push((job_id.clone(), Err(anyhow!("Job {} not found in SLURM queue or history", job_id)), None));
             }
         }
 
         Ok(results)
     }
 
-    /// Parse a single line of job_id|status format
-    fn parse_status_line(line: &str) -> Option<(String, JobStatus)> {
-        let (job_id, status_str) = line.split_once('|')?;
-        let status = Self::parse_status_code(status_str).ok()?;
-        Some((job_id.to_string(), status))
+    /// Parse a single line of job_id|status format, with an optional trailing |reason
+    /// A reason of "None" (squeue's value for running jobs) is treated as absent.
+    /// squeue briefly lists jobs that just ended; their raw reason ("TimeLimit", "NonZeroExitCode")
+    /// is replaced by the same explanation sacct's state gets, so only that reaches error_info
+    fn parse_status_line(line: &str) -> Option<(String, JobStatus, Option<String>)> {
+        let mut fields = line.splitn(3, '|');
+        let job_id = fields.next()?;
+        let state = fields.next()?;
+        let status = Self::parse_status_code(state).ok()?;
+        if matches!(status, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled) {
+            // squeue has no start time; only sacct can tell a job cancelled while queued
+            return Some((job_id.to_string(), status, terminal_state_detail(state, true)));
+        }
+        let reason = fields.next()
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty() && *reason != "None")
+            .map(String::from);
+        Some((job_id.to_string(), status, reason))
+    }
+
+    /// Parse a sacct line of job_id|state|start into the status and why it failed, if it did
+    /// sacct reports "CANCELLED by <uid>" and a start of "Unknown" for jobs cancelled while queued
+    fn parse_sacct_line(line: &str) -> Option<(String, JobStatus, Option<String>)> {
+        let mut fields = line.splitn(3, '|');
+        let job_id = fields.next()?;
+        let state = fields.next()?;
+        let status = Self::parse_status_code(state).ok()?;
+        let started = fields.next()
+            .map(str::trim)
+            .is_none_or(|start| !start.is_empty() && start != "Unknown" && start != "None");
+        Some((job_id.to_string(), status, terminal_state_detail(state, started)))
+    }
+
+    /// Query the status of every task of an array job, ordered by task ID
+    /// sacct lists finished tasks individually and still-queued ones as ranges ("1234_[5-9]")
+    pub async fn query_array_tasks(&self, slurm_job_id: &str) -> Result<Vec<ArrayTaskStatus>> {
+        let sacct_cmd = sacct_command(&[slurm_job_id.to_string()])?;
+
+        let result = self.run_slurm_command(&sacct_cmd, "sacct").await?;
+
+        if result.exit_code != 0 {
+            return Err(anyhow!("Failed to query array tasks of {}: {}", slurm_job_id, result.stderr));
+        }
+
+        Ok(Self::parse_array_task_statuses(&result.stdout, slurm_job_id))
+    }
+
+    /// Per-task statuses of one array job from sacct output; later lines win for repeated tasks
+    fn parse_array_task_statuses(output: &str, slurm_job_id: &str) -> Vec<ArrayTaskStatus> {
+        let mut tasks = std::collections::BTreeMap::new();
+
+        for (job_id, status, _) in output.lines().filter_map(Self::parse_sacct_line) {
+            let Some((array_id, task_spec)) = split_array_job_id(&job_id) else {
+                continue;
+            };
+            if array_id != slurm_job_id {
+                continue;
+            }
+            for task_id in parse_array_task_spec(task_spec) {
+                tasks.insert(task_id, status.clone());
+            }
+        }
+
+        tasks.into_iter().map(|(task_id, status)| ArrayTaskStatus { task_id, status }).collect()
+    }
+
+    /// Expected start and priority of a pending job
+    /// Only meaningful while the job is Pending - callers should not poll this for other jobs.
+    /// Ok(None) when the job has left the queue.
+    pub async fn query_queue_position(&self, slurm_job_id: &str) -> Result<Option<QueuePosition>> {
+        let start_cmd = queue_start_command(slurm_job_id)?;
+
+        let result = self.run_slurm_command(&start_cmd, "squeue").await?;
+
+        if result.exit_code != 0 {
+            return Err(anyhow!("Failed to query queue position of {}: {}", slurm_job_id, result.stderr));
+        }
+
+        Ok(parse_queue_position(&result.stdout))
+    }
+
+    /// SLURM's accounting record for a job, aggregated over its steps
+    /// Ok(None) when sacct has no record of the job (yet, or any more)
+    pub async fn query_job_accounting(&self, slurm_job_id: &str) -> Result<Option<crate::types::response_data::JobAccounting>> {
+        let accounting_cmd = sacct_accounting_command(slurm_job_id)?;
+
+        let result = self.run_slurm_command(&accounting_cmd, "sacct").await?;
+
+        if result.exit_code != 0 {
+            return Err(anyhow!("Failed to query accounting for {}: {}", slurm_job_id, result.stderr));
+        }
+
+        Ok(super::accounting::parse_job_accounting(&result.stdout, slurm_job_id))
+    }
+
+    /// Query the maintenance reservations currently scheduled on the cluster
+    pub async fn query_maintenance_windows(&self) -> Result<Vec<MaintenanceWindow>> {
+        let result = self.run_slurm_command(reservations_command(), "scontrol").await?;
+
+        if result.exit_code != 0 {
+            return Err(anyhow!("Failed to list reservations: {}", result.stderr));
+        }
+
+        Ok(parse_maintenance_windows(&result.stdout))
+    }
+
+    /// Query the cluster's partitions with sinfo
+    pub async fn query_partitions(&self) -> Result<Vec<crate::types::response_data::DiscoveredPartition>> {
+        let result = self.run_slurm_command(partitions_command(), "sinfo").await?;
+
+        if result.exit_code != 0 {
+            return Err(anyhow!("Failed to list partitions: {}", result.stderr));
+        }
+
+        Ok(super::discovery::parse_sinfo_partitions(&result.stdout))
+    }
+
+    /// Query QoS wall time limits with sacctmgr
+    /// Some clusters restrict sacctmgr to administrators; that comes back as an error
+    pub async fn query_qos_limits(&self) -> Result<Vec<crate::types::response_data::DiscoveredQos>> {
+        let result = self.run_slurm_command(qos_limits_command(), "sacctmgr").await?;
+
+        if result.exit_code != 0 {
+            return Err(anyhow!("Failed to list QoS: {}", result.stderr.trim()));
+        }
+
+        let qos = super::discovery::parse_sacctmgr_qos(&result.stdout);
+        if qos.is_empty() {
+            return Err(anyhow!("sacctmgr listed no QoS - access may be restricted on this cluster"));
+        }
+        Ok(qos)
     }
 
     /// Parse SLURM status code to JobStatus
+    /// Only the first word counts: sacct appends who cancelled ("CANCELLED by 12345")
     fn parse_status_code(status: &str) -> Result<JobStatus> {
-        let status = status.trim().to_uppercase();
+        let status = status.split_whitespace().next().unwrap_or_default().to_uppercase();
 
         match status.as_str() {
             // Pending states
@@ -127,29 +500,69 @@ impl SlurmStatusSync {
     }
 
     pub async fn cancel_job(&self, slurm_job_id: &str) -> Result<()> {
-        let scancel_cmd = cancel_job_command(slurm_job_id)?;
-
-        let result = retry_quick(|| {
-            let cmd = scancel_cmd.clone();
-            async move {
-                let connection_manager = get_connection_manager();
-                connection_manager.execute_command(&cmd, Some(crate::cluster::timeouts::SLURM_OPERATION)).await
-                    .map_err(|e| anyhow!("SLURM scancel failed: {}", e))
-            }
-        }).await?;
+        self.run_job_control(cancel_job_command(slurm_job_id)?, slurm_job_id, "cancel", "scancel").await
+    }
+
+    /// Keep a pending job in the queue without letting it start
+    pub async fn hold_job(&self, slurm_job_id: &str) -> Result<()> {
+        self.run_job_control(hold_job_command(slurm_job_id)?, slurm_job_id, "hold", "scontrol hold").await
+    }
+
+    /// Let a held job be scheduled again
+    pub async fn release_job(&self, slurm_job_id: &str) -> Result<()> {
+        self.run_job_control(release_job_command(slurm_job_id)?, slurm_job_id, "release", "scontrol release").await
+    }
+
+    /// Run a job control command (scancel/scontrol), failing on a non-zero exit
+    async fn run_job_control(&self, command: String, slurm_job_id: &str, action: &str, tool: &str) -> Result<()> {
+        let result = self.run_slurm_command(&command, tool).await?;
 
         if result.exit_code != 0 {
-            return Err(anyhow!("Failed to cancel job {}: {}", slurm_job_id, result.stderr));
+            return Err(anyhow!("Failed to {} job {}: {}", action, slurm_job_id, result.stderr));
         }
 
         Ok(())
     }
+
+    /// Run a SLURM command on the cluster with quick retries; `tool` names it in errors
+    async fn run_slurm_command(&self, command: &str, tool: &str) -> Result<CommandResult> {
+        retry_quick(|| async move {
+            self.remote.execute_command(command, Some(crate::cluster::timeouts::SLURM_OPERATION)).await
+                .map_err(|e| anyhow!("SLURM {} failed: {}", tool, e))
+        }).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_query_job_statuses_through_executor() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let mut remote = MockRemoteExecutor::new("testuser");
+        remote.respond_to("squeue", "1001|RUNNING|None\n1002|PENDING|Priority\n")
+            .respond_to("sacct", "1003|COMPLETED\n");
+        let job_ids: Vec<String> = ["1001", "1002", "1003", "1004"].iter().map(|id| id.to_string()).collect();
+
+        let results = SlurmStatusSync::with_executor(&remote).query_job_statuses(&job_ids).await.unwrap();
+        let by_id: std::collections::HashMap<_, _> = results.iter().map(|(id, status, reason)| (id.as_str(), (status, reason))).collect();
+
+        assert_eq!(*by_id["1001"].0.as_ref().unwrap(), JobStatus::Running);
+        assert_eq!(by_id["1001"].1, &None);
+        assert_eq!(*by_id["1002"].0.as_ref().unwrap(), JobStatus::Pending);
+        assert_eq!(by_id["1002"].1.as_deref(), Some("Priority"));
+        assert_eq!(*by_id["1003"].0.as_ref().unwrap(), JobStatus::Completed);
+        assert!(by_id["1004"].0.is_err());
+
+        // Only the jobs squeue didn't list are looked up in sacct
+        let executed = remote.executed();
+        assert_eq!(executed.len(), 2);
+        assert!(executed[0].starts_with("squeue"));
+        assert!(executed[1].starts_with("sacct") && !executed[1].contains("1001"));
+    }
+
     #[test]
     fn test_status_code_parsing() {
         // Test all documented SLURM status codes
@@ -184,26 +597,81 @@ mod tests {
         assert_eq!(SlurmStatusSync::parse_status_code("DEADLINE").unwrap(), JobStatus::Failed);
 
         // Error cases
+        assert_eq!(SlurmStatusSync::parse_status_code("CANCELLED by 123456").unwrap(), JobStatus::Cancelled);
         assert!(SlurmStatusSync::parse_status_code("UNKNOWN").is_err());
         assert!(SlurmStatusSync::parse_status_code("").is_err());
     }
 
+    #[test]
+    fn test_sacct_line_rare_terminal_states() {
+        let cases = [
+            ("101|TIMEOUT|2025-01-15T10:00:00", JobStatus::Failed, Some("Killed: reached its walltime limit - increase --time or restart from the last checkpoint")),
+            ("102|OUT_OF_MEMORY|2025-01-15T10:00:00", JobStatus::Failed, Some("Killed: out of memory - increase --mem")),
+            ("103|NODE_FAIL|2025-01-15T10:00:00", JobStatus::Failed, Some("A compute node failed while the job was running - resubmit it")),
+            ("104|PREEMPTED|2025-01-15T10:00:00", JobStatus::Failed, Some("Preempted by a higher-priority job - resubmit it, or use a QoS that can't be preempted")),
+            ("105|BOOT_FAIL|2025-01-15T10:00:00", JobStatus::Failed, Some("Its nodes failed to boot - resubmit it")),
+            ("106|DEADLINE|Unknown", JobStatus::Failed, Some("Could not finish before its --deadline")),
+            // Cancelled while still queued: sacct names who cancelled and has no start time
+            ("107|CANCELLED by 123456|Unknown", JobStatus::Cancelled, Some("Cancelled before it started")),
+            // Ordinary outcomes carry no reason
+            ("108|CANCELLED by 123456|2025-01-15T10:00:00", JobStatus::Cancelled, None),
+            ("109|FAILED|2025-01-15T10:00:00", JobStatus::Failed, None),
+            ("110|COMPLETED|2025-01-15T10:00:00", JobStatus::Completed, None),
+            // Without a start column nothing is assumed about whether the job ran
+            ("111|CANCELLED", JobStatus::Cancelled, None),
+        ];
+
+        for (line, expected_status, expected_detail) in cases {
+            let (job_id, status, detail) = SlurmStatusSync::parse_sacct_line(line).unwrap();
+            assert_eq!(job_id, line.split('|').next().unwrap());
+            assert_eq!(status, expected_status, "{}", line);
+            assert_eq!(detail.as_deref(), expected_detail, "{}", line);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_job_statuses_reports_failure_reason() {
+        use crate::ssh::test_utils::MockRemoteExecutor;
+
+        let mut remote = MockRemoteExecutor::new("testuser");
+        remote.respond_to("sacct", "2001|OUT_OF_MEMORY|2025-01-15T10:00:00\n2001.batch|OUT_OF_MEMORY|2025-01-15T10:00:00\n");
+
+        let results = SlurmStatusSync::with_executor(&remote).query_job_statuses(&["2001".to_string()]).await.unwrap();
+        let (_, status, reason) = results.iter().find(|(id, _, _)| id == "2001").unwrap();
+        assert_eq!(*status.as_ref().unwrap(), JobStatus::Failed);
+        assert_eq!(reason.as_deref(), Some("Killed: out of memory - increase --mem"));
+    }
+
     #[test]
     fn test_status_line_parsing() {
         // Test the job_id|status format parsing
         let result = SlurmStatusSync::parse_status_line("12345678|RUNNING");
         assert!(result.is_some());
-        let (job_id, status) = result.unwrap();
+        let (job_id, status, reason) = result.unwrap();
         assert_eq!(job_id, "12345678");
         assert_eq!(status, JobStatus::Running);
+        assert_eq!(reason, None);
 
         // Test completed status
         let result = SlurmStatusSync::parse_status_line("99999|COMPLETED");
         assert!(result.is_some());
-        let (job_id, status) = result.unwrap();
+        let (job_id, status, _) = result.unwrap();
         assert_eq!(job_id, "99999");
         assert_eq!(status, JobStatus::Completed);
 
+        // squeue adds the pending reason ("None" when there isn't one)
+        let (_, status, reason) = SlurmStatusSync::parse_status_line("555|PENDING|ReqNodeNotAvail, Reserved for maintenance").unwrap();
+        assert_eq!(status, JobStatus::Pending);
+        assert_eq!(reason.as_deref(), Some("ReqNodeNotAvail, Reserved for maintenance"));
+        assert_eq!(SlurmStatusSync::parse_status_line("556|RUNNING|None").unwrap().2, None);
+
+        // Recently ended jobs: squeue's raw reason is dropped in favour of the state's explanation
+        let (_, status, reason) = SlurmStatusSync::parse_status_line("557|TIMEOUT|TimeLimit").unwrap();
+        assert_eq!(status, JobStatus::Failed);
+        assert_eq!(reason, terminal_state_detail("TIMEOUT", true));
+        assert_eq!(SlurmStatusSync::parse_status_line("558|FAILED|NonZeroExitCode").unwrap().2, None);
+        assert_eq!(SlurmStatusSync::parse_status_line("559|COMPLETED|None").unwrap().2, None);
+
         // Test invalid format
         assert!(SlurmStatusSync::parse_status_line("no-pipe-here").is_none());
         assert!(SlurmStatusSync::parse_status_line("12345|INVALID_STATUS").is_none());
@@ -224,7 +692,7 @@ mod tests {
     fn test_command_format_consistency() {
         // Verify squeue uses consistent format
         let cmd = squeue_command(&["12345".to_string()]).unwrap();
-        assert!(cmd.contains("--format='%i|%T'"));
+        assert!(cmd.contains("--format='%i|%T|%r'"));
         assert!(cmd.contains("--noheader"));
 
         // Verify sacct uses consistent format
@@ -233,4 +701,167 @@ mod tests {
         assert!(cmd.contains("--parsable2"));
         assert!(cmd.contains("--noheader"));
     }
+
+    #[test]
+    fn test_is_next_to_start() {
+        assert!(is_next_to_start("Resources"));
+        assert!(is_next_to_start("(Resources)"));
+        assert!(!is_next_to_start("Priority"));
+        assert!(!is_next_to_start("ReqNodeNotAvail, Reserved for maintenance"));
+    }
+
+    #[test]
+    fn test_dependency_never_satisfied() {
+        assert!(is_dependency_never_satisfied("DependencyNeverSatisfied"));
+        assert!(is_dependency_never_satisfied("(DependencyNeverSatisfied)"));
+        // Still waiting on the dependency is normal
+        assert!(!is_dependency_never_satisfied("Dependency"));
+        assert!(!is_dependency_never_satisfied("Priority"));
+    }
+
+    #[test]
+    fn test_held_reason() {
+        assert!(is_held_reason("JobHeldUser"));
+        assert!(is_held_reason("(JobHeldUser)"));
+        assert!(is_held_reason("JobHeldAdmin"));
+        assert!(!is_held_reason("Priority"));
+        assert!(!is_held_reason("Dependency"));
+
+        assert_eq!(held_status_detail("(JobHeldUser)"), None);
+        assert!(held_status_detail("(JobHeldAdmin)").unwrap().contains("administrator"));
+    }
+
+    #[test]
+    fn test_parse_queue_position() {
+        let position = parse_queue_position("12345|2026-10-16T09:30:00|48213|(Priority)\n").unwrap();
+        assert_eq!(position, QueuePosition {
+            estimated_start: Some("2026-10-16T09:30:00".to_string()),
+            priority: Some(48213),
+            reason: Some("Priority".to_string()),
+        });
+
+        // No estimate yet
+        let position = parse_queue_position("12345|N/A|100|Resources\n").unwrap();
+        assert_eq!(position.estimated_start, None);
+        assert_eq!(position.reason.as_deref(), Some("Resources"));
+
+        // Array tasks: the earliest estimate
+        let position = parse_queue_position("777_[2-9]|N/A|90|None\n777_1|2026-10-17T01:00:00|90|Priority\n777_0|2026-10-16T23:00:00|90|Priority\n").unwrap();
+        assert_eq!(position.estimated_start.as_deref(), Some("2026-10-16T23:00:00"));
+
+        // Job already left the queue
+        assert_eq!(parse_queue_position(""), None);
+    }
+
+    #[test]
+    fn test_parse_array_task_spec() {
+        assert_eq!(split_array_job_id("1234_7"), Some(("1234", "7")));
+        assert_eq!(split_array_job_id("1234_[2-4]"), Some(("1234", "[2-4]")));
+        assert_eq!(split_array_job_id("1234_7.batch"), None);
+        assert_eq!(split_array_job_id("1234"), None);
+
+        assert_eq!(parse_array_task_spec("7"), vec![7]);
+        assert_eq!(parse_array_task_spec("[2-4]"), vec![2, 3, 4]);
+        assert_eq!(parse_array_task_spec("[2-3,7%4]"), vec![2, 3, 7]);
+        assert!(parse_array_task_spec("[garbage]").is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_array_status() {
+        use JobStatus::*;
+        assert_eq!(aggregate_array_status(&[Completed, Running, Pending]), Some(Running));
+        assert_eq!(aggregate_array_status(&[Completed, Failed, Pending]), Some(Pending));
+        assert_eq!(aggregate_array_status(&[Completed, Completed]), Some(Completed));
+        assert_eq!(aggregate_array_status(&[Completed, Failed, Cancelled]), Some(Failed));
+        assert_eq!(aggregate_array_status(&[Completed, Cancelled]), Some(Cancelled));
+        assert_eq!(aggregate_array_status(&[]), None);
+    }
+
+    #[test]
+    fn test_fold_array_tasks() {
+        let lines = vec![
+            ("1234_0".to_string(), JobStatus::Running, None),
+            ("999".to_string(), JobStatus::Pending, Some("Priority".to_string())),
+            ("1234_[1-9]".to_string(), JobStatus::Pending, Some("JobArrayTaskLimit".to_string())),
+        ];
+
+        assert_eq!(fold_array_tasks(lines), vec![
+            ("1234".to_string(), JobStatus::Running, Some("JobArrayTaskLimit".to_string())),
+            ("999".to_string(), JobStatus::Pending, Some("Priority".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_array_task_statuses() {
+        let output = "\
+1234_0|COMPLETED
+1234_0.batch|COMPLETED
+1234_1|FAILED
+1234_1.batch|FAILED
+1234_2|RUNNING
+1234_[3-4]|PENDING
+";
+
+        let tasks = SlurmStatusSync::parse_array_task_statuses(output, "1234");
+
+        let statuses: Vec<(u32, JobStatus)> = tasks.into_iter().map(|task| (task.task_id, task.status)).collect();
+        assert_eq!(statuses, vec![
+            (0, JobStatus::Completed),
+            (1, JobStatus::Failed),
+            (2, JobStatus::Running),
+            (3, JobStatus::Pending),
+            (4, JobStatus::Pending),
+        ]);
+    }
+
+    #[test]
+    fn test_classify_pending_reason() {
+        assert_eq!(classify_pending_reason("ReqNodeNotAvail, Reserved for maintenance"), Some(ReservationHold::Maintenance));
+        assert_eq!(classify_pending_reason("(ReqNodeNotAvail, Reserved for maintenance)"), Some(ReservationHold::Maintenance));
+        assert_eq!(classify_pending_reason("Reservation"), Some(ReservationHold::Reservation));
+        assert_eq!(classify_pending_reason("ResvDeleted"), Some(ReservationHold::Reservation));
+
+        // Ordinary scheduling reasons need no explanation
+        assert_eq!(classify_pending_reason("Priority"), None);
+        assert_eq!(classify_pending_reason("Resources"), None);
+        assert_eq!(classify_pending_reason("ReqNodeNotAvail, UnavailableNodes:c3cpu-a2-u1-1"), None);
+    }
+
+    #[test]
+    fn test_parse_maintenance_windows() {
+        let output = "\
+ReservationName=maint_nov StartTime=2026-11-03T06:00:00 EndTime=2026-11-03T18:00:00 Duration=12:00:00 Nodes=c3cpu-[001-100] NodeCnt=100 Flags=MAINT,IGNORE_JOBS,SPEC_NODES State=INACTIVE
+ReservationName=course_lab StartTime=2026-10-16T09:00:00 EndTime=2026-10-16T12:00:00 Duration=03:00:00 Nodes=c3cpu-001 NodeCnt=1 Flags=SPEC_NODES State=INACTIVE
+ReservationName=maint_oct StartTime=2026-10-20T06:00:00 EndTime=2026-10-21T18:00:00 Duration=1-12:00:00 Nodes=ALL NodeCnt=500 Flags=MAINT,IGNORE_JOBS,SPEC_NODES,ALL_NODES State=INACTIVE
+";
+
+        let windows = parse_maintenance_windows(output);
+
+        assert_eq!(windows, vec![
+            MaintenanceWindow { name: "maint_oct".to_string(), start: "2026-10-20T06:00:00".to_string(), end: "2026-10-21T18:00:00".to_string() },
+            MaintenanceWindow { name: "maint_nov".to_string(), start: "2026-11-03T06:00:00".to_string(), end: "2026-11-03T18:00:00".to_string() },
+        ]);
+        assert!(parse_maintenance_windows("No reservations in the system").is_empty());
+    }
+
+    #[test]
+    fn test_pending_status_detail() {
+        let windows = vec![MaintenanceWindow {
+            name: "maint_oct".to_string(),
+            start: "2026-10-20T06:00:00".to_string(),
+            end: "2026-10-21T18:00:00".to_string(),
+        }];
+
+        assert_eq!(
+            pending_status_detail("ReqNodeNotAvail, Reserved for maintenance", &windows).unwrap(),
+            "Waiting for cluster maintenance (2026-10-20 06:00 to 2026-10-21 18:00) - the job will start after the window ends"
+        );
+
+        // Window unknown (scontrol failed or nothing flagged MAINT) still explains the hold
+        let detail = pending_status_detail("ReqNodeNotAvail, Reserved for maintenance", &[]).unwrap();
+        assert!(detail.starts_with("Waiting for scheduled cluster maintenance"));
+
+        assert!(pending_status_detail("Reservation", &windows).unwrap().contains("reservation"));
+        assert_eq!(pending_status_detail("Priority", &windows), None);
+    }
 }
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use super::SSHError;
+
+/// Shared flag a transfer checks between chunks; setting it aborts the transfer
+pub type CancelToken = Arc<AtomicBool>;
+
+lazy_static::lazy_static! {
+    /// Transfers the UI can cancel, keyed by the progress key it already knows them by
+    static ref ACTIVE_TRANSFERS: Mutex<HashMap<String, CancelToken>> = Mutex::new(HashMap::new());
+}
+
+/// Keeps a transfer cancellable through `cancel(key)` until dropped
+pub struct TransferRegistration {
+    key: String,
+    token: CancelToken,
+}
+
+impl TransferRegistration {
+    pub fn token(&self) -> CancelToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for TransferRegistration {
+    fn drop(&mut self) {
+        let mut active = ACTIVE_TRANSFERS.lock().unwrap();
+        // A newer transfer may have taken over the key; leave its token alone
+        if active.get(&self.key).is_some_and(|token| Arc::ptr_eq(token, &self.token)) {
+            active.remove(&self.key);
+        }
+    }
+}
+
+/// Make a transfer cancellable under `key`, reusing the caller's token if it has one
+pub fn register(key: &str, token: Option<CancelToken>) -> TransferRegistration {
+    let token = token.unwrap_or_default();
+    ACTIVE_TRANSFERS.lock().unwrap().insert(key.to_string(), token.clone());
+    TransferRegistration { key: key.to_string(), token }
+}
+
+/// Flip the token of the active transfer under `key`; false if nothing is running under it
+pub fn cancel(key: &str) -> bool {
+    match ACTIVE_TRANSFERS.lock().unwrap().get(key) {
+        Some(token) => {
+            token.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Fail with TransferCancelled once the token has been set
+pub fn check(token: Option<&AtomicBool>, transfer: &str) -> Result<(), SSHError> {
+    if token.is_some_and(|token| token.load(Ordering::SeqCst)) {
+        return Err(SSHError::TransferCancelled(format!("{} was cancelled", transfer)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_flips_registered_token() {
+        let registration = register("cancel-test/structure.psf", None);
+
+        assert!(check(Some(&registration.token()), "structure.psf").is_ok());
+        assert!(cancel("cancel-test/structure.psf"));
+
+        let err = check(Some(&registration.token()), "structure.psf").unwrap_err();
+        assert!(matches!(err, SSHError::TransferCancelled(_)));
+    }
+
+    #[test]
+    fn test_cancel_unknown_key() {
+        assert!(!cancel("cancel-test/never-registered"));
+        assert!(check(None, "anything").is_ok());
+    }
+
+    #[test]
+    fn test_registration_ends_on_drop() {
+        let caller_token = CancelToken::default();
+        let registration = register("cancel-test/dropped", Some(caller_token.clone()));
+        assert!(Arc::ptr_eq(&registration.token(), &caller_token));
+
+        drop(registration);
+        assert!(!cancel("cancel-test/dropped"));
+        assert!(!caller_token.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_stale_registration_keeps_newer_transfer() {
+        let first = register("cancel-test/reused", None);
+        let second = register("cancel-test/reused", None);
+
+        drop(first);
+        assert!(cancel("cancel-test/reused"));
+        assert!(second.token().load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,108 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use sha2::{Digest, Sha256};
+use super::SSHError;
+
+/// Read size for local hashing; files are never held in memory whole
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// SHA-256 of a local file as lowercase hex, streamed so multi-GB trajectories are fine
+/// Hashing a large file takes seconds, so it runs on the blocking pool rather than an async worker
+pub async fn sha256_file(path: &Path) -> Result<String, SSHError> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || hash_file(&path))
+        .await
+        .map_err(|e| SSHError::FileTransferError(format!("Checksum task failed: {}", e)))?
+}
+
+fn hash_file(path: &Path) -> Result<String, SSHError> {
+    let mut file = File::open(path)
+        .map_err(|e| SSHError::FileTransferError(format!("Failed to open {} for checksum: {}", path.display(), e)))?;
+    sha256_reader(&mut file)
+        .map_err(|e| SSHError::FileTransferError(format!("Failed to read {} for checksum: {}", path.display(), e)))
+}
+
+/// SHA-256 of in-memory content as lowercase hex
+pub fn sha256_bytes(content: &[u8]) -> String {
+    to_hex(&Sha256::digest(content))
+}
+
+fn sha256_reader<R: Read>(reader: &mut R) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+fn to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Pull the hash out of `sha256sum <path>` output ("<hash>  <path>")
+pub fn parse_sha256sum_output(stdout: &str) -> Result<String, SSHError> {
+    let hash = stdout.split_whitespace().next().unwrap_or("").to_lowercase();
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(SSHError::CommandError(format!("Unexpected sha256sum output: {}", stdout.trim())));
+    }
+    Ok(hash)
+}
+
+/// Fail with ChecksumMismatch unless both sides hashed to the same value
+pub fn compare(remote_path: &str, local_hash: &str, remote_hash: &str) -> Result<(), SSHError> {
+    if local_hash.eq_ignore_ascii_case(remote_hash) {
+        return Ok(());
+    }
+    Err(SSHError::ChecksumMismatch(format!(
+        "{}: local sha256 {} but remote sha256 {}", remote_path, local_hash, remote_hash
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // sha256("abc") from FIPS 180-2
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn test_sha256_bytes_known_vector() {
+        assert_eq!(sha256_bytes(b"abc"), ABC_SHA256);
+        assert_eq!(sha256_bytes(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_file_hash_matches_in_memory_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("structure.psf");
+        // Spans several read buffers, with a partial last one
+        let content: Vec<u8> = (0..HASH_BUFFER_SIZE * 2 + 123).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        assert_eq!(sha256_file(&path).await.unwrap(), sha256_bytes(&content));
+    }
+
+    #[test]
+    fn test_parse_sha256sum_output() {
+        let output = format!("{}  /scratch/alice/job_001/input_files/structure.psf\n", ABC_SHA256.to_uppercase());
+        assert_eq!(parse_sha256sum_output(&output).unwrap(), ABC_SHA256);
+
+        assert!(parse_sha256sum_output("").is_err());
+        assert!(parse_sha256sum_output("sha256sum: structure.psf: No such file or directory").is_err());
+    }
+
+    #[test]
+    fn test_compare_reports_mismatch() {
+        assert!(compare("/remote/a.pdb", ABC_SHA256, &ABC_SHA256.to_uppercase()).is_ok());
+
+        let err = compare("/remote/a.pdb", ABC_SHA256, &sha256_bytes(b"abd")).unwrap_err();
+        assert!(matches!(err, SSHError::ChecksumMismatch(_)));
+        assert!(err.to_string().contains("/remote/a.pdb"));
+    }
+}
@@ -172,50 +172,266 @@ impl<'a> CommandExecutor<'a> {
     }
 }
 
-/// Generate a zip command for archiving output files
-/// Creates zip in /tmp/ and returns the temp file path
-pub fn zip_outputs_command(project_dir: &str, job_id: &str) -> Result<(String, String)> {
+/// Server directory for temporary files (dry-run scripts)
+pub const REMOTE_TEMP_DIR: &str = "/tmp";
+
+/// Dry-run script names are `{prefix}{job_id}{suffix}`; a dry run cut off by a dropped
+/// connection leaves its script behind for the temp sweep
+pub const DRY_RUN_SCRIPT_PREFIX: &str = "namdrunner_dryrun_";
+pub const DRY_RUN_SCRIPT_SUFFIX: &str = ".sbatch";
+
+/// Extract the job ID from a dry-run script file name, if it follows the naming pattern
+/// Returns None for anything else, including names whose job ID wouldn't sanitize
+pub fn temp_file_job_id(file_name: &str) -> Option<&str> {
+    let job_id = file_name
+        .strip_prefix(DRY_RUN_SCRIPT_PREFIX)?
+        .strip_suffix(DRY_RUN_SCRIPT_SUFFIX)?;
+
+    crate::security::input::sanitize_job_id(job_id).ok()?;
+    Some(job_id)
+}
+
+/// Generate a command that prints a remote file's SHA-256 ("<hash>  <path>")
+pub fn sha256sum_command(file_path: &str) -> String {
+    format!("sha256sum {}", crate::security::shell::escape_parameter(file_path))
+}
+
+/// Check whether a remote path names a gzip-compressed file
+pub fn is_gzip_path(path: &str) -> bool {
+    path.to_lowercase().ends_with(".gz")
+}
+
+/// Generate a command that prints a remote file as text
+/// `.gz` files are decompressed with gunzip -c. Other files are sniffed for the gzip
+/// magic bytes (1f 8b) on the server, so compressed logs without the extension still
+/// come back as text; plain files fall through to cat.
+pub fn read_text_file_command(file_path: &str) -> String {
+    use crate::security::shell;
+
+    let clean_path = shell::escape_parameter(file_path);
+
+    if is_gzip_path(file_path) {
+        return format!("gunzip -c {}", clean_path);
+    }
+
+    format!(
+        "if [ \"$(head -c 2 {path} 2>/dev/null | od -An -tx1 | tr -d ' \\n')\" = \"1f8b\" ]; then gunzip -c {path}; else cat {path}; fi",
+        path = clean_path
+    )
+}
+
+/// Generate a command that prints several small files in one round trip
+/// One line per file, in order: "OK <base64 of the content>" or "ERR <error message>",
+/// so neither the content nor a failure can break the line framing
+pub fn read_files_command(file_paths: &[String]) -> String {
     use crate::security::shell;
 
-    // Temp zip file path (use raw job_id, not escaped)
-    let temp_zip = format!("/tmp/namdrunner_outputs_{}.zip", job_id);
+    file_paths.iter()
+        .map(|path| format!(
+            "if out=$(base64 -w0 -- {path} 2>&1); then printf 'OK %s\\n' \"$out\"; else printf 'ERR %s\\n' \"$(printf %s \"$out\" | tr '\\n' ' ')\"; fi",
+            path = shell::escape_parameter(path)
+        ))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
 
-    // Escape parameters for shell command
-    let clean_project_dir = shell::escape_parameter(project_dir);
-    let clean_temp_zip = shell::escape_parameter(&temp_zip);
+/// Split `read_files_command` output into one result per file, in the order they were given
+/// A file with no line of its own (output cut short) is an error
+pub fn parse_read_files_output(output: &str, file_count: usize) -> Vec<std::result::Result<String, String>> {
+    use base64::Engine;
+
+    let mut lines = output.lines();
+    (0..file_count)
+        .map(|_| match lines.next() {
+            Some(line) => match line.split_once(' ').unwrap_or((line, "")) {
+                ("OK", encoded) => base64::engine::general_purpose::STANDARD.decode(encoded.trim())
+                    .map(|content| String::from_utf8_lossy(&content).into_owned())
+                    .map_err(|e| format!("Unreadable file content: {}", e)),
+                ("ERR", message) => Err(message.trim().to_string()),
+                _ => Err(format!("Unexpected output: {}", line)),
+            },
+            None => Err("No output for file".to_string()),
+        })
+        .collect()
+}
 
-    // Build command: cd to job dir and zip outputs subdirectory
-    let command = format!(
-        "cd {} && zip -r {} {}",
-        clean_project_dir,
-        clean_temp_zip,
-        shell::escape_parameter(super::JobDirectoryStructure::OUTPUTS)
-    );
+/// Generate a command that reports a directory's total size and its largest files
+/// First line is `du -sb` for the whole directory, followed by up to `max_files` lines
+/// of per-file `du -b` output sorted largest first. Sizes are in bytes.
+pub fn disk_usage_command(dir: &str, max_files: usize) -> String {
+    use crate::security::shell;
 
-    Ok((command, temp_zip))
+    let clean_dir = shell::escape_parameter(dir);
+    format!(
+        "du -sb {dir} && find {dir} -type f -exec du -b {{}} + | sort -rn | head -n {max}",
+        dir = clean_dir,
+        max = max_files
+    )
 }
 
-/// Generate a zip command for archiving input files
-/// Creates zip in /tmp/ and returns the temp file path
-pub fn zip_inputs_command(project_dir: &str, job_id: &str) -> Result<(String, String)> {
+/// Generate a command that reports only a directory's total size in bytes
+/// Output is the first line of `disk_usage_command`, so `parse_disk_usage` reads it
+pub fn directory_size_command(dir: &str) -> String {
     use crate::security::shell;
 
-    // Temp zip file path (use raw job_id, not escaped)
-    let temp_zip = format!("/tmp/namdrunner_inputs_{}.zip", job_id);
+    format!("du -sb {}", shell::escape_parameter(dir))
+}
+
+/// Parse output of `disk_usage_command` into (total bytes, largest files)
+/// File paths are made relative to `dir`; malformed lines are skipped
+pub fn parse_disk_usage(output: &str, dir: &str) -> Result<(u64, Vec<crate::types::response_data::FileUsage>)> {
+    let mut lines = output.lines().filter(|line| !line.trim().is_empty());
+
+    let total_line = lines.next()
+        .ok_or_else(|| anyhow::anyhow!("Empty disk usage output"))?;
+    let (total_bytes, _) = parse_du_line(total_line)
+        .ok_or_else(|| anyhow::anyhow!("Unrecognized disk usage total: {}", total_line))?;
+
+    let prefix = format!("{}/", dir.trim_end_matches('/'));
+    let largest_files = lines
+        .filter_map(parse_du_line)
+        .map(|(size_bytes, path)| crate::types::response_data::FileUsage {
+            path: path.strip_prefix(&prefix).unwrap_or(path).to_string(),
+            size_bytes,
+        })
+        .collect();
+
+    Ok((total_bytes, largest_files))
+}
 
-    // Escape parameters for shell command
-    let clean_project_dir = shell::escape_parameter(project_dir);
-    let clean_temp_zip = shell::escape_parameter(&temp_zip);
+/// Generate a command that reports a directory's total size in bytes and its file count
+/// Prints the `du -sb` line, then "files\t<count>". Both carry on past unreadable
+/// subdirectories, so `parse_directory_size_and_count` can report a partial total
+pub fn directory_size_and_count_command(dir: &str) -> String {
+    use crate::security::shell;
 
-    // Build command: cd to job dir and zip input_files subdirectory
-    let command = format!(
-        "cd {} && zip -r {} {}",
-        clean_project_dir,
-        clean_temp_zip,
-        shell::escape_parameter(super::JobDirectoryStructure::INPUT_FILES)
-    );
+    let clean_dir = shell::escape_parameter(dir);
+    format!(
+        "du -sb {dir}; printf 'files\\t%s\\n' \"$(find {dir} -type f 2>/dev/null | wc -l)\"",
+        dir = clean_dir
+    )
+}
 
-    Ok((command, temp_zip))
+/// Parse output of `directory_size_and_count_command`
+/// Permission-denied messages on stderr mark the result partial with a warning;
+/// it's only an error when `du` printed no total at all (e.g. the directory is missing)
+pub fn parse_directory_size_and_count(stdout: &str, stderr: &str, dir: &str) -> Result<crate::types::response_data::RemoteDirSize> {
+    let total_bytes = stdout.lines()
+        .find_map(parse_du_line)
+        .map(|(size, _)| size)
+        .ok_or_else(|| {
+            let reason = stderr.lines().find(|line| !line.trim().is_empty()).unwrap_or("no output from du");
+            anyhow::anyhow!("Could not measure {}: {}", dir, reason.trim())
+        })?;
+    let file_count = stdout.lines()
+        .find_map(|line| line.strip_prefix("files\t"))
+        .and_then(|count| count.trim().parse().ok())
+        .unwrap_or(0);
+
+    let unreadable = stderr.lines().filter(|line| line.contains("Permission denied")).count();
+    let warning = (unreadable > 0).then(|| format!(
+        "{} path{} under {} could not be read (permission denied); size and file count are partial",
+        unreadable, if unreadable == 1 { "" } else { "s" }, dir
+    ));
+
+    Ok(crate::types::response_data::RemoteDirSize {
+        path: dir.to_string(),
+        total_bytes,
+        file_count,
+        partial: warning.is_some(),
+        warning,
+    })
+}
+
+/// Split a `du` line ("<bytes>\t<path>") into its size and path
+fn parse_du_line(line: &str) -> Option<(u64, &str)> {
+    let (size, path) = line.split_once('\t')?;
+    Some((size.trim().parse().ok()?, path))
+}
+
+/// Generate a command that reports inode usage for the filesystem holding `dir`
+/// `-P` keeps each filesystem on one line even when the device name is long
+pub fn inode_usage_command(dir: &str) -> String {
+    use crate::security::shell;
+
+    format!("df -iP {}", shell::escape_parameter(dir))
+}
+
+/// Parse output of `inode_usage_command` into the number of free inodes
+/// Returns None when the filesystem doesn't report inode counts (fields shown as
+/// "-" or a zero total, as on some network and parallel filesystems)
+pub fn parse_free_inodes(output: &str) -> Option<u64> {
+    // Columns: Filesystem Inodes IUsed IFree IUse% Mounted-on
+    let line = output.lines().rfind(|line| !line.trim().is_empty())?;
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 6 {
+        return None;
+    }
+
+    let total: u64 = fields[1].parse().ok()?;
+    if total == 0 {
+        return None;
+    }
+    fields[3].parse().ok()
+}
+
+/// Shell prefix that sets `$d` to `dir` or its nearest existing parent, so space can be
+/// checked for a directory that is about to be created
+fn nearest_existing_dir(dir: &str) -> String {
+    use crate::security::shell;
+
+    format!(
+        "d={}; while [ ! -e \"$d\" ] && [ \"$d\" != / ]; do d=$(dirname \"$d\"); done; ",
+        shell::escape_parameter(dir)
+    )
+}
+
+/// Generate a command that reports free space on the filesystem holding `dir`
+/// `-P` keeps each filesystem on one line and `-k` fixes the block size at 1024 bytes
+pub fn disk_space_command(dir: &str) -> String {
+    format!("{}df -Pk \"$d\"", nearest_existing_dir(dir))
+}
+
+/// Parse output of `disk_space_command` into (total bytes, available bytes)
+/// Finds the capacity column ("NN%") and reads the block counts before it, so a device
+/// name wrapped onto its own line or a mount point with spaces still parses
+pub fn parse_disk_space(output: &str) -> Option<(u64, u64)> {
+    // Columns: Filesystem 1024-blocks Used Available Capacity Mounted-on
+    let fields: Vec<&str> = output.lines()
+        .filter(|line| !line.trim_start().starts_with("Filesystem"))
+        .flat_map(str::split_whitespace)
+        .collect();
+    let capacity = (3..fields.len()).find(|&i| fields[i].ends_with('%'))?;
+
+    let total: u64 = fields[capacity - 3].parse().ok()?;
+    let available: u64 = fields[capacity - 1].parse().ok()?;
+    Some((total * 1024, available * 1024))
+}
+
+/// Generate a command that reports `username`'s Lustre quota on the filesystem holding `dir`
+/// Fails (non-zero exit) where `lfs` isn't installed or the filesystem isn't Lustre
+pub fn lustre_quota_command(username: &str, dir: &str) -> String {
+    use crate::security::shell;
+
+    format!("{}lfs quota -q -u {} \"$d\"", nearest_existing_dir(dir), shell::escape_parameter(username))
+}
+
+/// Parse output of `lustre_quota_command` into the bytes left under the tightest block limit
+/// Returns None when no block quota or limit is set (both reported as 0)
+pub fn parse_lustre_quota_remaining(output: &str) -> Option<u64> {
+    // Columns: Filesystem kbytes quota limit grace files quota limit grace
+    // kbytes carries a trailing '*' once a limit is exceeded
+    let fields: Vec<&str> = output.split_whitespace().collect();
+    if fields.len() < 4 {
+        return None;
+    }
+
+    let used: u64 = fields[1].trim_end_matches('*').parse().ok()?;
+    let limit = fields[2..4].iter()
+        .filter_map(|field| field.parse::<u64>().ok())
+        .filter(|limit| *limit > 0)
+        .min()?;
+    Some(limit.saturating_sub(used) * 1024)
 }
 
 /// Generate a command to remove a temporary file
@@ -276,65 +492,78 @@ mod tests {
     }
 
     #[test]
-    fn test_zip_outputs_command_generation() {
-        let (command, temp_path) = zip_outputs_command("/projects/testuser/namdrunner_jobs/job_123", "job_123")
-            .expect("Should generate zip command");
+    fn test_temp_file_job_id() {
+        assert_eq!(temp_file_job_id("namdrunner_dryrun_job_123.sbatch"), Some("job_123"));
+        assert_eq!(temp_file_job_id("namdrunner_dryrun_my-job_1700000000.sbatch"), Some("my-job_1700000000"));
 
-        // Verify command has all required components in correct structure
-        assert!(command.contains("cd"), "Must change to job directory");
-        assert!(command.contains("&&"), "Must chain commands");
-        assert!(command.contains("zip -r"), "Must use recursive zip");
-        assert!(command.contains("outputs"), "Must zip outputs directory");
-        assert!(command.contains("/projects/testuser/namdrunner_jobs/job_123"), "Must use correct project directory");
+        // Round-trips with the path dry runs upload to
+        let path = crate::slurm::commands::dry_run_script_path("job_9").unwrap();
+        assert_eq!(temp_file_job_id(path.trim_start_matches(&format!("{}/", REMOTE_TEMP_DIR))), Some("job_9"));
 
-        // Verify temp path uses job_id without escaping (for file system)
-        assert_eq!(temp_path, "/tmp/namdrunner_outputs_job_123.zip");
-        assert!(!temp_path.contains("'"), "Temp path should not have shell escaping");
+        for name in ["namdrunner_dryrun_job_123.sh", "other_dryrun_job_123.sbatch", "namdrunner_dryrun_.sbatch",
+                     "namdrunner_dryrun_../x.sbatch", "namdrunner_outputs_job_123.zip", "job_123.sbatch"] {
+            assert_eq!(temp_file_job_id(name), None, "Should not match: {}", name);
+        }
     }
 
     #[test]
-    fn test_zip_outputs_command_escapes_special_characters() {
-        // Test that paths with spaces get properly escaped for shell safety
-        let (command, temp_path) = zip_outputs_command("/projects/user name/jobs/job_123", "job_123")
-            .expect("Should generate zip command");
-
-        // Path with spaces should be escaped (wrapped in quotes)
-        assert!(command.contains("'") || command.contains("\""), "Paths with spaces must be quoted");
-        assert!(command.contains("user name"), "Original path should be preserved in escaped form");
+    fn test_read_files_command_reads_each_path() {
+        let paths = vec!["/projects/u/namdrunner_jobs/job_1/job_info.json".to_string(), "/projects/u/namdrunner_jobs/it's/job_info.json".to_string()];
+        let command = read_files_command(&paths);
 
-        // Temp path still uses simple job_id
-        assert_eq!(temp_path, "/tmp/namdrunner_outputs_job_123.zip");
+        assert_eq!(command.matches("base64 -w0 -- ").count(), 2);
+        assert!(command.contains("'/projects/u/namdrunner_jobs/job_1/job_info.json'"), "{}", command);
+        assert!(!command.contains("it's/"), "Quotes must be escaped: {}", command);
     }
 
     #[test]
-    fn test_zip_inputs_command_generation() {
-        let (command, temp_path) = zip_inputs_command("/projects/testuser/namdrunner_jobs/job_123", "job_123")
-            .expect("Should generate zip command");
+    fn test_parse_read_files_output() {
+        // {"a":1} and an empty file, then a missing one
+        let output = "OK eyJhIjoxfQ==\nOK \nERR base64: /x/job_info.json: No such file or directory \n";
 
-        // Verify command has all required components in correct structure
-        assert!(command.contains("cd"), "Must change to job directory");
-        assert!(command.contains("&&"), "Must chain commands");
-        assert!(command.contains("zip -r"), "Must use recursive zip");
-        assert!(command.contains("input_files"), "Must zip input_files directory");
-        assert!(command.contains("/projects/testuser/namdrunner_jobs/job_123"), "Must use correct project directory");
+        let results = parse_read_files_output(output, 4);
 
-        // Verify temp path uses job_id without escaping (for file system)
-        assert_eq!(temp_path, "/tmp/namdrunner_inputs_job_123.zip");
-        assert!(!temp_path.contains("'"), "Temp path should not have shell escaping");
+        assert_eq!(results[0].as_deref(), Ok("{\"a\":1}"));
+        assert_eq!(results[1].as_deref(), Ok(""));
+        assert_eq!(results[2], Err("base64: /x/job_info.json: No such file or directory".to_string()));
+        assert!(results[3].is_err(), "Output cut short");
+        assert!(parse_read_files_output("garbage\n", 1)[0].is_err());
     }
 
     #[test]
-    fn test_zip_inputs_command_escapes_special_characters() {
-        // Test that paths with spaces get properly escaped for shell safety
-        let (command, temp_path) = zip_inputs_command("/projects/user name/jobs/job_123", "job_123")
-            .expect("Should generate zip command");
+    fn test_is_gzip_path() {
+        assert!(is_gzip_path("/projects/user/job/job_123.out.gz"));
+        assert!(is_gzip_path("/projects/user/job/job_123.ERR.GZ"));
+        assert!(!is_gzip_path("/projects/user/job/job_123.out"));
+        assert!(!is_gzip_path("/projects/user/job/gz_results.out"));
+    }
 
-        // Path with spaces should be escaped (wrapped in quotes)
-        assert!(command.contains("'") || command.contains("\""), "Paths with spaces must be quoted");
-        assert!(command.contains("user name"), "Original path should be preserved in escaped form");
+    #[test]
+    fn test_read_text_file_command_compressed() {
+        let command = read_text_file_command("/projects/user/job/job_123.out.gz");
+        assert_eq!(command, "gunzip -c '/projects/user/job/job_123.out.gz'");
+    }
+
+    #[test]
+    fn test_read_text_file_command_plain_sniffs_magic_bytes() {
+        let command = read_text_file_command("/projects/user/job/job_123.out");
+
+        // Plain names are checked for gzip magic bytes before falling back to cat
+        assert!(command.contains("head -c 2 '/projects/user/job/job_123.out'"));
+        assert!(command.contains("\"1f8b\""));
+        assert!(command.contains("then gunzip -c '/projects/user/job/job_123.out'"));
+        assert!(command.contains("else cat '/projects/user/job/job_123.out'"));
+    }
 
-        // Temp path still uses simple job_id
-        assert_eq!(temp_path, "/tmp/namdrunner_inputs_job_123.zip");
+    #[test]
+    fn test_read_text_file_command_escapes_special_characters() {
+        let command = read_text_file_command("/projects/user/it's a job.out.gz");
+        assert_eq!(command, "gunzip -c '/projects/user/it'\"'\"'s a job.out.gz'");
+    }
+
+    #[test]
+    fn test_sha256sum_command_escapes_path() {
+        assert_eq!(sha256sum_command("/projects/user/input_files/it's.psf"), "sha256sum '/projects/user/input_files/it'\"'\"'s.psf'");
     }
 
     #[test]
@@ -358,4 +587,150 @@ mod tests {
         assert!(command.contains("'") || command.contains("\""), "Paths with spaces must be quoted");
         assert!(command.contains("file with spaces"), "Original filename should be preserved in escaped form");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_disk_usage_command_generation() {
+        let command = disk_usage_command("/scratch/alpine/testuser/namdrunner_jobs/job_123", 10);
+
+        assert!(command.starts_with("du -sb '/scratch/alpine/testuser/namdrunner_jobs/job_123'"));
+        assert!(command.contains("find '/scratch/alpine/testuser/namdrunner_jobs/job_123' -type f -exec du -b {} +"));
+        assert!(command.ends_with("sort -rn | head -n 10"));
+    }
+
+    #[test]
+    fn test_parse_disk_usage() {
+        let dir = "/scratch/alpine/testuser/namdrunner_jobs/job_123";
+        let output = "5368709120\t/scratch/alpine/testuser/namdrunner_jobs/job_123\n\
+            4294967296\t/scratch/alpine/testuser/namdrunner_jobs/job_123/outputs/sim.dcd\n\
+            1048576\t/scratch/alpine/testuser/namdrunner_jobs/job_123/outputs/sim.coor\n\
+            2048\t/scratch/alpine/testuser/namdrunner_jobs/job_123/my file.log\n";
+
+        let (total, files) = parse_disk_usage(output, dir).unwrap();
+
+        assert_eq!(total, 5368709120);
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].path, "outputs/sim.dcd");
+        assert_eq!(files[0].size_bytes, 4294967296);
+        assert_eq!(files[1].path, "outputs/sim.coor");
+        assert_eq!(files[2].path, "my file.log");
+    }
+
+    #[test]
+    fn test_directory_size_and_count_command_generation() {
+        let command = directory_size_and_count_command("/projects/testuser/namdrunner_jobs/job 1/outputs");
+
+        assert!(command.starts_with("du -sb '/projects/testuser/namdrunner_jobs/job 1/outputs';"));
+        assert!(command.contains("find '/projects/testuser/namdrunner_jobs/job 1/outputs' -type f 2>/dev/null | wc -l"));
+    }
+
+    #[test]
+    fn test_parse_directory_size_and_count() {
+        let dir = "/projects/testuser/namdrunner_jobs/job_123/outputs";
+        let stdout = "5368709120\t/projects/testuser/namdrunner_jobs/job_123/outputs\nfiles\t     42\n";
+
+        let size = parse_directory_size_and_count(stdout, "", dir).unwrap();
+        assert_eq!(size.path, dir);
+        assert_eq!(size.total_bytes, 5368709120);
+        assert_eq!(size.file_count, 42);
+        assert!(!size.partial);
+        assert!(size.warning.is_none());
+    }
+
+    #[test]
+    fn test_parse_directory_size_and_count_permission_denied_is_partial() {
+        let dir = "/projects/testuser/namdrunner_jobs/job_123";
+        let stdout = "1024\t/projects/testuser/namdrunner_jobs/job_123\nfiles\t3\n";
+        let stderr = "du: cannot read directory '/projects/testuser/namdrunner_jobs/job_123/locked': Permission denied\n\
+            du: cannot read directory '/projects/testuser/namdrunner_jobs/job_123/other': Permission denied\n";
+
+        let size = parse_directory_size_and_count(stdout, stderr, dir).unwrap();
+        assert_eq!(size.total_bytes, 1024);
+        assert_eq!(size.file_count, 3);
+        assert!(size.partial);
+        assert!(size.warning.unwrap().starts_with("2 paths"));
+    }
+
+    #[test]
+    fn test_parse_directory_size_and_count_missing_directory() {
+        let stderr = "du: cannot access '/projects/testuser/namdrunner_jobs/job_123': No such file or directory\n";
+        let error = parse_directory_size_and_count("files\t0\n", stderr, "/projects/testuser/namdrunner_jobs/job_123").unwrap_err();
+        assert!(error.to_string().contains("No such file"));
+
+        assert!(parse_directory_size_and_count("", "", "/x").is_err());
+    }
+
+    #[test]
+    fn test_parse_disk_usage_empty_directory_and_bad_lines() {
+        let dir = "/scratch/alpine/testuser/namdrunner_jobs/job_123/";
+        let (total, files) = parse_disk_usage("4096\t/scratch/alpine/testuser/namdrunner_jobs/job_123\n", dir).unwrap();
+        assert_eq!(total, 4096);
+        assert!(files.is_empty());
+
+        let (_, files) = parse_disk_usage("4096\t/x\ngarbage line\nabc\t/x/y\n10\t/x/z\n", "/x").unwrap();
+        assert_eq!(files, vec![crate::types::response_data::FileUsage { path: "z".to_string(), size_bytes: 10 }]);
+
+        assert!(parse_disk_usage("", dir).is_err());
+        assert!(parse_disk_usage("du: cannot access\n", dir).is_err());
+    }
+
+    #[test]
+    fn test_inode_usage_command_generation() {
+        let command = inode_usage_command("/projects/testuser/namdrunner_jobs/job 1");
+        assert_eq!(command, "df -iP '/projects/testuser/namdrunner_jobs/job 1'");
+    }
+
+    #[test]
+    fn test_parse_free_inodes() {
+        let output = "Filesystem                          Inodes   IUsed    IFree IUse% Mounted on\n\
+            pl-nfs.rc.int.colorado.edu:/projects 5000000 4998000     2000  100% /projects\n";
+        assert_eq!(parse_free_inodes(output), Some(2000));
+
+        // Filesystems without inode accounting
+        let no_inodes = "Filesystem Inodes IUsed IFree IUse% Mounted on\nscratch - - - - /scratch/alpine\n";
+        assert_eq!(parse_free_inodes(no_inodes), None);
+        let zero_total = "Filesystem Inodes IUsed IFree IUse% Mounted on\nbeegfs 0 0 0 - /scratch\n";
+        assert_eq!(parse_free_inodes(zero_total), None);
+
+        // df missing -i or errors out
+        assert_eq!(parse_free_inodes(""), None);
+        assert_eq!(parse_free_inodes("df: invalid option -- 'i'\n"), None);
+    }
+
+    #[test]
+    fn test_disk_space_command_generation() {
+        let command = disk_space_command("/scratch/alpine/testuser/namdrunner_jobs/job 1");
+        assert!(command.starts_with("d='/scratch/alpine/testuser/namdrunner_jobs/job 1'; while [ ! -e \"$d\" ]"));
+        assert!(command.ends_with("df -Pk \"$d\""));
+
+        let quota = lustre_quota_command("testuser", "/scratch/alpine/testuser");
+        assert!(quota.ends_with("lfs quota -q -u 'testuser' \"$d\""));
+    }
+
+    #[test]
+    fn test_parse_disk_space() {
+        let output = "Filesystem         1024-blocks       Used  Available Capacity Mounted on\n\
+            pl-nfs:/projects    262144000  261120000    1024000     100% /projects\n";
+        assert_eq!(parse_disk_space(output), Some((262144000 * 1024, 1024000 * 1024)));
+
+        // Long device names wrapped onto their own line, mount points with spaces
+        let wrapped = "Filesystem 1K-blocks Used Available Use% Mounted on\n\
+            very-long-server-name.rc.int.colorado.edu:/scratch/alpine\n\
+            1000 400 600 40% /scratch/my alpine\n";
+        assert_eq!(parse_disk_space(wrapped), Some((1000 * 1024, 600 * 1024)));
+
+        assert_eq!(parse_disk_space(""), None);
+        assert_eq!(parse_disk_space("df: /nope: No such file or directory\n"), None);
+    }
+
+    #[test]
+    fn test_parse_lustre_quota_remaining() {
+        // 1000 KB used of a 5000 KB soft quota and 8000 KB hard limit
+        assert_eq!(parse_lustre_quota_remaining("/scratch/alpine 1000 5000 8000 - 12 0 0 -\n"), Some(4000 * 1024));
+        // Over quota
+        assert_eq!(parse_lustre_quota_remaining("/scratch/alpine\n 9000* 5000 8000 6d 12 0 0 -\n"), Some(0));
+        // No block limits set
+        assert_eq!(parse_lustre_quota_remaining("/scratch/alpine 1000 0 0 - 12 0 0 -\n"), None);
+        assert_eq!(parse_lustre_quota_remaining("lfs: command not found\n"), None);
+        assert_eq!(parse_lustre_quota_remaining(""), None);
+    }
+}
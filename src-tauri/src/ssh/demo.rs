@@ -0,0 +1,316 @@
+//! Demo mode: an in-memory stand-in for the cluster's filesystem
+//!
+//! With demo mode on, `ConnectionManager` logs in to a fake session and its file
+//! operations (uploads, downloads, archives, listings, reads and tails, mkdir/rm,
+//! renames and project-to-scratch mirroring) run against an in-memory
+//! `MockFileSystem` instead of SFTP, with progress events paced like a real transfer.
+//! This lets the job lifecycle's file flows be demoed and UI-tested without a cluster.
+//! Other remote commands (SLURM, du, sha256sum) are not simulated and fail with a clear error.
+
+use std::io::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use anyhow::Result;
+use super::cancellation;
+use super::commands::CommandResult;
+use super::connection::ConnectionInfo;
+use super::errors::SSHError;
+use super::memory_fs::MockFileSystem;
+use super::sftp::{ArchiveEntry, BatchUploadResult, FileTransferProgress, ProgressCallback, RemoteFileTail, SftpFileEntry, UploadTask};
+use super::zip_stream::ZipStreamWriter;
+use crate::log_info;
+
+/// Environment variable that starts the app in demo mode ("1" or "true")
+pub const DEMO_MODE_ENV: &str = "NAMDRUNNER_DEMO";
+
+/// Simulated link speed; demo transfers take about as long as they would at this rate
+const DEMO_TRANSFER_RATE: u64 = 16 * 1024 * 1024;
+
+/// Longest a single demo transfer takes, so large demo files don't stall the UI
+const MAX_DEMO_TRANSFER: Duration = Duration::from_secs(3);
+
+/// Progress is reported per chunk, matching the SFTP chunk size so events arrive at a similar cadence
+const DEMO_CHUNK_SIZE: u64 = 256 * 1024;
+
+/// Cap on progress steps per transfer; larger files report in bigger steps
+const MAX_DEMO_STEPS: u64 = 50;
+
+static DEMO_MODE: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    /// The fake session; Some while "connected" in demo mode
+    static ref DEMO_SESSION: Mutex<Option<ConnectionInfo>> = Mutex::new(None);
+    static ref DEMO_FILESYSTEM: Mutex<MockFileSystem> = Mutex::new(MockFileSystem::new());
+}
+
+/// Whether file operations run against the in-memory filesystem instead of a cluster
+pub fn is_demo_mode() -> bool {
+    DEMO_MODE.load(Ordering::SeqCst)
+}
+
+/// Turn demo mode on or off
+/// Each switch starts from an empty filesystem with no session, so demos are repeatable
+pub fn set_demo_mode(enabled: bool) {
+    if DEMO_MODE.swap(enabled, Ordering::SeqCst) != enabled {
+        *DEMO_SESSION.lock().unwrap() = None;
+        *DEMO_FILESYSTEM.lock().unwrap() = MockFileSystem::new();
+        log_info!(category: "Demo", message: if enabled { "Demo mode enabled" } else { "Demo mode disabled" });
+    }
+}
+
+/// Enable demo mode when NAMDRUNNER_DEMO is set to "1" or "true"
+pub fn enable_from_env() {
+    let requested = std::env::var(DEMO_MODE_ENV)
+        .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true"));
+    if requested {
+        set_demo_mode(true);
+    }
+}
+
+fn with_filesystem<T>(f: impl FnOnce(&mut MockFileSystem) -> T) -> T {
+    f(&mut DEMO_FILESYSTEM.lock().unwrap())
+}
+
+/// Log in to the fake session; any credentials are accepted
+pub fn connect(host: String, port: u16, username: String) -> ConnectionInfo {
+    let info = ConnectionInfo {
+        host,
+        port,
+        username,
+        connected: true,
+        connected_at: chrono::Utc::now().to_rfc3339(),
+    };
+    log_info!(category: "Demo", message: "Connected to demo session", details: "{}@{}", info.username, info.host);
+    *DEMO_SESSION.lock().unwrap() = Some(info.clone());
+    info
+}
+
+pub fn disconnect() {
+    *DEMO_SESSION.lock().unwrap() = None;
+}
+
+pub fn session_info() -> Option<ConnectionInfo> {
+    DEMO_SESSION.lock().unwrap().clone()
+}
+
+/// Same error a real manager gives when nothing is connected
+fn require_session() -> Result<ConnectionInfo> {
+    session_info().ok_or_else(|| SSHError::SessionError("Please connect to the cluster first".to_string()).into())
+}
+
+/// Remote commands have no demo equivalent
+pub fn command_unavailable(command: &str) -> anyhow::Error {
+    let program = command.split_whitespace().next().unwrap_or(command);
+    SSHError::CommandError(format!("'{}' is not available in demo mode", program)).into()
+}
+
+/// Pace a transfer of `total` bytes, reporting cumulative bytes after each step
+/// Cancellation is checked between steps, as real transfers check it between chunks
+async fn simulate_transfer(total: u64, transfer: &str, cancel: Option<&AtomicBool>, progress: impl Fn(u64)) -> Result<(), SSHError> {
+    let steps = total.div_ceil(DEMO_CHUNK_SIZE).clamp(1, MAX_DEMO_STEPS);
+    let duration = Duration::from_secs_f64(total as f64 / DEMO_TRANSFER_RATE as f64).min(MAX_DEMO_TRANSFER);
+    let pause = duration / steps as u32;
+
+    for step in 1..=steps {
+        cancellation::check(cancel, transfer)?;
+        tokio::time::sleep(pause).await;
+        progress(total * step / steps);
+    }
+    Ok(())
+}
+
+fn transfer_result(bytes: u64, elapsed: Duration, file_name: Option<String>) -> FileTransferProgress {
+    let seconds = elapsed.as_secs_f64();
+    FileTransferProgress {
+        bytes_transferred: bytes,
+        total_bytes: bytes,
+        percentage: 100.0,
+        transfer_rate: if seconds > 0.0 { bytes as f64 / seconds } else { 0.0 },
+        file_name,
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+pub async fn upload_bytes(remote_path: &str, content: &[u8]) -> Result<FileTransferProgress> {
+    require_session()?;
+    with_filesystem(|fs| fs.write_file(remote_path, content.to_vec()))?;
+    Ok(transfer_result(content.len() as u64, Duration::ZERO, None))
+}
+
+/// Upload a local file into the demo filesystem; nothing is stored if cancelled part way
+pub async fn upload_file(local_path: &str, remote_path: &str, cancel: Option<&AtomicBool>, progress: Option<ProgressCallback>) -> Result<FileTransferProgress> {
+    require_session()?;
+    let content = std::fs::read(local_path)
+        .map_err(|e| SSHError::FileTransferError(format!("Failed to read local file {}: {}", local_path, e)))?;
+    let total = content.len() as u64;
+
+    // ProgressCallback is Send but not Sync; the mutex lets the transfer future stay Send
+    let progress = Mutex::new(progress);
+    let started = std::time::Instant::now();
+    simulate_transfer(total, &format!("Upload of {}", file_name(local_path)), cancel, |bytes| {
+        if let Some(progress) = progress.lock().unwrap().as_ref() {
+            progress(bytes, total);
+        }
+    }).await?;
+    with_filesystem(|fs| fs.write_file(remote_path, content))?;
+
+    log_info!(category: "Demo", message: "Uploaded file", details: "{} -> {} ({} bytes)", local_path, remote_path, total);
+    Ok(transfer_result(total, started.elapsed(), None))
+}
+
+/// Upload several files one after another; `progress_for` builds each file's progress callback
+pub async fn upload_files(files: &[UploadTask], progress_for: impl Fn(&UploadTask) -> Option<ProgressCallback>) -> Result<BatchUploadResult> {
+    let started = std::time::Instant::now();
+    let mut results = Vec::with_capacity(files.len());
+    for file in files {
+        let local_path = file.local_path.to_string_lossy();
+        let mut result = upload_file(&local_path, &file.remote_path, None, progress_for(file)).await?;
+        result.file_name = file.progress_key.clone();
+        results.push(result);
+    }
+
+    let total_bytes = results.iter().map(|result| result.bytes_transferred).sum();
+    Ok(BatchUploadResult {
+        files: results,
+        total: transfer_result(total_bytes, started.elapsed(), None),
+    })
+}
+
+/// Download a demo file to the local disk, written only once the simulated transfer completes
+pub async fn download_file(remote_path: &str, local_path: &str, cancel: Option<&AtomicBool>) -> Result<FileTransferProgress> {
+    require_session()?;
+    let content = with_filesystem(|fs| fs.read_file(remote_path))?;
+    let total = content.len() as u64;
+
+    let started = std::time::Instant::now();
+    simulate_transfer(total, &format!("Download of {}", file_name(remote_path)), cancel, |_| {}).await?;
+    std::fs::write(local_path, &content)
+        .map_err(|e| SSHError::FileTransferError(format!("Failed to write local file {}: {}", local_path, e)))?;
+
+    log_info!(category: "Demo", message: "Downloaded file", details: "{} -> {} ({} bytes)", remote_path, local_path, total);
+    Ok(transfer_result(total, started.elapsed(), None))
+}
+
+/// Stream demo files into a local ZIP archive with the same progress reporting as `download_into_zip`
+pub async fn download_as_zip(
+    entries: &[ArchiveEntry],
+    local_path: &str,
+    compression_level: u8,
+    cancel: Option<&AtomicBool>,
+    progress_callback: &(dyn Fn(usize, u64) + Sync),
+) -> Result<u64> {
+    require_session()?;
+    let file = std::fs::File::create(local_path)
+        .map_err(|e| SSHError::FileTransferError(format!("Failed to create local file: {}", e)))?;
+    let mut zip = ZipStreamWriter::new(std::io::BufWriter::new(file), compression_level);
+    let archive_error = |e: std::io::Error| SSHError::FileTransferError(format!("Failed to write archive: {}", e));
+
+    let mut bytes_done = 0;
+    for (index, entry) in entries.iter().enumerate() {
+        let content = with_filesystem(|fs| fs.read_file(&entry.remote_path))?;
+        let size = content.len() as u64;
+        simulate_transfer(size, &format!("Archive download of {}", entry.archive_name), cancel, |bytes| {
+            progress_callback(index, bytes_done + bytes);
+        }).await?;
+
+        zip.start_entry(&entry.archive_name, entry.modified_time).map_err(archive_error)?;
+        zip.write_all(&content).map_err(archive_error)?;
+        bytes_done += size;
+        progress_callback(index + 1, bytes_done);
+    }
+
+    zip.finish().map_err(archive_error)?;
+    Ok(bytes_done)
+}
+
+/// Directory entries, directories first then files, each sorted by name
+pub fn list_files(remote_path: &str, include_directories: bool) -> Result<Vec<SftpFileEntry>> {
+    require_session()?;
+    let directory = match remote_path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    };
+    let mut entries = with_filesystem(|fs| fs.list_directory(directory))?;
+    if !include_directories {
+        entries.retain(|entry| !entry.is_directory);
+    }
+    entries.sort_by(|a, b| b.is_directory.cmp(&a.is_directory).then_with(|| a.name.cmp(&b.name)));
+    Ok(entries)
+}
+
+/// Whole file as text; invalid UTF-8 is replaced rather than failing the read
+pub fn read_file(remote_path: &str) -> Result<String> {
+    require_session()?;
+    let content = with_filesystem(|fs| fs.read_file(remote_path))?;
+    Ok(String::from_utf8_lossy(&content).into_owned())
+}
+
+/// Same offsets and limits as `SFTPOperations::tail_file`, read from the demo file
+pub fn tail_file(remote_path: &str, from_offset: u64, max_bytes: u64) -> Result<RemoteFileTail> {
+    require_session()?;
+    let content = with_filesystem(|fs| fs.read_file(remote_path))?;
+    let file_size = content.len() as u64;
+    super::sftp::read_tail(std::io::Cursor::new(content), file_size, from_offset, max_bytes)
+}
+
+pub fn rename(old_path: &str, new_path: &str) -> Result<()> {
+    require_session()?;
+    with_filesystem(|fs| fs.rename(old_path, new_path))?;
+    log_info!(category: "Demo", message: "Renamed remote path", details: "{} -> {}", old_path, new_path);
+    Ok(())
+}
+
+/// Copy a directory the way `rsync -a` does: "source/" copies its contents into
+/// `destination`, "source" copies the directory itself into it
+pub fn mirror_directory(source: &str, destination: &str) -> Result<CommandResult> {
+    require_session()?;
+    let target = if source.ends_with('/') {
+        destination.to_string()
+    } else {
+        format!("{}/{}", destination.trim_end_matches('/'), file_name(source))
+    };
+    with_filesystem(|fs| fs.copy_tree(source, &target))?;
+    log_info!(category: "Demo", message: "Mirrored directory", details: "{} -> {}", source, target);
+    Ok(command_success())
+}
+
+pub fn file_exists(remote_path: &str) -> Result<bool> {
+    require_session()?;
+    Ok(with_filesystem(|fs| fs.exists(remote_path)))
+}
+
+pub fn create_directory(remote_path: &str) -> Result<CommandResult> {
+    require_session()?;
+    with_filesystem(|fs| fs.create_dir_all(remote_path))?;
+    Ok(command_success())
+}
+
+pub fn delete_directory(remote_path: &str) -> Result<CommandResult> {
+    require_session()?;
+    with_filesystem(|fs| fs.remove_all(remote_path));
+    Ok(command_success())
+}
+
+fn command_success() -> CommandResult {
+    CommandResult { stdout: String::new(), stderr: String::new(), exit_code: 0, duration_ms: 0, timed_out: false }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_simulated_transfer_reports_progress_and_honours_cancel() {
+        let reported = Mutex::new(Vec::new());
+        simulate_transfer(3 * DEMO_CHUNK_SIZE, "Upload of sim.dcd", None, |bytes| reported.lock().unwrap().push(bytes)).await.unwrap();
+        assert_eq!(*reported.lock().unwrap(), vec![DEMO_CHUNK_SIZE, 2 * DEMO_CHUNK_SIZE, 3 * DEMO_CHUNK_SIZE]);
+
+        let cancel = AtomicBool::new(true);
+        let err = simulate_transfer(DEMO_CHUNK_SIZE, "Upload of sim.dcd", Some(&cancel), |_| {}).await.unwrap_err();
+        assert!(matches!(err, SSHError::TransferCancelled(_)));
+    }
+}
@@ -0,0 +1,319 @@
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use serde::Serialize;
+use ssh2::Session;
+use crate::types::ConnectParams;
+use super::{ConnectionConfig, JumpHostConfig};
+use super::connection::authenticate;
+use super::{host_keys, tunnel};
+
+/// Which hop of the connection path a diagnostic step tested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HopRole {
+    /// Bastion the destination is reached through (ProxyJump)
+    JumpHost,
+    /// The cluster login node
+    Destination,
+}
+
+/// Stages tested for each hop, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticStage {
+    Resolve,
+    Connect,
+    Authenticate,
+}
+
+impl DiagnosticStage {
+    pub const ALL: [DiagnosticStage; 3] = [Self::Resolve, Self::Connect, Self::Authenticate];
+}
+
+/// One hop of the connection path
+#[derive(Debug, Clone, PartialEq)]
+pub struct HopTarget {
+    pub role: HopRole,
+    pub host: String,
+    pub port: u16,
+}
+
+/// Outcome of a single stage against a single hop
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticStep {
+    pub hop: HopRole,
+    pub host: String,
+    pub stage: DiagnosticStage,
+    pub success: bool,
+    pub message: String,
+}
+
+/// Result of a full connectivity diagnostic
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionDiagnosis {
+    pub steps: Vec<DiagnosticStep>,
+    /// Hop and stage that failed (None when every hop succeeded)
+    pub failed_hop: Option<HopRole>,
+    pub failed_stage: Option<DiagnosticStage>,
+    pub summary: String,
+}
+
+/// Hops to test, nearest first
+/// A configured jump host goes in front of the destination so it is checked first
+pub fn diagnostic_hops(host: &str, port: u16, jump_host: Option<&JumpHostConfig>) -> Vec<HopTarget> {
+    let jump = jump_host.map(|jump| HopTarget { role: HopRole::JumpHost, host: jump.host.clone(), port: jump.port });
+    let destination = HopTarget { role: HopRole::Destination, host: host.to_string(), port };
+    jump.into_iter().chain(std::iter::once(destination)).collect()
+}
+
+/// Run every stage against each hop in order, stopping at the first failure
+/// so the report names the hop that broke rather than everything after it
+pub fn run_diagnostic_sequence<F>(hops: &[HopTarget], mut probe: F) -> ConnectionDiagnosis
+where
+    F: FnMut(&HopTarget, DiagnosticStage) -> Result<String, String>,
+{
+    let mut steps = Vec::new();
+
+    for hop in hops {
+        for stage in DiagnosticStage::ALL {
+            let result = probe(hop, stage);
+            let success = result.is_ok();
+            let message = result.unwrap_or_else(|e| e);
+            steps.push(DiagnosticStep { hop: hop.role, host: hop.host.clone(), stage, success, message: message.clone() });
+
+            if !success {
+                return ConnectionDiagnosis {
+                    steps,
+                    failed_hop: Some(hop.role),
+                    failed_stage: Some(stage),
+                    summary: failure_summary(hop, stage, &message),
+                };
+            }
+        }
+    }
+
+    ConnectionDiagnosis {
+        steps,
+        failed_hop: None,
+        failed_stage: None,
+        summary: "All connection checks passed".to_string(),
+    }
+}
+
+fn failure_summary(hop: &HopTarget, stage: DiagnosticStage, message: &str) -> String {
+    let hop_name = match hop.role {
+        HopRole::JumpHost => "jump host",
+        HopRole::Destination => "cluster",
+    };
+    let stage_name = match stage {
+        DiagnosticStage::Resolve => "could not be resolved",
+        DiagnosticStage::Connect => "could not be reached",
+        DiagnosticStage::Authenticate => "rejected authentication",
+    };
+    format!("The {} ({}) {}: {}", hop_name, hop.host, stage_name, message)
+}
+
+/// Probe against the real network, carrying state from one stage to the next
+/// Blocking - run inside spawn_blocking
+pub struct NetworkProbe {
+    params: ConnectParams,
+    timeout: Duration,
+    keepalive_interval: u32,
+    addresses: Vec<SocketAddr>,
+    stream: Option<TcpStream>,
+    /// Authenticated jump host session, kept to tunnel to the destination
+    jump_session: Option<(Session, TcpStream)>,
+}
+
+impl NetworkProbe {
+    pub fn new(params: ConnectParams, config: &ConnectionConfig) -> Self {
+        Self {
+            params,
+            timeout: Duration::from_secs(config.timeout),
+            keepalive_interval: config.keepalive_interval,
+            addresses: Vec::new(),
+            stream: None,
+            jump_session: None,
+        }
+    }
+
+    /// Behind a jump host the destination is resolved and reached by the bastion, not locally
+    fn via_jump_host(&self, hop: &HopTarget) -> bool {
+        hop.role == HopRole::Destination && self.params.jump_host.is_some()
+    }
+
+    pub fn probe(&mut self, hop: &HopTarget, stage: DiagnosticStage) -> Result<String, String> {
+        match stage {
+            DiagnosticStage::Resolve if self.via_jump_host(hop) => {
+                Ok("Resolved by the jump host when the tunnel opens".to_string())
+            }
+            DiagnosticStage::Resolve => {
+                self.addresses = (hop.host.as_str(), hop.port).to_socket_addrs()
+                    .map_err(|e| format!("DNS lookup failed: {}", e))?
+                    .collect();
+                if self.addresses.is_empty() {
+                    return Err("No addresses found".to_string());
+                }
+                Ok(format!("Resolved to {} address(es)", self.addresses.len()))
+            }
+            DiagnosticStage::Connect if self.via_jump_host(hop) => {
+                let Some((jump_session, jump_socket)) = self.jump_session.take() else {
+                    return Ok("Not tested - the jump host login uses keyboard-interactive prompts".to_string());
+                };
+                let channel = jump_session.channel_direct_tcpip(&hop.host, hop.port, None)
+                    .map_err(|e| format!("Jump host could not open a tunnel to {}:{}: {}", hop.host, hop.port, e))?;
+                let stream = tunnel::spawn_forwarder(jump_session, channel, jump_socket, self.keepalive_interval)
+                    .map_err(|e| format!("Failed to start tunnel: {}", e))?;
+                self.stream = Some(stream);
+                Ok(format!("Tunnel opened to {}:{} through the jump host", hop.host, hop.port))
+            }
+            DiagnosticStage::Connect => {
+                let mut last_error = "No addresses to connect to".to_string();
+                for address in &self.addresses {
+                    match TcpStream::connect_timeout(address, self.timeout) {
+                        Ok(stream) => {
+                            self.stream = Some(stream);
+                            return Ok(format!("Connected to {}", address));
+                        }
+                        Err(e) => last_error = format!("{}: {}", address, e),
+                    }
+                }
+                Err(last_error)
+            }
+            DiagnosticStage::Authenticate => {
+                let Some(stream) = self.stream.take() else {
+                    if self.via_jump_host(hop) {
+                        return Ok("Not tested - the cluster was not reached".to_string());
+                    }
+                    return Err("No open connection".to_string());
+                };
+                let username = match (hop.role, &self.params.jump_host) {
+                    (HopRole::JumpHost, Some(jump)) => jump.username.clone().unwrap_or_else(|| self.params.username.clone()),
+                    _ => self.params.username.clone(),
+                };
+
+                // A jump host's socket is kept so the tunnel's forwarder can wait on it
+                let jump_socket = match hop.role {
+                    HopRole::JumpHost => Some(stream.try_clone().map_err(|e| format!("Failed to prepare tunnel socket: {}", e))?),
+                    HopRole::Destination => None,
+                };
+                let session = self.handshake(hop, stream)?;
+                let Some(credentials) = self.params.credentials() else {
+                    // Don't trigger an MFA push just to diagnose - the handshake shows the server is answering
+                    let _ = session.disconnect(None, "Diagnostic complete", None);
+                    return Ok("SSH handshake succeeded; keyboard-interactive login is not attempted by the diagnostic".to_string());
+                };
+                authenticate(&session, &username, credentials).map_err(|e| e.to_string())?;
+
+                match jump_socket {
+                    Some(jump_socket) => self.jump_session = Some((session, jump_socket)),
+                    None => {
+                        let _ = session.disconnect(None, "Diagnostic complete", None);
+                    }
+                }
+                Ok(format!("Authenticated as {}", username))
+            }
+        }
+    }
+
+    /// Handshake and check the pinned host key, the same as a real connect, before any credentials are sent
+    fn handshake(&self, hop: &HopTarget, stream: TcpStream) -> Result<Session, String> {
+        let mut session = Session::new().map_err(|e| format!("Failed to create SSH session: {}", e))?;
+        session.set_tcp_stream(stream);
+        session.set_timeout(self.timeout.as_millis() as u32);
+        session.handshake().map_err(|e| format!("SSH handshake failed: {}", e))?;
+        let presented = host_keys::presented_key(&session).map_err(|e| e.to_string())?;
+        host_keys::HostKeyStore::default_location()
+            .and_then(|store| store.verify(&hop.host, hop.port, &presented))
+            .map_err(|e| e.to_string())?;
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hops() -> Vec<HopTarget> {
+        vec![
+            HopTarget { role: HopRole::JumpHost, host: "bastion.example.edu".to_string(), port: 22 },
+            HopTarget { role: HopRole::Destination, host: "login.example.edu".to_string(), port: 22 },
+        ]
+    }
+
+    #[test]
+    fn test_diagnostic_hops_direct_connection() {
+        assert_eq!(diagnostic_hops("login.example.edu", 22, None), vec![
+            HopTarget { role: HopRole::Destination, host: "login.example.edu".to_string(), port: 22 },
+        ]);
+    }
+
+    #[test]
+    fn test_diagnostic_hops_through_jump_host() {
+        let jump = JumpHostConfig { host: "bastion.example.edu".to_string(), port: 2222, username: None };
+        assert_eq!(diagnostic_hops("login.example.edu", 22, Some(&jump)), vec![
+            HopTarget { role: HopRole::JumpHost, host: "bastion.example.edu".to_string(), port: 2222 },
+            HopTarget { role: HopRole::Destination, host: "login.example.edu".to_string(), port: 22 },
+        ]);
+    }
+
+    #[test]
+    fn test_sequence_tests_jump_host_before_destination() {
+        let mut calls = Vec::new();
+        let diagnosis = run_diagnostic_sequence(&hops(), |hop, stage| {
+            calls.push((hop.role, stage));
+            Ok("ok".to_string())
+        });
+
+        assert_eq!(calls, vec![
+            (HopRole::JumpHost, DiagnosticStage::Resolve),
+            (HopRole::JumpHost, DiagnosticStage::Connect),
+            (HopRole::JumpHost, DiagnosticStage::Authenticate),
+            (HopRole::Destination, DiagnosticStage::Resolve),
+            (HopRole::Destination, DiagnosticStage::Connect),
+            (HopRole::Destination, DiagnosticStage::Authenticate),
+        ]);
+        assert!(diagnosis.steps.iter().all(|step| step.success));
+        assert_eq!(diagnosis.failed_hop, None);
+    }
+
+    #[test]
+    fn test_jump_host_auth_failure_stops_before_destination() {
+        let diagnosis = run_diagnostic_sequence(&hops(), |hop, stage| match (hop.role, stage) {
+            (HopRole::JumpHost, DiagnosticStage::Authenticate) => Err("Authentication failed for user alice".to_string()),
+            _ => Ok("ok".to_string()),
+        });
+
+        assert_eq!(diagnosis.failed_hop, Some(HopRole::JumpHost));
+        assert_eq!(diagnosis.failed_stage, Some(DiagnosticStage::Authenticate));
+        assert_eq!(diagnosis.steps.len(), 3);
+        assert!(diagnosis.steps.iter().all(|step| step.hop == HopRole::JumpHost));
+        assert!(diagnosis.summary.contains("jump host (bastion.example.edu) rejected authentication"));
+    }
+
+    #[test]
+    fn test_destination_failure_reported_distinctly() {
+        let diagnosis = run_diagnostic_sequence(&hops(), |hop, stage| match (hop.role, stage) {
+            (HopRole::Destination, DiagnosticStage::Authenticate) => Err("Authentication failed for user alice".to_string()),
+            _ => Ok("ok".to_string()),
+        });
+
+        assert_eq!(diagnosis.failed_hop, Some(HopRole::Destination));
+        assert_eq!(diagnosis.failed_stage, Some(DiagnosticStage::Authenticate));
+        assert_eq!(diagnosis.steps.len(), 6);
+        assert!(diagnosis.summary.contains("cluster (login.example.edu) rejected authentication"));
+        assert!(!diagnosis.summary.contains("jump host"));
+    }
+
+    #[test]
+    fn test_resolve_failure_skips_later_stages() {
+        let diagnosis = run_diagnostic_sequence(&hops(), |hop, stage| match (hop.role, stage) {
+            (HopRole::JumpHost, DiagnosticStage::Resolve) => Err("DNS lookup failed".to_string()),
+            _ => Ok("ok".to_string()),
+        });
+
+        assert_eq!(diagnosis.steps.len(), 1);
+        assert!(!diagnosis.steps[0].success);
+        assert!(diagnosis.summary.contains("could not be resolved"));
+    }
+}
@@ -32,6 +32,13 @@ impl JobDirectoryStructure {
         format!("{}/{}/{}/{}", SCRATCH_BASE, username, JOB_BASE_DIRECTORY, job_id)
     }
 
+    /// Build the user's top-level project directory
+    ///
+    /// Returns: `/projects/{username}`
+    pub fn project_user_dir(username: &str) -> String {
+        format!("{}/{}", PROJECT_BASE, username)
+    }
+
     /// Build base project path for user (parent of all jobs)
     ///
     /// Returns: `/projects/{username}/namdrunner_jobs`
@@ -66,6 +73,29 @@ impl JobDirectoryStructure {
     /// Subdirectory for NAMD output files (coordinates, velocities, trajectories)
     pub const OUTPUTS: &'static str = "outputs";
 
+    /// Rendered NAMD configuration file in the job root
+    pub const NAMD_CONFIG: &'static str = "config.namd";
+
+    /// NAMD stdout, redirected by the batch script into the directory NAMD runs in
+    pub const NAMD_OUTPUT_LOG: &'static str = "namd_output.log";
+
+    /// SLURM batch script in the job root
+    pub const SLURM_SCRIPT: &'static str = "job.sbatch";
+
+    /// Per-task working directory of an array job, also its subdirectory under outputs/
+    ///
+    /// Returns: "task_{task_id}"
+    pub fn array_task_dir(task_id: &str) -> String {
+        format!("task_{}", task_id)
+    }
+
+    /// Rendered NAMD config for one array task, in the job root
+    ///
+    /// Returns: "config_{task_id}.namd"
+    pub fn array_task_config(task_id: &str) -> String {
+        format!("config_{}.namd", task_id)
+    }
+
     /// Get all subdirectories that should be created for a job
     pub fn subdirectories() -> Vec<&'static str> {
         vec![Self::INPUT_FILES, Self::OUTPUTS]
@@ -22,6 +22,14 @@ pub enum SSHError {
     ConfigurationError(String),
     /// Session errors
     SessionError(String),
+    /// Server presented a different host key than the one pinned for it
+    HostKeyMismatch(String),
+    /// Transferred file's hash differs between the local and remote copies
+    ChecksumMismatch(String),
+    /// Transfer stopped at the user's request
+    TransferCancelled(String),
+    /// Target filesystem or quota can't hold what is about to be written
+    InsufficientSpace(String),
     /// Unknown errors
     UnknownError(String),
 }
@@ -38,6 +46,10 @@ impl fmt::Display for SSHError {
             SSHError::PermissionError(msg) => write!(f, "Permission denied: {}", msg),
             SSHError::ConfigurationError(msg) => write!(f, "Configuration error: {}", msg),
             SSHError::SessionError(msg) => write!(f, "Session error: {}", msg),
+            SSHError::HostKeyMismatch(msg) => write!(f, "Host key verification failed: {}", msg),
+            SSHError::ChecksumMismatch(msg) => write!(f, "Checksum mismatch: {}", msg),
+            SSHError::TransferCancelled(msg) => write!(f, "Transfer cancelled: {}", msg),
+            SSHError::InsufficientSpace(msg) => write!(f, "Insufficient disk space: {}", msg),
             SSHError::UnknownError(msg) => write!(f, "Unknown error: {}", msg),
         }
     }
@@ -45,6 +57,132 @@ impl fmt::Display for SSHError {
 
 impl Error for SSHError {}
 
+impl SSHError {
+    /// Whether retrying the same operation could succeed
+    /// None for variants that wrap mixed causes (command, file transfer, unknown) -
+    /// callers should fall back to inspecting the message for those
+    pub fn is_transient(&self) -> Option<bool> {
+        match self {
+            SSHError::NetworkError(_) |
+            SSHError::HandshakeError(_) |
+            SSHError::TimeoutError(_) |
+            SSHError::ChecksumMismatch(_) => Some(true),
+            SSHError::AuthenticationError(_) |
+            SSHError::PermissionError(_) |
+            SSHError::ConfigurationError(_) |
+            SSHError::SessionError(_) |
+            SSHError::HostKeyMismatch(_) |
+            SSHError::TransferCancelled(_) |
+            SSHError::InsufficientSpace(_) => Some(false),
+            SSHError::CommandError(_) |
+            SSHError::FileTransferError(_) |
+            SSHError::UnknownError(_) => None,
+        }
+    }
+}
+
+/// Whether an I/O error kind is worth retrying; None for kinds that don't say either way
+pub fn io_error_is_transient(kind: std::io::ErrorKind) -> Option<bool> {
+    use std::io::ErrorKind;
+    match kind {
+        ErrorKind::TimedOut |
+        ErrorKind::ConnectionReset |
+        ErrorKind::ConnectionAborted |
+        ErrorKind::ConnectionRefused |
+        ErrorKind::NotConnected |
+        ErrorKind::BrokenPipe |
+        ErrorKind::UnexpectedEof |
+        ErrorKind::HostUnreachable |
+        ErrorKind::NetworkUnreachable |
+        ErrorKind::NetworkDown |
+        ErrorKind::Interrupted |
+        ErrorKind::WouldBlock => Some(true),
+        ErrorKind::PermissionDenied |
+        ErrorKind::NotFound |
+        ErrorKind::InvalidInput => Some(false),
+        _ => None,
+    }
+}
+
+/// libssh2 session error codes for a dropped or stalled socket
+/// (SOCKET_SEND, TIMEOUT, SOCKET_DISCONNECT, SOCKET_TIMEOUT, EAGAIN, SOCKET_RECV)
+const TRANSIENT_LIBSSH2_CODES: [i32; 6] = [-7, -9, -13, -30, -37, -43];
+
+/// Whether an ssh2 error is worth retrying; None when its code doesn't say
+pub fn ssh2_error_is_transient(error: &ssh2::Error) -> Option<bool> {
+    match error.code() {
+        ssh2::ErrorCode::Session(code) if TRANSIENT_LIBSSH2_CODES.contains(&code) => Some(true),
+        // LIBSSH2_ERROR_AUTHENTICATION_FAILED, LIBSSH2_ERROR_PUBLICKEY_UNVERIFIED
+        ssh2::ErrorCode::Session(-18 | -19) => Some(false),
+        // SSH_FX_NO_CONNECTION, SSH_FX_CONNECTION_LOST
+        ssh2::ErrorCode::SFTP(6 | 7) => Some(true),
+        // SSH_FX_NO_SUCH_FILE, SSH_FX_PERMISSION_DENIED
+        ssh2::ErrorCode::SFTP(2 | 3) => Some(false),
+        _ => None,
+    }
+}
+
+/// Phrases marking an error as permanent; checked before TRANSIENT_PHRASES, so
+/// "permission denied (connection closed)" is not retried
+pub const PERMANENT_PHRASES: &[&str] = &[
+    "authentication",
+    "permission denied",
+    "access denied",
+    "unauthorized",
+    "host key",
+    "no such file",
+];
+
+/// Phrases marking an error as a passing network or server condition. Deliberately
+/// specific: a bare "connection" also matches errors like "bad connection string"
+pub const TRANSIENT_PHRASES: &[&str] = &[
+    "timed out",
+    "timeout",
+    "connection reset",
+    "reset by peer",
+    "connection refused",
+    "connection aborted",
+    "connection closed",
+    "connection lost",
+    "broken pipe",
+    "no route to host",
+    "network is unreachable",
+    "network unreachable",
+    "network error",
+    "host is down",
+    "temporary",
+    "temporarily",
+    "try again",
+    "busy",
+    "interrupted",
+    "unexpected eof",
+];
+
+/// Fallback for errors with no structured cause: match the lowercased message against
+/// PERMANENT_PHRASES, then TRANSIENT_PHRASES. An EOF on a channel ("channel 0: eof",
+/// "unable to read from channel: EOF") or a libssh2 socket code ("[Session(-43)]") also
+/// counts as transient. Anything else is not retried
+pub fn is_transient_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+
+    if PERMANENT_PHRASES.iter().any(|phrase| message.contains(phrase)) {
+        return false;
+    }
+    if TRANSIENT_PHRASES.iter().any(|phrase| message.contains(phrase)) {
+        return true;
+    }
+    if message.contains("channel") && message.contains("eof") {
+        return true;
+    }
+    TRANSIENT_LIBSSH2_CODES.iter().any(|code| message.contains(&format!("[session({})]", code)))
+}
+
+/// Find a typed SSHError anywhere in an anyhow error chain
+/// Works through `.context(...)` wrapping, so command handlers can map errors structurally
+pub fn find_ssh_error(error: &anyhow::Error) -> Option<&SSHError> {
+    error.chain().find_map(|cause| cause.downcast_ref::<SSHError>())
+}
+
 /// Error mapping for frontend compatibility
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct ConnectionError {
@@ -172,6 +310,52 @@ pub fn map_ssh_error(error: &SSHError) -> ConnectionError {
                 "This is normal after extended inactivity".to_string(),
             ],
         },
+        SSHError::HostKeyMismatch(msg) => ConnectionError {
+            category: "Security".to_string(),
+            code: "HOST_001".to_string(),
+            message: "WARNING: cluster host key has changed".to_string(),
+            details: Some(msg.clone()),
+            retryable: false,
+            suggestions: vec![
+                "Do not enter your password until the key change is explained".to_string(),
+                "Ask your cluster administrators whether the host keys were replaced".to_string(),
+                "If they confirm the new fingerprint, trust the new host key and reconnect".to_string(),
+            ],
+        },
+        SSHError::ChecksumMismatch(msg) => ConnectionError {
+            category: "FileOperation".to_string(),
+            code: "FILE_003".to_string(),
+            message: "Transferred file is corrupted".to_string(),
+            details: Some(msg.clone()),
+            retryable: true,
+            suggestions: vec![
+                "Transfer the file again".to_string(),
+                "Verify network connection stability".to_string(),
+                "Check available disk space on cluster".to_string(),
+            ],
+        },
+        SSHError::TransferCancelled(msg) => ConnectionError {
+            category: "FileOperation".to_string(),
+            code: "FILE_004".to_string(),
+            message: "Transfer cancelled".to_string(),
+            details: Some(msg.clone()),
+            retryable: false,
+            suggestions: vec![
+                "Start the transfer again when you are ready".to_string(),
+            ],
+        },
+        SSHError::InsufficientSpace(msg) => ConnectionError {
+            category: "FileOperation".to_string(),
+            code: "FILE_005".to_string(),
+            message: "Not enough disk space on cluster".to_string(),
+            details: Some(msg.clone()),
+            retryable: false,
+            suggestions: vec![
+                "Free up space in your project or scratch directory".to_string(),
+                "Check your quota with 'curc-quota' or 'lfs quota'".to_string(),
+                "Continue anyway if you know the estimate is too high".to_string(),
+            ],
+        },
         SSHError::UnknownError(msg) => ConnectionError {
             category: "Unknown".to_string(),
             code: "UNK_001".to_string(),
@@ -199,7 +383,11 @@ impl From<std::io::Error> for SSHError {
             }
             std::io::ErrorKind::ConnectionRefused |
             std::io::ErrorKind::ConnectionAborted |
-            std::io::ErrorKind::ConnectionReset => {
+            std::io::ErrorKind::ConnectionReset |
+            std::io::ErrorKind::BrokenPipe |
+            std::io::ErrorKind::UnexpectedEof |
+            std::io::ErrorKind::HostUnreachable |
+            std::io::ErrorKind::NetworkUnreachable => {
                 SSHError::NetworkError(error.to_string())
             }
             _ => SSHError::UnknownError(error.to_string())
@@ -254,6 +442,51 @@ mod tests {
         assert!(!conn_error.suggestions.is_empty());
     }
 
+    #[test]
+    fn test_host_key_mismatch_mapping() {
+        let ssh_error = SSHError::HostKeyMismatch("The host key for login.example.edu has changed".to_string());
+        let conn_error = map_ssh_error(&ssh_error);
+
+        assert_eq!(conn_error.category, "Security");
+        assert_eq!(conn_error.code, "HOST_001");
+        assert!(!conn_error.retryable);
+        assert_eq!(ssh_error.is_transient(), Some(false));
+        assert!(ssh_error.to_string().starts_with("Host key verification failed"));
+    }
+
+    #[test]
+    fn test_checksum_mismatch_mapping() {
+        let ssh_error = SSHError::ChecksumMismatch("/remote/structure.psf: local sha256 aa but remote sha256 bb".to_string());
+        let conn_error = map_ssh_error(&ssh_error);
+
+        assert_eq!(conn_error.category, "FileOperation");
+        assert_eq!(conn_error.code, "FILE_003");
+        // Corruption in transit is worth another transfer
+        assert!(conn_error.retryable);
+        assert_eq!(ssh_error.is_transient(), Some(true));
+    }
+
+    #[test]
+    fn test_insufficient_space_mapping() {
+        let ssh_error = SSHError::InsufficientSpace("/projects/testuser needs 2.0 GB but has 1.0 GB free".to_string());
+        let conn_error = map_ssh_error(&ssh_error);
+
+        assert_eq!(conn_error.code, "FILE_005");
+        assert!(!conn_error.retryable);
+        assert_eq!(ssh_error.is_transient(), Some(false));
+    }
+
+    #[test]
+    fn test_transfer_cancelled_mapping() {
+        let ssh_error = SSHError::TransferCancelled("Upload of structure.psf was cancelled".to_string());
+        let conn_error = map_ssh_error(&ssh_error);
+
+        // Distinct from a network failure, and never retried behind the user's back
+        assert_eq!(conn_error.code, "FILE_004");
+        assert!(!conn_error.retryable);
+        assert_eq!(ssh_error.is_transient(), Some(false));
+    }
+
     #[test]
     fn test_auth_error_mapping() {
         let ssh_error = SSHError::AuthenticationError("Wrong password".to_string());
@@ -286,6 +519,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_transient_by_variant() {
+        assert_eq!(SSHError::NetworkError("x".to_string()).is_transient(), Some(true));
+        assert_eq!(SSHError::HandshakeError("x".to_string()).is_transient(), Some(true));
+        assert_eq!(SSHError::TimeoutError("x".to_string()).is_transient(), Some(true));
+        assert_eq!(SSHError::AuthenticationError("x".to_string()).is_transient(), Some(false));
+        assert_eq!(SSHError::PermissionError("x".to_string()).is_transient(), Some(false));
+        assert_eq!(SSHError::ConfigurationError("x".to_string()).is_transient(), Some(false));
+        assert_eq!(SSHError::SessionError("x".to_string()).is_transient(), Some(false));
+        assert_eq!(SSHError::CommandError("x".to_string()).is_transient(), None);
+        assert_eq!(SSHError::FileTransferError("x".to_string()).is_transient(), None);
+        assert_eq!(SSHError::UnknownError("x".to_string()).is_transient(), None);
+    }
+
+    #[test]
+    fn test_transient_message_keywords() {
+        // Missed before: no "connection"/"timeout" keyword, or EOF on the channel
+        assert!(is_transient_message("ssh: connect to host login.rc.colorado.edu port 22: No route to host"));
+        assert!(is_transient_message("Failed to read: Connection reset by peer (os error 104)"));
+        assert!(is_transient_message("channel 0: unexpected EOF"));
+        assert!(is_transient_message("Unable to read from channel: EOF"));
+        assert!(is_transient_message("Failed to write to remote file: [Session(-7)] Unable to send FXP_WRITE"));
+
+        // Matched before on a bare "connection"/"unavailable"
+        assert!(!is_transient_message("bad connection string"));
+        assert!(!is_transient_message("Invalid connection configuration: port must be a number"));
+        assert!(!is_transient_message("Module namd/3.0 is unavailable"));
+
+        // Permanent phrases win when both appear
+        assert!(!is_transient_message("Permission denied (connection closed by remote host)"));
+        assert!(!is_transient_message("Failed to create remote file: no such file or directory"));
+    }
+
+    #[test]
+    fn test_structured_transient_checks() {
+        use std::io::ErrorKind;
+        assert_eq!(io_error_is_transient(ErrorKind::ConnectionReset), Some(true));
+        assert_eq!(io_error_is_transient(ErrorKind::UnexpectedEof), Some(true));
+        assert_eq!(io_error_is_transient(ErrorKind::HostUnreachable), Some(true));
+        assert_eq!(io_error_is_transient(ErrorKind::PermissionDenied), Some(false));
+        assert_eq!(io_error_is_transient(ErrorKind::Other), None);
+
+        let recv = ssh2::Error::new(ssh2::ErrorCode::Session(-43), "Failure while draining incoming flow");
+        assert_eq!(ssh2_error_is_transient(&recv), Some(true));
+        let auth = ssh2::Error::new(ssh2::ErrorCode::Session(-18), "Authentication failed");
+        assert_eq!(ssh2_error_is_transient(&auth), Some(false));
+        let lost = ssh2::Error::new(ssh2::ErrorCode::SFTP(7), "connection lost");
+        assert_eq!(ssh2_error_is_transient(&lost), Some(true));
+        let eof = ssh2::Error::new(ssh2::ErrorCode::SFTP(1), "end of file");
+        assert_eq!(ssh2_error_is_transient(&eof), None);
+    }
+
+    #[test]
+    fn test_find_ssh_error_through_context() {
+        let error = anyhow::Error::from(SSHError::PermissionError("/projects/other".to_string()))
+            .context("Failed to create job directory")
+            .context("Job creation failed");
+
+        match find_ssh_error(&error) {
+            Some(SSHError::PermissionError(msg)) => assert_eq!(msg, "/projects/other"),
+            other => panic!("Expected PermissionError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_find_ssh_error_absent_for_plain_errors() {
+        let error = anyhow::anyhow!("Database not initialized").context("Failed to load jobs");
+        assert!(find_ssh_error(&error).is_none());
+    }
+
     #[test]
     fn test_comprehensive_error_mapping() {
         // Test all error types get mapped correctly
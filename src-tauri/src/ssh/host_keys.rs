@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use base64::Engine;
+use ssh2::{CheckResult, HashType, HostKeyType, KnownHostFileKind, KnownHosts, Session};
+use super::SSHError;
+use crate::{log_info, log_warn};
+
+/// File holding pinned host keys (OpenSSH known_hosts format), kept next to the database
+pub const KNOWN_HOSTS_FILE: &str = "known_hosts";
+
+/// A host key as presented by the server during the handshake
+#[derive(Debug, Clone)]
+pub struct PresentedKey {
+    pub key: Vec<u8>,
+    pub key_type: HostKeyType,
+    /// OpenSSH-style "SHA256:..." fingerprint, for messages
+    pub fingerprint: String,
+}
+
+/// Outcome of a successful host key check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostKeyCheck {
+    /// Matched the pinned key
+    Trusted,
+    /// Never seen before - pinned now (trust on first use)
+    FirstUse,
+}
+
+lazy_static::lazy_static! {
+    /// Changed keys seen on a rejected connect, waiting for the user to accept them with trust_host_key
+    static ref PENDING_KEYS: Mutex<HashMap<String, PresentedKey>> = Mutex::new(HashMap::new());
+}
+
+/// known_hosts entry name: plain host for port 22, "[host]:port" otherwise (OpenSSH convention)
+fn entry_name(host: &str, port: u16) -> String {
+    if port == 22 {
+        host.to_string()
+    } else {
+        format!("[{}]:{}", host, port)
+    }
+}
+
+/// Pinned host keys stored in an OpenSSH known_hosts file
+pub struct HostKeyStore {
+    path: PathBuf,
+}
+
+impl HostKeyStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Store next to the current database
+    pub fn default_location() -> Result<Self, SSHError> {
+        crate::database::get_current_database_path()
+            .and_then(|db_path| db_path.parent().map(|dir| Self::new(dir.join(KNOWN_HOSTS_FILE))))
+            .ok_or_else(|| SSHError::ConfigurationError("Host key store unavailable - database not initialized".to_string()))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn load(&self) -> Result<KnownHosts, SSHError> {
+        let store_error = |e: ssh2::Error| SSHError::ConfigurationError(format!("Host key store {}: {}", self.path.display(), e));
+        let session = Session::new().map_err(store_error)?;
+        let mut known_hosts = session.known_hosts().map_err(store_error)?;
+        if self.path.exists() {
+            known_hosts.read_file(&self.path, KnownHostFileKind::OpenSSH).map_err(store_error)?;
+        }
+        Ok(known_hosts)
+    }
+
+    fn save(&self, known_hosts: &KnownHosts) -> Result<(), SSHError> {
+        known_hosts.write_file(&self.path, KnownHostFileKind::OpenSSH)
+            .map_err(|e| SSHError::ConfigurationError(format!("Failed to save host key store {}: {}", self.path.display(), e)))
+    }
+
+    /// Compare a presented key with the pinned one, pinning it if the host is new
+    /// A changed key is rejected and held as pending until `trust_pending` accepts it
+    pub fn verify(&self, host: &str, port: u16, presented: &PresentedKey) -> Result<HostKeyCheck, SSHError> {
+        let mut known_hosts = self.load()?;
+
+        // Check the exact entry name: check_port would also accept a plain-host entry for other ports
+        match known_hosts.check(&entry_name(host, port), &presented.key) {
+            CheckResult::Match => Ok(HostKeyCheck::Trusted),
+            CheckResult::NotFound => {
+                known_hosts.add(&entry_name(host, port), &presented.key, "added by NAMDRunner", presented.key_type.into())
+                    .map_err(|e| SSHError::ConfigurationError(format!("Failed to record host key: {}", e)))?;
+                self.save(&known_hosts)?;
+                log_info!(category: "SSH", message: "Pinned host key on first connect", details: "{}:{} {}", host, port, presented.fingerprint);
+                Ok(HostKeyCheck::FirstUse)
+            }
+            CheckResult::Mismatch => {
+                PENDING_KEYS.lock().unwrap().insert(entry_name(host, port), presented.clone());
+                log_warn!(category: "SSH", message: "Host key changed", details: "{}:{} now presents {}", host, port, presented.fingerprint);
+                Err(SSHError::HostKeyMismatch(format!(
+                    "The host key for {} has changed (now {}). This can mean someone is intercepting the connection, or the cluster replaced its keys. Confirm the new fingerprint with your cluster administrators before trusting it",
+                    entry_name(host, port), presented.fingerprint
+                )))
+            }
+            CheckResult::Failure => Err(SSHError::ConfigurationError(format!("Could not check host key for {}", entry_name(host, port)))),
+        }
+    }
+
+    /// Replace whatever is pinned for the host with this key
+    pub fn trust(&self, host: &str, port: u16, presented: &PresentedKey) -> Result<(), SSHError> {
+        let mut known_hosts = self.load()?;
+        let name = entry_name(host, port);
+
+        let existing = known_hosts.hosts()
+            .map_err(|e| SSHError::ConfigurationError(format!("Failed to read host key store: {}", e)))?;
+        for entry in existing.iter().filter(|entry| entry.name() == Some(name.as_str())) {
+            known_hosts.remove(entry)
+                .map_err(|e| SSHError::ConfigurationError(format!("Failed to remove old host key: {}", e)))?;
+        }
+
+        known_hosts.add(&name, &presented.key, "trusted in NAMDRunner", presented.key_type.into())
+            .map_err(|e| SSHError::ConfigurationError(format!("Failed to record host key: {}", e)))?;
+        self.save(&known_hosts)?;
+        log_info!(category: "SSH", message: "Host key trusted", details: "{} {}", name, presented.fingerprint);
+        Ok(())
+    }
+
+    /// Accept the changed key last rejected for this host, returning its fingerprint
+    pub fn trust_pending(&self, host: &str, port: u16) -> Result<String, SSHError> {
+        let name = entry_name(host, port);
+        let presented = PENDING_KEYS.lock().unwrap().remove(&name)
+            .ok_or_else(|| SSHError::ConfigurationError(format!("No changed host key is waiting for approval for {} - connect first", name)))?;
+
+        if let Err(e) = self.trust(host, port, &presented) {
+            PENDING_KEYS.lock().unwrap().insert(name, presented);
+            return Err(e);
+        }
+        Ok(presented.fingerprint)
+    }
+}
+
+/// The host key from a session that has completed its handshake
+pub fn presented_key(session: &Session) -> Result<PresentedKey, SSHError> {
+    let (key, key_type) = session.host_key()
+        .ok_or_else(|| SSHError::HandshakeError("Server did not present a host key".to_string()))?;
+    let fingerprint = session.host_key_hash(HashType::Sha256)
+        .map(|hash| format!("SHA256:{}", base64_unpadded(hash)))
+        .unwrap_or_else(|| "unknown fingerprint".to_string());
+    Ok(PresentedKey { key: key.to_vec(), key_type, fingerprint })
+}
+
+/// Standard base64 without padding, as OpenSSH prints fingerprints
+fn base64_unpadded(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD_NO_PAD.encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> PresentedKey {
+        // Raw key blobs are opaque to the store; any bytes round-trip through the file
+        PresentedKey { key: vec![byte; 51], key_type: HostKeyType::Ed25519, fingerprint: format!("SHA256:test{}", byte) }
+    }
+
+    fn store() -> (tempfile::TempDir, HostKeyStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = HostKeyStore::new(dir.path().join(KNOWN_HOSTS_FILE));
+        (dir, store)
+    }
+
+    #[test]
+    fn test_first_connect_pins_key() {
+        let (_dir, store) = store();
+
+        assert_eq!(store.verify("login.example.edu", 22, &key(1)).unwrap(), HostKeyCheck::FirstUse);
+
+        let contents = std::fs::read_to_string(store.path()).unwrap();
+        assert!(contents.starts_with("login.example.edu ssh-ed25519 "));
+    }
+
+    #[test]
+    fn test_same_key_matches() {
+        let (_dir, store) = store();
+        store.verify("login.example.edu", 22, &key(1)).unwrap();
+
+        // Fresh load from disk each time, as on a later app launch
+        assert_eq!(store.verify("login.example.edu", 22, &key(1)).unwrap(), HostKeyCheck::Trusted);
+    }
+
+    #[test]
+    fn test_changed_key_is_rejected() {
+        let (_dir, store) = store();
+        store.verify("mismatch.example.edu", 22, &key(1)).unwrap();
+
+        let err = store.verify("mismatch.example.edu", 22, &key(2)).unwrap_err();
+
+        assert!(matches!(err, SSHError::HostKeyMismatch(_)));
+        assert!(err.to_string().contains("SHA256:test2"));
+        // Still rejected until explicitly trusted
+        assert!(store.verify("mismatch.example.edu", 22, &key(2)).is_err());
+        assert_eq!(store.verify("mismatch.example.edu", 22, &key(1)).unwrap(), HostKeyCheck::Trusted);
+    }
+
+    #[test]
+    fn test_trusting_changed_key_replaces_pin() {
+        let (_dir, store) = store();
+        store.verify("rotated.example.edu", 22, &key(1)).unwrap();
+        store.verify("rotated.example.edu", 22, &key(2)).unwrap_err();
+
+        assert_eq!(store.trust_pending("rotated.example.edu", 22).unwrap(), "SHA256:test2");
+
+        assert!(store.trust_pending("rotated.example.edu", 22).is_err(), "nothing left pending");
+        assert_eq!(store.verify("rotated.example.edu", 22, &key(2)).unwrap(), HostKeyCheck::Trusted);
+        assert!(store.verify("rotated.example.edu", 22, &key(1)).is_err(), "old key is no longer trusted");
+    }
+
+    #[test]
+    fn test_keys_are_pinned_per_port() {
+        let (_dir, store) = store();
+        store.verify("ports.example.edu", 22, &key(1)).unwrap();
+
+        assert_eq!(store.verify("ports.example.edu", 2222, &key(2)).unwrap(), HostKeyCheck::FirstUse);
+        assert_eq!(store.verify("ports.example.edu", 2222, &key(2)).unwrap(), HostKeyCheck::Trusted);
+        assert_eq!(store.verify("ports.example.edu", 22, &key(1)).unwrap(), HostKeyCheck::Trusted);
+        assert!(std::fs::read_to_string(store.path()).unwrap().contains("[ports.example.edu]:2222 "));
+    }
+
+    #[test]
+    fn test_base64_unpadded() {
+        assert_eq!(base64_unpadded(b""), "");
+        assert_eq!(base64_unpadded(b"f"), "Zg");
+        assert_eq!(base64_unpadded(b"fo"), "Zm8");
+        assert_eq!(base64_unpadded(b"foo"), "Zm9v");
+        assert_eq!(base64_unpadded(b"foobar"), "Zm9vYmFy");
+    }
+}
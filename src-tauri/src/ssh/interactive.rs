@@ -0,0 +1,261 @@
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+use serde::Serialize;
+use ssh2::{KeyboardInteractivePrompt, Prompt};
+use super::SSHError;
+use crate::log_debug;
+
+/// Event carrying a keyboard-interactive challenge (OTP code, Duo menu) to the frontend
+pub const AUTH_CHALLENGE_EVENT: &str = "auth-challenge";
+
+/// How long to wait for the user to answer a challenge before giving up on the login
+pub const CHALLENGE_RESPONSE_TIMEOUT: Duration = Duration::from_secs(120);
+
+lazy_static::lazy_static! {
+    /// Sender for the login currently waiting on challenge responses (at most one)
+    static ref PENDING_RESPONSES: Mutex<Option<Sender<Vec<String>>>> = Mutex::new(None);
+}
+
+/// Deliver the user's answers to the login waiting on a challenge
+pub fn submit_challenge_responses(responses: Vec<String>) -> Result<(), SSHError> {
+    let pending = PENDING_RESPONSES.lock().unwrap();
+    let sender = pending.as_ref()
+        .ok_or_else(|| SSHError::SessionError("No login is waiting for a response".to_string()))?;
+    sender.send(responses)
+        .map_err(|_| SSHError::SessionError("The login waiting for a response has ended".to_string()))
+}
+
+/// One prompt within a challenge
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChallengePrompt {
+    pub text: String,
+    /// Whether the answer may be shown while typing (false for passwords and codes)
+    pub echo: bool,
+}
+
+/// A keyboard-interactive challenge round; there may be several per login
+/// A challenge with no prompts is informational (e.g. "Duo push sent") and needs no answer
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthChallenge {
+    pub username: String,
+    pub instructions: String,
+    pub prompts: Vec<ChallengePrompt>,
+    /// Seconds the login waits for the answers
+    pub timeout_secs: u64,
+}
+
+/// Why a challenge went unanswered
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChallengeError {
+    TimedOut(Duration),
+    ResponseCountMismatch { expected: usize, received: usize },
+    Cancelled,
+}
+
+impl From<ChallengeError> for SSHError {
+    fn from(error: ChallengeError) -> Self {
+        match error {
+            ChallengeError::TimedOut(timeout) => SSHError::TimeoutError(format!(
+                "No response to the authentication prompt within {} seconds - log in again to get a new prompt", timeout.as_secs()
+            )),
+            ChallengeError::ResponseCountMismatch { expected, received } => SSHError::AuthenticationError(format!(
+                "The authentication prompt asked for {} answer(s) but {} were given", expected, received
+            )),
+            ChallengeError::Cancelled => SSHError::AuthenticationError("The authentication prompt was cancelled".to_string()),
+        }
+    }
+}
+
+/// Keyboard-interactive login: challenges go out through `emit`, answers come back on a channel
+/// Creating one registers it as the target of `submit_challenge_responses`; dropping it unregisters
+pub struct InteractiveAuth {
+    emit: Box<dyn Fn(&AuthChallenge) + Send + Sync>,
+    responses: Mutex<Receiver<Vec<String>>>,
+    timeout: Duration,
+    registered: bool,
+}
+
+impl std::fmt::Debug for InteractiveAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InteractiveAuth").field("timeout", &self.timeout).finish()
+    }
+}
+
+impl InteractiveAuth {
+    pub fn new(emit: impl Fn(&AuthChallenge) + Send + Sync + 'static, timeout: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        *PENDING_RESPONSES.lock().unwrap() = Some(sender);
+        let mut auth = Self::with_channel(emit, receiver, timeout);
+        auth.registered = true;
+        auth
+    }
+
+    fn with_channel(emit: impl Fn(&AuthChallenge) + Send + Sync + 'static, responses: Receiver<Vec<String>>, timeout: Duration) -> Self {
+        Self { emit: Box::new(emit), responses: Mutex::new(responses), timeout, registered: false }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Send a challenge to the user and wait for exactly one answer per prompt
+    pub fn answer(&self, username: &str, instructions: &str, prompts: Vec<ChallengePrompt>) -> Result<Vec<String>, ChallengeError> {
+        let responses = self.responses.lock().unwrap();
+
+        // An answer that arrived after an earlier challenge timed out must not answer this one
+        while responses.try_recv().is_ok() {}
+
+        let expected = prompts.len();
+        (self.emit)(&AuthChallenge {
+            username: username.to_string(),
+            instructions: instructions.to_string(),
+            prompts,
+            timeout_secs: self.timeout.as_secs(),
+        });
+
+        if expected == 0 {
+            return Ok(Vec::new());
+        }
+
+        match responses.recv_timeout(self.timeout) {
+            Ok(answers) if answers.len() == expected => Ok(answers),
+            Ok(answers) => Err(ChallengeError::ResponseCountMismatch { expected, received: answers.len() }),
+            Err(RecvTimeoutError::Timeout) => Err(ChallengeError::TimedOut(self.timeout)),
+            Err(RecvTimeoutError::Disconnected) => Err(ChallengeError::Cancelled),
+        }
+    }
+
+    pub(super) fn prompter(&self) -> ChallengePrompter<'_> {
+        ChallengePrompter { auth: self, error: None }
+    }
+}
+
+impl Drop for InteractiveAuth {
+    fn drop(&mut self) {
+        if self.registered {
+            *PENDING_RESPONSES.lock().unwrap() = None;
+        }
+    }
+}
+
+/// Bridges libssh2's synchronous prompt callback to `InteractiveAuth::answer`
+/// Keeps the first failure so the login reports it instead of a generic auth error
+pub(super) struct ChallengePrompter<'a> {
+    auth: &'a InteractiveAuth,
+    pub error: Option<ChallengeError>,
+}
+
+impl KeyboardInteractivePrompt for ChallengePrompter<'_> {
+    fn prompt<'b>(&mut self, username: &str, instructions: &str, prompts: &[Prompt<'b>]) -> Vec<String> {
+        let challenge_prompts = prompts.iter()
+            .map(|p| ChallengePrompt { text: p.text.to_string(), echo: p.echo })
+            .collect();
+
+        match self.auth.answer(username, instructions, challenge_prompts) {
+            Ok(answers) => answers,
+            Err(e) => {
+                log_debug!(category: "SSH", message: "Authentication prompt unanswered", details: "{:?}", e);
+                self.error.get_or_insert(e);
+                // libssh2 expects one answer per prompt; blank answers fail the login
+                vec![String::new(); prompts.len()]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn prompt(text: &str, echo: bool) -> ChallengePrompt {
+        ChallengePrompt { text: text.to_string(), echo }
+    }
+
+    /// Auth whose "frontend" answers each challenge with `reply` as soon as it is emitted
+    fn auto_answering(reply: Option<Vec<String>>, timeout: Duration) -> (InteractiveAuth, Arc<Mutex<Vec<AuthChallenge>>>) {
+        let (sender, receiver) = mpsc::channel();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let sender = Mutex::new(sender);
+        let auth = InteractiveAuth::with_channel(move |challenge| {
+            seen_clone.lock().unwrap().push(challenge.clone());
+            if let Some(reply) = &reply {
+                sender.lock().unwrap().send(reply.clone()).unwrap();
+            }
+        }, receiver, timeout);
+        (auth, seen)
+    }
+
+    #[test]
+    fn test_multiple_prompts_in_one_challenge() {
+        let (auth, seen) = auto_answering(Some(vec!["hunter2".to_string(), "1".to_string()]), Duration::from_secs(5));
+
+        let answers = auth.answer("testuser", "Duo two-factor login", vec![
+            prompt("Password: ", false),
+            prompt("Passcode or option (1-2): ", true),
+        ]).unwrap();
+
+        assert_eq!(answers, vec!["hunter2", "1"]);
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].prompts.len(), 2);
+        assert!(!seen[0].prompts[0].echo);
+        assert_eq!(seen[0].timeout_secs, 5);
+    }
+
+    #[test]
+    fn test_unanswered_challenge_times_out() {
+        let (auth, seen) = auto_answering(None, Duration::from_millis(50));
+
+        let err = auth.answer("testuser", "", vec![prompt("Verification code: ", false)]).unwrap_err();
+
+        assert_eq!(err, ChallengeError::TimedOut(Duration::from_millis(50)));
+        assert_eq!(seen.lock().unwrap().len(), 1);
+        assert!(matches!(SSHError::from(err), SSHError::TimeoutError(_)));
+    }
+
+    #[test]
+    fn test_wrong_number_of_answers_rejected() {
+        let (auth, _) = auto_answering(Some(vec!["only one".to_string()]), Duration::from_secs(5));
+
+        let err = auth.answer("testuser", "", vec![prompt("Password: ", false), prompt("Code: ", false)]).unwrap_err();
+        assert_eq!(err, ChallengeError::ResponseCountMismatch { expected: 2, received: 1 });
+    }
+
+    #[test]
+    fn test_informational_challenge_needs_no_answer() {
+        let (auth, seen) = auto_answering(None, Duration::from_secs(60));
+
+        let answers = auth.answer("testuser", "Duo push sent to your phone", vec![]).unwrap();
+
+        assert!(answers.is_empty());
+        assert_eq!(seen.lock().unwrap()[0].instructions, "Duo push sent to your phone");
+    }
+
+    #[test]
+    fn test_stale_answer_does_not_answer_next_challenge() {
+        let (sender, receiver) = mpsc::channel();
+        let auth = InteractiveAuth::with_channel(|_| {}, receiver, Duration::from_millis(50));
+
+        // Arrived after a previous challenge had already timed out
+        sender.send(vec!["old code".to_string()]).unwrap();
+
+        let err = auth.answer("testuser", "", vec![prompt("Code: ", false)]).unwrap_err();
+        assert!(matches!(err, ChallengeError::TimedOut(_)));
+    }
+
+    #[test]
+    fn test_submit_responses_reaches_registered_login() {
+        assert!(submit_challenge_responses(vec!["123456".to_string()]).is_err(), "no login waiting yet");
+
+        let auth = InteractiveAuth::new(|_| {
+            submit_challenge_responses(vec!["123456".to_string()]).unwrap();
+        }, Duration::from_secs(5));
+        assert_eq!(auth.answer("testuser", "", vec![prompt("Code: ", false)]).unwrap(), vec!["123456"]);
+
+        drop(auth);
+        assert!(submit_challenge_responses(vec!["123456".to_string()]).is_err(), "unregistered once the login ends");
+    }
+}
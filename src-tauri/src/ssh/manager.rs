@@ -1,19 +1,68 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use std::future::Future;
+use std::path::Path;
 use tokio::sync::Mutex;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use tauri::Emitter;
-use super::{SSHConnection, ConnectionConfig, ConnectionInfo};
+use super::{SSHConnection, ConnectionConfig, ConnectionInfo, Credentials};
 use super::commands::CommandResult;
-use super::sftp::{FileTransferProgress, SftpFileEntry};
+use super::errors::SSHError;
+use super::sftp::{ArchiveEntry, BatchUploadResult, FileTransferProgress, ProgressCallback, RemoteFileTail, SftpFileEntry, UploadTask};
+use super::zip_stream::ZipStreamWriter;
+use super::interactive::InteractiveAuth;
+use super::checksum;
+use super::cancellation::{self, CancelToken};
+use super::demo;
 use crate::security::SecurePassword;
-use crate::{log_debug, log_info, log_error};
+use crate::{log_debug, log_info, log_error, log_warn};
+
+/// Event emitted while a dropped session is re-dialed automatically
+pub const RECONNECT_EVENT: &str = "connection-reconnecting";
+
+/// Progress of an automatic reconnect, for the UI's "reconnecting" state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconnectStatus {
+    Started,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ReconnectEvent {
+    pub host: String,
+    pub status: ReconnectStatus,
+    pub message: Option<String>,
+}
+
+/// What's needed to re-dial a dropped password session
+/// Only kept while connected with `auto_reconnect` on; cleared on disconnect
+#[derive(Debug, Clone)]
+struct ReconnectTarget {
+    host: String,
+    port: u16,
+    username: String,
+    password: SecurePassword,
+    config: ConnectionConfig,
+}
 
 /// Connection lifecycle management with proper cleanup and error handling
 #[derive(Debug)]
 pub struct ConnectionManager {
     connection: Arc<Mutex<Option<SSHConnection>>>,
+    /// Last time the connection showed signs of life (connect, command, keepalive)
+    last_activity: std::sync::Mutex<Option<Instant>>,
+    /// Set when the connection was dropped for being idle too long
+    expired: AtomicBool,
+    /// Retry attempts from the active connection's config
+    command_retry_attempts: AtomicU32,
+    transfer_retry_attempts: AtomicU32,
+    /// Upload rate cap in bytes per second (0 = unlimited)
+    upload_rate_limit: AtomicU64,
+    /// Set for auto_reconnect password sessions so a dropped session can be re-dialed
+    reconnect_target: std::sync::Mutex<Option<ReconnectTarget>>,
 }
 
 impl ConnectionManager {
@@ -21,22 +70,80 @@ impl ConnectionManager {
     pub fn new() -> Self {
         Self {
             connection: Arc::new(Mutex::new(None)),
+            last_activity: std::sync::Mutex::new(None),
+            expired: AtomicBool::new(false),
+            command_retry_attempts: AtomicU32::new(ConnectionConfig::default().command_retry_attempts),
+            transfer_retry_attempts: AtomicU32::new(ConnectionConfig::default().transfer_retry_attempts),
+            upload_rate_limit: AtomicU64::new(0),
+            reconnect_target: std::sync::Mutex::new(None),
         }
     }
 
-    /// Establish a new SSH connection, cleaning up any existing connection first
-    pub async fn connect(&self, host: String, port: u16, username: String, password: &SecurePassword) -> Result<ConnectionInfo> {
-        // Ensure any existing connection is properly cleaned up
-        self.disconnect().await?;
+    /// Establish a new SSH connection, replacing any existing one
+    ///
+    /// The previous session is always closed and dropped before authenticating, even if
+    /// its shutdown fails, so switching accounts never leaves the old user's session
+    /// (or its state) behind - whether or not the new connection succeeds.
+    pub async fn connect(&self, host: String, port: u16, username: String, password: &SecurePassword, config: ConnectionConfig) -> Result<ConnectionInfo> {
+        // The password is borrowed through to userauth. With auto_reconnect enabled (opt-in), a
+        // SecurePassword copy is kept for re-dialing until disconnect or the next connect
+        self.connect_with(host, port, username, Credentials::Password(password), config).await
+    }
+
+    /// Establish a new SSH connection using public-key authentication, replacing any existing one
+    /// With no key file the SSH agent's identities are tried; the passphrase is borrowed like a password
+    pub async fn connect_with_key(
+        &self,
+        host: String,
+        port: u16,
+        username: String,
+        private_key: Option<&Path>,
+        passphrase: Option<&SecurePassword>,
+        config: ConnectionConfig,
+    ) -> Result<ConnectionInfo> {
+        let credentials = match private_key {
+            Some(private_key) => Credentials::KeyFile { private_key, passphrase },
+            None => Credentials::Agent,
+        };
+        self.connect_with(host, port, username, credentials, config).await
+    }
+
+    /// Establish a new SSH connection using keyboard-interactive (MFA) authentication
+    /// Challenges are emitted by `auth` and block until answers arrive on its channel or it times out
+    pub async fn connect_interactive(&self, host: String, port: u16, username: String, auth: &Arc<InteractiveAuth>, config: ConnectionConfig) -> Result<ConnectionInfo> {
+        self.connect_with(host, port, username, Credentials::KeyboardInteractive(auth), config).await
+    }
 
-        // Create new connection with default config
-        let config = ConnectionConfig::default();
+    async fn connect_with(&self, host: String, port: u16, username: String, credentials: Credentials<'_>, config: ConnectionConfig) -> Result<ConnectionInfo> {
+        if demo::is_demo_mode() {
+            return Ok(demo::connect(host, port, username));
+        }
+
+        if let Some(previous) = self.close_existing().await {
+            log_info!(category: "SSH", message: "Closed previous session before connecting", details: "{}@{} -> {}@{}", previous.username, previous.host, username, host);
+        }
+
+        self.command_retry_attempts.store(config.command_retry_attempts, Ordering::SeqCst);
+        self.transfer_retry_attempts.store(config.transfer_retry_attempts, Ordering::SeqCst);
+        // Only password sessions can be re-dialed without the user - the other methods need a prompt or agent
+        let reconnect_target = match credentials {
+            Credentials::Password(password) if config.auto_reconnect => Some(ReconnectTarget {
+                host: host.clone(),
+                port,
+                username: username.clone(),
+                password: password.clone(),
+                config: config.clone(),
+            }),
+            _ => None,
+        };
         let mut connection = SSHConnection::new(host, port, username, config);
 
-        // Attempt to connect using secure password
-        // We need to extract the password before the async call since closures can't be async
-        let pwd_string = password.with_password(|pwd| pwd.to_string());
-        connection.connect(&pwd_string).await?;
+        match credentials {
+            Credentials::Password(password) => connection.connect(password).await?,
+            Credentials::KeyFile { private_key, passphrase } => connection.connect_with_key(Some(private_key), passphrase).await?,
+            Credentials::Agent => connection.connect_with_key(None, None).await?,
+            Credentials::KeyboardInteractive(auth) => connection.connect_interactive(auth).await?,
+        }
 
         // Get connection info before storing
         let info = connection.get_info();
@@ -46,48 +153,189 @@ impl ConnectionManager {
             let mut conn = self.connection.lock().await;
             *conn = Some(connection);
         }
+        *self.reconnect_target.lock().unwrap() = reconnect_target;
+        self.touch_activity();
 
         Ok(info)
     }
 
+    /// Close and drop the current connection (best effort), returning what it was connected to
+    /// A failed shutdown is logged rather than returned: dropping the session closes the socket anyway
+    async fn close_existing(&self) -> Option<ConnectionInfo> {
+        let previous = self.connection.lock().await.take();
+        *self.reconnect_target.lock().unwrap() = None;
+        *self.last_activity.lock().unwrap() = None;
+        self.expired.store(false, Ordering::SeqCst);
+
+        let mut connection = previous?;
+        let info = connection.get_info();
+        if let Err(e) = connection.disconnect().await {
+            log_debug!(category: "SSH", message: "Previous session did not close cleanly", details: "{}", e);
+        }
+        Some(info)
+    }
+
     /// Disconnect and clean up the current connection
     pub async fn disconnect(&self) -> Result<()> {
+        demo::disconnect();
         let mut conn = self.connection.lock().await;
+        *self.reconnect_target.lock().unwrap() = None;
         if let Some(mut connection) = conn.take() {
             connection.disconnect().await?;
         }
+        *self.last_activity.lock().unwrap() = None;
+        self.expired.store(false, Ordering::SeqCst);
         Ok(())
     }
 
+    /// Drop a connection that has gone quiet for too long and remember why,
+    /// so status queries report Expired instead of Disconnected
+    pub async fn expire(&self) -> Result<()> {
+        self.disconnect().await?;
+        self.expired.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Whether the last connection was dropped by idle expiry
+    pub fn is_expired(&self) -> bool {
+        self.expired.load(Ordering::SeqCst)
+    }
+
+    /// Last time the connection was known to be alive
+    pub fn last_activity(&self) -> Option<Instant> {
+        *self.last_activity.lock().unwrap()
+    }
+
+    /// Idle expiry threshold of the current connection (None if disabled or not connected)
+    pub async fn idle_expiry_timeout(&self) -> Option<Duration> {
+        let conn = self.connection.lock().await;
+        conn.as_ref()
+            .map(|c| c.config().idle_expiry_timeout)
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+    }
+
+    fn touch_activity(&self) {
+        *self.last_activity.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Re-dial a dropped session in place, at most once per operation (`attempted` is shared across its retries)
+    /// Returns None when no attempt was made: already tried, or no saved target to re-dial
+    async fn try_reconnect(&self, conn: &mut Option<SSHConnection>, attempted: &AtomicBool) -> Option<Result<()>> {
+        if attempted.load(Ordering::SeqCst) {
+            return None;
+        }
+        let target = self.reconnect_target.lock().unwrap().clone()?;
+        attempted.store(true, Ordering::SeqCst);
+
+        log_debug!(category: "SSH", message: "Re-dialing dropped session", details: "{}@{}:{}", target.username, target.host, target.port);
+        log_warn!(category: "SSH", message: "Connection dropped - reconnecting", show_toast: true);
+        emit_reconnect(&target.host, ReconnectStatus::Started, None);
+
+        let mut connection = SSHConnection::new(target.host.clone(), target.port, target.username.clone(), target.config.clone());
+        match connection.connect(&target.password).await {
+            Ok(()) => {
+                *conn = Some(connection);
+                self.touch_activity();
+                log_info!(category: "SSH", message: "Reconnected to cluster", show_toast: true);
+                emit_reconnect(&target.host, ReconnectStatus::Succeeded, None);
+                Some(Ok(()))
+            }
+            Err(e) => {
+                log_error!(category: "SSH", message: "Reconnect failed", details: "{}", e);
+                emit_reconnect(&target.host, ReconnectStatus::Failed, Some(e.to_string()));
+                Some(Err(e))
+            }
+        }
+    }
+
+    /// Change the keepalive interval of the active connection in place (0 disables)
+    /// Returns false when there is no connection to update
+    pub async fn set_keepalive_interval(&self, seconds: u32) -> bool {
+        let mut conn = self.connection.lock().await;
+        match conn.as_mut() {
+            Some(connection) => {
+                connection.set_keepalive_interval(seconds);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cap the rate of `upload_file` transfers in bytes per second (None or 0 = unlimited)
+    /// Takes effect for uploads started after the change
+    pub fn set_upload_rate_limit(&self, max_bytes_per_sec: Option<u64>) {
+        self.upload_rate_limit.store(max_bytes_per_sec.unwrap_or(0), Ordering::SeqCst);
+    }
+
+    /// Current upload rate cap, None when unlimited
+    pub fn upload_rate_limit(&self) -> Option<u64> {
+        Some(self.upload_rate_limit.load(Ordering::SeqCst)).filter(|limit| *limit > 0)
+    }
+
     /// Check if there's an active connection
     pub async fn is_connected(&self) -> bool {
+        if demo::is_demo_mode() {
+            return demo::session_info().is_some();
+        }
         let conn = self.connection.lock().await;
         conn.as_ref().is_some_and(|c| c.is_connected())
     }
 
     /// Get current connection information
     pub async fn get_connection_info(&self) -> Option<ConnectionInfo> {
+        if demo::is_demo_mode() {
+            return demo::session_info();
+        }
         let conn = self.connection.lock().await;
         conn.as_ref().map(|c| c.get_info())
     }
 
     /// Execute a command using the current connection
     pub async fn execute_command(&self, command: &str, timeout: Option<u64>) -> Result<CommandResult> {
+        if demo::is_demo_mode() {
+            return Err(demo::command_unavailable(command));
+        }
         // Use retry logic for command execution
-        retry_quick(|| self.execute_command_once(command, timeout)).await
+        let reconnect_attempted = AtomicBool::new(false);
+        self.retry_command(|| self.execute_command_once(command, timeout, &reconnect_attempted)).await
     }
 
-    async fn execute_command_once(&self, command: &str, timeout: Option<u64>) -> Result<CommandResult> {
-        let conn = self.connection.lock().await;
+    /// Time one trivial `echo` round-trip on the current session
+    /// Runs once with no retry or reconnect, so the latency (or failure) is the session's own
+    pub async fn ping(&self, timeout: Option<u64>) -> Result<Duration> {
+        let reconnect_attempted = AtomicBool::new(true);
+        let mut conn = self.connection.lock().await;
+        // Timed from here so waiting behind another operation's lock doesn't count as latency
+        let started = Instant::now();
+        let result = self.execute_on_connection(&mut conn, "echo ping", timeout, &reconnect_attempted).await?;
+        let latency = started.elapsed();
+        if result.exit_code != 0 || result.stdout.trim() != "ping" {
+            return Err(SSHError::CommandError(format!("Unexpected ping reply (exit {}): {}", result.exit_code, result.stdout.trim())).into());
+        }
+        Ok(latency)
+    }
+
+    async fn execute_command_once(&self, command: &str, timeout: Option<u64>, reconnect_attempted: &AtomicBool) -> Result<CommandResult> {
+        let mut conn = self.connection.lock().await;
+        self.execute_on_connection(&mut conn, command, timeout, reconnect_attempted).await
+    }
+
+    /// Run a command on the connection slot the caller has already locked
+    async fn execute_on_connection(&self, conn: &mut Option<SSHConnection>, command: &str, timeout: Option<u64>, reconnect_attempted: &AtomicBool) -> Result<CommandResult> {
+        if conn.as_ref().is_some_and(|c| !c.is_connected()) {
+            // A failed reconnect leaves the dead connection in place, reported below
+            let _ = self.try_reconnect(conn, reconnect_attempted).await;
+        }
         match conn.as_ref() {
             Some(connection) => {
                 if !connection.is_connected() {
                     log_error!(category: "SSH", message: "SSH connection is no longer active");
-                    return Err(anyhow::anyhow!("SSH connection is no longer active"));
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
                 }
                 log_info!(category: "SSH", message: "Executing command", details: "{}", command);
                 let session = connection.get_session()?;
-                let executor = super::commands::CommandExecutor::new(session, timeout.unwrap_or(crate::cluster::timeouts::DEFAULT_COMMAND));
+                let executor = super::commands::CommandExecutor::new(session, timeout.unwrap_or(connection.config().command_timeout));
                 let result = executor.execute(command).await?;
                 log_debug!(category: "SSH", message: "Command output", details: "{} bytes stdout, {} bytes stderr", result.stdout.len(), result.stderr.len());
 
@@ -96,19 +344,33 @@ impl ConnectionManager {
                     log_debug!(category: "SSH", message: "Command stderr", details: "{}", result.stderr);
                 }
 
+                self.touch_activity();
                 Ok(result)
             }
             None => {
                 log_error!(category: "SSH", message: "Not connected to cluster");
-                Err(anyhow::anyhow!("Please connect to the cluster first"))
+                Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
             }
         }
     }
 
     /// Upload bytes directly to remote server with retry logic
-    pub async fn upload_bytes(&self, remote_path: &str, content: &[u8]) -> Result<FileTransferProgress> {
+    /// With `verify_checksum`, the remote copy's sha256sum must match the content or the upload is retried
+    pub async fn upload_bytes(&self, remote_path: &str, content: &[u8], verify_checksum: bool) -> Result<FileTransferProgress> {
+        if demo::is_demo_mode() {
+            return demo::upload_bytes(remote_path, content).await;
+        }
+        let local_hash = verify_checksum.then(|| checksum::sha256_bytes(content));
+        let local_hash = local_hash.as_deref();
+
         // Use retry logic for file uploads
-        retry_files(|| self.upload_bytes_once(remote_path, content)).await
+        self.retry_transfer(|| async move {
+            let progress = self.upload_bytes_once(remote_path, content).await?;
+            if let Some(local_hash) = local_hash {
+                self.verify_remote_checksum(remote_path, local_hash).await?;
+            }
+            Ok(progress)
+        }).await
     }
 
     async fn upload_bytes_once(&self, remote_path: &str, content: &[u8]) -> Result<FileTransferProgress> {
@@ -116,7 +378,7 @@ impl ConnectionManager {
         match conn.as_mut() {
             Some(connection) => {
                 if !connection.is_connected() {
-                    return Err(anyhow::anyhow!("SSH connection is no longer active"));
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
                 }
 
                 // Set file transfer timeout before SFTP operation
@@ -131,35 +393,91 @@ impl ConnectionManager {
 
                 result
             }
-            None => Err(super::SSHError::SessionError("No active connection".to_string()).into())
+            None => Err(SSHError::SessionError("No active connection".to_string()).into())
         }
     }
 
     /// Upload a file with optional progress event emission
+    /// With `verify_checksum`, the remote copy's sha256sum must match the local file or the upload is retried.
+    /// Setting `cancel` stops the upload between chunks; with a `progress_key` it can also be
+    /// cancelled by key (`cancel_transfer`)
     pub async fn upload_file(
         &self,
         local_path: &str,
         remote_path: &str,
         app_handle: Option<tauri::AppHandle>,
         progress_key: Option<String>,
+        cancel: Option<CancelToken>,
+        verify_checksum: bool,
     ) -> Result<FileTransferProgress> {
+        let registration = progress_key.as_deref().map(|key| cancellation::register(key, cancel.clone()));
+        let cancel = registration.as_ref().map(|r| r.token()).or(cancel);
+        let cancel = cancel.as_deref();
+
+        if demo::is_demo_mode() {
+            let progress_callback = app_handle.map(|handle| {
+                let throttle = Arc::new(std::sync::Mutex::new(ProgressThrottle::new(PROGRESS_EMIT_INTERVAL)));
+                upload_progress_callback(handle, progress_key.clone(), Instant::now(), throttle, None)
+            });
+            return demo::upload_file(local_path, remote_path, cancel, progress_callback).await;
+        }
+
+        let local_hash = if verify_checksum { Some(checksum::sha256_file(Path::new(local_path)).await?) } else { None };
+        let local_hash = local_hash.as_deref();
+
         // Use retry logic for file uploads
-        retry_files(|| self.upload_file_once(local_path, remote_path, app_handle.clone(), progress_key.clone())).await
+        let reconnect_attempted = &AtomicBool::new(false);
+        self.retry_transfer(|| {
+            let progress_callback = app_handle.clone().map(|handle| {
+                let throttle = Arc::new(std::sync::Mutex::new(ProgressThrottle::new(PROGRESS_EMIT_INTERVAL)));
+                upload_progress_callback(handle, progress_key.clone(), Instant::now(), throttle, None)
+            });
+            async move {
+                let progress = self.upload_file_once(local_path, remote_path, cancel, progress_callback, reconnect_attempted).await?;
+                if let Some(local_hash) = local_hash {
+                    self.verify_remote_checksum(remote_path, local_hash).await?;
+                }
+                Ok(progress)
+            }
+        }).await
+    }
+
+    /// SHA-256 of a remote file, computed on the server with sha256sum
+    pub async fn remote_sha256(&self, remote_path: &str) -> Result<String> {
+        let result = self.execute_command(&super::commands::sha256sum_command(remote_path), None).await?;
+        if result.exit_code != 0 {
+            return Err(SSHError::CommandError(format!("sha256sum failed for {}: {}", remote_path, result.stderr.trim())).into());
+        }
+        Ok(checksum::parse_sha256sum_output(&result.stdout)?)
+    }
+
+    /// Compare a remote file's hash with the hash of what was sent
+    async fn verify_remote_checksum(&self, remote_path: &str, local_hash: &str) -> Result<()> {
+        let remote_hash = self.remote_sha256(remote_path).await?;
+        checksum::compare(remote_path, local_hash, &remote_hash).inspect_err(|e| {
+            log_error!(category: "SFTP", message: "Checksum mismatch after transfer", details: "{}", e);
+        })?;
+        log_debug!(category: "SFTP", message: "Checksum verified", details: "{} sha256 {}", remote_path, remote_hash);
+        Ok(())
     }
 
     async fn upload_file_once(
         &self,
         local_path: &str,
         remote_path: &str,
-        app_handle: Option<tauri::AppHandle>,
-        progress_key: Option<String>,
+        cancel: Option<&AtomicBool>,
+        progress_callback: Option<ProgressCallback>,
+        reconnect_attempted: &AtomicBool,
     ) -> Result<FileTransferProgress> {
         let mut conn = self.connection.lock().await;
+        if conn.as_ref().is_some_and(|c| !c.is_connected()) {
+            let _ = self.try_reconnect(&mut conn, reconnect_attempted).await;
+        }
         match conn.as_mut() {
             Some(connection) => {
                 if !connection.is_connected() {
                     log_error!(category: "SFTP", message: "SSH connection is no longer active");
-                    return Err(anyhow::anyhow!("SSH connection is no longer active"));
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
                 }
                 log_info!(category: "SFTP", message: "Uploading file", details: "{} -> {}", local_path, remote_path);
 
@@ -174,41 +492,11 @@ impl ConnectionManager {
                 let session = connection.get_session()?;
                 let sftp = super::sftp::SFTPOperations::new(session);
 
-                // Create progress callback if app_handle is provided
-                let progress_callback: Option<super::sftp::ProgressCallback> = app_handle.map(|handle| {
-                    let progress_key = progress_key.clone();
-                    let start_time = std::time::Instant::now();
-
-                    Box::new(move |bytes_transferred: u64, total_bytes: u64| {
-                        let elapsed = start_time.elapsed().as_secs_f64();
-                        let transfer_rate = if elapsed > 0.0 {
-                            bytes_transferred as f64 / elapsed // bytes per second
-                        } else {
-                            0.0
-                        };
-
-                        let percentage = if total_bytes > 0 {
-                            (bytes_transferred as f32 / total_bytes as f32) * 100.0
-                        } else {
-                            0.0
-                        };
-
-                        let progress = super::sftp::FileTransferProgress {
-                            bytes_transferred,
-                            total_bytes,
-                            percentage,
-                            transfer_rate,
-                            file_name: progress_key.clone(),
-                        };
-
-                        // Emit progress event to frontend
-                        let _ = handle.emit("file-upload-progress", progress);
-                    }) as super::sftp::ProgressCallback
-                });
-
                 let result = sftp.upload_file(
                     std::path::Path::new(local_path),
                     remote_path,
+                    cancel,
+                    self.upload_rate_limit(),
                     progress_callback
                 );
 
@@ -221,24 +509,153 @@ impl ConnectionManager {
             }
             None => {
                 log_error!(category: "SFTP", message: "Not connected to cluster");
-                Err(anyhow::anyhow!("Please connect to the cluster first"))
+                Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
+            }
+        }
+    }
+
+    /// Upload several files concurrently over the one session, emitting per-file progress events
+    /// The whole batch is retried on a transient failure (remote files are recreated), including
+    /// a checksum mismatch when `verify_checksum` is set
+    pub async fn upload_files_parallel(
+        &self,
+        files: Vec<UploadTask>,
+        max_concurrency: usize,
+        app_handle: Option<tauri::AppHandle>,
+        verify_checksum: bool,
+    ) -> Result<BatchUploadResult> {
+        if demo::is_demo_mode() {
+            let start_time = Instant::now();
+            return demo::upload_files(&files, |file| app_handle.clone().map(|handle| {
+                let throttle = Arc::new(std::sync::Mutex::new(ProgressThrottle::new(PROGRESS_EMIT_INTERVAL)));
+                upload_progress_callback(handle, file.progress_key.clone(), start_time, throttle, None)
+            })).await;
+        }
+
+        let mut local_hashes = Vec::new();
+        if verify_checksum {
+            for file in &files {
+                local_hashes.push(checksum::sha256_file(&file.local_path).await?);
+            }
+        }
+        let (files, local_hashes) = (&files, &local_hashes);
+
+        let reconnect_attempted = &AtomicBool::new(false);
+        self.retry_transfer(|| {
+            let app_handle = app_handle.clone();
+            async move {
+                let batch = self.upload_files_parallel_once(files, max_concurrency, app_handle, reconnect_attempted).await?;
+                for (file, local_hash) in files.iter().zip(local_hashes) {
+                    self.verify_remote_checksum(&file.remote_path, local_hash).await?;
+                }
+                Ok(batch)
+            }
+        }).await
+    }
+
+    async fn upload_files_parallel_once(
+        &self,
+        files: &[UploadTask],
+        max_concurrency: usize,
+        app_handle: Option<tauri::AppHandle>,
+        reconnect_attempted: &AtomicBool,
+    ) -> Result<BatchUploadResult> {
+        let mut conn = self.connection.lock().await;
+        if conn.as_ref().is_some_and(|c| !c.is_connected()) {
+            let _ = self.try_reconnect(&mut conn, reconnect_attempted).await;
+        }
+        match conn.as_mut() {
+            Some(connection) => {
+                if !connection.is_connected() {
+                    log_error!(category: "SFTP", message: "SSH connection is no longer active");
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
+                }
+                log_info!(category: "SFTP", message: "Uploading files in parallel", details: "{} files, up to {} at once", files.len(), max_concurrency);
+
+                connection.set_file_transfer_timeout()?;
+
+                let session = connection.get_session()?;
+                let sftp = super::sftp::SFTPOperations::new(session);
+                let start_time = Instant::now();
+                let mut throttles: Vec<ProgressThrottle> = files.iter().map(|_| ProgressThrottle::new(PROGRESS_EMIT_INTERVAL)).collect();
+
+                let result = sftp.upload_files_concurrent(files, max_concurrency, self.upload_rate_limit(), |index, bytes_transferred, total_bytes| {
+                    let Some(handle) = &app_handle else { return };
+                    if !throttles[index].should_emit(bytes_transferred, total_bytes, Instant::now()) {
+                        return;
+                    }
+
+                    let elapsed = start_time.elapsed().as_secs_f64();
+                    let progress = FileTransferProgress {
+                        bytes_transferred,
+                        total_bytes,
+                        percentage: if total_bytes > 0 { (bytes_transferred as f32 / total_bytes as f32) * 100.0 } else { 0.0 },
+                        transfer_rate: if elapsed > 0.0 { bytes_transferred as f64 / elapsed } else { 0.0 },
+                        file_name: files[index].progress_key.clone(),
+                    };
+                    let _ = handle.emit("file-upload-progress", progress);
+                });
+
+                // Reset to command timeout after operation (regardless of success/failure)
+                connection.reset_command_timeout()?;
+
+                let batch = result?;
+                log_info!(category: "SFTP", message: "Parallel upload complete", details: "{} bytes in {} files", batch.total.bytes_transferred, batch.files.len());
+                Ok(batch)
+            }
+            None => {
+                log_error!(category: "SFTP", message: "Not connected to cluster");
+                Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
             }
         }
     }
 
     /// Download a file using the current connection
-    pub async fn download_file(&self, remote_path: &str, local_path: &str) -> Result<FileTransferProgress> {
+    /// `resume` continues an existing partial local file; retries after a failed attempt always
+    /// resume, since the partial file is then our own
+    /// With `verify_checksum`, the remote sha256sum is taken before the transfer and must match the local file after
+    pub async fn download_file(
+        &self,
+        remote_path: &str,
+        local_path: &str,
+        resume: bool,
+        cancel: Option<CancelToken>,
+        verify_checksum: bool,
+    ) -> Result<FileTransferProgress> {
+        let cancel = cancel.as_deref();
+        if demo::is_demo_mode() {
+            return demo::download_file(remote_path, local_path, cancel).await;
+        }
+        let remote_hash = if verify_checksum { Some(self.remote_sha256(remote_path).await?) } else { None };
+        let remote_hash = remote_hash.as_deref();
+
         // Use retry logic for file downloads
-        retry_files(|| self.download_file_once(remote_path, local_path)).await
+        let attempted = &AtomicBool::new(false);
+        self.retry_transfer(|| {
+            let resume = resume || attempted.swap(true, Ordering::SeqCst);
+            async move {
+                let progress = self.download_file_once(remote_path, local_path, resume, cancel).await?;
+                if let Some(remote_hash) = remote_hash {
+                    let local_hash = checksum::sha256_file(Path::new(local_path)).await?;
+                    if let Err(e) = checksum::compare(remote_path, &local_hash, remote_hash) {
+                        log_error!(category: "SFTP", message: "Checksum mismatch after download", details: "{}", e);
+                        // Resuming would keep the corrupt bytes - the retry starts over
+                        let _ = std::fs::remove_file(local_path);
+                        return Err(e.into());
+                    }
+                }
+                Ok(progress)
+            }
+        }).await
     }
 
-    async fn download_file_once(&self, remote_path: &str, local_path: &str) -> Result<FileTransferProgress> {
+    async fn download_file_once(&self, remote_path: &str, local_path: &str, resume: bool, cancel: Option<&AtomicBool>) -> Result<FileTransferProgress> {
         let mut conn = self.connection.lock().await;
         match conn.as_mut() {
             Some(connection) => {
                 if !connection.is_connected() {
                     log_error!(category: "SFTP", message: "SSH connection is no longer active");
-                    return Err(anyhow::anyhow!("SSH connection is no longer active"));
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
                 }
                 log_info!(category: "SFTP", message: "Downloading file", details: "{} -> {}", remote_path, local_path);
 
@@ -247,7 +664,7 @@ impl ConnectionManager {
 
                 let session = connection.get_session()?;
                 let sftp = super::sftp::SFTPOperations::new(session);
-                let result = sftp.download_file(remote_path, std::path::Path::new(local_path), None);
+                let result = sftp.download_file(remote_path, std::path::Path::new(local_path), resume, cancel, None);
 
                 // Reset to command timeout after operation (regardless of success/failure)
                 connection.reset_command_timeout()?;
@@ -258,16 +675,119 @@ impl ConnectionManager {
             }
             None => {
                 log_error!(category: "SFTP", message: "Not connected to cluster");
-                Err(anyhow::anyhow!("Please connect to the cluster first"))
+                Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
             }
         }
     }
 
+    /// Download remote files into one local ZIP archive, streaming each file straight into it
+    /// Nothing is staged on the cluster and memory stays bounded by the read buffer.
+    /// Not retried, since a retry would have to start the archive over; a failed or
+    /// cancelled download removes the partial archive
+    pub async fn download_as_zip(
+        &self,
+        entries: &[ArchiveEntry],
+        local_path: &str,
+        compression_level: u8,
+        cancel: Option<CancelToken>,
+        progress_callback: &(dyn Fn(usize, u64) + Sync),
+    ) -> Result<u64> {
+        let result = if demo::is_demo_mode() {
+            demo::download_as_zip(entries, local_path, compression_level, cancel.as_deref(), progress_callback).await
+        } else {
+            self.download_as_zip_once(entries, local_path, compression_level, cancel.as_deref(), progress_callback).await
+        };
+        if result.is_err() {
+            let _ = std::fs::remove_file(local_path);
+        }
+        result
+    }
+
+    async fn download_as_zip_once(
+        &self,
+        entries: &[ArchiveEntry],
+        local_path: &str,
+        compression_level: u8,
+        cancel: Option<&AtomicBool>,
+        progress_callback: &(dyn Fn(usize, u64) + Sync),
+    ) -> Result<u64> {
+        let mut conn = self.connection.lock().await;
+        match conn.as_mut() {
+            Some(connection) => {
+                if !connection.is_connected() {
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
+                }
+                log_info!(category: "SFTP", message: "Downloading archive", details: "{} files -> {}", entries.len(), local_path);
+
+                let file = std::fs::File::create(local_path)
+                    .map_err(|e| SSHError::FileTransferError(format!("Failed to create local file: {}", e)))?;
+                let mut zip = ZipStreamWriter::new(std::io::BufWriter::new(file), compression_level);
+
+                connection.set_file_transfer_timeout()?;
+                let session = connection.get_session()?;
+                let sftp = super::sftp::SFTPOperations::new(session);
+                let result = sftp.download_into_zip(entries, &mut zip, cancel, progress_callback);
+                connection.reset_command_timeout()?;
+
+                let bytes_done = result?;
+                zip.finish()
+                    .map_err(|e| SSHError::FileTransferError(format!("Failed to finish archive: {}", e)))?;
+                log_info!(category: "SFTP", message: "Archive download complete", details: "{} bytes from {} files", bytes_done, entries.len());
+                Ok(bytes_done)
+            }
+            None => Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
+        }
+    }
+
+    /// Read what a remote file gained since `from_offset` (an SFTP seek, not a re-read of the file)
+    /// Returns the new content and the offset for the next poll; see `sftp::read_tail` for
+    /// truncation and size limits
+    pub async fn tail_remote_file(&self, remote_path: &str, from_offset: u64) -> Result<RemoteFileTail> {
+        if demo::is_demo_mode() {
+            return demo::tail_file(remote_path, from_offset, super::sftp::MAX_TAIL_BYTES);
+        }
+        self.retry_command(|| self.tail_remote_file_once(remote_path, from_offset, super::sftp::MAX_TAIL_BYTES)).await
+    }
+
+    /// Read the first `max_bytes` of a remote file over SFTP, with its total size
+    /// `has_more` is set when the file is longer than what was read
+    pub async fn read_remote_head(&self, remote_path: &str, max_bytes: u64) -> Result<RemoteFileTail> {
+        if demo::is_demo_mode() {
+            return demo::tail_file(remote_path, 0, max_bytes);
+        }
+        self.retry_command(|| self.tail_remote_file_once(remote_path, 0, max_bytes)).await
+    }
+
+    async fn tail_remote_file_once(&self, remote_path: &str, from_offset: u64, max_bytes: u64) -> Result<RemoteFileTail> {
+        let mut conn = self.connection.lock().await;
+        match conn.as_mut() {
+            Some(connection) => {
+                if !connection.is_connected() {
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
+                }
+
+                connection.set_file_transfer_timeout()?;
+
+                let session = connection.get_session()?;
+                let sftp = super::sftp::SFTPOperations::new(session);
+                let result = sftp.tail_file(remote_path, from_offset, max_bytes);
+
+                connection.reset_command_timeout()?;
+
+                result
+            }
+            None => Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
+        }
+    }
+
     /// List files in a directory using the current connection
     /// If include_directories is false, only regular files are returned
     pub async fn list_files(&self, remote_path: &str, include_directories: bool) -> Result<Vec<SftpFileEntry>> {
+        if demo::is_demo_mode() {
+            return demo::list_files(remote_path, include_directories);
+        }
         // Use retry logic for directory listing
-        retry_quick(|| self.list_files_once(remote_path, include_directories)).await
+        self.retry_command(|| self.list_files_once(remote_path, include_directories)).await
     }
 
     async fn list_files_once(&self, remote_path: &str, include_directories: bool) -> Result<Vec<SftpFileEntry>> {
@@ -275,7 +795,7 @@ impl ConnectionManager {
         match conn.as_mut() {
             Some(connection) => {
                 if !connection.is_connected() {
-                    return Err(anyhow::anyhow!("SSH connection is no longer active"));
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
                 }
 
                 // Set file transfer timeout before SFTP operation
@@ -295,14 +815,85 @@ impl ConnectionManager {
 
                 Ok(result)
             }
-            None => Err(anyhow::anyhow!("Please connect to the cluster first"))
+            None => Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
         }
     }
 
+    /// Upload a local directory tree into `remote_dir`, creating subdirectories (empty ones too)
+    /// Symlinks are skipped. Progress is rolled up across all files into one `file-upload-progress`
+    /// stream under `progress_key`, and the returned progress covers the whole tree.
+    /// Cancelling stops at the current file and removes its partial copy; finished files stay
+    pub async fn upload_directory(
+        &self,
+        local_dir: &str,
+        remote_dir: &str,
+        app_handle: Option<tauri::AppHandle>,
+        progress_key: Option<String>,
+        cancel: Option<CancelToken>,
+    ) -> Result<FileTransferProgress> {
+        let registration = progress_key.as_deref().map(|key| cancellation::register(key, cancel.clone()));
+        let cancel = registration.as_ref().map(|r| r.token()).or(cancel);
+        let cancel = cancel.as_deref();
+
+        let plan = super::sftp::plan_directory_upload(Path::new(local_dir), remote_dir)?;
+        log_info!(category: "SFTP", message: "Uploading directory", details: "{} -> {} ({} files, {} directories, {} bytes)",
+            local_dir, remote_dir, plan.files.len(), plan.directories.len(), plan.total_bytes);
+        for link in &plan.skipped_symlinks {
+            log_warn!(category: "SFTP", message: "Skipping symlink in directory upload", details: "{}", link.display());
+        }
+
+        for directory in &plan.directories {
+            let result = self.create_directory(directory).await?;
+            if result.exit_code != 0 {
+                return Err(SSHError::FileTransferError(format!("Failed to create remote directory {}: {}", directory, result.stderr.trim())).into());
+            }
+        }
+
+        let start_time = Instant::now();
+        let throttle = Arc::new(std::sync::Mutex::new(ProgressThrottle::new(PROGRESS_EMIT_INTERVAL)));
+        let reconnect_attempted = &AtomicBool::new(false);
+        let mut bytes_done = 0u64;
+
+        for file in &plan.files {
+            let local_path = file.local_path.to_string_lossy();
+            let rollup = Some((bytes_done, plan.total_bytes));
+            let progress = self.retry_transfer(|| {
+                let progress_callback = app_handle.clone()
+                    .map(|handle| upload_progress_callback(handle, progress_key.clone(), start_time, throttle.clone(), rollup));
+                let local_path = &local_path;
+                async move {
+                    self.upload_file_once(local_path, &file.remote_path, cancel, progress_callback, reconnect_attempted).await
+                }
+            }).await?;
+            bytes_done += progress.bytes_transferred;
+        }
+
+        let elapsed = start_time.elapsed().as_secs_f64();
+        let total = FileTransferProgress {
+            bytes_transferred: bytes_done,
+            total_bytes: plan.total_bytes,
+            percentage: if plan.total_bytes > 0 { (bytes_done as f32 / plan.total_bytes as f32) * 100.0 } else { 100.0 },
+            transfer_rate: if elapsed > 0.0 { bytes_done as f64 / elapsed } else { 0.0 },
+            file_name: progress_key,
+        };
+        // An empty tree never triggers a file callback, so report its completion directly
+        if plan.files.is_empty() {
+            if let Some(handle) = &app_handle {
+                let _ = handle.emit("file-upload-progress", total.clone());
+            }
+        }
+
+        log_info!(category: "SFTP", message: "Directory upload complete", details: "{} bytes in {} files", bytes_done, plan.files.len());
+        Ok(total)
+    }
+
     /// Create a directory using SSH mkdir -p command
     pub async fn create_directory(&self, remote_path: &str) -> Result<CommandResult> {
+        if demo::is_demo_mode() {
+            return demo::create_directory(remote_path);
+        }
         // Use retry logic for directory creation
-        retry_quick(|| self.create_directory_once(remote_path)).await
+        self.retry_command(|| self.create_directory_once(remote_path)).await
     }
 
     async fn create_directory_once(&self, remote_path: &str) -> Result<CommandResult> {
@@ -316,6 +907,9 @@ impl ConnectionManager {
 
     /// Delete a directory and all its contents using SSH command
     pub async fn delete_directory(&self, remote_path: &str) -> Result<CommandResult> {
+        if demo::is_demo_mode() {
+            return demo::delete_directory(remote_path);
+        }
         // Use rm -rf command for directory deletion with retry logic
         log_info!(category: "SSH", message: "Deleting directory", details: "{}", remote_path);
         let rm_command = format!("rm -rf {}", crate::security::shell::escape_parameter(remote_path));
@@ -324,6 +918,64 @@ impl ConnectionManager {
         Ok(result)
     }
 
+    /// Rename or move a remote file or directory
+    /// Refuses to overwrite an existing destination. SFTP rename cannot cross filesystems
+    /// (e.g. project to scratch), so a failed rename falls back to a cluster-side copy and delete
+    pub async fn rename_remote_file(&self, old_path: &str, new_path: &str) -> Result<()> {
+        if demo::is_demo_mode() {
+            return demo::rename(old_path, new_path);
+        }
+        if self.file_exists(new_path).await? {
+            return Err(anyhow!("Destination already exists: {}", new_path));
+        }
+        if !self.file_exists(old_path).await? {
+            return Err(anyhow!("Source does not exist: {}", old_path));
+        }
+
+        log_info!(category: "SSH", message: "Renaming remote path", details: "{} -> {}", old_path, new_path);
+        let rename_error = match self.retry_command(|| self.rename_once(old_path, new_path)).await {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+
+        log_debug!(category: "SSH", message: "Rename failed, falling back to copy and delete", details: "{}", rename_error);
+        let copy_command = format!(
+            "cp -a {} {} && rm -rf {}",
+            crate::security::shell::escape_parameter(old_path),
+            crate::security::shell::escape_parameter(new_path),
+            crate::security::shell::escape_parameter(old_path)
+        );
+        let result = self.execute_command(&copy_command, Some(crate::cluster::timeouts::DEFAULT_COMMAND)).await?;
+        if result.exit_code != 0 {
+            return Err(anyhow!("Failed to move {} to {}: {} (rename failed: {})", old_path, new_path, result.stderr.trim(), rename_error));
+        }
+
+        log_info!(category: "SSH", message: "Moved remote path by copy", details: "{} -> {}", old_path, new_path);
+        Ok(())
+    }
+
+    async fn rename_once(&self, old_path: &str, new_path: &str) -> Result<()> {
+        let mut conn = self.connection.lock().await;
+        match conn.as_mut() {
+            Some(connection) => {
+                if !connection.is_connected() {
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
+                }
+
+                connection.set_file_transfer_timeout()?;
+
+                let session = connection.get_session()?;
+                let sftp = super::sftp::SFTPOperations::new(session);
+                let result = sftp.rename(old_path, new_path);
+
+                connection.reset_command_timeout()?;
+
+                result
+            }
+            None => Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
+        }
+    }
+
     /// Sync directory from source to destination using rsync (cluster-side operation)
     ///
     /// Uses rsync to efficiently mirror directory contents. Only copies changed files on repeat syncs.
@@ -342,6 +994,9 @@ impl ConnectionManager {
     /// manager.mirror_directory(&source, &dest).await?;
     /// ```
     pub async fn mirror_directory(&self, source: &str, destination: &str) -> Result<CommandResult> {
+        if demo::is_demo_mode() {
+            return demo::mirror_directory(source, destination);
+        }
         log_info!(category: "SSH", message: "Syncing directory", details: "{} -> {}", source, destination);
 
         // Use rsync with archive mode and compression
@@ -360,10 +1015,34 @@ impl ConnectionManager {
         Ok(result)
     }
 
+    /// Total size in bytes and recursive file count of a remote directory
+    /// Only the connected user's job directories (and what's in them) are measured.
+    /// Unreadable subdirectories give a partial result with a warning rather than an error
+    pub async fn remote_dir_size(&self, path: &str) -> Result<crate::types::response_data::RemoteDirSize> {
+        use crate::ssh::directory_structure::JobDirectoryStructure;
+
+        let mut allowed_prefixes = JobDirectoryStructure::project_allowed_prefixes();
+        allowed_prefixes.extend(JobDirectoryStructure::scratch_allowed_prefixes());
+        crate::security::input::validate_path_safety(path, &allowed_prefixes)?;
+        let username = self.get_username().await?;
+        super::paths::confine_to_job_directories(path, &username)?;
+
+        let command = super::commands::directory_size_and_count_command(path);
+        let result = self.execute_command(&command, Some(crate::cluster::timeouts::DEFAULT_COMMAND)).await?;
+        let size = super::commands::parse_directory_size_and_count(&result.stdout, &result.stderr, path)?;
+        if let Some(warning) = &size.warning {
+            log_warn!(category: "SSH", message: "Directory size is partial", details: "{}", warning);
+        }
+        Ok(size)
+    }
+
     /// Check if a file or directory exists
     pub async fn file_exists(&self, remote_path: &str) -> Result<bool> {
+        if demo::is_demo_mode() {
+            return demo::file_exists(remote_path);
+        }
         // Use retry logic for existence checking
-        retry_quick(|| self.file_exists_once(remote_path)).await
+        self.retry_command(|| self.file_exists_once(remote_path)).await
     }
 
     async fn file_exists_once(&self, remote_path: &str) -> Result<bool> {
@@ -371,7 +1050,7 @@ impl ConnectionManager {
         match conn.as_mut() {
             Some(connection) => {
                 if !connection.is_connected() {
-                    return Err(anyhow::anyhow!("SSH connection is no longer active"));
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
                 }
 
                 // Set file transfer timeout before SFTP operation
@@ -402,42 +1081,145 @@ impl ConnectionManager {
 
                 stat_result
             }
-            None => Err(anyhow::anyhow!("Please connect to the cluster first"))
+            None => Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
         }
     }
 
     /// Get the username of the current connection
     pub async fn get_username(&self) -> Result<String> {
+        if demo::is_demo_mode() {
+            return demo::session_info()
+                .map(|info| info.username)
+                .ok_or_else(|| SSHError::SessionError("Please connect to the cluster first".to_string()).into());
+        }
         let conn = self.connection.lock().await;
         match conn.as_ref() {
             Some(connection) => {
                 if !connection.is_connected() {
-                    return Err(anyhow::anyhow!("SSH connection is no longer active"));
+                    return Err(SSHError::SessionError("SSH connection is no longer active".to_string()).into());
                 }
                 Ok(connection.get_username().to_string())
             }
-            None => Err(anyhow::anyhow!("Please connect to the cluster first"))
+            None => Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into())
         }
     }
 
     /// Read content from a remote file
+    /// Gzip-compressed files (by extension or magic bytes) are decompressed on the server
     pub async fn read_remote_file(&self, remote_path: &str) -> Result<String> {
-        let command = format!("cat '{}'", remote_path.replace('\'', "'\\''"));
+        if demo::is_demo_mode() {
+            return demo::read_file(remote_path);
+        }
+        let command = super::commands::read_text_file_command(remote_path);
         self.execute_command(&command, None).await.map(|result| result.stdout)
     }
 
+    /// Read several small text files with one remote command (one round trip for the lot)
+    /// The outer error is for the command itself; each file then succeeds or fails on its own
+    pub async fn read_remote_files(&self, remote_paths: &[String]) -> Result<Vec<Result<String>>> {
+        if demo::is_demo_mode() {
+            return Ok(remote_paths.iter().map(|path| demo::read_file(path)).collect());
+        }
+        let command = super::commands::read_files_command(remote_paths);
+        let result = self.execute_command(&command, None).await?;
+        Ok(super::commands::parse_read_files_output(&result.stdout, remote_paths.len())
+            .into_iter()
+            .map(|read| read.map_err(|e| anyhow::anyhow!(e)))
+            .collect())
+    }
+
     /// Send keepalive to maintain the connection
     pub async fn keepalive(&self) -> Result<()> {
         let conn = self.connection.lock().await;
         match conn.as_ref() {
             Some(connection) => {
-                connection.keepalive().await
+                connection.keepalive().await?;
+                self.touch_activity();
+                Ok(())
             }
             None => Ok(()) // No connection to keep alive
         }
     }
 }
 
+/// Minimum spacing between transfer progress events (~10 per second)
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Coalesces rapid transfer progress updates into time-spaced emissions
+/// The final update (all bytes transferred) always passes so the UI reaches 100%
+struct ProgressThrottle {
+    interval: Duration,
+    last_emit: Option<Instant>,
+}
+
+impl ProgressThrottle {
+    fn new(interval: Duration) -> Self {
+        Self { interval, last_emit: None }
+    }
+
+    fn should_emit(&mut self, bytes_transferred: u64, total_bytes: u64, now: Instant) -> bool {
+        let is_final = bytes_transferred >= total_bytes;
+        let interval_elapsed = self.last_emit
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.interval);
+
+        if is_final || interval_elapsed {
+            self.last_emit = Some(now);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Progress callback emitting throttled `file-upload-progress` events
+/// Chunks arrive every 256KB, so events are coalesced to avoid flooding the frontend.
+/// `rollup` reports the file as part of a larger transfer: (bytes finished before it, grand total)
+fn upload_progress_callback(
+    handle: tauri::AppHandle,
+    progress_key: Option<String>,
+    start_time: Instant,
+    throttle: Arc<std::sync::Mutex<ProgressThrottle>>,
+    rollup: Option<(u64, u64)>,
+) -> ProgressCallback {
+    Box::new(move |file_bytes: u64, file_total: u64| {
+        let (bytes_transferred, total_bytes) = match rollup {
+            Some((offset, total)) => (offset + file_bytes, total),
+            None => (file_bytes, file_total),
+        };
+
+        let should_emit = throttle.lock()
+            .map(|mut t| t.should_emit(bytes_transferred, total_bytes, Instant::now()))
+            .unwrap_or(true);
+        if !should_emit {
+            return;
+        }
+
+        let elapsed = start_time.elapsed().as_secs_f64();
+        let transfer_rate = if elapsed > 0.0 {
+            bytes_transferred as f64 / elapsed // bytes per second
+        } else {
+            0.0
+        };
+
+        let percentage = if total_bytes > 0 {
+            (bytes_transferred as f32 / total_bytes as f32) * 100.0
+        } else {
+            0.0
+        };
+
+        let progress = FileTransferProgress {
+            bytes_transferred,
+            total_bytes,
+            percentage,
+            transfer_rate,
+            file_name: progress_key.clone(),
+        };
+
+        // Emit progress event to frontend
+        let _ = handle.emit("file-upload-progress", progress);
+    })
+}
+
 impl Drop for ConnectionManager {
     fn drop(&mut self) {
         // Best-effort cleanup when manager is dropped
@@ -449,6 +1231,12 @@ impl Drop for ConnectionManager {
     }
 }
 
+fn emit_reconnect(host: &str, status: ReconnectStatus, message: Option<String>) {
+    if let Some(app_handle) = crate::logging::get_app_handle() {
+        let _ = app_handle.emit(RECONNECT_EVENT, ReconnectEvent { host: host.to_string(), status, message });
+    }
+}
+
 impl Default for ConnectionManager {
     fn default() -> Self {
         Self::new()
@@ -470,14 +1258,28 @@ where
     retry_with_backoff(operation, 2, 200, 2000, 2.0).await
 }
 
-/// Retry file transfer operations (uploads, downloads)
-/// Max 5 attempts, 2s base delay, patient retry for large file transfers
-async fn retry_files<T, F, Fut>(operation: F) -> Result<T>
-where
-    F: FnMut() -> Fut,
-    Fut: Future<Output = Result<T>>,
-{
-    retry_with_backoff(operation, 5, 2000, 60000, 1.5).await
+impl ConnectionManager {
+    /// Retry quick operations using the active connection's attempt count
+    /// Same backoff as retry_quick (200ms base delay)
+    async fn retry_command<T, F, Fut>(&self, operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let attempts = self.command_retry_attempts.load(Ordering::SeqCst);
+        retry_with_backoff(operation, attempts, 200, 2000, 2.0).await
+    }
+
+    /// Retry file transfer operations (uploads, downloads)
+    /// 2s base delay, patient retry for large file transfers; attempt count from the active config
+    async fn retry_transfer<T, F, Fut>(&self, operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let attempts = self.transfer_retry_attempts.load(Ordering::SeqCst);
+        retry_with_backoff(operation, attempts, 2000, 60000, 1.5).await
+    }
 }
 
 /// Core retry implementation with exponential backoff and jitter
@@ -529,31 +1331,23 @@ where
 }
 
 /// Determine if an error is transient and worth retrying
+/// The first cause in the chain with a structured answer decides: a typed SSHError variant,
+/// an I/O error kind or a libssh2 error code. Otherwise the whole chain's text goes through
+/// the keyword fallback (errors::is_transient_message)
 fn is_transient_error(error: &anyhow::Error) -> bool {
-    let error_msg = error.to_string().to_lowercase();
-
-    // Network-related errors that are often transient
-    if error_msg.contains("timeout") ||
-       error_msg.contains("connection") ||
-       error_msg.contains("network") ||
-       error_msg.contains("temporary") ||
-       error_msg.contains("busy") ||
-       error_msg.contains("unavailable") ||
-       error_msg.contains("interrupted") ||
-       error_msg.contains("broken pipe") {
-        return true;
-    }
-
-    // Authentication and permission errors are permanent
-    if error_msg.contains("authentication") ||
-       error_msg.contains("permission") ||
-       error_msg.contains("access denied") ||
-       error_msg.contains("unauthorized") {
-        return false;
-    }
-
-    // Default to not retrying for unknown errors
-    false
+    use super::errors::{io_error_is_transient, is_transient_message, ssh2_error_is_transient};
+
+    let structured = error.chain().find_map(|cause| {
+        if let Some(ssh_error) = cause.downcast_ref::<SSHError>() {
+            ssh_error.is_transient()
+        } else if let Some(io_error) = cause.downcast_ref::<std::io::Error>() {
+            io_error_is_transient(io_error.kind())
+        } else {
+            cause.downcast_ref::<ssh2::Error>().and_then(ssh2_error_is_transient)
+        }
+    });
+
+    structured.unwrap_or_else(|| is_transient_message(&format!("{:#}", error)))
 }
 
 #[cfg(test)]
@@ -583,11 +1377,109 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Please connect to the cluster"));
     }
 
+    #[tokio::test]
+    async fn test_ping_without_connection() {
+        let manager = ConnectionManager::new();
+        let result = manager.ping(None).await;
+        assert!(result.unwrap_err().to_string().contains("Please connect to the cluster"));
+    }
+
     #[tokio::test]
     async fn test_keepalive_without_connection() {
         let manager = ConnectionManager::new();
         // Should succeed (no-op) even without connection
         assert!(manager.keepalive().await.is_ok());
+        // A no-op keepalive is not a sign of life
+        assert!(manager.last_activity().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_keepalive_without_connection() {
+        let manager = ConnectionManager::new();
+        assert!(!manager.set_keepalive_interval(15).await);
+    }
+
+    fn unreachable_target(auto_reconnect: bool) -> ReconnectTarget {
+        ReconnectTarget {
+            host: "nonexistent.host.test".to_string(),
+            port: 22,
+            username: "testuser".to_string(),
+            password: SecurePassword::from_str("hunter2"),
+            config: ConnectionConfig { auto_reconnect, ..ConnectionConfig::default() },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_attempted_once_per_operation() {
+        let manager = ConnectionManager::new();
+        *manager.reconnect_target.lock().unwrap() = Some(unreachable_target(true));
+        let attempted = AtomicBool::new(false);
+        let mut conn = None;
+
+        assert!(matches!(manager.try_reconnect(&mut conn, &attempted).await, Some(Err(_))));
+        // Retries of the same operation don't dial again
+        assert!(manager.try_reconnect(&mut conn, &attempted).await.is_none());
+        assert!(conn.is_none());
+
+        // The next operation gets its own attempt
+        assert!(manager.try_reconnect(&mut conn, &AtomicBool::new(false)).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_no_reconnect_without_saved_target() {
+        let manager = ConnectionManager::new();
+        let attempted = AtomicBool::new(false);
+
+        assert!(manager.try_reconnect(&mut None, &attempted).await.is_none());
+        // Not spent - nothing was tried
+        assert!(!attempted.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_forgets_reconnect_target() {
+        let manager = ConnectionManager::new();
+        *manager.reconnect_target.lock().unwrap() = Some(unreachable_target(true));
+
+        manager.disconnect().await.unwrap();
+
+        assert!(manager.reconnect_target.lock().unwrap().is_none());
+        assert!(manager.try_reconnect(&mut None, &AtomicBool::new(false)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_connect_keeps_no_reconnect_target() {
+        let manager = ConnectionManager::new();
+        let password = SecurePassword::from_str("hunter2");
+        let config = ConnectionConfig { auto_reconnect: true, ..ConnectionConfig::default() };
+
+        let result = manager.connect("nonexistent.host.test".to_string(), 22, "testuser".to_string(), &password, config).await;
+
+        assert!(result.is_err());
+        assert!(manager.reconnect_target.lock().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_upload_files_parallel_without_connection() {
+        let manager = ConnectionManager::new();
+        let files = vec![UploadTask { local_path: "/local/a.pdb".into(), remote_path: "/remote/a.pdb".to_string(), progress_key: None }];
+
+        let result = manager.upload_files_parallel(files, 4, None, false).await;
+        assert!(result.unwrap_err().to_string().contains("Please connect to the cluster"));
+    }
+
+    #[tokio::test]
+    async fn test_expire_and_disconnect_state() {
+        let manager = ConnectionManager::new();
+        assert!(!manager.is_expired());
+        assert!(manager.idle_expiry_timeout().await.is_none());
+
+        manager.expire().await.unwrap();
+        assert!(manager.is_expired());
+        assert!(!manager.is_connected().await);
+
+        // An explicit disconnect clears the expired marker
+        manager.disconnect().await.unwrap();
+        assert!(!manager.is_expired());
     }
 
     #[tokio::test]
@@ -595,15 +1487,24 @@ mod tests {
         let manager = ConnectionManager::new();
 
         // Test upload without connection
-        let upload_result = manager.upload_file("/local/file.txt", "/remote/file.txt", None, None).await;
+        let upload_result = manager.upload_file("/local/file.txt", "/remote/file.txt", None, None, None, false).await;
         assert!(upload_result.is_err());
         assert!(upload_result.unwrap_err().to_string().contains("Please connect to the cluster"));
 
+        // Test directory upload without connection
+        let local_dir = tempfile::tempdir().unwrap();
+        let directory_result = manager.upload_directory(&local_dir.path().to_string_lossy(), "/remote/dir", None, None, None).await;
+        assert!(directory_result.unwrap_err().to_string().contains("Please connect to the cluster"));
+
         // Test download without connection
-        let download_result = manager.download_file("/remote/file.txt", "/local/file.txt").await;
+        let download_result = manager.download_file("/remote/file.txt", "/local/file.txt", false, None, false).await;
         assert!(download_result.is_err());
         assert!(download_result.unwrap_err().to_string().contains("Please connect to the cluster"));
 
+        // Test log tail without connection
+        let tail_result = manager.tail_remote_file("/remote/job.out", 0).await;
+        assert!(tail_result.unwrap_err().to_string().contains("Please connect to the cluster"));
+
         // Test list files without connection
         let list_result = manager.list_files("/home/user", true).await;
         assert!(list_result.is_err());
@@ -635,6 +1536,26 @@ mod tests {
 
 
 
+    #[tokio::test]
+    async fn test_connect_with_key_failure_leaves_disconnected() {
+        let manager = ConnectionManager::new();
+        let passphrase = SecurePassword::from_str("key passphrase");
+
+        let result = manager.connect_with_key(
+            "nonexistent.host.test".to_string(),
+            22,
+            "testuser".to_string(),
+            Some(Path::new("/home/testuser/.ssh/id_ed25519")),
+            Some(&passphrase),
+            ConnectionConfig::default()
+        ).await;
+
+        // Fails at the network stage, before the key is ever read
+        let err = result.unwrap_err();
+        assert!(matches!(super::super::errors::find_ssh_error(&err), Some(SSHError::NetworkError(_))));
+        assert!(!manager.is_connected().await);
+    }
+
     #[tokio::test]
     async fn test_secure_password_handling_in_connect() {
         // Test our SecurePassword integration without actual connection
@@ -647,7 +1568,8 @@ mod tests {
             "nonexistent.host.test".to_string(),
             22,
             "testuser".to_string(),
-            &password
+            &password,
+            ConnectionConfig::default()
         ).await;
 
         // Should fail due to network error, not password handling
@@ -657,6 +1579,67 @@ mod tests {
         assert!(!error_msg.contains("password"));
     }
 
+    #[tokio::test]
+    async fn test_connect_replaces_previous_session() {
+        let manager = ConnectionManager::new();
+        *manager.connection.lock().await = Some(SSHConnection::new(
+            "login.rc.colorado.edu".to_string(), 22, "typo_user".to_string(), ConnectionConfig::default()
+        ));
+        manager.touch_activity();
+        manager.expired.store(true, Ordering::SeqCst);
+
+        let result = manager.connect(
+            "nonexistent.host.test".to_string(),
+            22,
+            "real_user".to_string(),
+            &SecurePassword::from_str("password"),
+            ConnectionConfig::default()
+        ).await;
+
+        // The new login failed, but the old user's session and state must not survive it
+        assert!(result.is_err());
+        assert!(manager.get_connection_info().await.is_none());
+        assert!(manager.last_activity().is_none());
+        assert!(!manager.is_expired());
+    }
+
+    #[tokio::test]
+    async fn test_close_existing_reports_previous_session() {
+        let manager = ConnectionManager::new();
+        assert!(manager.close_existing().await.is_none());
+
+        *manager.connection.lock().await = Some(SSHConnection::new(
+            "login.rc.colorado.edu".to_string(), 22, "typo_user".to_string(), ConnectionConfig::default()
+        ));
+
+        let previous = manager.close_existing().await.unwrap();
+        assert_eq!(previous.username, "typo_user");
+        assert!(manager.connection.lock().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_connect_retains_no_password_copy() {
+        let manager = ConnectionManager::new();
+        let password = SecurePassword::from_str("hunter2-do-not-keep");
+
+        let result = manager.connect(
+            "nonexistent.host.test".to_string(),
+            22,
+            "testuser".to_string(),
+            &password,
+            ConnectionConfig::default()
+        ).await;
+        assert!(result.is_err());
+
+        // Connect only borrows the password; the caller's buffer is untouched
+        password.with_password(|pwd| assert_eq!(pwd, "hunter2-do-not-keep"));
+
+        // Nothing reachable from the manager holds the plaintext
+        let state = format!("{:?}", manager);
+        assert!(!state.contains("hunter2"));
+        assert!(!result.unwrap_err().to_string().contains("hunter2"));
+    }
+
     #[tokio::test]
     async fn test_connection_info_consistency() {
         let manager = ConnectionManager::new();
@@ -671,7 +1654,8 @@ mod tests {
             "invalid.host".to_string(),
             22,
             "user".to_string(),
-            &password
+            &password,
+            ConnectionConfig::default()
         ).await;
 
         // Should still be disconnected
@@ -691,7 +1675,8 @@ mod tests {
                 "test.host".to_string(),
                 22,
                 "user".to_string(),
-                &password
+                &password,
+                ConnectionConfig::default()
             ).await;
 
             // Manager goes out of scope here - should not panic
@@ -727,6 +1712,41 @@ mod tests {
         assert!(info.unwrap().is_none());
     }
 
+    // =========================================================================
+    // Progress Throttle Tests
+    // =========================================================================
+
+    #[test]
+    fn test_progress_throttle_coalesces_rapid_updates() {
+        let mut throttle = ProgressThrottle::new(Duration::from_millis(100));
+        let start = Instant::now();
+        let total = 100 * 256 * 1024;
+
+        // 256KB chunks arriving every 10ms: only every 10th update is spaced far enough apart
+        let emitted: Vec<u64> = (1..=50u64)
+            .filter(|i| throttle.should_emit(i * 256 * 1024, total, start + Duration::from_millis(i * 10)))
+            .collect();
+
+        assert_eq!(emitted, vec![1, 11, 21, 31, 41]);
+    }
+
+    #[test]
+    fn test_progress_throttle_always_emits_final_update() {
+        let mut throttle = ProgressThrottle::new(Duration::from_millis(100));
+        let start = Instant::now();
+
+        assert!(throttle.should_emit(512, 1024, start));
+        // Within the interval, intermediate updates are dropped but completion is not
+        assert!(!throttle.should_emit(768, 1024, start + Duration::from_millis(5)));
+        assert!(throttle.should_emit(1024, 1024, start + Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn test_progress_throttle_empty_file() {
+        let mut throttle = ProgressThrottle::new(Duration::from_millis(100));
+        assert!(throttle.should_emit(0, 0, Instant::now()));
+    }
+
     // =========================================================================
     // Retry Logic Tests
     // =========================================================================
@@ -801,6 +1821,55 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 1); // No retry for auth errors
     }
 
+    #[tokio::test]
+    async fn test_disconnected_errors_downcast_to_session_error() {
+        let manager = ConnectionManager::new();
+
+        let errors = vec![
+            manager.execute_command("echo test", None).await.unwrap_err(),
+            manager.list_files("/tmp", false).await.unwrap_err(),
+            manager.file_exists("/tmp").await.unwrap_err(),
+            manager.get_username().await.unwrap_err(),
+        ];
+
+        for error in errors {
+            assert!(
+                matches!(error.downcast_ref::<SSHError>(), Some(SSHError::SessionError(_))),
+                "Expected SessionError, got: {}", error
+            );
+        }
+    }
+
+    #[test]
+    fn test_transient_check_uses_typed_variant() {
+        // Typed variants win over message keywords
+        assert!(is_transient_error(&SSHError::TimeoutError("no response after 30s".to_string()).into()));
+        assert!(!is_transient_error(&SSHError::SessionError("connection no longer active".to_string()).into()));
+        assert!(!is_transient_error(&anyhow::Error::from(SSHError::AuthenticationError("network busy".to_string())).context("Connect failed")));
+
+        // Mixed-cause variants fall back to the message
+        assert!(is_transient_error(&SSHError::FileTransferError("write timeout".to_string()).into()));
+        assert!(!is_transient_error(&SSHError::FileTransferError("Failed to open local file".to_string()).into()));
+    }
+
+    #[tokio::test]
+    async fn test_retry_command_uses_configured_attempts() {
+        let manager = ConnectionManager::new();
+        manager.command_retry_attempts.store(3, Ordering::SeqCst);
+        let counter = Arc::new(AtomicU32::new(0));
+
+        let result: Result<()> = manager.retry_command(|| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err(anyhow::anyhow!("Connection timeout"))
+            }
+        }).await;
+
+        assert!(result.is_err());
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+    }
+
     #[test]
     fn test_transient_error_detection() {
         assert!(is_transient_error(&anyhow::anyhow!("Connection timeout")));
@@ -812,6 +1881,17 @@ mod tests {
         assert!(!is_transient_error(&anyhow::anyhow!("Permission denied")));
         assert!(!is_transient_error(&anyhow::anyhow!("Access denied")));
         assert!(!is_transient_error(&anyhow::anyhow!("Invalid configuration")));
+
+        // Structured causes decide before the message is read
+        let reset = anyhow::Error::from(std::io::Error::from(std::io::ErrorKind::ConnectionReset)).context("Upload failed");
+        assert!(is_transient_error(&reset));
+        let missing = anyhow::Error::from(std::io::Error::new(std::io::ErrorKind::NotFound, "connection timeout.log")).context("Upload failed");
+        assert!(!is_transient_error(&missing));
+
+        // The fallback sees the whole chain, not just the outermost context
+        let eof = anyhow::anyhow!("channel 0: unexpected EOF").context("Failed to execute command");
+        assert!(is_transient_error(&eof));
+        assert!(!is_transient_error(&anyhow::anyhow!("bad connection string")));
     }
 
     #[tokio::test]
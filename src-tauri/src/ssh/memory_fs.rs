@@ -0,0 +1,371 @@
+//! In-memory remote filesystem
+//!
+//! Backs `test_utils::MockRemoteExecutor` in tests and the demo-mode session (`demo.rs`),
+//! so both share one model of paths, listings and stat results.
+
+use std::collections::HashMap;
+use std::path::Path;
+use super::errors::SSHError;
+use super::sftp::SftpFileEntry;
+
+/// Permissions given to files and directories created through the mkdir/write helpers
+const FILE_PERMISSIONS: u32 = 0o644;
+const DIRECTORY_PERMISSIONS: u32 = 0o755;
+
+/// Mock file system state for SFTP operations testing
+#[derive(Debug, Clone, Default)]
+pub struct MockFileSystem {
+    pub files: HashMap<String, MockFile>,
+    pub directories: HashMap<String, Vec<String>>,
+}
+
+/// Mock file representation for testing
+#[derive(Debug, Clone)]
+pub struct MockFile {
+    pub size: u64,
+    pub permissions: u32,
+    pub is_directory: bool,
+    pub modified_time: u64,
+    pub content: Vec<u8>,
+}
+
+/// Drop trailing slashes so "/a/b/" and "/a/b" are the same entry
+fn normalize(path: &str) -> &str {
+    match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    }
+}
+
+fn parent_of(path: &str) -> Option<&str> {
+    Path::new(path).parent().and_then(|parent| parent.to_str())
+}
+
+fn name_of(path: &str) -> &str {
+    Path::new(path).file_name().and_then(|name| name.to_str()).unwrap_or(path)
+}
+
+fn not_found(path: &str) -> SSHError {
+    SSHError::FileTransferError(format!("File not found: {}", path))
+}
+
+/// Whether `candidate` is `path` itself or lies inside it
+fn is_within(candidate: &str, path: &str) -> bool {
+    candidate == path || candidate.strip_prefix(path).is_some_and(|rest| rest.starts_with('/') || path == "/")
+}
+
+impl MockFileSystem {
+    /// Create a new empty mock file system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a mock file to the file system
+    pub fn add_file(&mut self, path: &str, size: u64, permissions: u32) -> &mut Self {
+        let file = MockFile {
+            size,
+            permissions,
+            is_directory: false,
+            modified_time: 1234567890,
+            content: vec![0u8; size as usize],
+        };
+        self.files.insert(path.to_string(), file);
+        self.list_in_parent(path);
+        self
+    }
+
+    /// Add a mock directory to the file system
+    pub fn add_directory(&mut self, path: &str, permissions: u32) -> &mut Self {
+        let dir = MockFile {
+            size: 4096,
+            permissions,
+            is_directory: true,
+            modified_time: 1234567890,
+            content: vec![],
+        };
+        self.files.insert(path.to_string(), dir);
+        self.directories.insert(path.to_string(), Vec::new());
+        self.list_in_parent(path);
+        self
+    }
+
+    /// Add a mock text file (e.g. a job_info.json) with real content
+    pub fn add_text_file(&mut self, path: &str, content: &str) -> &mut Self {
+        self.add_file(path, content.len() as u64, FILE_PERMISSIONS);
+        if let Some(file) = self.files.get_mut(path) {
+            file.content = content.as_bytes().to_vec();
+        }
+        self
+    }
+
+    /// Add to the parent directory listing
+    fn list_in_parent(&mut self, path: &str) {
+        if let Some(parent) = parent_of(path) {
+            self.directories.entry(parent.to_string())
+                .or_default()
+                .push(name_of(path).to_string());
+        }
+    }
+
+    /// Remove from the parent directory listing
+    fn unlist_from_parent(&mut self, path: &str) {
+        if let Some(listing) = parent_of(path).and_then(|parent| self.directories.get_mut(parent)) {
+            let name = name_of(path);
+            listing.retain(|entry| entry != name);
+        }
+    }
+
+    /// Whether a file or directory exists at `path`
+    pub fn exists(&self, path: &str) -> bool {
+        let path = normalize(path);
+        self.files.contains_key(path) || self.directories.contains_key(path)
+    }
+
+    fn is_directory(&self, path: &str) -> bool {
+        self.directories.contains_key(path) || self.files.get(path).is_some_and(|file| file.is_directory)
+    }
+
+    /// Create a directory and any missing parents (mkdir -p)
+    pub fn create_dir_all(&mut self, path: &str) -> Result<(), SSHError> {
+        let path = normalize(path);
+        let ancestors: Vec<&str> = Path::new(path).ancestors().filter_map(|dir| dir.to_str()).collect();
+        for dir in ancestors.into_iter().rev() {
+            if self.is_directory(dir) {
+                continue;
+            }
+            if self.files.contains_key(dir) {
+                return Err(SSHError::FileTransferError(format!("Not a directory: {}", dir)));
+            }
+            self.add_directory(dir, DIRECTORY_PERMISSIONS);
+        }
+        Ok(())
+    }
+
+    /// Write a file, replacing any existing one and creating its parent directories
+    pub fn write_file(&mut self, path: &str, content: Vec<u8>) -> Result<(), SSHError> {
+        let path = normalize(path);
+        if self.is_directory(path) {
+            return Err(SSHError::FileTransferError(format!("Is a directory: {}", path)));
+        }
+        if !self.files.contains_key(path) {
+            if let Some(parent) = parent_of(path) {
+                self.create_dir_all(parent)?;
+            }
+            self.add_file(path, 0, FILE_PERMISSIONS);
+        }
+
+        let file = self.files.get_mut(path).ok_or_else(|| not_found(path))?;
+        file.size = content.len() as u64;
+        file.content = content;
+        file.modified_time = chrono::Utc::now().timestamp().max(0) as u64;
+        Ok(())
+    }
+
+    /// Content of a regular file
+    pub fn read_file(&self, path: &str) -> Result<Vec<u8>, SSHError> {
+        self.files.get(normalize(path))
+            .filter(|file| !file.is_directory)
+            .map(|file| file.content.clone())
+            .ok_or_else(|| not_found(path))
+    }
+
+    /// Remove a file or directory tree (rm -rf); missing paths are not an error
+    pub fn remove_all(&mut self, path: &str) {
+        let path = normalize(path).to_string();
+        self.files.retain(|candidate, _| !is_within(candidate, &path));
+        self.directories.retain(|candidate, _| !is_within(candidate, &path));
+        self.unlist_from_parent(&path);
+    }
+
+    /// Move a file or directory tree to `new_path`, creating its parent; the destination must not exist
+    pub fn rename(&mut self, old_path: &str, new_path: &str) -> Result<(), SSHError> {
+        let (old_path, new_path) = (normalize(old_path).to_string(), normalize(new_path).to_string());
+        if !self.exists(&old_path) {
+            return Err(not_found(&old_path));
+        }
+        if self.exists(&new_path) {
+            return Err(SSHError::FileTransferError(format!("Destination already exists: {}", new_path)));
+        }
+        if is_within(&new_path, &old_path) {
+            return Err(SSHError::FileTransferError(format!("Cannot move {} into itself", old_path)));
+        }
+        if let Some(parent) = parent_of(&new_path) {
+            self.create_dir_all(parent)?;
+        }
+
+        let relocate = |candidate: &str| format!("{}{}", new_path, &candidate[old_path.len()..]);
+        let moved_files: Vec<String> = self.files.keys().filter(|candidate| is_within(candidate, &old_path)).cloned().collect();
+        for from in moved_files {
+            if let Some(file) = self.files.remove(&from) {
+                self.files.insert(relocate(&from), file);
+            }
+        }
+        let moved_directories: Vec<String> = self.directories.keys().filter(|candidate| is_within(candidate, &old_path)).cloned().collect();
+        for from in moved_directories {
+            if let Some(listing) = self.directories.remove(&from) {
+                self.directories.insert(relocate(&from), listing);
+            }
+        }
+
+        self.unlist_from_parent(&old_path);
+        self.list_in_parent(&new_path);
+        Ok(())
+    }
+
+    /// Copy the contents of directory `source` into `destination`, replacing files that already exist
+    pub fn copy_tree(&mut self, source: &str, destination: &str) -> Result<(), SSHError> {
+        let (source, destination) = (normalize(source).to_string(), normalize(destination).to_string());
+        if !self.is_directory(&source) {
+            return Err(not_found(&source));
+        }
+
+        let relocate = |candidate: &str| format!("{}{}", destination, &candidate[source.len()..]);
+        let mut directories: Vec<String> = self.directories.keys().filter(|candidate| is_within(candidate, &source)).cloned().collect();
+        directories.sort();
+        let mut files: Vec<(String, Vec<u8>)> = self.files.iter()
+            .filter(|(candidate, file)| !file.is_directory && is_within(candidate, &source))
+            .map(|(candidate, file)| (candidate.clone(), file.content.clone()))
+            .collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for directory in directories {
+            self.create_dir_all(&relocate(&directory))?;
+        }
+        for (path, content) in files {
+            self.write_file(&relocate(&path), content)?;
+        }
+        Ok(())
+    }
+
+    /// Get file info for a path (simulates SFTP stat operation)
+    pub fn get_file_info(&self, path: &str) -> Result<SftpFileEntry, SSHError> {
+        match self.files.get(path) {
+            Some(file) => {
+                let name = std::path::Path::new(path)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(path)
+                    .to_string();
+
+                Ok(SftpFileEntry {
+                    name,
+                    path: path.to_string(),
+                    size: file.size,
+                    is_directory: file.is_directory,
+                    permissions: file.permissions,
+                    modified_time: Some(file.modified_time),
+                })
+            }
+            None => Err(SSHError::FileTransferError(format!("File not found: {}", path)))
+        }
+    }
+
+    /// List directory contents (simulates SFTP readdir operation)
+    pub fn list_directory(&self, path: &str) -> Result<Vec<SftpFileEntry>, SSHError> {
+        match self.directories.get(path) {
+            Some(entries) => {
+                let mut results = Vec::new();
+                for entry in entries {
+                    let full_path = if path.ends_with('/') {
+                        format!("{}{}", path, entry)
+                    } else {
+                        format!("{}/{}", path, entry)
+                    };
+
+                    if let Ok(info) = self.get_file_info(&full_path) {
+                        results.push(info);
+                    }
+                }
+                Ok(results)
+            }
+            None => Err(SSHError::FileTransferError(format!("Directory not found: {}", path)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(fs: &MockFileSystem, path: &str) -> Vec<(String, bool)> {
+        let mut entries: Vec<(String, bool)> = fs.list_directory(path).unwrap().into_iter()
+            .map(|entry| (entry.name, entry.is_directory))
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn test_write_file_creates_parents_and_lists_direct_children() {
+        let mut fs = MockFileSystem::new();
+        fs.write_file("/projects/alice/namdrunner_jobs/job_001/input_files/structure.psf", b"PSF".to_vec()).unwrap();
+        fs.write_file("/projects/alice/namdrunner_jobs/job_001/job.sbatch", b"#!/bin/bash".to_vec()).unwrap();
+        fs.create_dir_all("/projects/alice/namdrunner_jobs/job_001/outputs/").unwrap();
+        // Overwriting a file must not list it twice
+        fs.write_file("/projects/alice/namdrunner_jobs/job_001/job.sbatch", b"#!/bin/bash\n".to_vec()).unwrap();
+
+        assert_eq!(names(&fs, "/projects/alice/namdrunner_jobs/job_001"), vec![
+            ("input_files".to_string(), true),
+            ("job.sbatch".to_string(), false),
+            ("outputs".to_string(), true),
+        ]);
+        assert_eq!(fs.get_file_info("/projects/alice/namdrunner_jobs/job_001/job.sbatch").unwrap().size, 12);
+        assert!(fs.list_directory("/projects/alice/namdrunner_jobs/job_001/outputs").unwrap().is_empty());
+        assert!(fs.list_directory("/projects/alice/missing").is_err());
+        assert!(fs.exists("/projects"));
+        assert_eq!(names(&fs, "/"), vec![("projects".to_string(), true)]);
+    }
+
+    #[test]
+    fn test_remove_all_keeps_lookalike_siblings() {
+        let mut fs = MockFileSystem::new();
+        fs.write_file("/scratch/alice/job_001/outputs/sim.dcd", vec![0; 16]).unwrap();
+        fs.write_file("/scratch/alice/job_0011/outputs/sim.dcd", vec![0; 16]).unwrap();
+
+        fs.remove_all("/scratch/alice/job_001/");
+        assert!(!fs.exists("/scratch/alice/job_001"));
+        assert!(!fs.exists("/scratch/alice/job_001/outputs/sim.dcd"));
+        assert!(fs.exists("/scratch/alice/job_0011/outputs/sim.dcd"));
+        assert_eq!(names(&fs, "/scratch/alice"), vec![("job_0011".to_string(), true)]);
+    }
+
+    #[test]
+    fn test_rejects_file_directory_clashes() {
+        let mut fs = MockFileSystem::new();
+        fs.write_file("/projects/alice/notes", b"text".to_vec()).unwrap();
+        assert!(fs.create_dir_all("/projects/alice/notes/sub").is_err());
+        assert!(fs.write_file("/projects/alice", b"text".to_vec()).is_err());
+        assert_eq!(fs.read_file("/projects/alice/notes").unwrap(), b"text");
+        assert!(fs.read_file("/projects/alice").is_err());
+        assert!(fs.read_file("/projects/alice/missing").is_err());
+    }
+
+    #[test]
+    fn test_rename_moves_a_directory_tree() {
+        let mut fs = MockFileSystem::new();
+        fs.write_file("/projects/alice/job_001/outputs/sim.dcd", vec![1; 8]).unwrap();
+
+        fs.rename("/projects/alice/job_001/outputs", "/projects/alice/job_001/outputs_old").unwrap();
+        assert_eq!(fs.read_file("/projects/alice/job_001/outputs_old/sim.dcd").unwrap(), vec![1; 8]);
+        assert!(!fs.exists("/projects/alice/job_001/outputs"));
+        assert_eq!(names(&fs, "/projects/alice/job_001"), vec![("outputs_old".to_string(), true)]);
+
+        fs.write_file("/projects/alice/job_001/notes.txt", b"a".to_vec()).unwrap();
+        assert!(fs.rename("/projects/alice/job_001/notes.txt", "/projects/alice/job_001/outputs_old").is_err());
+        assert!(fs.rename("/projects/alice/job_001/missing.txt", "/projects/alice/job_001/other.txt").is_err());
+        assert!(fs.rename("/projects/alice/job_001", "/projects/alice/job_001/nested").is_err());
+    }
+
+    #[test]
+    fn test_copy_tree_mirrors_contents() {
+        let mut fs = MockFileSystem::new();
+        fs.write_file("/projects/alice/job_001/input_files/structure.psf", b"PSF".to_vec()).unwrap();
+        fs.write_file("/projects/alice/job_001/job.sbatch", b"old".to_vec()).unwrap();
+        fs.write_file("/scratch/alice/job_001/job.sbatch", b"stale".to_vec()).unwrap();
+
+        fs.copy_tree("/projects/alice/job_001/", "/scratch/alice/job_001").unwrap();
+        assert_eq!(fs.read_file("/scratch/alice/job_001/input_files/structure.psf").unwrap(), b"PSF");
+        assert_eq!(fs.read_file("/scratch/alice/job_001/job.sbatch").unwrap(), b"old");
+        assert!(fs.copy_tree("/projects/alice/job_001/job.sbatch", "/scratch/alice/elsewhere").is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::{Result, anyhow};
 use crate::types::JobInfo;
-use super::ConnectionManager;
+use super::RemoteExecutor;
 use std::io::Write;
 use tempfile::NamedTempFile;
 
@@ -10,7 +10,7 @@ use tempfile::NamedTempFile;
 /// Used by job creation, submission, and sync automations.
 ///
 /// # Arguments
-/// * `connection` - Active SSH connection manager
+/// * `connection` - Active connection (the global ConnectionManager outside tests)
 /// * `job` - JobInfo to serialize and upload
 /// * `project_dir` - Project directory path (e.g., `/projects/user/{JOB_BASE_DIRECTORY}/job_001`)
 /// * `log_context` - Context string for logging (e.g., "Job Creation", "Job Sync")
@@ -19,7 +19,7 @@ use tempfile::NamedTempFile;
 /// * `Ok(())` on success
 /// * `Err` if serialization or upload fails
 pub async fn upload_job_metadata(
-    connection: &ConnectionManager,
+    connection: &dyn RemoteExecutor,
     job: &JobInfo,
     project_dir: &str,
     log_context: &str,
@@ -56,7 +56,7 @@ pub async fn upload_job_metadata(
 ///
 /// This is a general-purpose helper for uploading any text content (scripts, configs, JSON, etc.)
 pub async fn upload_content(
-    connection: &ConnectionManager,
+    connection: &dyn RemoteExecutor,
     content: &str,
     remote_path: &str,
 ) -> Result<()> {
@@ -68,7 +68,7 @@ pub async fn upload_content(
 
     let temp_path = temp_file.path().to_string_lossy().to_string();
 
-    connection.upload_file(&temp_path, remote_path, None, None).await
+    connection.upload_file(&temp_path, remote_path).await
         .map_err(|e| anyhow!("Failed to upload to {}: {}", remote_path, e))?;
 
     Ok(())
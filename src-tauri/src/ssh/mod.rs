@@ -3,18 +3,32 @@ pub mod sftp;
 pub mod commands;
 pub mod errors;
 pub mod manager;
+pub mod monitor;
 pub mod metadata;
 pub mod directory_structure;
 pub mod paths;
+pub mod permissions;
+pub mod diagnostics;
+pub mod interactive;
+pub mod host_keys;
+pub mod tunnel;
+pub mod checksum;
+pub mod cancellation;
+pub mod zip_stream;
+pub mod demo;
+pub mod memory_fs;
+pub mod remote_executor;
 
 #[cfg(test)]
 pub mod test_utils;
 
-pub use connection::{SSHConnection, ConnectionConfig, ConnectionInfo};
-pub use sftp::{SFTPOperations, FileTransferProgress, SftpFileEntry, ProgressCallback};
+pub use connection::{SSHConnection, ConnectionConfig, ConnectionInfo, NetworkPreset, Credentials, JumpHostConfig, TimeoutOverrides};
+pub use sftp::{SFTPOperations, ArchiveEntry, FileTransferProgress, SftpFileEntry, ProgressCallback, UploadTask, BatchUploadResult, RemoteFileTail};
 pub use commands::{CommandExecutor, CommandResult};
+pub use cancellation::CancelToken;
 pub use errors::{SSHError, map_ssh_error, ConnectionError};
 pub use manager::{ConnectionManager, retry_quick};
+pub use remote_executor::RemoteExecutor;
 pub use directory_structure::JobDirectoryStructure;
 
 lazy_static::lazy_static! {
@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use crate::types::ConnectionState;
+use crate::types::response_data::ConnectionStatus;
+use crate::{log_debug, log_warn};
+
+/// How often the monitor wakes up to probe the connection
+/// Deliberately low-frequency: the goal is catching overnight staleness, not second-level accuracy
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Event emitted when the backend changes connection state on its own
+pub const CONNECTION_STATUS_EVENT: &str = "connection-status-changed";
+
+/// Decide whether a connection has been idle long enough to be considered expired
+///
+/// Pure function so the threshold logic can be tested with simulated timestamps.
+/// A connection with no recorded activity is never expired (nothing to measure from).
+pub fn should_expire(last_activity: Option<Instant>, now: Instant, idle_threshold: Duration) -> bool {
+    match last_activity {
+        Some(last) => now.saturating_duration_since(last) >= idle_threshold,
+        None => false,
+    }
+}
+
+/// Start the background task that expires stale connections
+///
+/// Every CHECK_INTERVAL the task sends a keepalive on the active connection. A successful
+/// keepalive counts as activity; if nothing has succeeded within the connection's
+/// idle_expiry_timeout, the connection is dropped, marked Expired, and the frontend is told.
+pub fn start_connection_monitor(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        // First tick completes immediately - skip it so we don't probe during startup
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            check_connection(&app_handle).await;
+        }
+    });
+}
+
+async fn check_connection(app_handle: &AppHandle) {
+    let manager = super::get_connection_manager();
+
+    let idle_threshold = match manager.idle_expiry_timeout().await {
+        Some(threshold) => threshold,
+        None => return, // Not connected, or expiry disabled
+    };
+
+    if let Err(e) = manager.keepalive().await {
+        log_debug!(category: "Connection Monitor", message: "Keepalive failed", details: "{}", e);
+    }
+
+    if !should_expire(manager.last_activity(), Instant::now(), idle_threshold) {
+        return;
+    }
+
+    log_debug!(category: "Connection Monitor", message: "Idle threshold exceeded", details: "No activity for over {} seconds", idle_threshold.as_secs());
+    log_warn!(
+        category: "Connection Monitor",
+        message: "Connection expired after inactivity - please reconnect",
+        show_toast: true
+    );
+
+    if let Err(e) = manager.expire().await {
+        log_debug!(category: "Connection Monitor", message: "Error closing expired connection", details: "{}", e);
+    }
+
+    let _ = app_handle.emit(CONNECTION_STATUS_EVENT, ConnectionStatus {
+        state: ConnectionState::Expired,
+        session_info: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_expire_after_idle_threshold() {
+        let last = Instant::now();
+        let threshold = Duration::from_secs(900);
+
+        assert!(should_expire(Some(last), last + Duration::from_secs(900), threshold));
+        assert!(should_expire(Some(last), last + Duration::from_secs(8 * 3600), threshold));
+    }
+
+    #[test]
+    fn test_should_not_expire_within_idle_threshold() {
+        let last = Instant::now();
+        let threshold = Duration::from_secs(900);
+
+        assert!(!should_expire(Some(last), last, threshold));
+        assert!(!should_expire(Some(last), last + Duration::from_secs(899), threshold));
+    }
+
+    #[test]
+    fn test_should_not_expire_without_activity() {
+        let now = Instant::now();
+        assert!(!should_expire(None, now + Duration::from_secs(86400), Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_should_not_expire_with_activity_after_now() {
+        // Activity recorded while the check was running must not underflow into an expiry
+        let now = Instant::now();
+        assert!(!should_expire(Some(now + Duration::from_secs(5)), now, Duration::from_secs(900)));
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use crate::ssh::directory_structure::JobDirectoryStructure;
 use crate::security::input;
 
@@ -46,6 +46,81 @@ pub fn scratch_directory(username: &str, job_id: &str) -> Result<String> {
     safe_job_directory(username, job_id, DirectoryType::Scratch)
 }
 
+/// Ensure a stored path lies inside the user's scratch job base
+/// Paths come from the database (or imported job_info.json), so re-check them
+/// before building commands that read or measure them
+pub fn confine_to_scratch(path: &str, username: &str) -> Result<()> {
+    let clean_username = input::sanitize_username(username)?;
+    input::validate_path_safety(path, &JobDirectoryStructure::scratch_allowed_prefixes())?;
+
+    let base = format!("{}/", JobDirectoryStructure::scratch_base(&clean_username));
+    let relative = path.strip_prefix(&base)
+        .ok_or_else(|| anyhow!("Path '{}' is not within the scratch directory for {}", path, clean_username))?;
+    input::validate_relative_file_path(relative)?;
+
+    Ok(())
+}
+
+/// Ensure a user-supplied directory lies inside the user's project space
+/// Used when adopting manually staged job directories, which may live anywhere
+/// under /projects/{username}/ rather than only in the namdrunner_jobs base
+pub fn confine_to_project(path: &str, username: &str) -> Result<()> {
+    let clean_username = input::sanitize_username(username)?;
+    input::validate_path_safety(path, &JobDirectoryStructure::project_allowed_prefixes())?;
+
+    let base = format!("{}/", JobDirectoryStructure::project_user_dir(&clean_username));
+    let relative = path.strip_prefix(&base)
+        .ok_or_else(|| anyhow!("Path '{}' is not within the project directory for {}", path, clean_username))?;
+    input::validate_relative_file_path(relative)?;
+
+    // Adopted paths end up in rsync/sbatch commands, so keep components to a plain allow-list
+    for component in relative.split('/') {
+        let plain = !component.is_empty()
+            && component.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        if !plain {
+            return Err(anyhow!("Path component '{}' contains unsupported characters", component));
+        }
+    }
+
+    Ok(())
+}
+
+/// Ensure a path is one of the user's job directories (project or scratch) or lies inside one
+/// For read-only operations such as measuring, where a whole job directory is a fair target
+pub fn confine_to_job_directories(path: &str, username: &str) -> Result<()> {
+    job_relative_path(path, username).map(|_| ())
+}
+
+/// Ensure a user-supplied path lies inside one of the user's job directories (project or scratch)
+/// Job directories themselves are refused: renaming one would orphan its database record
+pub fn confine_to_job_files(path: &str, username: &str) -> Result<()> {
+    let relative = job_relative_path(path, username)?;
+    let (_job_id, within_job) = relative.split_once('/')
+        .ok_or_else(|| anyhow!("Path '{}' is a job directory, not a file within one", path))?;
+    if within_job.trim_end_matches('/').is_empty() {
+        return Err(anyhow!("Path '{}' is a job directory, not a file within one", path));
+    }
+
+    Ok(())
+}
+
+/// Path relative to the user's project or scratch job base, after checking it stays inside
+fn job_relative_path<'a>(path: &'a str, username: &str) -> Result<&'a str> {
+    let clean_username = input::sanitize_username(username)?;
+    let mut allowed_prefixes = JobDirectoryStructure::project_allowed_prefixes();
+    allowed_prefixes.extend(JobDirectoryStructure::scratch_allowed_prefixes());
+    input::validate_path_safety(path, &allowed_prefixes)?;
+
+    let project_base = format!("{}/", JobDirectoryStructure::project_base(&clean_username));
+    let scratch_base = format!("{}/", JobDirectoryStructure::scratch_base(&clean_username));
+    let relative = path.strip_prefix(&project_base)
+        .or_else(|| path.strip_prefix(&scratch_base))
+        .ok_or_else(|| anyhow!("Path '{}' is not within the job directories for {}", path, clean_username))?;
+    input::validate_relative_file_path(relative)?;
+
+    Ok(relative)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +177,110 @@ mod tests {
                     "Should reject malicious combo: {} / {}", username, job_id);
         }
     }
+
+    #[test]
+    fn test_confine_to_scratch_accepts_job_directories() {
+        let job_dir = JobDirectoryStructure::scratch_dir("testuser", "job_001");
+        assert!(confine_to_scratch(&job_dir, "testuser").is_ok());
+        assert!(confine_to_scratch(&format!("{}/outputs", job_dir), "testuser").is_ok());
+    }
+
+    #[test]
+    fn test_confine_to_scratch_rejects_outside_paths() {
+        let base = JobDirectoryStructure::scratch_base("testuser");
+        let rejected = vec![
+            // Outside scratch entirely
+            JobDirectoryStructure::project_dir("testuser", "job_001"),
+            "/etc".to_string(),
+            // Another user's scratch
+            JobDirectoryStructure::scratch_dir("otheruser", "job_001"),
+            // The base itself and look-alike siblings
+            base.clone(),
+            format!("{}/", base),
+            format!("{}_evil/job_001", base),
+            // Traversal out of the base
+            format!("{}/../../otheruser/namdrunner_jobs/job_001", base),
+        ];
+
+        for path in rejected {
+            assert!(confine_to_scratch(&path, "testuser").is_err(), "Should reject: {}", path);
+        }
+    }
+
+    #[test]
+    fn test_confine_to_project_accepts_user_directories() {
+        let job_dir = JobDirectoryStructure::project_dir("testuser", "job_001");
+        assert!(confine_to_project(&job_dir, "testuser").is_ok());
+        assert!(confine_to_project("/projects/testuser/manual_runs/sim_a", "testuser").is_ok());
+    }
+
+    #[test]
+    fn test_confine_to_project_rejects_outside_paths() {
+        let rejected = vec![
+            JobDirectoryStructure::scratch_dir("testuser", "job_001"),
+            "/projects/otheruser/sim_a".to_string(),
+            "/projects/testuser".to_string(),
+            "/projects/testuser/".to_string(),
+            "/projects/testuser_evil/sim_a".to_string(),
+            "/projects/testuser/../otheruser/sim_a".to_string(),
+            "/projects/testuser/sim;rm -rf ~".to_string(),
+        ];
+
+        for path in rejected {
+            assert!(confine_to_project(&path, "testuser").is_err(), "Should reject: {}", path);
+        }
+    }
+
+    #[test]
+    fn test_confine_to_job_files_accepts_files_in_job_directories() {
+        let project_job = JobDirectoryStructure::project_dir("testuser", "job_001");
+        let scratch_job = JobDirectoryStructure::scratch_dir("testuser", "job_001");
+        assert!(confine_to_job_files(&format!("{}/outputs/run.dcd", project_job), "testuser").is_ok());
+        assert!(confine_to_job_files(&format!("{}/outputs/old", scratch_job), "testuser").is_ok());
+        assert!(confine_to_job_files(&format!("{}/notes.txt", project_job), "testuser").is_ok());
+    }
+
+    #[test]
+    fn test_confine_to_job_files_rejects_outside_paths() {
+        let project_base = JobDirectoryStructure::project_base("testuser");
+        let rejected = vec![
+            // Job directories and bases themselves
+            JobDirectoryStructure::project_dir("testuser", "job_001"),
+            format!("{}/", JobDirectoryStructure::scratch_dir("testuser", "job_001")),
+            project_base.clone(),
+            // Outside the job bases
+            "/projects/testuser/manual_runs/sim_a".to_string(),
+            "/etc/passwd".to_string(),
+            JobDirectoryStructure::project_dir("otheruser", "job_001") + "/outputs/run.dcd",
+            format!("{}_evil/job_001/run.dcd", project_base),
+            // Traversal out of a job directory
+            format!("{}/job_001/../../../otheruser/namdrunner_jobs/job_001/run.dcd", project_base),
+        ];
+
+        for path in rejected {
+            assert!(confine_to_job_files(&path, "testuser").is_err(), "Should reject: {}", path);
+        }
+    }
+
+    #[test]
+    fn test_confine_to_job_directories() {
+        let project_base = JobDirectoryStructure::project_base("testuser");
+        let project_job = JobDirectoryStructure::project_dir("testuser", "job_001");
+        let scratch_job = JobDirectoryStructure::scratch_dir("testuser", "job_001");
+        assert!(confine_to_job_directories(&project_job, "testuser").is_ok());
+        assert!(confine_to_job_directories(&format!("{}/outputs", project_job), "testuser").is_ok());
+        assert!(confine_to_job_directories(&scratch_job, "testuser").is_ok());
+
+        let rejected = vec![
+            // Another user's job directories, or the rest of the project space
+            JobDirectoryStructure::project_dir("otheruser", "job_001") + "/outputs",
+            JobDirectoryStructure::project_user_dir("testuser"),
+            "/projects/testuser/manual_runs/sim_a".to_string(),
+            project_base.clone(),
+            format!("{}/job_001/../../../otheruser/namdrunner_jobs/job_001", project_base),
+        ];
+        for path in rejected {
+            assert!(confine_to_job_directories(&path, "testuser").is_err(), "Should reject: {}", path);
+        }
+    }
 }
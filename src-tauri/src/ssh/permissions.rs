@@ -0,0 +1,187 @@
+use anyhow::Result;
+use crate::ssh::{ConnectionManager, JobDirectoryStructure, SSHError};
+use crate::security::shell;
+use crate::{log_debug, log_warn};
+
+/// A remote directory's access as the server decides it (`test -w` and `test -x`, so ACLs
+/// count), plus its mode and ownership from `stat -c '%a %u %g'` for explaining a denial
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryStat {
+    pub path: String,
+    pub writable: bool,
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+}
+
+/// The remote user's identity (`id -u` and `id -G`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteIdentity {
+    pub uid: u32,
+    pub gids: Vec<u32>,
+}
+
+/// Generate a command that tests and stats each existing directory, then prints the user's uid and gids
+/// Each directory line is `w` or `-` (writable and searchable), then mode, uid, gid and path.
+/// Missing directories are silently left out.
+pub fn directory_access_command(paths: &[String]) -> String {
+    let escaped: Vec<String> = paths.iter().map(|p| shell::escape_parameter(p)).collect();
+    format!(
+        "for dir in {}; do [ -d \"$dir\" ] || continue; if test -w \"$dir\" && test -x \"$dir\"; then access=w; else access=-; fi; \
+         echo \"$access $(stat -c '%a %u %g' \"$dir\") $dir\"; done; id -u; id -G",
+        escaped.join(" ")
+    )
+}
+
+/// Parse output of `directory_access_command`
+/// The last two lines are `id -u` and `id -G`; every line before them is a directory line
+pub fn parse_directory_access(output: &str) -> Option<(Vec<DirectoryStat>, RemoteIdentity)> {
+    let lines: Vec<&str> = output.lines().filter(|line| !line.trim().is_empty()).collect();
+    let [stat_lines @ .., uid_line, gids_line] = lines.as_slice() else {
+        return None;
+    };
+
+    let identity = RemoteIdentity {
+        uid: uid_line.trim().parse().ok()?,
+        gids: gids_line.split_whitespace().map(|gid| gid.parse().ok()).collect::<Option<_>>()?,
+    };
+
+    let stats = stat_lines.iter()
+        .map(|line| {
+            let mut fields = line.splitn(5, ' ');
+            Some(DirectoryStat {
+                writable: match fields.next()? {
+                    "w" => true,
+                    "-" => false,
+                    _ => return None,
+                },
+                mode: u32::from_str_radix(fields.next()?, 8).ok()?,
+                uid: fields.next()?.parse().ok()?,
+                gid: fields.next()?.parse().ok()?,
+                path: fields.next()?.to_string(),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some((stats, identity))
+}
+
+/// Check write access, explaining how to fix it when missing
+pub fn check_write_access(stat: &DirectoryStat, identity: &RemoteIdentity) -> std::result::Result<(), SSHError> {
+    if stat.writable {
+        return Ok(());
+    }
+
+    let remediation = if stat.uid == identity.uid {
+        format!("run `chmod u+wx {}` on the cluster", stat.path)
+    } else if identity.gids.contains(&stat.gid) {
+        format!("ask the directory owner to run `chmod g+wx {}` (group-writable is missing)", stat.path)
+    } else {
+        format!("ask the directory owner to add your account to group {} and make it group-writable", stat.gid)
+    };
+
+    Err(SSHError::PermissionError(format!(
+        "Cannot create job directories in {} (mode {:o}, owner uid {}, group gid {}) - {}",
+        stat.path, stat.mode, stat.uid, stat.gid, remediation
+    )))
+}
+
+/// Pre-flight check before creating a job: the nearest existing parent of the job
+/// directories must be writable. Skipped (Ok) when the check itself can't run -
+/// directory creation will then report its own error.
+pub async fn ensure_project_writable(connection_manager: &ConnectionManager, username: &str) -> Result<()> {
+    // Nearest first: the jobs base, or the user's project directory if the base doesn't exist yet
+    let candidates = vec![
+        JobDirectoryStructure::project_base(username),
+        JobDirectoryStructure::project_user_dir(username),
+    ];
+
+    let command = directory_access_command(&candidates);
+    let output = match connection_manager.execute_command(&command, Some(crate::cluster::timeouts::QUICK_OPERATION)).await {
+        Ok(result) => result.stdout,
+        Err(e) => {
+            log_debug!(category: "Permissions", message: "Permission pre-flight skipped", details: "{}", e);
+            return Ok(());
+        }
+    };
+
+    let Some((stats, identity)) = parse_directory_access(&output) else {
+        log_debug!(category: "Permissions", message: "Permission pre-flight skipped - unrecognized output", details: "{}", output);
+        return Ok(());
+    };
+
+    let nearest = candidates.iter().find_map(|path| stats.iter().find(|stat| &stat.path == path));
+    if let Some(stat) = nearest {
+        check_write_access(stat, &identity).inspect_err(|e| {
+            log_warn!(category: "Permissions", message: "Project directory not writable", details: "{}", e);
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat(writable: bool, mode: u32, uid: u32, gid: u32) -> DirectoryStat {
+        DirectoryStat { path: "/projects/testuser/namdrunner_jobs".to_string(), writable, mode, uid, gid }
+    }
+
+    fn user(uid: u32, gids: &[u32]) -> RemoteIdentity {
+        RemoteIdentity { uid, gids: gids.to_vec() }
+    }
+
+    #[test]
+    fn test_directory_access_command() {
+        let command = directory_access_command(&["/projects/testuser/namdrunner_jobs".to_string(), "/projects/testuser".to_string()]);
+        assert!(command.starts_with("for dir in '/projects/testuser/namdrunner_jobs' '/projects/testuser'; do"));
+        assert!(command.contains("test -w \"$dir\" && test -x \"$dir\""));
+        assert!(command.ends_with("; id -u; id -G"));
+    }
+
+    #[test]
+    fn test_parse_directory_access() {
+        let output = "w 2770 1001 5000 /projects/testuser/namdrunner_jobs\n- 755 1001 1001 /projects/testuser\n1001\n1001 5000 5001\n";
+        let (stats, identity) = parse_directory_access(output).unwrap();
+
+        assert_eq!(stats.len(), 2);
+        assert!(stats[0].writable);
+        assert!(!stats[1].writable);
+        assert_eq!(stats[0].mode, 0o2770);
+        assert_eq!(stats[0].gid, 5000);
+        assert_eq!(stats[1].path, "/projects/testuser");
+        assert_eq!(identity, user(1001, &[1001, 5000, 5001]));
+
+        // Base directory missing: only the parent is stat'ed
+        let (stats, _) = parse_directory_access("w 755 1001 1001 /projects/testuser\n1001\n1001\n").unwrap();
+        assert_eq!(stats.len(), 1);
+
+        assert!(parse_directory_access("").is_none());
+        assert!(parse_directory_access("garbage here\n1001\n1001\n").is_none());
+        assert!(parse_directory_access("755 1001 1001 /projects/testuser\n1001\n1001\n").is_none());
+    }
+
+    #[test]
+    fn test_server_decides_write_access() {
+        // Mode bits say no (group r-x), but an ACL grants write: the server's test wins
+        assert!(check_write_access(&stat(true, 0o2750, 2002, 5000), &user(1001, &[1001])).is_ok());
+        // Mode bits say yes, but the server refuses (e.g. a read-only mount)
+        assert!(check_write_access(&stat(false, 0o755, 1001, 1001), &user(1001, &[1001])).is_err());
+    }
+
+    #[test]
+    fn test_permission_error_remediation() {
+        let err = check_write_access(&stat(false, 0o2750, 2002, 5000), &user(1001, &[5000])).unwrap_err();
+        assert!(matches!(err, SSHError::PermissionError(_)));
+        let message = err.to_string();
+        assert!(message.contains("mode 2750"));
+        assert!(message.contains("chmod g+wx /projects/testuser/namdrunner_jobs"));
+
+        let err = check_write_access(&stat(false, 0o555, 1001, 1001), &user(1001, &[1001])).unwrap_err();
+        assert!(err.to_string().contains("chmod u+wx"));
+
+        let err = check_write_access(&stat(false, 0o770, 2002, 5000), &user(1001, &[1001])).unwrap_err();
+        assert!(err.to_string().contains("group 5000"));
+    }
+}
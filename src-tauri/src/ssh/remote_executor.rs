@@ -0,0 +1,80 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use super::commands::CommandResult;
+use super::manager::ConnectionManager;
+use super::sftp::{FileTransferProgress, SftpFileEntry};
+
+/// The remote operations automations rely on, so they can run against a mock in tests
+///
+/// `ConnectionManager` is the production implementation; automations take
+/// `&dyn RemoteExecutor` and callers pass `get_connection_manager()`.
+#[async_trait]
+pub trait RemoteExecutor: Send + Sync {
+    async fn is_connected(&self) -> bool;
+
+    async fn get_username(&self) -> Result<String>;
+
+    async fn execute_command(&self, command: &str, timeout: Option<u64>) -> Result<CommandResult>;
+
+    /// Plain upload: no progress events, cancellation, or checksum verification
+    async fn upload_file(&self, local_path: &str, remote_path: &str) -> Result<FileTransferProgress>;
+
+    /// Plain download of a whole file, replacing any local copy
+    async fn download_file(&self, remote_path: &str, local_path: &str) -> Result<FileTransferProgress>;
+
+    async fn list_files(&self, remote_path: &str, include_directories: bool) -> Result<Vec<SftpFileEntry>>;
+
+    async fn file_exists(&self, remote_path: &str) -> Result<bool>;
+
+    /// Copy `source` into `destination` on the cluster (rsync -a semantics)
+    async fn mirror_directory(&self, source: &str, destination: &str) -> Result<CommandResult>;
+
+    async fn read_remote_file(&self, remote_path: &str) -> Result<String>;
+
+    /// Read several small files in one round trip; one result per path, in order
+    async fn read_remote_files(&self, remote_paths: &[String]) -> Result<Vec<Result<String>>>;
+}
+
+// Inherent methods take precedence, so each call below forwards to ConnectionManager's own
+#[async_trait]
+impl RemoteExecutor for ConnectionManager {
+    async fn is_connected(&self) -> bool {
+        self.is_connected().await
+    }
+
+    async fn get_username(&self) -> Result<String> {
+        self.get_username().await
+    }
+
+    async fn execute_command(&self, command: &str, timeout: Option<u64>) -> Result<CommandResult> {
+        self.execute_command(command, timeout).await
+    }
+
+    async fn upload_file(&self, local_path: &str, remote_path: &str) -> Result<FileTransferProgress> {
+        self.upload_file(local_path, remote_path, None, None, None, false).await
+    }
+
+    async fn download_file(&self, remote_path: &str, local_path: &str) -> Result<FileTransferProgress> {
+        self.download_file(remote_path, local_path, false, None, false).await
+    }
+
+    async fn list_files(&self, remote_path: &str, include_directories: bool) -> Result<Vec<SftpFileEntry>> {
+        self.list_files(remote_path, include_directories).await
+    }
+
+    async fn file_exists(&self, remote_path: &str) -> Result<bool> {
+        self.file_exists(remote_path).await
+    }
+
+    async fn mirror_directory(&self, source: &str, destination: &str) -> Result<CommandResult> {
+        self.mirror_directory(source, destination).await
+    }
+
+    async fn read_remote_file(&self, remote_path: &str) -> Result<String> {
+        self.read_remote_file(remote_path).await
+    }
+
+    async fn read_remote_files(&self, remote_paths: &[String]) -> Result<Vec<Result<String>>> {
+        self.read_remote_files(remote_paths).await
+    }
+}
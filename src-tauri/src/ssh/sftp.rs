@@ -1,9 +1,13 @@
 use ssh2::{Session, Sftp};
-use std::path::Path;
-use std::fs::File;
-use std::io::{Read, Write, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write, BufReader, BufWriter};
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
 use anyhow::Result;
 use super::errors::SSHError;
+use super::cancellation;
+use super::zip_stream::ZipStreamWriter;
 
 /// Progress callback for file transfers
 pub type ProgressCallback = Box<dyn Fn(u64, u64) + Send>;
@@ -19,6 +23,63 @@ pub struct FileTransferProgress {
     pub file_name: Option<String>,
 }
 
+/// One file in a concurrent multi-file upload
+#[derive(Debug, Clone)]
+pub struct UploadTask {
+    pub local_path: PathBuf,
+    pub remote_path: String,
+    /// Name carried in this file's progress events
+    pub progress_key: Option<String>,
+}
+
+/// One remote file streamed into a downloaded archive
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    pub remote_path: String,
+    /// Path inside the archive, e.g. "outputs/sim.dcd"
+    pub archive_name: String,
+    pub size: u64,
+    pub modified_time: Option<u64>,
+}
+
+/// Result of a concurrent multi-file upload
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BatchUploadResult {
+    /// Per-file results, in task order
+    pub files: Vec<FileTransferProgress>,
+    /// All files together; transfer_rate is over the whole batch's wall time
+    pub total: FileTransferProgress,
+}
+
+/// What a recursive directory upload will do, worked out locally before anything is sent
+#[derive(Debug, Clone)]
+pub struct DirectoryUploadPlan {
+    /// Remote directories to create, parents before children (includes empty ones)
+    pub directories: Vec<String>,
+    /// Regular files to upload, in walk order
+    pub files: Vec<UploadTask>,
+    pub total_bytes: u64,
+    /// Local symlinks left out of the upload
+    pub skipped_symlinks: Vec<PathBuf>,
+}
+
+/// Most bytes returned by one tail read; a fast-growing log is caught up over several polls
+pub const MAX_TAIL_BYTES: u64 = 512 * 1024;
+
+/// Content appended to a remote file since a known offset
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RemoteFileTail {
+    pub content: String,
+    /// Offset to pass on the next poll
+    pub offset: u64,
+    /// Remote file size when it was read
+    pub file_size: u64,
+    /// The file was shorter than the requested offset (rotated or truncated), so reading restarted at 0
+    pub reset: bool,
+    /// More content is already waiting past `offset`
+    pub has_more: bool,
+}
+
 /// File information from SFTP
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SftpFileEntry {
@@ -102,10 +163,15 @@ impl<'a> SFTPOperations<'a> {
     ///
     /// Uses 256KB chunks with per-chunk flush to avoid timeout accumulation.
     /// Each chunk gets a fresh timeout window from the session timeout setting.
+    /// `cancel` is checked between chunks; a cancelled upload removes the partial remote file.
+    /// With `max_bytes_per_sec`, the loop sleeps after a chunk that got ahead of that rate;
+    /// None or 0 uploads at full speed.
     pub fn upload_file(
         &self,
         local_path: &Path,
         remote_path: &str,
+        cancel: Option<&AtomicBool>,
+        max_bytes_per_sec: Option<u64>,
         progress_callback: Option<ProgressCallback>
     ) -> Result<FileTransferProgress> {
         let sftp = self.get_sftp()?;
@@ -132,6 +198,12 @@ impl<'a> SFTPOperations<'a> {
         let start_time = std::time::Instant::now();
 
         loop {
+            if let Err(e) = cancellation::check(cancel, &format!("Upload of {}", file_name)) {
+                drop(remote_file);
+                let _ = sftp.unlink(Path::new(remote_path));
+                return Err(e.into());
+            }
+
             let bytes_read = reader.read(&mut buffer)?;
             if bytes_read == 0 {
                 break;
@@ -171,6 +243,10 @@ impl<'a> SFTPOperations<'a> {
 
             bytes_transferred += bytes_read as u64;
 
+            if let Some(delay) = throttle_delay(bytes_transferred, start_time.elapsed(), max_bytes_per_sec) {
+                std::thread::sleep(delay);
+            }
+
             // Call progress callback if provided
             if let Some(ref callback) = progress_callback {
                 callback(bytes_transferred, file_size);
@@ -193,11 +269,62 @@ impl<'a> SFTPOperations<'a> {
         })
     }
 
+    /// Upload several files at once over a pool of SFTP channels on this session
+    ///
+    /// A Session can only run one blocking call at a time, so channels and remote files are
+    /// opened one by one in blocking mode, then the writes run non-blocking and round-robin
+    /// so each channel's data is in flight while the others wait for acknowledgements.
+    /// `max_bytes_per_sec` caps the batch as a whole: all channels draw from one byte budget.
+    /// Progress is reported per file as (task index, bytes transferred, file size).
+    pub fn upload_files_concurrent(
+        &self,
+        tasks: &[UploadTask],
+        max_concurrency: usize,
+        max_bytes_per_sec: Option<u64>,
+        on_progress: impl FnMut(usize, u64, u64),
+    ) -> Result<BatchUploadResult> {
+        let slots = max_concurrency.clamp(1, tasks.len().max(1));
+        let channels = (0..slots).map(|_| self.get_sftp()).collect::<Result<Vec<_>>>()?;
+        // Non-blocking calls ignore the session timeout; the same limit applies to stalls instead
+        let stall_timeout = Some(self.session.timeout()).filter(|ms| *ms > 0).map(|ms| Duration::from_millis(ms as u64));
+
+        let result = run_concurrent_uploads(
+            tasks,
+            slots,
+            stall_timeout,
+            max_bytes_per_sec,
+            |slot, task| {
+                self.session.set_blocking(true);
+                let remote = channels[slot].create(Path::new(&task.remote_path));
+                self.session.set_blocking(false);
+                remote.map_err(|e| SSHError::FileTransferError(format!("Failed to create remote file {}: {}", task.remote_path, e)).into())
+            },
+            |mut remote| {
+                self.session.set_blocking(true);
+                let synced = remote.fsync();
+                drop(remote);
+                self.session.set_blocking(false);
+                synced.map_err(|e| SSHError::FileTransferError(format!("Failed to sync remote file: {}", e)).into())
+            },
+            on_progress,
+        );
+
+        self.session.set_blocking(true);
+        result
+    }
+
     /// Download a file from remote server
+    ///
+    /// With `resume`, an existing partial local file is continued from its length instead of
+    /// being overwritten. A local file longer than the remote one can't be a prefix of it
+    /// (the remote was replaced or truncated), so the download restarts from zero.
+    /// `cancel` is checked between chunks; a cancelled download removes the partial local file.
     pub fn download_file(
         &self,
         remote_path: &str,
         local_path: &Path,
+        resume: bool,
+        cancel: Option<&AtomicBool>,
         progress_callback: Option<ProgressCallback>
     ) -> Result<FileTransferProgress> {
         let sftp = self.get_sftp()?;
@@ -212,53 +339,74 @@ impl<'a> SFTPOperations<'a> {
         })?;
 
         // Open remote file
-        let mut remote_file = sftp.open(Path::new(remote_path))
+        let remote_file = sftp.open(Path::new(remote_path))
             .map_err(|e| SSHError::FileTransferError(format!("Failed to open remote file: {}", e)))?;
 
-        // Create local file
-        let local_file = File::create(local_path)
-            .map_err(|e| SSHError::FileTransferError(format!("Failed to create local file: {}", e)))?;
-
-        let mut writer = BufWriter::with_capacity(self.buffer_size, local_file);
+        download_to_local(remote_file, file_size, local_path, resume, self.buffer_size, cancel, progress_callback)
+    }
 
-        // Transfer file with progress tracking
+    /// Stream remote files one after another into a ZIP archive, entry by entry
+    /// Memory stays at one read buffer however large the files are.
+    /// `progress_callback` gets (files finished, bytes read so far) after every chunk
+    pub fn download_into_zip<W: Write + Seek>(
+        &self,
+        entries: &[ArchiveEntry],
+        zip: &mut ZipStreamWriter<W>,
+        cancel: Option<&AtomicBool>,
+        progress_callback: &dyn Fn(usize, u64),
+    ) -> Result<u64> {
+        let sftp = self.get_sftp()?;
         let mut buffer = vec![0u8; self.buffer_size];
-        let mut bytes_transferred = 0u64;
-        let start_time = std::time::Instant::now();
+        let mut bytes_done = 0;
 
-        loop {
-            let bytes_read = remote_file.read(&mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
+        for (index, entry) in entries.iter().enumerate() {
+            let mut remote_file = sftp.open(Path::new(&entry.remote_path))
+                .map_err(|e| SSHError::FileTransferError(format!("Failed to open remote file {}: {}", entry.remote_path, e)))?;
+            zip.start_entry(&entry.archive_name, entry.modified_time)
+                .map_err(|e| SSHError::FileTransferError(format!("Failed to write archive: {}", e)))?;
 
-            writer.write_all(&buffer[..bytes_read])
-                .map_err(|e| SSHError::FileTransferError(format!("Failed to write to local file: {}", e)))?;
+            let mut entry_bytes = 0;
+            loop {
+                cancellation::check(cancel, &format!("Archive download of {}", entry.archive_name))?;
 
-            bytes_transferred += bytes_read as u64;
+                let bytes_read = remote_file.read(&mut buffer)?;
+                if bytes_read == 0 {
+                    break;
+                }
 
-            // Call progress callback if provided
-            if let Some(ref callback) = progress_callback {
-                callback(bytes_transferred, file_size);
+                zip.write_all(&buffer[..bytes_read])
+                    .map_err(|e| SSHError::FileTransferError(format!("Failed to write archive: {}", e)))?;
+                entry_bytes += bytes_read as u64;
+                bytes_done += bytes_read as u64;
+                progress_callback(index, bytes_done);
+            }
+
+            // The file changed under us (still being written, or truncated) - the archive would be wrong
+            if entry_bytes != entry.size {
+                return Err(SSHError::FileTransferError(format!(
+                    "{} read {} bytes but was listed at {} bytes", entry.remote_path, entry_bytes, entry.size
+                )).into());
             }
+
+            progress_callback(index + 1, bytes_done);
         }
 
-        writer.flush()?;
+        Ok(bytes_done)
+    }
 
-        let duration = start_time.elapsed().as_secs_f64();
-        let transfer_rate = if duration > 0.0 {
-            bytes_transferred as f64 / duration
-        } else {
-            0.0
-        };
+    /// Read what a remote file gained past `from_offset`, at most `max_bytes` per call
+    pub fn tail_file(&self, remote_path: &str, from_offset: u64, max_bytes: u64) -> Result<RemoteFileTail> {
+        let sftp = self.get_sftp()?;
 
-        Ok(FileTransferProgress {
-            bytes_transferred,
-            total_bytes: file_size,
-            percentage: (bytes_transferred as f32 / file_size as f32) * 100.0,
-            transfer_rate,
-            file_name: None,
-        })
+        let stat = sftp.stat(Path::new(remote_path))
+            .map_err(|e| SSHError::FileTransferError(format!("Failed to stat remote file: {}", e)))?;
+        let file_size = stat.size
+            .ok_or_else(|| SSHError::FileTransferError(format!("File size not available for: {}", remote_path)))?;
+
+        let remote_file = sftp.open(Path::new(remote_path))
+            .map_err(|e| SSHError::FileTransferError(format!("Failed to open remote file: {}", e)))?;
+
+        read_tail(remote_file, file_size, from_offset, max_bytes)
     }
 
     /// List files in a directory
@@ -391,10 +539,676 @@ impl<'a> SFTPOperations<'a> {
 /// Matches SFTP best practices and OpenSSH behavior for large file transfers
 const CHUNK_SIZE: usize = 256 * 1024;
 
+/// How long to pause after `bytes_transferred` bytes took `elapsed`, to hold the average
+/// rate at `max_bytes_per_sec`; None when unlimited (None or 0) or not ahead of the limit
+fn throttle_delay(bytes_transferred: u64, elapsed: Duration, max_bytes_per_sec: Option<u64>) -> Option<Duration> {
+    let limit = max_bytes_per_sec.filter(|limit| *limit > 0)?;
+    let target = Duration::from_secs_f64(bytes_transferred as f64 / limit as f64);
+    target.checked_sub(elapsed).filter(|delay| !delay.is_zero())
+}
+
+/// Byte offset to continue a download from, given the partial local file's length
+/// Anything longer than the remote file can't be a prefix of it, so it starts over
+fn resume_offset(local_len: Option<u64>, remote_size: u64, resume: bool) -> u64 {
+    match local_len {
+        Some(len) if resume && len <= remote_size => len,
+        Some(len) if resume => {
+            crate::log_warn!(category: "SFTP", message: "Remote file is smaller than the partial download - restarting", details: "local {} bytes, remote {} bytes", len, remote_size);
+            0
+        }
+        _ => 0,
+    }
+}
+
+/// Copy a remote file to local disk, continuing a partial local file when resuming,
+/// and check the result is exactly `file_size` bytes
+fn download_to_local<R: Read + Seek>(
+    mut remote_file: R,
+    file_size: u64,
+    local_path: &Path,
+    resume: bool,
+    buffer_size: usize,
+    cancel: Option<&AtomicBool>,
+    progress_callback: Option<ProgressCallback>,
+) -> Result<FileTransferProgress> {
+    let local_len = std::fs::metadata(local_path).ok().filter(|m| m.is_file()).map(|m| m.len());
+    let offset = resume_offset(local_len, file_size, resume);
+
+    let local_file = if offset > 0 {
+        remote_file.seek(SeekFrom::Start(offset))
+            .map_err(|e| SSHError::FileTransferError(format!("Failed to seek remote file to byte {}: {}", offset, e)))?;
+        crate::log_info!(category: "SFTP", message: "Resuming download", details: "from byte {} of {}", offset, file_size);
+        OpenOptions::new().append(true).open(local_path)
+            .map_err(|e| SSHError::FileTransferError(format!("Failed to open local file for resume: {}", e)))?
+    } else {
+        File::create(local_path)
+            .map_err(|e| SSHError::FileTransferError(format!("Failed to create local file: {}", e)))?
+    };
+
+    let mut writer = BufWriter::with_capacity(buffer_size, local_file);
+
+    // Transfer file with progress tracking
+    let mut buffer = vec![0u8; buffer_size];
+    let mut bytes_transferred = offset;
+    let start_time = std::time::Instant::now();
+
+    let file_name = local_path.file_name().unwrap_or_default().to_string_lossy();
+
+    loop {
+        if let Err(e) = cancellation::check(cancel, &format!("Download of {}", file_name)) {
+            drop(writer);
+            let _ = std::fs::remove_file(local_path);
+            return Err(e.into());
+        }
+
+        let bytes_read = remote_file.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+
+        writer.write_all(&buffer[..bytes_read])
+            .map_err(|e| SSHError::FileTransferError(format!("Failed to write to local file: {}", e)))?;
+
+        bytes_transferred += bytes_read as u64;
+
+        // Call progress callback if provided
+        if let Some(ref callback) = progress_callback {
+            callback(bytes_transferred, file_size);
+        }
+    }
+
+    writer.flush()?;
+
+    // A short read here means the transfer was cut off (or the remote changed mid-download)
+    let local_size = std::fs::metadata(local_path)?.len();
+    if local_size != file_size {
+        return Err(SSHError::FileTransferError(format!(
+            "Downloaded size {} bytes does not match remote size {} bytes", local_size, file_size
+        )).into());
+    }
+
+    let duration = start_time.elapsed().as_secs_f64();
+    let transfer_rate = if duration > 0.0 {
+        (bytes_transferred - offset) as f64 / duration
+    } else {
+        0.0
+    };
+
+    Ok(FileTransferProgress {
+        bytes_transferred,
+        total_bytes: file_size,
+        percentage: if file_size > 0 { (bytes_transferred as f32 / file_size as f32) * 100.0 } else { 100.0 },
+        transfer_rate,
+        file_name: None,
+    })
+}
+
+/// Read up to `max_bytes` from `from_offset`, restarting at 0 if the file is now shorter
+/// A multi-byte character cut off at the end is left for the next read rather than mangled
+pub(super) fn read_tail<R: Read + Seek>(mut reader: R, file_size: u64, from_offset: u64, max_bytes: u64) -> Result<RemoteFileTail> {
+    let reset = from_offset > file_size;
+    let start = if reset { 0 } else { from_offset };
+    let wanted = (file_size - start).min(max_bytes);
+
+    reader.seek(SeekFrom::Start(start))
+        .map_err(|e| SSHError::FileTransferError(format!("Failed to seek remote file to byte {}: {}", start, e)))?;
+    let mut bytes = Vec::with_capacity(wanted as usize);
+    reader.take(wanted).read_to_end(&mut bytes)
+        .map_err(|e| SSHError::FileTransferError(format!("Failed to read remote file: {}", e)))?;
+
+    if let Err(e) = std::str::from_utf8(&bytes) {
+        if e.error_len().is_none() {
+            bytes.truncate(e.valid_up_to());
+        }
+    }
+    let offset = start + bytes.len() as u64;
+
+    Ok(RemoteFileTail {
+        content: String::from_utf8_lossy(&bytes).into_owned(),
+        offset,
+        file_size,
+        reset,
+        has_more: offset < file_size,
+    })
+}
+
+/// Sleep between rounds when every concurrent upload is waiting on the network
+const UPLOAD_POLL: Duration = Duration::from_millis(2);
+
+/// A file being written in one slot of a concurrent upload
+struct ActiveUpload<W> {
+    index: usize,
+    reader: BufReader<File>,
+    remote: W,
+    buffer: Vec<u8>,
+    /// buffer[pos..len] is read from disk but not yet accepted by the remote
+    pos: usize,
+    len: usize,
+    bytes_transferred: u64,
+    total_bytes: u64,
+    started: Instant,
+}
+
+/// Round-robin scheduler behind `upload_files_concurrent`, generic over the remote writer
+/// `remote` writers are non-blocking: WouldBlock means "try again later", never failure.
+/// `open_remote` gets the slot index so each slot can keep to its own channel.
+fn run_concurrent_uploads<W: Write>(
+    tasks: &[UploadTask],
+    slots: usize,
+    stall_timeout: Option<Duration>,
+    max_bytes_per_sec: Option<u64>,
+    mut open_remote: impl FnMut(usize, &UploadTask) -> Result<W>,
+    mut finish_remote: impl FnMut(W) -> Result<()>,
+    mut on_progress: impl FnMut(usize, u64, u64),
+) -> Result<BatchUploadResult> {
+    let batch_started = Instant::now();
+    let mut queue = tasks.iter().enumerate();
+    let mut active: Vec<Option<ActiveUpload<W>>> = (0..slots).map(|_| None).collect();
+    let mut results: Vec<Option<FileTransferProgress>> = vec![None; tasks.len()];
+    let mut last_progress = Instant::now();
+    // Bytes sent across every slot, paced against the one limit
+    let mut batch_bytes = 0u64;
+
+    loop {
+        for (slot, entry) in active.iter_mut().enumerate() {
+            if entry.is_none() {
+                if let Some((index, task)) = queue.next() {
+                    let local_file = File::open(&task.local_path)
+                        .map_err(|e| SSHError::FileTransferError(format!("Failed to open local file {}: {}", task.local_path.display(), e)))?;
+                    let total_bytes = local_file.metadata()?.len();
+                    *entry = Some(ActiveUpload {
+                        index,
+                        reader: BufReader::with_capacity(CHUNK_SIZE, local_file),
+                        remote: open_remote(slot, task)?,
+                        buffer: vec![0u8; CHUNK_SIZE],
+                        pos: 0,
+                        len: 0,
+                        bytes_transferred: 0,
+                        total_bytes,
+                        started: Instant::now(),
+                    });
+                }
+            }
+        }
+
+        if active.iter().all(Option::is_none) {
+            break;
+        }
+
+        let mut progressed = false;
+        for entry in active.iter_mut() {
+            let Some(upload) = entry else { continue };
+
+            if upload.pos == upload.len {
+                upload.len = upload.reader.read(&mut upload.buffer)?;
+                upload.pos = 0;
+            }
+
+            if upload.len == 0 {
+                let done = entry.take().expect("slot is active");
+                let name = tasks[done.index].local_path.display().to_string();
+                finish_remote(done.remote).map_err(|e| e.context(format!("Failed to finish upload of {}", name)))?;
+                results[done.index] = Some(completed_progress(done.bytes_transferred, done.total_bytes, done.started.elapsed(), tasks[done.index].progress_key.clone()));
+                progressed = true;
+                continue;
+            }
+
+            match upload.remote.write(&upload.buffer[upload.pos..upload.len]) {
+                Ok(0) => {
+                    return Err(SSHError::FileTransferError(format!("Remote closed while writing {}", tasks[upload.index].remote_path)).into());
+                }
+                Ok(written) => {
+                    upload.pos += written;
+                    upload.bytes_transferred += written as u64;
+                    batch_bytes += written as u64;
+                    on_progress(upload.index, upload.bytes_transferred, upload.total_bytes);
+                    progressed = true;
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => {
+                    return Err(SSHError::FileTransferError(format!(
+                        "Failed to write to {} ({} of {} bytes sent): {}",
+                        tasks[upload.index].remote_path, upload.bytes_transferred, upload.total_bytes, e
+                    )).into());
+                }
+            }
+        }
+
+        if progressed {
+            if let Some(delay) = throttle_delay(batch_bytes, batch_started.elapsed(), max_bytes_per_sec) {
+                std::thread::sleep(delay);
+            }
+            last_progress = Instant::now();
+        } else {
+            if let Some(timeout) = stall_timeout.filter(|timeout| last_progress.elapsed() >= *timeout) {
+                return Err(SSHError::TimeoutError(format!("Uploads made no progress for {} seconds", timeout.as_secs())).into());
+            }
+            std::thread::sleep(UPLOAD_POLL);
+        }
+    }
+
+    let files: Vec<FileTransferProgress> = results.into_iter().map(|result| result.expect("every task finished")).collect();
+    let total_bytes = files.iter().map(|file| file.total_bytes).sum();
+    let bytes_transferred = files.iter().map(|file| file.bytes_transferred).sum();
+    let total = completed_progress(bytes_transferred, total_bytes, batch_started.elapsed(), None);
+
+    Ok(BatchUploadResult { files, total })
+}
+
+/// Walk `local_dir` and map it onto `remote_dir`
+/// Symlinks are skipped rather than followed so a link can't pull files from outside the tree
+pub fn plan_directory_upload(local_dir: &Path, remote_dir: &str) -> Result<DirectoryUploadPlan> {
+    let metadata = std::fs::symlink_metadata(local_dir)
+        .map_err(|e| SSHError::FileTransferError(format!("Cannot read local directory {}: {}", local_dir.display(), e)))?;
+    if !metadata.is_dir() {
+        return Err(SSHError::FileTransferError(format!("{} is not a directory", local_dir.display())).into());
+    }
+
+    let mut plan = DirectoryUploadPlan {
+        directories: Vec::new(),
+        files: Vec::new(),
+        total_bytes: 0,
+        skipped_symlinks: Vec::new(),
+    };
+    plan_directory_entries(local_dir, remote_dir.trim_end_matches('/'), &mut plan)?;
+    Ok(plan)
+}
+
+fn plan_directory_entries(local_dir: &Path, remote_dir: &str, plan: &mut DirectoryUploadPlan) -> Result<()> {
+    plan.directories.push(remote_dir.to_string());
+
+    let read_error = |e: std::io::Error| SSHError::FileTransferError(format!("Cannot read local directory {}: {}", local_dir.display(), e));
+    let mut entries = std::fs::read_dir(local_dir).map_err(read_error)?
+        .collect::<std::io::Result<Vec<_>>>().map_err(read_error)?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let local_path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let remote_path = format!("{}/{}", remote_dir, name);
+        let file_type = entry.file_type().map_err(read_error)?;
+
+        if file_type.is_symlink() {
+            plan.skipped_symlinks.push(local_path);
+        } else if file_type.is_dir() {
+            plan_directory_entries(&local_path, &remote_path, plan)?;
+        } else if file_type.is_file() {
+            plan.total_bytes += entry.metadata().map_err(read_error)?.len();
+            plan.files.push(UploadTask { local_path, remote_path, progress_key: Some(name) });
+        }
+    }
+    Ok(())
+}
+
+fn completed_progress(bytes_transferred: u64, total_bytes: u64, elapsed: Duration, file_name: Option<String>) -> FileTransferProgress {
+    let duration = elapsed.as_secs_f64();
+    FileTransferProgress {
+        bytes_transferred,
+        total_bytes,
+        percentage: if total_bytes > 0 { (bytes_transferred as f32 / total_bytes as f32) * 100.0 } else { 100.0 },
+        transfer_rate: if duration > 0.0 { bytes_transferred as f64 / duration } else { 0.0 },
+        file_name,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ssh::test_utils::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Non-blocking writer that refuses every other write and accepts at most `max_write` bytes
+    struct FlakyRemote {
+        data: Arc<Mutex<Vec<u8>>>,
+        max_write: usize,
+        calls: usize,
+        fail: bool,
+    }
+
+    impl Write for FlakyRemote {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.calls += 1;
+            if self.fail {
+                return Err(std::io::Error::new(ErrorKind::ConnectionReset, "channel closed"));
+            }
+            if self.calls % 2 == 1 {
+                return Err(ErrorKind::WouldBlock.into());
+            }
+            let n = buf.len().min(self.max_write);
+            self.data.lock().unwrap().extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn local_files(dir: &tempfile::TempDir, sizes: &[usize]) -> Vec<UploadTask> {
+        sizes.iter().enumerate().map(|(i, size)| {
+            let local_path = dir.path().join(format!("input{}.pdb", i));
+            std::fs::write(&local_path, (0..*size).map(|b| (b % 251) as u8).collect::<Vec<_>>()).unwrap();
+            UploadTask { local_path, remote_path: format!("/remote/input{}.pdb", i), progress_key: Some(format!("file{}", i)) }
+        }).collect()
+    }
+
+    fn remote_content(size: usize) -> Vec<u8> {
+        (0..size).map(|b| (b % 253) as u8).collect()
+    }
+
+    #[test]
+    fn test_fresh_download_replaces_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("trajectory.dcd");
+        std::fs::write(&local, b"unrelated file").unwrap();
+        let remote = remote_content(100_000);
+
+        let progress = download_to_local(std::io::Cursor::new(remote.clone()), remote.len() as u64, &local, false, 4096, None, None).unwrap();
+
+        assert_eq!(std::fs::read(&local).unwrap(), remote);
+        assert_eq!(progress.bytes_transferred, 100_000);
+        assert_eq!(progress.percentage, 100.0);
+    }
+
+    #[test]
+    fn test_resumed_download_continues_from_partial_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("trajectory.dcd");
+        let remote = remote_content(100_000);
+        std::fs::write(&local, &remote[..90_000]).unwrap();
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let reported_clone = reported.clone();
+        let callback: ProgressCallback = Box::new(move |bytes, total| reported_clone.lock().unwrap().push((bytes, total)));
+
+        let progress = download_to_local(std::io::Cursor::new(remote.clone()), remote.len() as u64, &local, true, 4096, None, Some(callback)).unwrap();
+
+        assert_eq!(std::fs::read(&local).unwrap(), remote);
+        assert_eq!(progress.bytes_transferred, 100_000);
+        // Only the missing tail was read: progress starts past the resume point
+        let reported = reported.lock().unwrap();
+        assert!(reported.first().unwrap().0 > 90_000);
+        assert_eq!(reported.len(), 3);
+    }
+
+    #[test]
+    fn test_resume_restarts_when_remote_shrank() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("trajectory.dcd");
+        std::fs::write(&local, remote_content(5_000)).unwrap();
+        // The remote file was replaced by a shorter one since the partial download
+        let remote: Vec<u8> = vec![7u8; 3_000];
+
+        download_to_local(std::io::Cursor::new(remote.clone()), remote.len() as u64, &local, true, 4096, None, None).unwrap();
+
+        assert_eq!(std::fs::read(&local).unwrap(), remote);
+    }
+
+    #[test]
+    fn test_download_size_mismatch_is_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("trajectory.dcd");
+
+        // Stat said 2000 bytes but the stream ended early
+        let err = download_to_local(std::io::Cursor::new(remote_content(1_500)), 2_000, &local, false, 4096, None, None).unwrap_err();
+
+        assert!(err.to_string().contains("does not match remote size"));
+    }
+
+    #[test]
+    fn test_cancelled_download_removes_partial_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("trajectory.dcd");
+        let remote = remote_content(20_000);
+
+        // Cancel from the progress callback after the first chunk lands
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_from_callback = cancel.clone();
+        let callback: ProgressCallback = Box::new(move |_, _| cancel_from_callback.store(true, std::sync::atomic::Ordering::SeqCst));
+
+        let err = download_to_local(std::io::Cursor::new(remote), 20_000, &local, false, 4096, Some(&cancel), Some(callback)).unwrap_err();
+
+        assert!(matches!(err.downcast_ref::<SSHError>(), Some(SSHError::TransferCancelled(_))));
+        assert!(err.to_string().contains("trajectory.dcd"));
+        assert!(!local.exists());
+    }
+
+    #[test]
+    fn test_tail_reads_from_offset() {
+        let log = b"Info: Startup\nTIMING: 500\nTIMING: 1000\n";
+        let first = read_tail(std::io::Cursor::new(&log[..]), log.len() as u64, 0, MAX_TAIL_BYTES).unwrap();
+        assert_eq!(first.content.as_bytes(), log);
+        assert_eq!(first.offset, log.len() as u64);
+        assert!(!first.reset && !first.has_more);
+
+        let grown = [&log[..], b"TIMING: 1500\n"].concat();
+        let next = read_tail(std::io::Cursor::new(&grown), grown.len() as u64, first.offset, MAX_TAIL_BYTES).unwrap();
+        assert_eq!(next.content, "TIMING: 1500\n");
+
+        // Nothing new since the last poll
+        let idle = read_tail(std::io::Cursor::new(&grown), grown.len() as u64, next.offset, MAX_TAIL_BYTES).unwrap();
+        assert_eq!(idle.content, "");
+        assert_eq!(idle.offset, next.offset);
+    }
+
+    #[test]
+    fn test_tail_resets_after_truncation() {
+        let rotated = b"Info: restarted\n";
+
+        let tail = read_tail(std::io::Cursor::new(&rotated[..]), rotated.len() as u64, 4_000, MAX_TAIL_BYTES).unwrap();
+
+        assert!(tail.reset);
+        assert_eq!(tail.content, "Info: restarted\n");
+        assert_eq!(tail.offset, rotated.len() as u64);
+    }
+
+    #[test]
+    fn test_tail_is_bounded_per_read() {
+        let log = vec![b'x'; 10_000];
+
+        let tail = read_tail(std::io::Cursor::new(&log), 10_000, 1_000, 4_096).unwrap();
+
+        assert_eq!(tail.offset, 5_096);
+        assert!(tail.has_more);
+    }
+
+    #[test]
+    fn test_tail_keeps_split_character_for_next_read() {
+        // "Å" is two bytes; a read limit inside it must not emit a replacement character
+        let log = "ENERGY: Å\n".as_bytes();
+        let split = log.len() as u64 - 2;
+
+        let first = read_tail(std::io::Cursor::new(log), log.len() as u64, 0, split).unwrap();
+        assert_eq!(first.content, "ENERGY: ");
+        assert_eq!(first.offset, split - 1);
+
+        let second = read_tail(std::io::Cursor::new(log), log.len() as u64, first.offset, MAX_TAIL_BYTES).unwrap();
+        assert_eq!(second.content, "Å\n");
+    }
+
+    #[test]
+    fn test_resume_offset() {
+        assert_eq!(resume_offset(None, 1000, true), 0);
+        assert_eq!(resume_offset(Some(400), 1000, false), 0);
+        assert_eq!(resume_offset(Some(400), 1000, true), 400);
+        assert_eq!(resume_offset(Some(1000), 1000, true), 1000);
+        assert_eq!(resume_offset(Some(1001), 1000, true), 0);
+    }
+
+    #[test]
+    fn test_throttle_delay() {
+        let second = Duration::from_secs(1);
+        assert_eq!(throttle_delay(1_000_000, Duration::ZERO, None), None);
+        assert_eq!(throttle_delay(1_000_000, Duration::ZERO, Some(0)), None);
+        // 2 MB at 1 MB/s should take 2s; 1.5s have passed
+        assert_eq!(throttle_delay(2_000_000, Duration::from_millis(1500), Some(1_000_000)), Some(Duration::from_millis(500)));
+        // Already at or below the limit
+        assert_eq!(throttle_delay(1_000_000, second, Some(1_000_000)), None);
+        assert_eq!(throttle_delay(1_000_000, second * 3, Some(1_000_000)), None);
+    }
+
+    #[test]
+    fn test_concurrent_uploads_complete_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let tasks = local_files(&dir, &[CHUNK_SIZE * 2 + 17, 0, 1000]);
+        let remotes: Vec<Arc<Mutex<Vec<u8>>>> = (0..tasks.len()).map(|_| Arc::new(Mutex::new(Vec::new()))).collect();
+        let mut opened = Vec::new();
+        let mut finished = 0;
+        let mut last_progress = vec![(0u64, 0u64); tasks.len()];
+
+        let result = run_concurrent_uploads(
+            &tasks,
+            2,
+            Some(Duration::from_secs(5)),
+            None,
+            |slot, task| {
+                let index = tasks.iter().position(|t| t.remote_path == task.remote_path).unwrap();
+                opened.push((slot, index));
+                Ok(FlakyRemote { data: remotes[index].clone(), max_write: 4096, calls: 0, fail: false })
+            },
+            |_| { finished += 1; Ok(()) },
+            |index, bytes, total| last_progress[index] = (bytes, total),
+        ).unwrap();
+
+        for (i, task) in tasks.iter().enumerate() {
+            assert_eq!(*remotes[i].lock().unwrap(), std::fs::read(&task.local_path).unwrap(), "file {} content", i);
+            assert_eq!(result.files[i].file_name.as_deref(), Some(format!("file{}", i).as_str()));
+            assert_eq!(result.files[i].percentage, 100.0);
+        }
+        assert_eq!(finished, 3);
+        // Two files start together; the third takes whichever slot frees first
+        assert_eq!(&opened[..2], &[(0, 0), (1, 1)]);
+        assert_eq!(opened[2].1, 2);
+        assert_eq!(last_progress[0], ((CHUNK_SIZE * 2 + 17) as u64, (CHUNK_SIZE * 2 + 17) as u64));
+        assert_eq!(result.total.total_bytes, (CHUNK_SIZE * 2 + 17 + 1000) as u64);
+        assert_eq!(result.total.bytes_transferred, result.total.total_bytes);
+        assert_eq!(result.total.file_name, None);
+    }
+
+    #[test]
+    fn test_concurrent_uploads_share_one_rate_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        // 400 KB over two slots at 2 MB/s: ~200ms if the slots share the budget, ~100ms if each had its own
+        let tasks = local_files(&dir, &[200_000, 200_000]);
+
+        let started = Instant::now();
+        let result = run_concurrent_uploads(
+            &tasks,
+            2,
+            None,
+            Some(2_000_000),
+            |_, _| Ok(FlakyRemote { data: Arc::new(Mutex::new(Vec::new())), max_write: 65536, calls: 0, fail: false }),
+            |_| Ok(()),
+            |_, _, _| {},
+        ).unwrap();
+
+        assert_eq!(result.total.bytes_transferred, 400_000);
+        assert!(started.elapsed() >= Duration::from_millis(190), "took {:?}", started.elapsed());
+    }
+
+    #[test]
+    fn test_concurrent_upload_write_error_names_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let tasks = local_files(&dir, &[100, 100]);
+
+        let err = run_concurrent_uploads(
+            &tasks,
+            2,
+            None,
+            None,
+            |slot, _| Ok(FlakyRemote { data: Arc::new(Mutex::new(Vec::new())), max_write: 4096, calls: 0, fail: slot == 1 }),
+            |_| Ok(()),
+            |_, _, _| {},
+        ).unwrap_err();
+
+        assert!(err.to_string().contains("/remote/input1.pdb"));
+    }
+
+    #[test]
+    fn test_concurrent_upload_stall_times_out() {
+        struct Stuck;
+        impl Write for Stuck {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(ErrorKind::WouldBlock.into())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let tasks = local_files(&dir, &[100]);
+
+        let err = run_concurrent_uploads(&tasks, 4, Some(Duration::from_millis(30)), None, |_, _| Ok(Stuck), |_| Ok(()), |_, _, _| {}).unwrap_err();
+
+        assert!(matches!(err.downcast_ref::<SSHError>(), Some(SSHError::TimeoutError(_))));
+    }
+
+    #[test]
+    fn test_directory_upload_plan_walks_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("toppar/par files")).unwrap();
+        std::fs::create_dir(root.join("empty")).unwrap();
+        std::fs::write(root.join("structure.psf"), vec![0u8; 100]).unwrap();
+        std::fs::write(root.join("toppar/par files/par all36.prm"), vec![0u8; 25]).unwrap();
+
+        let plan = plan_directory_upload(root, "/scratch/alice/job_001/input_files/").unwrap();
+
+        assert_eq!(plan.directories, vec![
+            "/scratch/alice/job_001/input_files",
+            "/scratch/alice/job_001/input_files/empty",
+            "/scratch/alice/job_001/input_files/toppar",
+            "/scratch/alice/job_001/input_files/toppar/par files",
+        ]);
+        let remote_files: Vec<&str> = plan.files.iter().map(|file| file.remote_path.as_str()).collect();
+        assert_eq!(remote_files, vec![
+            "/scratch/alice/job_001/input_files/structure.psf",
+            "/scratch/alice/job_001/input_files/toppar/par files/par all36.prm",
+        ]);
+        assert_eq!(plan.total_bytes, 125);
+        assert!(plan.skipped_symlinks.is_empty());
+    }
+
+    #[test]
+    fn test_directory_upload_plan_empty_directory() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let plan = plan_directory_upload(dir.path(), "/remote/empty").unwrap();
+
+        assert_eq!(plan.directories, vec!["/remote/empty"]);
+        assert!(plan.files.is_empty());
+        assert_eq!(plan.total_bytes, 0);
+    }
+
+    #[test]
+    fn test_directory_upload_plan_rejects_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("structure.psf");
+        std::fs::write(&file, b"psf").unwrap();
+
+        assert!(plan_directory_upload(&file, "/remote").is_err());
+        assert!(plan_directory_upload(&dir.path().join("missing"), "/remote").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_directory_upload_plan_skips_symlinks() {
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret.key"), b"key").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("structure.pdb"), b"pdb").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret.key"), dir.path().join("linked.key")).unwrap();
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("linked_dir")).unwrap();
+
+        let plan = plan_directory_upload(dir.path(), "/remote").unwrap();
+
+        assert_eq!(plan.directories, vec!["/remote"]);
+        assert_eq!(plan.files.len(), 1);
+        assert_eq!(plan.files[0].remote_path, "/remote/structure.pdb");
+        assert_eq!(plan.skipped_symlinks.len(), 2);
+        assert_eq!(plan.total_bytes, 3);
+    }
 
     #[test]
     fn test_file_transfer_progress() {
@@ -4,131 +4,14 @@
 //! requiring actual SSH connections or file operations.
 
 use std::collections::HashMap;
+use std::sync::Mutex;
+use anyhow::Result;
+use async_trait::async_trait;
 use super::errors::SSHError;
 use super::sftp::{SftpFileEntry, FileTransferProgress};
+pub use super::memory_fs::{MockFileSystem, MockFile};
 use super::commands::CommandResult;
-
-/// Mock file system state for SFTP operations testing
-#[derive(Debug, Clone, Default)]
-pub struct MockFileSystem {
-    pub files: HashMap<String, MockFile>,
-    pub directories: HashMap<String, Vec<String>>,
-}
-
-/// Mock file representation for testing
-#[derive(Debug, Clone)]
-pub struct MockFile {
-    pub size: u64,
-    pub permissions: u32,
-    pub is_directory: bool,
-    pub modified_time: u64,
-    pub content: Vec<u8>,
-}
-
-impl MockFileSystem {
-    /// Create a new empty mock file system
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Add a mock file to the file system
-    pub fn add_file(&mut self, path: &str, size: u64, permissions: u32) -> &mut Self {
-        let file = MockFile {
-            size,
-            permissions,
-            is_directory: false,
-            modified_time: 1234567890,
-            content: vec![0u8; size as usize],
-        };
-        self.files.insert(path.to_string(), file);
-
-        // Add to parent directory listing
-        if let Some(parent) = std::path::Path::new(path).parent() {
-            let parent_str = parent.to_str().unwrap_or("/");
-            let filename = std::path::Path::new(path).file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or(path);
-
-            self.directories.entry(parent_str.to_string())
-                .or_default()
-                .push(filename.to_string());
-        }
-
-        self
-    }
-
-    /// Add a mock directory to the file system
-    pub fn add_directory(&mut self, path: &str, permissions: u32) -> &mut Self {
-        let dir = MockFile {
-            size: 4096,
-            permissions,
-            is_directory: true,
-            modified_time: 1234567890,
-            content: vec![],
-        };
-        self.files.insert(path.to_string(), dir);
-        self.directories.insert(path.to_string(), Vec::new());
-
-        // Add to parent directory listing
-        if let Some(parent) = std::path::Path::new(path).parent() {
-            let parent_str = parent.to_str().unwrap_or("/");
-            let dirname = std::path::Path::new(path).file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or(path);
-
-            self.directories.entry(parent_str.to_string())
-                .or_default()
-                .push(dirname.to_string());
-        }
-
-        self
-    }
-
-    /// Get file info for a path (simulates SFTP stat operation)
-    pub fn get_file_info(&self, path: &str) -> Result<SftpFileEntry, SSHError> {
-        match self.files.get(path) {
-            Some(file) => {
-                let name = std::path::Path::new(path)
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or(path)
-                    .to_string();
-
-                Ok(SftpFileEntry {
-                    name,
-                    path: path.to_string(),
-                    size: file.size,
-                    is_directory: file.is_directory,
-                    permissions: file.permissions,
-                    modified_time: Some(file.modified_time),
-                })
-            }
-            None => Err(SSHError::FileTransferError(format!("File not found: {}", path)))
-        }
-    }
-
-    /// List directory contents (simulates SFTP readdir operation)
-    pub fn list_directory(&self, path: &str) -> Result<Vec<SftpFileEntry>, SSHError> {
-        match self.directories.get(path) {
-            Some(entries) => {
-                let mut results = Vec::new();
-                for entry in entries {
-                    let full_path = if path.ends_with('/') {
-                        format!("{}{}", path, entry)
-                    } else {
-                        format!("{}/{}", path, entry)
-                    };
-
-                    if let Ok(info) = self.get_file_info(&full_path) {
-                        results.push(info);
-                    }
-                }
-                Ok(results)
-            }
-            None => Err(SSHError::FileTransferError(format!("Directory not found: {}", path)))
-        }
-    }
-}
+use super::remote_executor::RemoteExecutor;
 
 /// Mock command execution results for testing
 pub struct MockCommandExecutor {
@@ -171,6 +54,130 @@ impl MockCommandExecutor {
     }
 }
 
+/// RemoteExecutor backed by a MockFileSystem, for testing automations without a cluster
+/// Commands get the response of the first registered prefix they start with (empty
+/// success otherwise) and are recorded in order, so tests can assert what was run
+pub struct MockRemoteExecutor {
+    pub username: String,
+    pub connected: bool,
+    pub filesystem: Mutex<MockFileSystem>,
+    pub command_responses: Vec<(String, CommandResult)>,
+    pub executed_commands: Mutex<Vec<String>>,
+}
+
+impl MockRemoteExecutor {
+    /// A connected executor for `username` with an empty file system
+    pub fn new(username: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            connected: true,
+            filesystem: Mutex::new(MockFileSystem::new()),
+            command_responses: Vec::new(),
+            executed_commands: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Answer commands starting with `prefix` with `stdout` and exit code 0
+    pub fn respond_to(&mut self, prefix: &str, stdout: &str) -> &mut Self {
+        self.command_responses.push((prefix.to_string(), CommandResult {
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+            duration_ms: 10,
+            timed_out: false,
+        }));
+        self
+    }
+
+    /// Commands run so far, in order
+    pub fn executed(&self) -> Vec<String> {
+        self.executed_commands.lock().unwrap().clone()
+    }
+
+    fn require_connected(&self) -> Result<()> {
+        if !self.connected {
+            return Err(SSHError::SessionError("Please connect to the cluster first".to_string()).into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RemoteExecutor for MockRemoteExecutor {
+    async fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    async fn get_username(&self) -> Result<String> {
+        self.require_connected()?;
+        Ok(self.username.clone())
+    }
+
+    async fn execute_command(&self, command: &str, _timeout: Option<u64>) -> Result<CommandResult> {
+        self.require_connected()?;
+        self.executed_commands.lock().unwrap().push(command.to_string());
+        Ok(self.command_responses.iter()
+            .find(|(prefix, _)| command.starts_with(prefix.as_str()))
+            .map(|(_, result)| result.clone())
+            .unwrap_or_else(|| MockCommandExecutor::new().default_response))
+    }
+
+    async fn upload_file(&self, local_path: &str, remote_path: &str) -> Result<FileTransferProgress> {
+        self.require_connected()?;
+        let content = std::fs::read_to_string(local_path)?;
+        self.filesystem.lock().unwrap().add_text_file(remote_path, &content);
+        Ok(MockProgressBuilder::completed(content.len() as u64))
+    }
+
+    async fn download_file(&self, remote_path: &str, local_path: &str) -> Result<FileTransferProgress> {
+        self.require_connected()?;
+        let content = self.filesystem.lock().unwrap().files.get(remote_path)
+            .map(|file| file.content.clone())
+            .ok_or_else(|| SSHError::FileTransferError(format!("File not found: {}", remote_path)))?;
+        std::fs::write(local_path, &content)?;
+        Ok(MockProgressBuilder::completed(content.len() as u64))
+    }
+
+    async fn list_files(&self, remote_path: &str, include_directories: bool) -> Result<Vec<SftpFileEntry>> {
+        self.require_connected()?;
+        let mut entries = self.filesystem.lock().unwrap().list_directory(remote_path)?;
+        if !include_directories {
+            entries.retain(|entry| !entry.is_directory);
+        }
+        Ok(entries)
+    }
+
+    async fn file_exists(&self, remote_path: &str) -> Result<bool> {
+        self.require_connected()?;
+        Ok(self.filesystem.lock().unwrap().files.contains_key(remote_path))
+    }
+
+    async fn mirror_directory(&self, source: &str, destination: &str) -> Result<CommandResult> {
+        self.execute_command(&format!("rsync -az {} {}", source, destination), None).await
+    }
+
+    /// Recorded as the read command a real cluster would run, answered from the file system
+    async fn read_remote_file(&self, remote_path: &str) -> Result<String> {
+        self.require_connected()?;
+        self.executed_commands.lock().unwrap().push(super::commands::read_text_file_command(remote_path));
+        self.filesystem.lock().unwrap().files.get(remote_path)
+            .map(|file| String::from_utf8_lossy(&file.content).into_owned())
+            .ok_or_else(|| SSHError::FileTransferError(format!("File not found: {}", remote_path)).into())
+    }
+
+    /// Recorded as the batched command a real cluster would run, answered from the file system
+    async fn read_remote_files(&self, remote_paths: &[String]) -> Result<Vec<Result<String>>> {
+        self.require_connected()?;
+        self.executed_commands.lock().unwrap().push(super::commands::read_files_command(remote_paths));
+        let filesystem = self.filesystem.lock().unwrap();
+        Ok(remote_paths.iter()
+            .map(|path| filesystem.files.get(path)
+                .map(|file| String::from_utf8_lossy(&file.content).into_owned())
+                .ok_or_else(|| SSHError::FileTransferError(format!("File not found: {}", path)).into()))
+            .collect())
+    }
+}
+
 /// Helper to create common error scenarios for testing
 pub struct MockErrorBuilder;
 
@@ -325,6 +332,26 @@ mod tests {
         assert!(result.stdout.contains("total 8"));
     }
 
+    #[tokio::test]
+    async fn test_mock_remote_executor() {
+        let mut remote = MockRemoteExecutor::new("testuser");
+        remote.respond_to("squeue", "123|RUNNING|None\n");
+        remote.filesystem.lock().unwrap()
+            .add_directory("/projects/testuser", 0o755)
+            .add_text_file("/projects/testuser/job_info.json", "{}");
+
+        assert_eq!(remote.get_username().await.unwrap(), "testuser");
+        assert_eq!(remote.execute_command("squeue -j 123", None).await.unwrap().stdout, "123|RUNNING|None\n");
+        assert!(remote.execute_command("sacct -j 123", None).await.unwrap().stdout.is_empty());
+        assert_eq!(remote.executed(), vec!["squeue -j 123", "sacct -j 123"]);
+        assert_eq!(remote.read_remote_file("/projects/testuser/job_info.json").await.unwrap(), "{}");
+        assert!(remote.read_remote_file("/projects/testuser/missing.json").await.is_err());
+        assert_eq!(remote.list_files("/projects/testuser", false).await.unwrap().len(), 1);
+
+        remote.connected = false;
+        assert!(remote.get_username().await.is_err());
+    }
+
     #[test]
     fn test_path_validation() {
         assert!(MockPathValidator::is_valid_remote_path("/home/user"));
@@ -0,0 +1,207 @@
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+use polling::{Event, Events, Poller};
+use ssh2::{BlockDirections, Channel, Session};
+use crate::log_debug;
+
+/// Longest the forwarder waits without a socket event, so keepalives still go out on a quiet link
+const MAX_IDLE_WAIT: Duration = Duration::from_secs(1);
+
+/// Pause before retrying a write the other side isn't ready to take
+const WRITE_RETRY: Duration = Duration::from_millis(2);
+
+/// Bridge a jump host's direct-tcpip channel to a local socket
+///
+/// libssh2 can only run a session over a real socket, so the destination session is given
+/// one end of a loopback connection while a background thread copies bytes between the
+/// other end and the channel. The thread sleeps until the loopback socket or the jump
+/// host's socket (`jump_socket`, a clone of the one the session runs on) has data.
+/// It owns the jump host session and ends (closing it) when either side closes.
+pub fn spawn_forwarder(session: Session, channel: Channel, jump_socket: TcpStream, keepalive_interval: u32) -> io::Result<TcpStream> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))?;
+    let client = TcpStream::connect(listener.local_addr()?)?;
+    let (local, peer) = listener.accept()?;
+
+    // Only our own client may be on the other end of the loopback socket
+    if peer != client.local_addr()? {
+        return Err(io::Error::new(ErrorKind::PermissionDenied, "Unexpected connection to tunnel socket"));
+    }
+
+    local.set_nonblocking(true)?;
+    session.set_blocking(false);
+    let mut readiness = SocketReadiness::new(&local, &jump_socket)?;
+
+    std::thread::spawn(move || {
+        let keepalive = (keepalive_interval > 0).then(|| Duration::from_secs(keepalive_interval as u64));
+        let mut last_keepalive = Instant::now();
+        let mut channel = channel;
+
+        let result = pump(&mut channel, local, || {
+            if keepalive.is_some_and(|interval| last_keepalive.elapsed() >= interval) {
+                let _ = session.keepalive_send();
+                last_keepalive = Instant::now();
+            }
+            // libssh2 may be stalled on sending (a window adjust, a rekey) rather than on receiving
+            let jump_writable = matches!(session.block_directions(), BlockDirections::Outbound | BlockDirections::Both);
+            readiness.wait(MAX_IDLE_WAIT, jump_writable)
+        });
+        log_debug!(category: "SSH", message: "Jump host tunnel closed", details: "{:?}", result);
+
+        let _ = channel.close();
+    });
+
+    Ok(client)
+}
+
+/// Blocks until one of two sockets is readable (or the second one writable), using the OS poller
+pub struct SocketReadiness {
+    poller: Poller,
+    events: Events,
+    sockets: [TcpStream; 2],
+}
+
+impl SocketReadiness {
+    pub fn new(first: &TcpStream, second: &TcpStream) -> io::Result<Self> {
+        let poller = Poller::new()?;
+        let sockets = [first.try_clone()?, second.try_clone()?];
+        for (key, socket) in sockets.iter().enumerate() {
+            // SAFETY: both sockets are owned here and removed from the poller in Drop, before they close
+            unsafe { poller.add(socket, Event::none(key))? };
+        }
+        Ok(Self { poller, events: Events::new(), sockets })
+    }
+
+    /// Wait up to `timeout` for either socket to be readable, or for the second to be writable
+    /// when `second_writable` is set. Returns early on any event; a timeout is not an error
+    pub fn wait(&mut self, timeout: Duration, second_writable: bool) -> io::Result<()> {
+        // Interest is one-shot, so both sockets are re-armed on every wait
+        self.poller.modify(&self.sockets[0], Event::readable(0))?;
+        self.poller.modify(&self.sockets[1], if second_writable { Event::all(1) } else { Event::readable(1) })?;
+
+        self.events.clear();
+        match self.poller.wait(&mut self.events, Some(timeout)) {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::Interrupted => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Drop for SocketReadiness {
+    fn drop(&mut self) {
+        for socket in &self.sockets {
+            let _ = self.poller.delete(socket);
+        }
+    }
+}
+
+/// Copy bytes both ways between two non-blocking streams until either side closes
+/// `wait` runs whenever neither side has anything to copy, and should block until one might
+pub fn pump<A: Read + Write, B: Read + Write>(a: &mut A, mut b: B, mut wait: impl FnMut() -> io::Result<()>) -> io::Result<()> {
+    let mut buf = [0u8; 32 * 1024];
+
+    loop {
+        let a_to_b = copy_available(a, &mut b, &mut buf)?;
+        let b_to_a = copy_available(&mut b, a, &mut buf)?;
+
+        match (a_to_b, b_to_a) {
+            (Some(0), _) | (_, Some(0)) => return Ok(()),
+            (None, None) => wait()?,
+            _ => {}
+        }
+    }
+}
+
+/// Copy what is readable right now: Some(n) bytes copied (0 = closed), None if nothing was ready
+fn copy_available<R: Read, W: Write>(from: &mut R, to: &mut W, buf: &mut [u8]) -> io::Result<Option<usize>> {
+    match from.read(buf) {
+        Ok(0) => Ok(Some(0)),
+        Ok(n) => {
+            write_all_nonblocking(to, &buf[..n])?;
+            Ok(Some(n))
+        }
+        Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::Interrupted => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// write_all for a non-blocking writer: waits out WouldBlock instead of failing
+fn write_all_nonblocking<W: Write>(to: &mut W, mut data: &[u8]) -> io::Result<()> {
+    while !data.is_empty() {
+        match to.write(data) {
+            Ok(0) => return Err(io::Error::new(ErrorKind::WriteZero, "Tunnel peer closed")),
+            Ok(n) => data = &data[n..],
+            Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::Interrupted => std::thread::sleep(WRITE_RETRY),
+            Err(e) => return Err(e),
+        }
+    }
+    to.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A connected loopback pair: (our end, far end)
+    fn socket_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let near = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (far, _) = listener.accept().unwrap();
+        (near, far)
+    }
+
+    #[test]
+    fn test_pump_copies_both_directions_until_close() {
+        // client <-> [a  pump  b] <-> server
+        let (mut client, mut a) = socket_pair();
+        let (b, mut server) = socket_pair();
+        a.set_nonblocking(true).unwrap();
+        b.set_nonblocking(true).unwrap();
+        let mut readiness = SocketReadiness::new(&a, &b).unwrap();
+
+        let forwarder = std::thread::spawn(move || pump(&mut a, b, || readiness.wait(MAX_IDLE_WAIT, false)));
+
+        client.write_all(b"SSH-2.0-client\r\n").unwrap();
+        let mut greeting = [0u8; 16];
+        server.read_exact(&mut greeting).unwrap();
+        assert_eq!(&greeting, b"SSH-2.0-client\r\n");
+
+        let large: Vec<u8> = (0..200_000u32).map(|i| i as u8).collect();
+        let writer = std::thread::spawn(move || {
+            server.write_all(&large).unwrap();
+            server
+        });
+        let mut received = vec![0u8; 200_000];
+        client.read_exact(&mut received).unwrap();
+        assert!(received.iter().enumerate().all(|(i, b)| *b == i as u8));
+
+        // Closing one side ends the forwarder
+        drop(writer.join().unwrap());
+        assert!(forwarder.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_pump_sleeps_until_a_socket_is_ready() {
+        let (client, mut a) = socket_pair();
+        let (b, server) = socket_pair();
+        a.set_nonblocking(true).unwrap();
+        b.set_nonblocking(true).unwrap();
+        let mut readiness = SocketReadiness::new(&a, &b).unwrap();
+
+        let forwarder = std::thread::spawn(move || {
+            let mut waits = 0;
+            pump(&mut a, b, || {
+                waits += 1;
+                readiness.wait(MAX_IDLE_WAIT, false)
+            }).unwrap();
+            waits
+        });
+
+        // A quiet link must block in the poller, not spin: one wait covers the whole pause
+        std::thread::sleep(Duration::from_millis(50));
+        drop(client);
+        assert!(forwarder.join().unwrap() <= 2);
+        drop(server);
+    }
+}
@@ -0,0 +1,191 @@
+use std::io::{self, Seek, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Regular file, rw-r--r--
+const UNIX_FILE_MODE: u32 = 0o644;
+
+/// Highest deflate level
+pub const MAX_COMPRESSION_LEVEL: u8 = 9;
+
+/// zip's own default level (0 stores files without compressing them)
+pub const DEFAULT_COMPRESSION_LEVEL: u8 = 6;
+
+/// Writes a ZIP archive one entry at a time straight to a seekable sink (the destination file).
+/// Entry data is streamed through `Write`; only the central directory is kept in memory.
+/// Every entry is written as a large file, so local headers and the central directory
+/// carry the same ZIP64 fields whatever size the entry turns out to be.
+pub struct ZipStreamWriter<W: Write + Seek> {
+    zip: ZipWriter<W>,
+    level: u8,
+    entry_open: bool,
+}
+
+impl<W: Write + Seek> ZipStreamWriter<W> {
+    /// `level` 0 stores entries as-is; 1-9 deflates them at that level
+    pub fn new(inner: W, level: u8) -> Self {
+        Self { zip: ZipWriter::new(inner), level, entry_open: false }
+    }
+
+    /// Begin a new entry; the previous one (if any) is finished first
+    pub fn start_entry(&mut self, name: &str, modified_time: Option<u64>) -> io::Result<()> {
+        self.zip.start_file(name, entry_options(self.level, modified_time))?;
+        self.entry_open = true;
+        Ok(())
+    }
+
+    /// Write the central directory and hand back the sink
+    pub fn finish(self) -> io::Result<W> {
+        Ok(self.zip.finish()?)
+    }
+}
+
+impl<W: Write + Seek> Write for ZipStreamWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.entry_open {
+            return Err(io::Error::other("No archive entry started"));
+        }
+        self.zip.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.zip.flush()
+    }
+}
+
+fn entry_options(level: u8, modified_time: Option<u64>) -> SimpleFileOptions {
+    let options = SimpleFileOptions::default()
+        .large_file(true)
+        .unix_permissions(UNIX_FILE_MODE)
+        .last_modified_time(zip_timestamp(modified_time));
+    if level == 0 {
+        options.compression_method(CompressionMethod::Stored)
+    } else {
+        options
+            .compression_method(CompressionMethod::Deflated)
+            .compression_level(Some(level.min(MAX_COMPRESSION_LEVEL) as i64))
+    }
+}
+
+/// Entry timestamp (UTC); unknown or out-of-range times become the ZIP epoch, 1980-01-01
+fn zip_timestamp(modified_time: Option<u64>) -> zip::DateTime {
+    modified_time
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs as i64, 0))
+        .and_then(|time| zip::DateTime::try_from(time.naive_utc()).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+    use zip::ZipArchive;
+
+    fn write_archive(level: u8, files: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut zip = ZipStreamWriter::new(Cursor::new(Vec::new()), level);
+        for (name, data) in files {
+            zip.start_entry(name, Some(1_700_000_000)).unwrap();
+            // Several writes per entry, as the SFTP loop does
+            for chunk in data.chunks(1000) {
+                zip.write_all(chunk).unwrap();
+            }
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    fn read_archive(bytes: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        (0..archive.len())
+            .map(|index| {
+                let mut entry = archive.by_index(index).unwrap();
+                let mut data = Vec::new();
+                entry.read_to_end(&mut data).unwrap();
+                (entry.name().to_string(), data)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stored_archive_round_trip() {
+        let files = vec![
+            ("outputs/sim.dcd", (0..5000u32).flat_map(|i| i.to_le_bytes()).collect::<Vec<u8>>()),
+            ("outputs/task_0/namd.log", b"ENERGY: 0 1.0 2.0\n".to_vec()),
+        ];
+        let archive = write_archive(0, &files);
+
+        let entries = read_archive(&archive);
+        assert_eq!(entries.len(), 2);
+        for ((name, data), (expected_name, expected_data)) in entries.iter().zip(&files) {
+            assert_eq!(name, expected_name);
+            assert_eq!(data, expected_data);
+        }
+
+        let mut reader = ZipArchive::new(Cursor::new(&archive)).unwrap();
+        assert_eq!(reader.by_index(0).unwrap().compression(), CompressionMethod::Stored);
+    }
+
+    #[test]
+    fn test_deflated_archive_round_trip() {
+        let log = "ENERGY:    100    12.3   45.6\n".repeat(2000).into_bytes();
+        let archive = write_archive(6, &[("outputs/namd_output.log", log.clone())]);
+
+        let entries = read_archive(&archive);
+        assert_eq!(entries, vec![("outputs/namd_output.log".to_string(), log.clone())]);
+        assert!(archive.len() < log.len() / 10, "Repetitive log should compress well");
+    }
+
+    #[test]
+    fn test_compression_levels() {
+        let log = "ENERGY:    100    12.3   45.6\n".repeat(2000).into_bytes();
+        let fast = write_archive(1, &[("outputs/namd_output.log", log.clone())]);
+        let best = write_archive(MAX_COMPRESSION_LEVEL, &[("outputs/namd_output.log", log.clone())]);
+        let stored = write_archive(0, &[("outputs/namd_output.log", log.clone())]);
+
+        for archive in [&fast, &best] {
+            let mut reader = ZipArchive::new(Cursor::new(archive)).unwrap();
+            assert_eq!(reader.by_index(0).unwrap().compression(), CompressionMethod::Deflated);
+        }
+        assert!(best.len() <= fast.len());
+        assert!(stored.len() > log.len(), "Store-only keeps every byte");
+    }
+
+    #[test]
+    fn test_empty_archive_is_valid() {
+        let archive = write_archive(6, &[]);
+        assert!(read_archive(&archive).is_empty());
+    }
+
+    #[test]
+    fn test_empty_entry() {
+        let archive = write_archive(6, &[("outputs/empty.txt", Vec::new())]);
+        assert_eq!(read_archive(&archive), vec![("outputs/empty.txt".to_string(), Vec::new())]);
+    }
+
+    #[test]
+    fn test_write_without_entry_fails() {
+        let mut zip = ZipStreamWriter::new(Cursor::new(Vec::new()), 0);
+        assert!(zip.write_all(b"data").is_err());
+    }
+
+    #[test]
+    fn test_entry_metadata_reads_back() {
+        let archive = write_archive(0, &[("outputs/sim.coor", b"abc".to_vec())]);
+        let mut reader = ZipArchive::new(Cursor::new(&archive)).unwrap();
+        let entry = reader.by_index(0).unwrap();
+
+        assert_eq!(entry.size(), 3);
+        assert_eq!(entry.unix_mode().map(|mode| mode & 0o777), Some(UNIX_FILE_MODE));
+        // 2023-11-14 22:13:20 UTC
+        let modified = entry.last_modified().unwrap();
+        assert_eq!(
+            (modified.year(), modified.month(), modified.day(), modified.hour(), modified.minute(), modified.second()),
+            (2023, 11, 14, 22, 13, 20)
+        );
+    }
+
+    #[test]
+    fn test_zip_timestamp_falls_back_to_epoch() {
+        assert_eq!(zip_timestamp(None), zip::DateTime::default());
+        assert_eq!(zip_timestamp(Some(0)), zip::DateTime::default());
+    }
+}
@@ -0,0 +1,288 @@
+use crate::templates::Template;
+use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// A parent marks overridable regions of its config with `{{#block name}} ... {{/block}}`.
+/// A child's config holds only the blocks it replaces (or nothing, to take the parent's
+/// config unchanged); its variables are merged over the parent's, child winning.
+const BLOCK_START: &str = "{{#block ";
+const BLOCK_END: &str = "{{/block}}";
+
+struct Block<'a> {
+    name: &'a str,
+    /// From the start marker through the end marker
+    span: Range<usize>,
+    body: Range<usize>,
+}
+
+/// Flatten a template and its ancestors into the template that actually gets rendered
+/// `load` fetches a template by ID; a missing parent or an inheritance cycle is an error
+pub fn resolve_template(
+    template: &Template,
+    load: impl Fn(&str) -> Result<Option<Template>>,
+) -> Result<Template> {
+    // Walk up to the root; `chain` keeps the way back down
+    let mut chain = vec![template.clone()];
+    let mut seen = HashSet::from([template.id.clone()]);
+    let mut resolved = loop {
+        let current = chain.pop().expect("chain always holds the current template");
+        let Some(parent_id) = current.extends.clone() else {
+            break current;
+        };
+        if !seen.insert(parent_id.clone()) {
+            let path: Vec<&str> = chain.iter().chain([&current]).map(|t| t.id.as_str()).collect();
+            return Err(anyhow!("Template inheritance cycle: {} -> {}", path.join(" -> "), parent_id));
+        }
+        let parent = load(&parent_id)?
+            .ok_or_else(|| anyhow!("Template '{}' extends '{}', which doesn't exist", current.id, parent_id))?;
+        chain.push(current);
+        chain.push(parent);
+    };
+
+    while let Some(child) = chain.pop() {
+        let namd_config_template = override_blocks(&resolved.namd_config_template, &child.namd_config_template)
+            .map_err(|e| anyhow!("Template '{}': {}", child.id, e))?;
+        let mut variables = resolved.variables;
+        variables.extend(child.variables.clone());
+        resolved = Template { namd_config_template, variables, ..child };
+    }
+    Ok(resolved)
+}
+
+/// Check that block markers in a config are well formed
+pub fn validate_blocks(config: &str) -> Result<()> {
+    parse_blocks(config).map(|_| ())
+}
+
+/// Remove block markers before rendering; a line left empty by that is dropped entirely
+pub fn strip_block_markers(config: &str) -> String {
+    config
+        .split_inclusive('\n')
+        .filter_map(|line| {
+            let stripped = remove_markers(line);
+            if stripped.len() != line.len() && stripped.trim().is_empty() {
+                None
+            } else {
+                Some(stripped)
+            }
+        })
+        .collect()
+}
+
+/// Parent config with each of the child's blocks swapped in; markers are kept so
+/// grandchildren can override the same blocks
+fn override_blocks(parent: &str, child: &str) -> Result<String> {
+    if child.trim().is_empty() {
+        return Ok(parent.to_string());
+    }
+
+    let child_blocks = parse_blocks(child)?;
+    let mut outside = String::new();
+    let mut pos = 0;
+    for block in &child_blocks {
+        outside.push_str(&child[pos..block.span.start]);
+        pos = block.span.end;
+    }
+    outside.push_str(&child[pos..]);
+    if !outside.trim().is_empty() {
+        return Err(anyhow!("a template that extends another may only contain {{{{#block}}}} overrides"));
+    }
+
+    let parent_blocks = parse_blocks(parent)?;
+    if let Some(unknown) = child_blocks.iter().find(|c| !parent_blocks.iter().any(|p| p.name == c.name)) {
+        return Err(anyhow!("overrides block '{}', which the parent doesn't define", unknown.name));
+    }
+
+    let mut merged = String::new();
+    let mut pos = 0;
+    for block in &parent_blocks {
+        merged.push_str(&parent[pos..block.body.start]);
+        match child_blocks.iter().find(|c| c.name == block.name) {
+            Some(replacement) => merged.push_str(&child[replacement.body.clone()]),
+            None => merged.push_str(&parent[block.body.clone()]),
+        }
+        pos = block.body.end;
+    }
+    merged.push_str(&parent[pos..]);
+    Ok(merged)
+}
+
+fn parse_blocks(config: &str) -> Result<Vec<Block<'_>>> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = config[pos..].find(BLOCK_START) {
+        let start = pos + offset;
+        let name_start = start + BLOCK_START.len();
+        let name_len = config[name_start..].find("}}")
+            .ok_or_else(|| anyhow!("unclosed {{{{#block}}}} marker"))?;
+        let name = config[name_start..name_start + name_len].trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(anyhow!("block name '{}' must contain only alphanumeric characters and underscores", name));
+        }
+
+        let body_start = name_start + name_len + 2;
+        let body_len = config[body_start..].find(BLOCK_END)
+            .ok_or_else(|| anyhow!("block '{}' is missing {{{{/block}}}}", name))?;
+        let body_end = body_start + body_len;
+        if config[body_start..body_end].contains(BLOCK_START) {
+            return Err(anyhow!("block '{}' contains another block - blocks can't be nested", name));
+        }
+        if blocks.iter().any(|b| b.name == name) {
+            return Err(anyhow!("block '{}' is defined twice", name));
+        }
+
+        let end = body_end + BLOCK_END.len();
+        blocks.push(Block { name, span: start..end, body: body_start..body_end });
+        pos = end;
+    }
+
+    if config.matches(BLOCK_END).count() != blocks.len() {
+        return Err(anyhow!("{{{{/block}}}} without a matching {{{{#block}}}}"));
+    }
+    Ok(blocks)
+}
+
+fn remove_markers(line: &str) -> String {
+    let mut out = line.replace(BLOCK_END, "");
+    while let Some(start) = out.find(BLOCK_START) {
+        match out[start..].find("}}") {
+            Some(len) => out.replace_range(start..start + len + 2, ""),
+            None => break,
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::{VariableDefinition, VariableType};
+    use std::collections::HashMap;
+
+    const BASE_CONFIG: &str = "\
+structure {{structure_file}}
+{{#block solvent}}
+PME no
+{{/block}}
+temperature {{temperature}}
+{{#block ensemble}}
+langevin on
+{{/block}}
+run {{steps}}
+";
+
+    fn template(id: &str, extends: Option<&str>, config: &str, variables: &[(&str, f64)]) -> Template {
+        Template {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            namd_config_template: config.to_string(),
+            variables: variables.iter().map(|(key, default)| {
+                (key.to_string(), VariableDefinition {
+                    key: key.to_string(),
+                    label: key.to_string(),
+                    var_type: VariableType::Number { min: 0.0, max: 1e9, default: *default },
+                    help_text: None,
+                    visible_when: None,
+                    unit: None,
+                    render_unit: None,
+                    precision: None,
+                })
+            }).collect(),
+            extends: extends.map(str::to_string),
+            created_at: "2025-01-01".to_string(),
+            updated_at: "2025-01-01".to_string(),
+        }
+    }
+
+    fn loader(templates: Vec<Template>) -> impl Fn(&str) -> Result<Option<Template>> {
+        let by_id: HashMap<String, Template> = templates.into_iter().map(|t| (t.id.clone(), t)).collect();
+        move |id| Ok(by_id.get(id).cloned())
+    }
+
+    fn default_of(template: &Template, key: &str) -> f64 {
+        match template.variables[key].var_type {
+            VariableType::Number { default, .. } => default,
+            _ => panic!("not a number"),
+        }
+    }
+
+    #[test]
+    fn test_child_overrides_blocks_and_variables() {
+        let base = template("base", None, BASE_CONFIG, &[("temperature", 300.0), ("steps", 1000.0)]);
+        let solvent = template(
+            "explicit_solvent",
+            Some("base"),
+            "{{#block solvent}}\nPME yes\nPMEGridSpacing 1.0\n{{/block}}\n",
+            &[("steps", 50000.0), ("cutoff", 12.0)],
+        );
+
+        let resolved = resolve_template(&solvent, loader(vec![base])).unwrap();
+
+        assert_eq!(resolved.id, "explicit_solvent");
+        assert_eq!(
+            strip_block_markers(&resolved.namd_config_template),
+            "structure {{structure_file}}\nPME yes\nPMEGridSpacing 1.0\ntemperature {{temperature}}\nlangevin on\nrun {{steps}}\n"
+        );
+        assert_eq!(resolved.variables.len(), 3);
+        assert_eq!(default_of(&resolved, "steps"), 50000.0);
+        assert_eq!(default_of(&resolved, "temperature"), 300.0);
+    }
+
+    #[test]
+    fn test_multi_level_and_empty_child_config() {
+        let base = template("base", None, BASE_CONFIG, &[]);
+        let npt = template("npt", Some("base"), "{{#block ensemble}}\nlangevinPiston on\n{{/block}}", &[]);
+        let npt_copy = template("npt_copy", Some("npt"), "", &[]);
+
+        let resolved = resolve_template(&npt_copy, loader(vec![base, npt])).unwrap();
+        let config = strip_block_markers(&resolved.namd_config_template);
+        assert!(config.contains("PME no\n"));
+        assert!(config.contains("langevinPiston on\n"));
+        assert!(!config.contains("langevin on"));
+    }
+
+    #[test]
+    fn test_cycles_and_missing_parents_rejected() {
+        let a = template("a", Some("b"), "", &[]);
+        let b = template("b", Some("a"), "", &[]);
+        let err = resolve_template(&a, loader(vec![b.clone()])).unwrap_err().to_string();
+        assert!(err.contains("cycle: a -> b -> a"), "{}", err);
+
+        let itself = template("a", Some("a"), "", &[]);
+        assert!(resolve_template(&itself, loader(vec![])).unwrap_err().to_string().contains("cycle"));
+
+        let orphan = template("orphan", Some("gone"), "", &[]);
+        assert!(resolve_template(&orphan, loader(vec![])).unwrap_err().to_string().contains("doesn't exist"));
+    }
+
+    #[test]
+    fn test_invalid_child_configs() {
+        let base = template("base", None, BASE_CONFIG, &[]);
+
+        let loose_text = template("child", Some("base"), "{{#block solvent}}PME yes{{/block}}\nrun 10\n", &[]);
+        let err = resolve_template(&loose_text, loader(vec![base.clone()])).unwrap_err().to_string();
+        assert!(err.contains("may only contain"), "{}", err);
+
+        let unknown = template("child", Some("base"), "{{#block restraints}}\n{{/block}}", &[]);
+        let err = resolve_template(&unknown, loader(vec![base])).unwrap_err().to_string();
+        assert!(err.contains("block 'restraints'"), "{}", err);
+    }
+
+    #[test]
+    fn test_block_syntax_validation() {
+        assert!(validate_blocks(BASE_CONFIG).is_ok());
+        assert!(validate_blocks("{{#block a}}\nrun 10\n").is_err());
+        assert!(validate_blocks("{{#block a}}{{#block b}}{{/block}}{{/block}}").is_err());
+        assert!(validate_blocks("{{#block a}}{{/block}}{{#block a}}{{/block}}").is_err());
+        assert!(validate_blocks("run 10\n{{/block}}").is_err());
+        assert!(validate_blocks("{{#block bad name}}{{/block}}").is_err());
+    }
+
+    #[test]
+    fn test_strip_markers_inline() {
+        assert_eq!(strip_block_markers("a {{#block x}}b{{/block}} c\n"), "a b c\n");
+        assert_eq!(strip_block_markers("run 10"), "run 10");
+    }
+}
@@ -1,5 +1,8 @@
 pub mod types;
 pub mod renderer;
+pub mod inheritance;
+pub mod units;
 
-pub use types::{Template, VariableDefinition, VariableType, TemplateSummary};
+pub use types::{Template, VariableDefinition, VariableType, TemplateSummary, Condition};
 pub use renderer::render_template;
+pub use inheritance::resolve_template;
@@ -1,89 +1,404 @@
-use crate::templates::{Template, VariableType};
+use crate::templates::{Template, VariableDefinition, VariableType};
 use anyhow::{Result, anyhow};
 use std::collections::HashMap;
 use serde_json::Value;
 
 /// Render a template by substituting {{variables}} with actual values
 /// File paths are extracted to filenames and get "input_files/" prepended automatically
+/// Numbers with a `render_unit` are converted from their form `unit` (templates::units)
+/// Lines using a hidden variable that has no value are left out of the config
+/// Inheritance must already be resolved (templates::resolve_template); block markers are dropped
+///
+/// Besides `{{var}}`, configs can use:
+/// - `{{#if var}} ... {{else}} ... {{/if}}` - kept when var is true, a non-zero number,
+///   non-empty text or a non-empty list; `{{else}}` is optional
+/// - `{{#each list}} ... {{/each}}` - repeated per item of a List variable, with `{{this}}`
+///   for the item and `{{@index}}` for its position from 0
+/// - `\{{` for a literal `{{`
+///
+/// A line holding nothing but a block tag is removed along with its line break, and blocks
+/// nest. Unmatched or mismatched tags are an error naming the line.
 pub fn render_template(
     template: &Template,
     values: &HashMap<String, Value>,
 ) -> Result<String> {
-    let mut rendered = template.namd_config_template.clone();
-
-    // Replace each variable with its value
-    for (key, var_def) in &template.variables {
-        // Get the value for this variable
-        let value = values.get(key)
-            .ok_or_else(|| anyhow!("Missing required variable: {}", key))?;
-
-        // Convert value to string based on variable type
-        let value_str = match &var_def.var_type {
-            VariableType::FileUpload { .. } => {
-                // File uploads: extract filename from potential full path, then prepend input_files/
-                let file_path = value.as_str()
-                    .ok_or_else(|| anyhow!("Variable {} must be a string (filename)", key))?;
-
-                // Extract filename from full path if present (handles both "/home/user/file.psf" and "file.psf")
-                let filename = std::path::Path::new(file_path)
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or(file_path);  // Fallback to original if extraction fails
-
-                format!("{}/{}", crate::ssh::JobDirectoryStructure::INPUT_FILES, filename)
+    let hidden_unset = template.hidden_unset_keys(values);
+    let hidden_placeholders: Vec<String> = hidden_unset.iter().map(|key| format!("{{{{{}}}}}", key)).collect();
+    let config: String = crate::templates::inheritance::strip_block_markers(&template.namd_config_template)
+        .split_inclusive('\n')
+        .filter(|line| !hidden_placeholders.iter().any(|placeholder| line.contains(placeholder.as_str())))
+        .collect();
+
+    // Every variable that applies needs a value, whether or not the config uses it
+    for key in template.variables.keys() {
+        if !hidden_unset.contains(key) && !values.contains_key(key) {
+            return Err(anyhow!("Missing required variable: {}", key));
+        }
+    }
+
+    let nodes = parse(&config)?;
+    let context = RenderContext { template, values, hidden_unset: &hidden_unset };
+    let mut rendered = String::new();
+    let mut unreplaced = Vec::new();
+    context.render_nodes(&nodes, None, &mut rendered, &mut unreplaced)?;
+
+    // Unknown names indicate missing values or a typo in the template
+    if !unreplaced.is_empty() {
+        return Err(anyhow!(
+            "Template contains unreplaced variables: {}",
+            unreplaced.join(", ")
+        ));
+    }
+
+    Ok(rendered)
+}
+
+/// Check a config's tags are well formed without rendering it
+pub fn validate_syntax(config: &str) -> Result<()> {
+    parse(&crate::templates::inheritance::strip_block_markers(config)).map(|_| ())
+}
+
+/// Variable names a config uses in `{{var}}`, `{{#if var}}` and `{{#each var}}` tags, each with
+/// the line of its first use, in order of appearance
+pub fn referenced_variables(config: &str) -> Result<Vec<(String, usize)>> {
+    fn collect(nodes: &[Node], found: &mut Vec<(String, usize)>) {
+        for node in nodes {
+            match node {
+                Node::Var { name, line } => add(found, name, *line),
+                Node::If { name, line, then, otherwise } => {
+                    add(found, name, *line);
+                    collect(then, found);
+                    collect(otherwise, found);
+                }
+                Node::Each { name, line, body } => {
+                    add(found, name, *line);
+                    collect(body, found);
+                }
+                Node::Text(_) | Node::Item { .. } => {}
             }
-            VariableType::Number { .. } => {
-                // Numbers: convert to string
-                if let Some(num) = value.as_f64() {
-                    // Format number appropriately (remove unnecessary decimals for integers)
-                    if num.fract() == 0.0 {
-                        format!("{:.0}", num)
-                    } else {
-                        format!("{}", num)
-                    }
-                } else if let Some(num) = value.as_i64() {
-                    format!("{}", num)
+        }
+    }
+    fn add(found: &mut Vec<(String, usize)>, name: &str, line: usize) {
+        if !found.iter().any(|(n, _)| n == name) {
+            found.push((name.to_string(), line));
+        }
+    }
+
+    let mut found = Vec::new();
+    collect(&parse(&crate::templates::inheritance::strip_block_markers(config))?, &mut found);
+    Ok(found)
+}
+
+/// Convert one value to its config text based on the variable type
+fn format_value(key: &str, var_def: &VariableDefinition, value: &Value) -> Result<String> {
+    let value_str = match &var_def.var_type {
+        VariableType::FileUpload { .. } => {
+            // File uploads: extract filename from potential full path, then prepend input_files/
+            let file_path = value.as_str()
+                .ok_or_else(|| anyhow!("Variable {} must be a string (filename)", key))?;
+
+            // Extract filename from full path if present (handles both "/home/user/file.psf" and "file.psf")
+            let filename = std::path::Path::new(file_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(file_path);  // Fallback to original if extraction fails
+
+            format!("{}/{}", crate::ssh::JobDirectoryStructure::INPUT_FILES, filename)
+        }
+        VariableType::Number { .. } => {
+            // Numbers: convert to the config's unit and precision, then to string
+            if let Some(num) = value.as_f64() {
+                let num = crate::templates::units::render_number(var_def, num)
+                    .map_err(|e| anyhow!("Variable {}: {}", key, e))?;
+                // Format number appropriately (remove unnecessary decimals for integers)
+                if num.fract() == 0.0 {
+                    format!("{:.0}", num)
                 } else {
-                    return Err(anyhow!("Variable {} must be a number", key));
+                    format!("{}", num)
+                }
+            } else if let Some(num) = value.as_i64() {
+                format!("{}", num)
+            } else {
+                return Err(anyhow!("Variable {} must be a number", key));
+            }
+        }
+        VariableType::Boolean { .. } => {
+            // Booleans: convert to "yes"/"no" for NAMD
+            let bool_val = value.as_bool()
+                .ok_or_else(|| anyhow!("Variable {} must be a boolean", key))?;
+            if bool_val { "yes" } else { "no" }.to_string()
+        }
+        VariableType::Text { .. } | VariableType::Select { .. } => {
+            // Text and Select: use the string as-is
+            value.as_str()
+                .ok_or_else(|| anyhow!("Variable {} must be a string", key))?
+                .to_string()
+        }
+        VariableType::List { .. } => {
+            // Lists outside {{#each}}: items separated by spaces
+            let items = value.as_array()
+                .ok_or_else(|| anyhow!("Variable {} must be a list", key))?;
+            items.iter().map(item_text).collect::<Vec<_>>().join(" ")
+        }
+    };
+    Ok(value_str)
+}
+
+fn item_text(item: &Value) -> String {
+    match item {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Whether an {{#if}} keeps its first branch
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.trim().is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Text(String),
+    Var { name: String, line: usize },
+    /// {{this}} (false) or {{@index}} (true) inside {{#each}}
+    Item { index: bool, line: usize },
+    If { name: String, line: usize, then: Vec<Node>, otherwise: Vec<Node> },
+    Each { name: String, line: usize, body: Vec<Node> },
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Tag { tag: String, line: usize },
+}
+
+fn is_block_tag(tag: &str) -> bool {
+    tag.starts_with('#') || tag.starts_with('/') || tag == "else"
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Split config text into literal text and {{tags}}, applying \{{ escapes and removing
+/// lines that hold only a block tag
+fn tokenize(config: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut pos = 0;
+
+    while let Some(offset) = config[pos..].find("{{") {
+        let start = pos + offset;
+        if config[..start].ends_with('\\') {
+            literal.push_str(&config[pos..start - 1]);
+            literal.push_str("{{");
+            pos = start + 2;
+            continue;
+        }
+        literal.push_str(&config[pos..start]);
+
+        let line = config[..start].matches('\n').count() + 1;
+        let close = config[start..].find("}}")
+            .ok_or_else(|| anyhow!("Unclosed '{{{{' on line {} (write \\{{{{ for a literal '{{{{')", line))?;
+        let tag = config[start + 2..start + close].trim().to_string();
+        let mut end = start + close + 2;
+
+        if is_block_tag(&tag) {
+            let line_start = config[..start].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = config[end..].find('\n').map_or(config.len(), |i| end + i);
+            let before = &config[line_start..start];
+            if before.trim().is_empty() && config[end..line_end].trim().is_empty() {
+                literal.truncate(literal.len() - before.len());
+                end = (line_end + 1).min(config.len());
+            }
+        }
+
+        tokens.push(Token::Text(std::mem::take(&mut literal)));
+        tokens.push(Token::Tag { tag, line });
+        pos = end;
+    }
+    literal.push_str(&config[pos..]);
+    tokens.push(Token::Text(literal));
+    Ok(tokens)
+}
+
+/// The block a run of nodes sits in, for matching its closing tag
+struct OpenBlock<'a> {
+    kind: &'a str,
+    name: &'a str,
+    line: usize,
+    allows_else: bool,
+}
+
+enum BlockEnd {
+    Else,
+    Close,
+}
+
+fn parse(config: &str) -> Result<Vec<Node>> {
+    let tokens = tokenize(config)?;
+    let mut tokens = tokens.into_iter();
+    let (nodes, _) = parse_nodes(&mut tokens, None)?;
+    Ok(nodes)
+}
+
+fn parse_nodes(tokens: &mut std::vec::IntoIter<Token>, open: Option<&OpenBlock>) -> Result<(Vec<Node>, BlockEnd)> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        let (tag, line) = match token {
+            Token::Text(text) => {
+                if !text.is_empty() {
+                    nodes.push(Node::Text(text));
                 }
+                continue;
             }
-            VariableType::Boolean { .. } => {
-                // Booleans: convert to "yes"/"no" for NAMD
-                let bool_val = value.as_bool()
-                    .ok_or_else(|| anyhow!("Variable {} must be a boolean", key))?;
-                if bool_val { "yes" } else { "no" }.to_string()
+            Token::Tag { tag, line } => (tag, line),
+        };
+
+        if let Some(closing) = tag.strip_prefix('/') {
+            return match open {
+                Some(block) if block.kind == closing => Ok((nodes, BlockEnd::Close)),
+                Some(block) => Err(anyhow!(
+                    "{{{{/{}}}}} on line {} doesn't match {{{{#{} {}}}}} opened on line {}",
+                    closing, line, block.kind, block.name, block.line
+                )),
+                None => Err(anyhow!("{{{{/{}}}}} on line {} has no matching {{{{#{}}}}}", closing, line, closing)),
+            };
+        }
+
+        if tag == "else" {
+            return match open {
+                Some(block) if block.allows_else => Ok((nodes, BlockEnd::Else)),
+                _ => Err(anyhow!("{{{{else}}}} on line {} is not inside an {{{{#if}}}}", line)),
+            };
+        }
+
+        if let Some(opening) = tag.strip_prefix('#') {
+            let (kind, name) = opening.split_once(char::is_whitespace)
+                .map(|(kind, name)| (kind, name.trim()))
+                .unwrap_or((opening, ""));
+            if kind != "if" && kind != "each" {
+                return Err(anyhow!("Unknown block {{{{#{}}}}} on line {} (expected #if or #each)", kind, line));
             }
-            VariableType::Text { .. } => {
-                // Text: use as-is
-                value.as_str()
-                    .ok_or_else(|| anyhow!("Variable {} must be a string", key))?
-                    .to_string()
+            if !is_identifier(name) {
+                return Err(anyhow!("{{{{#{}}}}} on line {} needs a variable name", kind, line));
             }
+
+            let block = OpenBlock { kind, name, line, allows_else: kind == "if" };
+            let (body, end) = parse_nodes(tokens, Some(&block))?;
+            let node = match (kind, end) {
+                ("if", BlockEnd::Else) => {
+                    let no_second_else = OpenBlock { allows_else: false, ..block };
+                    let (otherwise, _) = parse_nodes(tokens, Some(&no_second_else))?;
+                    Node::If { name: name.to_string(), line, then: body, otherwise }
+                }
+                ("if", BlockEnd::Close) => Node::If { name: name.to_string(), line, then: body, otherwise: Vec::new() },
+                _ => Node::Each { name: name.to_string(), line, body },
+            };
+            nodes.push(node);
+            continue;
+        }
+
+        let node = match tag.as_str() {
+            "this" => Node::Item { index: false, line },
+            "@index" => Node::Item { index: true, line },
+            name if is_identifier(name) => Node::Var { name: name.to_string(), line },
+            _ => return Err(anyhow!("Unrecognized tag '{{{{{}}}}}' on line {}", tag, line)),
         };
+        nodes.push(node);
+    }
 
-        // Replace all occurrences of {{key}} with value_str
-        let placeholder = format!("{{{{{}}}}}", key);
-        rendered = rendered.replace(&placeholder, &value_str);
+    match open {
+        Some(block) => Err(anyhow!(
+            "{{{{#{} {}}}}} opened on line {} is never closed with {{{{/{}}}}}",
+            block.kind, block.name, block.line, block.kind
+        )),
+        None => Ok((nodes, BlockEnd::Close)),
     }
+}
+
+struct RenderContext<'a> {
+    template: &'a Template,
+    values: &'a HashMap<String, Value>,
+    hidden_unset: &'a [String],
+}
 
-    // Check for any unreplaced variables (indicates missing values or typo in template)
-    if rendered.contains("{{") && rendered.contains("}}") {
-        // Find unreplaced variables for error message
-        let unreplaced: Vec<&str> = rendered
-            .split("{{")
-            .skip(1)
-            .filter_map(|s| s.split("}}").next())
-            .collect();
-
-        if !unreplaced.is_empty() {
-            return Err(anyhow!(
-                "Template contains unreplaced variables: {}",
-                unreplaced.join(", ")
-            ));
+impl RenderContext<'_> {
+    /// Value of a variable for a block tag; hidden variables without a value count as absent
+    fn block_value(&self, name: &str, unreplaced: &mut Vec<String>) -> Option<&Value> {
+        if !self.template.variables.contains_key(name) {
+            if !unreplaced.iter().any(|n| n == name) {
+                unreplaced.push(name.to_string());
+            }
+            return None;
+        }
+        if self.hidden_unset.iter().any(|key| key == name) {
+            return None;
         }
+        self.values.get(name)
     }
 
-    Ok(rendered)
+    fn render_nodes(
+        &self,
+        nodes: &[Node],
+        item: Option<(&Value, usize)>,
+        out: &mut String,
+        unreplaced: &mut Vec<String>,
+    ) -> Result<()> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Var { name, line } => {
+                    match (self.template.variables.get(name), self.values.get(name)) {
+                        (Some(var_def), Some(value)) => out.push_str(&format_value(name, var_def, value)?),
+                        (Some(_), None) if self.hidden_unset.contains(name) => {}
+                        (Some(_), None) => return Err(anyhow!("Missing required variable: {} (line {})", name, line)),
+                        (None, _) => {
+                            if !unreplaced.contains(name) {
+                                unreplaced.push(name.clone());
+                            }
+                        }
+                    }
+                }
+                Node::Item { index, line } => {
+                    let (value, position) = item.ok_or_else(|| {
+                        let tag = if *index { "@index" } else { "this" };
+                        anyhow!("{{{{{}}}}} on line {} is outside {{{{#each}}}}", tag, line)
+                    })?;
+                    if *index {
+                        out.push_str(&position.to_string());
+                    } else {
+                        out.push_str(&item_text(value));
+                    }
+                }
+                Node::If { name, then, otherwise, .. } => {
+                    let branch = if self.block_value(name, unreplaced).is_some_and(is_truthy) { then } else { otherwise };
+                    self.render_nodes(branch, item, out, unreplaced)?;
+                }
+                Node::Each { name, line, body } => {
+                    let Some(value) = self.block_value(name, unreplaced) else {
+                        continue;
+                    };
+                    let items = value.as_array()
+                        .ok_or_else(|| anyhow!("{{{{#each {}}}}} on line {} needs a list, got {}", name, line, value))?;
+                    for (position, entry) in items.iter().enumerate() {
+                        self.render_nodes(body, Some((entry, position)), out, unreplaced)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -105,6 +420,10 @@ mod tests {
                     default: 300.0,
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
         variables.insert(
@@ -116,6 +435,10 @@ mod tests {
                     extensions: vec![".psf".to_string()],
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
 
@@ -125,6 +448,7 @@ mod tests {
             description: "Test template".to_string(),
             namd_config_template: "temperature {{temperature}}\nstructure {{structure_file}}".to_string(),
             variables,
+            extends: None,
             created_at: "2025-01-01".to_string(),
             updated_at: "2025-01-01".to_string(),
         };
@@ -147,6 +471,10 @@ mod tests {
                 label: "PME".to_string(),
                 var_type: VariableType::Boolean { default: true },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
 
@@ -156,6 +484,7 @@ mod tests {
             description: "Test template".to_string(),
             namd_config_template: "PME {{pme_enabled}}".to_string(),
             variables,
+            extends: None,
             created_at: "2025-01-01".to_string(),
             updated_at: "2025-01-01".to_string(),
         };
@@ -185,6 +514,10 @@ mod tests {
                     default: 300.0,
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
 
@@ -194,6 +527,7 @@ mod tests {
             description: "Test template".to_string(),
             namd_config_template: "temperature {{temperature}}".to_string(),
             variables,
+            extends: None,
             created_at: "2025-01-01".to_string(),
             updated_at: "2025-01-01".to_string(),
         };
@@ -215,6 +549,7 @@ mod tests {
             description: "Test template".to_string(),
             namd_config_template: "temperature {{undefined_var}}".to_string(),
             variables,
+            extends: None,
             created_at: "2025-01-01".to_string(),
             updated_at: "2025-01-01".to_string(),
         };
@@ -240,6 +575,10 @@ mod tests {
                     default: 1000000.0,
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
 
@@ -249,6 +588,7 @@ mod tests {
             description: "Test template".to_string(),
             namd_config_template: "run {{steps}}".to_string(),
             variables,
+            extends: None,
             created_at: "2025-01-01".to_string(),
             updated_at: "2025-01-01".to_string(),
         };
@@ -259,4 +599,224 @@ mod tests {
         let rendered = render_template(&template, &values).unwrap();
         assert_eq!(rendered, "run 10000"); // Should not have .0
     }
+
+    fn barostat_template() -> Template {
+        let mut variables = HashMap::new();
+        variables.insert(
+            "npt_enabled".to_string(),
+            VariableDefinition {
+                key: "npt_enabled".to_string(),
+                label: "NPT".to_string(),
+                var_type: VariableType::Boolean { default: false },
+                help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
+            },
+        );
+        variables.insert(
+            "barostat_pressure".to_string(),
+            VariableDefinition {
+                key: "barostat_pressure".to_string(),
+                label: "Barostat Pressure".to_string(),
+                var_type: VariableType::Number { min: 0.0, max: 10.0, default: 1.01325 },
+                help_text: None,
+                visible_when: Some(crate::templates::Condition {
+                    variable: "npt_enabled".to_string(),
+                    equals: Value::from(true),
+                }),
+                unit: None,
+                render_unit: None,
+                precision: None,
+            },
+        );
+
+        Template {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: "Test template".to_string(),
+            namd_config_template: "langevinPiston {{npt_enabled}}\nlangevinPistonTarget {{barostat_pressure}}\nrun 1000\n".to_string(),
+            variables,
+            extends: None,
+            created_at: "2025-01-01".to_string(),
+            updated_at: "2025-01-01".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_hidden_unset_variable_lines_omitted() {
+        let template = barostat_template();
+
+        let mut values = HashMap::new();
+        values.insert("npt_enabled".to_string(), Value::from(false));
+        assert_eq!(render_template(&template, &values).unwrap(), "langevinPiston no\nrun 1000\n");
+
+        // Hidden but provided: still rendered
+        values.insert("barostat_pressure".to_string(), Value::from(1.5));
+        assert_eq!(render_template(&template, &values).unwrap(), "langevinPiston no\nlangevinPistonTarget 1.5\nrun 1000\n");
+
+        // Visible and unset is still an error
+        let mut values = HashMap::new();
+        values.insert("npt_enabled".to_string(), Value::from(true));
+        assert!(render_template(&template, &values).unwrap_err().to_string().contains("Missing required variable: barostat_pressure"));
+    }
+
+    fn var(key: &str, var_type: VariableType) -> (String, VariableDefinition) {
+        (key.to_string(), VariableDefinition {
+            key: key.to_string(),
+            label: key.to_string(),
+            var_type,
+            help_text: None,
+            visible_when: None,
+            unit: None,
+            render_unit: None,
+            precision: None,
+        })
+    }
+
+    fn ensemble_template(config: &str) -> Template {
+        let variables = [
+            var("npt_enabled", VariableType::Boolean { default: false }),
+            var("pme_enabled", VariableType::Boolean { default: true }),
+            var("pressure", VariableType::Number { min: 0.0, max: 10.0, default: 1.01325 }),
+            var("restraints", VariableType::List { default: vec![] }),
+        ].into_iter().collect();
+
+        Template {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: "Test template".to_string(),
+            namd_config_template: config.to_string(),
+            variables,
+            extends: None,
+            created_at: "2025-01-01".to_string(),
+            updated_at: "2025-01-01".to_string(),
+        }
+    }
+
+    fn ensemble_values(npt: bool, pme: bool, restraints: &[&str]) -> HashMap<String, Value> {
+        HashMap::from([
+            ("npt_enabled".to_string(), Value::from(npt)),
+            ("pme_enabled".to_string(), Value::from(pme)),
+            ("pressure".to_string(), Value::from(1.5)),
+            ("restraints".to_string(), Value::from(restraints.iter().map(|r| r.to_string()).collect::<Vec<_>>())),
+        ])
+    }
+
+    const NESTED_CONFIG: &str = "\
+{{#if npt_enabled}}
+langevinPiston on
+{{#if pme_enabled}}
+langevinPistonTarget {{pressure}}
+{{else}}
+# barostat needs PME
+{{/if}}
+{{else}}
+langevinPiston off
+{{/if}}
+run 1000
+";
+
+    #[test]
+    fn test_nested_conditionals() {
+        let template = ensemble_template(NESTED_CONFIG);
+
+        let render = |npt, pme| render_template(&template, &ensemble_values(npt, pme, &[])).unwrap();
+        assert_eq!(render(true, true), "langevinPiston on\nlangevinPistonTarget 1.5\nrun 1000\n");
+        assert_eq!(render(true, false), "langevinPiston on\n# barostat needs PME\nrun 1000\n");
+        assert_eq!(render(false, true), "langevinPiston off\nrun 1000\n");
+        assert_eq!(render(false, false), "langevinPiston off\nrun 1000\n");
+    }
+
+    #[test]
+    fn test_each_over_list() {
+        let template = ensemble_template("\
+colvars on
+{{#each restraints}}
+colvar {{@index}} {{this}}{{#if npt_enabled}} npt{{/if}}
+{{/each}}
+run 1000
+");
+
+        let rendered = render_template(&template, &ensemble_values(true, true, &["dist_a", "dist_b"])).unwrap();
+        assert_eq!(rendered, "colvars on\ncolvar 0 dist_a npt\ncolvar 1 dist_b npt\nrun 1000\n");
+
+        let rendered = render_template(&template, &ensemble_values(false, true, &[])).unwrap();
+        assert_eq!(rendered, "colvars on\nrun 1000\n");
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        let template = ensemble_template("set box \\{{0 0 0}}\nPME {{pme_enabled}}");
+        let rendered = render_template(&template, &ensemble_values(false, true, &[])).unwrap();
+        assert_eq!(rendered, "set box {{0 0 0}}\nPME yes");
+    }
+
+    #[test]
+    fn test_unmatched_tags_are_errors() {
+        let error = |config: &str| {
+            let template = ensemble_template(config);
+            render_template(&template, &ensemble_values(true, true, &[])).unwrap_err().to_string()
+        };
+
+        let err = error("{{#if npt_enabled}}\nlangevinPiston on\n");
+        assert!(err.contains("{{#if npt_enabled}} opened on line 1 is never closed with {{/if}}"), "{}", err);
+
+        let err = error("{{#if npt_enabled}}\n{{#each restraints}}\n{{/if}}\n{{/each}}\n");
+        assert!(err.contains("{{/if}} on line 3 doesn't match {{#each restraints}} opened on line 2"), "{}", err);
+
+        let err = error("run 1000\n{{/each}}\n");
+        assert!(err.contains("{{/each}} on line 2 has no matching {{#each}}"), "{}", err);
+
+        let err = error("{{else}}\n");
+        assert!(err.contains("not inside an {{#if}}"), "{}", err);
+
+        let err = error("{{#if npt_enabled}}a{{else}}b{{else}}c{{/if}}");
+        assert!(err.contains("not inside an {{#if}}"), "{}", err);
+
+        let err = error("colvar {{this}}\n");
+        assert!(err.contains("{{this}} on line 1 is outside {{#each}}"), "{}", err);
+
+        let err = error("run {{steps\n");
+        assert!(err.contains("Unclosed '{{' on line 1"), "{}", err);
+
+        let err = error("{{#while npt_enabled}}{{/while}}");
+        assert!(err.contains("Unknown block {{#while}}"), "{}", err);
+
+        let err = error("{{#each pme_enabled}}x{{/each}}");
+        assert!(err.contains("needs a list"), "{}", err);
+
+        let err = error("{{#if undefined_flag}}x{{/if}}");
+        assert!(err.contains("unreplaced variables: undefined_flag"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_syntax() {
+        assert!(validate_syntax(NESTED_CONFIG).is_ok());
+        assert!(validate_syntax("{{#if a}}{{#each b}}{{/if}}").is_err());
+    }
+
+    #[test]
+    fn test_unit_conversion_and_precision() {
+        let mut template = ensemble_template("temperature {{temperature}}\nlangevinPistonTarget {{pressure}}\n");
+        let (key, mut temperature) = var("temperature", VariableType::Number { min: -50.0, max: 100.0, default: 25.0 });
+        temperature.unit = Some("C".to_string());
+        temperature.render_unit = Some("K".to_string());
+        template.variables.insert(key, temperature);
+        let pressure = template.variables.get_mut("pressure").unwrap();
+        pressure.unit = Some("atm".to_string());
+        pressure.render_unit = Some("bar".to_string());
+
+        let mut values = ensemble_values(true, true, &[]);
+        values.insert("temperature".to_string(), Value::from(37.0));
+        values.insert("pressure".to_string(), Value::from(1.0));
+        let rendered = render_template(&template, &values).unwrap();
+        assert_eq!(rendered, "temperature 310.15\nlangevinPistonTarget 1.01325\n");
+
+        template.variables.get_mut("pressure").unwrap().precision = Some(2);
+        template.variables.get_mut("temperature").unwrap().precision = Some(0);
+        let rendered = render_template(&template, &values).unwrap();
+        assert_eq!(rendered, "temperature 310\nlangevinPistonTarget 1.01\n");
+    }
 }
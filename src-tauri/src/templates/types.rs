@@ -9,6 +9,9 @@ pub struct Template {
     pub description: String,
     pub namd_config_template: String,  // NAMD config with {{variables}}
     pub variables: HashMap<String, VariableDefinition>,
+    /// Parent template ID: inherits its config and variables (see templates::inheritance)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -26,6 +29,44 @@ impl Template {
             })
             .collect()
     }
+
+    /// Whether a variable applies given the current values
+    /// A condition on an unset variable compares against that variable's default, and a
+    /// variable whose controlling variable is itself hidden is hidden too
+    pub fn is_variable_visible(&self, key: &str, values: &HashMap<String, serde_json::Value>) -> bool {
+        let mut current = key;
+        // Bounded walk so a cyclic definition can't loop forever (import rejects cycles anyway)
+        for _ in 0..=self.variables.len() {
+            let Some(condition) = self.variables.get(current).and_then(|var_def| var_def.visible_when.as_ref()) else {
+                return true;
+            };
+            let Some(controller) = self.variables.get(&condition.variable) else {
+                return false;
+            };
+            let value = values.get(&condition.variable).cloned().or_else(|| controller.var_type.default_value());
+            if !value.is_some_and(|value| values_equal(&value, &condition.equals)) {
+                return false;
+            }
+            current = &condition.variable;
+        }
+        false
+    }
+
+    /// Variables that are hidden and have no value - treated as absent when rendering
+    pub fn hidden_unset_keys(&self, values: &HashMap<String, serde_json::Value>) -> Vec<String> {
+        self.variables.keys()
+            .filter(|key| !values.contains_key(*key) && !self.is_variable_visible(key, values))
+            .cloned()
+            .collect()
+    }
+}
+
+/// JSON equality, except numbers compare by value so 1 matches 1.0
+fn values_equal(a: &serde_json::Value, b: &serde_json::Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
 }
 
 /// Variable definition describes a template variable's type and constraints
@@ -36,6 +77,25 @@ pub struct VariableDefinition {
     pub var_type: VariableType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub help_text: Option<String>,
+    /// Only shown (and required) while another variable has a given value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visible_when: Option<Condition>,
+    /// Number variables: unit the form collects the value in (e.g. "C"), shown next to the field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Number variables: unit NAMD expects (e.g. "K"); the value is converted from `unit` when rendering
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub render_unit: Option<String>,
+    /// Number variables: decimal places kept in the rendered config (see templates::units)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<u32>,
+}
+
+/// Visibility rule: `variable` (another variable's key) currently equals `equals`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Condition {
+    pub variable: String,
+    pub equals: serde_json::Value,
 }
 
 /// Variable type enum with type-specific options
@@ -57,6 +117,27 @@ pub enum VariableType {
     FileUpload {
         extensions: Vec<String>,  // e.g., [".psf", ".pdb"]
     },
+    Select {
+        options: Vec<String>,  // e.g., ["velocity-verlet", "langevin"]
+        default: String,       // Required: must be one of options
+    },
+    List {
+        default: Vec<String>,  // Required: items used by {{#each}}, may be empty
+    },
+}
+
+impl VariableType {
+    /// Default value as JSON; FileUpload has none
+    pub fn default_value(&self) -> Option<serde_json::Value> {
+        match self {
+            VariableType::Number { default, .. } => Some(serde_json::Value::from(*default)),
+            VariableType::Text { default } => Some(serde_json::Value::from(default.clone())),
+            VariableType::Boolean { default } => Some(serde_json::Value::from(*default)),
+            VariableType::Select { default, .. } => Some(serde_json::Value::from(default.clone())),
+            VariableType::List { default } => Some(serde_json::Value::from(default.clone())),
+            VariableType::FileUpload { .. } => None,
+        }
+    }
 }
 
 /// Summary view of template for list display
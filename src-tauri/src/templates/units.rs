@@ -0,0 +1,114 @@
+use crate::templates::VariableDefinition;
+use anyhow::{Result, anyhow};
+
+/// Decimal places kept after a conversion when the variable doesn't set `precision`
+pub const DEFAULT_CONVERTED_PRECISION: u32 = 6;
+
+/// Most decimal places a variable may ask for
+pub const MAX_PRECISION: u32 = 12;
+
+/// A unit in the conversion table: `value * scale + offset` gives the value in the
+/// dimension's base unit (K, bar, fs, Å)
+struct Unit {
+    symbols: &'static [&'static str],
+    dimension: &'static str,
+    scale: f64,
+    offset: f64,
+}
+
+const UNITS: &[Unit] = &[
+    // Temperature
+    Unit { symbols: &["K"], dimension: "temperature", scale: 1.0, offset: 0.0 },
+    Unit { symbols: &["C", "°C"], dimension: "temperature", scale: 1.0, offset: 273.15 },
+    Unit { symbols: &["F", "°F"], dimension: "temperature", scale: 5.0 / 9.0, offset: 459.67 * 5.0 / 9.0 },
+    // Pressure
+    Unit { symbols: &["bar"], dimension: "pressure", scale: 1.0, offset: 0.0 },
+    Unit { symbols: &["atm"], dimension: "pressure", scale: 1.01325, offset: 0.0 },
+    Unit { symbols: &["Pa"], dimension: "pressure", scale: 1e-5, offset: 0.0 },
+    Unit { symbols: &["kPa"], dimension: "pressure", scale: 1e-2, offset: 0.0 },
+    Unit { symbols: &["MPa"], dimension: "pressure", scale: 10.0, offset: 0.0 },
+    // Time
+    Unit { symbols: &["fs"], dimension: "time", scale: 1.0, offset: 0.0 },
+    Unit { symbols: &["ps"], dimension: "time", scale: 1e3, offset: 0.0 },
+    Unit { symbols: &["ns"], dimension: "time", scale: 1e6, offset: 0.0 },
+    // Length
+    Unit { symbols: &["A", "Å"], dimension: "length", scale: 1.0, offset: 0.0 },
+    Unit { symbols: &["pm"], dimension: "length", scale: 1e-2, offset: 0.0 },
+    Unit { symbols: &["nm"], dimension: "length", scale: 10.0, offset: 0.0 },
+];
+
+fn lookup(symbol: &str) -> Result<&'static Unit> {
+    UNITS.iter()
+        .find(|unit| unit.symbols.contains(&symbol))
+        .ok_or_else(|| anyhow!("unknown unit '{}'", symbol))
+}
+
+/// Check a value in `from` can be converted to `to`
+pub fn check_conversion(from: &str, to: &str) -> Result<()> {
+    let (source, target) = (lookup(from)?, lookup(to)?);
+    if source.dimension != target.dimension {
+        return Err(anyhow!(
+            "cannot convert '{}' ({}) to '{}' ({})",
+            from, source.dimension, to, target.dimension
+        ));
+    }
+    Ok(())
+}
+
+/// Convert a value between two units of the same dimension
+pub fn convert(value: f64, from: &str, to: &str) -> Result<f64> {
+    check_conversion(from, to)?;
+    let (source, target) = (lookup(from)?, lookup(to)?);
+    let base = value * source.scale + source.offset;
+    Ok((base - target.offset) / target.scale)
+}
+
+/// Round to a number of decimal places
+pub fn round_to(value: f64, precision: u32) -> f64 {
+    let factor = 10f64.powi(precision as i32);
+    (value * factor).round() / factor
+}
+
+/// A Number variable's value as written to the config: converted from `unit` to `render_unit`
+/// when both are set, then rounded to `precision` (or DEFAULT_CONVERTED_PRECISION after a conversion)
+pub fn render_number(var_def: &VariableDefinition, value: f64) -> Result<f64> {
+    let (value, converted) = match (&var_def.unit, &var_def.render_unit) {
+        (Some(unit), Some(render_unit)) if unit != render_unit => (convert(value, unit, render_unit)?, true),
+        _ => (value, false),
+    };
+    Ok(match var_def.precision {
+        Some(precision) => round_to(value, precision),
+        None if converted => round_to(value, DEFAULT_CONVERTED_PRECISION),
+        None => value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(round_to(convert(25.0, "C", "K").unwrap(), 6), 298.15);
+        assert_eq!(round_to(convert(32.0, "°F", "K").unwrap(), 6), 273.15);
+        assert_eq!(round_to(convert(1.0, "atm", "bar").unwrap(), 6), 1.01325);
+        assert_eq!(round_to(convert(101325.0, "Pa", "atm").unwrap(), 6), 1.0);
+        assert_eq!(convert(2.0, "ps", "fs").unwrap(), 2000.0);
+        assert_eq!(convert(1.2, "nm", "Å").unwrap(), 12.0);
+        assert_eq!(convert(300.0, "K", "K").unwrap(), 300.0);
+    }
+
+    #[test]
+    fn test_incompatible_and_unknown_units() {
+        let err = check_conversion("C", "nm").unwrap_err().to_string();
+        assert_eq!(err, "cannot convert 'C' (temperature) to 'nm' (length)");
+        assert!(check_conversion("atm", "psi").unwrap_err().to_string().contains("unknown unit 'psi'"));
+    }
+
+    #[test]
+    fn test_round_to() {
+        assert_eq!(round_to(0.1 + 0.2, 6), 0.3);
+        assert_eq!(round_to(1.23456, 2), 1.23);
+        assert_eq!(round_to(2.5, 0), 3.0);
+    }
+}
@@ -20,9 +20,80 @@ pub struct ValidateJobConfigParams {
 // Connection management command parameters and results
 #[derive(Debug, Clone, Deserialize)]
 pub struct ConnectParams {
+    /// Empty uses the active cluster profile's login server
+    #[serde(default)]
     pub host: String,
+    /// SSH port; defaults to the active profile's port when connecting to its login server, else 22
+    #[serde(default)]
+    pub port: Option<u16>,
     pub username: String,
+    /// Used by password authentication; may be empty for key authentication
+    #[serde(default)]
     pub password: SecurePassword,
+    /// Password (default) or public key
+    #[serde(default)]
+    pub auth_method: AuthMethod,
+    /// Network preset for timeouts/keepalive/retries (None = last used preset)
+    #[serde(default)]
+    pub network_preset: Option<crate::ssh::NetworkPreset>,
+    /// Bastion to connect through (ProxyJump); the same auth method is used on both hops
+    #[serde(default)]
+    pub jump_host: Option<crate::ssh::JumpHostConfig>,
+    /// Re-dial automatically if the session drops (password authentication only)
+    #[serde(default)]
+    pub auto_reconnect: bool,
+    /// Connect timeout in seconds (5-300), replacing the preset's; saved for later connects
+    #[serde(default)]
+    pub connect_timeout: Option<u64>,
+    /// Per-chunk SFTP timeout in seconds (30-7200), replacing the preset's; saved for later connects
+    #[serde(default)]
+    pub file_transfer_timeout: Option<u64>,
+    /// "Remember me": save the password in the OS keychain after a successful password login
+    #[serde(default)]
+    pub remember_password: bool,
+}
+
+/// How connect_to_cluster authenticates
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthMethod {
+    #[default]
+    Password,
+    /// Private key file (with a passphrase if encrypted); no key_path uses the SSH agent
+    Key {
+        #[serde(default)]
+        key_path: Option<String>,
+        #[serde(default)]
+        passphrase: Option<SecurePassword>,
+    },
+    /// Server-driven prompts (MFA/Duo); challenges arrive as events and are answered
+    /// with respond_to_auth_challenge
+    KeyboardInteractive,
+}
+
+impl ConnectParams {
+    /// Timeout overrides requested for this connect
+    pub fn timeout_overrides(&self) -> crate::ssh::TimeoutOverrides {
+        crate::ssh::TimeoutOverrides {
+            connect_timeout: self.connect_timeout,
+            file_transfer_timeout: self.file_transfer_timeout,
+        }
+    }
+
+    /// Credentials for the chosen auth method, borrowing the secrets held here
+    /// None for keyboard-interactive, which needs a live responder rather than stored secrets
+    pub fn credentials(&self) -> Option<crate::ssh::Credentials<'_>> {
+        use crate::ssh::Credentials;
+        match &self.auth_method {
+            AuthMethod::Password => Some(Credentials::Password(&self.password)),
+            AuthMethod::Key { key_path: Some(path), passphrase } => Some(Credentials::KeyFile {
+                private_key: std::path::Path::new(path),
+                passphrase: passphrase.as_ref(),
+            }),
+            AuthMethod::Key { key_path: None, .. } => Some(Credentials::Agent),
+            AuthMethod::KeyboardInteractive => None,
+        }
+    }
 }
 
 // Job management command parameters
@@ -32,14 +103,138 @@ pub struct CreateJobParams {
     pub template_id: String,
     pub template_values: std::collections::HashMap<String, serde_json::Value>,
     pub slurm_config: SlurmConfig,
+    /// job_ids of jobs that must complete successfully before this one starts
+    #[serde(default)]
+    pub depends_on: Option<Vec<String>>,
+    /// Run as a job array sweeping one template variable over these values
+    #[serde(default)]
+    pub array: Option<JobArray>,
+    /// Address SLURM emails on the events below
+    #[serde(default)]
+    pub notify_email: Option<String>,
+    /// Events to email about; END and FAIL when an address is given without any
+    #[serde(default)]
+    pub notify_events: Vec<MailEvent>,
+    /// Upload even when the disk space preflight finds too little room
+    #[serde(default)]
+    pub ignore_space_check: bool,
+}
+
+/// Job list sort key for paged loads
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobSortField {
+    #[default]
+    CreatedAt,
+    Status,
+    JobName,
+}
+
+impl JobSortField {
+    /// JSON path of the sort key inside a stored job record
+    pub fn json_path(&self) -> &'static str {
+        match self {
+            JobSortField::CreatedAt => "$.created_at",
+            JobSortField::Status => "$.status",
+            JobSortField::JobName => "$.job_name",
+        }
+    }
+
+    /// The same key read from a loaded job; compares the way SQLite's default collation does
+    pub fn key(&self, job: &JobInfo) -> String {
+        match self {
+            JobSortField::CreatedAt => job.created_at.clone(),
+            JobSortField::Status => serde_json::to_value(&job.status).ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            JobSortField::JobName => job.job_name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+/// File format for export_jobs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
 }
 
 // Complex batch operation results (domain-specific)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SyncJobsResult {
     pub success: bool,
     pub jobs: Vec<JobInfo>,        // Complete job list after sync
     pub jobs_updated: u32,          // Number of jobs updated during sync
     pub errors: Vec<String>,
+    pub warnings: Vec<String>,      // Problems that didn't stop the sync (e.g. two jobs claiming one SLURM ID)
+    pub already_in_progress: bool,  // Another sync was running; this call waited for it and returns its results
+    pub suggested_next_poll_secs: u64,  // Wait before the next sync: short near job transitions, backing off while nothing changes
+}
+
+/// Outcome of submit_jobs: every job lands in exactly one of the two lists
+#[derive(Debug, Default, Serialize)]
+pub struct BatchSubmitResult {
+    pub submitted: Vec<JobInfo>,
+    pub failed: Vec<BatchSubmitFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchSubmitFailure {
+    pub job_id: String,
+    pub error: String,
+}
+
+/// Progress of a multi-file download: emitted after each file of a filtered output download,
+/// and per percent and per file while a zip archive streams in
+#[derive(Debug, Clone, Serialize)]
+pub struct OutputDownloadProgress {
+    pub job_id: String,
+    /// File in progress or just finished (relative to outputs/, or the archive path for zips)
+    pub file: String,
+    /// Files finished so far
+    pub completed: usize,
+    pub total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+/// Emitted after each job in a batch submission, e.g. "3/12 submitted"
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchSubmitProgress {
+    pub job_id: String,
+    /// Jobs attempted so far, including this one
+    pub completed: usize,
+    pub total: usize,
+    pub submitted: usize,
+    pub failed: usize,
+    /// Why this job failed; None when it was submitted
+    pub error: Option<String>,
 }
 
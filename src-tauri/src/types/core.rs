@@ -68,6 +68,9 @@ pub enum JobStatus {
     Created,
     #[serde(rename = "PENDING")]
     Pending,
+    /// Queued but held (scontrol hold) - won't start until released
+    #[serde(rename = "HELD")]
+    Held,
     #[serde(rename = "RUNNING")]
     Running,
     #[serde(rename = "COMPLETED")]
@@ -108,6 +111,173 @@ pub struct JobInfo {
     pub slurm_config: SlurmConfig,
     pub input_files: Vec<String>,
     pub output_files: Vec<OutputFile>,
+
+    // Local organization - defaulted so older records and job_info.json files still load
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub pinned: bool,
+    /// Labels for grouping and filtering jobs (same characters as job IDs)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Free-form notes the user keeps on the job
+    #[serde(default)]
+    pub notes: Option<String>,
+
+    // Explanation shown next to the status, e.g. why a job is still pending
+    #[serde(default)]
+    pub status_detail: Option<String>,
+
+    // Exact sbatch script text submitted to SLURM, kept so it can be recovered locally
+    #[serde(default)]
+    pub submitted_script: Option<String>,
+
+    // Resolved template the job was created from; travels in job_info.json so the job can still be
+    // re-rendered, and its template restored by discovery, after the local template is gone
+    #[serde(default)]
+    pub template_snapshot: Option<crate::templates::Template>,
+    // NAMD config rendered at creation (task 0's for arrays), exactly as written to the job directory
+    #[serde(default)]
+    pub rendered_config: Option<String>,
+
+    // Internal job_ids that must finish successfully before this job starts (sbatch --dependency=afterok)
+    #[serde(default)]
+    pub depends_on: Option<Vec<String>>,
+
+    // Parameter sweep submitted as one SLURM job array (sbatch --array)
+    #[serde(default)]
+    pub array: Option<JobArray>,
+
+    // SLURM email notifications (--mail-user/--mail-type); address validated with security::input::sanitize_email
+    #[serde(default)]
+    pub notify_email: Option<String>,
+    #[serde(default)]
+    pub notify_events: Vec<MailEvent>,
+
+    // SUs actually used, from sacct's elapsed walltime at the billing rates; set once the job finishes
+    #[serde(default)]
+    pub actual_cost: Option<f64>,
+
+    // Live NAMD progress attached by get_job_status for running jobs - read from the log, never stored
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub namd_progress: Option<NamdProgress>,
+
+    // Scheduler's expected start time attached by get_job_status for pending jobs - never stored
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub estimated_start: Option<String>,
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub queue_priority: Option<u64>,
+}
+
+/// Job events SLURM can send email for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MailEvent {
+    Begin,
+    End,
+    Fail,
+    Requeue,
+    /// Walltime is 80% used - time to extend or checkpoint
+    #[serde(rename = "time_limit_80")]
+    TimeLimit80,
+}
+
+impl MailEvent {
+    /// Events used when an address is given without choosing any
+    pub const DEFAULT_EVENTS: [MailEvent; 2] = [MailEvent::End, MailEvent::Fail];
+
+    /// Name used in `#SBATCH --mail-type`
+    pub fn slurm_name(&self) -> &'static str {
+        match self {
+            MailEvent::Begin => "BEGIN",
+            MailEvent::End => "END",
+            MailEvent::Fail => "FAIL",
+            MailEvent::Requeue => "REQUEUE",
+            MailEvent::TimeLimit80 => "TIME_LIMIT_80",
+        }
+    }
+}
+
+/// Which kind of NAMD run a log is reporting on
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NamdPhase {
+    Minimization,
+    Dynamics,
+}
+
+/// How far the current NAMD phase has got, parsed from its stdout
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NamdProgress {
+    pub phase: NamdPhase,
+    /// Steps completed in this phase (not NAMD's absolute step number)
+    pub current_step: u64,
+    pub total_steps: Option<u64>,
+    pub percent_complete: Option<f64>,
+    /// Simulation speed; None while minimizing
+    pub ns_per_day: Option<f64>,
+    pub eta_seconds: Option<u64>,
+}
+
+/// Parameter sweep run as a SLURM job array: task N runs the template with `variable` set to `values[N]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobArray {
+    pub variable: String,
+    pub values: Vec<serde_json::Value>,
+    /// Per-task status from the last sync, ordered by task ID
+    #[serde(default)]
+    pub task_statuses: Vec<ArrayTaskStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArrayTaskStatus {
+    pub task_id: u32,
+    pub status: JobStatus,
+}
+
+impl JobArray {
+    /// Task IDs that ended Failed or Cancelled
+    pub fn failed_tasks(&self) -> Vec<u32> {
+        self.task_statuses.iter()
+            .filter(|task| matches!(task.status, JobStatus::Failed | JobStatus::Cancelled))
+            .map(|task| task.task_id)
+            .collect()
+    }
+
+    /// Progress line for the status detail, e.g. "3 running, 12 of 20 tasks finished; failed tasks: 4, 9"
+    /// None once every task completed successfully (or before any task status is known)
+    pub fn status_summary(&self) -> Option<String> {
+        if self.task_statuses.is_empty() {
+            return None;
+        }
+
+        let count = |wanted: JobStatus| self.task_statuses.iter().filter(|task| task.status == wanted).count();
+        let running = count(JobStatus::Running);
+        let pending = count(JobStatus::Pending);
+        let total = self.values.len().max(self.task_statuses.len());
+        let finished = self.task_statuses.len() - running - pending;
+        let failed = self.failed_tasks();
+
+        if failed.is_empty() && finished == total {
+            return None;
+        }
+
+        let mut summary = Vec::new();
+        if running > 0 {
+            summary.push(format!("{} running", running));
+        }
+        if pending > 0 {
+            summary.push(format!("{} pending", pending));
+        }
+        summary.push(format!("{} of {} tasks finished", finished, total));
+
+        let mut detail = summary.join(", ");
+        if !failed.is_empty() {
+            let ids: Vec<String> = failed.iter().map(|id| id.to_string()).collect();
+            detail.push_str(&format!("; failed tasks: {}", ids.join(", ")));
+        }
+        Some(detail)
+    }
 }
 
 // JobInfo has no custom constructor - construct directly using struct literal syntax
@@ -121,9 +291,36 @@ pub struct SlurmConfig {
     pub walltime: String,
     pub partition: String,
     pub qos: String,
+    /// GPUs per node (--gres=gpu:N); defaulted so jobs saved before GPU support still load
+    #[serde(default)]
+    pub gpus: Option<u32>,
+    /// Generic resource type name on the cluster, e.g. "a100" for --gres=gpu:a100:N
+    #[serde(default)]
+    pub gpu_type: Option<String>,
 }
 
 impl SlurmConfig {
+    /// Number of GPUs requested (0 for CPU-only jobs)
+    pub fn gpu_count(&self) -> u32 {
+        self.gpus.unwrap_or(0)
+    }
+
+    /// GPU generic resource request ("gpu:2" or "gpu:a100:2"), None for CPU-only jobs
+    pub fn gpu_gres(&self) -> anyhow::Result<Option<String>> {
+        let count = self.gpu_count();
+        if count == 0 {
+            return Ok(None);
+        }
+
+        match self.gpu_type.as_deref().map(str::trim).filter(|gpu_type| !gpu_type.is_empty()) {
+            None => Ok(Some(format!("gpu:{}", count))),
+            Some(gpu_type) if gpu_type.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')) => {
+                Ok(Some(format!("gpu:{}:{}", gpu_type, count)))
+            }
+            Some(gpu_type) => Err(anyhow::anyhow!("Invalid GPU type '{}': use the cluster's GRES name, e.g. 'a100'", gpu_type)),
+        }
+    }
+
     /// Parse memory string to GB (e.g., "16GB", "32", "2048MB")
     pub fn parse_memory_gb(&self) -> anyhow::Result<f64> {
         let clean = self.memory.trim().to_lowercase();
@@ -192,6 +389,8 @@ impl Default for SlurmConfig {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         }
     }
 }
@@ -203,6 +402,16 @@ pub struct SessionInfo {
     pub connected_at: String,
 }
 
+/// A login target that connected successfully, offered again on the connect form
+/// Deliberately holds no credentials
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentConnection {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub last_connected_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileUpload {
     pub local_path: String,
@@ -229,6 +438,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 16.0);
 
@@ -238,6 +449,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 16.0);
 
@@ -248,6 +461,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 32.0);
 
@@ -258,6 +473,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 2.0);
 
@@ -267,6 +484,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 0.5);
     }
@@ -279,6 +498,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 1.5);
 
@@ -288,6 +509,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 0.5);
     }
@@ -300,6 +523,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 16.0);
 
@@ -309,10 +534,40 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 16.0);
     }
 
+    #[test]
+    fn test_gpu_gres() {
+        let mut config = SlurmConfig::default();
+        assert_eq!(config.gpu_gres().unwrap(), None);
+
+        config.gpus = Some(0);
+        assert_eq!(config.gpu_gres().unwrap(), None);
+
+        config.gpus = Some(2);
+        assert_eq!(config.gpu_gres().unwrap().as_deref(), Some("gpu:2"));
+
+        config.gpu_type = Some("a100".to_string());
+        assert_eq!(config.gpu_gres().unwrap().as_deref(), Some("gpu:a100:2"));
+
+        config.gpu_type = Some("a100; rm -rf ~".to_string());
+        assert!(config.gpu_gres().is_err());
+    }
+
+    #[test]
+    fn test_slurm_config_without_gpu_fields_deserializes() {
+        // Jobs saved before GPU support have no gpus/gpu_type
+        let json = r#"{"cores":24,"memory":"32GB","walltime":"04:00:00","partition":"amilan","qos":"normal"}"#;
+        let config: SlurmConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.gpus, None);
+        assert_eq!(config.gpu_type, None);
+        assert_eq!(config.gpu_count(), 0);
+    }
+
     #[test]
     fn test_parse_memory_gb_case_insensitive() {
         let config = SlurmConfig {
@@ -321,6 +576,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 16.0);
 
@@ -330,6 +587,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_memory_gb().unwrap(), 2.0);
     }
@@ -343,6 +602,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert!(config.parse_memory_gb().is_err());
 
@@ -353,6 +614,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert!(config.parse_memory_gb().is_err());
 
@@ -363,6 +626,8 @@ mod tests {
             walltime: "01:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert!(config.parse_memory_gb().is_err());
     }
@@ -376,6 +641,8 @@ mod tests {
             walltime: "24:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_walltime_hours().unwrap(), 24.0);
 
@@ -386,6 +653,8 @@ mod tests {
             walltime: "04:30:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_walltime_hours().unwrap(), 4.5);
 
@@ -396,6 +665,8 @@ mod tests {
             walltime: "01:30:30".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         // 1 hour + 30 minutes (0.5) + 30 seconds (0.00833...)
         let result = config.parse_walltime_hours().unwrap();
@@ -411,6 +682,8 @@ mod tests {
             walltime: "00:00:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert_eq!(config.parse_walltime_hours().unwrap(), 0.0);
 
@@ -421,6 +694,8 @@ mod tests {
             walltime: "99:59:59".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         let result = config.parse_walltime_hours().unwrap();
         assert!(result > 99.9 && result < 100.0);
@@ -435,6 +710,8 @@ mod tests {
             walltime: "".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert!(config.parse_walltime_hours().is_err());
 
@@ -445,6 +722,8 @@ mod tests {
             walltime: "24".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert!(config.parse_walltime_hours().is_err());
 
@@ -455,6 +734,8 @@ mod tests {
             walltime: "24:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert!(config.parse_walltime_hours().is_err());
 
@@ -465,6 +746,8 @@ mod tests {
             walltime: "01:60:00".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert!(config.parse_walltime_hours().is_err());
 
@@ -475,6 +758,8 @@ mod tests {
             walltime: "01:00:60".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert!(config.parse_walltime_hours().is_err());
 
@@ -485,6 +770,8 @@ mod tests {
             walltime: "aa:bb:cc".to_string(),
             partition: "amilan".to_string(),
             qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
         };
         assert!(config.parse_walltime_hours().is_err());
     }
@@ -515,12 +802,30 @@ mod tests {
                 walltime: "24:00:00".to_string(),
                 partition: "amilan".to_string(),
                 qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
             },
             input_files: vec![
                 "structure.pdb".to_string(),
                 "structure.psf".to_string(),
             ],
             output_files: vec![],
+            archived: false,
+            pinned: false,
+            tags: Vec::new(),
+            notes: None,
+            status_detail: None,
+            submitted_script: None,
+            template_snapshot: None,
+            rendered_config: None,
+            depends_on: None,
+            array: None,
+            notify_email: None,
+            notify_events: Vec::new(),
+            actual_cost: None,
+            namd_progress: None,
+            estimated_start: None,
+            queue_priority: None,
         };
 
         // Should serialize successfully
@@ -530,4 +835,34 @@ mod tests {
         assert!(json.contains("input_files"));
         assert!(json.contains("structure.pdb"));
     }
+
+    fn sweep(statuses: &[JobStatus]) -> JobArray {
+        JobArray {
+            variable: "temperature".to_string(),
+            values: (0..statuses.len()).map(|i| serde_json::json!(300 + i * 10)).collect(),
+            task_statuses: statuses.iter().enumerate()
+                .map(|(i, status)| ArrayTaskStatus { task_id: i as u32, status: status.clone() })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_array_summary_names_failed_tasks() {
+        let array = sweep(&[JobStatus::Completed, JobStatus::Failed, JobStatus::Running, JobStatus::Pending, JobStatus::Cancelled]);
+
+        assert_eq!(array.failed_tasks(), vec![1, 4]);
+        assert_eq!(
+            array.status_summary().unwrap(),
+            "1 running, 1 pending, 3 of 5 tasks finished; failed tasks: 1, 4"
+        );
+    }
+
+    #[test]
+    fn test_array_summary_when_all_tasks_succeed() {
+        assert!(sweep(&[JobStatus::Completed, JobStatus::Completed]).status_summary().is_none());
+        assert!(sweep(&[]).status_summary().is_none());
+
+        let finished_with_failure = sweep(&[JobStatus::Completed, JobStatus::Failed]);
+        assert_eq!(finished_with_failure.status_summary().unwrap(), "2 of 2 tasks finished; failed tasks: 1");
+    }
 }
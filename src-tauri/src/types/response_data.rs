@@ -11,12 +11,74 @@ pub struct DownloadInfo {
     pub file_size: u64,
 }
 
+/// Filtered output download response data
+/// Every output file lands in `matched` or `skipped` (paths relative to outputs/)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilteredDownload {
+    /// Local directory the matched files were written into
+    pub saved_to: String,
+    /// Patterns as applied, with extension shorthand expanded
+    pub patterns: Vec<String>,
+    pub matched: Vec<FileUsage>,
+    pub skipped: Vec<FileUsage>,
+    pub downloaded_bytes: u64,
+}
+
+/// Remote text file response data
+/// The start of a job file for in-app viewing, e.g. "showing first 1 MB of 40 MB"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteText {
+    pub relative_path: String,
+    /// UTF-8 (invalid sequences replaced), never ending in a split character
+    pub content: String,
+    /// Bytes of the file that `content` covers
+    pub shown_bytes: u64,
+    pub file_size: u64,
+    pub truncated: bool,
+}
+
+/// One page of the job list, with the number of jobs across all pages
+#[derive(Debug, Clone, Serialize)]
+pub struct JobPage {
+    pub jobs: Vec<JobInfo>,
+    pub total: u32,
+}
+
+/// Job list row - the fields the job list shows, without cached logs or template values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobSummary {
+    pub job_id: String,
+    pub job_name: String,
+    pub status: JobStatus,
+    pub slurm_job_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+    pub submitted_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Template usage response data
+/// How many jobs use a template (total and per status) and when it was last used
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateUsage {
+    pub template_id: String,
+    pub template_name: String,
+    pub total_jobs: u32,
+    /// Job count per status (e.g. "COMPLETED": 12); statuses with no jobs are omitted
+    pub jobs_by_status: std::collections::BTreeMap<String, u32>,
+    /// created_at of the newest job using the template (None if never used)
+    pub last_used: Option<String>,
+}
+
 /// Database information response data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseInfo {
     pub path: String,
     pub size_bytes: u64,
     pub job_count: usize,
+    /// Job rows that couldn't be parsed (corrupt or from an incompatible version)
+    pub skipped_job_records: usize,
 }
 
 /// Database operation response data
@@ -33,12 +95,34 @@ pub struct ConnectionStatus {
     pub session_info: Option<SessionInfo>,
 }
 
+/// Connection ping response data
+/// Round-trip time of a trivial command on the live session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionPing {
+    /// Whether the session is still authenticated after the probe
+    pub authenticated: bool,
+    /// None when the probe failed
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
 /// Job discovery response data
 /// Used when scanning cluster for existing jobs
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscoveryReport {
     pub imported_jobs: Vec<JobInfo>,
     pub failed_imports: Vec<FailedImport>,
+    /// Template IDs re-created from imported jobs' snapshots
+    pub restored_templates: Vec<String>,
+    /// Imported jobs whose template is gone and that carry no snapshot to restore it from
+    pub missing_templates: Vec<MissingTemplate>,
+}
+
+/// An imported job that can't be re-rendered because its template is missing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissingTemplate {
+    pub job_id: String,
+    pub template_id: String,
 }
 
 /// Information about a failed import during discovery
@@ -48,6 +132,213 @@ pub struct FailedImport {
     pub reason: String,
 }
 
+/// Submission preview response data
+/// The NAMD config and SLURM script a job will submit, cross-checked against each other
+#[derive(Debug, Clone, Serialize)]
+pub struct SubmissionArtifacts {
+    pub namd_config: String,
+    pub slurm_script: String,
+    pub validation: crate::validation::job::ValidationResult,
+}
+
+/// Dry run response data
+/// What `sbatch --test-only` made of a job's script; nothing is queued or saved
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunResult {
+    /// The scheduler would accept the job as submitted
+    pub accepted: bool,
+    /// Scheduler's estimate, e.g. "2025-01-15T10:30:00"
+    pub estimated_start: Option<String>,
+    /// sbatch error messages, without the "sbatch: error:" prefix
+    pub errors: Vec<String>,
+    /// Raw sbatch output
+    pub output: String,
+    /// The script that was checked
+    pub script: String,
+}
+
+/// One partition as reported by sinfo, merged over its node types
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiscoveredPartition {
+    pub name: String,
+    /// Marked with '*' - SLURM's default partition
+    pub is_default: bool,
+    /// None for an unlimited time limit
+    pub max_walltime_hours: Option<u32>,
+    pub cores_per_node: u32,
+    pub memory_per_node_mb: u64,
+    pub gpu_type: Option<String>,
+    pub gpu_count: Option<u32>,
+}
+
+/// One QoS as reported by sacctmgr
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiscoveredQos {
+    pub name: String,
+    /// None when the QoS sets no MaxWall
+    pub max_walltime_hours: Option<u32>,
+}
+
+/// What discover_cluster_capabilities found and the capabilities after merging it in
+#[derive(Debug, Clone, Serialize)]
+pub struct CapabilityDiscovery {
+    pub partitions: Vec<DiscoveredPartition>,
+    pub qos_options: Vec<DiscoveredQos>,
+    /// Why QoS limits couldn't be read (sacctmgr is restricted on some clusters); partitions still merge
+    pub qos_error: Option<String>,
+    /// Configured partitions sinfo didn't list; kept, but may no longer exist
+    pub missing_partitions: Vec<String>,
+    pub merged: crate::cluster::ClusterCapabilities,
+    /// Whether `merged` was saved to the profile
+    pub applied: bool,
+}
+
+/// Job accounting response data
+/// SLURM's post-mortem record from sacct, aggregated over the job's steps
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JobAccounting {
+    pub slurm_job_id: String,
+    /// SLURM state of the job allocation, e.g. "COMPLETED", "OUT_OF_MEMORY"
+    pub state: String,
+    /// "exit code:signal", e.g. "0:0"
+    pub exit_code: String,
+    pub elapsed_seconds: Option<u64>,
+    /// Allocated core time (elapsed x allocated CPUs)
+    pub cpu_time_seconds: Option<u64>,
+    /// CPU time the job actually used
+    pub total_cpu_seconds: Option<f64>,
+    /// total_cpu_seconds / cpu_time_seconds, 0.0-1.0
+    pub cpu_efficiency: Option<f64>,
+    /// Peak resident memory of the largest step
+    pub max_rss_bytes: Option<u64>,
+    pub max_vmsize_bytes: Option<u64>,
+    /// Memory requested for the whole job (per-CPU requests are multiplied out)
+    pub requested_memory_bytes: Option<u64>,
+    /// max_rss_bytes / requested_memory_bytes, 0.0-1.0
+    pub memory_efficiency: Option<f64>,
+}
+
+/// Submission readiness response data
+/// One checklist item per prerequisite; ready only when every item passes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionReadiness {
+    pub job_id: String,
+    pub ready: bool,
+    pub items: Vec<ReadinessItem>,
+}
+
+/// A single submission prerequisite and whether it is satisfied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessItem {
+    pub id: String,
+    pub label: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Template compatibility response data
+/// Whether one job's saved template values still validate against the current template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobCompatibility {
+    pub job_id: String,
+    pub job_name: String,
+    pub compatible: bool,
+    /// Variables the template now requires that the job has no value for
+    pub missing_variables: Vec<String>,
+    /// Job values for variables the template no longer defines (would be ignored)
+    pub removed_variables: Vec<String>,
+    /// Validation failures for the job's values (wrong type, out of range, bad extension)
+    pub issues: Vec<String>,
+}
+
+/// Scratch usage response data
+/// Total size of a job's scratch directory and its largest files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScratchUsage {
+    pub scratch_dir: String,
+    pub total_bytes: u64,
+    pub largest_files: Vec<FileUsage>,
+}
+
+/// Job deletion preview response data
+/// What `delete_job` would remove: without `delete_remote` only the database record goes
+/// (after cancelling an active job); the `remote_*` fields are what `delete_remote: true` removes on top
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletionPreview {
+    pub job_id: String,
+    pub job_name: String,
+    /// SLURM job that either kind of deletion cancels first (job still pending, held or running)
+    pub cancels_slurm_job: Option<String>,
+    pub remote_directories: Vec<DirectoryDeletionPreview>,
+    pub remote_file_count: u64,
+    pub remote_bytes: u64,
+    /// Why the remote directories couldn't be listed (e.g. not connected)
+    pub remote_error: Option<String>,
+}
+
+/// One job directory that `delete_remote: true` removes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectoryDeletionPreview {
+    /// "project" or "scratch"
+    pub kind: String,
+    pub path: String,
+    /// False when the directory is already gone (e.g. purged scratch)
+    pub exists: bool,
+    pub file_count: u64,
+    pub total_bytes: u64,
+    /// Largest files first, paths relative to the directory (capped; counts cover everything)
+    pub files: Vec<FileUsage>,
+    /// Set when deletion would refuse this path; it isn't counted in the totals
+    pub refused: Option<String>,
+}
+
+/// Inode check response data
+/// Whether the filesystem holding a remote directory has room for a planned number of files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InodeCheck {
+    pub remote_dir: String,
+    pub planned_files: u64,
+    /// None when the filesystem doesn't report inode counts (check skipped)
+    pub free_inodes: Option<u64>,
+    pub sufficient: bool,
+    pub message: String,
+}
+
+/// Disk space check response data
+/// Whether the filesystem (and quota, where reported) holding a remote path can take a planned write
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiskSpaceCheck {
+    pub path: String,
+    pub required_bytes: u64,
+    /// None when `df` could not report the filesystem (check skipped)
+    pub total_bytes: Option<u64>,
+    pub available_bytes: Option<u64>,
+    /// Bytes left under the user's Lustre quota; None off Lustre or without a limit
+    pub quota_remaining_bytes: Option<u64>,
+    pub sufficient: bool,
+    /// How many more bytes the write needs than the tighter of free space and quota
+    pub shortfall_bytes: u64,
+    pub message: String,
+}
+
+/// Total size and file count of a remote directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteDirSize {
+    pub path: String,
+    pub total_bytes: u64,
+    pub file_count: u64,
+    /// True when some subdirectories could not be read, so the figures undercount
+    pub partial: bool,
+    pub warning: Option<String>,
+}
+
+/// Size of a single remote file (path relative to the directory measured)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileUsage {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
 /// App initialization response data
 /// Contains all data needed to initialize the frontend on startup
 #[derive(Debug, Clone, Serialize, Deserialize)]
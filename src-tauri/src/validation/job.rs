@@ -1,5 +1,5 @@
 use anyhow::anyhow;
-use crate::cluster::{get_partition_by_name, get_qos_for_partition};
+use crate::cluster::{get_partition_by_name, get_qos_for_partition, qos_for_walltime};
 use crate::types::commands::ValidateJobConfigParams;
 use crate::security::input;
 use crate::commands::helpers;
@@ -128,15 +128,61 @@ pub fn validate_resource_allocation(
         field_errors.insert("cores".to_string(), error);
     }
 
-    // Validate memory against partition limit
-    let max_memory = config.cores as f64 * partition.max_memory_per_core_gb;
-    if memory_gb > max_memory {
+    // GPUs can only be requested where the partition's nodes have them
+    let gpus = config.gpu_count();
+    if gpus > 0 {
+        match partition.gpu_count.unwrap_or(0) {
+            0 => {
+                let error = format!("Partition '{}' is CPU-only - it has no GPUs to request", partition_id);
+                issues.push(error.clone());
+                field_errors.insert("gpus".to_string(), error);
+                suggestions.push("Choose a GPU partition, or request 0 GPUs".to_string());
+            }
+            max_gpus if gpus > max_gpus => {
+                let error = format!(
+                    "GPUs ({}) exceeds partition '{}' limit ({} per node)",
+                    gpus, partition_id, max_gpus
+                );
+                issues.push(error.clone());
+                field_errors.insert("gpus".to_string(), error);
+            }
+            _ => {}
+        }
+
+        if let Err(e) = config.gpu_gres() {
+            let error = e.to_string();
+            issues.push(error.clone());
+            field_errors.insert("gpu_type".to_string(), error);
+        }
+    }
+
+    // Validate memory against partition limits
+    // Per-node limit first: no core count can make a request larger than one node schedulable
+    let max_node_memory = partition.max_memory_per_node_gb();
+    if memory_gb > max_node_memory {
         let error = format!(
-            "Memory ({:.1}GB) exceeds limit for {} cores on partition '{}' ({:.1}GB)",
-            memory_gb, config.cores, partition_id, max_memory
+            "Memory ({:.1}GB) exceeds the per-node limit on partition '{}' ({:.1}GB = {} cores x {:.2}GB per core)",
+            memory_gb, partition_id, max_node_memory, partition.max_cores, partition.max_memory_per_core_gb
         );
         issues.push(error.clone());
         field_errors.insert("memory".to_string(), error);
+    } else if config.cores > 0 {
+        let memory_per_core = memory_gb / config.cores as f64;
+        if memory_per_core > partition.max_memory_per_core_gb {
+            let max_memory = config.cores as f64 * partition.max_memory_per_core_gb;
+            let error = format!(
+                "Memory ({:.1}GB) exceeds limit for {} cores on partition '{}' ({:.1}GB): {:.2}GB per core requested, partition allows {:.2}GB per core",
+                memory_gb, config.cores, partition_id, max_memory, memory_per_core, partition.max_memory_per_core_gb
+            );
+            issues.push(error.clone());
+            field_errors.insert("memory".to_string(), error);
+
+            let cores_needed = (memory_gb / partition.max_memory_per_core_gb).ceil() as u32;
+            suggestions.push(format!(
+                "Request at least {} cores to use {:.1}GB on partition '{}'",
+                cores_needed, memory_gb, partition_id
+            ));
+        }
     }
 
     // Validate QOS
@@ -145,11 +191,25 @@ pub fn validate_resource_allocation(
         // Validate walltime against QOS limit
         if walltime_hours > qos.max_walltime_hours as f64 {
             let error = format!(
-                "Walltime ({:.1}h) exceeds QOS '{}' limit ({}h)",
+                "Walltime ({:.1}h) exceeds QOS '{}' limit ({}h) - SLURM will reject this job",
                 walltime_hours, qos_id, qos.max_walltime_hours
             );
             issues.push(error.clone());
             field_errors.insert("walltime".to_string(), error);
+
+            match qos_for_walltime(&valid_qos, walltime_hours, memory_gb) {
+                Some(better) => suggestions.push(format!(
+                    "Use QOS '{}' (up to {}h) for a {:.1}h walltime",
+                    better.name, better.max_walltime_hours, walltime_hours
+                )),
+                None => {
+                    let longest = valid_qos.iter().map(|q| q.max_walltime_hours).max().unwrap_or(qos.max_walltime_hours);
+                    suggestions.push(format!(
+                        "No QOS on partition '{}' allows {:.1}h - reduce walltime to {}h or less",
+                        partition_id, walltime_hours, longest
+                    ));
+                }
+            }
         }
 
         // QOS-specific memory validation (use min_memory_gb field)
@@ -178,10 +238,6 @@ pub fn validate_resource_allocation(
         warnings.push("Consider 'amilan' partition for jobs under 64 cores".to_string());
     }
 
-    if walltime_hours > 48.0 && qos_id == "normal" {
-        suggestions.push("Consider 'long' QOS for runs over 48 hours".to_string());
-    }
-
     // Memory optimization suggestions
     let recommended_memory = config.cores as f64 * 2.0; // 2GB per core is often efficient
     if memory_gb > recommended_memory * 2.0 {
@@ -229,7 +285,7 @@ pub async fn validate_job_config(params: ValidateJobConfigParams) -> ValidationR
     // Validate template values (if template selected)
     if !params.template_id.is_empty() {
         // Load template and validate values
-        match helpers::load_template_or_fail(&params.template_id, "Validation") {
+        match helpers::load_resolved_template_or_fail(&params.template_id, "Validation") {
             Ok(template) => {
                 // Call template validation module directly (not command wrapper)
                 let template_validation = crate::validation::template::validate_values(&template, &params.template_values);
@@ -241,6 +297,18 @@ pub async fn validate_job_config(params: ValidateJobConfigParams) -> ValidationR
                 if let Some(template_field_errors) = template_validation.field_errors {
                     field_errors.extend(template_field_errors);
                 }
+
+                // Physics checks need the rendered config, so only once the values themselves are valid
+                if template_validation.is_valid {
+                    if let Ok(namd_config) = crate::templates::render_template(&template, &params.template_values) {
+                        let namd_validation = crate::validation::namd::validate_namd_config(&namd_config);
+                        issues.extend(namd_validation.issues);
+                        warnings.extend(namd_validation.warnings);
+                        if let Some(namd_field_errors) = namd_validation.field_errors {
+                            field_errors.extend(namd_field_errors);
+                        }
+                    }
+                }
             }
             Err(e) => {
                 let error = format!("{}", e);
@@ -257,6 +325,8 @@ pub async fn validate_job_config(params: ValidateJobConfigParams) -> ValidationR
         walltime: params.walltime.clone(),
         partition: params.partition.clone(),
         qos: params.qos.clone(),
+        gpus: None,
+        gpu_type: None,
     };
 
     let resource_validation = validate_resource_allocation(&slurm_config, &params.partition, &params.qos);
@@ -278,6 +348,124 @@ pub async fn validate_job_config(params: ValidateJobConfigParams) -> ValidationR
     }
 }
 
+/// Cross-check a rendered NAMD config against the SLURM script that will run it
+/// Catches mismatches neither preview shows on its own: the script invoking a different
+/// config filename than the one uploaded, outputs written outside outputs/, and input
+/// files the config expects but the job never uploaded
+pub fn validate_submission_artifacts(
+    namd_config: &str,
+    slurm_script: &str,
+    input_files: &[String],
+) -> ValidationResult {
+    use crate::ssh::JobDirectoryStructure;
+
+    let mut issues = Vec::new();
+    let mut warnings = Vec::new();
+    let mut field_errors = std::collections::HashMap::new();
+
+    // The script must run the config file that job creation uploads
+    match namd_config_argument(slurm_script) {
+        Some(config_arg) if config_arg == JobDirectoryStructure::NAMD_CONFIG => {}
+        Some(config_arg) => {
+            let error = format!(
+                "SLURM script runs NAMD with '{}' but the rendered config is uploaded as '{}'",
+                config_arg, JobDirectoryStructure::NAMD_CONFIG
+            );
+            issues.push(error.clone());
+            field_errors.insert("slurm_script".to_string(), error);
+        }
+        None => {
+            let error = "SLURM script does not invoke namd3".to_string();
+            issues.push(error.clone());
+            field_errors.insert("slurm_script".to_string(), error);
+        }
+    }
+
+    // Job completion only collects files from outputs/
+    let outputs_prefix = format!("{}/", JobDirectoryStructure::OUTPUTS);
+    match namd_config_value(namd_config, "outputName") {
+        Some(output_name) if !output_name.starts_with(&outputs_prefix) => {
+            warnings.push(format!(
+                "outputName '{}' is outside '{}' - results will not appear in the job's output files",
+                output_name, outputs_prefix
+            ));
+        }
+        Some(_) => {}
+        None => warnings.push("NAMD config does not set outputName".to_string()),
+    }
+
+    // Every input_files/ reference must have been uploaded with the job
+    let inputs_prefix = format!("{}/", JobDirectoryStructure::INPUT_FILES);
+    let mut missing_inputs: Vec<&str> = Vec::new();
+    for line in namd_config.lines().map(strip_namd_comment) {
+        for filename in line.split_whitespace().filter_map(|token| token.strip_prefix(&inputs_prefix)) {
+            if !input_files.iter().any(|f| f == filename) && !missing_inputs.contains(&filename) {
+                missing_inputs.push(filename);
+            }
+        }
+    }
+    for filename in missing_inputs {
+        let error = format!("NAMD config references '{}{}' which was not uploaded with the job", inputs_prefix, filename);
+        issues.push(error.clone());
+        field_errors.insert("namd_config".to_string(), error);
+    }
+
+    ValidationResult {
+        is_valid: issues.is_empty(),
+        issues,
+        warnings,
+        suggestions: vec![],
+        field_errors: if field_errors.is_empty() { None } else { Some(field_errors) },
+    }
+}
+
+/// Longest job notes accepted, in characters
+pub const MAX_JOB_NOTES_LENGTH: usize = 4000;
+
+/// Validate notes for a job; blank notes clear them
+pub fn validate_job_notes(notes: Option<String>) -> anyhow::Result<Option<String>> {
+    let Some(notes) = notes.filter(|notes| !notes.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    let length = notes.chars().count();
+    if length > MAX_JOB_NOTES_LENGTH {
+        return Err(anyhow!("Notes are too long ({} characters, max {})", length, MAX_JOB_NOTES_LENGTH));
+    }
+    if notes.contains('\0') {
+        return Err(anyhow!("Notes contain null bytes"));
+    }
+
+    Ok(Some(notes))
+}
+
+/// Find the config file argument passed to namd3 in a SLURM script
+fn namd_config_argument(slurm_script: &str) -> Option<&str> {
+    slurm_script.lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .find_map(|line| {
+            let mut tokens = line.split_whitespace();
+            tokens.by_ref().find(|token| token.ends_with("namd3"))?;
+            tokens.next()
+        })
+}
+
+/// Look up a NAMD keyword's value (keywords are case-insensitive)
+fn namd_config_value<'a>(namd_config: &'a str, keyword: &str) -> Option<&'a str> {
+    namd_config.lines()
+        .map(strip_namd_comment)
+        .find_map(|line| {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some(key) if key.eq_ignore_ascii_case(keyword) => tokens.next(),
+                _ => None,
+            }
+        })
+}
+
+fn strip_namd_comment(line: &str) -> &str {
+    line.split('#').next().unwrap_or("")
+}
 
 #[cfg(test)]
 mod tests {
@@ -378,6 +566,215 @@ mod tests {
         assert!(field_errors.contains_key("cores"));
     }
 
+    fn slurm_config(cores: u32, memory: &str, partition: &str) -> crate::types::SlurmConfig {
+        crate::types::SlurmConfig {
+            cores,
+            memory: memory.to_string(),
+            walltime: "04:00:00".to_string(),
+            partition: partition.to_string(),
+            qos: "normal".to_string(),
+            gpus: None,
+            gpu_type: None,
+        }
+    }
+
+    #[test]
+    fn test_walltime_within_qos_cap() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let mut config = slurm_config(24, "48GB", "amilan");
+        config.walltime = "24:00:00".to_string();
+        let result = validate_resource_allocation(&config, "amilan", "normal");
+
+        assert!(result.is_valid, "unexpected issues: {:?}", result.issues);
+    }
+
+    #[test]
+    fn test_gpu_request_on_cpu_partition_rejected() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let mut config = slurm_config(24, "48GB", "amilan");
+        config.gpus = Some(1);
+        let result = validate_resource_allocation(&config, "amilan", "normal");
+
+        assert!(!result.is_valid);
+        assert!(result.field_errors.as_ref().unwrap()["gpus"].contains("CPU-only"));
+    }
+
+    #[test]
+    fn test_gpu_request_on_gpu_partition() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let mut config = slurm_config(32, "64GB", "aa100");
+        config.gpus = Some(2);
+        config.gpu_type = Some("a100".to_string());
+        let result = validate_resource_allocation(&config, "aa100", "normal");
+        assert!(result.is_valid, "unexpected issues: {:?}", result.issues);
+
+        // aa100 nodes have 3 GPUs
+        config.gpus = Some(4);
+        let result = validate_resource_allocation(&config, "aa100", "normal");
+        assert!(result.field_errors.as_ref().unwrap()["gpus"].contains("limit (3 per node)"));
+    }
+
+    #[test]
+    fn test_walltime_over_qos_cap_suggests_qos() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        // normal caps at 24h on amilan; long allows 168h
+        let mut config = slurm_config(24, "48GB", "amilan");
+        config.walltime = "48:00:00".to_string();
+        let result = validate_resource_allocation(&config, "amilan", "normal");
+
+        assert!(!result.is_valid);
+        let walltime_error = &result.field_errors.as_ref().unwrap()["walltime"];
+        assert!(walltime_error.contains("exceeds QOS 'normal' limit (24h)"));
+        assert!(result.suggestions.iter().any(|s| s.contains("Use QOS 'long' (up to 168h)")));
+    }
+
+    #[test]
+    fn test_walltime_over_every_qos_cap() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let mut config = slurm_config(24, "48GB", "amilan");
+        config.walltime = "200:00:00".to_string();
+        let result = validate_resource_allocation(&config, "amilan", "long");
+
+        assert!(!result.is_valid);
+        assert!(result.suggestions.iter().any(|s| s.contains("reduce walltime to 168h or less")));
+    }
+
+    #[test]
+    fn test_memory_per_core_within_partition_limit() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        // amilan allows 3.75GB per core: 16 cores x 2GB is well within limits
+        let config = slurm_config(16, "32GB", "amilan");
+        let result = validate_resource_allocation(&config, "amilan", "normal");
+
+        assert!(result.is_valid, "unexpected issues: {:?}", result.issues);
+        assert!(result.field_errors.is_none());
+    }
+
+    #[test]
+    fn test_memory_per_core_exceeds_partition_limit() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        // 4 cores x 8GB per core on a 3.75GB/core partition
+        let config = slurm_config(4, "32GB", "amilan");
+        let result = validate_resource_allocation(&config, "amilan", "normal");
+
+        assert!(!result.is_valid);
+        let memory_error = &result.field_errors.as_ref().unwrap()["memory"];
+        assert!(memory_error.contains("8.00GB per core requested"));
+        assert!(memory_error.contains("3.75GB per core"));
+        assert!(result.suggestions.iter().any(|s| s.contains("at least 9 cores")));
+    }
+
+    #[test]
+    fn test_memory_exceeds_partition_node_limit() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        // amilan nodes top out at 64 x 3.75 = 240GB, no core count can fit 500GB
+        let config = slurm_config(4, "500GB", "amilan");
+        let result = validate_resource_allocation(&config, "amilan", "normal");
+
+        assert!(!result.is_valid);
+        let memory_error = &result.field_errors.as_ref().unwrap()["memory"];
+        assert!(memory_error.contains("per-node limit"));
+        assert!(memory_error.contains("240.0GB"));
+        assert!(!result.suggestions.iter().any(|s| s.contains("Request at least")));
+    }
+
+    #[test]
+    fn test_memory_per_core_high_memory_partition() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        // The same 8GB per core that fails on amilan is fine on amem (21.5GB per core)
+        let config = slurm_config(32, "256GB", "amem");
+        let result = validate_resource_allocation(&config, "amem", "mem");
+
+        assert!(result.is_valid, "unexpected issues: {:?}", result.issues);
+    }
+
+    const TEST_NAMD_CONFIG: &str = "# Generated by NAMDRunner\n\
+        structure          input_files/system.psf\n\
+        coordinates        input_files/system.pdb\n\
+        outputName         outputs/production\n\
+        parameters         input_files/par_all36.prm  ;# CHARMM\n";
+
+    fn test_script(config_file: &str) -> String {
+        format!(
+            "#!/bin/bash\n#SBATCH --job-name=test\ncd /scratch/alpine/user/job\nmpirun -np $SLURM_NTASKS namd3 {} > namd_output.log",
+            config_file
+        )
+    }
+
+    fn uploaded(files: &[&str]) -> Vec<String> {
+        files.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn test_submission_artifacts_consistent() {
+        let result = validate_submission_artifacts(
+            TEST_NAMD_CONFIG,
+            &test_script("config.namd"),
+            &uploaded(&["system.psf", "system.pdb", "par_all36.prm"]),
+        );
+
+        assert!(result.is_valid, "unexpected issues: {:?}", result.issues);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_submission_artifacts_mismatched_config_filename() {
+        let result = validate_submission_artifacts(
+            TEST_NAMD_CONFIG,
+            &test_script("production.namd"),
+            &uploaded(&["system.psf", "system.pdb", "par_all36.prm"]),
+        );
+
+        assert!(!result.is_valid);
+        assert!(result.issues[0].contains("'production.namd'"));
+        assert!(result.issues[0].contains("'config.namd'"));
+        assert!(result.field_errors.as_ref().unwrap().contains_key("slurm_script"));
+    }
+
+    #[test]
+    fn test_submission_artifacts_missing_input_file() {
+        let result = validate_submission_artifacts(
+            TEST_NAMD_CONFIG,
+            &test_script("config.namd"),
+            &uploaded(&["system.psf", "system.pdb"]),
+        );
+
+        assert!(!result.is_valid);
+        assert_eq!(result.issues.len(), 1);
+        assert!(result.issues[0].contains("input_files/par_all36.prm"));
+    }
+
+    #[test]
+    fn test_submission_artifacts_output_outside_outputs_dir() {
+        let config = TEST_NAMD_CONFIG.replace("outputs/production", "production");
+        let result = validate_submission_artifacts(
+            &config,
+            &test_script("config.namd"),
+            &uploaded(&["system.psf", "system.pdb", "par_all36.prm"]),
+        );
+
+        // Still submittable, but the user should know outputs won't be collected
+        assert!(result.is_valid);
+        assert!(result.warnings.iter().any(|w| w.contains("outputName 'production'")));
+    }
+
+    #[test]
+    fn test_generated_script_matches_uploaded_config_name() {
+        let config = crate::types::SlurmConfig::default();
+        let script = crate::slurm::script_generator::SlurmScriptGenerator::preview_script("test".to_string(), config).unwrap();
+
+        assert_eq!(namd_config_argument(&script), Some(crate::ssh::JobDirectoryStructure::NAMD_CONFIG));
+    }
+
     #[test]
     fn test_validation_result_valid() {
         let result = ValidationResult::valid();
@@ -416,4 +813,20 @@ mod tests {
         assert!(error_opt.is_some());
         assert!(error_opt.unwrap().to_string().contains("Error 1"));
     }
+
+    #[test]
+    fn test_job_notes() {
+        assert_eq!(validate_job_notes(None).unwrap(), None);
+        assert_eq!(validate_job_notes(Some("  \n".to_string())).unwrap(), None);
+        assert_eq!(
+            validate_job_notes(Some("used the tighter restraints".to_string())).unwrap().as_deref(),
+            Some("used the tighter restraints")
+        );
+
+        // The cap counts characters, not bytes
+        assert!(validate_job_notes(Some("å".repeat(MAX_JOB_NOTES_LENGTH))).is_ok());
+        let error = validate_job_notes(Some("a".repeat(MAX_JOB_NOTES_LENGTH + 1))).unwrap_err();
+        assert!(error.to_string().contains("too long"));
+        assert!(validate_job_notes(Some("a\0b".to_string())).is_err());
+    }
 }
@@ -3,3 +3,9 @@ pub mod job;
 
 /// Template validation business logic
 pub mod template;
+
+/// Submission readiness checklist
+pub mod readiness;
+
+/// NAMD physics checks on rendered configs
+pub mod namd;
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use super::job::ValidationResult;
+
+/// How a failed rule is reported: errors block submission, warnings don't
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One physics check on a rendered NAMD config
+/// `check` returns the problem description, or None when the config passes
+pub struct NamdRule {
+    pub name: &'static str,
+    pub severity: Severity,
+    pub check: fn(&NamdConfig) -> Option<String>,
+}
+
+/// Rules run by `validate_namd_config`; add new checks here
+pub const NAMD_RULES: &[NamdRule] = &[
+    NamdRule { name: "timestep_rigid_bonds", severity: Severity::Error, check: long_timestep_without_rigid_bonds },
+    NamdRule { name: "timestep_two_fs_rigid_bonds", severity: Severity::Warning, check: two_fs_timestep_without_rigid_bonds },
+    NamdRule { name: "pme_cell", severity: Severity::Error, check: pme_without_cell },
+    NamdRule { name: "execution_steps", severity: Severity::Error, check: missing_or_empty_execution },
+    NamdRule { name: "execution_steps_unchecked", severity: Severity::Warning, check: unparsed_execution_steps },
+    NamdRule { name: "minimization_steps", severity: Severity::Warning, check: minimization_longer_than_run },
+];
+
+/// Keyword lines of a rendered NAMD config, with Tcl `set` variables substituted
+/// Keywords are lowercased (NAMD keywords are case-insensitive); values keep their case
+pub struct NamdConfig {
+    lines: Vec<(String, Vec<String>)>,
+}
+
+impl NamdConfig {
+    pub fn parse(config: &str) -> Self {
+        let mut variables: HashMap<String, String> = HashMap::new();
+        let mut lines = Vec::new();
+
+        for line in config.lines().map(strip_comment) {
+            let mut tokens = line.split_whitespace();
+            let Some(keyword) = tokens.next() else { continue };
+            let args: Vec<String> = tokens
+                .map(|token| match token.strip_prefix('$') {
+                    Some(name) => variables.get(name).cloned().unwrap_or_else(|| token.to_string()),
+                    None => token.to_string(),
+                })
+                .collect();
+
+            if keyword == "set" {
+                if let [name, value, ..] = args.as_slice() {
+                    variables.insert(name.clone(), value.clone());
+                }
+                continue;
+            }
+            lines.push((keyword.to_ascii_lowercase(), args));
+        }
+
+        NamdConfig { lines }
+    }
+
+    /// First argument of the last line setting `keyword` (later settings override earlier ones)
+    pub fn value(&self, keyword: &str) -> Option<&str> {
+        self.lines.iter().rev()
+            .find(|(key, _)| key.eq_ignore_ascii_case(keyword))
+            .and_then(|(_, args)| args.first())
+            .map(String::as_str)
+    }
+
+    pub fn number(&self, keyword: &str) -> Option<f64> {
+        self.value(keyword)?.parse().ok()
+    }
+
+    /// Whether a yes/no keyword is switched on (NAMD accepts on/yes/true)
+    pub fn is_on(&self, keyword: &str) -> bool {
+        self.value(keyword).is_some_and(|value| matches!(value.to_ascii_lowercase().as_str(), "on" | "yes" | "true"))
+    }
+
+    /// Step counts of every occurrence of a command such as `run` or `minimize`, in order
+    pub fn steps(&self, command: &str) -> Vec<Option<f64>> {
+        self.step_args(command).into_iter()
+            .map(|steps| steps.and_then(|steps| steps.parse().ok()))
+            .collect()
+    }
+
+    /// Raw arguments of every occurrence of a command, joined, before parsing (None when missing)
+    pub fn step_args(&self, command: &str) -> Vec<Option<String>> {
+        self.lines.iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case(command))
+            .map(|(_, args)| (!args.is_empty()).then(|| args.join(" ")))
+            .collect()
+    }
+}
+
+/// Drop a NAMD comment, including the `;#` form used after a value
+fn strip_comment(line: &str) -> &str {
+    line.split('#').next().unwrap_or("").trim_end().trim_end_matches(';')
+}
+
+fn rigid_bonds_all(config: &NamdConfig) -> bool {
+    config.value("rigidBonds").is_some_and(|value| value.eq_ignore_ascii_case("all"))
+}
+
+fn long_timestep_without_rigid_bonds(config: &NamdConfig) -> Option<String> {
+    let timestep = config.number("timestep")?;
+    (timestep > 2.0 && !rigid_bonds_all(config)).then(|| format!(
+        "timestep {} fs needs 'rigidBonds all' - bonds to hydrogen are unstable above 2 fs without it",
+        timestep
+    ))
+}
+
+fn two_fs_timestep_without_rigid_bonds(config: &NamdConfig) -> Option<String> {
+    let timestep = config.number("timestep")?;
+    (timestep > 1.0 && timestep <= 2.0 && config.value("rigidBonds").is_none_or(|value| value.eq_ignore_ascii_case("none")))
+        .then(|| format!("timestep {} fs without rigidBonds is usually only stable at 1 fs", timestep))
+}
+
+fn pme_without_cell(config: &NamdConfig) -> Option<String> {
+    if !config.is_on("PME") || config.value("extendedSystem").is_some() {
+        return None;
+    }
+    let missing: Vec<&str> = ["cellBasisVector1", "cellBasisVector2", "cellBasisVector3"].into_iter()
+        .filter(|keyword| config.value(keyword).is_none())
+        .collect();
+    (!missing.is_empty()).then(|| format!(
+        "PME needs a periodic cell, but {} not set (and no extendedSystem file is given)",
+        if missing.len() == 1 { format!("{} is", missing[0]) } else { format!("{} are", missing.join(", ")) }
+    ))
+}
+
+/// Every run/minimize command and the numsteps keyword, with its raw step argument
+fn execution_commands(config: &NamdConfig) -> Vec<(&'static str, Option<String>)> {
+    ["minimize", "run", "numsteps"].into_iter()
+        .flat_map(|command| config.step_args(command).into_iter().map(move |steps| (command, steps)))
+        .collect()
+}
+
+/// Step arguments that are Tcl (`[expr $n*1000]`, an unset `$steps`) are left to
+/// `unparsed_execution_steps`; only numbers that NAMD would reject fail here
+fn missing_or_empty_execution(config: &NamdConfig) -> Option<String> {
+    let commands = execution_commands(config);
+    if commands.is_empty() {
+        return Some("NAMD config has no 'run', 'minimize' or 'numsteps' - the job would do nothing".to_string());
+    }
+    commands.into_iter()
+        .find(|(_, steps)| match steps.as_deref().map(str::parse::<f64>) {
+            None => true,
+            Some(Ok(steps)) => !(steps >= 1.0 && steps.fract() == 0.0),
+            Some(Err(_)) => false,
+        })
+        .map(|(command, _)| format!("'{}' needs a positive whole number of steps", command))
+}
+
+fn unparsed_execution_steps(config: &NamdConfig) -> Option<String> {
+    execution_commands(config).into_iter()
+        .find_map(|(command, steps)| steps.filter(|steps| steps.parse::<f64>().is_err()).map(|steps| (command, steps)))
+        .map(|(command, steps)| format!(
+            "'{} {}' is computed by Tcl, so its step count can't be checked before NAMD runs it",
+            command, steps
+        ))
+}
+
+fn minimization_longer_than_run(config: &NamdConfig) -> Option<String> {
+    let total = |command| config.steps(command).into_iter().flatten().sum::<f64>();
+    let (minimize, run) = (total("minimize"), total("run"));
+    (minimize > 0.0 && run > 0.0 && minimize > run).then(|| format!(
+        "{} minimization steps is more than the {} dynamics steps that follow - check the step counts aren't swapped",
+        minimize, run
+    ))
+}
+
+/// Run every NAMD_RULES check on a rendered config
+/// Failed Error rules become issues (the result is invalid), Warning rules become warnings
+pub fn validate_namd_config(config: &str) -> ValidationResult {
+    let parsed = NamdConfig::parse(config);
+    let mut issues = Vec::new();
+    let mut warnings = Vec::new();
+    let mut field_errors = HashMap::new();
+
+    for rule in NAMD_RULES {
+        let Some(problem) = (rule.check)(&parsed) else { continue };
+        match rule.severity {
+            Severity::Error => {
+                field_errors.insert("namd_config".to_string(), problem.clone());
+                issues.push(problem);
+            }
+            Severity::Warning => warnings.push(problem),
+        }
+    }
+
+    ValidationResult {
+        is_valid: issues.is_empty(),
+        issues,
+        warnings,
+        suggestions: vec![],
+        field_errors: if field_errors.is_empty() { None } else { Some(field_errors) },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "\
+set temperature 300
+temperature        $temperature
+timestep           2  ;# fs/step
+rigidBonds         all
+cellBasisVector1   124 0 0
+cellBasisVector2   0 114 0
+cellBasisVector3   0 0 323
+PME                yes
+minimize           1000
+run                50000
+";
+
+    #[test]
+    fn test_valid_config_passes() {
+        let result = validate_namd_config(VALID);
+        assert!(result.is_valid, "{:?}", result.issues);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn test_parse_substitutes_set_variables_and_strips_comments() {
+        let config = NamdConfig::parse(VALID);
+        assert_eq!(config.value("temperature"), Some("300"));
+        assert_eq!(config.number("TIMESTEP"), Some(2.0));
+        assert!(config.is_on("pme"));
+        assert_eq!(config.steps("run"), vec![Some(50000.0)]);
+    }
+
+    #[test]
+    fn test_timestep_rigid_bonds() {
+        let result = validate_namd_config("timestep 4\nrigidBonds water\nrun 100\n");
+        assert!(!result.is_valid);
+        assert!(result.issues[0].contains("timestep 4 fs needs 'rigidBonds all'"));
+
+        let result = validate_namd_config("timestep 2\nrun 100\n");
+        assert!(result.is_valid);
+        assert_eq!(result.warnings.len(), 1);
+
+        assert!(validate_namd_config("timestep 1\nrun 100\n").warnings.is_empty());
+    }
+
+    #[test]
+    fn test_pme_needs_cell() {
+        let result = validate_namd_config("PME yes\ncellBasisVector1 10 0 0\nrun 100\n");
+        assert!(!result.is_valid);
+        assert!(result.issues[0].contains("cellBasisVector2, cellBasisVector3 are not set"));
+
+        // A restart's .xsc supplies the cell instead
+        assert!(validate_namd_config("PME on\nextendedSystem restart.xsc\nrun 100\n").is_valid);
+        assert!(validate_namd_config("PME no\nrun 100\n").is_valid);
+    }
+
+    #[test]
+    fn test_execution_steps() {
+        assert!(validate_namd_config("timestep 1\n").issues[0].contains("no 'run', 'minimize' or 'numsteps'"));
+        assert!(validate_namd_config("run 0\n").issues[0].contains("'run' needs a positive whole number"));
+        assert!(validate_namd_config("run\n").issues[0].contains("'run' needs a positive whole number"));
+        assert!(validate_namd_config("numsteps 2.5\n").issues[0].contains("'numsteps' needs a positive whole number"));
+        assert!(validate_namd_config("numsteps 500000\n").is_valid);
+
+        // Tcl step counts can't be evaluated here - a warning, not an error
+        for config in ["minimize $steps\n", "set n 50\nrun [expr $n*1000]\n"] {
+            let result = validate_namd_config(config);
+            assert!(result.is_valid, "{}", config);
+            assert!(result.warnings[0].contains("can't be checked"), "{}", config);
+        }
+        assert!(validate_namd_config("set n 50\nrun [expr $n*1000]\n").warnings[0].starts_with("'run [expr $n*1000]'"));
+        assert!(validate_namd_config("set steps 1000\nrun $steps\n").warnings.is_empty());
+
+        let result = validate_namd_config("minimize 50000\nrun 1000\n");
+        assert!(result.is_valid);
+        assert!(result.warnings[0].contains("50000 minimization steps"));
+    }
+
+    #[test]
+    fn test_bundled_templates_pass_with_defaults() {
+        for json in [
+            include_str!("../../templates/explicit_solvent_npt_v1.json"),
+            include_str!("../../templates/vacuum_optimization_v1.json"),
+        ] {
+            let template: crate::templates::Template = serde_json::from_str(json).unwrap();
+            let values = template.variables.iter()
+                .map(|(key, var_def)| (key.clone(), var_def.var_type.default_value().unwrap_or_else(|| "input.dat".into())))
+                .collect();
+            let config = crate::templates::render_template(&template, &values).unwrap();
+
+            let result = validate_namd_config(&config);
+            assert!(result.is_valid, "{}: {:?}", template.id, result.issues);
+            assert!(result.warnings.is_empty(), "{}: {:?}", template.id, result.warnings);
+        }
+    }
+}
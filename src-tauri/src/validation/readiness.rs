@@ -0,0 +1,303 @@
+use crate::types::JobInfo;
+use crate::types::response_data::{ReadinessItem, SubmissionReadiness};
+use crate::ssh::{JobDirectoryStructure, SftpFileEntry};
+use crate::validation::job::{validate_resource_allocation, validate_submission_artifacts};
+
+/// Detail used for remote checks that can't run while disconnected
+const NOT_CHECKED: &str = "Not checked - connect to the cluster first";
+
+/// What the server reported about a job's project directory
+/// Listings are None when the directory couldn't be read; files are None when absent
+#[derive(Debug, Default)]
+pub struct RemoteJobState {
+    pub connected: bool,
+    pub root_entries: Option<Vec<SftpFileEntry>>,
+    pub input_entries: Option<Vec<SftpFileEntry>>,
+    pub namd_config: Option<String>,
+    pub slurm_script: Option<String>,
+}
+
+fn item(id: &str, label: &str, passed: bool, detail: impl Into<String>) -> ReadinessItem {
+    ReadinessItem {
+        id: id.to_string(),
+        label: label.to_string(),
+        passed,
+        detail: detail.into(),
+    }
+}
+
+fn check_connection(remote: &RemoteJobState) -> ReadinessItem {
+    if remote.connected {
+        item("connection", "Connected to cluster", true, "SSH session is active")
+    } else {
+        item("connection", "Connected to cluster", false, "Not connected - connect before submitting")
+    }
+}
+
+fn check_job_state(job: &JobInfo) -> ReadinessItem {
+    match crate::automations::job_submission::validate_job_submission_state(job) {
+        Ok(()) => item("job_state", "Job can be submitted", true, format!("Status is {:?}", job.status)),
+        Err(e) => item("job_state", "Job can be submitted", false, e.to_string()),
+    }
+}
+
+fn check_directories(job: &JobInfo, remote: &RemoteJobState) -> ReadinessItem {
+    const LABEL: &str = "Job directories exist";
+
+    let Some(project_dir) = &job.project_dir else {
+        return item("directories", LABEL, false, "Job has no project directory");
+    };
+    if !remote.connected {
+        return item("directories", LABEL, false, NOT_CHECKED);
+    }
+    let Some(entries) = &remote.root_entries else {
+        return item("directories", LABEL, false, format!("Project directory {} is missing or unreadable", project_dir));
+    };
+
+    let missing: Vec<&str> = JobDirectoryStructure::subdirectories().into_iter()
+        .filter(|subdir| !entries.iter().any(|e| e.is_directory && e.name == *subdir))
+        .collect();
+
+    if missing.is_empty() {
+        item("directories", LABEL, true, project_dir.clone())
+    } else {
+        item("directories", LABEL, false, format!("Missing in {}: {}", project_dir, missing.join(", ")))
+    }
+}
+
+fn check_input_files(job: &JobInfo, remote: &RemoteJobState) -> ReadinessItem {
+    const LABEL: &str = "Input files uploaded";
+
+    if !remote.connected {
+        return item("input_files", LABEL, false, NOT_CHECKED);
+    }
+    if job.input_files.is_empty() {
+        return item("input_files", LABEL, true, "Job has no input files");
+    }
+    let Some(entries) = &remote.input_entries else {
+        return item("input_files", LABEL, false, format!("{}/ is missing or unreadable", JobDirectoryStructure::INPUT_FILES));
+    };
+
+    let mut problems = Vec::new();
+    for name in &job.input_files {
+        match entries.iter().find(|e| !e.is_directory && &e.name == name) {
+            Some(entry) if entry.size == 0 => problems.push(format!("{} is empty", name)),
+            Some(_) => {}
+            None => problems.push(format!("{} not found", name)),
+        }
+    }
+
+    if problems.is_empty() {
+        item("input_files", LABEL, true, format!("{} files present", job.input_files.len()))
+    } else {
+        item("input_files", LABEL, false, problems.join("; "))
+    }
+}
+
+fn check_config(remote: &RemoteJobState) -> ReadinessItem {
+    const LABEL: &str = "Config and script uploaded and consistent";
+
+    if !remote.connected {
+        return item("config", LABEL, false, NOT_CHECKED);
+    }
+    let (Some(namd_config), Some(slurm_script)) = (&remote.namd_config, &remote.slurm_script) else {
+        let missing: Vec<&str> = [
+            (remote.namd_config.is_none(), JobDirectoryStructure::NAMD_CONFIG),
+            (remote.slurm_script.is_none(), JobDirectoryStructure::SLURM_SCRIPT),
+        ].into_iter().filter(|(absent, _)| *absent).map(|(_, name)| name).collect();
+        return item("config", LABEL, false, format!("Not uploaded: {}", missing.join(", ")));
+    };
+
+    // Check what is actually on the server against what was actually uploaded
+    let uploaded_inputs: Vec<String> = remote.input_entries.iter().flatten()
+        .filter(|e| !e.is_directory)
+        .map(|e| e.name.clone())
+        .collect();
+    let validation = validate_submission_artifacts(namd_config, slurm_script, &uploaded_inputs);
+
+    if validation.is_valid {
+        item("config", LABEL, true, format!("{} and {} validated", JobDirectoryStructure::NAMD_CONFIG, JobDirectoryStructure::SLURM_SCRIPT))
+    } else {
+        item("config", LABEL, false, validation.issues.join("; "))
+    }
+}
+
+fn check_resources(job: &JobInfo) -> ReadinessItem {
+    let config = &job.slurm_config;
+    let validation = validate_resource_allocation(config, &config.partition, &config.qos);
+
+    if validation.is_valid {
+        item("resources", "Resources within cluster limits", true,
+            format!("{} cores, {}, {} on {}/{}", config.cores, config.memory, config.walltime, config.partition, config.qos))
+    } else {
+        item("resources", "Resources within cluster limits", false, validation.issues.join("; "))
+    }
+}
+
+/// Build the submission checklist from the job and what the server reported
+pub fn build_submission_readiness(job: &JobInfo, remote: &RemoteJobState) -> SubmissionReadiness {
+    let items = vec![
+        check_connection(remote),
+        check_job_state(job),
+        check_directories(job, remote),
+        check_input_files(job, remote),
+        check_config(remote),
+        check_resources(job),
+    ];
+
+    SubmissionReadiness {
+        job_id: job.job_id.clone(),
+        ready: items.iter().all(|i| i.passed),
+        items,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{JobStatus, SlurmConfig};
+    use std::collections::HashMap;
+
+    const CONFIG: &str = "structure input_files/protein.psf\ncoordinates input_files/protein.pdb\noutputName outputs/run\n";
+    const SCRIPT: &str = "#!/bin/bash\n#SBATCH --job-name=test\nmpirun -np $SLURM_NTASKS namd3 config.namd > namd_output.log\n";
+
+    fn job() -> JobInfo {
+        let mut job = crate::automations::job_creation::create_job_info(
+            "job_1".to_string(),
+            "job".to_string(),
+            "template".to_string(),
+            HashMap::new(),
+            SlurmConfig {
+                cores: 24,
+                memory: "48GB".to_string(),
+                walltime: "04:00:00".to_string(),
+                partition: "amilan".to_string(),
+                qos: "normal".to_string(),
+                gpus: None,
+                gpu_type: None,
+            },
+            vec!["protein.psf".to_string(), "protein.pdb".to_string()],
+        );
+        job.project_dir = Some("/projects/testuser/namdrunner_jobs/job_1".to_string());
+        job
+    }
+
+    fn entry(name: &str, size: u64, is_directory: bool) -> SftpFileEntry {
+        SftpFileEntry {
+            name: name.to_string(),
+            path: name.to_string(),
+            size,
+            is_directory,
+            permissions: 0o644,
+            modified_time: None,
+        }
+    }
+
+    fn ready_remote() -> RemoteJobState {
+        RemoteJobState {
+            connected: true,
+            root_entries: Some(vec![
+                entry("input_files", 0, true),
+                entry("outputs", 0, true),
+                entry("config.namd", 100, false),
+                entry("job.sbatch", 100, false),
+            ]),
+            input_entries: Some(vec![entry("protein.psf", 2048, false), entry("protein.pdb", 4096, false)]),
+            namd_config: Some(CONFIG.to_string()),
+            slurm_script: Some(SCRIPT.to_string()),
+        }
+    }
+
+    fn failed_ids(readiness: &SubmissionReadiness) -> Vec<&str> {
+        readiness.items.iter().filter(|i| !i.passed).map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_all_prerequisites_met() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let readiness = build_submission_readiness(&job(), &ready_remote());
+
+        assert!(readiness.ready, "unexpected failures: {:?}", readiness.items);
+        assert_eq!(readiness.items.len(), 6);
+    }
+
+    #[test]
+    fn test_disconnected_fails_connection_and_remote_checks() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let readiness = build_submission_readiness(&job(), &RemoteJobState::default());
+
+        assert!(!readiness.ready);
+        assert_eq!(failed_ids(&readiness), vec!["connection", "directories", "input_files", "config"]);
+        assert!(readiness.items.iter().filter(|i| i.id != "connection" && !i.passed).all(|i| i.detail == NOT_CHECKED));
+    }
+
+    #[test]
+    fn test_job_state_failure() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let mut running = job();
+        running.status = JobStatus::Running;
+        let readiness = build_submission_readiness(&running, &ready_remote());
+
+        assert_eq!(failed_ids(&readiness), vec!["job_state"]);
+    }
+
+    #[test]
+    fn test_missing_directories() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let mut remote = ready_remote();
+        remote.root_entries.as_mut().unwrap().retain(|e| e.name != "outputs");
+        let readiness = build_submission_readiness(&job(), &remote);
+        assert_eq!(failed_ids(&readiness), vec!["directories"]);
+        assert!(readiness.items[2].detail.contains("outputs"));
+
+        let mut no_project = job();
+        no_project.project_dir = None;
+        assert_eq!(failed_ids(&build_submission_readiness(&no_project, &ready_remote())), vec!["directories"]);
+    }
+
+    #[test]
+    fn test_missing_or_empty_input_files() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let mut remote = ready_remote();
+        remote.input_entries = Some(vec![entry("protein.psf", 0, false)]);
+        let readiness = build_submission_readiness(&job(), &remote);
+
+        // The config check also notices protein.pdb is gone
+        assert_eq!(failed_ids(&readiness), vec!["input_files", "config"]);
+        let detail = &readiness.items[3].detail;
+        assert!(detail.contains("protein.psf is empty"));
+        assert!(detail.contains("protein.pdb not found"));
+    }
+
+    #[test]
+    fn test_config_not_uploaded_or_inconsistent() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let mut missing = ready_remote();
+        missing.namd_config = None;
+        let readiness = build_submission_readiness(&job(), &missing);
+        assert_eq!(failed_ids(&readiness), vec!["config"]);
+        assert!(readiness.items[4].detail.contains("config.namd"));
+
+        let mut inconsistent = ready_remote();
+        inconsistent.slurm_script = Some(SCRIPT.replace("config.namd", "other.namd"));
+        assert_eq!(failed_ids(&build_submission_readiness(&job(), &inconsistent)), vec!["config"]);
+    }
+
+    #[test]
+    fn test_invalid_resources() {
+        crate::cluster::set_cluster_config_cache(crate::cluster::load_default_config_for_tests());
+
+        let mut over_limit = job();
+        over_limit.slurm_config.walltime = "48:00:00".to_string();
+        let readiness = build_submission_readiness(&over_limit, &ready_remote());
+
+        assert_eq!(failed_ids(&readiness), vec!["resources"]);
+        assert!(readiness.items[5].detail.contains("exceeds QOS 'normal'"));
+    }
+}
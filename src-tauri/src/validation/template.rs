@@ -1,4 +1,6 @@
 use crate::templates::{Template, VariableType};
+use crate::types::JobInfo;
+use crate::types::response_data::JobCompatibility;
 use serde_json::Value;
 use std::collections::HashMap;
 use super::job::ValidationResult;
@@ -9,9 +11,10 @@ pub fn validate_values(template: &Template, values: &HashMap<String, Value>) ->
     let mut issues = Vec::new();
     let mut field_errors = std::collections::HashMap::new();
 
-    // Check all template variables have values (all variables are required)
+    // Check all template variables have values (every visible variable is required;
+    // hidden ones are still type-checked below when a value is given)
     for (key, var_def) in &template.variables {
-        if !values.contains_key(key) {
+        if !values.contains_key(key) && template.is_variable_visible(key, values) {
             let error = format!("Required variable missing: {}", var_def.label);
             issues.push(error.clone());
             field_errors.insert(key.clone(), format!("{} is required", var_def.label));
@@ -47,6 +50,29 @@ pub fn validate_values(template: &Template, values: &HashMap<String, Value>) ->
                         field_errors.insert(key.clone(), error);
                     }
                 }
+                VariableType::Select { options, .. } => {
+                    match value.as_str() {
+                        Some(choice) if options.iter().any(|option| option == choice) => {}
+                        Some(choice) => {
+                            let error = format!("'{}' is not one of {:?}", choice, options);
+                            issues.push(format!("{}: {}", var_def.label, error));
+                            field_errors.insert(key.clone(), error);
+                        }
+                        None => {
+                            let error = format!("expected one of {:?}, got {:?}", options, value);
+                            issues.push(format!("{}: {}", var_def.label, error));
+                            field_errors.insert(key.clone(), error);
+                        }
+                    }
+                }
+                VariableType::List { .. } => {
+                    let is_text_list = value.as_array().is_some_and(|items| items.iter().all(Value::is_string));
+                    if !is_text_list {
+                        let error = format!("expected a list of text items, got {:?}", value);
+                        issues.push(format!("{}: {}", var_def.label, error));
+                        field_errors.insert(key.clone(), error);
+                    }
+                }
                 VariableType::Boolean { .. } => {
                     if !value.is_boolean() {
                         let error = format!("expected boolean, got {:?}", value);
@@ -89,6 +115,116 @@ pub fn validate_values(template: &Template, values: &HashMap<String, Value>) ->
     }
 }
 
+/// Validate the real filenames about to be uploaded against a template's FileUpload variables
+/// `file_names` maps variable key -> filename (a local path is fine, only the name is checked).
+/// Every visible FileUpload variable needs a file (`values` decides visibility), and each file
+/// must match that variable's extensions
+pub fn validate_file_names(
+    template: &Template,
+    values: &HashMap<String, Value>,
+    file_names: &HashMap<String, String>,
+) -> ValidationResult {
+    let mut issues = Vec::new();
+    let mut field_errors = HashMap::new();
+
+    for (key, var_def) in &template.variables {
+        let VariableType::FileUpload { extensions } = &var_def.var_type else {
+            continue;
+        };
+
+        let error = match file_names.get(key).map(|name| name.trim()) {
+            None | Some("") if !template.is_variable_visible(key, values) => None,
+            None | Some("") => Some("file is required".to_string()),
+            Some(name) => {
+                let filename = std::path::Path::new(name)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(name);
+                let ext_match = extensions.iter().any(|ext| filename.to_lowercase().ends_with(&ext.to_lowercase()));
+                (!ext_match).then(|| format!("file '{}' does not match allowed extensions: {:?}", filename, extensions))
+            }
+        };
+
+        if let Some(error) = error {
+            issues.push(format!("{}: {}", var_def.label, error));
+            field_errors.insert(key.clone(), error);
+        }
+    }
+
+    for key in file_names.keys() {
+        match template.variables.get(key) {
+            Some(var_def) if matches!(var_def.var_type, VariableType::FileUpload { .. }) => {}
+            Some(var_def) => issues.push(format!("{}: not a file upload variable", var_def.label)),
+            None => issues.push(format!("Unknown file variable: {}", key)),
+        }
+    }
+
+    ValidationResult {
+        is_valid: issues.is_empty(),
+        issues,
+        warnings: vec![],
+        suggestions: vec![],
+        field_errors: if field_errors.is_empty() { None } else { Some(field_errors) },
+    }
+}
+
+/// Check whether a job's saved values still validate against the current template
+/// Used before bulk operations (e.g. cloning jobs onto a new template version)
+pub fn check_job_compatibility(template: &Template, job: &JobInfo) -> JobCompatibility {
+    let result = validate_values(template, &job.template_values);
+
+    let mut missing_variables: Vec<String> = template.variables.keys()
+        .filter(|key| !job.template_values.contains_key(*key) && template.is_variable_visible(key, &job.template_values))
+        .cloned()
+        .collect();
+    missing_variables.sort();
+
+    let mut removed_variables: Vec<String> = job.template_values.keys()
+        .filter(|key| !template.variables.contains_key(*key))
+        .cloned()
+        .collect();
+    removed_variables.sort();
+
+    let mut issues = result.issues;
+    issues.sort();
+
+    JobCompatibility {
+        job_id: job.job_id.clone(),
+        job_name: job.job_name.clone(),
+        compatible: result.is_valid,
+        missing_variables,
+        removed_variables,
+        issues,
+    }
+}
+
+/// Check every variable the config references is defined on the template
+/// Fails naming each undefined token and its line; returns warnings for defined variables
+/// the config never uses (they would still be asked for on every job).
+/// Inheritance must already be resolved, since a child's blocks can use its parent's variables
+pub fn validate_template_completeness(template: &Template) -> anyhow::Result<Vec<String>> {
+    let referenced = crate::templates::renderer::referenced_variables(&template.namd_config_template)?;
+
+    let undefined: Vec<String> = referenced.iter()
+        .filter(|(name, _)| !template.variables.contains_key(name))
+        .map(|(name, line)| format!("{{{{{}}}}} on line {}", name, line))
+        .collect();
+    if !undefined.is_empty() {
+        return Err(anyhow::anyhow!("NAMD config template uses undefined variables: {}", undefined.join(", ")));
+    }
+
+    // A variable only used to show or hide others still has a purpose
+    let mut unused: Vec<&String> = template.variables.keys()
+        .filter(|key| !referenced.iter().any(|(name, _)| name == *key))
+        .filter(|key| !template.variables.values().any(|v| v.visible_when.as_ref().is_some_and(|c| &c.variable == *key)))
+        .collect();
+    unused.sort();
+
+    Ok(unused.into_iter()
+        .map(|key| format!("Variable '{}' is defined but never used in the NAMD config template", key))
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,6 +244,10 @@ mod tests {
                     default: 300.0,
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
 
@@ -120,6 +260,10 @@ mod tests {
                     extensions: vec![".psf".to_string(), ".pdb".to_string()],
                 },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
 
@@ -130,6 +274,10 @@ mod tests {
                 label: "PME Enabled".to_string(),
                 var_type: VariableType::Boolean { default: true },
                 help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
             },
         );
 
@@ -139,6 +287,7 @@ mod tests {
             description: "Test".to_string(),
             namd_config_template: "test".to_string(),
             variables,
+            extends: None,
             created_at: "2025-01-01".to_string(),
             updated_at: "2025-01-01".to_string(),
         }
@@ -171,6 +320,113 @@ mod tests {
         assert!(result.issues.iter().any(|e| e.contains("PME Enabled")));
     }
 
+    #[test]
+    fn test_select_value_must_be_an_option() {
+        let mut template = create_test_template();
+        template.variables.insert(
+            "integrator".to_string(),
+            VariableDefinition {
+                key: "integrator".to_string(),
+                label: "Integrator".to_string(),
+                var_type: VariableType::Select {
+                    options: vec!["velocity-verlet".to_string(), "langevin".to_string()],
+                    default: "langevin".to_string(),
+                },
+                help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
+            },
+        );
+        let mut values = HashMap::new();
+        values.insert("temperature".to_string(), Value::from(300.0));
+        values.insert("structure_file".to_string(), Value::from("structure.psf"));
+        values.insert("pme_enabled".to_string(), Value::from(true));
+
+        values.insert("integrator".to_string(), Value::from("velocity-verlet"));
+        assert!(validate_values(&template, &values).is_valid);
+
+        values.insert("integrator".to_string(), Value::from("Langevin"));
+        let result = validate_values(&template, &values);
+        assert!(!result.is_valid);
+        assert!(result.issues[0].contains("'Langevin' is not one of"));
+
+        values.insert("integrator".to_string(), Value::from(1));
+        assert!(!validate_values(&template, &values).is_valid);
+    }
+
+    #[test]
+    fn test_list_value_must_be_text_items() {
+        let mut template = create_test_template();
+        template.variables.insert(
+            "restraints".to_string(),
+            VariableDefinition {
+                key: "restraints".to_string(),
+                label: "Restraints".to_string(),
+                var_type: VariableType::List { default: vec![] },
+                help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
+            },
+        );
+        let mut values = HashMap::new();
+        values.insert("temperature".to_string(), Value::from(300.0));
+        values.insert("structure_file".to_string(), Value::from("structure.psf"));
+        values.insert("pme_enabled".to_string(), Value::from(true));
+
+        values.insert("restraints".to_string(), serde_json::json!(["dist_a", "dist_b"]));
+        assert!(validate_values(&template, &values).is_valid);
+
+        values.insert("restraints".to_string(), serde_json::json!(["dist_a", 2]));
+        let result = validate_values(&template, &values);
+        assert!(!result.is_valid);
+        assert!(result.issues[0].contains("expected a list of text items"));
+
+        values.insert("restraints".to_string(), Value::from("dist_a"));
+        assert!(!validate_values(&template, &values).is_valid);
+    }
+
+    #[test]
+    fn test_hidden_variable_not_required_but_still_validated() {
+        let mut template = create_test_template();
+        template.variables.insert(
+            "pme_grid_spacing".to_string(),
+            VariableDefinition {
+                key: "pme_grid_spacing".to_string(),
+                label: "PME Grid Spacing".to_string(),
+                var_type: VariableType::Number { min: 0.5, max: 2.0, default: 1.0 },
+                help_text: None,
+                visible_when: Some(crate::templates::Condition {
+                    variable: "pme_enabled".to_string(),
+                    equals: Value::from(true),
+                }),
+                unit: None,
+                render_unit: None,
+                precision: None,
+            },
+        );
+        let mut values = HashMap::new();
+        values.insert("temperature".to_string(), Value::from(300.0));
+        values.insert("structure_file".to_string(), Value::from("structure.psf"));
+
+        // pme_enabled unset: its default (true) makes the grid spacing required
+        let result = validate_values(&template, &values);
+        assert_eq!(result.issues.len(), 2);
+        assert!(result.issues.iter().any(|e| e.contains("PME Grid Spacing")));
+
+        values.insert("pme_enabled".to_string(), Value::from(false));
+        assert!(validate_values(&template, &values).is_valid);
+
+        // Hidden but given an out-of-range value: still rejected
+        values.insert("pme_grid_spacing".to_string(), Value::from(5.0));
+        let result = validate_values(&template, &values);
+        assert!(!result.is_valid);
+        assert!(result.issues[0].contains("above maximum"));
+    }
+
     #[test]
     fn test_number_below_minimum() {
         let template = create_test_template();
@@ -271,4 +527,188 @@ mod tests {
         assert!(!result.is_valid);
         assert_eq!(result.issues.len(), 3, "Should report all validation errors");
     }
+
+    fn file_names(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_file_names_match_extensions() {
+        let template = create_test_template();
+
+        let result = validate_file_names(&template, &HashMap::new(), &file_names(&[("structure_file", "/home/user/sims/lysozyme.PDB")]));
+        assert!(result.is_valid, "unexpected issues: {:?}", result.issues);
+
+        let result = validate_file_names(&template, &HashMap::new(), &file_names(&[("structure_file", "lysozyme.gro")]));
+        assert!(!result.is_valid);
+        assert!(result.issues[0].contains("'lysozyme.gro' does not match allowed extensions"));
+        assert!(result.field_errors.unwrap().contains_key("structure_file"));
+    }
+
+    #[test]
+    fn test_file_names_missing_or_misassigned() {
+        let template = create_test_template();
+
+        let missing = validate_file_names(&template, &HashMap::new(), &HashMap::new());
+        assert_eq!(missing.issues, vec!["Structure File: file is required"]);
+
+        let misassigned = validate_file_names(&template, &HashMap::new(), &file_names(&[
+            ("structure_file", "protein.psf"),
+            ("temperature", "temp.psf"),
+            ("coordinates", "protein.pdb"),
+        ]));
+        assert!(!misassigned.is_valid);
+        assert_eq!(misassigned.issues.len(), 2);
+        assert!(misassigned.issues.iter().any(|i| i == "Temperature (K): not a file upload variable"));
+        assert!(misassigned.issues.iter().any(|i| i == "Unknown file variable: coordinates"));
+    }
+
+    fn job_with_values(values: HashMap<String, Value>) -> JobInfo {
+        JobInfo {
+            job_id: "job_001".to_string(),
+            job_name: "equilibration".to_string(),
+            status: crate::types::JobStatus::Completed,
+            slurm_job_id: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: None,
+            submitted_at: None,
+            completed_at: None,
+            project_dir: None,
+            scratch_dir: None,
+            error_info: None,
+            slurm_stdout: None,
+            slurm_stderr: None,
+            template_id: "test_template".to_string(),
+            template_values: values,
+            slurm_config: crate::types::SlurmConfig::default(),
+            input_files: vec![],
+            output_files: vec![],
+            archived: false,
+            pinned: false,
+            tags: Vec::new(),
+            notes: None,
+            status_detail: None,
+            submitted_script: None,
+            template_snapshot: None,
+            rendered_config: None,
+            depends_on: None,
+            array: None,
+            notify_email: None,
+            notify_events: Vec::new(),
+            actual_cost: None,
+            namd_progress: None,
+            estimated_start: None,
+            queue_priority: None,
+        }
+    }
+
+    fn valid_job_values() -> HashMap<String, Value> {
+        let mut values = HashMap::new();
+        values.insert("temperature".to_string(), Value::from(300.0));
+        values.insert("structure_file".to_string(), Value::from("structure.psf"));
+        values.insert("pme_enabled".to_string(), Value::from(true));
+        values
+    }
+
+    #[test]
+    fn test_job_compatible_with_unchanged_template() {
+        let report = check_job_compatibility(&create_test_template(), &job_with_values(valid_job_values()));
+
+        assert!(report.compatible);
+        assert_eq!(report.job_id, "job_001");
+        assert!(report.missing_variables.is_empty());
+        assert!(report.removed_variables.is_empty());
+        assert!(report.issues.is_empty());
+    }
+
+    #[test]
+    fn test_job_incompatible_when_variable_added() {
+        let mut template = create_test_template();
+        template.variables.insert(
+            "timestep".to_string(),
+            VariableDefinition {
+                key: "timestep".to_string(),
+                label: "Timestep (fs)".to_string(),
+                var_type: VariableType::Number { min: 0.5, max: 4.0, default: 2.0 },
+                help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
+            },
+        );
+
+        let report = check_job_compatibility(&template, &job_with_values(valid_job_values()));
+
+        assert!(!report.compatible);
+        assert_eq!(report.missing_variables, vec!["timestep"]);
+        assert!(report.issues.iter().any(|i| i.contains("Timestep (fs)")));
+    }
+
+    #[test]
+    fn test_job_reports_removed_variable_without_failing() {
+        let mut template = create_test_template();
+        template.variables.remove("pme_enabled");
+
+        let report = check_job_compatibility(&template, &job_with_values(valid_job_values()));
+
+        // The stale value is ignored at render time, so validation still passes
+        assert!(report.compatible);
+        assert_eq!(report.removed_variables, vec!["pme_enabled"]);
+        assert!(report.missing_variables.is_empty());
+    }
+
+    #[test]
+    fn test_job_incompatible_when_variable_retyped() {
+        let mut template = create_test_template();
+        template.variables.insert(
+            "pme_enabled".to_string(),
+            VariableDefinition {
+                key: "pme_enabled".to_string(),
+                label: "PME Grid Spacing".to_string(),
+                var_type: VariableType::Number { min: 0.5, max: 2.0, default: 1.0 },
+                help_text: None,
+                visible_when: None,
+                unit: None,
+                render_unit: None,
+                precision: None,
+            },
+        );
+
+        let report = check_job_compatibility(&template, &job_with_values(valid_job_values()));
+
+        assert!(!report.compatible);
+        assert!(report.missing_variables.is_empty());
+        assert!(report.removed_variables.is_empty());
+        assert_eq!(report.issues.len(), 1);
+        assert!(report.issues[0].contains("expected number"));
+    }
+
+    #[test]
+    fn test_template_completeness() {
+        let mut template = create_test_template();
+        template.namd_config_template = "structure {{structure_file}}\n{{#if pme_enabled}}\nPME yes\n{{/if}}\ntemperature {{temperature}}\n".to_string();
+        assert_eq!(validate_template_completeness(&template).unwrap(), Vec::<String>::new());
+
+        template.namd_config_template = "structure {{structure_file}}\ntemperature {{temperatur}}\n{{#each restraints}}{{this}}{{/each}}\n".to_string();
+        let err = validate_template_completeness(&template).unwrap_err().to_string();
+        assert!(err.contains("{{temperatur}} on line 2, {{restraints}} on line 3"), "{}", err);
+
+        template.namd_config_template = "structure {{structure_file}}\n".to_string();
+        let warnings = validate_template_completeness(&template).unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("'pme_enabled' is defined but never used"));
+        assert!(warnings[1].contains("'temperature'"));
+    }
+
+    #[test]
+    fn test_visibility_controller_counts_as_used() {
+        let mut template = create_test_template();
+        template.namd_config_template = "structure {{structure_file}}\ntemperature {{temperature}}\n".to_string();
+        template.variables.get_mut("temperature").unwrap().visible_when = Some(crate::templates::Condition {
+            variable: "pme_enabled".to_string(),
+            equals: Value::from(true),
+        });
+        assert!(validate_template_completeness(&template).unwrap().is_empty());
+    }
 }
@@ -83,15 +83,13 @@ export const billingRates = derived(
 export async function calculateJobCost(
   cores: number,
   walltime: string,
-  hasGpu: boolean = false,
-  gpuCount: number = 1
+  gpus: number = 0
 ): Promise<number> {
   try {
     return await invoke<number>('calculate_job_cost', {
       cores,
       walltime,
-      has_gpu: hasGpu,
-      gpu_count: gpuCount
+      gpus
     });
   } catch (error) {
     return 0;
@@ -126,7 +124,9 @@ export async function validateResourceRequest(
   memory: string,
   walltime: string,
   partitionId: string,
-  qosId: string
+  qosId: string,
+  gpus?: number,
+  gpuType?: string
 ): Promise<ValidationResult> {
   // Check if config is loaded
   const config = get(clusterCapabilitiesStore);
@@ -146,7 +146,9 @@ export async function validateResourceRequest(
       memory,
       walltime,
       partition_id: partitionId,
-      qos_id: qosId
+      qos_id: qosId,
+      gpus: gpus ?? null,
+      gpu_type: gpuType ?? null
     });
     return result;
   } catch (error) {
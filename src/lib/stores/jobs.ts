@@ -1,6 +1,7 @@
-import { writable, derived } from 'svelte/store';
+import { writable, derived, get } from 'svelte/store';
 import type {
   JobInfo,
+  JobPage,
   JobStatus,
   CreateJobParams,
   SyncJobsResult,
@@ -8,7 +9,7 @@ import type {
 } from '../types/api';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import { sessionActions } from './session';
+import { sessionActions, isConnected } from './session';
 import { isConnectionError } from './storeFactory';
 
 // Progress tracking interface
@@ -20,6 +21,7 @@ interface JobProgress {
 // Jobs store state with sync timing and progress tracking
 interface JobsState {
   jobs: JobInfo[];
+  archivedJobs: JobInfo[];
   lastSyncTime: Date;
   hasEverSynced: boolean;
   isSyncing: boolean;
@@ -30,6 +32,7 @@ interface JobsState {
 // Initialize with empty state - jobs will be loaded when connected
 const initialJobsState: JobsState = {
   jobs: [],
+  archivedJobs: [],
   lastSyncTime: new Date(0), // No sync yet
   hasEverSynced: false,
   isSyncing: false,
@@ -37,20 +40,100 @@ const initialJobsState: JobsState = {
   submissionProgress: { message: '', isActive: false }
 };
 
+// Fallback gap between scheduled syncs when a sync didn't suggest one (matches the backend minimum)
+const DEFAULT_POLL_SECS = 30;
+
 // Create jobs store
 function createJobsStore() {
   const { subscribe, set, update } = writable<JobsState>(initialJobsState);
 
+  // Scheduled polling: after every sync, wait the gap the backend suggested before the next one
+  let polling = false;
+  let pollTimer: ReturnType<typeof setTimeout> | null = null;
+
+  function clearPollTimer() {
+    if (pollTimer !== null) {
+      clearTimeout(pollTimer);
+      pollTimer = null;
+    }
+  }
+
+  function schedulePoll(secs: number) {
+    clearPollTimer();
+    if (!polling) return;
+    pollTimer = setTimeout(() => {
+      pollTimer = null;
+      if (get(isConnected)) {
+        sync(true);
+      } else {
+        polling = false;
+      }
+    }, secs * 1000);
+  }
+
+  // Sync with backend; scheduled marks timer polls (manual refreshes don't lengthen the back-off)
+  async function sync(scheduled = false) {
+    // Set syncing state
+    update(state => ({ ...state, isSyncing: true }));
+    let nextPollSecs = DEFAULT_POLL_SECS;
+
+    try {
+      // Call syncJobs to update job statuses from SLURM, then fetch updated jobs
+
+      const syncResult = await invoke<SyncJobsResult>('sync_jobs', { scheduled });
+      nextPollSecs = syncResult.suggested_next_poll_secs || DEFAULT_POLL_SECS;
+
+      if (syncResult.success) {
+        // Pure caching - backend returns complete job list (discovery happens automatically if DB empty)
+        update(state => ({
+          ...state,
+          jobs: syncResult.jobs || [],
+          lastSyncTime: new Date(),
+          hasEverSynced: true,
+          isSyncing: false
+        }));
+      } else {
+        // Sync failed - check if it's a connection error
+        const errorMsg = syncResult.errors.join(', ');
+        if (isConnectionError(errorMsg)) {
+          sessionActions.markExpired(errorMsg);
+        }
+
+        update(state => ({
+          ...state,
+          lastSyncTime: new Date(),
+          hasEverSynced: true,
+          isSyncing: false
+        }));
+      }
+    } catch (error) {
+      // Check if exception indicates connection failure
+      const errorMsg = error instanceof Error ? error.message : String(error);
+      if (isConnectionError(errorMsg)) {
+        sessionActions.markExpired(errorMsg);
+      }
+
+      update(state => ({
+        ...state,
+        lastSyncTime: new Date(),
+        isSyncing: false
+      }));
+    } finally {
+      // Any sync, manual or scheduled, restarts the countdown from its own suggestion
+      schedulePoll(nextPollSecs);
+    }
+  }
+
   return {
     subscribe,
 
     // Load jobs from database (for offline/startup)
     loadFromDatabase: async () => {
       try {
-        const result = await invoke<ApiResult<JobInfo[]>>('get_all_jobs');
+        const result = await invoke<ApiResult<JobPage>>('get_all_jobs');
 
         if (result.success && result.data) {
-          const jobs = result.data;
+          const jobs = result.data.jobs;
           update(state => ({
             ...state,
             jobs,
@@ -62,54 +145,22 @@ function createJobsStore() {
       }
     },
 
-    // Sync with backend
-    sync: async () => {
-      // Set syncing state
-      update(state => ({ ...state, isSyncing: true }));
+    sync,
 
-      try {
-        // Call syncJobs to update job statuses from SLURM, then fetch updated jobs
-
-        const syncResult = await invoke<SyncJobsResult>('sync_jobs');
-
-        if (syncResult.success) {
-          // Pure caching - backend returns complete job list (discovery happens automatically if DB empty)
-          update(state => ({
-            ...state,
-            jobs: syncResult.jobs || [],
-            lastSyncTime: new Date(),
-            hasEverSynced: true,
-            isSyncing: false
-          }));
-        } else {
-          // Sync failed - check if it's a connection error
-          const errorMsg = syncResult.errors.join(', ');
-          if (isConnectionError(errorMsg)) {
-            sessionActions.markExpired(errorMsg);
-          }
-
-          update(state => ({
-            ...state,
-            lastSyncTime: new Date(),
-            hasEverSynced: true,
-            isSyncing: false
-          }));
-        }
-      } catch (error) {
-        // Check if exception indicates connection failure
-        const errorMsg = error instanceof Error ? error.message : String(error);
-        if (isConnectionError(errorMsg)) {
-          sessionActions.markExpired(errorMsg);
-        }
-
-        update(state => ({
-          ...state,
-          lastSyncTime: new Date(),
-          isSyncing: false
-        }));
+    // Start scheduled syncs after connecting; each sync then sets the gap to the next one
+    startPolling: () => {
+      polling = true;
+      if (pollTimer === null) {
+        schedulePoll(DEFAULT_POLL_SECS);
       }
     },
 
+    // Stop scheduled syncs (disconnect or expired session)
+    stopPolling: () => {
+      polling = false;
+      clearPollTimer();
+    },
+
     // Create a new job via backend with progress tracking
     createJob: async (params: CreateJobParams) => {
       // Set up progress tracking
@@ -172,7 +223,8 @@ function createJobsStore() {
     },
 
     // Submit a job for execution via backend with progress tracking
-    submitJob: async (job_id: string) => {
+    // ignore_space_check submits even when scratch looks too full (after the user confirmed)
+    submitJob: async (job_id: string, ignore_space_check = false) => {
       // Set up progress tracking
       update(state => ({
         ...state,
@@ -189,7 +241,7 @@ function createJobsStore() {
       });
 
       try {
-        const result = await invoke<ApiResult<JobInfo>>('submit_job', { job_id });
+        const result = await invoke<ApiResult<JobInfo>>('submit_job', { job_id, ignore_space_check });
 
         if (result.success && result.data) {
           // Update progress to completion
@@ -232,6 +284,62 @@ function createJobsStore() {
       }
     },
 
+    // Archive a job - hidden from the list, nothing deleted; restore_job brings it back
+    archiveJob: async (job_id: string) => {
+      try {
+        const result = await invoke<ApiResult<JobInfo>>('archive_job', { job_id });
+
+        if (result.success && result.data) {
+          const archived = result.data;
+          update(state => ({
+            ...state,
+            jobs: state.jobs.filter(job => job.job_id !== job_id),
+            archivedJobs: [archived, ...state.archivedJobs.filter(job => job.job_id !== job_id)]
+          }));
+          return { success: true };
+        }
+        return { success: false, error: result.error || 'Job archive failed' };
+      } catch (error) {
+        return { success: false, error: error instanceof Error ? error.message : String(error) };
+      }
+    },
+
+    // Load archived jobs from the database (they are left out of sync results)
+    loadArchived: async () => {
+      try {
+        const result = await invoke<ApiResult<JobPage>>('get_all_jobs', { include_archived: true });
+
+        if (result.success && result.data) {
+          const archivedJobs = result.data.jobs.filter(job => job.archived);
+          update(state => ({ ...state, archivedJobs }));
+          return { success: true };
+        }
+        return { success: false, error: result.error || 'Failed to load archived jobs' };
+      } catch (error) {
+        return { success: false, error: error instanceof Error ? error.message : String(error) };
+      }
+    },
+
+    // Restore an archived job back into the job list
+    restoreJob: async (job_id: string) => {
+      try {
+        const result = await invoke<ApiResult<JobInfo>>('restore_job', { job_id });
+
+        if (result.success && result.data) {
+          const restored = result.data;
+          update(state => ({
+            ...state,
+            jobs: [restored, ...state.jobs.filter(job => job.job_id !== job_id)],
+            archivedJobs: state.archivedJobs.filter(job => job.job_id !== job_id)
+          }));
+          return { success: true };
+        }
+        return { success: false, error: result.error || 'Job restore failed' };
+      } catch (error) {
+        return { success: false, error: error instanceof Error ? error.message : String(error) };
+      }
+    },
+
     // Delete a job via backend
     deleteJob: async (job_id: string) => {
       try {
@@ -293,6 +401,34 @@ function createJobsStore() {
       }
     },
 
+    // Re-query SLURM for one job only (cheaper than a full sync; runs completion if it just finished)
+    syncJob: async (job_id: string) => {
+      try {
+        const result = await invoke<ApiResult<JobInfo>>('sync_single_job', { job_id });
+
+        if (result.success && result.data) {
+          const synced = result.data;
+          update(state => ({
+            ...state,
+            jobs: state.jobs.map(job => job.job_id === job_id ? synced : job)
+          }));
+          return result;
+        } else {
+          const errorMsg = result.error || 'Job refresh failed';
+          if (isConnectionError(errorMsg)) {
+            sessionActions.markExpired(errorMsg);
+          }
+          return { success: false, error: errorMsg };
+        }
+      } catch (error) {
+        const errorMsg = error instanceof Error ? error.message : String(error);
+        if (isConnectionError(errorMsg)) {
+          sessionActions.markExpired(errorMsg);
+        }
+        return { success: false, error: errorMsg };
+      }
+    },
+
     // Reset to initial state
     reset: () => set(initialJobsState),
 
@@ -301,6 +437,7 @@ function createJobsStore() {
       update((state) => ({
         ...state,
         jobs: [],
+        archivedJobs: [],
         lastSyncTime: new Date(0),
         hasEverSynced: false,
       })),
@@ -319,6 +456,7 @@ export const jobsStore = createJobsStore();
 
 // Derived stores for convenience
 export const jobs = derived(jobsStore, $store => $store.jobs);
+export const archivedJobs = derived(jobsStore, $store => $store.archivedJobs);
 export const lastSyncTime = derived(jobsStore, $store => $store.lastSyncTime);
 export const hasEverSynced = derived(jobsStore, $store => $store.hasEverSynced);
 export const isSyncing = derived(jobsStore, $store => $store.isSyncing);
@@ -328,6 +466,7 @@ export const jobsByStatus = derived(jobs, $jobs => {
   const grouped = {
     CREATED: [] as JobInfo[],
     PENDING: [] as JobInfo[],
+    HELD: [] as JobInfo[],
     RUNNING: [] as JobInfo[],
     COMPLETED: [] as JobInfo[],
     FAILED: [] as JobInfo[],
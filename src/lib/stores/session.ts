@@ -55,7 +55,8 @@ export const sessionActions = {
           lastError: null,
         }));
 
-        // Sync jobs after successful connection
+        // Sync jobs after successful connection, then keep polling at the pace each sync suggests
+        jobsStore.startPolling();
         await jobsStore.sync();
 
         return true;
@@ -84,6 +85,7 @@ export const sessionActions = {
   // Disconnect from cluster
   async disconnect(): Promise<boolean> {
     try {
+      jobsStore.stopPolling();
       const result = await invoke<ApiResult<void>>('disconnect');
 
       sessionStore.update((state) => ({
@@ -134,6 +136,7 @@ export const sessionActions = {
 
   // Mark connection as expired (called when connection failure detected)
   markExpired(error: string): void {
+    jobsStore.stopPolling();
     sessionStore.update((state) => ({
       ...state,
       connectionState: 'Expired',
@@ -143,6 +146,7 @@ export const sessionActions = {
 
   // Reset session to initial state
   reset(): void {
+    jobsStore.stopPolling();
     sessionStore.set(initialState);
   },
 };
\ No newline at end of file
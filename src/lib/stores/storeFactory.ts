@@ -93,6 +93,14 @@ export function isConnectionError(errorMessage: string): boolean {
   );
 }
 
+/**
+ * Helper: Detect the disk space preflight refusing a create or submit (SSHError::InsufficientSpace)
+ * The user can retry with ignore_space_check
+ */
+export function isInsufficientSpaceError(errorMessage: string): boolean {
+  return errorMessage.startsWith('Insufficient disk space');
+}
+
 /**
  * Handle API result and update store state
  * Returns true if successful, false otherwise
@@ -163,8 +171,8 @@ function handleException<T>(
  * @example With load command
  * ```ts
  * const jobsStore = createStore({
- *   initialData: [] as JobInfo[],
- *   loadCommand: 'get_all_jobs'
+ *   initialData: [] as JobSummary[],
+ *   loadCommand: 'get_all_jobs_summary'
  * });
  * ```
  */
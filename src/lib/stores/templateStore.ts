@@ -40,12 +40,12 @@ export const templateStore = {
     await templatesListStore.load();
   },
 
-  // Load specific template by ID
-  async loadTemplate(templateId: string): Promise<Template | null> {
+  // Load specific template by ID; `resolved` merges in parent templates (for creating jobs, not editing)
+  async loadTemplate(templateId: string, resolved = false): Promise<Template | null> {
     // Set loading on main store
     templatesListStore.update((state) => ({ ...state, loading: true, error: null }));
 
-    const result = await invokeWithErrorHandling<Template>('get_template', {
+    const result = await invokeWithErrorHandling<Template>(resolved ? 'get_resolved_template' : 'get_template', {
       template_id: templateId,
     });
 
@@ -99,6 +99,26 @@ export const templateStore = {
     }
   },
 
+  // Save a copy under a new name; returns the copy so it can be opened for editing
+  async duplicateTemplate(sourceId: string, newName: string): Promise<Template | null> {
+    templatesListStore.update((state) => ({ ...state, loading: true, error: null }));
+
+    const result = await invokeWithErrorHandling<Template>('duplicate_template', {
+      source_id: sourceId,
+      new_name: newName,
+    });
+
+    templatesListStore.update((state) => ({ ...state, loading: false }));
+
+    if (result.success && result.data) {
+      await templatesListStore.load();
+      return result.data;
+    } else {
+      templatesListStore.setError(result.error || 'Failed to duplicate template');
+      return null;
+    }
+  },
+
   // Delete template
   async deleteTemplate(templateId: string): Promise<boolean> {
     templatesListStore.update((state) => ({ ...state, loading: true, error: null }));
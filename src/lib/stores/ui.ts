@@ -5,6 +5,7 @@ export type View = 'jobs' | 'create' | 'templates' | 'template-edit' | 'settings
 interface UIState {
   currentView: View;
   selectedJobId: string | null;
+  showArchivedJobs: boolean;
   selectedTemplateId: string | null;
   templateEditorMode: 'create' | 'edit';
   consoleOpen: boolean;
@@ -20,6 +21,7 @@ interface BreadcrumbItem {
 const initialState: UIState = {
   currentView: 'jobs',
   selectedJobId: null,
+  showArchivedJobs: false,
   selectedTemplateId: null,
   templateEditorMode: 'create',
   consoleOpen: false,
@@ -36,9 +38,17 @@ function createUIStore() {
       update(state => ({
         ...state,
         currentView: view,
-        selectedJobId: null // Clear selection when changing views
+        selectedJobId: null, // Clear selection when changing views
+        showArchivedJobs: false
       }));
     },
+    // Jobs page listing archived jobs instead of active ones
+    showArchived: (show: boolean) => update(state => ({
+      ...state,
+      currentView: 'jobs',
+      selectedJobId: null,
+      showArchivedJobs: show
+    })),
     selectJob: (job_id: string | null) => update(state => ({
       ...state,
       selectedJobId: job_id
@@ -71,6 +81,7 @@ export const uiStore = createUIStore();
 // Derived stores for convenience
 export const currentView = derived(uiStore, $ui => $ui.currentView);
 export const selectedJobId = derived(uiStore, $ui => $ui.selectedJobId);
+export const showArchivedJobs = derived(uiStore, $ui => $ui.showArchivedJobs);
 export const selectedTemplateId = derived(uiStore, $ui => $ui.selectedTemplateId);
 export const templateEditorMode = derived(uiStore, $ui => $ui.templateEditorMode);
 export const consoleOpen = derived(uiStore, $ui => $ui.consoleOpen);
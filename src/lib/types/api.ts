@@ -1,4 +1,4 @@
-import type { TemplateSummary } from './template';
+import type { Template, TemplateSummary } from './template';
 
 // JSON value type matching Rust serde_json::Value
 // Can be any valid JSON value: string, number, boolean, null, object, or array
@@ -17,7 +17,7 @@ export interface ApiResult<T> {
 
 // Core type definitions matching Rust types
 export type ConnectionState = 'Disconnected' | 'Connecting' | 'Connected' | 'Expired';
-export type JobStatus = 'CREATED' | 'PENDING' | 'RUNNING' | 'COMPLETED' | 'FAILED' | 'CANCELLED';
+export type JobStatus = 'CREATED' | 'PENDING' | 'HELD' | 'RUNNING' | 'COMPLETED' | 'FAILED' | 'CANCELLED';
 export type JobId = string;
 export type SlurmJobId = string;
 export type Timestamp = string;
@@ -29,6 +29,13 @@ export interface SessionInfo {
   connected_at: Timestamp;
 }
 
+export interface RecentConnection {  // list_recent_connections
+  host: string;
+  port: number;
+  username: string;
+  last_connected_at: Timestamp;
+}
+
 export interface JobInfo {
   job_id: JobId;
   job_name: string;
@@ -48,14 +55,78 @@ export interface JobInfo {
   slurm_config: SlurmConfig;
   input_files: string[];
   output_files: OutputFile[];
+  archived: boolean;
+  pinned: boolean;
+  tags?: string[];
+  notes?: string;
+  status_detail?: string;
+  submitted_script?: string;
+  template_snapshot?: Template;
+  rendered_config?: string;
+  depends_on?: string[];
+  array?: JobArray;
+  notify_email?: string;
+  notify_events?: MailEvent[];
+  actual_cost?: number;
+  namd_progress?: NamdProgress;
+  estimated_start?: string;
+  queue_priority?: number;
+}
+
+export type MailEvent = 'begin' | 'end' | 'fail' | 'requeue' | 'time_limit_80';
+
+export type NamdPhase = 'minimization' | 'dynamics';
+
+export interface NamdProgress {
+  phase: NamdPhase;
+  current_step: number;
+  total_steps?: number;
+  percent_complete?: number;
+  ns_per_day?: number;
+  eta_seconds?: number;
+}
+
+export interface JobArray {
+  variable: string;
+  values: JsonValue[];
+  task_statuses: ArrayTaskStatus[];
+}
+
+export interface ArrayTaskStatus {
+  task_id: number;
+  status: JobStatus;
+}
+
+export interface JobSummary {
+  job_id: JobId;
+  job_name: string;
+  status: JobStatus;
+  slurm_job_id?: SlurmJobId;
+  created_at: Timestamp;
+  updated_at?: Timestamp;
+  submitted_at?: Timestamp;
+  completed_at?: Timestamp;
+  tags: string[];
+}
+
+export interface JobPage {
+  jobs: JobInfo[];
+  total: number;  // Matching jobs across all pages
 }
 
+export type JobSortField = 'created_at' | 'status' | 'job_name';
+export type SortOrder = 'asc' | 'desc';
+
+export type ExportFormat = 'csv' | 'json';
+
 export interface SlurmConfig {
   cores: number;
   memory: string;
   walltime: string;
   partition?: string;
   qos?: string;
+  gpus?: number;
+  gpu_type?: string;
 }
 
 export interface OutputFile {
@@ -76,10 +147,51 @@ export interface DownloadInfo {
   file_size: number;
 }
 
+export interface FilteredDownload {
+  saved_to: string;
+  patterns: string[];
+  matched: { path: string; size_bytes: number }[];
+  skipped: { path: string; size_bytes: number }[];
+  downloaded_bytes: number;
+}
+
+export interface OutputDownloadProgress {  // output-download-progress / archive-download-progress event payload
+  job_id: string;
+  file: string;
+  completed: number;
+  total: number;
+  bytes_done: number;
+  bytes_total: number;
+}
+
+export interface RemoteText {  // read_remote_text
+  relative_path: string;
+  content: string;
+  shown_bytes: number;
+  file_size: number;
+  truncated: boolean;
+}
+
+export interface RemoteDirSize {  // remote_dir_size, get_job_output_size
+  path: string;
+  total_bytes: number;
+  file_count: number;
+  partial: boolean;
+  warning: string | null;
+}
+
+export type LogLevel = 'off' | 'error' | 'warn' | 'info' | 'debug';  // set_log_level, set_log_filter
+
+export interface LogFilter {  // set_log_filter
+  min_level: LogLevel;
+  category_overrides: Record<string, LogLevel>;
+}
+
 export interface DatabaseInfo {
   path: string;
   size_bytes: number;
   job_count: number;
+  skipped_job_records: number;
 }
 
 export interface DatabaseOperationData {
@@ -92,11 +204,117 @@ export interface ConnectionStatus {
   session_info?: SessionInfo;
 }
 
+export interface ConnectionPing {
+  authenticated: boolean;
+  latency_ms: number | null;  // null when the probe failed
+  error: string | null;
+}
+
+export type HopRole = 'jump_host' | 'destination';
+export type DiagnosticStage = 'resolve' | 'connect' | 'authenticate';
+
+export interface DiagnosticStep {
+  hop: HopRole;
+  host: string;
+  stage: DiagnosticStage;
+  success: boolean;
+  message: string;
+}
+
+export interface ConnectionDiagnosis {
+  steps: DiagnosticStep[];
+  failed_hop: HopRole | null;
+  failed_stage: DiagnosticStage | null;
+  summary: string;
+}
+
 // Command parameters
+export type NetworkPreset = 'LAN' | 'VPN' | 'Slow';
+
+export type AuthMethod =
+  | { type: 'password' }
+  | { type: 'key'; key_path?: string; passphrase?: string }
+  | { type: 'keyboard_interactive' };
+
+export interface ChallengePrompt {
+  text: string;
+  echo: boolean;
+}
+
+export interface AuthChallenge {
+  username: string;
+  instructions: string;
+  prompts: ChallengePrompt[];
+  timeout_secs: number;
+}
+
 export interface ConnectParams {
   host: string;
+  port?: number;
   username: string;
   password: string;
+  auth_method?: AuthMethod;
+  network_preset?: NetworkPreset;
+  jump_host?: JumpHostConfig;
+  auto_reconnect?: boolean;
+  connect_timeout?: number;
+  file_transfer_timeout?: number;
+  remember_password?: boolean;
+}
+
+export interface TimeoutOverrides {  // set_connection_timeouts, get_connection_timeouts
+  connect_timeout: number | null;
+  file_transfer_timeout: number | null;
+}
+
+export interface ClusterProfile {
+  name: string;
+  login_server: string;
+  port?: number;
+  module_setup?: string;
+}
+
+export interface ClusterProfiles {
+  profiles: ClusterProfile[];
+  active_profile?: string;
+}
+
+export interface DiscoveredPartition {
+  name: string;
+  is_default: boolean;
+  max_walltime_hours?: number;
+  cores_per_node: number;
+  memory_per_node_mb: number;
+  gpu_type?: string;
+  gpu_count?: number;
+}
+
+export interface DiscoveredQos {
+  name: string;
+  max_walltime_hours?: number;
+}
+
+export interface CapabilityDiscovery {
+  partitions: DiscoveredPartition[];
+  qos_options: DiscoveredQos[];
+  qos_error?: string;
+  missing_partitions: string[];
+  merged: ClusterCapabilities;
+  applied: boolean;
+}
+
+export type ReconnectStatus = 'started' | 'succeeded' | 'failed';
+
+export interface ReconnectEvent {
+  host: string;
+  status: ReconnectStatus;
+  message?: string;
+}
+
+export interface JumpHostConfig {
+  host: string;
+  port?: number;
+  username?: string;
 }
 
 export interface CreateJobParams {
@@ -104,6 +322,11 @@ export interface CreateJobParams {
   template_id: string;
   template_values: Record<string, JsonValue>;
   slurm_config: SlurmConfig;
+  depends_on?: string[];
+  array?: JobArray;
+  notify_email?: string;
+  notify_events?: MailEvent[];
+  ignore_space_check?: boolean;  // Upload even if the disk space preflight fails
 }
 
 // Complex batch operation results (domain-specific)
@@ -112,6 +335,81 @@ export interface SyncJobsResult {
   jobs: JobInfo[];           // Complete job list after sync
   jobs_updated: number;       // Number of jobs updated during sync
   errors: string[];
+  warnings: string[];         // Problems that didn't stop the sync (e.g. two jobs claiming one SLURM ID)
+  already_in_progress: boolean;  // Another sync was running; this call waited for it and returns its results
+  suggested_next_poll_secs: number;  // Wait before the next sync: 30s near job transitions, backing off to 600s while nothing changes
+}
+
+export interface BatchSubmitResult {
+  submitted: JobInfo[];
+  failed: BatchSubmitFailure[];
+}
+
+export interface DryRunResult {
+  accepted: boolean;               // Scheduler would accept the job as submitted
+  estimated_start: string | null;  // e.g. "2025-01-15T10:30:00"
+  errors: string[];                // sbatch errors without the "sbatch: error:" prefix
+  output: string;                  // Raw sbatch output
+  script: string;                  // The script that was checked
+}
+
+export interface DiskSpaceCheck {
+  path: string;
+  required_bytes: number;
+  total_bytes: number | null;            // null when df can't report the filesystem (check skipped)
+  available_bytes: number | null;
+  quota_remaining_bytes: number | null;  // Lustre quota; null off Lustre or without a limit
+  sufficient: boolean;
+  shortfall_bytes: number;
+  message: string;
+}
+
+export interface JobAccounting {  // get_job_accounting: sacct record folded over the job's steps
+  slurm_job_id: string;
+  state: string;                          // e.g. "COMPLETED", "OUT_OF_MEMORY"
+  exit_code: string;                      // "code:signal"
+  elapsed_seconds: number | null;
+  cpu_time_seconds: number | null;        // Allocated core time
+  total_cpu_seconds: number | null;       // Core time actually used
+  cpu_efficiency: number | null;          // 0-1
+  max_rss_bytes: number | null;           // Peak memory of the largest step
+  max_vmsize_bytes: number | null;
+  requested_memory_bytes: number | null;
+  memory_efficiency: number | null;       // 0-1
+}
+
+export interface DeletionPreview {  // preview_job_deletion: delete_remote=false removes only the DB record
+  job_id: string;
+  job_name: string;
+  cancels_slurm_job: string | null;       // Cancelled by either kind of deletion
+  remote_directories: DirectoryDeletionPreview[];  // Removed only with delete_remote=true
+  remote_file_count: number;
+  remote_bytes: number;
+  remote_error: string | null;            // Remote listing failed (e.g. not connected)
+}
+
+export interface DirectoryDeletionPreview {
+  kind: 'project' | 'scratch';
+  path: string;
+  exists: boolean;
+  file_count: number;
+  total_bytes: number;
+  files: { path: string; size_bytes: number }[];  // Up to 100, largest first, relative to path
+  refused: string | null;                 // Deletion would refuse this path; excluded from totals
+}
+
+export interface BatchSubmitFailure {
+  job_id: string;
+  error: string;
+}
+
+export interface BatchSubmitProgress {  // batch-submission-progress event payload
+  job_id: string;
+  completed: number;  // Jobs attempted so far, including this one
+  total: number;
+  submitted: number;
+  failed: number;
+  error?: string;     // Set when this job failed
 }
 
 // Cluster Capabilities (from backend)
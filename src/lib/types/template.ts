@@ -12,6 +12,7 @@ export interface Template {
   description: string;
   namd_config_template: string;
   variables: Record<string, VariableDefinition>;
+  extends?: string;  // Parent template ID: inherits its config ({{#block}} overrides) and variables
   created_at: string;
   updated_at: string;
 }
@@ -21,20 +22,33 @@ export interface VariableDefinition {
   label: string;
   var_type: VariableType;
   help_text: string | null;
+  visible_when?: Condition;  // Only shown (and required) while another variable equals a value
+  unit?: string;  // Number only: unit the form collects in (e.g. "C")
+  render_unit?: string;  // Number only: unit NAMD expects (e.g. "K"); converted when rendering
+  precision?: number;  // Number only: decimal places in the rendered config
+}
+
+export interface Condition {
+  variable: string;  // Key of the controlling variable
+  equals: unknown;
 }
 
 export type VariableType =
   | { Number: { min: number; max: number; default: number } }
   | { Text: { default: string } }
   | { Boolean: { default: boolean } }
-  | { FileUpload: { extensions: string[] } };
+  | { FileUpload: { extensions: string[] } }
+  | { Select: { options: string[]; default: string } }
+  | { List: { default: string[] } };  // Repeated with {{#each key}}
 
 // Helper function to get the variant name
-export function getVariableTypeName(varType: VariableType): 'Number' | 'Text' | 'Boolean' | 'FileUpload' {
+export function getVariableTypeName(varType: VariableType): 'Number' | 'Text' | 'Boolean' | 'FileUpload' | 'Select' | 'List' {
   if ('Number' in varType) return 'Number';
   if ('Text' in varType) return 'Text';
   if ('Boolean' in varType) return 'Boolean';
   if ('FileUpload' in varType) return 'FileUpload';
+  if ('Select' in varType) return 'Select';
+  if ('List' in varType) return 'List';
   throw new Error('Unknown variable type');
 }
 
@@ -28,6 +28,12 @@ export function getStatusInfo(status: string) {
         class: 'pending',
         icon: '⏳'
       };
+    case 'HELD':
+      return {
+        label: 'Held',
+        class: 'pending',
+        icon: '⏸️'
+      };
     case 'RUNNING':
       return {
         label: 'Running',
@@ -3,18 +3,22 @@
  * Shared logic for template variable extraction, naming, and sample value generation
  */
 
+import type { Template, VariableType } from '$lib/types/template';
+
 /**
  * Extract variables from template text in order of first occurrence
  * Returns array of variable names preserving template text order
+ * Includes variables named by {{#if x}} and {{#each x}}; escaped \{{ and {{else}}/{{this}} are skipped
  */
 export function extractVariablesFromTemplate(templateText: string): string[] {
-  const regex = /\{\{([a-zA-Z_][a-zA-Z0-9_]*)\}\}/g;
+  const regex = /(?<!\\)\{\{(?:#(?:if|each)\s+)?([a-zA-Z_][a-zA-Z0-9_]*)\}\}/g;
+  const keywords = new Set(['else', 'this']);
   const firstOccurrence = new Map<string, number>(); // variable → position
 
   let match;
   while ((match = regex.exec(templateText)) !== null) {
     const varName = match[1];
-    if (varName && !firstOccurrence.has(varName)) {
+    if (varName && !keywords.has(varName) && !firstOccurrence.has(varName)) {
       // Track first occurrence only (handles duplicates)
       firstOccurrence.set(varName, match.index);
     }
@@ -26,6 +30,43 @@ export function extractVariablesFromTemplate(templateText: string): string[] {
     .map(entry => entry[0]);
 }
 
+/**
+ * Whether a variable applies given the current values (mirrors Template::is_variable_visible)
+ * An unset controlling variable is compared by its default; a hidden controller hides its dependents
+ */
+export function isVariableVisible(template: Template, key: string, values: Record<string, unknown>): boolean {
+  let current = key;
+  for (let i = 0; i <= Object.keys(template.variables).length; i++) {
+    const condition = template.variables[current]?.visible_when;
+    if (!condition) return true;
+    const controller = template.variables[condition.variable];
+    if (!controller) return false;
+    const value = condition.variable in values ? values[condition.variable] : getDefaultValue(controller.var_type);
+    if (value === undefined || value !== condition.equals) return false;
+    current = condition.variable;
+  }
+  return false;
+}
+
+/**
+ * Values with hidden variables removed, so they are treated as unset when the job is created
+ */
+export function visibleTemplateValues(template: Template | null, values: Record<string, unknown>): Record<string, unknown> {
+  if (!template) return values;
+  return Object.fromEntries(
+    Object.entries(values).filter(([key]) => isVariableVisible(template, key, values))
+  );
+}
+
+function getDefaultValue(varType: VariableType): unknown {
+  if ('Number' in varType) return varType.Number.default;
+  if ('Text' in varType) return varType.Text.default;
+  if ('Boolean' in varType) return varType.Boolean.default;
+  if ('Select' in varType) return varType.Select.default;
+  if ('List' in varType) return varType.List.default;
+  return undefined;
+}
+
 /**
  * Generate a human-readable label from a variable name
  * Converts snake_case to Title Case